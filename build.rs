@@ -0,0 +1,27 @@
+//! Generates the gRPC service of `proto/random_generator.proto`.
+//!
+//! The messages are written by hand in `src/grpc.rs`, so only the service
+//! glue is generated here and no `protoc` is needed to build.
+
+use tonic_build::manual::{Builder, Method, Service};
+
+fn main() {
+    let method = |name: &str, route: &str, input: &str, output: &str| {
+        Method::builder()
+            .name(name)
+            .route_name(route)
+            .input_type(format!("crate::grpc::proto::{}", input))
+            .output_type(format!("crate::grpc::proto::{}", output))
+            .codec_path("tonic::codec::ProstCodec")
+            .build()
+    };
+    let service = Service::builder()
+        .name("RandomGenerator")
+        .package("random_tool.v1")
+        .method(method("generate", "Generate", "GenerateRequest", "GenerateResponse"))
+        .method(method("get_stats", "GetStats", "GetStatsRequest", "Stats"))
+        .method(method("list_presets", "ListPresets", "ListPresetsRequest", "ListPresetsResponse"))
+        .build();
+    Builder::new().compile(&[service]);
+    println!("cargo:rerun-if-changed=build.rs");
+}
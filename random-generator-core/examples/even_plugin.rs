@@ -0,0 +1,85 @@
+//! A sample plugin adding an "Even" mode and a "Markdown" export format.
+//!
+//! Build it with `cargo build -p random-generator-core --example even_plugin --features plugins`
+//! and copy the library from `target/debug/examples` into the `random-tool/plugins`
+//! folder of the configuration directory.
+
+use std::collections::HashSet;
+use std::ffi::c_char;
+use std::ptr;
+
+use random_generator_core::plugin::{RtExporter, RtMode, RtPlugin, RtRandom, RtRequest, RtSink, PLUGIN_ABI_VERSION, RT_INPUT_BOUNDS};
+
+/// Even numbers in the range
+unsafe extern "C" fn even(request: *const RtRequest, random: *const RtRandom, out: *mut i64) -> i32 {
+    let (request, random) = (&*request, &*random);
+    let first = request.lower + request.lower.rem_euclid(2);
+    if first > request.upper {
+        return 1;
+    }
+    let choices = ((request.upper - first) / 2 + 1) as u64;
+    if !request.allow_duplicates && (request.count as u64) > choices {
+        return 2;
+    }
+
+    let mut seen = HashSet::new();
+    let mut written = 0;
+    while written < request.count {
+        // Modulo bias is negligible for a sample
+        let value = first + ((random.next_u64)(random.context) % choices) as i64 * 2;
+        if request.allow_duplicates || seen.insert(value) {
+            *out.add(written) = value;
+            written += 1;
+        }
+    }
+    0
+}
+
+/// A Markdown table with one row per result
+unsafe extern "C" fn markdown(numbers: *const i64, len: usize, sink: *const RtSink) -> i32 {
+    let sink = &*sink;
+    let mut table = String::from("| # | Value |\n|---|---|\n");
+    for (index, value) in std::slice::from_raw_parts(numbers, len).iter().enumerate() {
+        table.push_str(&format!("| {} | {} |\n", index + 1, value));
+    }
+    (sink.write)(sink.context, table.as_ptr(), table.len());
+    0
+}
+
+struct Descriptor(RtPlugin);
+
+// The description only points at static data
+unsafe impl Sync for Descriptor {}
+
+struct Modes([RtMode; 1]);
+unsafe impl Sync for Modes {}
+
+struct Exporters([RtExporter; 1]);
+unsafe impl Sync for Exporters {}
+
+static MODES: Modes = Modes([RtMode {
+    name: c"Even".as_ptr() as *const c_char,
+    input: RT_INPUT_BOUNDS,
+    generate: even,
+}]);
+
+static EXPORTERS: Exporters = Exporters([RtExporter {
+    name: c"Markdown".as_ptr() as *const c_char,
+    extension: c"md".as_ptr() as *const c_char,
+    export_numbers: markdown,
+}]);
+
+static PLUGIN: Descriptor = Descriptor(RtPlugin {
+    abi_version: PLUGIN_ABI_VERSION,
+    name: c"Even numbers".as_ptr() as *const c_char,
+    modes: ptr::addr_of!(MODES.0) as *const RtMode,
+    mode_count: 1,
+    exporters: ptr::addr_of!(EXPORTERS.0) as *const RtExporter,
+    exporter_count: 1,
+});
+
+/// Entry point looked up by random-tool
+#[no_mangle]
+pub extern "C" fn random_tool_plugin() -> *const RtPlugin {
+    &PLUGIN.0
+}
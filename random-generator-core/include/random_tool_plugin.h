@@ -0,0 +1,96 @@
+/*
+ * Plugin interface for random-tool.
+ *
+ * A plugin is a dynamic library (.so, .dll or .dylib) that exports
+ * random_tool_plugin(), returning a description of the generator modes and
+ * export formats it adds. Put it in the random-tool/plugins folder of the
+ * configuration directory and its modes and formats show up in the mode
+ * picker and the export format list on the next start.
+ *
+ *     static int32_t even(const RtRequest *request, const RtRandom *random, int64_t *out) { ... }
+ *
+ *     static const RtMode modes[] = {{"Even", RT_INPUT_BOUNDS, even}};
+ *     static const RtPlugin plugin = {RT_PLUGIN_ABI_VERSION, "Even numbers", modes, 1, NULL, 0};
+ *
+ *     const RtPlugin *random_tool_plugin(void) { return &plugin; }
+ *
+ * All strings are UTF-8 and, like everything the description points to, must
+ * stay valid while the library is loaded. Plugins run inside the program with
+ * its full permissions, so only install plugins you trust.
+ */
+#ifndef RANDOM_TOOL_PLUGIN_H
+#define RANDOM_TOOL_PLUGIN_H
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+#define RT_PLUGIN_ABI_VERSION 1
+
+/* RtMode.input values */
+#define RT_INPUT_BOUNDS 0
+#define RT_INPUT_LIST 1
+
+/* One draw's settings */
+typedef struct RtRequest {
+    /* Used by RT_INPUT_BOUNDS modes */
+    int64_t lower;
+    int64_t upper;
+    /* Used by RT_INPUT_LIST modes */
+    const int64_t *list;
+    size_t list_len;
+    /* Number of results to write to out */
+    size_t count;
+    bool allow_duplicates;
+} RtRequest;
+
+/* The generator's random numbers; use these so seeds and replays work */
+typedef struct RtRandom {
+    void *context;
+    uint64_t (*next_u64)(void *context);
+} RtRandom;
+
+/* Receives an export, in one or more pieces of UTF-8 */
+typedef struct RtSink {
+    void *context;
+    void (*write)(void *context, const uint8_t *bytes, size_t len);
+} RtSink;
+
+typedef struct RtMode {
+    /* Must not be a built-in mode such as "Range"; the plugin is not loaded otherwise */
+    const char *name;
+    uint32_t input;
+    /* Write request->count numbers to out; return 0 on success */
+    int32_t (*generate)(const RtRequest *request, const RtRandom *random, int64_t *out);
+} RtMode;
+
+typedef struct RtExporter {
+    /* Must not be Text, CSV or JSON */
+    const char *name;
+    /* File extension without the dot */
+    const char *extension;
+    /* Pass the file content to sink->write; return 0 on success */
+    int32_t (*export_numbers)(const int64_t *numbers, size_t len, const RtSink *sink);
+} RtExporter;
+
+typedef struct RtPlugin {
+    uint32_t abi_version;
+    const char *name;
+    const RtMode *modes;
+    size_t mode_count;
+    const RtExporter *exporters;
+    size_t exporter_count;
+} RtPlugin;
+
+/* The one function a plugin must export */
+const RtPlugin *random_tool_plugin(void);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
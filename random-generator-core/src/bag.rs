@@ -0,0 +1,105 @@
+use rand::{Rng, RngCore};
+use std::collections::HashSet;
+
+use crate::{Number, Progress, RandomGeneratorError, PROGRESS_INTERVAL};
+
+/// 洗牌袋:不放回地抽取,袋子抽空后自动装回全部数字重新洗牌
+///
+/// 放在 [`Config::shuffle_bag`](crate::Config::shuffle_bag) 中,生成器每次生成后把抽剩的状态写回配置,
+/// 因此连续多次生成时,每个数字都要等其他数字都抽到过才会再次出现。
+/// 按权重的自定义列表中,重复的条目在袋子里有几份就会在一轮中出现几次。
+/// 列表或区间改变后袋子重新装满;袋子最多装 [`MAX_POOL`](crate::MAX_POOL) 个数字
+///
+/// ```
+/// use random_generator_core::{GeneratorConfig, RandomGenerator, ShuffleBag};
+///
+/// let mut generator = RandomGenerator::with_config(GeneratorConfig {
+///     lower_bound: 1,
+///     upper_bound: 5,
+///     num_to_generate: 2,
+///     shuffle_bag: Some(ShuffleBag::new()),
+///     ..Default::default()
+/// })?;
+/// let mut drawn = Vec::new();
+/// for _ in 0..2 {
+///     generator.generate_numbers()?;
+///     drawn.extend_from_slice(generator.get_numbers());
+/// }
+/// drawn.sort();
+/// drawn.dedup();
+/// assert_eq!(drawn.len(), 4);
+/// assert_eq!(generator.get_config().shuffle_bag.as_ref().map(ShuffleBag::left), Some(1));
+/// # Ok::<(), random_generator_core::RandomGeneratorError>(())
+/// ```
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ShuffleBag<T: Number> {
+    /// 装袋时的全部数字,用来发现列表或区间变了
+    pool: Vec<T>,
+    /// 本轮还没有抽到的数字
+    left: Vec<T>,
+}
+
+impl<T: Number> ShuffleBag<T> {
+    /// 空袋子,第一次抽取时装满
+    pub fn new() -> Self {
+        Self { pool: Vec::new(), left: Vec::new() }
+    }
+
+    /// 一轮的数字个数;还没有抽过时为 0
+    pub fn size(&self) -> usize {
+        self.pool.len()
+    }
+
+    /// 本轮还剩几个数字没有抽到
+    pub fn left(&self) -> usize {
+        self.left.len()
+    }
+
+    /// 放回本轮已经抽到的数字,重新开始一轮
+    pub fn refill(&mut self) {
+        self.left = self.pool.clone();
+    }
+
+    /// 从袋子里抽取 `count` 个数字;`pool` 是当前模式的全部数字
+    ///
+    /// 不允许重复时,本批已经抽到的值再次抽到会留在袋子里,等以后再抽
+    pub(crate) fn draw(
+        &mut self,
+        pool: Vec<T>,
+        count: usize,
+        allow_duplicates: bool,
+        rng: &mut dyn RngCore,
+        progress: &mut Progress<'_>,
+    ) -> Result<Vec<T>, RandomGeneratorError> {
+        if pool.is_empty() {
+            return Err(RandomGeneratorError::EmptyList);
+        }
+        if !pool.iter().map(|value| value.key()).eq(self.pool.iter().map(|value| value.key())) {
+            self.pool = pool;
+            self.refill();
+        }
+
+        let mut seen = HashSet::new();
+        let mut held = Vec::new();
+        let mut numbers = Vec::new();
+        let mut attempts = 0usize;
+        while numbers.len() < count {
+            if attempts.is_multiple_of(PROGRESS_INTERVAL) {
+                progress.checkpoint(numbers.len(), count)?;
+            }
+            attempts += 1;
+            if self.left.is_empty() {
+                self.refill();
+            }
+            let value = self.left.swap_remove(rng.gen_range(0..self.left.len()));
+            if allow_duplicates || seen.insert(value.key()) {
+                numbers.push(value);
+            } else {
+                held.push(value);
+            }
+        }
+        self.left.extend(held);
+        Ok(numbers)
+    }
+}
@@ -0,0 +1,108 @@
+use std::fmt;
+use std::sync::atomic::AtomicBool;
+use std::time::{Duration, Instant};
+
+use crate::{Config, Generator, GeneratorMode, Number, Progress, RandomGeneratorError, UniqueStrategy};
+
+/// 抽取数量超过范围的这个比例时不再测试集合法,它会反复抽到已有的数字,耗时难以预料
+const SET_LIMIT: f64 = 0.9;
+
+/// 按当前配置比较各种抽取算法的耗时
+#[derive(Debug, Clone, PartialEq)]
+pub struct BenchmarkReport {
+    /// 每轮抽取的数量
+    pub count: usize,
+    /// 每种算法重复的轮数
+    pub rounds: usize,
+    /// 各算法的结果
+    pub results: Vec<BenchmarkResult>,
+}
+
+/// 一种算法的计时结果
+#[derive(Debug, Clone, PartialEq)]
+pub struct BenchmarkResult {
+    /// 测试的算法,`None` 表示当前配置没有可选的算法,直接按配置生成
+    pub strategy: Option<UniqueStrategy>,
+    /// 生成时是否会自动选用这种算法
+    pub chosen: bool,
+    /// 所有轮次的总耗时,跳过时为 `None`
+    pub elapsed: Option<Duration>,
+}
+
+impl BenchmarkReport {
+    /// 某个结果每秒生成的数字个数
+    pub fn throughput(&self, result: &BenchmarkResult) -> Option<f64> {
+        let seconds = result.elapsed?.as_secs_f64();
+        (seconds > 0.0).then(|| (self.count * self.rounds) as f64 / seconds)
+    }
+}
+
+impl fmt::Display for BenchmarkReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} numbers x {} rounds", self.count, self.rounds)?;
+        for result in &self.results {
+            let name = result.strategy.map_or("As configured".to_string(), |strategy| strategy.to_string());
+            let marker = if result.chosen { " (used)" } else { "" };
+            match (result.elapsed, self.throughput(result)) {
+                (Some(elapsed), Some(rate)) => {
+                    write!(f, "\n{}{}: {:.2?}, {:.0} numbers/s", name, marker, elapsed, rate)?
+                }
+                (Some(elapsed), None) => write!(f, "\n{}{}: {:.2?}", name, marker, elapsed)?,
+                (None, _) => write!(f, "\n{}{}: skipped", name, marker)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<T: Number> Generator<T> {
+    /// 按当前配置把每种不重复抽取算法各运行 `rounds` 轮并计时
+    ///
+    /// 只有离散范围的不重复抽取有多种算法可比,其他配置只测当前的生成方式;
+    /// 已生成的结果和事件回调都不受影响
+    pub fn benchmark(&mut self, rounds: usize) -> Result<BenchmarkReport, RandomGeneratorError> {
+        let config = self.config.clone();
+        self.validate_config(&config)?;
+        let source = self.registry.get(config.mode).ok_or(RandomGeneratorError::UnknownMode(config.mode))?;
+
+        let cancel = AtomicBool::new(false);
+        let mut progress = Progress { cancel: &cancel, on_progress: &mut |_, _| {} };
+        let count = config.num_to_generate;
+        let mut results = Vec::new();
+
+        match range_size(&config) {
+            Some(size) => {
+                let chosen = UniqueStrategy::for_range(count, size);
+                for strategy in UniqueStrategy::ALL {
+                    let elapsed = if strategy == UniqueStrategy::Set && count as f64 > size as f64 * SET_LIMIT {
+                        None
+                    } else {
+                        let start = Instant::now();
+                        for _ in 0..rounds {
+                            strategy.indices(&mut *self.rng, size, count, &mut progress)?;
+                        }
+                        Some(start.elapsed())
+                    };
+                    results.push(BenchmarkResult { strategy: Some(strategy), chosen: strategy == chosen, elapsed });
+                }
+            }
+            None => {
+                let start = Instant::now();
+                for _ in 0..rounds {
+                    source.generate(&config, &mut *self.rng, &mut progress)?;
+                }
+                results.push(BenchmarkResult { strategy: None, chosen: true, elapsed: Some(start.elapsed()) });
+            }
+        }
+
+        Ok(BenchmarkReport { count, rounds, results })
+    }
+}
+
+/// 有多种算法可比时返回离散范围的大小
+fn range_size<T: Number>(config: &Config<T>) -> Option<u128> {
+    if config.mode != GeneratorMode::RANGE || config.allow_duplicates {
+        return None;
+    }
+    T::values_between(config.lower_bound, config.upper_bound)
+}
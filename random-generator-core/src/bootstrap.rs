@@ -0,0 +1,237 @@
+use std::sync::atomic::AtomicBool;
+
+use crate::{Config, GeneratorMode, GeneratorSource, ListDuplicates, ListSource, Progress, RandomGeneratorError, Seed, PROGRESS_INTERVAL};
+
+/// 一次最多抽取的数字总数(重抽样次数乘以每次的个数)
+const MAX_DRAWS: usize = 10_000_000;
+
+/// 文本报告中直方图的组数
+const HISTOGRAM_BINS: usize = 20;
+
+/// 直方图中最长的一条有几个 `#`
+const BAR_WIDTH: usize = 40;
+
+/// 自助法(bootstrap)重抽样:从一组数据中有放回地抽取 N 次、每次 M 个,看平均数怎样分布
+///
+/// 每次重抽样都按列表模式允许重复地抽取,数据中重复的值按出现的次数计,
+/// 与原始数据的分布相同。平均数的分布给出标准误和百分位区间,常用来讲解抽样误差
+///
+/// ```
+/// use random_generator_core::{Bootstrap, Seed};
+///
+/// let data = Bootstrap::parse("2.5 3.1 4.0\n5.2, 6.8")?;
+/// let report = data.resample(1000, None, Seed::from([5; 32]))?;
+/// assert_eq!(report.means.len(), 1000);
+/// assert_eq!(report.size, 5);
+/// let (low, high) = report.interval(0.95);
+/// assert!(2.5 <= low && low <= report.data_mean && report.data_mean <= high && high <= 6.8);
+/// # Ok::<(), random_generator_core::RandomGeneratorError>(())
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bootstrap {
+    data: Vec<f64>,
+}
+
+/// 一次重抽样的结果
+#[derive(Debug, Clone, PartialEq)]
+pub struct BootstrapReport {
+    /// 原始数据有几个值
+    pub data_len: usize,
+    /// 原始数据的平均数
+    pub data_mean: f64,
+    /// 每次重抽样抽几个值
+    pub size: usize,
+    /// 每次重抽样的平均数,按抽取的先后
+    pub means: Vec<f64>,
+}
+
+impl Bootstrap {
+    /// 至少一个值,并且都是有限的数字
+    pub fn new(data: Vec<f64>) -> Result<Self, RandomGeneratorError> {
+        if data.is_empty() {
+            return Err(RandomGeneratorError::InvalidBootstrap("the dataset has no numbers".to_string()));
+        }
+        if let Some(value) = data.iter().find(|value| !value.is_finite()) {
+            return Err(RandomGeneratorError::InvalidBootstrap(format!("{} is not a finite number", value)));
+        }
+        Ok(Self { data })
+    }
+
+    /// 解析数据:数字之间用逗号、分号、空格或换行分隔,`#` 之后是注释;出错时指出是第几行
+    pub fn parse(text: &str) -> Result<Self, RandomGeneratorError> {
+        let mut data = Vec::new();
+        for (index, line) in text.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or_default();
+            for part in line.split([',', ';', ' ', '\t']).map(str::trim).filter(|part| !part.is_empty()) {
+                match part.parse::<f64>() {
+                    Ok(value) if value.is_finite() => data.push(value),
+                    _ => return Err(RandomGeneratorError::InvalidBootstrap(format!("line {}: '{}' is not a number", index + 1, part))),
+                }
+            }
+        }
+        Self::new(data)
+    }
+
+    /// 原始数据,按文本中的先后
+    pub fn data(&self) -> &[f64] {
+        &self.data
+    }
+
+    /// 重抽样 `resamples` 次,每次抽 `size` 个值;`None` 表示与原始数据一样多
+    pub fn resample(&self, resamples: usize, size: Option<usize>, seed: Seed) -> Result<BootstrapReport, RandomGeneratorError> {
+        self.resample_with_progress(resamples, size, seed, &AtomicBool::new(false), &mut |_, _| {})
+    }
+
+    /// 重抽样,并以 `(已抽的值数, 总数)` 回报进度
+    ///
+    /// 每次回报前检查 `cancel`,被置位时返回 `Cancelled`
+    pub fn resample_with_progress(
+        &self,
+        resamples: usize,
+        size: Option<usize>,
+        seed: Seed,
+        cancel: &AtomicBool,
+        on_progress: &mut dyn FnMut(usize, usize),
+    ) -> Result<BootstrapReport, RandomGeneratorError> {
+        let size = size.unwrap_or(self.data.len());
+        if resamples == 0 {
+            return Err(RandomGeneratorError::InvalidBootstrap("make at least 1 resample".to_string()));
+        }
+        if size == 0 {
+            return Err(RandomGeneratorError::InvalidBootstrap("each resample needs at least 1 value".to_string()));
+        }
+        if resamples.checked_mul(size).is_none_or(|draws| draws > MAX_DRAWS) {
+            return Err(RandomGeneratorError::InvalidBootstrap(format!(
+                "{} resamples of {} values is more than {} draws",
+                resamples, size, MAX_DRAWS
+            )));
+        }
+
+        let config = Config {
+            mode: GeneratorMode::CUSTOM_LIST,
+            custom_list: self.data.clone(),
+            list_duplicates: ListDuplicates::Weighted,
+            allow_duplicates: true,
+            num_to_generate: size,
+            ..Config::default()
+        };
+        let mut rng = seed.rng();
+        // 每次重抽样内部不回报,进度按抽完的重抽样计
+        let mut quiet = Progress { cancel, on_progress: &mut |_, _| {} };
+        let mut progress = Progress { cancel, on_progress };
+        let every = (PROGRESS_INTERVAL / size).max(1);
+        let mut means = Vec::with_capacity(resamples);
+        for i in 0..resamples {
+            if i.is_multiple_of(every) {
+                progress.checkpoint(i * size, resamples * size)?;
+            }
+            means.push(mean(&ListSource.generate(&config, &mut rng, &mut quiet)?));
+        }
+        Ok(BootstrapReport { data_len: self.data.len(), data_mean: mean(&self.data), size, means })
+    }
+}
+
+impl BootstrapReport {
+    /// 各次重抽样平均数的平均数
+    pub fn mean(&self) -> f64 {
+        mean(&self.means)
+    }
+
+    /// 标准误:各次平均数的样本标准差;只有一次重抽样时为 0
+    pub fn standard_error(&self) -> f64 {
+        if self.means.len() < 2 {
+            return 0.0;
+        }
+        let mean = self.mean();
+        let squares: f64 = self.means.iter().map(|value| (value - mean).powi(2)).sum();
+        (squares / (self.means.len() - 1) as f64).sqrt()
+    }
+
+    /// 平均数的第 `p` 分位数,`p` 从 0 到 1,相邻两个值之间按线性插值
+    pub fn percentile(&self, p: f64) -> f64 {
+        let sorted = self.sorted();
+        let position = p.clamp(0.0, 1.0) * (sorted.len() - 1) as f64;
+        let (below, above) = (position.floor() as usize, position.ceil() as usize);
+        sorted[below] + (sorted[above] - sorted[below]) * (position - below as f64)
+    }
+
+    /// 百分位区间:`level` 为 0.95 时是第 2.5 到第 97.5 分位数
+    pub fn interval(&self, level: f64) -> (f64, f64) {
+        let tail = (1.0 - level.clamp(0.0, 1.0)) / 2.0;
+        (self.percentile(tail), self.percentile(1.0 - tail))
+    }
+
+    /// 把平均数从最小到最大等宽分成 `bins` 组,每组给出下限、上限和个数;所有平均数相同时只有一组
+    pub fn histogram(&self, bins: usize) -> Vec<(f64, f64, usize)> {
+        histogram(&self.sorted(), bins)
+    }
+
+    /// 摘要加上平均数分布的直方图
+    pub fn to_text(&self) -> String {
+        let (low, high) = self.interval(0.95);
+        let mut text = format!(
+            "Dataset: {} values, mean {:.4}\nResamples: {} of {} values each\nMean of the means: {:.4}\nStandard error: {:.4}\n95% interval: {:.4} to {:.4}\n\n",
+            self.data_len,
+            self.data_mean,
+            self.means.len(),
+            self.size,
+            self.mean(),
+            self.standard_error(),
+            low,
+            high
+        );
+        text.push_str(&histogram_bars(&self.sorted()));
+        text
+    }
+
+    /// 每次重抽样一行的 CSV,带表头 `resample,mean`,按抽取的先后
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("resample,mean\n");
+        for (i, mean) in self.means.iter().enumerate() {
+            csv.push_str(&format!("{},{}\n", i + 1, mean));
+        }
+        csv
+    }
+
+    fn sorted(&self) -> Vec<f64> {
+        let mut sorted = self.means.clone();
+        sorted.sort_by(f64::total_cmp);
+        sorted
+    }
+}
+
+/// 把排好序的值从最小到最大等宽分成 `bins` 组,每组给出下限、上限和个数;所有值相同时只有一组
+fn histogram(sorted: &[f64], bins: usize) -> Vec<(f64, f64, usize)> {
+    let (low, high) = (sorted[0], sorted[sorted.len() - 1]);
+    let bins = if high > low { bins.max(1) } else { 1 };
+    let width = (high - low) / bins as f64;
+    let mut counts = vec![0; bins];
+    for value in sorted {
+        let bin = if width > 0.0 { ((value - low) / width) as usize } else { 0 };
+        counts[bin.min(bins - 1)] += 1;
+    }
+    counts
+        .into_iter()
+        .enumerate()
+        .map(|(bin, count)| (low + width * bin as f64, low + width * (bin + 1) as f64, count))
+        .collect()
+}
+
+/// 排好序的值画成文本直方图,每组一行,例如 `0.1000 to 0.2000  ####  12`
+pub(crate) fn histogram_bars(sorted: &[f64]) -> String {
+    let histogram = histogram(sorted, HISTOGRAM_BINS);
+    let most = histogram.iter().map(|&(_, _, count)| count).max().unwrap_or(1);
+    let labels: Vec<(String, String)> = histogram.iter().map(|&(from, to, _)| (format!("{:.4}", from), format!("{:.4}", to))).collect();
+    let from_width = labels.iter().map(|(from, _)| from.len()).max().unwrap_or(0);
+    let to_width = labels.iter().map(|(_, to)| to.len()).max().unwrap_or(0);
+    let mut text = String::new();
+    for ((from, to), &(_, _, count)) in labels.iter().zip(&histogram) {
+        let bar = "#".repeat((count * BAR_WIDTH).div_ceil(most));
+        text.push_str(&format!("{:>from_width$} to {:>to_width$}  {:<BAR_WIDTH$}  {}\n", from, to, bar, count));
+    }
+    text
+}
+
+fn mean(values: &[f64]) -> f64 {
+    values.iter().sum::<f64>() / values.len() as f64
+}
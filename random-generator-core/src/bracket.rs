@@ -0,0 +1,169 @@
+use rand::seq::SliceRandom;
+
+use crate::{RandomGeneratorError, Seed};
+
+/// 轮空位置的显示
+const BYE: &str = "(bye)";
+
+/// 对阵表中的一场比赛
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BracketMatch {
+    /// 第几轮,从 1 开始
+    pub round: usize,
+    /// 比赛编号,从第一轮起连续编号,从 1 开始
+    pub number: usize,
+    /// 上方的参赛者;之后各轮是 `Winner of N`,对手轮空时直接是晋级的人
+    pub top: String,
+    /// 下方的参赛者,轮空时为 `(bye)`
+    pub bottom: String,
+}
+
+/// 随机排位的单败淘汰对阵表
+///
+/// 人数不是 2 的幂时补足轮空;轮空按种子位置均匀分布在对阵表中,
+/// 每场比赛最多一方轮空,轮空的一方直接晋级第二轮
+///
+/// ```
+/// use random_generator_core::{Bracket, Seed};
+///
+/// let names = ["Ann", "Bo", "Cy", "Di", "Ed"].map(String::from).to_vec();
+/// let bracket = Bracket::draw(names, Seed::from([4; 32]))?;
+/// assert_eq!(bracket.size(), 8);
+/// assert_eq!(bracket.rounds(), 3);
+/// let matches = bracket.matches();
+/// assert_eq!(matches.len(), 7);
+/// assert_eq!(matches.iter().filter(|m| m.bottom == "(bye)").count(), bracket.byes());
+/// # Ok::<(), random_generator_core::RandomGeneratorError>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bracket {
+    /// 第一轮从上到下的位置,`None` 为轮空
+    slots: Vec<Option<String>>,
+}
+
+impl Bracket {
+    /// 把参赛者随机排入对阵表,至少 2 人
+    pub fn draw(mut names: Vec<String>, seed: Seed) -> Result<Self, RandomGeneratorError> {
+        names.retain(|name| !name.trim().is_empty());
+        if names.len() < 2 {
+            return Err(RandomGeneratorError::InvalidDraw("a bracket needs at least 2 participants".to_string()));
+        }
+        names.shuffle(&mut seed.rng());
+        // 打乱后的第 k 个人是 k 号种子,超出人数的种子号就是轮空
+        let size = names.len().next_power_of_two();
+        let mut names = names.into_iter();
+        let mut by_seed: Vec<Option<String>> = (0..size).map(|_| names.next()).collect();
+        let slots = seed_positions(size).into_iter().map(|seed| by_seed[seed - 1].take()).collect();
+        Ok(Bracket { slots })
+    }
+
+    /// 对阵表的位置数,即不小于人数的 2 的幂
+    pub fn size(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// 轮空的个数
+    pub fn byes(&self) -> usize {
+        self.slots.iter().filter(|slot| slot.is_none()).count()
+    }
+
+    /// 轮数
+    pub fn rounds(&self) -> usize {
+        self.size().trailing_zeros() as usize
+    }
+
+    /// 按轮次排列的全部比赛,第一轮中对手轮空的比赛也列出
+    pub fn matches(&self) -> Vec<BracketMatch> {
+        let mut matches = Vec::with_capacity(self.size() - 1);
+        // 上一轮每场的晋级者:轮空比赛直接是那个人
+        let mut entrants: Vec<String> = self.slots.iter().map(|slot| slot.clone().unwrap_or_else(|| BYE.to_string())).collect();
+        let mut byes: Vec<bool> = self.slots.iter().map(Option::is_none).collect();
+        for round in 1..=self.rounds() {
+            let mut next = Vec::with_capacity(entrants.len() / 2);
+            let mut next_byes = Vec::with_capacity(entrants.len() / 2);
+            for pair in 0..entrants.len() / 2 {
+                let (top, bottom) = (entrants[2 * pair].clone(), entrants[2 * pair + 1].clone());
+                let number = matches.len() + 1;
+                next.push(match (byes[2 * pair], byes[2 * pair + 1]) {
+                    (false, true) => top.clone(),
+                    (true, false) => bottom.clone(),
+                    _ => format!("Winner of {}", number),
+                });
+                next_byes.push(false);
+                matches.push(BracketMatch { round, number, top, bottom });
+            }
+            entrants = next;
+            byes = next_byes;
+        }
+        matches
+    }
+
+    /// 画成文本的对阵树,每个参赛者一行,轮次从左到右
+    pub fn to_text(&self) -> String {
+        let names: Vec<&str> = self.slots.iter().map(|slot| slot.as_deref().unwrap_or(BYE)).collect();
+        let name_width = names.iter().map(|name| name.chars().count()).max().unwrap_or(0);
+        let width = name_width + 1 + self.rounds() * 4 + 2;
+        let mut grid = vec![vec![' '; width]; self.size() * 2 - 1];
+        for (i, name) in names.iter().enumerate() {
+            for (x, c) in name.chars().enumerate() {
+                grid[2 * i][x] = c;
+            }
+        }
+
+        // 每个参赛者或上一场比赛所在的行,以及线从哪一列开始
+        let mut rows: Vec<usize> = (0..self.size()).map(|i| 2 * i).collect();
+        let mut start = name_width + 1;
+        for round in 0..self.rounds() {
+            let x = name_width + 1 + round * 4 + 2;
+            let mut next = Vec::with_capacity(rows.len() / 2);
+            for pair in rows.chunks(2) {
+                let (top, bottom) = (pair[0], pair[1]);
+                for &row in pair {
+                    grid[row][start..x].fill('─');
+                }
+                grid[top][x] = '┐';
+                grid[bottom][x] = '┘';
+                for row in &mut grid[top + 1..bottom] {
+                    row[x] = '│';
+                }
+                let middle = (top + bottom) / 2;
+                grid[middle][x] = '├';
+                next.push(middle);
+            }
+            rows = next;
+            start = x + 1;
+        }
+        grid[rows[0]][start..width].fill('─');
+
+        grid.into_iter()
+            .map(|row| row.into_iter().collect::<String>().trim_end().to_string() + "\n")
+            .collect()
+    }
+
+    /// 全部比赛的 CSV,带表头 `round,match,top,bottom`
+    pub fn to_csv(&self) -> String {
+        let cell = |text: &str| {
+            if text.contains([',', '"', '\n', '\r']) {
+                format!("\"{}\"", text.replace('"', "\"\""))
+            } else {
+                text.to_string()
+            }
+        };
+        let mut csv = String::from("round,match,top,bottom\n");
+        for m in self.matches() {
+            csv.push_str(&format!("{},{},{},{}\n", m.round, m.number, cell(&m.top), cell(&m.bottom)));
+        }
+        csv
+    }
+}
+
+/// 标准的种子位置:`size` 个位置从上到下各是几号种子,1 号和 2 号只会在决赛相遇,
+/// 而 `k` 号种子第一轮遇到 `size + 1 - k` 号
+fn seed_positions(size: usize) -> Vec<usize> {
+    let mut positions = vec![1];
+    while positions.len() < size {
+        let total = positions.len() * 2 + 1;
+        positions = positions.into_iter().flat_map(|seed| [seed, total - seed]).collect();
+    }
+    positions
+}
@@ -0,0 +1,154 @@
+use rand::RngCore;
+use std::fmt;
+use std::io::Write;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::{RandomGeneratorError, Seed};
+
+/// 每次写入的字节数,也是回报进度和检查取消的间隔
+const CHUNK: usize = 64 * 1024;
+
+/// 字节数的单位,按 1024 进位
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ByteUnit {
+    /// 字节
+    Bytes,
+    /// 1024 字节
+    Kilobytes,
+    /// 1024 KB
+    #[default]
+    Megabytes,
+    /// 1024 MB
+    Gigabytes,
+}
+
+impl ByteUnit {
+    /// 所有单位,从小到大
+    pub const ALL: [ByteUnit; 4] = [ByteUnit::Bytes, ByteUnit::Kilobytes, ByteUnit::Megabytes, ByteUnit::Gigabytes];
+
+    /// 一个单位是多少字节
+    pub fn bytes(self) -> u64 {
+        match self {
+            ByteUnit::Bytes => 1,
+            ByteUnit::Kilobytes => 1 << 10,
+            ByteUnit::Megabytes => 1 << 20,
+            ByteUnit::Gigabytes => 1 << 30,
+        }
+    }
+}
+
+impl fmt::Display for ByteUnit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ByteUnit::Bytes => write!(f, "B"),
+            ByteUnit::Kilobytes => write!(f, "KB"),
+            ByteUnit::Megabytes => write!(f, "MB"),
+            ByteUnit::Gigabytes => write!(f, "GB"),
+        }
+    }
+}
+
+/// 文件大小,解析 `4096`、`512 KB`、`1.5GB` 这样的文本
+///
+/// `K`、`KB`、`KiB` 都表示 1024 字节,不区分大小写;小数部分不足一字节的舍去
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ByteSize(pub u64);
+
+impl ByteSize {
+    /// `amount` 个 `unit`
+    pub fn new(amount: f64, unit: ByteUnit) -> Result<Self, RandomGeneratorError> {
+        let bytes = amount * unit.bytes() as f64;
+        if !bytes.is_finite() || bytes < 0.0 || bytes >= u64::MAX as f64 {
+            return Err(RandomGeneratorError::InvalidSize(format!("{} {} is out of range", amount, unit)));
+        }
+        Ok(ByteSize(bytes as u64))
+    }
+}
+
+impl FromStr for ByteSize {
+    type Err = RandomGeneratorError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let text = s.trim();
+        let split = text.find(|c: char| !c.is_ascii_digit() && c != '.').unwrap_or(text.len());
+        let (amount, unit) = text.split_at(split);
+        let unit = match unit.trim().to_ascii_lowercase().as_str() {
+            "" | "b" => ByteUnit::Bytes,
+            "k" | "kb" | "kib" => ByteUnit::Kilobytes,
+            "m" | "mb" | "mib" => ByteUnit::Megabytes,
+            "g" | "gb" | "gib" => ByteUnit::Gigabytes,
+            _ => return Err(RandomGeneratorError::InvalidSize(format!("unknown unit in '{}', use B, KB, MB or GB", text))),
+        };
+        let amount = amount
+            .parse::<f64>()
+            .map_err(|_| RandomGeneratorError::InvalidSize(format!("'{}' is not a size like 10MB", text)))?;
+        ByteSize::new(amount, unit)
+    }
+}
+
+impl fmt::Display for ByteSize {
+    /// 用能整除的最大单位显示,例如 `10 MB`、`1536 KB`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let unit = ByteUnit::ALL
+            .into_iter()
+            .rev()
+            .find(|unit| self.0 != 0 && self.0.is_multiple_of(unit.bytes()))
+            .unwrap_or(ByteUnit::Bytes);
+        write!(f, "{} {}", self.0 / unit.bytes(), unit)
+    }
+}
+
+/// 一段由种子确定的随机字节,用 ChaCha20 生成,可以当作密码学安全的随机数据
+///
+/// 用于测试文件和磁盘测速;同一个种子总是写出同样的内容
+///
+/// ```
+/// use random_generator_core::{ByteSize, RandomBytes, Seed};
+/// use std::sync::atomic::AtomicBool;
+///
+/// let bytes = RandomBytes { size: "100 KB".parse()?, seed: Seed::from([1; 32]) };
+/// let mut buffer = Vec::new();
+/// bytes.write_with_progress(&mut buffer, &AtomicBool::new(false), &mut |_, _| {})?;
+/// assert_eq!(buffer.len(), 100 * 1024);
+/// assert_eq!(bytes.size, ByteSize(102_400));
+/// # Ok::<(), random_generator_core::RandomGeneratorError>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RandomBytes {
+    /// 字节数
+    pub size: ByteSize,
+    /// 种子
+    pub seed: Seed,
+}
+
+impl RandomBytes {
+    /// 把全部字节写入 `writer`
+    ///
+    /// `on_progress` 以 `(已写字节, 总字节)` 的形式被周期性调用;
+    /// 每写一块都会检查 `cancel`,被置位时返回 `Cancelled`,已写入的部分保留在 `writer` 中
+    pub fn write_with_progress(
+        &self,
+        writer: &mut dyn Write,
+        cancel: &AtomicBool,
+        on_progress: &mut dyn FnMut(u64, u64),
+    ) -> Result<(), RandomGeneratorError> {
+        let total = self.size.0;
+        let mut rng = self.seed.rng();
+        let mut buffer = vec![0u8; CHUNK];
+        let mut written = 0u64;
+        on_progress(0, total);
+        while written < total {
+            if cancel.load(Ordering::Relaxed) {
+                return Err(RandomGeneratorError::Cancelled);
+            }
+            let chunk = &mut buffer[..(total - written).min(CHUNK as u64) as usize];
+            rng.fill_bytes(chunk);
+            writer.write_all(chunk)?;
+            written += chunk.len() as u64;
+            on_progress(written, total);
+        }
+        writer.flush()?;
+        Ok(())
+    }
+}
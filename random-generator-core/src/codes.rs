@@ -0,0 +1,295 @@
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::collections::HashSet;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::{RandomGeneratorError, Seed};
+
+/// 默认字母表:去掉了容易看错的 0、O、1、I
+pub const CODE_ALPHABET: &str = "23456789ABCDEFGHJKLMNPQRSTUVWXYZ";
+/// 每要一个不重复的兑换码,平均最多尝试的次数
+const UNIQUE_ATTEMPTS: usize = 50;
+
+/// 兑换码末尾的校验字符
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CheckCharacter {
+    /// 不加校验字符
+    #[default]
+    None,
+    /// Luhn mod N 算法,能发现任一字符写错和大多数相邻字符对调
+    Luhn,
+    /// CRC-8 校验值对字母表长度取余
+    Crc,
+}
+
+impl CheckCharacter {
+    /// 所有可选的校验方式
+    pub const ALL: [CheckCharacter; 3] = [CheckCharacter::None, CheckCharacter::Luhn, CheckCharacter::Crc];
+}
+
+impl fmt::Display for CheckCharacter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CheckCharacter::None => write!(f, "None"),
+            CheckCharacter::Luhn => write!(f, "Luhn"),
+            CheckCharacter::Crc => write!(f, "CRC"),
+        }
+    }
+}
+
+impl FromStr for CheckCharacter {
+    type Err = RandomGeneratorError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.trim();
+        CheckCharacter::ALL
+            .into_iter()
+            .find(|check| check.to_string().eq_ignore_ascii_case(name))
+            .ok_or_else(|| RandomGeneratorError::InvalidCode(format!("unknown check character '{}'", name)))
+    }
+}
+
+/// 批量生成便于人工输入的兑换码、邀请码
+///
+/// 同一批中的兑换码互不相同,也可以排除以前导出过的兑换码。
+/// 分组的短横线只为便于阅读,比较和校验时忽略,大小写也不区分
+///
+/// ```
+/// use random_generator_core::{CheckCharacter, CodeSpec, Seed};
+///
+/// let spec = CodeSpec { length: 7, check: CheckCharacter::Luhn, group: 4, ..Default::default() };
+/// let codes = spec.generate(3, Seed::from([4; 32]), &Default::default())?;
+/// assert_eq!(codes[0].len(), 9); // 7 个字符、1 个校验字符和 1 个短横线
+/// assert!(codes.iter().all(|code| spec.is_valid(code)));
+/// # Ok::<(), random_generator_core::RandomGeneratorError>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CodeSpec {
+    /// 随机字符的个数,不含校验字符
+    pub length: usize,
+    /// 可用的字符
+    pub alphabet: String,
+    /// 校验字符
+    pub check: CheckCharacter,
+    /// 每隔几个字符插入一个短横线;0 表示不分组
+    pub group: usize,
+}
+
+impl Default for CodeSpec {
+    fn default() -> Self {
+        CodeSpec { length: 8, alphabet: CODE_ALPHABET.to_string(), check: CheckCharacter::None, group: 0 }
+    }
+}
+
+impl CodeSpec {
+    /// 用种子生成 `count` 个互不相同的兑换码,并避开 `exclude` 中的兑换码
+    ///
+    /// `exclude` 中的兑换码应先经过 [`CodeSpec::normalize`]
+    pub fn generate(&self, count: usize, seed: Seed, exclude: &HashSet<String>) -> Result<Vec<String>, RandomGeneratorError> {
+        let alphabet = self.alphabet()?;
+        if self.length == 0 {
+            return Err(RandomGeneratorError::InvalidCode("the length must be at least 1".to_string()));
+        }
+        let combinations = (alphabet.len() as u128).checked_pow(self.length as u32).unwrap_or(u128::MAX);
+        if count as u128 > combinations.saturating_sub(exclude.len() as u128) {
+            return Err(RandomGeneratorError::InvalidCode(format!(
+                "only {} different codes of length {} are possible",
+                combinations, self.length
+            )));
+        }
+
+        let mut rng = seed.rng();
+        let mut seen = HashSet::with_capacity(count);
+        let mut codes = Vec::with_capacity(count);
+        let mut attempts = count.saturating_mul(UNIQUE_ATTEMPTS).max(1000);
+        while codes.len() < count {
+            if attempts == 0 {
+                return Err(RandomGeneratorError::InvalidCode(format!("could not find {} unused codes", count)));
+            }
+            attempts -= 1;
+            let mut code: String = (0..self.length).map(|_| *alphabet.choose(&mut rng).unwrap()).collect();
+            if let Some(check) = check_character(self.check, &alphabet, &code) {
+                code.push(check);
+            }
+            if !exclude.contains(&code) && seen.insert(code.clone()) {
+                codes.push(self.grouped(&code));
+            }
+        }
+        Ok(codes)
+    }
+
+    /// 去掉短横线和空白并转为大写,用于比较
+    pub fn normalize(code: &str) -> String {
+        code.chars().filter(|c| *c != '-' && !c.is_whitespace()).flat_map(char::to_uppercase).collect()
+    }
+
+    /// 从以前导出的文本、CSV 或 JSON 中取出所有兑换码,已经过 [`CodeSpec::normalize`]
+    ///
+    /// 引号、逗号、括号和空白都当作分隔符,表头之类的多余词不影响排除
+    pub fn collect_codes(exported: &str) -> HashSet<String> {
+        exported
+            .split(|c: char| !c.is_alphanumeric() && c != '-')
+            .map(Self::normalize)
+            .filter(|code| !code.is_empty())
+            .collect()
+    }
+
+    /// 兑换码的长度、字符和校验字符是否正确
+    pub fn is_valid(&self, code: &str) -> bool {
+        let Ok(alphabet) = self.alphabet() else {
+            return false;
+        };
+        let code = Self::normalize(code);
+        let length = self.length + usize::from(self.check != CheckCharacter::None);
+        if code.chars().count() != length || !code.chars().all(|c| alphabet.contains(&c)) {
+            return false;
+        }
+        match self.check {
+            CheckCharacter::None => true,
+            check => {
+                let (payload, last) = code.split_at(code.len() - code.chars().last().map_or(0, char::len_utf8));
+                check_character(check, &alphabet, payload).is_some_and(|expected| last.starts_with(expected))
+            }
+        }
+    }
+
+    /// 不重复且按大写比较的字母表
+    fn alphabet(&self) -> Result<Vec<char>, RandomGeneratorError> {
+        let mut alphabet: Vec<char> = Vec::new();
+        for c in self.alphabet.chars().flat_map(char::to_uppercase) {
+            if c == '-' || c.is_whitespace() {
+                return Err(RandomGeneratorError::InvalidCode("the alphabet can't contain '-' or spaces".to_string()));
+            }
+            if !alphabet.contains(&c) {
+                alphabet.push(c);
+            }
+        }
+        if alphabet.len() < 2 {
+            return Err(RandomGeneratorError::InvalidCode("the alphabet needs at least 2 different characters".to_string()));
+        }
+        Ok(alphabet)
+    }
+
+    fn grouped(&self, code: &str) -> String {
+        if self.group == 0 {
+            return code.to_string();
+        }
+        let chars: Vec<char> = code.chars().collect();
+        chars.chunks(self.group).map(|chunk| chunk.iter().collect::<String>()).collect::<Vec<_>>().join("-")
+    }
+}
+
+fn check_character(check: CheckCharacter, alphabet: &[char], payload: &str) -> Option<char> {
+    let base = alphabet.len() as u32;
+    let value = match check {
+        CheckCharacter::None => return None,
+        CheckCharacter::Luhn => {
+            // 从右往左,每隔一位乘 2,乘积按 N 进制拆开相加
+            let sum: u32 = payload.chars().rev().enumerate().map(|(i, c)| {
+                let position = alphabet.iter().position(|&a| a == c).unwrap_or(0) as u32;
+                let product = if i % 2 == 0 { position * 2 } else { position };
+                product / base + product % base
+            }).sum();
+            (base - sum % base) % base
+        }
+        CheckCharacter::Crc => {
+            let crc = payload.bytes().fold(0u8, |crc, byte| {
+                (0..8).fold(crc ^ byte, |crc, _| if crc & 0x80 != 0 { (crc << 1) ^ 0x07 } else { crc << 1 })
+            });
+            u32::from(crc) % base
+        }
+    };
+    Some(alphabet[value as usize])
+}
+
+/// 批量生成设备 PIN 码,可以排除容易被猜到的 PIN
+///
+/// 同一批中的 PIN 互不相同
+///
+/// ```
+/// use random_generator_core::{PinSpec, Seed};
+///
+/// let spec = PinSpec { length: 4, ..Default::default() };
+/// let pins = spec.generate(100, Seed::from([5; 32]))?;
+/// assert!(pins.iter().all(|pin| pin.len() == 4 && spec.is_allowed(pin)));
+/// assert!(!spec.is_allowed("1984") && !spec.is_allowed("7771") && !spec.is_allowed("0456"));
+/// # Ok::<(), random_generator_core::RandomGeneratorError>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PinSpec {
+    /// 位数,4 到 12
+    pub length: usize,
+    /// 不允许同一数字连续出现三次,如 `777`
+    pub no_repeats: bool,
+    /// 不允许任意连续四位是 1900 到 2099 之间的年份
+    pub no_years: bool,
+    /// 不允许三位连续递增或递减,如 `345`、`987`
+    pub no_sequences: bool,
+}
+
+impl Default for PinSpec {
+    fn default() -> Self {
+        PinSpec { length: 6, no_repeats: true, no_years: true, no_sequences: true }
+    }
+}
+
+impl PinSpec {
+    /// PIN 允许的位数
+    pub const LENGTHS: std::ops::RangeInclusive<usize> = 4..=12;
+
+    /// 用种子生成 `count` 个互不相同、符合规则的 PIN
+    pub fn generate(&self, count: usize, seed: Seed) -> Result<Vec<String>, RandomGeneratorError> {
+        if !Self::LENGTHS.contains(&self.length) {
+            return Err(RandomGeneratorError::InvalidCode(format!(
+                "a PIN has {} to {} digits",
+                Self::LENGTHS.start(),
+                Self::LENGTHS.end()
+            )));
+        }
+        let combinations = 10u128.pow(self.length as u32);
+        if count as u128 > combinations {
+            return Err(RandomGeneratorError::InvalidCode(format!(
+                "only {} different PINs of {} digits are possible",
+                combinations, self.length
+            )));
+        }
+
+        let mut rng = seed.rng();
+        let mut seen = HashSet::with_capacity(count);
+        let mut pins = Vec::with_capacity(count);
+        let mut attempts = count.saturating_mul(UNIQUE_ATTEMPTS).max(1000);
+        while pins.len() < count {
+            if attempts == 0 {
+                return Err(RandomGeneratorError::InvalidCode(format!(
+                    "could not find {} different PINs that follow the rules",
+                    count
+                )));
+            }
+            attempts -= 1;
+            let pin: String = (0..self.length).map(|_| char::from(b'0' + rng.gen_range(0..10u8))).collect();
+            if self.is_allowed(&pin) && seen.insert(pin.clone()) {
+                pins.push(pin);
+            }
+        }
+        Ok(pins)
+    }
+
+    /// PIN 是否只有数字、位数正确并符合所有选中的规则
+    pub fn is_allowed(&self, pin: &str) -> bool {
+        let digits: Vec<i32> = pin.bytes().map(|b| i32::from(b) - i32::from(b'0')).collect();
+        if digits.len() != self.length || digits.iter().any(|d| !(0..=9).contains(d)) {
+            return false;
+        }
+        let repeat = |w: &[i32]| w[0] == w[1] && w[1] == w[2];
+        let sequence = |w: &[i32]| {
+            let step = w[1] - w[0];
+            step.abs() == 1 && w[2] - w[1] == step
+        };
+        let year = |w: &[i32]| (1900..=2099).contains(&w.iter().fold(0, |year, d| year * 10 + d));
+        !(self.no_repeats && digits.windows(3).any(repeat)
+            || self.no_sequences && digits.windows(3).any(sequence)
+            || self.no_years && digits.windows(4).any(year))
+    }
+}
@@ -0,0 +1,176 @@
+use rand::{Rng, RngCore};
+use std::fmt;
+
+use crate::faker::csv_cell;
+use crate::{Distribution, RandomGeneratorError, Seed};
+
+/// 分布的值最多保留的小数位数
+const MAX_DECIMALS: usize = 15;
+
+/// 一列的值从哪里来
+#[derive(Debug, Clone, PartialEq)]
+pub enum ColumnValues {
+    /// `lower` 到 `upper`(含)之间的整数,机会相同
+    Range(i64, i64),
+    /// 从分布抽的值,`decimals` 为保留的小数位数,`None` 为不舍入
+    Distribution {
+        /// 分布
+        distribution: Distribution,
+        /// 小数位数
+        decimals: Option<usize>,
+    },
+    /// 从这些文本中任选一个,机会相同
+    Choice(Vec<String>),
+}
+
+/// 表中的一列:列名和它的值
+#[derive(Debug, Clone, PartialEq)]
+pub struct Column {
+    /// 列名,也是 CSV 的表头
+    pub name: String,
+    /// 值从哪里来
+    pub values: ColumnValues,
+}
+
+/// 多列测试数据:每列有自己的范围、分布或候选项,各列、各行相互独立
+///
+/// 每行写一列,形如 `名字: 值`,值可以是
+///
+/// - 整数范围 `1..6`(含两端)
+/// - 分布 `normal(50, 10)`,可以加 `round 1` 保留 1 位小数
+/// - 逗号分隔的候选项 `red, green, blue`
+///
+/// 空行和 `#` 开头的行会被跳过
+///
+/// ```
+/// use random_generator_core::{ColumnSchema, Seed};
+///
+/// let schema = ColumnSchema::parse("die: 1..6\nscore: normal(50, 10) round 1\ncolour: red, green, blue")?;
+/// let rows = schema.generate(5, Seed::from([6; 32]))?;
+/// assert_eq!(rows.len(), 5);
+/// assert!(["red", "green", "blue"].contains(&rows[0][2].as_str()));
+/// assert!(schema.to_csv(&rows).starts_with("die,score,colour\n"));
+/// # Ok::<(), random_generator_core::RandomGeneratorError>(())
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnSchema {
+    /// 按输出顺序排列的列
+    pub columns: Vec<Column>,
+}
+
+impl ColumnValues {
+    /// 例如 `1..6`、`normal(50, 10) round 1`、`red, green, blue`
+    fn parse(text: &str) -> Result<Self, String> {
+        let text = text.trim();
+        if let Some((lower, upper)) = text.split_once("..") {
+            let number = |value: &str| value.trim().parse::<i64>().map_err(|_| format!("'{}' is not a whole number", value.trim()));
+            let (lower, upper) = (number(lower)?, number(upper)?);
+            if lower > upper {
+                return Err(format!("{}..{} goes backwards", lower, upper));
+            }
+            return Ok(ColumnValues::Range(lower, upper));
+        }
+        // 有括号就是分布,写错时报错而不是当作候选项
+        if text.contains('(') {
+            let (distribution, decimals) = match text.rsplit_once(')') {
+                Some((distribution, rest)) if !rest.trim().is_empty() => {
+                    let decimals = rest
+                        .trim()
+                        .strip_prefix("round")
+                        .and_then(|decimals| decimals.trim().parse::<usize>().ok())
+                        .filter(|&decimals| decimals <= MAX_DECIMALS)
+                        .ok_or_else(|| format!("write 'round N' with N up to {} after the distribution, not '{}'", MAX_DECIMALS, rest.trim()))?;
+                    (format!("{})", distribution), Some(decimals))
+                }
+                _ => (text.to_string(), None),
+            };
+            let distribution = distribution.parse::<Distribution>().map_err(|e| match e {
+                RandomGeneratorError::InvalidDistribution(message) => message,
+                e => e.to_string(),
+            })?;
+            return Ok(ColumnValues::Distribution { distribution, decimals });
+        }
+        let choices: Vec<String> = text.split(',').map(str::trim).filter(|choice| !choice.is_empty()).map(String::from).collect();
+        if choices.is_empty() {
+            return Err("give a range like 1..6, a distribution like normal(50, 10) or a list like red, green, blue".to_string());
+        }
+        Ok(ColumnValues::Choice(choices))
+    }
+
+    /// 抽一个值,写成文本
+    fn draw(&self, rng: &mut dyn RngCore) -> String {
+        match self {
+            ColumnValues::Range(lower, upper) => rng.gen_range(*lower..=*upper).to_string(),
+            ColumnValues::Distribution { distribution, decimals } => {
+                let value = distribution.sample(rng);
+                match decimals {
+                    Some(decimals) => format!("{:.*}", decimals, value),
+                    None => value.to_string(),
+                }
+            }
+            ColumnValues::Choice(choices) => choices[rng.gen_range(0..choices.len())].clone(),
+        }
+    }
+}
+
+impl fmt::Display for ColumnValues {
+    /// 写回 [`ColumnSchema::parse`] 能读的形式
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ColumnValues::Range(lower, upper) => write!(f, "{}..{}", lower, upper),
+            ColumnValues::Distribution { distribution, decimals: None } => write!(f, "{}", distribution),
+            ColumnValues::Distribution { distribution, decimals: Some(decimals) } => write!(f, "{} round {}", distribution, decimals),
+            ColumnValues::Choice(choices) => write!(f, "{}", choices.join(", ")),
+        }
+    }
+}
+
+impl ColumnSchema {
+    /// 由文本创建,每行一列;出错时指出是第几行
+    pub fn parse(text: &str) -> Result<Self, RandomGeneratorError> {
+        let mut columns: Vec<Column> = Vec::new();
+        for (index, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let invalid = |message: String| RandomGeneratorError::InvalidColumns { line: index + 1, message };
+            let (name, values) = line.split_once(':').ok_or_else(|| invalid("write a name, a ':' and the values, like die: 1..6".to_string()))?;
+            let name = name.trim();
+            if name.is_empty() {
+                return Err(invalid("the column needs a name before the ':'".to_string()));
+            }
+            if columns.iter().any(|column| column.name == name) {
+                return Err(invalid(format!("there is already a column named {}", name)));
+            }
+            let values = ColumnValues::parse(values).map_err(invalid)?;
+            columns.push(Column { name: name.to_string(), values });
+        }
+        if columns.is_empty() {
+            return Err(RandomGeneratorError::InvalidColumns { line: 0, message: "write one column per line, like die: 1..6".to_string() });
+        }
+        Ok(ColumnSchema { columns })
+    }
+
+    /// 用种子生成 `count` 行,每行的值与 `columns` 一一对应
+    pub fn generate(&self, count: usize, seed: Seed) -> Result<Vec<Vec<String>>, RandomGeneratorError> {
+        if self.columns.is_empty() {
+            return Err(RandomGeneratorError::InvalidColumns { line: 0, message: "add at least one column".to_string() });
+        }
+        let mut rng = seed.rng();
+        Ok((0..count).map(|_| self.columns.iter().map(|column| column.values.draw(&mut rng)).collect()).collect())
+    }
+
+    /// 带表头的 CSV
+    pub fn to_csv(&self, rows: &[Vec<String>]) -> String {
+        let header: Vec<String> = self.columns.iter().map(|column| csv_cell(&column.name)).collect();
+        let mut csv = header.join(",");
+        csv.push('\n');
+        for row in rows {
+            let cells: Vec<String> = row.iter().map(|cell| csv_cell(cell)).collect();
+            csv.push_str(&cells.join(","));
+            csv.push('\n');
+        }
+        csv
+    }
+}
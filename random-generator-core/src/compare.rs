@@ -0,0 +1,67 @@
+use std::collections::HashSet;
+
+use crate::Number;
+
+/// 两组结果的比较:两组都有的值、只在其中一组的值,例如核对两轮抽奖有没有重复中奖的
+///
+/// 每个值只列一次,按在所在那组中第一次出现的先后排列
+///
+/// ```
+/// use random_generator_core::Comparison;
+///
+/// let rounds = Comparison::<i64>::new(&[4, 8, 15, 8], &[15, 16, 4]);
+/// assert_eq!(rounds.both, [4, 15]);
+/// assert_eq!(rounds.only_first, [8]);
+/// assert_eq!(rounds.only_second, [16]);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comparison<T: Number> {
+    /// 两组都有的值,按第一组中的先后
+    pub both: Vec<T>,
+    /// 只在第一组的值
+    pub only_first: Vec<T>,
+    /// 只在第二组的值
+    pub only_second: Vec<T>,
+}
+
+impl<T: Number> Comparison<T> {
+    /// 比较两组值
+    pub fn new(first: &[T], second: &[T]) -> Self {
+        let first_keys: HashSet<u128> = first.iter().map(|value| value.key()).collect();
+        let second_keys: HashSet<u128> = second.iter().map(|value| value.key()).collect();
+        let mut comparison = Comparison { both: Vec::new(), only_first: Vec::new(), only_second: Vec::new() };
+        let mut seen = HashSet::new();
+        for &value in first {
+            if !seen.insert(value.key()) {
+                continue;
+            }
+            match second_keys.contains(&value.key()) {
+                true => comparison.both.push(value),
+                false => comparison.only_first.push(value),
+            }
+        }
+        for &value in second {
+            if !first_keys.contains(&value.key()) && seen.insert(value.key()) {
+                comparison.only_second.push(value);
+            }
+        }
+        comparison
+    }
+
+    /// 两组的值完全相同(不计重复和顺序)
+    pub fn is_same(&self) -> bool {
+        self.only_first.is_empty() && self.only_second.is_empty()
+    }
+
+    /// 文字报告,三段各占一行;`first` 和 `second` 是两组的名字
+    pub fn to_text(&self, first: &str, second: &str) -> String {
+        let line = |title: String, values: &[T]| {
+            let values: Vec<String> = values.iter().map(T::to_string).collect();
+            format!("{} ({}): {}\n", title, values.len(), values.join(", "))
+        };
+        let mut text = line("In both".to_string(), &self.both);
+        text.push_str(&line(format!("Only in {}", first), &self.only_first));
+        text.push_str(&line(format!("Only in {}", second), &self.only_second));
+        text
+    }
+}
@@ -0,0 +1,122 @@
+use rand::{Rng, RngCore};
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use crate::{Number, Progress, RandomGeneratorError, PROGRESS_INTERVAL};
+
+/// 先随机抽这么多次,都抽到冷却中的值时再逐个列出可以抽的值
+const REJECTION_TRIES: usize = 64;
+
+/// 冷却:抽到的值在之后的若干次抽取中不会再被抽到
+///
+/// 放在 [`Config::cooldown`](crate::Config::cooldown) 中,与洗牌袋一样,生成器每次生成后把最近抽到的值写回配置,
+/// 因此冷却跨越多次生成。例如冷却 2 次时,一个人被点到后,之后的两次都不会再抽到这个人,
+/// 长期看每个人的机会仍然相同
+///
+/// ```
+/// use random_generator_core::{Cooldown, GeneratorConfig, RandomGenerator};
+///
+/// let mut generator = RandomGenerator::with_config(GeneratorConfig {
+///     lower_bound: 1,
+///     upper_bound: 3,
+///     allow_duplicates: true,
+///     num_to_generate: 30,
+///     cooldown: Some(Cooldown::new(1)),
+///     ..Default::default()
+/// })?;
+/// generator.generate_numbers()?;
+/// assert!(generator.get_numbers().windows(2).all(|pair| pair[0] != pair[1]));
+/// # Ok::<(), random_generator_core::RandomGeneratorError>(())
+/// ```
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Cooldown<T: Number> {
+    draws: usize,
+    /// 最近抽到的值,最早的在前,最多 `draws` 个
+    recent: VecDeque<T>,
+}
+
+impl<T: Number> Cooldown<T> {
+    /// 抽到的值在之后 `draws` 次抽取中不会再出现
+    pub fn new(draws: usize) -> Self {
+        Self { draws, recent: VecDeque::with_capacity(draws) }
+    }
+
+    /// 冷却的次数
+    pub fn draws(&self) -> usize {
+        self.draws
+    }
+
+    /// 改变冷却的次数,只保留最近抽到的那些值
+    pub fn set_draws(&mut self, draws: usize) {
+        self.draws = draws;
+        while self.recent.len() > draws {
+            self.recent.pop_front();
+        }
+    }
+
+    /// 正在冷却的值,最早抽到的在前
+    pub fn recent(&self) -> impl Iterator<Item = T> + '_ {
+        self.recent.iter().copied()
+    }
+
+    /// 结束所有冷却
+    pub fn clear(&mut self) {
+        self.recent.clear();
+    }
+
+    /// 从 `pool` 中抽取 `count` 个数字,跳过正在冷却的值
+    pub(crate) fn draw(
+        &mut self,
+        pool: Vec<T>,
+        count: usize,
+        allow_duplicates: bool,
+        rng: &mut dyn RngCore,
+        progress: &mut Progress<'_>,
+    ) -> Result<Vec<T>, RandomGeneratorError> {
+        if pool.is_empty() {
+            return Err(RandomGeneratorError::EmptyList);
+        }
+        // 冷却中的值可能重复出现在窗口里,按次数记
+        let mut resting: HashMap<u128, usize> = HashMap::with_capacity(self.draws);
+        for value in &self.recent {
+            *resting.entry(value.key()).or_insert(0) += 1;
+        }
+        let mut batch = HashSet::new();
+        let mut numbers = Vec::new();
+        for i in 0..count {
+            if i.is_multiple_of(PROGRESS_INTERVAL) {
+                progress.checkpoint(i, count)?;
+            }
+            let excluded = |value: T| resting.contains_key(&value.key()) || batch.contains(&value.key());
+            let value = match (0..REJECTION_TRIES).map(|_| pool[rng.gen_range(0..pool.len())]).find(|&value| !excluded(value)) {
+                Some(value) => value,
+                None => {
+                    let candidates: Vec<T> = pool.iter().copied().filter(|&value| !excluded(value)).collect();
+                    if candidates.is_empty() {
+                        return Err(RandomGeneratorError::InvalidDraw("every value is cooling down".to_string()));
+                    }
+                    candidates[rng.gen_range(0..candidates.len())]
+                }
+            };
+
+            numbers.push(value);
+            if !allow_duplicates {
+                batch.insert(value.key());
+            }
+            if self.draws > 0 {
+                self.recent.push_back(value);
+                *resting.entry(value.key()).or_insert(0) += 1;
+                if let Some(expired) = (self.recent.len() > self.draws).then(|| self.recent.pop_front()).flatten() {
+                    let key = expired.key();
+                    match resting.get_mut(&key) {
+                        Some(times) if *times > 1 => *times -= 1,
+                        _ => {
+                            resting.remove(&key);
+                        }
+                    }
+                }
+            }
+        }
+        Ok(numbers)
+    }
+}
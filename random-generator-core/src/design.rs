@@ -0,0 +1,276 @@
+use rand::seq::SliceRandom;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::atomic::AtomicBool;
+
+use crate::matching::check_names;
+use crate::{Progress, RandomGeneratorError, Seed, PROGRESS_INTERVAL};
+
+/// 最多的处理数
+pub const MAX_TREATMENTS: usize = 1000;
+
+/// 一个设计最多的格数
+pub const MAX_CELLS: usize = 1_000_000;
+
+/// 实验设计的种类
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DesignKind {
+    /// 拉丁方:N 行 N 列,每个处理在每行、每列各出现一次
+    #[default]
+    LatinSquare,
+    /// Williams 平衡拉丁方:另外每个处理紧跟在其他每个处理之后的次数相同,
+    /// 用来平衡顺序的残留效应;处理数为奇数时需要 2N 行
+    Balanced,
+    /// 随机完全区组:每个区组包含所有处理,各区组内的顺序独立随机
+    Blocks,
+}
+
+impl DesignKind {
+    /// 所有的种类
+    pub const ALL: [DesignKind; 3] = [DesignKind::LatinSquare, DesignKind::Balanced, DesignKind::Blocks];
+
+    /// 每行的名字,例如 `Row`、`Block`
+    fn row_name(self) -> &'static str {
+        match self {
+            DesignKind::LatinSquare | DesignKind::Balanced => "Row",
+            DesignKind::Blocks => "Block",
+        }
+    }
+}
+
+impl fmt::Display for DesignKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DesignKind::LatinSquare => write!(f, "Latin square"),
+            DesignKind::Balanced => write!(f, "Balanced Latin square"),
+            DesignKind::Blocks => write!(f, "Randomized blocks"),
+        }
+    }
+}
+
+impl FromStr for DesignKind {
+    type Err = RandomGeneratorError;
+
+    /// 接受 `latin`、`balanced`(或 `williams`)、`blocks` 和显示的名字,不区分大小写
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.trim().to_lowercase();
+        match name.as_str() {
+            "latin" => Ok(DesignKind::LatinSquare),
+            "balanced" | "williams" => Ok(DesignKind::Balanced),
+            "blocks" | "rcbd" => Ok(DesignKind::Blocks),
+            _ => DesignKind::ALL
+                .into_iter()
+                .find(|kind| kind.to_string().to_lowercase() == name)
+                .ok_or_else(|| RandomGeneratorError::InvalidDesign(format!("unknown design '{}'", s.trim()))),
+        }
+    }
+}
+
+/// 要比较的一组处理,用来排出交叉实验或区组实验的随机顺序
+///
+/// 拉丁方和平衡拉丁方的每一行是一组受试者依次接受的处理,每一列是一个时段;
+/// 随机区组的每一行是一个区组。处理的名字不能为空或重复
+///
+/// ```
+/// use random_generator_core::{DesignKind, ExperimentDesign, Seed};
+///
+/// let design = ExperimentDesign::parse("4")?;
+/// assert_eq!(design.treatments(), ["A", "B", "C", "D"]);
+/// let square = design.generate(DesignKind::LatinSquare, 0, Seed::from([5; 32]))?;
+/// assert_eq!(square.rows.len(), 4);
+/// for row in &square.rows {
+///     let mut sorted = row.clone();
+///     sorted.sort();
+///     assert_eq!(sorted, ["A", "B", "C", "D"]);
+/// }
+///
+/// let blocks = ExperimentDesign::parse("Placebo, Low, High")?.generate(DesignKind::Blocks, 5, Seed::from([5; 32]))?;
+/// assert_eq!(blocks.rows.len(), 5);
+/// # Ok::<(), random_generator_core::RandomGeneratorError>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExperimentDesign {
+    treatments: Vec<String>,
+}
+
+/// 排好的设计,每行按时段或区组内的位置排列
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DesignTable {
+    /// 设计的种类
+    pub kind: DesignKind,
+    /// 每行依次的处理
+    pub rows: Vec<Vec<String>>,
+}
+
+impl ExperimentDesign {
+    /// 至少 2 个、最多 [`MAX_TREATMENTS`] 个处理
+    pub fn new(treatments: Vec<String>) -> Result<Self, RandomGeneratorError> {
+        check_names(&treatments).map_err(|e| match e {
+            RandomGeneratorError::InvalidDraw(message) => RandomGeneratorError::InvalidDesign(message),
+            other => other,
+        })?;
+        if !(2..=MAX_TREATMENTS).contains(&treatments.len()) {
+            return Err(RandomGeneratorError::InvalidDesign(format!("a design needs 2 to {} treatments", MAX_TREATMENTS)));
+        }
+        Ok(ExperimentDesign { treatments })
+    }
+
+    /// 名为 A、B、C …(超过 26 个时接着是 AA、AB …)的 `order` 个处理
+    pub fn of_order(order: usize) -> Result<Self, RandomGeneratorError> {
+        if order > MAX_TREATMENTS {
+            return Err(RandomGeneratorError::InvalidDesign(format!("a design needs 2 to {} treatments", MAX_TREATMENTS)));
+        }
+        Self::new((0..order).map(letters).collect())
+    }
+
+    /// 一个数字表示处理的个数,否则是用逗号或换行分隔的处理名,跳过空白
+    pub fn parse(text: &str) -> Result<Self, RandomGeneratorError> {
+        if let Ok(order) = text.trim().parse() {
+            return Self::of_order(order);
+        }
+        Self::new(text.split([',', '\n']).map(str::trim).filter(|name| !name.is_empty()).map(String::from).collect())
+    }
+
+    /// 处理的名字
+    pub fn treatments(&self) -> &[String] {
+        &self.treatments
+    }
+
+    /// 设计有几行;`blocks` 只用于随机区组
+    pub fn row_count(&self, kind: DesignKind, blocks: usize) -> usize {
+        let n = self.treatments.len();
+        match kind {
+            DesignKind::LatinSquare => n,
+            DesignKind::Balanced if n % 2 == 1 => 2 * n,
+            DesignKind::Balanced => n,
+            DesignKind::Blocks => blocks,
+        }
+    }
+
+    /// 用种子排出设计;`blocks` 是随机区组的区组数,其他种类忽略
+    ///
+    /// 拉丁方由循环方阵随机置换行、列和处理得到;平衡拉丁方由 Williams 设计随机置换行和处理得到,
+    /// 列的顺序不能打乱,否则残留效应不再平衡
+    pub fn generate(&self, kind: DesignKind, blocks: usize, seed: Seed) -> Result<DesignTable, RandomGeneratorError> {
+        self.generate_with_progress(kind, blocks, seed, &AtomicBool::new(false), &mut |_, _| {})
+    }
+
+    /// 用种子排出设计,并以 `(已完成的格数, 总数)` 回报进度;排出方阵和填入处理名各算一遍格数
+    ///
+    /// 每次回报前检查 `cancel`,被置位时返回 `Cancelled`
+    pub fn generate_with_progress(
+        &self,
+        kind: DesignKind,
+        blocks: usize,
+        seed: Seed,
+        cancel: &AtomicBool,
+        on_progress: &mut dyn FnMut(usize, usize),
+    ) -> Result<DesignTable, RandomGeneratorError> {
+        let n = self.treatments.len();
+        let rows = self.row_count(kind, blocks);
+        if kind == DesignKind::Blocks && rows == 0 {
+            return Err(RandomGeneratorError::InvalidDesign("randomized blocks need at least one block".to_string()));
+        }
+        if rows.checked_mul(n).is_none_or(|cells| cells > MAX_CELLS) {
+            return Err(RandomGeneratorError::InvalidDesign(format!("a design can have at most {} cells", MAX_CELLS)));
+        }
+
+        let mut progress = Progress { cancel, on_progress };
+        let cells = rows * n;
+        let every = (PROGRESS_INTERVAL / n).max(1);
+        let mut rng = seed.rng();
+        let mut symbols: Vec<usize> = (0..n).collect();
+        symbols.shuffle(&mut rng);
+        let mut grid: Vec<Vec<usize>> = match kind {
+            DesignKind::LatinSquare => {
+                let mut columns: Vec<usize> = (0..n).collect();
+                columns.shuffle(&mut rng);
+                (0..n).map(|i| columns.iter().map(|&j| (i + j) % n).collect()).collect()
+            }
+            DesignKind::Balanced => {
+                // 第一行是 0, 1, n-1, 2, n-2 …,其余各行依次加 1;奇数时再加上每行倒过来的一份
+                let first: Vec<usize> = (0..n).map(|j| if j % 2 == 1 { j.div_ceil(2) } else { (n - j / 2) % n }).collect();
+                let mut grid: Vec<Vec<usize>> = (0..n).map(|i| first.iter().map(|&t| (t + i) % n).collect()).collect();
+                if n % 2 == 1 {
+                    let reversed: Vec<Vec<usize>> = grid.iter().map(|row| row.iter().rev().copied().collect()).collect();
+                    grid.extend(reversed);
+                }
+                grid
+            }
+            DesignKind::Blocks => {
+                let mut grid = Vec::with_capacity(blocks);
+                for i in 0..blocks {
+                    if i > 0 && i.is_multiple_of(every) {
+                        progress.checkpoint(i * n, 2 * cells)?;
+                    }
+                    let mut order: Vec<usize> = (0..n).collect();
+                    order.shuffle(&mut rng);
+                    grid.push(order);
+                }
+                grid
+            }
+        };
+        grid.shuffle(&mut rng);
+        progress.checkpoint(cells, 2 * cells)?;
+        let mut named = Vec::with_capacity(rows);
+        for (i, row) in grid.into_iter().enumerate() {
+            if i > 0 && i.is_multiple_of(every) {
+                progress.checkpoint(cells + i * n, 2 * cells)?;
+            }
+            named.push(row.into_iter().map(|t| self.treatments[symbols[t]].clone()).collect());
+        }
+        Ok(DesignTable { kind, rows: named })
+    }
+}
+
+/// 第 `index` 个处理的默认名字:A … Z, AA, AB …
+fn letters(index: usize) -> String {
+    let mut name = Vec::new();
+    let mut rest = index + 1;
+    while rest > 0 {
+        rest -= 1;
+        name.push(b'A' + (rest % 26) as u8);
+        rest /= 26;
+    }
+    name.reverse();
+    String::from_utf8(name).unwrap_or_default()
+}
+
+impl DesignTable {
+    /// 每行一行,例如 `Row 1  B  D  A  C`,各列对齐
+    pub fn to_text(&self) -> String {
+        let labels: Vec<String> = (1..=self.rows.len()).map(|i| format!("{} {}", self.kind.row_name(), i)).collect();
+        let label_width = labels.iter().map(|label| label.chars().count()).max().unwrap_or(0);
+        let width = self.rows.iter().flatten().map(|cell| cell.chars().count()).max().unwrap_or(0);
+        let mut text = String::new();
+        for (label, row) in labels.iter().zip(&self.rows) {
+            let cells: Vec<String> = row.iter().map(|cell| format!("{:<width$}", cell, width = width)).collect();
+            let line = format!("{:<label_width$}  {}", label, cells.join("  "), label_width = label_width);
+            text.push_str(line.trim_end());
+            text.push('\n');
+        }
+        text
+    }
+
+    /// 每行一行的 CSV,表头是 `row` 或 `block` 加上位置 1 到 N
+    pub fn to_csv(&self) -> String {
+        let cell = |text: &str| {
+            if text.contains([',', '"', '\n', '\r']) {
+                format!("\"{}\"", text.replace('"', "\"\""))
+            } else {
+                text.to_string()
+            }
+        };
+        let columns = self.rows.first().map_or(0, Vec::len);
+        let mut csv = self.kind.row_name().to_lowercase();
+        for position in 1..=columns {
+            csv.push_str(&format!(",{}", position));
+        }
+        csv.push('\n');
+        for (i, row) in self.rows.iter().enumerate() {
+            let cells: Vec<String> = row.iter().map(|text| cell(text)).collect();
+            csv.push_str(&format!("{},{}\n", i + 1, cells.join(",")));
+        }
+        csv
+    }
+}
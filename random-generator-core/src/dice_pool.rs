@@ -0,0 +1,170 @@
+use rand::Rng;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::script::MAX_DICE;
+use crate::{RandomGeneratorError, Seed};
+
+/// 骰池中的一颗骰子
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PoolDie {
+    /// 点数
+    pub value: u32,
+    /// 是否是前一颗骰子爆骰后追加掷的
+    pub bonus: bool,
+}
+
+/// 掷一次骰池的结果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PoolRoll {
+    /// 按掷出的顺序,追加的骰子紧跟在引起它的骰子后面
+    pub dice: Vec<PoolDie>,
+    /// 达到成功线的骰子数
+    pub successes: usize,
+}
+
+/// 桌游的骰池:掷一把骰子,数有几颗达到成功线,例如 d10 中 8 点及以上算成功
+///
+/// 可以爆骰:点数达到爆骰线的骰子再追加掷一颗,追加的骰子同样可以成功和爆骰。
+/// 写法如 `6d10>=8`,末尾加 `!` 表示掷出最大点数时爆骰,`!9` 表示 9 点及以上爆骰
+///
+/// ```
+/// use random_generator_core::{DicePool, Seed};
+///
+/// let pool: DicePool = "6d10>=8!".parse()?;
+/// assert_eq!(pool, DicePool { dice: 6, sides: 10, target: 8, explode: Some(10) });
+/// let rolls = pool.rolls(3, Seed::from([2; 32]))?;
+/// for roll in &rolls {
+///     assert!(roll.dice.iter().filter(|die| !die.bonus).count() == 6);
+///     assert_eq!(roll.successes, roll.dice.iter().filter(|die| die.value >= 8).count());
+/// }
+/// # Ok::<(), random_generator_core::RandomGeneratorError>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DicePool {
+    /// 骰子数
+    pub dice: u32,
+    /// 每颗骰子的面数
+    pub sides: u32,
+    /// 点数达到这个值算一次成功
+    pub target: u32,
+    /// 点数达到这个值时追加掷一颗;`None` 表示不爆骰
+    pub explode: Option<u32>,
+}
+
+impl Default for DicePool {
+    fn default() -> Self {
+        DicePool { dice: 5, sides: 10, target: 8, explode: None }
+    }
+}
+
+impl DicePool {
+    /// 检查骰子数、面数、成功线和爆骰线
+    pub fn validate(&self) -> Result<(), RandomGeneratorError> {
+        if !(1..=MAX_DICE).contains(&self.dice) {
+            return Err(RandomGeneratorError::InvalidGame(format!("roll 1 to {} dice", MAX_DICE)));
+        }
+        if self.sides < 2 {
+            return Err(RandomGeneratorError::InvalidGame("a die needs at least 2 sides".to_string()));
+        }
+        if !(1..=self.sides).contains(&self.target) {
+            return Err(RandomGeneratorError::InvalidGame(format!("the success target must be from 1 to {}", self.sides)));
+        }
+        // 每颗都爆骰就永远掷不完
+        if self.explode.is_some_and(|explode| !(2..=self.sides).contains(&explode)) {
+            return Err(RandomGeneratorError::InvalidGame(format!("dice can explode on 2 to {}", self.sides)));
+        }
+        Ok(())
+    }
+
+    /// 掷一次;追加的骰子连同原来的最多 1000 颗,之后不再爆骰
+    pub fn roll(&self, rng: &mut impl Rng) -> PoolRoll {
+        let mut dice = Vec::with_capacity(self.dice as usize);
+        for i in 0..self.dice {
+            let mut bonus = false;
+            loop {
+                let value = rng.gen_range(1..=self.sides);
+                dice.push(PoolDie { value, bonus });
+                // 还没掷的骰子也要留出位置
+                let room = dice.len() + ((self.dice - i - 1) as usize) < MAX_DICE as usize;
+                if !(room && self.explode.is_some_and(|explode| value >= explode)) {
+                    break;
+                }
+                bonus = true;
+            }
+        }
+        let successes = dice.iter().filter(|die| die.value >= self.target).count();
+        PoolRoll { dice, successes }
+    }
+
+    /// 用种子掷 `count` 次
+    pub fn rolls(&self, count: usize, seed: Seed) -> Result<Vec<PoolRoll>, RandomGeneratorError> {
+        self.validate()?;
+        let mut rng = seed.rng();
+        Ok((0..count).map(|_| self.roll(&mut rng)).collect())
+    }
+
+    /// 每次一行:编号、各颗骰子和成功数,追加的骰子前面加 `+`
+    pub fn to_text(&self, rolls: &[PoolRoll]) -> String {
+        let number_width = rolls.len().to_string().len();
+        rolls
+            .iter()
+            .enumerate()
+            .map(|(i, roll)| {
+                let word = if roll.successes == 1 { "success" } else { "successes" };
+                format!("{:>number_width$}. {}  → {} {}\n", i + 1, dice_text(roll), roll.successes, word, number_width = number_width)
+            })
+            .collect()
+    }
+
+    /// 每次一行的 CSV,带表头 `roll,dice,successes`;`dice` 是以空格分隔的点数,追加的骰子前面加 `+`
+    pub fn to_csv(&self, rolls: &[PoolRoll]) -> String {
+        let mut csv = String::from("roll,dice,successes\n");
+        for (i, roll) in rolls.iter().enumerate() {
+            csv.push_str(&format!("{},{},{}\n", i + 1, dice_text(roll), roll.successes));
+        }
+        csv
+    }
+}
+
+/// 以空格分隔的点数,追加的骰子前面加 `+`
+fn dice_text(roll: &PoolRoll) -> String {
+    let dice: Vec<String> = roll.dice.iter().map(|die| format!("{}{}", if die.bonus { "+" } else { "" }, die.value)).collect();
+    dice.join(" ")
+}
+
+impl fmt::Display for DicePool {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}d{}>={}", self.dice, self.sides, self.target)?;
+        match self.explode {
+            Some(explode) if explode == self.sides => write!(f, "!"),
+            Some(explode) => write!(f, "!{}", explode),
+            None => Ok(()),
+        }
+    }
+}
+
+impl FromStr for DicePool {
+    type Err = RandomGeneratorError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let text = s.trim();
+        let invalid = || RandomGeneratorError::InvalidGame(format!("'{}' is not a pool like 6d10>=8 or 6d10>=8!", text));
+        let (pool, explode) = match text.split_once('!') {
+            Some((pool, explode)) => (pool, Some(explode.trim())),
+            None => (text, None),
+        };
+        let (dice, target) = pool.split_once(">=").ok_or_else(invalid)?;
+        let (count, sides) = dice.trim().split_once(['d', 'D']).ok_or_else(invalid)?;
+        let count = if count.is_empty() { 1 } else { count.parse().map_err(|_| invalid())? };
+        let sides = sides.parse().map_err(|_| invalid())?;
+        let explode = match explode {
+            Some("") => Some(sides),
+            Some(explode) => Some(explode.parse().map_err(|_| invalid())?),
+            None => None,
+        };
+        let pool = DicePool { dice: count, sides, target: target.trim().parse().map_err(|_| invalid())?, explode };
+        pool.validate()?;
+        Ok(pool)
+    }
+}
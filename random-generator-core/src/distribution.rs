@@ -0,0 +1,1010 @@
+use rand::{Rng, RngCore};
+use std::f64::consts::{SQRT_2, TAU};
+use std::fmt;
+use std::str::FromStr;
+use std::sync::atomic::AtomicBool;
+
+use crate::bootstrap::histogram_bars;
+use crate::{Progress, RandomGeneratorError, Seed, PROGRESS_INTERVAL};
+
+/// 一次最多抽取的值数
+pub const MAX_SAMPLES: usize = 1_000_000;
+
+/// 保留区间的概率不低于这个值时直接抽取并丢弃区间外的值,平均最多抽 1 / 0.3 次;
+/// 更低时改用逆变换,抽一次就落在区间中
+const REJECTION_MASS: f64 = 0.3;
+
+/// 连续分布的种类
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DistributionKind {
+    /// 区间中的均匀分布
+    Uniform,
+    /// 正态分布
+    #[default]
+    Normal,
+    /// 0 和 1 之间的 Beta 分布,常用来模拟比率
+    Beta,
+    /// Gamma 分布,常用来模拟等待时间
+    Gamma,
+}
+
+impl DistributionKind {
+    /// 所有的种类
+    pub const ALL: [DistributionKind; 4] = [DistributionKind::Uniform, DistributionKind::Normal, DistributionKind::Beta, DistributionKind::Gamma];
+
+    /// 参数的名字,按 [`Distribution::new`] 的参数顺序
+    pub fn parameters(self) -> &'static [&'static str] {
+        match self {
+            DistributionKind::Uniform => &["Low", "High"],
+            DistributionKind::Normal => &["Mean", "SD"],
+            DistributionKind::Beta => &["Alpha", "Beta"],
+            DistributionKind::Gamma => &["Shape", "Scale"],
+        }
+    }
+
+    /// 参数的默认值
+    pub fn defaults(self) -> &'static [f64] {
+        match self {
+            DistributionKind::Uniform => &[0.0, 1.0],
+            DistributionKind::Normal => &[0.0, 1.0],
+            DistributionKind::Beta => &[2.0, 5.0],
+            DistributionKind::Gamma => &[2.0, 1.0],
+        }
+    }
+
+    /// 写成文本时的名字,例如 `normal`
+    fn name(self) -> &'static str {
+        match self {
+            DistributionKind::Uniform => "uniform",
+            DistributionKind::Normal => "normal",
+            DistributionKind::Beta => "beta",
+            DistributionKind::Gamma => "gamma",
+        }
+    }
+}
+
+impl fmt::Display for DistributionKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DistributionKind::Uniform => write!(f, "Uniform"),
+            DistributionKind::Normal => write!(f, "Normal"),
+            DistributionKind::Beta => write!(f, "Beta"),
+            DistributionKind::Gamma => write!(f, "Gamma"),
+        }
+    }
+}
+
+/// 一个连续分布及其参数
+///
+/// 写成文本是 `normal(100, 15)`、`uniform(0, 1)`、`beta(2, 5)`、`gamma(2, 1)` 这样的形式,`FromStr` 可以读回
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Distribution {
+    /// `lower` 到 `upper` 之间的均匀分布
+    Uniform {
+        /// 下限
+        lower: f64,
+        /// 上限
+        upper: f64,
+    },
+    /// 平均数为 `mean`、标准差为 `sd` 的正态分布
+    Normal {
+        /// 平均数
+        mean: f64,
+        /// 标准差
+        sd: f64,
+    },
+    /// 形状参数为 `alpha`、`beta` 的 Beta 分布,取值在 0 和 1 之间,平均数为 α / (α + β)
+    Beta {
+        /// α
+        alpha: f64,
+        /// β
+        beta: f64,
+    },
+    /// 形状为 `shape`(k)、尺度为 `scale`(θ)的 Gamma 分布,取值不小于 0,平均数为 kθ
+    Gamma {
+        /// 形状 k
+        shape: f64,
+        /// 尺度 θ
+        scale: f64,
+    },
+}
+
+impl Default for Distribution {
+    fn default() -> Self {
+        Distribution::Normal { mean: 0.0, sd: 1.0 }
+    }
+}
+
+impl Distribution {
+    /// 由种类和参数创建,参数的个数和顺序见 [`DistributionKind::parameters`]
+    pub fn new(kind: DistributionKind, parameters: &[f64]) -> Result<Self, RandomGeneratorError> {
+        let expected = kind.parameters().len();
+        if parameters.len() != expected {
+            return Err(RandomGeneratorError::InvalidDistribution(format!("{} takes {} parameters", kind.name(), expected)));
+        }
+        let distribution = match kind {
+            DistributionKind::Uniform => Distribution::Uniform { lower: parameters[0], upper: parameters[1] },
+            DistributionKind::Normal => Distribution::Normal { mean: parameters[0], sd: parameters[1] },
+            DistributionKind::Beta => Distribution::Beta { alpha: parameters[0], beta: parameters[1] },
+            DistributionKind::Gamma => Distribution::Gamma { shape: parameters[0], scale: parameters[1] },
+        };
+        distribution.validate()?;
+        Ok(distribution)
+    }
+
+    /// 分布的种类
+    pub fn kind(&self) -> DistributionKind {
+        match self {
+            Distribution::Uniform { .. } => DistributionKind::Uniform,
+            Distribution::Normal { .. } => DistributionKind::Normal,
+            Distribution::Beta { .. } => DistributionKind::Beta,
+            Distribution::Gamma { .. } => DistributionKind::Gamma,
+        }
+    }
+
+    /// 参数,顺序与 [`Distribution::new`] 相同
+    pub fn parameters(&self) -> Vec<f64> {
+        match *self {
+            Distribution::Uniform { lower, upper } => vec![lower, upper],
+            Distribution::Normal { mean, sd } => vec![mean, sd],
+            Distribution::Beta { alpha, beta } => vec![alpha, beta],
+            Distribution::Gamma { shape, scale } => vec![shape, scale],
+        }
+    }
+
+    /// 检查参数
+    pub fn validate(&self) -> Result<(), RandomGeneratorError> {
+        if !self.parameters().iter().all(|value| value.is_finite()) {
+            return Err(RandomGeneratorError::InvalidDistribution("the parameters must be numbers".to_string()));
+        }
+        match *self {
+            Distribution::Uniform { lower, upper } if !(lower < upper && (upper - lower).is_finite()) => {
+                Err(RandomGeneratorError::InvalidDistribution("a uniform distribution needs low below high".to_string()))
+            }
+            Distribution::Normal { sd, .. } if sd <= 0.0 => {
+                Err(RandomGeneratorError::InvalidDistribution("the standard deviation must be above 0".to_string()))
+            }
+            Distribution::Beta { alpha, beta } if alpha <= 0.0 || beta <= 0.0 => {
+                Err(RandomGeneratorError::InvalidDistribution("alpha and beta must be above 0".to_string()))
+            }
+            Distribution::Gamma { shape, scale } if shape <= 0.0 || scale <= 0.0 => {
+                Err(RandomGeneratorError::InvalidDistribution("the shape and the scale must be above 0".to_string()))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// 抽一个值
+    pub fn sample(&self, rng: &mut dyn RngCore) -> f64 {
+        match *self {
+            Distribution::Uniform { lower, upper } => rng.gen_range(lower..upper),
+            Distribution::Normal { mean, sd } => mean + sd * standard_normal(rng),
+            Distribution::Beta { alpha, beta } => {
+                // X / (X + Y),X、Y 分别服从 Gamma(α)、Gamma(β);在对数上计算,参数很小时不会得到 0 / 0
+                let (x, y) = (log_standard_gamma(alpha, rng), log_standard_gamma(beta, rng));
+                1.0 / (1.0 + (y - x).exp())
+            }
+            Distribution::Gamma { shape, scale } => scale * log_standard_gamma(shape, rng).exp(),
+        }
+    }
+
+    /// 概率密度,用来由累积概率求分位数
+    fn pdf(&self, x: f64) -> f64 {
+        match *self {
+            Distribution::Uniform { lower, upper } if (lower..=upper).contains(&x) => 1.0 / (upper - lower),
+            Distribution::Normal { mean, sd } => (-((x - mean) / sd).powi(2) / 2.0).exp() / (sd * TAU.sqrt()),
+            Distribution::Beta { alpha, beta } if x > 0.0 && x < 1.0 => {
+                ((alpha - 1.0) * x.ln() + (beta - 1.0) * (-x).ln_1p() - log_beta(alpha, beta)).exp()
+            }
+            Distribution::Gamma { shape, scale } if x > 0.0 => {
+                let x = x / scale;
+                ((shape - 1.0) * x.ln() - x - libm::lgamma(shape)).exp() / scale
+            }
+            _ => 0.0,
+        }
+    }
+
+    /// 累积分布函数:值不超过 `x` 的概率
+    pub fn cdf(&self, x: f64) -> f64 {
+        match *self {
+            Distribution::Uniform { lower, upper } => ((x - lower) / (upper - lower)).clamp(0.0, 1.0),
+            Distribution::Normal { mean, sd } => 0.5 * libm::erfc(-(x - mean) / (sd * SQRT_2)),
+            Distribution::Beta { .. } | Distribution::Gamma { .. } => self.tails(x).0,
+        }
+    }
+
+    /// 值超过 `x` 的概率;在上尾比 `1 - cdf(x)` 准确
+    pub fn sf(&self, x: f64) -> f64 {
+        match *self {
+            Distribution::Uniform { lower, upper } => ((upper - x) / (upper - lower)).clamp(0.0, 1.0),
+            Distribution::Normal { mean, sd } => 0.5 * libm::erfc((x - mean) / (sd * SQRT_2)),
+            Distribution::Beta { .. } | Distribution::Gamma { .. } => self.tails(x).1,
+        }
+    }
+
+    /// 分位数:`cdf` 等于 `p` 的值,`p` 在 0 和 1 之间
+    pub fn quantile(&self, p: f64) -> f64 {
+        match *self {
+            Distribution::Uniform { lower, upper } => lower + (upper - lower) * p,
+            Distribution::Normal { mean, sd } => mean + sd * standard_normal_quantile(p),
+            Distribution::Beta { .. } | Distribution::Gamma { .. } => self.invert(p, false),
+        }
+    }
+
+    /// `sf` 等于 `q` 的值;在上尾比 `quantile(1 - q)` 准确
+    pub fn upper_quantile(&self, q: f64) -> f64 {
+        match *self {
+            Distribution::Uniform { lower, upper } => upper - (upper - lower) * q,
+            Distribution::Normal { mean, sd } => mean - sd * standard_normal_quantile(q),
+            Distribution::Beta { .. } | Distribution::Gamma { .. } => self.invert(q, true),
+        }
+    }
+
+    /// Beta 和 Gamma 分布的 `(cdf, sf)`,由正则化不完全 Beta、Gamma 函数得到,两者各自在自己的尾部准确
+    fn tails(&self, x: f64) -> (f64, f64) {
+        match *self {
+            Distribution::Beta { .. } if x <= 0.0 => (0.0, 1.0),
+            Distribution::Beta { .. } if x >= 1.0 => (1.0, 0.0),
+            Distribution::Beta { alpha, beta } => (regularized_beta(alpha, beta, x), regularized_beta(beta, alpha, 1.0 - x)),
+            Distribution::Gamma { .. } if x <= 0.0 => (0.0, 1.0),
+            Distribution::Gamma { .. } if x == f64::INFINITY => (1.0, 0.0),
+            Distribution::Gamma { shape, scale } => regularized_gamma(shape, x / scale),
+            _ => (self.cdf(x), self.sf(x)),
+        }
+    }
+
+    /// 没有分位数公式的分布:在取值范围中找 `cdf`(`upper` 时为 `sf`)等于 `p` 的值
+    ///
+    /// 用牛顿法,跳出已知的区间时改为二分,所以总会收敛
+    fn invert(&self, p: f64, upper: bool) -> f64 {
+        let (mut low, mut high) = match *self {
+            Distribution::Beta { .. } => (0.0, 1.0),
+            _ => (0.0, f64::INFINITY),
+        };
+        if p <= 0.0 {
+            return if upper { high } else { low };
+        }
+        if p >= 1.0 {
+            return if upper { low } else { high };
+        }
+        // 值越大 error 越大
+        let error = |x: f64| if upper { p - self.sf(x) } else { self.cdf(x) - p };
+        if high.is_infinite() {
+            high = 1.0;
+            while error(high) < 0.0 && high < f64::MAX / 2.0 {
+                low = high;
+                high *= 2.0;
+            }
+        }
+        let mut x = (low + high) / 2.0;
+        for _ in 0..200 {
+            let e = error(x);
+            if e == 0.0 {
+                return x;
+            }
+            if e < 0.0 {
+                low = x;
+            } else {
+                high = x;
+            }
+            let newton = x - e / self.pdf(x);
+            let next = if newton > low && newton < high { newton } else { (low + high) / 2.0 };
+            if (next - x).abs() <= 1e-15 * x.abs() || next == low || next == high {
+                return next;
+            }
+            x = next;
+        }
+        x
+    }
+}
+
+impl fmt::Display for Distribution {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let parameters: Vec<String> = self.parameters().iter().map(f64::to_string).collect();
+        write!(f, "{}({})", self.kind().name(), parameters.join(", "))
+    }
+}
+
+impl FromStr for Distribution {
+    type Err = RandomGeneratorError;
+
+    /// 例如 `normal(100, 15)`,名字不区分大小写
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let text = s.trim();
+        let invalid = || RandomGeneratorError::InvalidDistribution(format!("'{}' is not a distribution like normal(100, 15)", text));
+        let (name, rest) = text.split_once('(').ok_or_else(invalid)?;
+        let arguments = rest.strip_suffix(')').ok_or_else(invalid)?;
+        let kind = DistributionKind::ALL
+            .into_iter()
+            .find(|kind| kind.name().eq_ignore_ascii_case(name.trim()))
+            .ok_or_else(|| RandomGeneratorError::InvalidDistribution(format!("unknown distribution '{}'", name.trim())))?;
+        let parameters = arguments
+            .split(',')
+            .map(|argument| argument.trim().parse::<f64>().map_err(|_| invalid()))
+            .collect::<Result<Vec<_>, _>>()?;
+        Distribution::new(kind, &parameters)
+    }
+}
+
+/// 标准正态分布的一个值
+///
+/// Box-Muller 变换;1 - u 落在 (0, 1] 中,避免对 0 取对数
+pub(crate) fn standard_normal(rng: &mut dyn RngCore) -> f64 {
+    let (u, v) = (1.0 - rng.gen::<f64>(), rng.gen::<f64>());
+    (-2.0 * u.ln()).sqrt() * (TAU * v).cos()
+}
+
+/// 尺度为 1 的 Gamma 分布的一个值的自然对数
+///
+/// Marsaglia-Tsang 方法;形状小于 1 时抽 Gamma(k + 1) 再乘以 U^(1/k),取对数以免下溢成 0
+fn log_standard_gamma(shape: f64, rng: &mut dyn RngCore) -> f64 {
+    if shape < 1.0 {
+        let u = 1.0 - rng.gen::<f64>();
+        return log_standard_gamma(shape + 1.0, rng) + u.ln() / shape;
+    }
+    let d = shape - 1.0 / 3.0;
+    let c = 1.0 / (9.0 * d).sqrt();
+    loop {
+        let x = standard_normal(rng);
+        let v = (1.0 + c * x).powi(3);
+        if v <= 0.0 {
+            continue;
+        }
+        let u = 1.0 - rng.gen::<f64>();
+        if u.ln() < x * x / 2.0 + d - d * v + d * v.ln() {
+            return (d * v).ln();
+        }
+    }
+}
+
+/// ln B(a, b)
+fn log_beta(a: f64, b: f64) -> f64 {
+    libm::lgamma(a) + libm::lgamma(b) - libm::lgamma(a + b)
+}
+
+/// 级数和连分式最多的项数,以及停止的相对精度
+const MAX_TERMS: usize = 1000;
+const EPSILON: f64 = 1e-15;
+
+/// 正则化不完全 Gamma 函数 `(P(a, x), Q(a, x))`
+///
+/// x 小于 a + 1 时用级数求 P,否则用连分式求 Q,另一个由 1 减去得到
+fn regularized_gamma(a: f64, x: f64) -> (f64, f64) {
+    let front = (a * x.ln() - x - libm::lgamma(a)).exp();
+    if x < a + 1.0 {
+        let (mut term, mut sum) = (1.0 / a, 1.0 / a);
+        for n in 1..MAX_TERMS {
+            term *= x / (a + n as f64);
+            sum += term;
+            if term.abs() < sum.abs() * EPSILON {
+                break;
+            }
+        }
+        let p = (sum * front).min(1.0);
+        (p, 1.0 - p)
+    } else {
+        let q = (front * continued_fraction(|n| if n == 1 { 1.0 } else { -((n - 1) as f64) * ((n - 1) as f64 - a) }, |n| x + (2 * n - 1) as f64 - a)).min(1.0);
+        (1.0 - q, q)
+    }
+}
+
+/// 正则化不完全 Beta 函数 I_x(a, b)
+///
+/// x 小于 (a + 1) / (a + b + 2) 时连分式收敛得快,否则用 I_x(a, b) = 1 - I_(1-x)(b, a)
+fn regularized_beta(a: f64, b: f64, x: f64) -> f64 {
+    if x <= 0.0 {
+        return 0.0;
+    }
+    if x >= 1.0 {
+        return 1.0;
+    }
+    if x > (a + 1.0) / (a + b + 2.0) {
+        return 1.0 - regularized_beta(b, a, 1.0 - x);
+    }
+    let front = (a * x.ln() + b * (-x).ln_1p() - log_beta(a, b)).exp() / a;
+    let numerator = |n: usize| {
+        if n == 1 {
+            return 1.0;
+        }
+        let m = ((n - 1) / 2) as f64;
+        if n % 2 == 1 {
+            m * (b - m) * x / ((a + 2.0 * m - 1.0) * (a + 2.0 * m))
+        } else {
+            -(a + m) * (a + b + m) * x / ((a + 2.0 * m) * (a + 2.0 * m + 1.0))
+        }
+    };
+    (front * continued_fraction(numerator, |_| 1.0)).min(1.0)
+}
+
+/// 连分式 a1 / (b1 + a2 / (b2 + …)),用修正的 Lentz 方法求值
+fn continued_fraction(a: impl Fn(usize) -> f64, b: impl Fn(usize) -> f64) -> f64 {
+    const TINY: f64 = 1e-300;
+    let nonzero = |value: f64| if value.abs() < TINY { TINY } else { value };
+    let mut f = TINY;
+    let (mut c, mut d) = (f, 0.0);
+    for n in 1..MAX_TERMS {
+        d = 1.0 / nonzero(b(n) + a(n) * d);
+        c = nonzero(b(n) + a(n) / c);
+        let delta = c * d;
+        f *= delta;
+        if (delta - 1.0).abs() < EPSILON {
+            break;
+        }
+    }
+    f
+}
+
+/// 标准正态分布的分位数
+///
+/// Acklam 的有理函数近似(相对误差约 1e-9),再用 Halley 法修正一步到接近机器精度
+fn standard_normal_quantile(p: f64) -> f64 {
+    const A: [f64; 6] = [-3.969683028665376e1, 2.209460984245205e2, -2.759285104469687e2, 1.38357751867269e2, -3.066479806614716e1, 2.506628277459239];
+    const B: [f64; 5] = [-5.447609879822406e1, 1.615858368580409e2, -1.556989798598866e2, 6.680131188771972e1, -1.328068155288572e1];
+    const C: [f64; 6] = [-7.784894002430293e-3, -3.223964580411365e-1, -2.400758277161838, -2.549732539343734, 4.374664141464968, 2.938163982698783];
+    const D: [f64; 4] = [7.784695709041462e-3, 3.224671290700398e-1, 2.445134137142996, 3.754408661907416];
+    const LOW: f64 = 0.02425;
+
+    if p <= 0.0 {
+        return f64::NEG_INFINITY;
+    }
+    if p >= 1.0 {
+        return f64::INFINITY;
+    }
+    let tail = |q: f64| {
+        let q = (-2.0 * q.ln()).sqrt();
+        (((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5]) / ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.0)
+    };
+    let x = if p < LOW {
+        tail(p)
+    } else if p <= 1.0 - LOW {
+        let q = p - 0.5;
+        let r = q * q;
+        (((((A[0] * r + A[1]) * r + A[2]) * r + A[3]) * r + A[4]) * r + A[5]) * q
+            / (((((B[0] * r + B[1]) * r + B[2]) * r + B[3]) * r + B[4]) * r + 1.0)
+    } else {
+        -tail(1.0 - p)
+    };
+
+    // 在下尾用 cdf、上尾用 sf 计算误差,避免 1 - p 的舍入
+    let error = if x < 0.0 { 0.5 * libm::erfc(-x / SQRT_2) - p } else { (1.0 - p) - 0.5 * libm::erfc(x / SQRT_2) };
+    let step = error * (TAU.sqrt()) * (x * x / 2.0).exp();
+    if step.is_finite() {
+        x - step / (1.0 + x * step / 2.0)
+    } else {
+        x
+    }
+}
+
+/// 截断到 `lower..=upper` 的分布:只取区间中的值,区间中各值的相对机会不变
+///
+/// 两端都可以不截断。保留的概率较大时直接抽取并丢弃区间外的值;较小时用逆变换
+/// 在区间的累积概率之间均匀取值再求分位数,落在远离中位数的尾部时从尾部一侧计算,保持精度
+///
+/// ```
+/// use random_generator_core::{Distribution, Seed, TruncatedDistribution};
+///
+/// let iq = TruncatedDistribution::new("normal(100, 15)".parse()?, Some(60.0), Some(140.0))?;
+/// let report = iq.sample(1000, Seed::from([9; 32]))?;
+/// assert!(report.values.iter().all(|value| (60.0..=140.0).contains(value)));
+///
+/// // 远在尾部也能直接抽到
+/// let tail = TruncatedDistribution::new(Distribution::default(), Some(8.0), None)?;
+/// assert!(tail.sample(100, Seed::from([9; 32]))?.values.iter().all(|&value| value >= 8.0));
+/// # Ok::<(), random_generator_core::RandomGeneratorError>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TruncatedDistribution {
+    /// 原来的分布
+    pub distribution: Distribution,
+    /// 最小的值,`None` 为不截断
+    pub lower: Option<f64>,
+    /// 最大的值,`None` 为不截断
+    pub upper: Option<f64>,
+}
+
+/// 抽样的结果
+#[derive(Debug, Clone, PartialEq)]
+pub struct DistributionReport {
+    /// 抽样用的分布
+    pub distribution: TruncatedDistribution,
+    /// 抽到的值,按抽取的先后
+    pub values: Vec<f64>,
+}
+
+impl TruncatedDistribution {
+    /// 检查分布和截断区间,区间中要有值可抽
+    pub fn new(distribution: Distribution, lower: Option<f64>, upper: Option<f64>) -> Result<Self, RandomGeneratorError> {
+        let truncated = TruncatedDistribution { distribution, lower, upper };
+        truncated.validate()?;
+        Ok(truncated)
+    }
+
+    /// 检查分布和截断区间
+    pub fn validate(&self) -> Result<(), RandomGeneratorError> {
+        self.distribution.validate()?;
+        let (lower, upper) = self.bounds();
+        if lower.is_nan() || upper.is_nan() || lower > upper {
+            return Err(RandomGeneratorError::InvalidDistribution("the minimum must not be above the maximum".to_string()));
+        }
+        if self.mass() <= 0.0 {
+            return Err(RandomGeneratorError::InvalidDistribution(format!(
+                "{} has practically no chance of a value from {} to {}",
+                self.distribution, lower, upper
+            )));
+        }
+        Ok(())
+    }
+
+    /// 截断区间,没有截断的一端是无穷
+    fn bounds(&self) -> (f64, f64) {
+        (self.lower.unwrap_or(f64::NEG_INFINITY), self.upper.unwrap_or(f64::INFINITY))
+    }
+
+    /// 区间在中位数以上时从上尾计算
+    fn in_upper_tail(&self) -> bool {
+        self.distribution.cdf(self.bounds().0) > 0.5
+    }
+
+    /// 原来的分布落在截断区间中的概率
+    pub fn mass(&self) -> f64 {
+        let (lower, upper) = self.bounds();
+        if self.in_upper_tail() {
+            self.distribution.sf(lower) - self.distribution.sf(upper)
+        } else {
+            self.distribution.cdf(upper) - self.distribution.cdf(lower)
+        }
+    }
+
+    /// 抽一个区间中的值
+    fn draw(&self, mass: f64, rng: &mut dyn RngCore) -> f64 {
+        let (lower, upper) = self.bounds();
+        if mass >= REJECTION_MASS {
+            loop {
+                let value = self.distribution.sample(rng);
+                if (lower..=upper).contains(&value) {
+                    return value;
+                }
+            }
+        }
+        let value = if self.in_upper_tail() {
+            let (low, high) = (self.distribution.sf(upper), self.distribution.sf(lower));
+            self.distribution.upper_quantile(low + (high - low) * rng.gen::<f64>())
+        } else {
+            let (low, high) = (self.distribution.cdf(lower), self.distribution.cdf(upper));
+            self.distribution.quantile(low + (high - low) * rng.gen::<f64>())
+        };
+        // 分位数的舍入可能稍稍越过区间的端点
+        value.clamp(lower, upper)
+    }
+
+    /// 用种子抽 `count` 个值
+    pub fn sample(&self, count: usize, seed: Seed) -> Result<DistributionReport, RandomGeneratorError> {
+        self.sample_with_progress(count, seed, &AtomicBool::new(false), &mut |_, _| {})
+    }
+
+    /// 用种子抽 `count` 个值,并以 `(已抽的值数, 总数)` 回报进度
+    ///
+    /// 每次回报前检查 `cancel`,被置位时返回 `Cancelled`
+    pub fn sample_with_progress(
+        &self,
+        count: usize,
+        seed: Seed,
+        cancel: &AtomicBool,
+        on_progress: &mut dyn FnMut(usize, usize),
+    ) -> Result<DistributionReport, RandomGeneratorError> {
+        self.validate()?;
+        if !(1..=MAX_SAMPLES).contains(&count) {
+            return Err(RandomGeneratorError::InvalidDistribution(format!("draw 1 to {} values", MAX_SAMPLES)));
+        }
+        let mut progress = Progress { cancel, on_progress };
+        let mut rng = seed.rng();
+        let mass = self.mass();
+        let mut values = Vec::with_capacity(count);
+        for i in 0..count {
+            if i > 0 && i.is_multiple_of(PROGRESS_INTERVAL) {
+                progress.checkpoint(i, count)?;
+            }
+            values.push(self.draw(mass, &mut rng));
+        }
+        Ok(DistributionReport { distribution: *self, values })
+    }
+}
+
+impl fmt::Display for TruncatedDistribution {
+    /// 例如 `normal(100, 15) in 60..=140`,没有截断时只有分布
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.distribution)?;
+        match (self.lower, self.upper) {
+            (None, None) => Ok(()),
+            (lower, upper) => write!(
+                f,
+                " in {}..={}",
+                lower.map_or(String::new(), |value| value.to_string()),
+                upper.map_or(String::new(), |value| value.to_string())
+            ),
+        }
+    }
+}
+
+impl DistributionReport {
+    /// 样本的平均数
+    pub fn mean(&self) -> f64 {
+        mean(&self.values)
+    }
+
+    /// 样本标准差;只有一个值时为 0
+    pub fn standard_deviation(&self) -> f64 {
+        standard_deviation(&self.values)
+    }
+
+    /// 摘要加上直方图
+    pub fn to_text(&self) -> String {
+        summary(&self.distribution.to_string(), &self.values, String::new())
+    }
+
+    /// 每个值一行的 CSV,带表头 `index,value`,序号从 1 开始
+    pub fn to_csv(&self) -> String {
+        values_csv(&self.values)
+    }
+}
+
+fn mean(values: &[f64]) -> f64 {
+    values.iter().sum::<f64>() / values.len() as f64
+}
+
+fn standard_deviation(values: &[f64]) -> f64 {
+    if values.len() < 2 {
+        return 0.0;
+    }
+    let mean = mean(values);
+    let squares: f64 = values.iter().map(|value| (value - mean).powi(2)).sum();
+    (squares / (values.len() - 1) as f64).sqrt()
+}
+
+/// 分布的名字、个数、平均数、标准差和范围,接着是 `extra` 和直方图
+fn summary(distribution: &str, values: &[f64], extra: String) -> String {
+    let mut sorted = values.to_vec();
+    sorted.sort_by(f64::total_cmp);
+    let mut text = format!(
+        "Distribution: {}\nValues: {}\nMean: {:.4}\nStandard deviation: {:.4}\nRange: {:.4} to {:.4}\n{}\n",
+        distribution,
+        values.len(),
+        mean(values),
+        standard_deviation(values),
+        sorted[0],
+        sorted[sorted.len() - 1],
+        extra,
+    );
+    text.push_str(&histogram_bars(&sorted));
+    text
+}
+
+fn values_csv(values: &[f64]) -> String {
+    let mut csv = String::from("index,value\n");
+    for (i, value) in values.iter().enumerate() {
+        csv.push_str(&format!("{},{}\n", i + 1, value));
+    }
+    csv
+}
+
+/// 自定义分布的表最多的点数
+pub const MAX_CDF_POINTS: usize = 10_000;
+
+/// 由 (值, 累积概率) 点给出的自定义分布,用来抽工具没有内置的分布
+///
+/// 每个点表示“不超过这个值的概率”;相邻两点之间的概率均匀分布在两个值之间,即累积分布函数在点之间
+/// 线性插值。最后一个点的累积概率要是 1;第一个点的累积概率大于 0 时,这部分概率全落在第一个值上。
+/// 两个点的值相同时,概率之差落在这个值上。抽样用逆变换:抽 u,找到累积概率跨过 u 的两点再插值
+///
+/// ```
+/// use random_generator_core::{CdfTable, Seed};
+///
+/// // 一半在 0 到 10 之间,另一半在 10 到 100 之间
+/// let table = CdfTable::parse("0, 0\n10, 0.5\n100, 1")?;
+/// assert_eq!(table.quantile(0.75), 55.0);
+/// let sample = table.sample(1000, Seed::from([3; 32]))?;
+/// assert!(sample.values.iter().all(|&value| (0.0..=100.0).contains(&value)));
+/// # Ok::<(), random_generator_core::RandomGeneratorError>(())
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct CdfTable {
+    /// 按值排好的 (值, 累积概率)
+    points: Vec<(f64, f64)>,
+}
+
+/// 从自定义分布抽样的结果
+#[derive(Debug, Clone, PartialEq)]
+pub struct CdfSample {
+    /// 抽样用的表
+    pub table: CdfTable,
+    /// 抽到的值,按抽取的先后
+    pub values: Vec<f64>,
+}
+
+impl CdfTable {
+    /// 检查各点并按值排序;排序后累积概率不能下降,最后一个要是 1
+    pub fn new(mut points: Vec<(f64, f64)>) -> Result<Self, RandomGeneratorError> {
+        let invalid = |message: String| Err(RandomGeneratorError::InvalidDistribution(message));
+        if !(2..=MAX_CDF_POINTS).contains(&points.len()) {
+            return invalid(format!("a table needs 2 to {} points", MAX_CDF_POINTS));
+        }
+        if let Some(&(value, p)) = points.iter().find(|(value, p)| !value.is_finite() || !(0.0..=1.0).contains(p)) {
+            return invalid(format!("'{}, {}': values must be numbers and cumulative probabilities from 0 to 1", value, p));
+        }
+        points.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.total_cmp(&b.1)));
+        if let Some(pair) = points.windows(2).find(|pair| pair[1].1 < pair[0].1) {
+            return invalid(format!(
+                "the cumulative probability can't go down: {} at {} but {} at {}",
+                pair[0].1, pair[0].0, pair[1].1, pair[1].0
+            ));
+        }
+        if points[points.len() - 1].1 != 1.0 {
+            return invalid("the largest value must have cumulative probability 1".to_string());
+        }
+        Ok(CdfTable { points })
+    }
+
+    /// 由文本创建:每行或每个分号一个点,值和累积概率用逗号或空白分隔;空行和 `#` 开头的行会被跳过
+    pub fn parse(text: &str) -> Result<Self, RandomGeneratorError> {
+        let points = text
+            .split([';', '\n'])
+            .map(str::trim)
+            .filter(|point| !point.is_empty() && !point.starts_with('#'))
+            .map(|point| {
+                let numbers = point
+                    .split([',', ' ', '\t'])
+                    .filter(|number| !number.is_empty())
+                    .map(str::parse::<f64>)
+                    .collect::<Result<Vec<_>, _>>();
+                match numbers.as_deref() {
+                    Ok(&[value, p]) => Ok((value, p)),
+                    _ => Err(RandomGeneratorError::InvalidDistribution(format!(
+                        "'{}' is not a value and a cumulative probability",
+                        point
+                    ))),
+                }
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Self::new(points)
+    }
+
+    /// 按值排好的各点
+    pub fn points(&self) -> &[(f64, f64)] {
+        &self.points
+    }
+
+    /// 分布的平均数:第一个值乘以它的概率,加上每段的概率乘以这段的中点
+    pub fn mean(&self) -> f64 {
+        let (first, p) = self.points[0];
+        first * p
+            + self
+                .points
+                .windows(2)
+                .map(|pair| (pair[1].1 - pair[0].1) * (pair[0].0 + pair[1].0) / 2.0)
+                .sum::<f64>()
+    }
+
+    /// 累积概率为 `p`(0 到 1)的值,即累积分布函数在点之间线性插值后的反函数
+    pub fn quantile(&self, p: f64) -> f64 {
+        // 第一个累积概率大于 p 的点;p 为 1 时取最后一个值
+        let i = self.points.partition_point(|&(_, cumulative)| cumulative <= p);
+        if i == 0 {
+            return self.points[0].0;
+        }
+        let Some(&(high, high_p)) = self.points.get(i) else {
+            return self.points[self.points.len() - 1].0;
+        };
+        let (low, low_p) = self.points[i - 1];
+        low + (high - low) * (p - low_p) / (high_p - low_p)
+    }
+
+    /// 用种子抽 `count` 个值
+    pub fn sample(&self, count: usize, seed: Seed) -> Result<CdfSample, RandomGeneratorError> {
+        if !(1..=MAX_SAMPLES).contains(&count) {
+            return Err(RandomGeneratorError::InvalidDistribution(format!("draw 1 to {} values", MAX_SAMPLES)));
+        }
+        let mut rng = seed.rng();
+        let values = (0..count).map(|_| self.quantile(rng.gen::<f64>())).collect();
+        Ok(CdfSample { table: self.clone(), values })
+    }
+}
+
+impl fmt::Display for CdfTable {
+    /// 例如 `table of 3 points from 0 to 100`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "table of {} points from {} to {}", self.points.len(), self.points[0].0, self.points[self.points.len() - 1].0)
+    }
+}
+
+impl CdfSample {
+    /// 样本的平均数
+    pub fn mean(&self) -> f64 {
+        mean(&self.values)
+    }
+
+    /// 样本标准差;只有一个值时为 0
+    pub fn standard_deviation(&self) -> f64 {
+        standard_deviation(&self.values)
+    }
+
+    /// 摘要加上直方图,摘要中有表算出的平均数
+    pub fn to_text(&self) -> String {
+        summary(&self.table.to_string(), &self.values, format!("Expected mean: {:.4}\n", self.table.mean()))
+    }
+
+    /// 每个值一行的 CSV,带表头 `index,value`,序号从 1 开始
+    pub fn to_csv(&self) -> String {
+        values_csv(&self.values)
+    }
+}
+
+/// 随机概率向量最多的分量数
+pub const MAX_COMPONENTS: usize = 10_000;
+
+/// Dirichlet 分布:N 个不小于 0、加起来等于 1 的值,用来生成随机权重或随机的概率分布
+///
+/// 每个分量有一个集中度 α。所有 α 都是 1 时,单纯形上每个向量的机会相同;α 越大,各分量越接近
+/// α 的比例;α 小于 1 时,大部分权重集中在少数几个分量上。第 i 个分量的平均数是 αᵢ / Σα
+///
+/// ```
+/// use random_generator_core::{Dirichlet, Seed};
+///
+/// let weights = Dirichlet::symmetric(4, 1.0)?.sample(10, Seed::from([2; 32]))?;
+/// for vector in &weights.vectors {
+///     assert_eq!(vector.len(), 4);
+///     assert!(vector.iter().all(|&p| p >= 0.0));
+///     assert!((vector.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+/// }
+///
+/// let skewed = Dirichlet::parse(None, "1, 2, 7")?;
+/// assert_eq!(skewed.means(), [0.1, 0.2, 0.7]);
+/// # Ok::<(), random_generator_core::RandomGeneratorError>(())
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Dirichlet {
+    concentration: Vec<f64>,
+}
+
+/// 抽到的概率向量
+#[derive(Debug, Clone, PartialEq)]
+pub struct DirichletSample {
+    /// 抽样用的分布
+    pub dirichlet: Dirichlet,
+    /// 抽到的向量,按抽取的先后
+    pub vectors: Vec<Vec<f64>>,
+}
+
+impl Dirichlet {
+    /// 各分量的集中度,2 到 [`MAX_COMPONENTS`] 个,都要大于 0
+    pub fn new(concentration: Vec<f64>) -> Result<Self, RandomGeneratorError> {
+        if !(2..=MAX_COMPONENTS).contains(&concentration.len()) {
+            return Err(RandomGeneratorError::InvalidDistribution(format!("a probability vector needs 2 to {} components", MAX_COMPONENTS)));
+        }
+        if !concentration.iter().all(|&alpha| alpha > 0.0 && alpha.is_finite()) {
+            return Err(RandomGeneratorError::InvalidDistribution("the concentrations must be numbers above 0".to_string()));
+        }
+        Ok(Dirichlet { concentration })
+    }
+
+    /// `components` 个分量的集中度都是 `alpha`
+    pub fn symmetric(components: usize, alpha: f64) -> Result<Self, RandomGeneratorError> {
+        if components > MAX_COMPONENTS {
+            return Err(RandomGeneratorError::InvalidDistribution(format!("a probability vector needs 2 to {} components", MAX_COMPONENTS)));
+        }
+        Self::new(vec![alpha; components])
+    }
+
+    /// 一个数字是所有分量共同的集中度,这时要给出分量数;用逗号或空白分隔的多个数字是各分量的集中度,
+    /// 给出分量数时个数要相同
+    pub fn parse(components: Option<usize>, concentration: &str) -> Result<Self, RandomGeneratorError> {
+        let values = concentration
+            .split([',', ' ', '\t', '\n'])
+            .filter(|value| !value.trim().is_empty())
+            .map(|value| {
+                value.trim().parse::<f64>().map_err(|_| RandomGeneratorError::InvalidDistribution(format!("'{}' is not a concentration", value.trim())))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        match (values.as_slice(), components) {
+            ([], _) => Err(RandomGeneratorError::InvalidDistribution("enter a concentration, like 1".to_string())),
+            (&[alpha], Some(components)) => Self::symmetric(components, alpha),
+            ([_], None) => Err(RandomGeneratorError::InvalidDistribution("give the number of components, or one concentration for each".to_string())),
+            (values, Some(components)) if values.len() != components => Err(RandomGeneratorError::InvalidDistribution(format!(
+                "{} concentrations for {} components",
+                values.len(),
+                components
+            ))),
+            _ => Self::new(values),
+        }
+    }
+
+    /// 各分量的集中度
+    pub fn concentration(&self) -> &[f64] {
+        &self.concentration
+    }
+
+    /// 各分量的平均数 αᵢ / Σα
+    pub fn means(&self) -> Vec<f64> {
+        let total: f64 = self.concentration.iter().sum();
+        self.concentration.iter().map(|alpha| alpha / total).collect()
+    }
+
+    /// 用种子抽 `count` 个向量
+    ///
+    /// 各分量抽 Gamma(αᵢ) 再除以总和;在对数上计算,α 很小时也不会全部下溢成 0
+    pub fn sample(&self, count: usize, seed: Seed) -> Result<DirichletSample, RandomGeneratorError> {
+        if count == 0 || count.checked_mul(self.concentration.len()).is_none_or(|values| values > MAX_SAMPLES) {
+            return Err(RandomGeneratorError::InvalidDistribution(format!("draw 1 to {} values in all", MAX_SAMPLES)));
+        }
+        let mut rng = seed.rng();
+        let vectors = (0..count)
+            .map(|_| {
+                let logs: Vec<f64> = self.concentration.iter().map(|&alpha| log_standard_gamma(alpha, &mut rng)).collect();
+                let largest = logs.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+                let weights: Vec<f64> = logs.iter().map(|log| (log - largest).exp()).collect();
+                let total: f64 = weights.iter().sum();
+                weights.into_iter().map(|weight| weight / total).collect()
+            })
+            .collect();
+        Ok(DirichletSample { dirichlet: self.clone(), vectors })
+    }
+}
+
+impl fmt::Display for Dirichlet {
+    /// 例如 `dirichlet(1, 2, 7)`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let concentration: Vec<String> = self.concentration.iter().map(f64::to_string).collect();
+        write!(f, "dirichlet({})", concentration.join(", "))
+    }
+}
+
+impl DirichletSample {
+    /// 各分量的样本平均数
+    pub fn means(&self) -> Vec<f64> {
+        let mut sums = vec![0.0; self.dirichlet.concentration.len()];
+        for vector in &self.vectors {
+            for (sum, value) in sums.iter_mut().zip(vector) {
+                *sum += value;
+            }
+        }
+        sums.into_iter().map(|sum| sum / self.vectors.len() as f64).collect()
+    }
+
+    /// 每个向量一行,保留 4 位小数,最后是样本平均数和理论平均数
+    pub fn to_text(&self) -> String {
+        let row = |label: String, values: &[f64]| {
+            let cells: Vec<String> = values.iter().map(|value| format!("{:.4}", value)).collect();
+            format!("{:<9} {}\n", label, cells.join("  "))
+        };
+        let mut text = format!("Distribution: {}\nVectors: {}\n\n", self.dirichlet, self.vectors.len());
+        for (i, vector) in self.vectors.iter().enumerate() {
+            text.push_str(&row(format!("{}", i + 1), vector));
+        }
+        text.push('\n');
+        text.push_str(&row("Mean".to_string(), &self.means()));
+        text.push_str(&row("Expected".to_string(), &self.dirichlet.means()));
+        text
+    }
+
+    /// 每个向量一行的 CSV,表头是 `vector,p1,p2 …`,序号从 1 开始,权重按 [`Self::format_weight`] 写出
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("vector");
+        for i in 1..=self.dirichlet.concentration.len() {
+            csv.push_str(&format!(",p{}", i));
+        }
+        csv.push('\n');
+        for (i, vector) in self.vectors.iter().enumerate() {
+            let values: Vec<String> = vector.iter().map(|&value| Self::format_weight(value)).collect();
+            csv.push_str(&format!("{},{}\n", i + 1, values.join(",")));
+        }
+        csv
+    }
+
+    /// 一个权重的文本:保留 6 位小数;小于 0.000001 的用科学计数法,否则会写出上百位小数或者变成 0
+    pub fn format_weight(value: f64) -> String {
+        if value != 0.0 && value.abs() < 1e-6 {
+            format!("{:.3e}", value)
+        } else {
+            format!("{:.6}", value)
+        }
+    }
+}
@@ -0,0 +1,66 @@
+/// 抽取前可以预览的抽取空间,见 [`Generator::draw_space`](crate::Generator::draw_space)
+///
+/// 数量超过 `capacity` 时生成会报 `TooManyNumbers`;允许重复时,
+/// `collision_chance` 说明结果中出现相同数字的可能有多大
+///
+/// ```
+/// use random_generator_core::{GeneratorConfig, RandomGenerator};
+///
+/// let generator = RandomGenerator::new();
+/// let birthdays = GeneratorConfig { lower_bound: 1, upper_bound: 365, num_to_generate: 23, allow_duplicates: true, ..Default::default() };
+/// let space = generator.draw_space(&birthdays);
+/// assert_eq!(space.values, Some(365));
+/// assert!((space.collision_chance.unwrap() - 0.507).abs() < 0.001);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DrawSpace {
+    /// 可以抽到的不同数字个数,已去掉排除的数字;`None` 表示不限
+    pub values: Option<u128>,
+    /// 一次最多能抽几个数字,`None` 表示不限
+    pub capacity: Option<u128>,
+    /// 允许重复时这次抽取的结果中至少有两个相同的概率,不计每个值出现次数的限制;
+    /// 不允许重复或无法计算(例如按分布抽取)时为 `None`
+    pub collision_chance: Option<f64>,
+}
+
+/// 按权重计算概率时列表的不同数字个数与抽取个数之积的上限,超过时不计算
+const MAX_WEIGHTED_STEPS: usize = 10_000_000;
+
+/// 从 `values` 个机会相同的数字中有放回地抽 `count` 个时,至少有两个相同的概率
+pub(crate) fn uniform_collision_chance(values: u128, count: usize) -> f64 {
+    if count as u128 > values {
+        return 1.0;
+    }
+    // 全都不同的概率是 (1 - 0/n)(1 - 1/n)…(1 - (k-1)/n),取对数相加避免下溢
+    let mut distinct = 0.0_f64;
+    for i in 0..count {
+        distinct += (-(i as f64) / values as f64).ln_1p();
+        if distinct < -745.0 {
+            return 1.0;
+        }
+    }
+    -distinct.exp_m1()
+}
+
+/// 按权重有放回地抽 `count` 个时至少有两个相同的概率;列表太大时为 `None`
+///
+/// 全都不同的概率是 k! 乘以各概率的 k 次初等对称多项式,逐项递推
+pub(crate) fn weighted_collision_chance(weights: &[usize], count: usize) -> Option<f64> {
+    if count > weights.len() {
+        return Some(1.0);
+    }
+    if weights.len().saturating_mul(count) > MAX_WEIGHTED_STEPS {
+        return None;
+    }
+    let total: usize = weights.iter().sum();
+    // sums[j] 是前几个值中任选 j 个不同值、按任意顺序抽到的概率之和,即 j! 乘以初等对称多项式
+    let mut sums = vec![0.0_f64; count + 1];
+    sums[0] = 1.0;
+    for (seen, &weight) in weights.iter().enumerate() {
+        let chance = weight as f64 / total as f64;
+        for j in (1..=count.min(seen + 1)).rev() {
+            sums[j] += sums[j - 1] * chance * j as f64;
+        }
+    }
+    Some((1.0 - sums[count]).clamp(0.0, 1.0))
+}
@@ -0,0 +1,132 @@
+use std::collections::HashSet;
+
+use crate::Number;
+
+/// 排除集合:已经抽到的值不会再被抽到,直到清空
+///
+/// 放在 [`Config::exclude_drawn`](crate::Config::exclude_drawn) 中,只用于区间模式。
+/// 生成器每次生成后把新抽到的值加进去,因此一次会话中的多次生成不会抽到相同的值;
+/// 区间中可以抽的值相应减少,抽完时需要清空
+///
+/// ```
+/// use random_generator_core::{ExclusionSet, GeneratorConfig, RandomGenerator};
+///
+/// let mut generator = RandomGenerator::with_config(GeneratorConfig {
+///     lower_bound: 1,
+///     upper_bound: 10,
+///     num_to_generate: 5,
+///     exclude_drawn: Some(ExclusionSet::new()),
+///     ..Default::default()
+/// })?;
+/// generator.generate_numbers()?;
+/// let first = generator.get_numbers().to_vec();
+/// generator.generate_numbers()?;
+/// assert!(generator.get_numbers().iter().all(|n| !first.contains(n)));
+/// assert_eq!(generator.get_config().exclude_drawn.as_ref().map(ExclusionSet::len), Some(10));
+/// # Ok::<(), random_generator_core::RandomGeneratorError>(())
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ExclusionSet<T: Number> {
+    /// 按加入的先后
+    values: Vec<T>,
+    keys: HashSet<u128>,
+}
+
+impl<T: Number> ExclusionSet<T> {
+    /// 空集合
+    pub fn new() -> Self {
+        Self { values: Vec::new(), keys: HashSet::new() }
+    }
+
+    /// 排除的值的个数
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// 是否还没有排除任何值
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// 这个值是否被排除
+    pub fn contains(&self, value: T) -> bool {
+        self.keys.contains(&value.key())
+    }
+
+    /// 排除一个值;已经排除过时返回 `false`
+    pub fn insert(&mut self, value: T) -> bool {
+        let added = self.keys.insert(value.key());
+        if added {
+            self.values.push(value);
+        }
+        added
+    }
+
+    /// 排除的值,按加入的先后
+    pub fn values(&self) -> &[T] {
+        &self.values
+    }
+
+    /// 清空,所有值重新可以抽到
+    pub fn clear(&mut self) {
+        self.values.clear();
+        self.keys.clear();
+    }
+
+    /// `lower..=upper` 中被排除的值的个数
+    pub(crate) fn count_between(&self, lower: T, upper: T) -> usize {
+        self.values.iter().filter(|&&value| lower <= value && value <= upper).count()
+    }
+
+    /// `lower..=upper` 中被排除的值相对 `lower` 的位置,从小到大;只用于离散类型
+    pub(crate) fn indices_between(&self, lower: T, upper: T) -> Vec<u128> {
+        let mut indices: Vec<u128> = self
+            .values
+            .iter()
+            .filter(|&&value| lower <= value && value <= upper)
+            .filter_map(|&value| T::values_between(lower, value).map(|count| count - 1))
+            .collect();
+        indices.sort_unstable();
+        indices
+    }
+}
+
+impl<T: Number> Extend<T> for ExclusionSet<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, values: I) {
+        for value in values {
+            self.insert(value);
+        }
+    }
+}
+
+/// 只写出排除的值,按加入的先后
+#[cfg(feature = "serde")]
+impl<T: Number + serde::Serialize> serde::Serialize for ExclusionSet<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.values.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: Number + serde::Deserialize<'de>> serde::Deserialize<'de> for ExclusionSet<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut set = Self::new();
+        set.extend(Vec::<T>::deserialize(deserializer)?);
+        Ok(set)
+    }
+}
+
+/// 跳过 `skipped`(从小到大)后的第 `index` 个位置
+pub(crate) fn skip_to(skipped: &[u128], index: u128) -> u128 {
+    // 第 i 个被跳过的位置之前有 skipped[i] - i 个可以抽的位置
+    let (mut low, mut high) = (0, skipped.len());
+    while low < high {
+        let middle = (low + high) / 2;
+        if skipped[middle] - middle as u128 <= index {
+            low = middle + 1;
+        } else {
+            high = middle;
+        }
+    }
+    index + low as u128
+}
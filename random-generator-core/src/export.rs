@@ -0,0 +1,197 @@
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::{ExportFormat, Number, NumberFormat, RandomGeneratorError};
+
+/// 内置格式之外的一种导出格式
+///
+/// 实现后通过 [`Generator::register_exporter`](crate::Generator::register_exporter) 注册,
+/// 再用 [`Generator::save_numbers_with`](crate::Generator::save_numbers_with) 按名称保存
+pub trait Exporter<T: Number>: Send + Sync {
+    /// 显示在格式列表中的名称,也用于选择这种格式
+    fn name(&self) -> &str;
+
+    /// 对应的文件扩展名,不带句点
+    fn extension(&self) -> &str;
+
+    /// 把结果转换为文件内容
+    fn export(&self, numbers: &[T], format: &NumberFormat) -> Result<String, RandomGeneratorError>;
+}
+
+/// 几组结果一起导出时每组的标题,见 [`ExportFormat::render_groups`](crate::ExportFormat::render_groups)
+///
+/// 模板中的 `{n}` 换成第几组(从 1 开始),`{count}` 换成这组有几个数;
+/// 模板必须含有 `{n}`,这样各组的标题才不会相同
+///
+/// ```
+/// use random_generator_core::GroupHeader;
+///
+/// let header: GroupHeader = "Round {n} ({count} winners)".parse()?;
+/// assert_eq!(header.header(2, 3), "Round 2 (3 winners)");
+/// # Ok::<(), random_generator_core::RandomGeneratorError>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GroupHeader {
+    template: String,
+}
+
+impl GroupHeader {
+    /// 第 `n` 组(从 1 开始)、有 `count` 个数时的标题
+    pub fn header(&self, n: usize, count: usize) -> String {
+        self.template.replace("{n}", &n.to_string()).replace("{count}", &count.to_string())
+    }
+}
+
+impl Default for GroupHeader {
+    /// `Round {n}`
+    fn default() -> Self {
+        Self { template: "Round {n}".to_string() }
+    }
+}
+
+impl FromStr for GroupHeader {
+    type Err = RandomGeneratorError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let template = s.trim();
+        if !template.contains("{n}") {
+            return Err(RandomGeneratorError::InvalidGroupHeader(format!("'{}' needs {{n}} for the group number, like Round {{n}}", template)));
+        }
+        if template.contains(['\n', '\r']) {
+            return Err(RandomGeneratorError::InvalidGroupHeader("a group header must fit on one line".to_string()));
+        }
+        Ok(Self { template: template.to_string() })
+    }
+}
+
+impl fmt::Display for GroupHeader {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.template)
+    }
+}
+
+impl ExportFormat {
+    /// 按文件扩展名判断格式,不认识的扩展名当作文本
+    pub fn from_path(path: &str) -> Self {
+        let extension = Path::new(path).extension().and_then(|extension| extension.to_str()).unwrap_or_default();
+        ExportFormat::ALL.into_iter().find(|format| format.extension().eq_ignore_ascii_case(extension)).unwrap_or(ExportFormat::Text)
+    }
+
+    /// 读回用这种格式保存的结果
+    ///
+    /// 文本文件每行一个数,可以带序号(`#3: 42`)和千位分隔符;分组导出的各组标题会被跳过。
+    /// CSV 取 `value` 列;JSON 是数字数组,或分组导出的各组 `numbers`。显示为名字的值读不回来
+    ///
+    /// ```
+    /// use random_generator_core::ExportFormat;
+    ///
+    /// let numbers: Vec<i64> = ExportFormat::Text.read_numbers("Round 1\n#1: 1,024\n#2: 7\n\nRound 2\n#1: 3")?;
+    /// assert_eq!(numbers, [1024, 7, 3]);
+    /// # Ok::<(), random_generator_core::RandomGeneratorError>(())
+    /// ```
+    pub fn read_numbers<T: Number>(self, text: &str) -> Result<Vec<T>, RandomGeneratorError> {
+        let invalid = |line: usize, message: String| RandomGeneratorError::InvalidResultsFile { line, message };
+        let not_a_number = |line: usize, value: &str| invalid(line, format!("'{}' is not a number", value));
+        let mut numbers = Vec::new();
+        match self {
+            ExportFormat::Text => {
+                let mut block_start = true;
+                for (index, line) in text.lines().enumerate() {
+                    let line = line.trim();
+                    if line.is_empty() {
+                        block_start = true;
+                        continue;
+                    }
+                    match saved_value(line) {
+                        Some(value) => numbers.push(value),
+                        // 分组导出时每组的第一行是标题
+                        None if block_start => {}
+                        None => return Err(not_a_number(index + 1, line)),
+                    }
+                    block_start = false;
+                }
+            }
+            ExportFormat::Csv => {
+                let mut lines = text.lines().enumerate().filter(|(_, line)| !line.trim().is_empty());
+                let Some((_, header)) = lines.next() else {
+                    return Ok(numbers);
+                };
+                let column = csv_row(header)
+                    .iter()
+                    .position(|cell| cell.trim() == "value")
+                    .ok_or_else(|| invalid(1, "the header has no value column".to_string()))?;
+                for (index, line) in lines {
+                    let cells = csv_row(line);
+                    let cell = cells.get(column).map(|cell| cell.trim()).unwrap_or_default();
+                    numbers.push(saved_value(cell).ok_or_else(|| not_a_number(index + 1, cell))?);
+                }
+            }
+            ExportFormat::Json => {
+                let text = text.trim();
+                let grouped = text.strip_prefix('[').is_some_and(|rest| rest.trim_start().starts_with('{'));
+                if !grouped {
+                    json_array(text, &mut numbers).map_err(|message| invalid(0, message))?;
+                    return Ok(numbers);
+                }
+                // 分组导出:依次读各组的 numbers 数组;标题中的引号已转义,不会误认为键
+                let mut rest = text;
+                while let Some(start) = rest.find("\"numbers\"") {
+                    let after_key = rest[start + "\"numbers\"".len()..].trim_start();
+                    let array = after_key.strip_prefix(':').ok_or_else(|| invalid(0, "expected ':' after \"numbers\"".to_string()))?;
+                    rest = json_array(array.trim_start(), &mut numbers).map_err(|message| invalid(0, message))?;
+                }
+            }
+        }
+        Ok(numbers)
+    }
+}
+
+/// 保存的文本或 CSV 中的一个值:去掉序号,必要时去掉千位分隔符
+fn saved_value<T: Number>(text: &str) -> Option<T> {
+    let text = match text.strip_prefix('#').and_then(|rest| rest.split_once(": ")) {
+        Some((index, value)) if index.chars().all(|c| c.is_ascii_digit()) => value.trim(),
+        _ => text,
+    };
+    let parse = |text: &str| text.parse::<T>().ok().filter(|value| value.is_finite());
+    let plain = text.replace([',', ' ', '\''], "");
+    // 句点也可能是小数点,只有三位一组时才当作分隔符去掉,`2.5` 不会读成 25
+    parse(text).or_else(|| parse(&plain)).or_else(|| dot_grouped(&plain).then(|| parse(&plain.replace('.', ""))).flatten())
+}
+
+/// 句点是否把数字三位一组地分开,例如 `1.234.567`
+fn dot_grouped(text: &str) -> bool {
+    let digits = |group: &str| group.chars().all(|c| c.is_ascii_digit());
+    let mut groups = text.strip_prefix('-').unwrap_or(text).split('.');
+    let first = groups.next().unwrap_or_default();
+    text.contains('.') && (1..=3).contains(&first.len()) && digits(first) && groups.all(|group| group.len() == 3 && digits(group))
+}
+
+/// CSV 的一行,处理带引号的格;不支持跨行的格
+pub(crate) fn csv_row(line: &str) -> Vec<String> {
+    let mut cells = vec![String::new()];
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                chars.next();
+                cells.last_mut().unwrap().push('"');
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => cells.push(String::new()),
+            c => cells.last_mut().unwrap().push(c),
+        }
+    }
+    cells
+}
+
+/// 读一个 JSON 数字数组,返回数组后面的文本
+fn json_array<'a, T: Number>(text: &'a str, numbers: &mut Vec<T>) -> Result<&'a str, String> {
+    let body = text.strip_prefix('[').ok_or_else(|| "expected a list of numbers in [ ]".to_string())?;
+    let end = body.find(']').ok_or_else(|| "the list has no closing ]".to_string())?;
+    for value in body[..end].split(',').map(str::trim).filter(|value| !value.is_empty()) {
+        numbers.push(value.parse::<T>().ok().filter(|value| value.is_finite()).ok_or_else(|| format!("'{}' is not a number", value))?);
+    }
+    Ok(&body[end + 1..])
+}
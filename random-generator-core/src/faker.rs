@@ -0,0 +1,422 @@
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::{RandomGeneratorError, Seed};
+
+/// 测试数据中的一列
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FakeField {
+    /// 姓名
+    FullName,
+    /// 名
+    FirstName,
+    /// 姓
+    LastName,
+    /// 与姓名对应的电子邮件地址,只使用保留的 example 域名
+    Email,
+    /// 电话号码
+    Phone,
+    /// 街道地址
+    Address,
+    /// 城市
+    City,
+    /// 邮政编码
+    PostalCode,
+}
+
+impl FakeField {
+    /// 所有可选的列
+    pub const ALL: [FakeField; 8] = [
+        FakeField::FullName,
+        FakeField::FirstName,
+        FakeField::LastName,
+        FakeField::Email,
+        FakeField::Phone,
+        FakeField::Address,
+        FakeField::City,
+        FakeField::PostalCode,
+    ];
+
+    /// 用作 CSV 表头和 JSON 键的名称
+    pub fn key(self) -> &'static str {
+        match self {
+            FakeField::FullName => "name",
+            FakeField::FirstName => "first_name",
+            FakeField::LastName => "last_name",
+            FakeField::Email => "email",
+            FakeField::Phone => "phone",
+            FakeField::Address => "address",
+            FakeField::City => "city",
+            FakeField::PostalCode => "postcode",
+        }
+    }
+
+    /// 显示给用户的英文名称
+    pub fn label(self) -> &'static str {
+        match self {
+            FakeField::FullName => "Full name",
+            FakeField::FirstName => "First name",
+            FakeField::LastName => "Last name",
+            FakeField::Email => "Email",
+            FakeField::Phone => "Phone",
+            FakeField::Address => "Street address",
+            FakeField::City => "City",
+            FakeField::PostalCode => "Postal code",
+        }
+    }
+}
+
+impl fmt::Display for FakeField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.label())
+    }
+}
+
+impl FromStr for FakeField {
+    type Err = RandomGeneratorError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let key = s.trim();
+        FakeField::ALL
+            .into_iter()
+            .find(|field| field.key() == key)
+            .ok_or_else(|| RandomGeneratorError::InvalidSchema(format!("unknown field '{}'", key)))
+    }
+}
+
+/// 测试数据的地区,决定姓名、电话和地址的写法
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum FakeLocale {
+    /// 美国英语
+    #[default]
+    English,
+    /// 简体中文
+    Chinese,
+}
+
+impl FakeLocale {
+    /// 所有可选的地区
+    pub const ALL: [FakeLocale; 2] = [FakeLocale::English, FakeLocale::Chinese];
+
+    /// 地区代码:`en` 或 `zh`
+    pub fn code(self) -> &'static str {
+        match self {
+            FakeLocale::English => "en",
+            FakeLocale::Chinese => "zh",
+        }
+    }
+}
+
+impl fmt::Display for FakeLocale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            FakeLocale::English => "English (US)",
+            FakeLocale::Chinese => "中文",
+        })
+    }
+}
+
+impl FromStr for FakeLocale {
+    type Err = RandomGeneratorError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let code = s.trim();
+        FakeLocale::ALL
+            .into_iter()
+            .find(|locale| locale.code().eq_ignore_ascii_case(code))
+            .ok_or_else(|| RandomGeneratorError::InvalidSchema(format!("unknown locale '{}'", code)))
+    }
+}
+
+/// 测试数据的字段表:要生成哪些列、按哪个地区生成
+///
+/// 每一行先生成一个完整的虚构人物,再取出选中的列,因此同一行的姓名和邮箱相互对应,
+/// 同一种子在增减列之后其他列的内容也不变
+///
+/// ```
+/// use random_generator_core::{FakeField, FakeLocale, FieldSchema, Seed};
+///
+/// let schema = FieldSchema::new(vec![FakeField::FullName, FakeField::Email], FakeLocale::English);
+/// let rows = schema.generate(3, Seed::from([7; 32]))?;
+/// assert_eq!(rows.len(), 3);
+/// assert!(rows[0][1].ends_with("@example.com"));
+/// assert!(schema.to_csv(&rows).starts_with("name,email\n"));
+/// # Ok::<(), random_generator_core::RandomGeneratorError>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldSchema {
+    /// 按输出顺序排列的列
+    pub fields: Vec<FakeField>,
+    /// 地区
+    pub locale: FakeLocale,
+}
+
+impl FieldSchema {
+    /// 由列和地区创建字段表
+    pub fn new(fields: Vec<FakeField>, locale: FakeLocale) -> Self {
+        FieldSchema { fields, locale }
+    }
+
+    /// 解析逗号分隔的列名,例如 `name,email,phone`
+    pub fn parse(fields: &str, locale: FakeLocale) -> Result<Self, RandomGeneratorError> {
+        let fields = fields
+            .split(',')
+            .filter(|field| !field.trim().is_empty())
+            .map(str::parse)
+            .collect::<Result<_, _>>()?;
+        Ok(FieldSchema { fields, locale })
+    }
+
+    /// 用种子生成 `count` 行,每行的值与 `fields` 一一对应
+    pub fn generate(&self, count: usize, seed: Seed) -> Result<Vec<Vec<String>>, RandomGeneratorError> {
+        if self.fields.is_empty() {
+            return Err(RandomGeneratorError::InvalidSchema("choose at least one field".to_string()));
+        }
+        let mut rng = seed.rng();
+        let rows = (0..count)
+            .map(|_| {
+                let person = Person::random(&mut rng, self.locale);
+                self.fields.iter().map(|&field| person.field(field)).collect()
+            })
+            .collect();
+        Ok(rows)
+    }
+
+    /// 带表头的 CSV
+    pub fn to_csv(&self, rows: &[Vec<String>]) -> String {
+        let mut csv = self.fields.iter().map(|field| field.key()).collect::<Vec<_>>().join(",");
+        csv.push('\n');
+        for row in rows {
+            let cells: Vec<String> = row.iter().map(|cell| csv_cell(cell)).collect();
+            csv.push_str(&cells.join(","));
+            csv.push('\n');
+        }
+        csv
+    }
+
+    /// 以列名为键的 JSON 对象数组
+    pub fn to_json(&self, rows: &[Vec<String>]) -> String {
+        let objects: Vec<String> = rows
+            .iter()
+            .map(|row| {
+                let pairs: Vec<String> = self
+                    .fields
+                    .iter()
+                    .zip(row)
+                    .map(|(field, value)| format!("{}: {}", json_string(field.key()), json_string(value)))
+                    .collect();
+                format!("  {{{}}}", pairs.join(", "))
+            })
+            .collect();
+        if objects.is_empty() {
+            "[]\n".to_string()
+        } else {
+            format!("[\n{}\n]\n", objects.join(",\n"))
+        }
+    }
+}
+
+/// 一行测试数据背后的虚构人物
+pub(crate) struct Person {
+    locale: FakeLocale,
+    first: &'static str,
+    last: &'static str,
+    /// 邮箱用户名,中文地区用拼音
+    login: String,
+    phone: String,
+    street: String,
+    city: String,
+    postcode: String,
+}
+
+impl Person {
+    pub(crate) fn random(rng: &mut impl Rng, locale: FakeLocale) -> Self {
+        match locale {
+            FakeLocale::English => {
+                let first = *EN_FIRST_NAMES.choose(rng).unwrap();
+                let last = *EN_LAST_NAMES.choose(rng).unwrap();
+                let &(city, state) = EN_CITIES.choose(rng).unwrap();
+                Person {
+                    locale,
+                    first,
+                    last,
+                    login: format!("{}.{}{}", first, last, rng.gen_range(1..100)).to_lowercase(),
+                    // 555-0100 到 555-0199 是专门留作虚构用途的号码
+                    phone: format!("({}) 555-01{:02}", rng.gen_range(201..990), rng.gen_range(0..100)),
+                    street: format!(
+                        "{} {} {}",
+                        rng.gen_range(1..10000),
+                        EN_STREETS.choose(rng).unwrap(),
+                        EN_STREET_SUFFIXES.choose(rng).unwrap()
+                    ),
+                    city: format!("{}, {}", city, state),
+                    postcode: format!("{:05}", rng.gen_range(1000..100000)),
+                }
+            }
+            FakeLocale::Chinese => {
+                let &(first, first_pinyin) = ZH_GIVEN_NAMES.choose(rng).unwrap();
+                let &(last, last_pinyin) = ZH_SURNAMES.choose(rng).unwrap();
+                let &(city, district) = ZH_PLACES.choose(rng).unwrap();
+                Person {
+                    locale,
+                    first,
+                    last,
+                    login: format!("{}{}{}", last_pinyin, first_pinyin, rng.gen_range(1..100)),
+                    phone: format!(
+                        "1{}{} {:04} {:04}",
+                        ["3", "5", "8"].choose(rng).unwrap(),
+                        rng.gen_range(0..10),
+                        rng.gen_range(0..10000),
+                        rng.gen_range(0..10000)
+                    ),
+                    street: format!("{}区{}路{}号", district, ZH_ROADS.choose(rng).unwrap(), rng.gen_range(1..500)),
+                    city: format!("{}市", city),
+                    postcode: format!("{:06}", rng.gen_range(100000..900000)),
+                }
+            }
+        }
+    }
+
+    pub(crate) fn field(&self, field: FakeField) -> String {
+        match field {
+            FakeField::FullName => match self.locale {
+                FakeLocale::English => format!("{} {}", self.first, self.last),
+                FakeLocale::Chinese => format!("{}{}", self.last, self.first),
+            },
+            FakeField::FirstName => self.first.to_string(),
+            FakeField::LastName => self.last.to_string(),
+            FakeField::Email => format!("{}@example.com", self.login),
+            FakeField::Phone => self.phone.clone(),
+            FakeField::Address => self.street.clone(),
+            FakeField::City => self.city.clone(),
+            FakeField::PostalCode => self.postcode.clone(),
+        }
+    }
+}
+
+pub(crate) fn csv_cell(cell: &str) -> String {
+    if cell.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", cell.replace('"', "\"\""))
+    } else {
+        cell.to_string()
+    }
+}
+
+/// 带引号并转义的 JSON 字符串
+pub(crate) fn json_string(text: &str) -> String {
+    let mut json = String::with_capacity(text.len() + 2);
+    json.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if (c as u32) < 0x20 => json.push_str(&format!("\\u{:04x}", c as u32)),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+const EN_FIRST_NAMES: [&str; 24] = [
+    "James", "Mary", "John", "Patricia", "Robert", "Jennifer", "Michael", "Linda", "William", "Elizabeth", "David",
+    "Barbara", "Richard", "Susan", "Joseph", "Jessica", "Thomas", "Sarah", "Charles", "Karen", "Daniel", "Emily",
+    "Matthew", "Olivia",
+];
+
+const EN_LAST_NAMES: [&str; 20] = [
+    "Smith", "Johnson", "Williams", "Brown", "Jones", "Garcia", "Miller", "Davis", "Rodriguez", "Martinez", "Wilson",
+    "Anderson", "Taylor", "Thomas", "Moore", "Jackson", "Martin", "Lee", "Clark", "Walker",
+];
+
+const EN_STREETS: [&str; 10] = ["Main", "Oak", "Pine", "Maple", "Cedar", "Elm", "Washington", "Lake", "Hill", "Park"];
+
+const EN_STREET_SUFFIXES: [&str; 6] = ["St", "Ave", "Rd", "Blvd", "Ln", "Dr"];
+
+const EN_CITIES: [(&str, &str); 12] = [
+    ("Springfield", "IL"),
+    ("Portland", "OR"),
+    ("Austin", "TX"),
+    ("Denver", "CO"),
+    ("Columbus", "OH"),
+    ("Madison", "WI"),
+    ("Raleigh", "NC"),
+    ("Boise", "ID"),
+    ("Richmond", "VA"),
+    ("Tucson", "AZ"),
+    ("Albany", "NY"),
+    ("Salem", "MA"),
+];
+
+/// 姓和它的拼音
+const ZH_SURNAMES: [(&str, &str); 20] = [
+    ("王", "wang"),
+    ("李", "li"),
+    ("张", "zhang"),
+    ("刘", "liu"),
+    ("陈", "chen"),
+    ("杨", "yang"),
+    ("黄", "huang"),
+    ("赵", "zhao"),
+    ("吴", "wu"),
+    ("周", "zhou"),
+    ("徐", "xu"),
+    ("孙", "sun"),
+    ("马", "ma"),
+    ("朱", "zhu"),
+    ("胡", "hu"),
+    ("郭", "guo"),
+    ("何", "he"),
+    ("林", "lin"),
+    ("高", "gao"),
+    ("罗", "luo"),
+];
+
+/// 名和它的拼音
+const ZH_GIVEN_NAMES: [(&str, &str); 22] = [
+    ("伟", "wei"),
+    ("芳", "fang"),
+    ("娜", "na"),
+    ("敏", "min"),
+    ("静", "jing"),
+    ("丽", "li"),
+    ("强", "qiang"),
+    ("磊", "lei"),
+    ("洋", "yang"),
+    ("艳", "yan"),
+    ("勇", "yong"),
+    ("军", "jun"),
+    ("杰", "jie"),
+    ("娟", "juan"),
+    ("涛", "tao"),
+    ("明", "ming"),
+    ("超", "chao"),
+    ("秀英", "xiuying"),
+    ("霞", "xia"),
+    ("平", "ping"),
+    ("刚", "gang"),
+    ("桂英", "guiying"),
+];
+
+/// 城市和其中的一个区
+const ZH_PLACES: [(&str, &str); 12] = [
+    ("北京", "朝阳"),
+    ("北京", "海淀"),
+    ("上海", "浦东新"),
+    ("上海", "徐汇"),
+    ("广州", "天河"),
+    ("深圳", "南山"),
+    ("杭州", "西湖"),
+    ("南京", "鼓楼"),
+    ("成都", "武侯"),
+    ("武汉", "江汉"),
+    ("西安", "雁塔"),
+    ("苏州", "姑苏"),
+];
+
+const ZH_ROADS: [&str; 8] = ["人民", "解放", "中山", "建设", "和平", "新华", "长江", "黄河"];
@@ -0,0 +1,231 @@
+use std::f64::consts::{E, PI};
+use std::fmt;
+use std::iter::Peekable;
+use std::str::{CharIndices, FromStr};
+
+use crate::RandomGeneratorError;
+
+/// 含变量 `x` 的算式,例如 `x^2 + sin(x)`
+///
+/// 支持 `+ - * / ^`(乘方从右往左结合)、括号、常数 `pi` 和 `e`,
+/// 以及函数 `sin cos tan asin acos atan exp ln log sqrt abs`,其中 `log` 以 10 为底。
+/// 乘号不能省略,`2x` 要写成 `2*x`
+///
+/// ```
+/// use random_generator_core::Formula;
+///
+/// let formula: Formula = "2*x^2 - sqrt(x) + pi".parse()?;
+/// assert_eq!(formula.eval(4.0), 32.0 - 2.0 + std::f64::consts::PI);
+/// assert!("2x".parse::<Formula>().is_err());
+/// # Ok::<(), random_generator_core::RandomGeneratorError>(())
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Formula {
+    text: String,
+    root: Node,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Node {
+    Number(f64),
+    X,
+    Negate(Box<Node>),
+    Binary(char, Box<Node>, Box<Node>),
+    Call(Function, Box<Node>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Function {
+    Sin,
+    Cos,
+    Tan,
+    Asin,
+    Acos,
+    Atan,
+    Exp,
+    Ln,
+    Log,
+    Sqrt,
+    Abs,
+}
+
+impl Function {
+    const ALL: [(&'static str, Function); 11] = [
+        ("sin", Function::Sin),
+        ("cos", Function::Cos),
+        ("tan", Function::Tan),
+        ("asin", Function::Asin),
+        ("acos", Function::Acos),
+        ("atan", Function::Atan),
+        ("exp", Function::Exp),
+        ("ln", Function::Ln),
+        ("log", Function::Log),
+        ("sqrt", Function::Sqrt),
+        ("abs", Function::Abs),
+    ];
+
+    fn apply(self, value: f64) -> f64 {
+        match self {
+            Function::Sin => value.sin(),
+            Function::Cos => value.cos(),
+            Function::Tan => value.tan(),
+            Function::Asin => value.asin(),
+            Function::Acos => value.acos(),
+            Function::Atan => value.atan(),
+            Function::Exp => value.exp(),
+            Function::Ln => value.ln(),
+            Function::Log => value.log10(),
+            Function::Sqrt => value.sqrt(),
+            Function::Abs => value.abs(),
+        }
+    }
+}
+
+impl Formula {
+    /// 代入 `x` 求值;超出定义域时得到 NaN,例如 `sqrt(-1)`
+    pub fn eval(&self, x: f64) -> f64 {
+        self.root.eval(x)
+    }
+}
+
+impl Node {
+    fn eval(&self, x: f64) -> f64 {
+        match self {
+            &Node::Number(value) => value,
+            Node::X => x,
+            Node::Negate(inner) => -inner.eval(x),
+            Node::Binary(op, left, right) => {
+                let (left, right) = (left.eval(x), right.eval(x));
+                match op {
+                    '+' => left + right,
+                    '-' => left - right,
+                    '*' => left * right,
+                    '/' => left / right,
+                    _ => left.powf(right),
+                }
+            }
+            Node::Call(function, inner) => function.apply(inner.eval(x)),
+        }
+    }
+}
+
+impl fmt::Display for Formula {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.text)
+    }
+}
+
+impl FromStr for Formula {
+    type Err = RandomGeneratorError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let text = s.trim();
+        if text.is_empty() {
+            return Err(RandomGeneratorError::InvalidFormula("write a formula in x, like x^2".to_string()));
+        }
+        let mut parser = Parser { chars: text.char_indices().peekable() };
+        let root = parser.sum().map_err(RandomGeneratorError::InvalidFormula)?;
+        if let Some((at, c)) = parser.peek() {
+            let hint = if c.is_ascii_alphanumeric() || c == '(' { "; multiplying needs a *, like 2*x" } else { "" };
+            return Err(RandomGeneratorError::InvalidFormula(format!("unexpected '{}' at {}{}", c, at + 1, hint)));
+        }
+        Ok(Self { text: text.to_string(), root })
+    }
+}
+
+/// 递归下降:和 → 积 → 负号 → 乘方 → 数字、`x`、常数、函数或括号
+struct Parser<'a> {
+    chars: Peekable<CharIndices<'a>>,
+}
+
+impl Parser<'_> {
+    /// 下一个不是空白的字符及其位置
+    fn peek(&mut self) -> Option<(usize, char)> {
+        while self.chars.next_if(|(_, c)| c.is_whitespace()).is_some() {}
+        self.chars.peek().copied()
+    }
+
+    fn eat(&mut self, expected: char) -> bool {
+        self.peek().is_some_and(|(_, c)| c == expected) && self.chars.next().is_some()
+    }
+
+    fn sum(&mut self) -> Result<Node, String> {
+        let mut node = self.product()?;
+        while let Some(op) = self.peek().map(|(_, c)| c).filter(|c| matches!(c, '+' | '-')) {
+            self.chars.next();
+            node = Node::Binary(op, Box::new(node), Box::new(self.product()?));
+        }
+        Ok(node)
+    }
+
+    fn product(&mut self) -> Result<Node, String> {
+        let mut node = self.unary()?;
+        while let Some(op) = self.peek().map(|(_, c)| c).filter(|c| matches!(c, '*' | '/')) {
+            self.chars.next();
+            node = Node::Binary(op, Box::new(node), Box::new(self.unary()?));
+        }
+        Ok(node)
+    }
+
+    /// `-x^2` 是 `-(x^2)`
+    fn unary(&mut self) -> Result<Node, String> {
+        if self.eat('-') {
+            return Ok(Node::Negate(Box::new(self.unary()?)));
+        }
+        self.eat('+');
+        self.power()
+    }
+
+    fn power(&mut self) -> Result<Node, String> {
+        let base = self.atom()?;
+        if self.eat('^') {
+            return Ok(Node::Binary('^', Box::new(base), Box::new(self.unary()?)));
+        }
+        Ok(base)
+    }
+
+    fn atom(&mut self) -> Result<Node, String> {
+        let Some((at, c)) = self.peek() else {
+            return Err("the formula ends too early".to_string());
+        };
+        if c == '(' {
+            self.chars.next();
+            let inner = self.sum()?;
+            return if self.eat(')') { Ok(inner) } else { Err(format!("the '(' at {} is never closed", at + 1)) };
+        }
+        if c.is_ascii_digit() || c == '.' {
+            let mut number = String::new();
+            while let Some((_, c)) = self.chars.next_if(|(_, c)| c.is_ascii_digit() || *c == '.') {
+                number.push(c);
+            }
+            return number.parse().map(Node::Number).map_err(|_| format!("'{}' is not a number", number));
+        }
+        if c.is_ascii_alphabetic() {
+            let mut name = String::new();
+            while let Some((_, c)) = self.chars.next_if(|(_, c)| c.is_ascii_alphanumeric()) {
+                name.push(c.to_ascii_lowercase());
+            }
+            return match name.as_str() {
+                "x" => Ok(Node::X),
+                "pi" => Ok(Node::Number(PI)),
+                "e" => Ok(Node::Number(E)),
+                _ => {
+                    let function = Function::ALL
+                        .iter()
+                        .find(|(known, _)| *known == name)
+                        .map(|&(_, function)| function)
+                        .ok_or_else(|| format!("'{}' is not x, pi, e or a known function", name))?;
+                    if !self.eat('(') {
+                        return Err(format!("{} needs brackets, like {}(x)", name, name));
+                    }
+                    let inner = self.sum()?;
+                    if !self.eat(')') {
+                        return Err(format!("{}( is never closed", name));
+                    }
+                    Ok(Node::Call(function, Box::new(inner)))
+                }
+            };
+        }
+        Err(format!("unexpected '{}' at {}", c, at + 1))
+    }
+}
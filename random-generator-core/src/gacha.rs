@@ -0,0 +1,184 @@
+use rand::distributions::{Distribution, WeightedIndex};
+
+use crate::{RandomGeneratorError, Seed};
+
+/// 一档稀有度
+#[derive(Debug, Clone, PartialEq)]
+pub struct RarityTier {
+    /// 名称,例如 `Legendary`
+    pub name: String,
+    /// 权重,通常写成百分比;各档按权重的比例掉落,合计不必是 100
+    pub weight: f64,
+    /// 保底:连续这么多次没有掉落这一档时,这一次必定掉落;`None` 表示没有保底
+    pub pity: Option<usize>,
+}
+
+/// 模拟中一档稀有度的统计
+#[derive(Debug, Clone, PartialEq)]
+pub struct TierResult {
+    /// 名称
+    pub name: String,
+    /// 设定的掉落率,0 到 1
+    pub rate: f64,
+    /// 掉落的次数
+    pub count: usize,
+    /// 其中由保底触发的次数
+    pub pity_hits: usize,
+    /// 最长连续多少次没有掉落这一档
+    pub longest_gap: usize,
+}
+
+/// 一次模拟的结果
+#[derive(Debug, Clone, PartialEq)]
+pub struct GachaReport {
+    /// 抽取的次数
+    pub pulls: usize,
+    /// 按掉落表的顺序,每档一项
+    pub tiers: Vec<TierResult>,
+}
+
+/// 抽卡、掉落表的模拟:按各档稀有度的权重抽取 N 次,统计每档的次数,可以设保底
+///
+/// 几档的保底在同一次触发时,掉落率最低的一档优先,其他档的计数继续累积
+///
+/// ```
+/// use random_generator_core::{LootTable, Seed};
+///
+/// let table = LootTable::parse("Common, 80%\nRare, 15%\nLegendary, 5%, 50")?;
+/// let report = table.simulate(10_000, Seed::from([3; 32]))?;
+/// assert_eq!(report.tiers.iter().map(|tier| tier.count).sum::<usize>(), 10_000);
+/// assert!(report.tiers[2].longest_gap < 50);
+/// # Ok::<(), random_generator_core::RandomGeneratorError>(())
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct LootTable {
+    tiers: Vec<RarityTier>,
+}
+
+impl LootTable {
+    /// 至少一档;权重必须是正数,保底至少为 1,名称不能为空或重复
+    pub fn new(tiers: Vec<RarityTier>) -> Result<Self, RandomGeneratorError> {
+        if tiers.is_empty() {
+            return Err(RandomGeneratorError::InvalidGame("the loot table needs at least one tier".to_string()));
+        }
+        for (i, tier) in tiers.iter().enumerate() {
+            if tier.name.trim().is_empty() {
+                return Err(RandomGeneratorError::InvalidGame("tier names can't be empty".to_string()));
+            }
+            if tiers[..i].iter().any(|other| other.name.eq_ignore_ascii_case(&tier.name)) {
+                return Err(RandomGeneratorError::InvalidGame(format!("'{}' is in the table twice", tier.name)));
+            }
+            if !(tier.weight.is_finite() && tier.weight > 0.0) {
+                return Err(RandomGeneratorError::InvalidGame(format!("the rate of '{}' must be above 0", tier.name)));
+            }
+            if tier.pity == Some(0) {
+                return Err(RandomGeneratorError::InvalidGame(format!("the pity of '{}' must be at least 1", tier.name)));
+            }
+        }
+        Ok(LootTable { tiers })
+    }
+
+    /// 每行一档:`名称, 掉落率[, 保底]`,例如 `Legendary, 5%, 90`;百分号可以省略,跳过空行
+    pub fn parse(text: &str) -> Result<Self, RandomGeneratorError> {
+        let tiers = text
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(|line| {
+                let invalid = || RandomGeneratorError::InvalidGame(format!("'{}' is not a tier like 'Rare, 15%' or 'Legendary, 5%, 90'", line));
+                let parts: Vec<&str> = line.split(',').map(str::trim).collect();
+                let (name, weight, pity) = match parts[..] {
+                    [name, weight] => (name, weight, None),
+                    [name, weight, pity] => (name, weight, Some(pity)),
+                    _ => return Err(invalid()),
+                };
+                let weight = weight.trim_end_matches('%').trim().parse().map_err(|_| invalid())?;
+                let pity = pity.map(str::parse).transpose().map_err(|_| invalid())?;
+                Ok(RarityTier { name: name.to_string(), weight, pity })
+            })
+            .collect::<Result<_, _>>()?;
+        Self::new(tiers)
+    }
+
+    /// 各档稀有度
+    pub fn tiers(&self) -> &[RarityTier] {
+        &self.tiers
+    }
+
+    /// 用种子模拟抽取 `pulls` 次
+    pub fn simulate(&self, pulls: usize, seed: Seed) -> Result<GachaReport, RandomGeneratorError> {
+        let weights = WeightedIndex::new(self.tiers.iter().map(|tier| tier.weight))
+            .map_err(|e| RandomGeneratorError::InvalidGame(e.to_string()))?;
+        let total: f64 = self.tiers.iter().map(|tier| tier.weight).sum();
+        let mut rng = seed.rng();
+        let mut tiers: Vec<TierResult> = self
+            .tiers
+            .iter()
+            .map(|tier| TierResult { name: tier.name.clone(), rate: tier.weight / total, count: 0, pity_hits: 0, longest_gap: 0 })
+            .collect();
+        // 每档已经连续多少次没有掉落
+        let mut since = vec![0usize; self.tiers.len()];
+        for _ in 0..pulls {
+            let guaranteed = (0..self.tiers.len())
+                .filter(|&i| self.tiers[i].pity.is_some_and(|pity| since[i] + 1 >= pity))
+                .min_by(|&a, &b| self.tiers[a].weight.total_cmp(&self.tiers[b].weight));
+            let drop = guaranteed.unwrap_or_else(|| weights.sample(&mut rng));
+            tiers[drop].count += 1;
+            if guaranteed.is_some() {
+                tiers[drop].pity_hits += 1;
+            }
+            for (i, gap) in since.iter_mut().enumerate() {
+                if i == drop {
+                    *gap = 0;
+                } else {
+                    *gap += 1;
+                    tiers[i].longest_gap = tiers[i].longest_gap.max(*gap);
+                }
+            }
+        }
+        Ok(GachaReport { pulls, tiers })
+    }
+}
+
+impl GachaReport {
+    /// 对齐的表格,每档一行:设定的掉落率、次数、实际比例、保底次数和最长间隔
+    pub fn to_text(&self) -> String {
+        let name_width = self.tiers.iter().map(|tier| tier.name.chars().count()).max().unwrap_or(0).max(4);
+        let mut text = format!("{:<name_width$}  {:>7}  {:>10}  {:>7}  {:>6}  {:>11}\n", "Tier", "Rate", "Count", "Share", "Pity", "Longest gap");
+        for tier in &self.tiers {
+            text.push_str(&format!(
+                "{:<name_width$}  {:>6.2}%  {:>10}  {:>6.2}%  {:>6}  {:>11}\n",
+                tier.name,
+                tier.rate * 100.0,
+                tier.count,
+                self.share(tier) * 100.0,
+                tier.pity_hits,
+                tier.longest_gap
+            ));
+        }
+        text
+    }
+
+    /// 每档一行的 CSV,带表头 `tier,rate,count,share,pity_hits,longest_gap`,比例是 0 到 1 的小数
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("tier,rate,count,share,pity_hits,longest_gap\n");
+        for tier in &self.tiers {
+            let name = if tier.name.contains([',', '"', '\n', '\r']) {
+                format!("\"{}\"", tier.name.replace('"', "\"\""))
+            } else {
+                tier.name.clone()
+            };
+            csv.push_str(&format!("{},{},{},{},{},{}\n", name, tier.rate, tier.count, self.share(tier), tier.pity_hits, tier.longest_gap));
+        }
+        csv
+    }
+
+    /// 一档实际掉落的比例
+    fn share(&self, tier: &TierResult) -> f64 {
+        if self.pulls == 0 {
+            0.0
+        } else {
+            tier.count as f64 / self.pulls as f64
+        }
+    }
+}
@@ -0,0 +1,250 @@
+use rand::Rng;
+use std::collections::HashSet;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::atomic::AtomicBool;
+
+use crate::source::UniqueStrategy;
+use crate::{Progress, RandomGeneratorError, Seed, PROGRESS_INTERVAL};
+
+/// 一个图最多的节点数
+pub const MAX_NODES: usize = 1_000_000;
+
+/// 一个图最多的边数
+pub const MAX_EDGES: usize = 1_000_000;
+
+/// 随机图的模型
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum GraphModel {
+    /// Erdős–Rényi G(n, m):在所有可能的边中均匀地选 `edges` 条
+    #[default]
+    ErdosRenyi,
+    /// Barabási–Albert 优先连接:每个新节点连 `edges` 条边,连到度数越大的节点机会越大
+    BarabasiAlbert,
+}
+
+impl GraphModel {
+    /// 所有的模型
+    pub const ALL: [GraphModel; 2] = [GraphModel::ErdosRenyi, GraphModel::BarabasiAlbert];
+}
+
+impl fmt::Display for GraphModel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GraphModel::ErdosRenyi => write!(f, "Erdős–Rényi"),
+            GraphModel::BarabasiAlbert => write!(f, "Barabási–Albert"),
+        }
+    }
+}
+
+impl FromStr for GraphModel {
+    type Err = RandomGeneratorError;
+
+    /// 接受 `er`、`ba`、`erdos-renyi`、`barabasi-albert` 和显示的名字,不区分大小写
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.trim().to_lowercase();
+        match name.as_str() {
+            "er" | "erdos-renyi" | "gnm" => Ok(GraphModel::ErdosRenyi),
+            "ba" | "barabasi-albert" => Ok(GraphModel::BarabasiAlbert),
+            _ => GraphModel::ALL
+                .into_iter()
+                .find(|model| model.to_string().to_lowercase() == name)
+                .ok_or_else(|| RandomGeneratorError::InvalidGraph(format!("unknown model '{}'", s.trim()))),
+        }
+    }
+}
+
+/// 随机生成的无向简单图,用来测试图算法
+///
+/// 节点编号从 0 到 `nodes - 1`,没有自环和重边。`edges` 的意思取决于模型:
+/// Erdős–Rényi 是图的总边数;Barabási–Albert 是每个新节点连出的边数,
+/// 前 `edges + 1` 个节点先连成完全图
+///
+/// ```
+/// use random_generator_core::{GraphModel, RandomGraph, Seed};
+///
+/// let spec = RandomGraph { model: GraphModel::ErdosRenyi, nodes: 10, edges: 15 };
+/// let graph = spec.generate(Seed::from([2; 32]))?;
+/// assert_eq!(graph.edges.len(), 15);
+/// assert!(graph.edges.iter().all(|&(a, b)| a < b && b < 10));
+/// assert!(graph.to_dot().starts_with("graph G {\n"));
+/// # Ok::<(), random_generator_core::RandomGeneratorError>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RandomGraph {
+    /// 用哪个模型
+    pub model: GraphModel,
+    /// 节点数
+    pub nodes: usize,
+    /// 总边数,或 Barabási–Albert 中每个新节点的边数
+    pub edges: usize,
+}
+
+impl Default for RandomGraph {
+    fn default() -> Self {
+        RandomGraph { model: GraphModel::default(), nodes: 20, edges: 30 }
+    }
+}
+
+/// 生成的图
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Graph {
+    /// 节点数
+    pub nodes: usize,
+    /// 边,每条边较小的端点在前
+    pub edges: Vec<(usize, usize)>,
+}
+
+impl RandomGraph {
+    /// 图会有几条边,超出 `usize` 时为 `None`
+    pub fn edge_count(&self) -> Option<usize> {
+        match self.model {
+            GraphModel::ErdosRenyi => Some(self.edges),
+            // 起始的完全图加上之后每个节点的边
+            GraphModel::BarabasiAlbert => {
+                let clique = self.edges.checked_mul(self.edges + 1)? / 2;
+                let rest = self.nodes.checked_sub(self.edges + 1)?.checked_mul(self.edges)?;
+                clique.checked_add(rest)
+            }
+        }
+    }
+
+    /// 检查节点数和边数
+    pub fn validate(&self) -> Result<(), RandomGeneratorError> {
+        if !(1..=MAX_NODES).contains(&self.nodes) {
+            return Err(RandomGeneratorError::InvalidGraph(format!("the graph needs 1 to {} nodes", MAX_NODES)));
+        }
+        match self.model {
+            GraphModel::ErdosRenyi => {
+                let possible = pair_count(self.nodes);
+                if self.edges as u128 > possible {
+                    return Err(RandomGeneratorError::InvalidGraph(format!("{} nodes have at most {} edges", self.nodes, possible)));
+                }
+            }
+            GraphModel::BarabasiAlbert => {
+                if self.edges == 0 || self.edges >= self.nodes {
+                    return Err(RandomGeneratorError::InvalidGraph("each new node needs 1 to nodes - 1 edges".to_string()));
+                }
+            }
+        }
+        if self.edge_count().is_none_or(|count| count > MAX_EDGES) {
+            return Err(RandomGeneratorError::InvalidGraph(format!("the graph can have at most {} edges", MAX_EDGES)));
+        }
+        Ok(())
+    }
+
+    /// 用种子生成图
+    pub fn generate(&self, seed: Seed) -> Result<Graph, RandomGeneratorError> {
+        self.generate_with_progress(seed, &AtomicBool::new(false), &mut |_, _| {})
+    }
+
+    /// 用种子生成图,并以 `(已生成的边数, 总数)` 回报进度
+    ///
+    /// 每次回报前检查 `cancel`,被置位时返回 `Cancelled`
+    pub fn generate_with_progress(
+        &self,
+        seed: Seed,
+        cancel: &AtomicBool,
+        on_progress: &mut dyn FnMut(usize, usize),
+    ) -> Result<Graph, RandomGeneratorError> {
+        self.validate()?;
+        let mut progress = Progress { cancel, on_progress };
+        let mut rng = seed.rng();
+        let edges = match self.model {
+            GraphModel::ErdosRenyi => {
+                let size = pair_count(self.nodes);
+                let mut indices = UniqueStrategy::for_range(self.edges, size).indices(&mut rng, size, self.edges, &mut progress)?;
+                indices.sort_unstable();
+                indices.into_iter().map(pair_at).collect()
+            }
+            GraphModel::BarabasiAlbert => {
+                let attach = self.edges;
+                let mut edges = Vec::with_capacity(self.edge_count().unwrap_or(0));
+                for b in 1..=attach {
+                    edges.extend((0..b).map(|a| (a, b)));
+                }
+                // 每条边的两个端点各记一次,均匀地抽一个端点就是按度数加权地抽节点
+                let mut ends: Vec<usize> = edges.iter().flat_map(|&(a, b)| [a, b]).collect();
+                // 集合只用来去重,按抽中的顺序连边,同一个种子得到同样的图
+                let mut seen = HashSet::with_capacity(attach);
+                let mut chosen = Vec::with_capacity(attach);
+                let total = self.edge_count().unwrap_or(0);
+                // 每个节点带来的边数可能很多,按边数而不是节点数回报
+                let mut next_report = PROGRESS_INTERVAL;
+                for node in attach + 1..self.nodes {
+                    if edges.len() >= next_report {
+                        progress.checkpoint(edges.len(), total)?;
+                        next_report += PROGRESS_INTERVAL;
+                    }
+                    seen.clear();
+                    chosen.clear();
+                    while chosen.len() < attach {
+                        let target = ends[rng.gen_range(0..ends.len())];
+                        if seen.insert(target) {
+                            chosen.push(target);
+                        }
+                    }
+                    for &target in &chosen {
+                        edges.push((target, node));
+                        ends.extend([target, node]);
+                    }
+                }
+                edges
+            }
+        };
+        Ok(Graph { nodes: self.nodes, edges })
+    }
+}
+
+/// `nodes` 个节点之间可能的边数
+fn pair_count(nodes: usize) -> u128 {
+    let nodes = nodes as u128;
+    nodes * nodes.saturating_sub(1) / 2
+}
+
+/// 按 (0, 1), (0, 2), (1, 2), (0, 3) … 的顺序排列的第 `index` 条边
+fn pair_at(index: u128) -> (usize, usize) {
+    // b 是满足 b(b - 1)/2 <= index 的最大整数;先用浮点数估计再修正
+    let mut b = ((1.0 + (1.0 + 8.0 * index as f64).sqrt()) / 2.0) as u128;
+    while b * (b - 1) / 2 > index {
+        b -= 1;
+    }
+    while (b + 1) * b / 2 <= index {
+        b += 1;
+    }
+    ((index - b * (b - 1) / 2) as usize, b as usize)
+}
+
+impl Graph {
+    /// 每条边一行,两个端点用空格分隔;没有边的节点不出现
+    pub fn to_edge_list(&self) -> String {
+        let mut list = String::new();
+        for (a, b) in &self.edges {
+            list.push_str(&format!("{} {}\n", a, b));
+        }
+        list
+    }
+
+    /// Graphviz DOT 格式的无向图,先列出所有节点,没有边的节点也会画出来
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("graph G {\n");
+        for node in 0..self.nodes {
+            dot.push_str(&format!("  {};\n", node));
+        }
+        for (a, b) in &self.edges {
+            dot.push_str(&format!("  {} -- {};\n", a, b));
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// 每个节点的度数
+    pub fn degrees(&self) -> Vec<usize> {
+        let mut degrees = vec![0; self.nodes];
+        for &(a, b) in &self.edges {
+            degrees[a] += 1;
+            degrees[b] += 1;
+        }
+        degrees
+    }
+}
@@ -0,0 +1,64 @@
+use crate::{Config, Number, RandomGeneratorError};
+
+type StartHook<T> = Box<dyn FnMut(&Config<T>) + Send + Sync>;
+type ProgressHook = Box<dyn FnMut(usize, usize) + Send + Sync>;
+type ValueHook<T> = Box<dyn FnMut(T) + Send + Sync>;
+type CompleteHook<T> = Box<dyn FnMut(Result<&[T], &RandomGeneratorError>) + Send + Sync>;
+
+/// 注册在生成器上的事件回调,每种事件可以有多个,按注册顺序调用
+pub(crate) struct Hooks<T: Number> {
+    start: Vec<StartHook<T>>,
+    progress: Vec<ProgressHook>,
+    value: Vec<ValueHook<T>>,
+    complete: Vec<CompleteHook<T>>,
+}
+
+impl<T: Number> Hooks<T> {
+    pub(crate) fn add_start(&mut self, hook: StartHook<T>) {
+        self.start.push(hook);
+    }
+
+    pub(crate) fn add_progress(&mut self, hook: ProgressHook) {
+        self.progress.push(hook);
+    }
+
+    pub(crate) fn add_value(&mut self, hook: ValueHook<T>) {
+        self.value.push(hook);
+    }
+
+    pub(crate) fn add_complete(&mut self, hook: CompleteHook<T>) {
+        self.complete.push(hook);
+    }
+
+    pub(crate) fn start(&mut self, config: &Config<T>) {
+        self.start.iter_mut().for_each(|hook| hook(config));
+    }
+
+    pub(crate) fn progress(&mut self, done: usize, total: usize) {
+        self.progress.iter_mut().for_each(|hook| hook(done, total));
+    }
+
+    pub(crate) fn values(&mut self, values: &[T]) {
+        if self.value.is_empty() {
+            return;
+        }
+        for &value in values {
+            self.value.iter_mut().for_each(|hook| hook(value));
+        }
+    }
+
+    pub(crate) fn complete(&mut self, result: Result<&[T], &RandomGeneratorError>) {
+        self.complete.iter_mut().for_each(|hook| hook(result));
+    }
+}
+
+impl<T: Number> Default for Hooks<T> {
+    fn default() -> Self {
+        Self {
+            start: Vec::new(),
+            progress: Vec::new(),
+            value: Vec::new(),
+            complete: Vec::new(),
+        }
+    }
+}
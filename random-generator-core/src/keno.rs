@@ -0,0 +1,109 @@
+use rand::seq::index;
+use std::collections::HashMap;
+
+use crate::{RandomGeneratorError, Seed};
+
+/// 每张票最多选的号码数
+const MAX_PICKS: usize = 100;
+
+/// 一张机选票
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KenoTicket {
+    /// 选中的号码,从小到大
+    pub numbers: Vec<u32>,
+    /// 与本批中更早的哪张票号码完全相同,从 0 开始
+    pub duplicate_of: Option<usize>,
+}
+
+/// 基诺(Keno)一类“从 1 到 N 中选 K 个”的机选
+///
+/// 每张票的号码互不相同;一批中号码完全相同的票会标出来,而不是重新抽取,
+/// 这样每张票仍然是独立抽取的
+///
+/// ```
+/// use random_generator_core::{KenoSpec, Seed};
+///
+/// let spec = KenoSpec { pool: 80, picks: 10 };
+/// let tickets = spec.tickets(5, Seed::from([7; 32]))?;
+/// assert_eq!(tickets.len(), 5);
+/// assert!(tickets.iter().all(|t| t.numbers.len() == 10 && t.numbers.iter().all(|n| (1..=80).contains(n))));
+/// # Ok::<(), random_generator_core::RandomGeneratorError>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KenoSpec {
+    /// 号码从 1 到 `pool`
+    pub pool: u32,
+    /// 每张票选几个号码
+    pub picks: usize,
+}
+
+impl Default for KenoSpec {
+    fn default() -> Self {
+        KenoSpec { pool: 80, picks: 10 }
+    }
+}
+
+impl KenoSpec {
+    /// 检查号码范围和每张票的号码数
+    pub fn validate(&self) -> Result<(), RandomGeneratorError> {
+        if self.pool == 0 {
+            return Err(RandomGeneratorError::InvalidGame("the pool needs at least 1 number".to_string()));
+        }
+        if !(1..=MAX_PICKS).contains(&self.picks) {
+            return Err(RandomGeneratorError::InvalidGame(format!("pick 1 to {} numbers per ticket", MAX_PICKS)));
+        }
+        if self.picks > self.pool as usize {
+            return Err(RandomGeneratorError::InvalidGame(format!("can't pick {} numbers from a pool of {}", self.picks, self.pool)));
+        }
+        Ok(())
+    }
+
+    /// 用种子生成 `count` 张票,并标出重复的票
+    pub fn tickets(&self, count: usize, seed: Seed) -> Result<Vec<KenoTicket>, RandomGeneratorError> {
+        self.validate()?;
+        let mut rng = seed.rng();
+        let mut first = HashMap::with_capacity(count);
+        Ok((0..count)
+            .map(|i| {
+                let mut numbers: Vec<u32> =
+                    index::sample(&mut rng, self.pool as usize, self.picks).into_iter().map(|n| n as u32 + 1).collect();
+                numbers.sort_unstable();
+                let duplicate_of = first.get(&numbers).copied();
+                if duplicate_of.is_none() {
+                    first.insert(numbers.clone(), i);
+                }
+                KenoTicket { numbers, duplicate_of }
+            })
+            .collect())
+    }
+
+    /// 每张票一行,前面是票号,从 1 开始;号码按范围的位数补零对齐,重复的票注明与哪张相同
+    pub fn to_text(&self, tickets: &[KenoTicket]) -> String {
+        let width = self.pool.to_string().len();
+        let number_width = tickets.len().to_string().len();
+        tickets
+            .iter()
+            .enumerate()
+            .map(|(i, ticket)| {
+                let numbers: Vec<String> = ticket.numbers.iter().map(|n| format!("{:0width$}", n, width = width)).collect();
+                let line = format!("{:>number_width$}. {}", i + 1, numbers.join(" "), number_width = number_width);
+                match ticket.duplicate_of {
+                    Some(first) => format!("{}  (same as ticket {})\n", line, first + 1),
+                    None => format!("{}\n", line),
+                }
+            })
+            .collect()
+    }
+
+    /// 每张票一行的 CSV,带表头 `ticket,n1,…,same_as`;不重复的票 `same_as` 为空
+    pub fn to_csv(&self, tickets: &[KenoTicket]) -> String {
+        let columns: Vec<String> = (1..=self.picks).map(|i| format!("n{}", i)).collect();
+        let mut csv = format!("ticket,{},same_as\n", columns.join(","));
+        for (i, ticket) in tickets.iter().enumerate() {
+            let numbers: Vec<String> = ticket.numbers.iter().map(u32::to_string).collect();
+            let same_as = ticket.duplicate_of.map(|first| (first + 1).to_string()).unwrap_or_default();
+            csv.push_str(&format!("{},{},{}\n", i + 1, numbers.join(","), same_as));
+        }
+        csv
+    }
+}
@@ -0,0 +1,100 @@
+use std::fmt;
+
+use crate::RandomGeneratorError;
+
+/// 把结果换成名字的对照表,例如 1 换成“红队”,2 换成“蓝队”
+///
+/// 每行写一项,形如 `1: red team`,也可以用 `1..3: low` 给一段整数同一个名字;
+/// 空行和 `#` 开头的行会被跳过。表中没有的值照常显示为数字
+///
+/// ```
+/// use random_generator_core::{LabelMap, NumberFormat};
+///
+/// let labels = LabelMap::parse("1: red team\n2: blue team\n3..5: spectators")?;
+/// let format = NumberFormat { labels, ..Default::default() };
+/// assert_eq!(format.format(2), "blue team");
+/// assert_eq!(format.format(4), "spectators");
+/// assert_eq!(format.format(6), "6");
+/// # Ok::<(), random_generator_core::RandomGeneratorError>(())
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LabelMap {
+    /// 每项的最小值、最大值(含)和名字,按书写的先后
+    entries: Vec<(i64, i64, String)>,
+}
+
+impl LabelMap {
+    /// 由文本创建,每行一项;出错时指出是第几行
+    pub fn parse(text: &str) -> Result<Self, RandomGeneratorError> {
+        let mut labels = LabelMap::default();
+        let mut lines = Vec::new();
+        for (index, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let invalid = |message: String| RandomGeneratorError::InvalidLabels { line: index + 1, message };
+            let (values, label) = line.split_once(':').ok_or_else(|| invalid("write a number, a ':' and the label, like 1: red team".to_string()))?;
+            let number = |value: &str| value.trim().parse::<i64>().map_err(|_| invalid(format!("'{}' is not a whole number", value.trim())));
+            let (lower, upper) = match values.split_once("..") {
+                Some((lower, upper)) => (number(lower)?, number(upper)?),
+                None => (number(values)?, number(values)?),
+            };
+            if lower > upper {
+                return Err(invalid(format!("{}..{} goes backwards", lower, upper)));
+            }
+            let label = label.trim();
+            if label.is_empty() {
+                return Err(invalid(format!("{} needs a label after the ':'", values.trim())));
+            }
+            if let Some(other) = labels.entries.iter().position(|&(low, high, _)| lower <= high && low <= upper) {
+                return Err(invalid(format!("{} already has a label on line {}", values.trim(), lines[other])));
+            }
+            labels.entries.push((lower, upper, label.to_string()));
+            lines.push(index + 1);
+        }
+        Ok(labels)
+    }
+
+    /// 按先后给名字编号,第一个名字是 1;空的名字跳过但仍占一个编号
+    pub fn numbered(names: &[String]) -> Self {
+        let entries = (1..)
+            .zip(names)
+            .map(|(number, name)| (number, number, name.trim().to_string()))
+            .filter(|(_, _, name)| !name.is_empty())
+            .collect();
+        LabelMap { entries }
+    }
+
+    /// 没有任何一项
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// 项数;一段整数算一项
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// 值对应的名字;不是整数或表中没有时为 `None`
+    pub fn get<T: fmt::Display>(&self, value: T) -> Option<&str> {
+        if self.entries.is_empty() {
+            return None;
+        }
+        let value = value.to_string().parse::<i64>().ok()?;
+        self.entries.iter().find(|&&(lower, upper, _)| (lower..=upper).contains(&value)).map(|(_, _, label)| label.as_str())
+    }
+}
+
+impl fmt::Display for LabelMap {
+    /// 写回 [`LabelMap::parse`] 能读的形式,每项一行
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (lower, upper, label) in &self.entries {
+            match lower == upper {
+                true => writeln!(f, "{}: {}", lower, label)?,
+                false => writeln!(f, "{}..{}: {}", lower, upper, label)?,
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,3859 @@
+//! 随机数生成核心库，不依赖任何图形界面。
+//!
+//! 按 [`GeneratorConfig`] 在整数区间或自定义列表中抽取数字，可选是否允许重复，
+//! 并支持进度回报、取消以及以文本、CSV 或 JSON 导出结果。
+//! [`RandomGenerator`] 输出 `i64`；其他数字类型（`u64`、`i128`、`f64`）使用泛型的
+//! [`Generator`] 和 [`Config`]，抽取逻辑完全相同。
+//! 每种模式都是一个 [`GeneratorSource`]，实现该 trait 并用
+//! [`Generator::register_source`] 注册即可增加新的模式。
+//! 导出格式同样可以通过实现 [`Exporter`] 扩展。
+//! 除数字外，[`FieldSchema`] 还能按所选字段生成姓名、邮箱、电话、地址等测试数据，
+//! [`StringPattern`] 按 `AA-####` 这样的模式生成序列号等字符串。
+//! 所有失败都以 [`RandomGeneratorError`] 返回。
+//!
+//! ```
+//! use random_generator_core::{GeneratorConfig, RandomGenerator};
+//!
+//! let mut generator = RandomGenerator::with_config(GeneratorConfig {
+//!     lower_bound: 1,
+//!     upper_bound: 6,
+//!     num_to_generate: 3,
+//!     ..Default::default()
+//! })?;
+//! generator.generate_numbers()?;
+//! assert_eq!(generator.get_numbers().len(), 3);
+//! # Ok::<(), random_generator_core::RandomGeneratorError>(())
+//! ```
+//!
+//! 启用 `serde` 特性后，配置用到的枚举以及洗牌袋、冷却和排除集合的状态可以序列化；
+//! 启用 `plugins` 特性后，可以用 [`Plugin`] 从动态库加载第三方的模式和导出格式；
+//! 启用 `schema` 特性后，可以用 [`JsonSchema`] 按 JSON Schema 生成随机 JSON 文档。
+
+#![warn(missing_docs)]
+
+use rand::seq::SliceRandom;
+use rand::{RngCore, SeedableRng};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::error::Error;
+use std::fmt;
+use std::ops::Range;
+use std::sync::atomic::{AtomicBool, Ordering};
+use regex::Regex;
+
+mod bag;
+mod benchmark;
+mod bootstrap;
+mod bracket;
+mod bytes;
+mod codes;
+mod columns;
+mod compare;
+mod cooldown;
+mod design;
+mod distribution;
+mod draw_space;
+mod dice_pool;
+mod exclusion;
+mod export;
+mod faker;
+mod formula;
+mod gacha;
+mod graph;
+mod hooks;
+mod keno;
+mod labels;
+mod merge;
+mod line_sample;
+mod network;
+mod matching;
+mod monte_carlo;
+mod multivariate;
+mod number;
+mod pairing;
+mod partition;
+mod passphrase;
+mod paste;
+mod pattern;
+mod primes;
+mod santa;
+#[cfg(feature = "plugins")]
+pub mod plugin;
+#[cfg(feature = "schema")]
+mod schema;
+mod script;
+mod seed;
+mod selftest;
+mod source;
+mod strata;
+mod tickets;
+mod transform;
+mod trials;
+mod walk;
+
+pub use bag::ShuffleBag;
+pub use benchmark::{BenchmarkReport, BenchmarkResult};
+pub use bootstrap::{Bootstrap, BootstrapReport};
+pub use bracket::{Bracket, BracketMatch};
+pub use bytes::{ByteSize, ByteUnit, RandomBytes};
+pub use codes::{CheckCharacter, CodeSpec, PinSpec, CODE_ALPHABET};
+pub use columns::{Column, ColumnSchema, ColumnValues};
+pub use compare::Comparison;
+pub use cooldown::Cooldown;
+pub use design::{DesignKind, DesignTable, ExperimentDesign};
+pub use draw_space::DrawSpace;
+pub use distribution::{CdfSample, CdfTable, Dirichlet, DirichletSample, Distribution, DistributionKind, DistributionReport, TruncatedDistribution, MAX_CDF_POINTS, MAX_COMPONENTS};
+pub use dice_pool::{DicePool, PoolDie, PoolRoll};
+pub use exclusion::ExclusionSet;
+pub use export::{Exporter, GroupHeader};
+pub use faker::{FakeField, FakeLocale, FieldSchema};
+pub use formula::Formula;
+pub use gacha::{GachaReport, LootTable, RarityTier, TierResult};
+pub use graph::{Graph, GraphModel, RandomGraph};
+use hooks::Hooks;
+pub use keno::{KenoSpec, KenoTicket};
+pub use labels::LabelMap;
+pub use merge::MergedResults;
+pub use line_sample::{LineSample, SampledLines};
+pub use monte_carlo::{MonteCarlo, MonteCarloReport, MonteCarloTarget};
+pub use multivariate::{MultivariateNormal, MultivariateSample, MAX_DIMENSIONS};
+pub use network::{AddressKind, AddressSpec};
+pub use number::Number;
+pub use pairing::{Pair, Pairing};
+pub use partition::PartitionSource;
+pub use passphrase::{Capitalization, PassphraseSpec, Wordlist};
+pub use paste::PastedList;
+pub use pattern::StringPattern;
+pub use primes::{is_prime, PrimeSource};
+pub use santa::{Assignment, SecretSanta};
+pub use tickets::TicketSource;
+#[cfg(feature = "plugins")]
+pub use plugin::Plugin;
+#[cfg(feature = "schema")]
+pub use schema::JsonSchema;
+pub use script::{Script, ScriptSource};
+pub use seed::Seed;
+pub use selftest::{ChiSquared, RunsTest, SelfTestReport};
+pub use source::{GeneratorMode, GeneratorSource, ListSource, ModeRegistry, MAX_POOL, RangeSource, SourceInput, UniqueStrategy};
+use source::RepeatLimits;
+pub use strata::{StratifiedSource, Strata, Stratum, StratumValues};
+pub use transform::Transform;
+pub use trials::{GeometricSource, NegativeBinomialSource, MAX_SUCCESSES};
+pub use walk::{RandomWalk, StepDistribution};
+
+/// 生成过程中回报进度的间隔(迭代次数)
+pub(crate) const PROGRESS_INTERVAL: usize = 1 << 16;
+
+/// 一次最多生成的数字个数;数量来自深层链接、FFI 或守护进程时,过大的数量会耗尽内存
+pub const MAX_COUNT: usize = 10_000_000;
+
+/// 自定义错误类型
+#[derive(Debug)]
+pub enum RandomGeneratorError {
+    /// 下界大于上界
+    InvalidBounds,
+    /// 不允许重复时,要求的数量超过可选数字的个数
+    TooManyNumbers,
+    /// 要求的数量超过一次最多生成的 [`MAX_COUNT`] 个
+    CountTooLarge,
+    /// 读写文件失败
+    IoError(std::io::Error),
+    /// 自定义列表中有无法解析的数字
+    InvalidInputFormat,
+    /// 自定义列表为空
+    EmptyList,
+    /// 生成被取消
+    Cancelled,
+    /// 没有注册这种模式
+    UnknownMode(GeneratorMode),
+    /// 自定义列表有重复的数字,而重复策略要求拒绝
+    DuplicateEntries,
+    /// 种子不是 64 位十六进制文本
+    InvalidSeed,
+    /// 抽取脚本有误
+    InvalidScript {
+        /// 出错的是第几行,从 1 开始;0 表示整个脚本
+        line: usize,
+        /// 说明
+        message: String,
+    },
+    /// 分层抽样的设置有误,或某一层抽不够个数
+    InvalidStrata {
+        /// 出错的是第几行,从 1 开始;0 表示整个设置
+        line: usize,
+        /// 说明
+        message: String,
+    },
+    /// 没有注册这种导出格式
+    UnknownFormat(String),
+    /// 插件加载或运行失败
+    Plugin(String),
+    /// 测试数据的字段表有误
+    InvalidSchema(String),
+    /// 字符串模式有误,或匹配的字符串不够多
+    InvalidPattern(String),
+    /// 兑换码、PIN 或口令的设置有误,或能用的不够多
+    InvalidCode(String),
+    /// 文件大小写法有误
+    InvalidSize(String),
+    /// MAC 前缀或 IP 网段有误,或网段中的地址不够多
+    InvalidAddress(String),
+    /// 区间不适合抽取素数,或其中的素数不够多
+    InvalidPrimes(String),
+    /// 名单或抽签的限制有误,或限制多到无法抽签
+    InvalidDraw(String),
+    /// 游戏模拟(基诺机选、抽卡掉落表、骰池等)的设置有误
+    InvalidGame(String),
+    /// 自助法重抽样的数据或次数有误
+    InvalidBootstrap(String),
+    /// 随机游走或时间序列的设置有误
+    InvalidWalk(String),
+    /// 算式写法有误
+    InvalidFormula(String),
+    /// 蒙特卡洛估计的设置有误,或被积函数在区间中没有定义
+    InvalidMonteCarlo(String),
+    /// 总和无法按份数和上下限拆分
+    InvalidPartition(String),
+    /// 从文件抽取行的设置有误
+    InvalidLineSample(String),
+    /// 随机图的节点数、边数或模型有误
+    InvalidGraph(String),
+    /// 拉丁方或区组设计的处理或区组数有误
+    InvalidDesign(String),
+    /// 分布的参数或截断区间有误
+    InvalidDistribution(String),
+    /// 几何分布或负二项分布的成功机会或成功次数有误
+    InvalidTrials(String),
+    /// 结果的变换写错了,或变换后的值超出数字类型的范围
+    InvalidTransform(String),
+    /// 多列测试数据的某一列写错了
+    InvalidColumns {
+        /// 出错的是第几行,从 1 开始;0 表示整个设置
+        line: usize,
+        /// 说明
+        message: String,
+    },
+    /// 分组导出的标题模板有误
+    InvalidGroupHeader(String),
+    /// 结果的名字对照表某一行写错了
+    InvalidLabels {
+        /// 出错的是第几行,从 1 开始
+        line: usize,
+        /// 说明
+        message: String,
+    },
+    /// 保存的结果文件读不回来
+    InvalidResultsFile {
+        /// 出错的是第几行,从 1 开始;0 表示整个文件
+        line: usize,
+        /// 说明
+        message: String,
+    },
+    /// 配置同时有多个问题
+    Multiple(Vec<RandomGeneratorError>),
+}
+
+impl fmt::Display for RandomGeneratorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RandomGeneratorError::InvalidBounds => write!(f, "The lower bound must be less than or equal to the upper bound"),
+            RandomGeneratorError::TooManyNumbers => write!(f, "The number of requested numbers exceeds the range size"),
+            RandomGeneratorError::CountTooLarge => write!(f, "At most {} numbers can be generated at once", MAX_COUNT),
+            RandomGeneratorError::IoError(e) => write!(f, "IO Error: {}", e),
+            RandomGeneratorError::InvalidInputFormat => write!(f, "Invalid input format for custom list"),
+            RandomGeneratorError::EmptyList => write!(f, "Custom list cannot be empty"),
+            RandomGeneratorError::Cancelled => write!(f, "Generation cancelled"),
+            RandomGeneratorError::DuplicateEntries => write!(f, "Custom list contains repeated numbers"),
+            RandomGeneratorError::InvalidSeed => write!(f, "A seed must be 64 hexadecimal digits"),
+            RandomGeneratorError::InvalidScript { line: 0, message } => write!(f, "Script: {}", message),
+            RandomGeneratorError::InvalidScript { line, message } => write!(f, "Script line {}: {}", line, message),
+            RandomGeneratorError::InvalidStrata { line: 0, message } => write!(f, "Strata: {}", message),
+            RandomGeneratorError::InvalidStrata { line, message } => write!(f, "Strata line {}: {}", line, message),
+            RandomGeneratorError::UnknownMode(mode) => write!(f, "Unknown generator mode: {}", mode),
+            RandomGeneratorError::UnknownFormat(name) => write!(f, "Unknown export format: {}", name),
+            RandomGeneratorError::Plugin(message) => write!(f, "Plugin: {}", message),
+            RandomGeneratorError::InvalidSchema(message) => write!(f, "Schema: {}", message),
+            RandomGeneratorError::InvalidPattern(message) => write!(f, "Pattern: {}", message),
+            RandomGeneratorError::InvalidCode(message) => write!(f, "Codes: {}", message),
+            RandomGeneratorError::InvalidSize(message) => write!(f, "Size: {}", message),
+            RandomGeneratorError::InvalidAddress(message) => write!(f, "Address: {}", message),
+            RandomGeneratorError::InvalidPrimes(message) => write!(f, "Primes: {}", message),
+            RandomGeneratorError::InvalidDraw(message) => write!(f, "Draw: {}", message),
+            RandomGeneratorError::InvalidGame(message) => write!(f, "Game: {}", message),
+            RandomGeneratorError::InvalidBootstrap(message) => write!(f, "Bootstrap: {}", message),
+            RandomGeneratorError::InvalidWalk(message) => write!(f, "Walk: {}", message),
+            RandomGeneratorError::InvalidFormula(message) => write!(f, "Formula: {}", message),
+            RandomGeneratorError::InvalidMonteCarlo(message) => write!(f, "Monte Carlo: {}", message),
+            RandomGeneratorError::InvalidPartition(message) => write!(f, "Partition: {}", message),
+            RandomGeneratorError::InvalidLineSample(message) => write!(f, "Line sample: {}", message),
+            RandomGeneratorError::InvalidGraph(message) => write!(f, "Graph: {}", message),
+            RandomGeneratorError::InvalidDesign(message) => write!(f, "Design: {}", message),
+            RandomGeneratorError::InvalidDistribution(message) => write!(f, "Distribution: {}", message),
+            RandomGeneratorError::InvalidTrials(message) => write!(f, "Trials: {}", message),
+            RandomGeneratorError::InvalidTransform(message) => write!(f, "Transform: {}", message),
+            RandomGeneratorError::InvalidColumns { line: 0, message } => write!(f, "Columns: {}", message),
+            RandomGeneratorError::InvalidColumns { line, message } => write!(f, "Columns line {}: {}", line, message),
+            RandomGeneratorError::InvalidGroupHeader(message) => write!(f, "Group header: {}", message),
+            RandomGeneratorError::InvalidLabels { line, message } => write!(f, "Labels line {}: {}", line, message),
+            RandomGeneratorError::InvalidResultsFile { line: 0, message } => write!(f, "Results file: {}", message),
+            RandomGeneratorError::InvalidResultsFile { line, message } => write!(f, "Results file line {}: {}", line, message),
+            RandomGeneratorError::Multiple(errors) => {
+                let messages: Vec<String> = errors.iter().map(|e| e.to_string()).collect();
+                write!(f, "{}", messages.join("; "))
+            }
+        }
+    }
+}
+
+impl RandomGeneratorError {
+    /// 逐个列出包含的错误;`Multiple` 展开为其中的每一个,其他错误只有自身
+    pub fn errors(&self) -> &[RandomGeneratorError] {
+        match self {
+            RandomGeneratorError::Multiple(errors) => errors,
+            single => std::slice::from_ref(single),
+        }
+    }
+}
+
+impl Error for RandomGeneratorError {}
+
+impl From<std::io::Error> for RandomGeneratorError {
+    fn from(error: std::io::Error) -> Self {
+        RandomGeneratorError::IoError(error)
+    }
+}
+
+/// 千位分隔符
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ThousandsSeparator {
+    /// 1234567
+    #[default]
+    None,
+    /// 1,234,567
+    Comma,
+    /// 1.234.567
+    Period,
+    /// 1 234 567
+    Space,
+    /// 1'234'567
+    Apostrophe,
+}
+
+impl ThousandsSeparator {
+    /// 所有可选的分隔符
+    pub const ALL: [ThousandsSeparator; 5] = [
+        ThousandsSeparator::None,
+        ThousandsSeparator::Comma,
+        ThousandsSeparator::Period,
+        ThousandsSeparator::Space,
+        ThousandsSeparator::Apostrophe,
+    ];
+
+    fn as_char(self) -> Option<char> {
+        match self {
+            ThousandsSeparator::None => None,
+            ThousandsSeparator::Comma => Some(','),
+            ThousandsSeparator::Period => Some('.'),
+            ThousandsSeparator::Space => Some(' '),
+            ThousandsSeparator::Apostrophe => Some('\''),
+        }
+    }
+}
+
+impl fmt::Display for ThousandsSeparator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ThousandsSeparator::None => write!(f, "None"),
+            ThousandsSeparator::Comma => write!(f, "1,000"),
+            ThousandsSeparator::Period => write!(f, "1.000"),
+            ThousandsSeparator::Space => write!(f, "1 000"),
+            ThousandsSeparator::Apostrophe => write!(f, "1'000"),
+        }
+    }
+}
+
+/// 数字的显示与导出格式
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NumberFormat {
+    /// 千位分隔符
+    pub separator: ThousandsSeparator,
+    /// 最少数字位数,不足时补零;0 表示不补零
+    pub min_width: usize,
+    /// 有名字的值显示为名字,不再补零和分组
+    pub labels: LabelMap,
+    /// 在每个结果前加上它是第几个,例如 `#1: 4821`;CSV 本来就有序号列,JSON 不受影响
+    pub numbered: bool,
+}
+
+impl NumberFormat {
+    /// 按当前格式把数字转换为文本
+    ///
+    /// 补零和分组只作用于整数部分;以句点分组时小数点改用逗号
+    pub fn format<T: fmt::Display>(&self, value: T) -> String {
+        if let Some(label) = self.labels.get(&value) {
+            return label.to_string();
+        }
+        let text = value.to_string();
+        let (sign, unsigned) = match text.strip_prefix('-') {
+            Some(rest) => ("-", rest),
+            None => ("", text.as_str()),
+        };
+        let (integer, fraction) = match unsigned.split_once('.') {
+            Some((integer, fraction)) => (integer, Some(fraction)),
+            None => (unsigned, None),
+        };
+        let digits = format!("{:0>width$}", integer, width = self.min_width);
+
+        let separator = self.separator.as_char();
+        let mut result = String::with_capacity(digits.len() + digits.len() / 3 + 1);
+        result.push_str(sign);
+        for (i, c) in digits.chars().enumerate() {
+            if let Some(separator) = separator {
+                if i > 0 && (digits.len() - i) % 3 == 0 {
+                    result.push(separator);
+                }
+            }
+            result.push(c);
+        }
+        if let Some(fraction) = fraction {
+            result.push(if separator == Some('.') { ',' } else { '.' });
+            result.push_str(fraction);
+        }
+        result
+    }
+
+    /// 第 `index` 个(从 0 开始)结果的文本,开启 [`numbered`](Self::numbered) 时带序号
+    pub fn format_at<T: fmt::Display>(&self, index: usize, value: T) -> String {
+        self.number(index, self.format(value))
+    }
+
+    /// 开启 [`numbered`](Self::numbered) 时在第 `index` 个结果已经格式化的文本前加上序号
+    pub fn number(&self, index: usize, text: String) -> String {
+        match self.numbered {
+            true => format!("#{}: {}", index + 1, text),
+            false => text,
+        }
+    }
+}
+
+/// 随机数来源
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum RngBackend {
+    /// 线程本地的 ChaCha 生成器,定期从系统重新播种
+    #[default]
+    Thread,
+    /// 每次都直接向操作系统索取随机数,最慢但不保留任何状态
+    Os,
+    /// 每次生成时从系统熵播种一个新的 StdRng
+    Std,
+}
+
+impl RngBackend {
+    /// 所有可选的随机数来源
+    pub const ALL: [RngBackend; 3] = [RngBackend::Thread, RngBackend::Os, RngBackend::Std];
+
+    fn create(self) -> Box<dyn RngCore + Send + Sync> {
+        match self {
+            RngBackend::Thread => Box::new(CurrentThreadRng),
+            RngBackend::Os => Box::new(rand::rngs::OsRng),
+            RngBackend::Std => Box::new(rand::rngs::StdRng::from_entropy()),
+        }
+    }
+}
+
+/// 每次调用时使用当前线程的 `ThreadRng`
+///
+/// `ThreadRng` 不能跨线程传递,这里不保存它,生成器因此可以在线程间移动和共享
+struct CurrentThreadRng;
+
+impl RngCore for CurrentThreadRng {
+    fn next_u32(&mut self) -> u32 {
+        rand::thread_rng().next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        rand::thread_rng().next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        rand::thread_rng().fill_bytes(dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        rand::thread_rng().try_fill_bytes(dest)
+    }
+}
+
+impl fmt::Display for RngBackend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RngBackend::Thread => write!(f, "Thread (ChaCha)"),
+            RngBackend::Os => write!(f, "Operating system"),
+            RngBackend::Std => write!(f, "StdRng"),
+        }
+    }
+}
+
+/// 保存结果时使用的文件格式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ExportFormat {
+    /// 每行一个数字
+    #[default]
+    Text,
+    /// 带表头的 index,value 两列
+    Csv,
+    /// 整数数组;JSON 数字不能带分隔符,因此忽略数字格式
+    Json,
+}
+
+impl ExportFormat {
+    /// 所有可选的导出格式
+    pub const ALL: [ExportFormat; 3] = [ExportFormat::Text, ExportFormat::Csv, ExportFormat::Json];
+
+    /// 对应的文件扩展名
+    pub fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::Text => "txt",
+            ExportFormat::Csv => "csv",
+            ExportFormat::Json => "json",
+        }
+    }
+
+    /// 一组结果的文件内容
+    pub fn render<T: Number>(self, numbers: &[T], format: &NumberFormat) -> String {
+        match self {
+            ExportFormat::Text => numbers
+                .iter()
+                .enumerate()
+                .map(|(i, &num)| format.format_at(i, num))
+                .collect::<Vec<String>>()
+                .join("\n"),
+            ExportFormat::Csv => {
+                let mut content = String::from("index,value\n");
+                for (i, &num) in numbers.iter().enumerate() {
+                    // 分隔符和名字里可能有逗号,需要加引号
+                    content.push_str(&format!("{},{}\n", i + 1, faker::csv_cell(&format.format(num))));
+                }
+                content
+            }
+            ExportFormat::Json => json_values(numbers, format),
+        }
+    }
+
+    /// 几组结果写进同一个文件的内容,每组有 `header` 生成的标题
+    ///
+    /// 文本中每组前面一行标题,组与组之间空一行;CSV 多一列 `group`,序号在组内从 1 数起;
+    /// JSON 是 `{"group": 标题, "numbers": [...]}` 的数组
+    pub fn render_groups<T: Number>(self, groups: &[Vec<T>], header: &GroupHeader, format: &NumberFormat) -> String {
+        let headers = groups.iter().enumerate().map(|(i, numbers)| header.header(i + 1, numbers.len()));
+        match self {
+            ExportFormat::Text => headers
+                .zip(groups)
+                .map(|(header, numbers)| format!("{}\n{}", header, self.render(numbers, format)))
+                .collect::<Vec<String>>()
+                .join("\n\n"),
+            ExportFormat::Csv => {
+                let mut content = String::from("group,index,value\n");
+                for (header, numbers) in headers.zip(groups) {
+                    let header = faker::csv_cell(&header);
+                    for (i, &num) in numbers.iter().enumerate() {
+                        content.push_str(&format!("{},{},{}\n", header, i + 1, faker::csv_cell(&format.format(num))));
+                    }
+                }
+                content
+            }
+            ExportFormat::Json => format!(
+                "[{}]",
+                headers
+                    .zip(groups)
+                    .map(|(header, numbers)| format!("{{\"group\": {}, \"numbers\": {}}}", faker::json_string(&header), json_values(numbers, format)))
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ),
+        }
+    }
+}
+
+/// JSON 数组;有名字的值写成字符串,其余仍是数字
+fn json_values<T: Number>(numbers: &[T], format: &NumberFormat) -> String {
+    let values: Vec<String> = numbers.iter().map(|num| format.labels.get(num).map_or_else(|| num.to_string(), faker::json_string)).collect();
+    format!("[{}]", values.join(", "))
+}
+
+impl fmt::Display for ExportFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExportFormat::Text => write!(f, "Text"),
+            ExportFormat::Csv => write!(f, "CSV"),
+            ExportFormat::Json => write!(f, "JSON"),
+        }
+    }
+}
+
+/// 结果的排列顺序
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ResultOrder {
+    /// 按抽出的先后,第一个抽出的排在最前
+    #[default]
+    Drawn,
+    /// 从小到大
+    Sorted,
+}
+
+impl ResultOrder {
+    /// 所有可选的顺序
+    pub const ALL: [ResultOrder; 2] = [ResultOrder::Drawn, ResultOrder::Sorted];
+}
+
+impl fmt::Display for ResultOrder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ResultOrder::Drawn => write!(f, "Draw order"),
+            ResultOrder::Sorted => write!(f, "Sorted"),
+        }
+    }
+}
+
+/// 对已经生成的结果重新排列,不重新抽取
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rearrange {
+    /// 从小到大
+    SortAscending,
+    /// 从大到小
+    SortDescending,
+    /// 随机打乱
+    Shuffle,
+    /// 倒过来
+    Reverse,
+}
+
+impl Rearrange {
+    /// 所有的排列方式
+    pub const ALL: [Rearrange; 4] = [Rearrange::SortAscending, Rearrange::SortDescending, Rearrange::Shuffle, Rearrange::Reverse];
+}
+
+impl fmt::Display for Rearrange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Rearrange::SortAscending => write!(f, "Sort ascending"),
+            Rearrange::SortDescending => write!(f, "Sort descending"),
+            Rearrange::Shuffle => write!(f, "Shuffle"),
+            Rearrange::Reverse => write!(f, "Reverse"),
+        }
+    }
+}
+
+/// 自定义列表中重复数字的处理方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ListDuplicates {
+    /// 重复几次就有几倍的机会被抽中;不允许重复时每个值仍只出现一次
+    #[default]
+    Weighted,
+    /// 先去掉重复,每个值机会均等
+    Dedupe,
+    /// 列表有重复时报错
+    Reject,
+}
+
+impl ListDuplicates {
+    /// 所有可选的处理方式
+    pub const ALL: [ListDuplicates; 3] = [ListDuplicates::Weighted, ListDuplicates::Dedupe, ListDuplicates::Reject];
+}
+
+impl fmt::Display for ListDuplicates {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ListDuplicates::Weighted => write!(f, "Count as weight"),
+            ListDuplicates::Dedupe => write!(f, "Remove repeats"),
+            ListDuplicates::Reject => write!(f, "Reject the list"),
+        }
+    }
+}
+
+/// 输出 `i64` 的生成器配置
+pub type GeneratorConfig = Config<i64>;
+
+/// 随机数生成器配置,`T` 是输出的数字类型
+#[derive(Debug, Clone)]
+pub struct Config<T: Number> {
+    /// 区间下界(包含)
+    pub lower_bound: T,
+    /// 区间上界(包含)
+    pub upper_bound: T,
+    /// 要生成的数量
+    pub num_to_generate: usize,
+    /// 是否允许重复
+    pub allow_duplicates: bool,
+    /// 允许重复时每个值在一次生成中最多出现几次;`None` 表示不限
+    pub max_repeats: Option<usize>,
+    /// 允许重复时同一个值最多连续出现几次;`None` 表示不限。结果需要按抽出的先后排列
+    pub max_streak: Option<usize>,
+    /// 生成器模式
+    pub mode: GeneratorMode,
+    /// 解析后的自定义列表
+    pub custom_list: Vec<T>,
+    /// 自定义列表的原始输入
+    pub custom_list_input: String,
+    /// 自定义列表中不是数字的项跳过而不报错,跳过的项见 [`Generator::skipped_list_items`]
+    pub lenient_list: bool,
+    /// 自定义列表中重复数字的处理方式
+    pub list_duplicates: ListDuplicates,
+    /// 随机数来源,每次生成从中取出新的种子
+    pub rng_backend: RngBackend,
+    /// 结果的排列顺序
+    pub order: ResultOrder,
+    /// 生成后依次对每个结果做的变换,在排序之前;见 [`Transform`]
+    pub transforms: Vec<Transform<T>>,
+    /// 固定的种子;设置后每次生成的结果都相同,用于重现以前的抽取
+    pub seed: Option<Seed>,
+    /// 设置后从洗牌袋中抽取,每次生成后更新为抽剩的状态;`None` 表示每次独立抽取
+    pub shuffle_bag: Option<ShuffleBag<T>>,
+    /// 设置后最近抽到的值暂时不会再被抽到,每次生成后更新;`None` 表示没有冷却
+    pub cooldown: Option<Cooldown<T>>,
+    /// 设置后集合中的值不会被抽到,每次生成后加入新抽到的值;只用于区间和发票抽奖模式,`None` 表示不排除
+    pub exclude_drawn: Option<ExclusionSet<T>>,
+}
+
+impl<T: Number> Default for Config<T> {
+    fn default() -> Self {
+        Self {
+            lower_bound: T::DEFAULT_LOWER,
+            upper_bound: T::DEFAULT_UPPER,
+            num_to_generate: 1,
+            allow_duplicates: false,
+            max_repeats: None,
+            max_streak: None,
+            mode: GeneratorMode::RANGE,
+            custom_list: Vec::new(),
+            custom_list_input: String::new(),
+            lenient_list: false,
+            list_duplicates: ListDuplicates::default(),
+            rng_backend: RngBackend::default(),
+            order: ResultOrder::default(),
+            transforms: Vec::new(),
+            seed: None,
+            shuffle_bag: None,
+            cooldown: None,
+            exclude_drawn: None,
+        }
+    }
+}
+
+/// 输出 `i64` 的随机数生成器
+pub type RandomGenerator = Generator<i64>;
+
+/// 优化后的随机数生成器
+///
+/// 同一套抽取逻辑适用于所有实现了 [`Number`] 的类型。
+/// 生成器是 `Send + Sync` 的,可以移动到工作线程,或放进 `Arc<Mutex<_>>` 在线程间共享
+pub struct Generator<T: Number> {
+    core_version: String,
+    config: Config<T>,
+    generated_numbers: Vec<T>,
+    rng: Box<dyn RngCore + Send + Sync>,
+    registry: ModeRegistry<T>,
+    exporters: Vec<Box<dyn Exporter<T>>>,
+    hooks: Hooks<T>,
+    last_seed: Option<Seed>,
+}
+
+impl<T: Number> Generator<T> {
+    /// 创建新的随机数生成器实例
+    pub fn new() -> Self {
+        Self {
+            core_version: "v2.0".to_string(),
+            config: Config::default(),
+            generated_numbers: Vec::new(),
+            rng: RngBackend::default().create(),
+            registry: ModeRegistry::default(),
+            exporters: Vec::new(),
+            hooks: Hooks::default(),
+            last_seed: None,
+        }
+    }
+
+    /// 使用自定义配置创建生成器
+    pub fn with_config(config: Config<T>) -> Result<Self, RandomGeneratorError> {
+        let mut generator = Self::new();
+        generator.set_config(config)?;
+        Ok(generator)
+    }
+
+    /// 先注册一种抽取方式,再使用配置创建生成器;用于带参数的模式,例如骰子脚本
+    pub fn with_source(source: Box<dyn GeneratorSource<T>>, config: Config<T>) -> Result<Self, RandomGeneratorError> {
+        let mut generator = Self::new();
+        generator.register_source(source);
+        generator.set_config(config)?;
+        Ok(generator)
+    }
+
+    /// 设置配置
+    pub fn set_config(&mut self, config: Config<T>) -> Result<(), RandomGeneratorError> {
+        self.validate_config(&config)?;
+        if config.rng_backend != self.config.rng_backend {
+            self.rng = config.rng_backend.create();
+        }
+        self.config = config;
+        Ok(())
+    }
+
+    /// 获取当前配置
+    pub fn get_config(&self) -> &Config<T> {
+        &self.config
+    }
+
+    /// 设置下界
+    pub fn set_lower_bound(&mut self, lower: T) -> Result<(), RandomGeneratorError> {
+        if !T::is_valid_range(lower, self.config.upper_bound) {
+            return Err(RandomGeneratorError::InvalidBounds);
+        }
+        self.config.lower_bound = lower;
+        Ok(())
+    }
+
+    /// 设置上界
+    pub fn set_upper_bound(&mut self, upper: T) -> Result<(), RandomGeneratorError> {
+        if !T::is_valid_range(self.config.lower_bound, upper) {
+            return Err(RandomGeneratorError::InvalidBounds);
+        }
+        self.config.upper_bound = upper;
+        Ok(())
+    }
+
+    /// 设置生成数量
+    pub fn set_num_to_generate(&mut self, num: usize) -> Result<(), RandomGeneratorError> {
+        if num > MAX_COUNT {
+            return Err(RandomGeneratorError::CountTooLarge);
+        }
+        if !Self::fits(num, Self::batch_capacity(self.config.allow_duplicates, RepeatLimits::of(&self.config), self.available())) {
+            return Err(RandomGeneratorError::TooManyNumbers);
+        }
+        self.config.num_to_generate = num;
+        Ok(())
+    }
+
+    /// 设置是否允许重复
+    pub fn set_allow_duplicates(&mut self, allow: bool) -> Result<(), RandomGeneratorError> {
+        if !Self::fits(self.config.num_to_generate, Self::batch_capacity(allow, RepeatLimits::of(&self.config), self.available())) {
+            return Err(RandomGeneratorError::TooManyNumbers);
+        }
+        self.config.allow_duplicates = allow;
+        Ok(())
+    }
+
+    /// 获取是否允许重复
+    pub fn get_allow_duplicates(&self) -> bool {
+        self.config.allow_duplicates
+    }
+
+    /// 设置允许重复时每个值最多出现几次,`None` 表示不限
+    pub fn set_max_repeats(&mut self, max: Option<usize>) -> Result<(), RandomGeneratorError> {
+        if max == Some(0) {
+            return Err(Self::no_repeats_left());
+        }
+        let limits = RepeatLimits { per_value: max, ..RepeatLimits::of(&self.config) };
+        if !Self::fits(self.config.num_to_generate, Self::batch_capacity(self.config.allow_duplicates, limits, self.available())) {
+            return Err(RandomGeneratorError::TooManyNumbers);
+        }
+        self.config.max_repeats = max;
+        Ok(())
+    }
+
+    /// 每个值最多出现几次
+    pub fn max_repeats(&self) -> Option<usize> {
+        self.config.max_repeats
+    }
+
+    /// 设置允许重复时同一个值最多连续出现几次,`None` 表示不限
+    pub fn set_max_streak(&mut self, max: Option<usize>) -> Result<(), RandomGeneratorError> {
+        if max == Some(0) {
+            return Err(Self::no_repeats_left());
+        }
+        let limits = RepeatLimits { streak: max, ..RepeatLimits::of(&self.config) };
+        if !Self::fits(self.config.num_to_generate, Self::batch_capacity(self.config.allow_duplicates, limits, self.available())) {
+            return Err(RandomGeneratorError::TooManyNumbers);
+        }
+        self.config.max_streak = max;
+        Ok(())
+    }
+
+    /// 同一个值最多连续出现几次
+    pub fn max_streak(&self) -> Option<usize> {
+        self.config.max_streak
+    }
+
+    /// 设置洗牌袋,`None` 表示每次独立抽取;当前模式不能用洗牌袋时在生成时报错
+    pub fn set_shuffle_bag(&mut self, bag: Option<ShuffleBag<T>>) {
+        self.config.shuffle_bag = bag;
+    }
+
+    /// 当前的洗牌袋
+    pub fn shuffle_bag(&self) -> Option<&ShuffleBag<T>> {
+        self.config.shuffle_bag.as_ref()
+    }
+
+    /// 设置冷却,`None` 表示没有冷却;当前模式不能用冷却时在生成时报错
+    pub fn set_cooldown(&mut self, cooldown: Option<Cooldown<T>>) {
+        self.config.cooldown = cooldown;
+    }
+
+    /// 当前的冷却
+    pub fn cooldown(&self) -> Option<&Cooldown<T>> {
+        self.config.cooldown.as_ref()
+    }
+
+    /// 设置生成后依次对每个数字做的变换,空表示不变换
+    pub fn set_transforms(&mut self, transforms: Vec<Transform<T>>) -> Result<(), RandomGeneratorError> {
+        transforms.iter().try_for_each(Transform::validate)?;
+        self.config.transforms = transforms;
+        Ok(())
+    }
+
+    /// 当前的变换
+    pub fn transforms(&self) -> &[Transform<T>] {
+        &self.config.transforms
+    }
+
+    /// 注册一种抽取方式,之后即可通过 [`set_mode`](Self::set_mode) 选择它
+    pub fn register_source(&mut self, source: Box<dyn GeneratorSource<T>>) {
+        self.registry.register(source);
+    }
+
+    /// 所有可选的模式,按注册顺序排列
+    pub fn modes(&self) -> Vec<GeneratorMode> {
+        self.registry.modes()
+    }
+
+    /// 查找模式对应的抽取方式
+    pub fn source(&self, mode: GeneratorMode) -> Option<&dyn GeneratorSource<T>> {
+        self.registry.get(mode)
+    }
+
+    /// 注册一种导出格式,同名的旧格式会被替换
+    pub fn register_exporter(&mut self, exporter: Box<dyn Exporter<T>>) {
+        match self.exporters.iter().position(|e| e.name() == exporter.name()) {
+            Some(index) => self.exporters[index] = exporter,
+            None => self.exporters.push(exporter),
+        }
+    }
+
+    /// 已注册的导出格式,按注册顺序排列;不含内置的 [`ExportFormat`]
+    pub fn exporters(&self) -> impl Iterator<Item = &dyn Exporter<T>> {
+        self.exporters.iter().map(|e| e.as_ref())
+    }
+
+    /// 设置生成器模式
+    pub fn set_mode(&mut self, mode: GeneratorMode) -> Result<(), RandomGeneratorError> {
+        self.config.mode = mode;
+        // 刚切换到自定义列表模式时列表通常还是空的,留到生成时再检查
+        match self.validate_config(&self.config) {
+            Err(RandomGeneratorError::EmptyList) => Ok(()),
+            other => other,
+        }
+    }
+
+    /// 获取生成器模式
+    pub fn get_mode(&self) -> GeneratorMode {
+        self.config.mode
+    }
+
+    /// 设置自定义列表输入
+    pub fn set_custom_list_input(&mut self, input: String) -> Result<(), RandomGeneratorError> {
+        self.config.custom_list_input = input;
+        self.parse_custom_list()?;
+        self.validate_config(&self.config)?;
+        Ok(())
+    }
+
+    /// 获取自定义列表输入
+    pub fn get_custom_list_input(&self) -> &str {
+        &self.config.custom_list_input
+    }
+
+    /// 设置是否跳过自定义列表中不是数字的项;改为不跳过时,列表中有这样的项会报错
+    pub fn set_lenient_list(&mut self, lenient: bool) -> Result<(), RandomGeneratorError> {
+        self.config.lenient_list = lenient;
+        self.parse_custom_list()?;
+        self.validate_config(&self.config)?;
+        Ok(())
+    }
+
+    /// 自定义列表中不是数字的项,按出现的先后;宽松模式下它们被跳过
+    pub fn skipped_list_items(&self) -> Vec<&str> {
+        let input = &self.config.custom_list_input;
+        Self::custom_list_items(input).into_iter().filter(|(_, value)| value.is_none()).map(|(range, _)| &input[range]).collect()
+    }
+
+    /// 解析自定义列表输入
+    fn parse_custom_list(&mut self) -> Result<(), RandomGeneratorError> {
+        self.config.custom_list = match self.config.lenient_list {
+            true => Self::custom_list_items(&self.config.custom_list_input).into_iter().filter_map(|(_, value)| value).collect(),
+            false => Self::parse_custom_list_input(&self.config.custom_list_input)?,
+        };
+        Ok(())
+    }
+
+    /// 把自定义列表文本解析为数字,空输入得到空列表
+    pub fn parse_custom_list_input(input: &str) -> Result<Vec<T>, RandomGeneratorError> {
+        Self::custom_list_items(input)
+            .into_iter()
+            .map(|(_, value)| value.ok_or(RandomGeneratorError::InvalidInputFormat))
+            .collect()
+    }
+
+    /// 自定义列表文本中的每一项:在文本中的字节范围和解析出的数字,不是数字时为 `None`
+    ///
+    /// 各项之间可以用逗号、分号、空格或换行分隔,与 [`Generator::parse_custom_list_input`] 相同
+    ///
+    /// ```
+    /// use random_generator_core::RandomGenerator;
+    ///
+    /// let items = RandomGenerator::custom_list_items("4, x;\n15");
+    /// assert_eq!(items, [(0..1, Some(4)), (3..4, None), (6..8, Some(15))]);
+    /// ```
+    pub fn custom_list_items(input: &str) -> Vec<(Range<usize>, Option<T>)> {
+        // 支持多种分隔符：逗号、空格、换行等
+        let re = Regex::new(r"[^,\s;]+").unwrap();
+        re.find_iter(input)
+            .map(|item| (item.range(), item.as_str().parse::<T>().ok().filter(|num| num.is_finite())))
+            .collect()
+    }
+
+    /// 生成随机数
+    pub fn generate_numbers(&mut self) -> Result<(), RandomGeneratorError> {
+        self.generate_numbers_with_progress(&AtomicBool::new(false), &mut |_, _| {})
+    }
+
+    /// 生成随机数并回报进度,支持协作式取消
+    ///
+    /// `on_progress` 会以 `(已完成, 总量)` 的形式被周期性调用,适合在后台线程中驱动进度条;
+    /// 生成循环会定期检查 `cancel`,一旦被置位就返回 `Cancelled`,已生成的结果保持为空
+    pub fn generate_numbers_with_progress(
+        &mut self,
+        cancel: &AtomicBool,
+        on_progress: &mut dyn FnMut(usize, usize),
+    ) -> Result<(), RandomGeneratorError> {
+        self.validate_config(&self.config)?;
+
+        // 回调在生成期间需要可变借用,先取出,结束后放回
+        let mut hooks = std::mem::take(&mut self.hooks);
+        hooks.start(&self.config);
+
+        self.generated_numbers.clear();
+        let mut report = |done: usize, total: usize| {
+            on_progress(done, total);
+            hooks.progress(done, total);
+        };
+        let mut progress = Progress { cancel, on_progress: &mut report };
+        let result = self.run_generation(&mut progress);
+        if result.is_ok() {
+            let total = self.generated_numbers.len();
+            (progress.on_progress)(total, total);
+        }
+
+        match &result {
+            Ok(()) => {
+                hooks.values(&self.generated_numbers);
+                hooks.complete(Ok(&self.generated_numbers));
+            }
+            Err(e) => {
+                self.generated_numbers.clear();
+                hooks.complete(Err(e));
+            }
+        }
+        self.hooks = hooks;
+        result
+    }
+
+    /// 每次生成开始时调用,参数是本次使用的配置
+    ///
+    /// 配置校验失败时生成不会开始,也不会触发任何事件
+    pub fn on_start(&mut self, hook: impl FnMut(&Config<T>) + Send + Sync + 'static) {
+        self.hooks.add_start(Box::new(hook));
+    }
+
+    /// 生成过程中周期性调用,参数是 `(已完成, 总量)`
+    pub fn on_progress(&mut self, hook: impl FnMut(usize, usize) + Send + Sync + 'static) {
+        self.hooks.add_progress(Box::new(hook));
+    }
+
+    /// 生成成功后按结果顺序对每个数字调用一次,在 [`on_complete`](Self::on_complete) 之前
+    pub fn on_value(&mut self, hook: impl FnMut(T) + Send + Sync + 'static) {
+        self.hooks.add_value(Box::new(hook));
+    }
+
+    /// 每次生成结束时调用,成功时得到全部结果,失败或取消时得到错误
+    pub fn on_complete(&mut self, hook: impl FnMut(Result<&[T], &RandomGeneratorError>) + Send + Sync + 'static) {
+        self.hooks.add_complete(Box::new(hook));
+    }
+
+    /// 移除所有事件回调
+    pub fn clear_hooks(&mut self) {
+        self.hooks = Hooks::default();
+    }
+
+    /// 交给当前模式对应的抽取方式生成
+    fn run_generation(&mut self, progress: &mut Progress) -> Result<(), RandomGeneratorError> {
+        let source = self.registry.get(self.config.mode).ok_or(RandomGeneratorError::UnknownMode(self.config.mode))?;
+        let seed = self.config.seed.unwrap_or_else(|| Seed::random(&mut *self.rng));
+        self.last_seed = Some(seed);
+        let (count, allow_duplicates) = (self.config.num_to_generate, self.config.allow_duplicates);
+        // 取消或失败时袋子和冷却都保持原样,变换出错也算失败
+        let (mut bag, mut cooldown) = (self.config.shuffle_bag.clone(), self.config.cooldown.clone());
+        let numbers = if let Some(bag) = &mut bag {
+            let pool = source.pool(&self.config).ok_or_else(|| Self::needs_pool("a shuffle bag"))?;
+            bag.draw(pool, count, allow_duplicates, &mut seed.rng(), progress)?
+        } else if let Some(cooldown) = &mut cooldown {
+            let pool = source.pool(&self.config).ok_or_else(|| Self::needs_pool("a cooldown"))?;
+            cooldown.draw(pool, count, allow_duplicates, &mut seed.rng(), progress)?
+        } else {
+            source.generate(&self.config, &mut seed.rng(), progress)?
+        };
+        // 袋子、冷却和排除记的是变换前的值
+        let transformed = match self.config.transforms.is_empty() {
+            true => None,
+            false => Some(transform::apply_all(&self.config.transforms, numbers.clone())?),
+        };
+        (self.config.shuffle_bag, self.config.cooldown) = (bag, cooldown);
+        if let Some(excluded) = &mut self.config.exclude_drawn {
+            excluded.extend(numbers.iter().copied());
+        }
+        self.generated_numbers = transformed.unwrap_or(numbers);
+        if self.config.order == ResultOrder::Sorted {
+            self.generated_numbers.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+        }
+        Ok(())
+    }
+
+    /// 最近一次生成所用的种子,还没有生成过时为 `None`
+    ///
+    /// 把它填入配置的 [`seed`](Config::seed) 再生成,就能重现那一次的结果
+    pub fn last_seed(&self) -> Option<Seed> {
+        self.last_seed
+    }
+
+    /// 用给定的种子按当前配置重新生成,配置本身不变
+    pub fn replay(&mut self, seed: Seed) -> Result<(), RandomGeneratorError> {
+        let fixed = self.config.seed.replace(seed);
+        let result = self.generate_numbers();
+        self.config.seed = fixed;
+        result
+    }
+
+    /// 清除生成的数字
+    pub fn clear_numbers(&mut self) {
+        self.generated_numbers.clear();
+    }
+
+    /// 重新排列生成的数字;打乱用生成器自己的随机数,与种子无关
+    pub fn rearrange(&mut self, how: Rearrange) {
+        let numbers = &mut self.generated_numbers;
+        match how {
+            Rearrange::SortAscending => numbers.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal)),
+            Rearrange::SortDescending => numbers.sort_by(|a, b| b.partial_cmp(a).unwrap_or(std::cmp::Ordering::Equal)),
+            Rearrange::Shuffle => numbers.shuffle(&mut *self.rng),
+            Rearrange::Reverse => numbers.reverse(),
+        }
+    }
+
+    /// 去掉生成的数字中重复的,每个值保留第一次出现的位置;返回去掉了几个
+    pub fn remove_duplicates(&mut self) -> usize {
+        let numbers = &mut self.generated_numbers;
+        let mut order: Vec<usize> = (0..numbers.len()).collect();
+        // 稳定排序,相同的值按出现的先后排在一起
+        order.sort_by(|&a, &b| numbers[a].partial_cmp(&numbers[b]).unwrap_or(std::cmp::Ordering::Equal));
+        let mut keep = vec![true; numbers.len()];
+        for pair in order.windows(2) {
+            if numbers[pair[0]] == numbers[pair[1]] {
+                keep[pair[1]] = false;
+            }
+        }
+        let before = numbers.len();
+        let mut index = 0;
+        numbers.retain(|_| {
+            index += 1;
+            keep[index - 1]
+        });
+        before - numbers.len()
+    }
+
+    /// 获取生成的数字
+    pub fn get_numbers(&self) -> &[T] {
+        &self.generated_numbers
+    }
+
+    /// 获取生成的数字(可变引用)
+    pub fn get_numbers_mut(&mut self) -> &mut Vec<T> {
+        &mut self.generated_numbers
+    }
+
+    /// 获取边界
+    pub fn get_bounds(&self) -> (T, T) {
+        (self.config.lower_bound, self.config.upper_bound)
+    }
+
+    /// 获取设置
+    pub fn get_settings(&self) -> (usize, bool) {
+        (self.config.num_to_generate, self.config.allow_duplicates)
+    }
+
+    /// 保存数字到文件
+    pub fn save_numbers(&self, filename: &str) -> Result<(), RandomGeneratorError> {
+        self.save_numbers_formatted(filename, &NumberFormat::default())
+    }
+
+    /// 按指定格式保存数字到文件
+    pub fn save_numbers_formatted(&self, filename: &str, format: &NumberFormat) -> Result<(), RandomGeneratorError> {
+        self.save_numbers_as(filename, ExportFormat::Text, format)
+    }
+
+    /// 以指定的文件格式和数字格式保存数字
+    pub fn save_numbers_as(&self, filename: &str, export: ExportFormat, format: &NumberFormat) -> Result<(), RandomGeneratorError> {
+        if self.generated_numbers.is_empty() {
+            return Ok(());
+        }
+
+        fs::write(filename, export.render(&self.generated_numbers, format))?;
+        Ok(())
+    }
+
+    /// 以名称对应的已注册导出格式保存数字
+    pub fn save_numbers_with(&self, filename: &str, exporter: &str, format: &NumberFormat) -> Result<(), RandomGeneratorError> {
+        let exporter = self
+            .exporters()
+            .find(|e| e.name() == exporter)
+            .ok_or_else(|| RandomGeneratorError::UnknownFormat(exporter.to_string()))?;
+        if self.generated_numbers.is_empty() {
+            return Ok(());
+        }
+        fs::write(filename, exporter.export(&self.generated_numbers, format)?)?;
+        Ok(())
+    }
+
+    /// 从保存的结果文件加载数字,替换生成的数字;按扩展名判断格式,见 [`ExportFormat::read_numbers`]
+    pub fn load_numbers(&mut self, filename: &str) -> Result<(), RandomGeneratorError> {
+        let content = fs::read_to_string(filename)?;
+        self.generated_numbers = ExportFormat::from_path(filename).read_numbers(&content)?;
+        Ok(())
+    }
+
+    /// 获取核心版本
+    pub fn get_core_version(&self) -> &str {
+        &self.core_version
+    }
+
+    /// 获取统计信息
+    pub fn get_stats(&self) -> Stats<T> {
+        Stats::of(&self.generated_numbers)
+    }
+
+    /// 列出配置的所有问题,没有问题时返回空列表
+    ///
+    /// 与只报告第一个问题不同,界面可以据此一次标出所有无效的输入
+    pub fn validate(&self, config: &Config<T>) -> Vec<RandomGeneratorError> {
+        match self.registry.get(config.mode) {
+            Some(source) => Self::validate_with(source, config),
+            None => vec![RandomGeneratorError::UnknownMode(config.mode)],
+        }
+    }
+
+    /// 用给定的抽取方式代替注册的那个列出配置的所有问题,用于在注册之前检查带参数的模式
+    pub fn validate_with(source: &dyn GeneratorSource<T>, config: &Config<T>) -> Vec<RandomGeneratorError> {
+        let mut problems = source.validate(config);
+        if config.num_to_generate > MAX_COUNT {
+            problems.push(RandomGeneratorError::CountTooLarge);
+        }
+        let capacity = Self::batch_capacity(config.allow_duplicates, RepeatLimits::of(config), source.available(config));
+        // 区间或列表本身无效时,可选数字的个数没有意义
+        if problems.is_empty() && !Self::fits(config.num_to_generate, capacity) {
+            problems.push(RandomGeneratorError::TooManyNumbers);
+        }
+        if problems.is_empty() {
+            problems.extend(Self::validate_pool_options(config, source.available(config)));
+        }
+        problems.extend(config.transforms.iter().filter_map(|transform| transform.validate().err()));
+        problems
+    }
+
+    /// 按配置一次生成最多能抽到几个数字,`None` 表示不限;数量超出时生成会报 `TooManyNumbers`
+    pub fn capacity(&self, config: &Config<T>) -> Option<u128> {
+        let available = self.registry.get(config.mode).and_then(|source| source.available(config));
+        Self::batch_capacity(config.allow_duplicates, RepeatLimits::of(config), available)
+    }
+
+    /// 按配置预览抽取空间:可以抽到的不同数字个数、一次最多抽几个,以及允许重复时出现相同数字的概率
+    ///
+    /// 只有区间和自定义列表能算出概率;按权重的列表按各值的机会计算
+    pub fn draw_space(&self, config: &Config<T>) -> DrawSpace {
+        let values = self.registry.get(config.mode).and_then(|source| source.available(config));
+        let collision_chance = match config.allow_duplicates {
+            false => None,
+            true if config.mode == GeneratorMode::RANGE || config.mode == GeneratorMode::TICKETS => values.map(|values| draw_space::uniform_collision_chance(values, config.num_to_generate)),
+            true if config.mode == GeneratorMode::CUSTOM_LIST => {
+                let weights: Vec<usize> = ListSource::weights(config).into_iter().map(|(_, weight)| weight).collect();
+                draw_space::weighted_collision_chance(&weights, config.num_to_generate)
+            }
+            true => None,
+        };
+        DrawSpace { values, capacity: self.capacity(config), collision_chance }
+    }
+
+    /// 验证配置,有多个问题时合并为 `Multiple`
+    fn validate_config(&self, config: &Config<T>) -> Result<(), RandomGeneratorError> {
+        let mut problems = self.validate(config);
+        match problems.len() {
+            0 => Ok(()),
+            1 => Err(problems.remove(0)),
+            _ => Err(RandomGeneratorError::Multiple(problems)),
+        }
+    }
+
+    /// 当前模式下可供抽取的不同数字个数,`None` 表示不限
+    fn available(&self) -> Option<u128> {
+        self.registry.get(self.config.mode).and_then(|source| source.available(&self.config))
+    }
+
+    /// 检查洗牌袋、冷却、排除集合和重复的限制,`values` 是可以抽到的不同数字个数
+    fn validate_pool_options(config: &Config<T>, values: Option<u128>) -> Option<RandomGeneratorError> {
+        // 不允许重复时这些限制没有作用
+        if config.allow_duplicates && (config.max_repeats.is_some() || config.max_streak.is_some()) {
+            if config.max_repeats == Some(0) || config.max_streak == Some(0) {
+                return Some(Self::no_repeats_left());
+            }
+            if config.shuffle_bag.is_some() || config.cooldown.is_some() {
+                return Some(RandomGeneratorError::InvalidDraw("repeat limits don't work with a shuffle bag or a cooldown".to_string()));
+            }
+            if config.max_streak.is_some() && config.order == ResultOrder::Sorted {
+                return Some(RandomGeneratorError::InvalidDraw("sorting would put repeated values next to each other; keep the draw order".to_string()));
+            }
+        }
+        if config.exclude_drawn.is_some() {
+            if config.mode != GeneratorMode::RANGE && config.mode != GeneratorMode::TICKETS {
+                return Some(RandomGeneratorError::InvalidDraw("only range and ticket draws can exclude earlier results".to_string()));
+            }
+            if config.shuffle_bag.is_some() || config.cooldown.is_some() {
+                return Some(RandomGeneratorError::InvalidDraw("excluding earlier results doesn't work with a shuffle bag or a cooldown".to_string()));
+            }
+        }
+        let feature = match (&config.shuffle_bag, &config.cooldown) {
+            (None, None) => return None,
+            (Some(_), Some(_)) => return Some(RandomGeneratorError::InvalidDraw("use a shuffle bag or a cooldown, not both".to_string())),
+            (Some(_), None) => "a shuffle bag",
+            (None, Some(_)) => "a cooldown",
+        };
+        let Some(values) = values.filter(|&values| values <= MAX_POOL) else {
+            return Some(Self::needs_pool(feature));
+        };
+        // 冷却中的值都不能抽时至少还要剩一个
+        let draws = config.cooldown.as_ref().map_or(0, Cooldown::draws);
+        (values <= draws as u128).then(|| {
+            RandomGeneratorError::InvalidDraw(format!("a cooldown of {} draws needs at least {} different values", draws, draws + 1))
+        })
+    }
+
+    /// 当前模式不能一一列出全部数字
+    fn needs_pool(feature: &str) -> RandomGeneratorError {
+        RandomGeneratorError::InvalidDraw(format!("{} needs a list or a range of at most {} values", feature, MAX_POOL))
+    }
+
+    /// 重复的限制为 0
+    fn no_repeats_left() -> RandomGeneratorError {
+        RandomGeneratorError::InvalidDraw("each value must be allowed at least once".to_string())
+    }
+
+    /// 一次生成最多能抽到几个数字:不允许重复时是不同数字的个数,否则由重复的限制决定
+    fn batch_capacity(allow_duplicates: bool, limits: RepeatLimits, available: Option<u128>) -> Option<u128> {
+        if allow_duplicates {
+            limits.capacity(available)
+        } else {
+            available
+        }
+    }
+
+    /// 抽取 `count` 个数字时,可选的数字是否足够
+    fn fits(count: usize, available: Option<u128>) -> bool {
+        available.is_none_or(|available| count as u128 <= available)
+    }
+}
+
+/// 生成过程中的进度回报与取消检查
+pub struct Progress<'a> {
+    cancel: &'a AtomicBool,
+    on_progress: &'a mut dyn FnMut(usize, usize),
+}
+
+impl Progress<'_> {
+    /// 回报进度,如果已请求取消则返回 `Cancelled`
+    pub fn checkpoint(&mut self, done: usize, total: usize) -> Result<(), RandomGeneratorError> {
+        if self.cancel.load(Ordering::Relaxed) {
+            return Err(RandomGeneratorError::Cancelled);
+        }
+        (self.on_progress)(done, total);
+        Ok(())
+    }
+}
+
+/// `i64` 结果的统计信息
+pub type GeneratorStats = Stats<i64>;
+
+/// 统计信息
+#[derive(Debug)]
+pub struct Stats<T: Number> {
+    /// 结果个数
+    pub count: usize,
+    /// 最小值
+    pub min: Option<T>,
+    /// 最大值
+    pub max: Option<T>,
+    /// 总和,累加中超出类型范围时为 `None`
+    pub sum: Option<T>,
+    /// 平均值;总和超出范围时按浮点数累加
+    pub avg: f64,
+}
+
+impl<T: Number> Stats<T> {
+    /// 一组数字的统计信息
+    pub fn of(numbers: &[T]) -> Self {
+        let zero = T::from_i64(0).expect("every number type has 0");
+        let sum = numbers.iter().try_fold(zero, |sum, &value| sum.checked_add(value));
+        Stats {
+            count: numbers.len(),
+            min: numbers.iter().copied().reduce(|a, b| if b < a { b } else { a }),
+            max: numbers.iter().copied().reduce(|a, b| if b > a { b } else { a }),
+            sum,
+            avg: match sum {
+                _ if numbers.is_empty() => 0.0,
+                Some(sum) => sum.as_f64() / numbers.len() as f64,
+                None => numbers.iter().map(|value| value.as_f64()).sum::<f64>() / numbers.len() as f64,
+            },
+        }
+    }
+}
+
+impl<T: Number> Default for Generator<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::{HashMap, HashSet};
+
+    #[test]
+    fn test_generator_is_thread_safe() {
+        fn assert_send_sync<S: Send + Sync>() {}
+        assert_send_sync::<RandomGenerator>();
+        assert_send_sync::<Generator<f64>>();
+
+        let shared = std::sync::Arc::new(std::sync::Mutex::new(RandomGenerator::new()));
+        let workers: Vec<_> = (0..4)
+            .map(|_| {
+                let shared = shared.clone();
+                std::thread::spawn(move || {
+                    let mut random_gen = shared.lock().unwrap();
+                    random_gen.generate_numbers().unwrap();
+                    random_gen.get_numbers().len()
+                })
+            })
+            .collect();
+        for worker in workers {
+            assert_eq!(worker.join().unwrap(), 1);
+        }
+    }
+
+    #[test]
+    fn test_basic_generation() {
+        let mut random_gen = RandomGenerator::new();
+        random_gen.set_num_to_generate(5).unwrap();
+        random_gen.generate_numbers().unwrap();
+        assert_eq!(random_gen.get_numbers().len(), 5);
+    }
+
+    #[test]
+    fn test_no_duplicates() {
+        let mut random_gen = RandomGenerator::new();
+        random_gen.set_num_to_generate(10).unwrap();
+        random_gen.set_allow_duplicates(false).unwrap();
+        random_gen.generate_numbers().unwrap();
+
+        let numbers = random_gen.get_numbers();
+        let mut unique = HashSet::new();
+        for &num in numbers {
+            assert!(unique.insert(num), "发现重复数字: {}", num);
+        }
+    }
+
+    #[test]
+    fn test_bounds_validation() {
+        let mut random_gen = RandomGenerator::new();
+        assert!(random_gen.set_lower_bound(2000).is_err());
+        assert!(random_gen.set_upper_bound(-100).is_err());
+    }
+
+    #[test]
+    fn test_progress_reaches_total() {
+        let mut random_gen = RandomGenerator::new();
+        random_gen.set_num_to_generate(500).unwrap();
+
+        let mut last = (0, 0);
+        random_gen
+            .generate_numbers_with_progress(&AtomicBool::new(false), &mut |done, total| last = (done, total))
+            .unwrap();
+        assert_eq!(last, (500, 500));
+    }
+
+    #[test]
+    fn test_cancelled_generation() {
+        let mut random_gen = RandomGenerator::new();
+        random_gen.set_num_to_generate(500).unwrap();
+
+        let result = random_gen.generate_numbers_with_progress(&AtomicBool::new(true), &mut |_, _| {});
+        assert!(matches!(result, Err(RandomGeneratorError::Cancelled)));
+        assert!(random_gen.get_numbers().is_empty());
+    }
+
+    #[test]
+    fn test_with_config_validates_its_own_range() {
+        let config = GeneratorConfig {
+            lower_bound: 1,
+            upper_bound: 10,
+            num_to_generate: 20,
+            ..GeneratorConfig::default()
+        };
+        assert!(matches!(
+            RandomGenerator::with_config(config),
+            Err(RandomGeneratorError::TooManyNumbers)
+        ));
+
+        // 数量过大时在分配内存之前就报错,允许重复时也一样
+        let huge = GeneratorConfig { allow_duplicates: true, num_to_generate: MAX_COUNT + 1, ..GeneratorConfig::default() };
+        assert!(matches!(RandomGenerator::with_config(huge), Err(RandomGeneratorError::CountTooLarge)));
+        assert!(matches!(RandomGenerator::new().set_num_to_generate(usize::MAX), Err(RandomGeneratorError::CountTooLarge)));
+    }
+
+    #[test]
+    fn test_other_number_types() {
+        let mut floats = Generator::<f64>::with_config(Config {
+            lower_bound: -0.5,
+            upper_bound: 0.5,
+            num_to_generate: 100,
+            ..Config::default()
+        })
+        .unwrap();
+        floats.generate_numbers().unwrap();
+        assert_eq!(floats.get_numbers().len(), 100);
+        assert!(floats.get_numbers().iter().all(|&x| (-0.5..=0.5).contains(&x)));
+        assert!(floats.set_upper_bound(f64::INFINITY).is_err());
+
+        // 浮点区间按可表示的值计数,只有一个值时不能不重复地抽两个
+        assert_eq!(f64::representable_between(1.0, 1.0 + 2.0 * f64::EPSILON), 3);
+        assert_eq!(f64::representable_between(-0.0, 0.0), 1);
+        assert_eq!(f64::nth_from(1.0, 2), 1.0 + 2.0 * f64::EPSILON);
+        assert_eq!(f64::nth_from(-f64::MIN_POSITIVE, 1).to_bits(), (-f64::MIN_POSITIVE).next_up().to_bits());
+        let single = Config { lower_bound: 1.5, upper_bound: 1.5, num_to_generate: 2, ..Config::default() };
+        assert!(matches!(Generator::<f64>::new().validate(&single)[..], [RandomGeneratorError::TooManyNumbers]));
+        let mut narrow = Generator::<f64>::with_config(Config { upper_bound: 1.5 + 2.0 * f64::EPSILON, num_to_generate: 3, ..single }).unwrap();
+        narrow.generate_numbers().unwrap();
+        assert_eq!(narrow.get_numbers().len(), 3);
+
+        let mut big = Generator::<i128>::with_config(Config {
+            lower_bound: i128::MAX - 9,
+            upper_bound: i128::MAX,
+            num_to_generate: 10,
+            ..Config::default()
+        })
+        .unwrap();
+        big.generate_numbers().unwrap();
+        let mut numbers = big.get_numbers().to_vec();
+        numbers.sort();
+        assert_eq!(numbers, ((i128::MAX - 9)..=i128::MAX).collect::<Vec<_>>());
+        assert!(big.set_num_to_generate(11).is_err());
+
+        let full = Config::<u64> { lower_bound: 0, upper_bound: u64::MAX, ..Config::default() };
+        assert_eq!(RangeSource.available(&full), Some(u64::MAX as u128 + 1));
+        assert_eq!(Generator::<u64>::parse_custom_list_input("1, 2").unwrap(), [1, 2]);
+        assert!(Generator::<u64>::parse_custom_list_input("-1").is_err());
+        assert_eq!(Generator::<u64>::custom_list_items(" 7,,-1 "), [(1..2, Some(7)), (4..6, None)]);
+    }
+
+    #[test]
+    fn test_validate_lists_problems() {
+        let random_gen = RandomGenerator::new();
+        assert!(random_gen.validate(&GeneratorConfig::default()).is_empty());
+
+        let reversed = GeneratorConfig { lower_bound: 5, upper_bound: 1, num_to_generate: 10, ..GeneratorConfig::default() };
+        assert!(matches!(random_gen.validate(&reversed)[..], [RandomGeneratorError::InvalidBounds]));
+
+        let missing = GeneratorConfig { mode: GeneratorMode::new("Missing"), ..GeneratorConfig::default() };
+        assert!(matches!(random_gen.validate(&missing)[..], [RandomGeneratorError::UnknownMode(_)]));
+
+        let combined = RandomGeneratorError::Multiple(vec![RandomGeneratorError::InvalidBounds, RandomGeneratorError::EmptyList]);
+        assert_eq!(combined.errors().len(), 2);
+        assert_eq!(
+            combined.to_string(),
+            "The lower bound must be less than or equal to the upper bound; Custom list cannot be empty"
+        );
+        assert_eq!(RandomGeneratorError::Cancelled.errors().len(), 1);
+    }
+
+    #[test]
+    fn test_registered_source() {
+        /// 只抽偶数
+        struct EvenSource;
+
+        impl GeneratorSource<i64> for EvenSource {
+            fn mode(&self) -> GeneratorMode {
+                GeneratorMode::new("Even")
+            }
+
+            fn input(&self) -> SourceInput {
+                SourceInput::Bounds
+            }
+
+            fn validate(&self, config: &GeneratorConfig) -> Vec<RandomGeneratorError> {
+                RangeSource.validate(config)
+            }
+
+            fn available(&self, config: &GeneratorConfig) -> Option<u128> {
+                Some(((config.upper_bound.div_euclid(2) - (config.lower_bound + 1).div_euclid(2)) + 1).max(0) as u128)
+            }
+
+            fn generate(
+                &self,
+                config: &GeneratorConfig,
+                rng: &mut dyn RngCore,
+                progress: &mut Progress<'_>,
+            ) -> Result<Vec<i64>, RandomGeneratorError> {
+                let halves = GeneratorConfig {
+                    lower_bound: (config.lower_bound + 1).div_euclid(2),
+                    upper_bound: config.upper_bound.div_euclid(2),
+                    ..config.clone()
+                };
+                let numbers = RangeSource.generate(&halves, rng, progress)?;
+                Ok(numbers.into_iter().map(|n| n * 2).collect())
+            }
+        }
+
+        let mut random_gen = RandomGenerator::new();
+        let even = GeneratorMode::new("Even");
+        assert!(matches!(random_gen.set_mode(even), Err(RandomGeneratorError::UnknownMode(_))));
+
+        random_gen.register_source(Box::new(EvenSource));
+        assert_eq!(random_gen.modes(), [GeneratorMode::RANGE, GeneratorMode::CUSTOM_LIST, GeneratorMode::SCRIPT, GeneratorMode::PRIMES, GeneratorMode::STRATIFIED, GeneratorMode::PARTITION, GeneratorMode::GEOMETRIC, GeneratorMode::NEGATIVE_BINOMIAL, GeneratorMode::TICKETS, even]);
+        random_gen.set_mode(even).unwrap();
+        random_gen.set_lower_bound(1).unwrap();
+        random_gen.set_upper_bound(9).unwrap();
+        random_gen.set_num_to_generate(4).unwrap();
+        assert!(random_gen.set_num_to_generate(5).is_err());
+
+        random_gen.generate_numbers().unwrap();
+        let mut numbers = random_gen.get_numbers().to_vec();
+        numbers.sort();
+        assert_eq!(numbers, [2, 4, 6, 8]);
+    }
+
+    #[test]
+    fn test_event_hooks() {
+        use std::sync::{Arc, Mutex};
+
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let mut random_gen = RandomGenerator::new();
+        random_gen.set_num_to_generate(3).unwrap();
+
+        let log = events.clone();
+        random_gen.on_start(move |config| log.lock().unwrap().push(format!("start {}", config.num_to_generate)));
+        let log = events.clone();
+        random_gen.on_value(move |value| log.lock().unwrap().push(format!("value {}", value)));
+        let log = events.clone();
+        random_gen.on_complete(move |result| log.lock().unwrap().push(format!("complete {:?}", result.map(<[i64]>::len).ok())));
+        let log = events.clone();
+        random_gen.on_progress(move |done, total| {
+            if done == total {
+                log.lock().unwrap().push(format!("progress {}/{}", done, total));
+            }
+        });
+
+        random_gen.generate_numbers().unwrap();
+        let numbers = random_gen.get_numbers().to_vec();
+        let mut expected = vec!["start 3".to_string(), "progress 3/3".to_string()];
+        expected.extend(numbers.iter().map(|n| format!("value {}", n)));
+        expected.push("complete Some(3)".to_string());
+        assert_eq!(*events.lock().unwrap(), expected);
+
+        events.lock().unwrap().clear();
+        let result = random_gen.generate_numbers_with_progress(&AtomicBool::new(true), &mut |_, _| {});
+        assert!(result.is_err());
+        assert_eq!(*events.lock().unwrap(), ["start 3", "complete None"]);
+
+        random_gen.clear_hooks();
+        events.lock().unwrap().clear();
+        random_gen.generate_numbers().unwrap();
+        assert!(events.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_result_order() {
+        // 不重复抽取按第一次抽到的先后保留
+        let mut draws = [5i64, 3, 5, 9, 3, 1, 7].into_iter();
+        let mut progress = Progress { cancel: &AtomicBool::new(false), on_progress: &mut |_, _| {} };
+        let numbers = source::draw_distinct(4, &mut progress, || draws.next().unwrap()).unwrap();
+        assert_eq!(numbers, [5, 3, 9, 1]);
+
+        let mut random_gen = RandomGenerator::with_config(GeneratorConfig {
+            num_to_generate: 50,
+            order: ResultOrder::Sorted,
+            ..GeneratorConfig::default()
+        })
+        .unwrap();
+        random_gen.generate_numbers().unwrap();
+        assert!(random_gen.get_numbers().windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn test_rearrange() {
+        let mut random_gen = RandomGenerator::new();
+        random_gen.get_numbers_mut().extend([3, -1, 7, 3]);
+        random_gen.rearrange(Rearrange::SortDescending);
+        assert_eq!(random_gen.get_numbers(), [7, 3, 3, -1]);
+        random_gen.rearrange(Rearrange::Reverse);
+        assert_eq!(random_gen.get_numbers(), [-1, 3, 3, 7]);
+        random_gen.rearrange(Rearrange::Shuffle);
+        let mut shuffled = random_gen.get_numbers().to_vec();
+        shuffled.sort();
+        assert_eq!(shuffled, [-1, 3, 3, 7]);
+        random_gen.rearrange(Rearrange::SortAscending);
+        assert_eq!(random_gen.get_numbers(), [-1, 3, 3, 7]);
+    }
+
+    #[test]
+    fn test_remove_duplicates() {
+        let mut random_gen = RandomGenerator::new();
+        random_gen.get_numbers_mut().extend([4, 2, 4, 9, 2, 4]);
+        assert_eq!(random_gen.remove_duplicates(), 3);
+        // 保留每个值第一次出现的位置
+        assert_eq!(random_gen.get_numbers(), [4, 2, 9]);
+        assert_eq!(random_gen.remove_duplicates(), 0);
+
+        let mut float_gen = Generator::<f64>::new();
+        float_gen.get_numbers_mut().extend([0.5, 1.5, 0.5]);
+        assert_eq!(float_gen.remove_duplicates(), 1);
+        assert_eq!(float_gen.get_numbers(), [0.5, 1.5]);
+    }
+
+    #[test]
+    fn test_comparison() {
+        let comparison = Comparison::<i64>::new(&[5, 3, 9, 3, 1], &[1, 7, 5, 7]);
+        assert_eq!(comparison.both, [5, 1]);
+        assert_eq!(comparison.only_first, [3, 9]);
+        assert_eq!(comparison.only_second, [7]);
+        assert!(!comparison.is_same());
+        assert_eq!(comparison.to_text("#2", "#1"), "In both (2): 5, 1\nOnly in #2 (2): 3, 9\nOnly in #1 (1): 7\n");
+
+        // 只是顺序和重复次数不同时算相同
+        assert!(Comparison::<i64>::new(&[2, 1, 2], &[1, 2]).is_same());
+        let empty = Comparison::<i64>::new(&[], &[4]);
+        assert!(empty.both.is_empty());
+        assert_eq!(empty.only_second, [4]);
+    }
+
+    #[test]
+    fn test_pasted_list() {
+        // 电子表格的几列:取第一列全是数字的,跳过表头
+        let sheet = PastedList::<i64>::parse("Name\tTicket\tScore\r\nAnn\t104\t7\r\n\r\nBo\t1,250\t9\r\n").unwrap();
+        assert_eq!(sheet, PastedList::Numbers(vec![104, 1250]));
+        assert!(sheet.labels().is_empty());
+
+        // 带引号的 CSV 格里的逗号不拆开
+        let csv = PastedList::<i64>::parse("id,\"amount\"\n1,\"1,000\"\n2,\"2,500\"\n").unwrap();
+        assert_eq!(csv.to_list_input(), "1, 2");
+        assert_eq!(PastedList::<i64>::parse("\"1,000\"\n\"2,500\"").unwrap().to_list_input(), "1000, 2500");
+
+        // 只有一行时整行就是列表
+        assert_eq!(PastedList::<i64>::parse("4\t8\t15\t16\n").unwrap().to_list_input(), "4, 8, 15, 16");
+        assert_eq!(PastedList::<i64>::parse("4, 8, 15").unwrap().to_list_input(), "4, 8, 15");
+        assert_eq!(PastedList::<i64>::parse("4 8;15").unwrap().to_list_input(), "4, 8, 15");
+        assert_eq!(PastedList::<f64>::parse("0.5\n1.25").unwrap(), PastedList::Numbers(vec![0.5, 1.25]));
+
+        // 没有数字列时是名字,编号从 1 开始
+        let names = PastedList::<i64>::parse("\"Lee, Ann\"\tA\nBo Chen\tB\n\nCy\n").unwrap();
+        assert_eq!(names, PastedList::Names(vec!["Lee, Ann".to_string(), "Bo Chen".to_string(), "Cy".to_string()]));
+        assert_eq!(names.len(), 3);
+        assert_eq!(names.to_list_input(), "1, 2, 3");
+        let labels = names.labels();
+        assert_eq!(labels.get(2), Some("Bo Chen"));
+        assert_eq!(LabelMap::parse(&labels.to_string()).unwrap(), labels);
+
+        assert!(matches!(PastedList::<i64>::parse(" \n\t\n"), Err(RandomGeneratorError::EmptyList)));
+    }
+
+    #[test]
+    fn test_transforms() {
+        let steps = Transform::<i64>::parse_list("scale 3, Offset -20; abs, mod 7, round 5").unwrap();
+        assert_eq!(steps, [Transform::Scale(3), Transform::Offset(-20), Transform::Abs, Transform::Modulo(7), Transform::RoundTo(5)]);
+        assert_eq!(steps.iter().map(ToString::to_string).collect::<Vec<_>>().join(", "), "scale 3, offset -20, abs, mod 7, round 5");
+        assert_eq!(Transform::<i64>::parse_list(" ").unwrap(), []);
+        for bad in ["scale", "scale x", "mod 0", "round -5", "abs 2", "square 2"] {
+            assert!(matches!(bad.parse::<Transform<i64>>(), Err(RandomGeneratorError::InvalidTransform(_))), "{}", bad);
+        }
+
+        // 负数取模结果不为负,正好一半时向上取整
+        assert_eq!(Transform::Modulo(7).apply(-1i64), Some(6));
+        assert_eq!([-15i64, -12, 12, 15].map(|value| Transform::RoundTo(10).apply(value)), [Some(-10), Some(-10), Some(10), Some(20)]);
+        assert_eq!(Transform::Abs.apply(i64::MIN), None);
+        assert_eq!(Transform::Abs.apply(5u64), Some(5));
+        assert_eq!(Transform::RoundTo(10).apply(u64::MAX), None);
+        assert_eq!(Transform::Scale(0.5).apply(3.0), Some(1.5));
+        assert_eq!(Transform::RoundTo(0.25).apply(0.3), Some(0.25));
+
+        // 洗牌袋记录原始值,排序用变换后的值
+        let config = GeneratorConfig {
+            lower_bound: 1,
+            upper_bound: 10,
+            num_to_generate: 10,
+            order: ResultOrder::Sorted,
+            transforms: vec![Transform::Scale(-1)],
+            shuffle_bag: Some(ShuffleBag::new()),
+            ..GeneratorConfig::default()
+        };
+        let mut random_gen = RandomGenerator::with_config(config.clone()).unwrap();
+        random_gen.generate_numbers().unwrap();
+        assert_eq!(random_gen.get_numbers(), (-10..=-1).collect::<Vec<_>>());
+        assert_eq!(random_gen.shuffle_bag().unwrap().left(), 0);
+
+        let mut overflow = RandomGenerator::with_config(GeneratorConfig { transforms: vec![Transform::Scale(i64::MAX)], ..config.clone() }).unwrap();
+        assert!(matches!(overflow.generate_numbers(), Err(RandomGeneratorError::InvalidTransform(_))));
+        assert!(overflow.get_numbers().is_empty());
+        assert_eq!(overflow.shuffle_bag().unwrap().size(), 0);
+        let problems = random_gen.validate(&GeneratorConfig { transforms: vec![Transform::Modulo(0)], ..config });
+        assert!(matches!(problems[..], [RandomGeneratorError::InvalidTransform(_)]));
+    }
+
+    #[test]
+    fn test_repeated_list_entries() {
+        let config = GeneratorConfig {
+            mode: GeneratorMode::CUSTOM_LIST,
+            custom_list: vec![7, 7, 7, 7, 7, 7, 3],
+            num_to_generate: 2,
+            ..GeneratorConfig::default()
+        };
+        for policy in [ListDuplicates::Weighted, ListDuplicates::Dedupe] {
+            let mut random_gen = RandomGenerator::with_config(GeneratorConfig { list_duplicates: policy, ..config.clone() }).unwrap();
+            random_gen.generate_numbers().unwrap();
+            let mut numbers = random_gen.get_numbers().to_vec();
+            numbers.sort();
+            assert_eq!(numbers, [3, 7]);
+            assert!(matches!(random_gen.set_num_to_generate(3), Err(RandomGeneratorError::TooManyNumbers)));
+        }
+        assert_eq!(ListSource::weights(&config), [(7, 6), (3, 1)]);
+        assert_eq!(ListSource::weights(&GeneratorConfig { list_duplicates: ListDuplicates::Dedupe, ..config.clone() }), [(7, 1), (3, 1)]);
+
+        let rejected = RandomGenerator::with_config(GeneratorConfig { list_duplicates: ListDuplicates::Reject, ..config });
+        assert!(matches!(rejected, Err(RandomGeneratorError::DuplicateEntries)));
+    }
+
+    #[test]
+    fn test_shuffle_bag() {
+        let mut random_gen = RandomGenerator::with_config(GeneratorConfig {
+            lower_bound: 1,
+            upper_bound: 5,
+            num_to_generate: 3,
+            shuffle_bag: Some(ShuffleBag::new()),
+            ..GeneratorConfig::default()
+        })
+        .unwrap();
+        let left = |random_gen: &RandomGenerator| random_gen.get_config().shuffle_bag.as_ref().unwrap().left();
+        for _ in 0..20 {
+            random_gen.generate_numbers().unwrap();
+            let mut first = random_gen.get_numbers().to_vec();
+            assert_eq!(left(&random_gen), 2);
+            // The last two of the round come first, then a new round starts without repeating within the batch
+            random_gen.generate_numbers().unwrap();
+            let second = random_gen.get_numbers();
+            assert_eq!(second.iter().collect::<HashSet<_>>().len(), 3);
+            assert_eq!(left(&random_gen), 4);
+            first.extend_from_slice(&second[..2]);
+            first.sort();
+            assert_eq!(first, [1, 2, 3, 4, 5]);
+            let mut config = random_gen.get_config().clone();
+            config.shuffle_bag.as_mut().unwrap().refill();
+            random_gen.set_config(config).unwrap();
+        }
+
+        // Repeated entries come out as often as they are listed, once per round
+        let mut weighted = RandomGenerator::with_config(GeneratorConfig {
+            mode: GeneratorMode::CUSTOM_LIST,
+            custom_list: vec![7, 7, 3],
+            num_to_generate: 6,
+            allow_duplicates: true,
+            shuffle_bag: Some(ShuffleBag::new()),
+            ..GeneratorConfig::default()
+        })
+        .unwrap();
+        weighted.generate_numbers().unwrap();
+        let numbers = weighted.get_numbers();
+        for round in numbers.chunks(3) {
+            let mut round = round.to_vec();
+            round.sort();
+            assert_eq!(round, [3, 7, 7]);
+        }
+
+        // A new list starts a full bag
+        let mut config = weighted.get_config().clone();
+        config.custom_list = vec![1, 2, 3, 4];
+        config.num_to_generate = 1;
+        weighted.set_config(config).unwrap();
+        weighted.generate_numbers().unwrap();
+        let bag = weighted.get_config().shuffle_bag.as_ref().unwrap();
+        assert_eq!((bag.size(), bag.left()), (4, 3));
+
+        let script = RandomGenerator::with_source(Box::new(ScriptSource { script: "roll(1, 6)".to_string() }), GeneratorConfig {
+            mode: GeneratorMode::SCRIPT,
+            shuffle_bag: Some(ShuffleBag::new()),
+            ..GeneratorConfig::default()
+        });
+        assert!(matches!(script, Err(RandomGeneratorError::InvalidDraw(_))));
+        let huge = RandomGenerator::with_config(GeneratorConfig { upper_bound: 10_000_000, shuffle_bag: Some(ShuffleBag::new()), ..GeneratorConfig::default() });
+        assert!(matches!(huge, Err(RandomGeneratorError::InvalidDraw(_))));
+    }
+
+    #[test]
+    fn test_cooldown() {
+        let config = GeneratorConfig {
+            mode: GeneratorMode::CUSTOM_LIST,
+            custom_list: vec![1, 2, 3, 4],
+            num_to_generate: 1,
+            cooldown: Some(Cooldown::new(2)),
+            ..GeneratorConfig::default()
+        };
+        // One draw per click still keeps the last two out, across clicks
+        let mut random_gen = RandomGenerator::with_config(config.clone()).unwrap();
+        let mut drawn = Vec::new();
+        for _ in 0..200 {
+            random_gen.generate_numbers().unwrap();
+            drawn.push(random_gen.get_numbers()[0]);
+        }
+        assert!(drawn.windows(3).all(|run| run[0] != run[1] && run[0] != run[2] && run[1] != run[2]));
+        assert!((1..=4).all(|value| drawn.contains(&value)));
+        let mut cooldown = random_gen.get_config().cooldown.clone().unwrap();
+        assert_eq!(cooldown.recent().collect::<Vec<_>>(), drawn[198..]);
+        cooldown.set_draws(1);
+        assert_eq!(cooldown.recent().collect::<Vec<_>>(), drawn[199..]);
+
+        // A batch with repeats obeys it between its own draws too
+        let mut batch = RandomGenerator::with_config(GeneratorConfig { num_to_generate: 100, allow_duplicates: true, ..config.clone() }).unwrap();
+        batch.generate_numbers().unwrap();
+        assert!(batch.get_numbers().windows(3).all(|run| run[0] != run[1] && run[0] != run[2] && run[1] != run[2]));
+
+        let too_long = RandomGenerator::with_config(GeneratorConfig { cooldown: Some(Cooldown::new(4)), ..config.clone() });
+        assert!(matches!(too_long, Err(RandomGeneratorError::InvalidDraw(_))));
+        let both = RandomGenerator::with_config(GeneratorConfig { shuffle_bag: Some(ShuffleBag::new()), ..config });
+        assert!(matches!(both, Err(RandomGeneratorError::InvalidDraw(_))));
+    }
+
+    #[test]
+    fn test_exclude_drawn() {
+        let skipped = [0, 2, 3, 7];
+        let mapped: Vec<u128> = (0..6).map(|i| exclusion::skip_to(&skipped, i)).collect();
+        assert_eq!(mapped, [1, 4, 5, 6, 8, 9]);
+
+        let mut random_gen = RandomGenerator::with_config(GeneratorConfig {
+            lower_bound: 1,
+            upper_bound: 10,
+            num_to_generate: 4,
+            exclude_drawn: Some(ExclusionSet::new()),
+            ..GeneratorConfig::default()
+        })
+        .unwrap();
+        let mut drawn = Vec::new();
+        for _ in 0..2 {
+            random_gen.generate_numbers().unwrap();
+            drawn.extend_from_slice(random_gen.get_numbers());
+        }
+        assert_eq!(drawn.iter().collect::<HashSet<_>>().len(), 8);
+        assert!(matches!(random_gen.generate_numbers(), Err(RandomGeneratorError::TooManyNumbers)));
+        random_gen.set_num_to_generate(2).unwrap();
+        random_gen.generate_numbers().unwrap();
+        drawn.extend_from_slice(random_gen.get_numbers());
+        drawn.sort();
+        assert_eq!(drawn, (1..=10).collect::<Vec<_>>());
+
+        // Repeats within a batch are still fine, but nothing is left to draw
+        random_gen.set_allow_duplicates(true).unwrap();
+        assert!(matches!(random_gen.generate_numbers(), Err(RandomGeneratorError::InvalidDraw(_))));
+        let mut config = random_gen.get_config().clone();
+        config.exclude_drawn.as_mut().unwrap().clear();
+        config.mode = GeneratorMode::CUSTOM_LIST;
+        config.custom_list = vec![1, 2];
+        assert!(matches!(random_gen.set_config(config), Err(RandomGeneratorError::InvalidDraw(_))));
+
+        let mut continuous = Generator::<f64>::with_config(Config {
+            num_to_generate: 100,
+            exclude_drawn: Some(ExclusionSet::new()),
+            ..Config::default()
+        })
+        .unwrap();
+        continuous.generate_numbers().unwrap();
+        continuous.generate_numbers().unwrap();
+        assert_eq!(continuous.get_config().exclude_drawn.as_ref().unwrap().len(), 200);
+    }
+
+    #[test]
+    fn test_tickets() {
+        // 还没有发票
+        let config = GeneratorConfig { mode: GeneratorMode::TICKETS, lower_bound: 1, upper_bound: 0, ..GeneratorConfig::default() };
+        assert!(matches!(RandomGenerator::with_config(config.clone()), Err(RandomGeneratorError::InvalidDraw(_))));
+        assert!(Generator::<f64>::with_config(Config { mode: GeneratorMode::TICKETS, ..Config::default() }).is_err());
+
+        // 发了 3 张票,每轮抽一张,中过奖的票不再抽到
+        let mut random_gen = RandomGenerator::with_config(GeneratorConfig {
+            upper_bound: 3,
+            exclude_drawn: Some(ExclusionSet::new()),
+            ..config
+        })
+        .unwrap();
+        let mut winners = Vec::new();
+        for _ in 0..3 {
+            random_gen.generate_numbers().unwrap();
+            winners.extend_from_slice(random_gen.get_numbers());
+        }
+        winners.sort();
+        assert_eq!(winners, [1, 2, 3]);
+
+        // 再发一张票后只剩新票可以抽
+        random_gen.set_upper_bound(4).unwrap();
+        random_gen.generate_numbers().unwrap();
+        assert_eq!(random_gen.get_numbers(), [4]);
+        assert_eq!(random_gen.draw_space(random_gen.get_config()).values, Some(0));
+    }
+
+    #[test]
+    fn test_max_repeats() {
+        let config = GeneratorConfig {
+            lower_bound: 1,
+            upper_bound: 4,
+            num_to_generate: 12,
+            allow_duplicates: true,
+            max_repeats: Some(3),
+            ..GeneratorConfig::default()
+        };
+        let count = |numbers: &[i64], value: i64| numbers.iter().filter(|&&n| n == value).count();
+        let mut random_gen = RandomGenerator::with_config(config.clone()).unwrap();
+        for _ in 0..20 {
+            random_gen.generate_numbers().unwrap();
+            assert!((1..=4).all(|value| count(random_gen.get_numbers(), value) == 3));
+        }
+        assert!(matches!(random_gen.set_num_to_generate(13), Err(RandomGeneratorError::TooManyNumbers)));
+        assert!(matches!(random_gen.set_max_repeats(Some(2)), Err(RandomGeneratorError::TooManyNumbers)));
+        assert!(matches!(random_gen.set_max_repeats(Some(0)), Err(RandomGeneratorError::InvalidDraw(_))));
+        random_gen.set_max_repeats(None).unwrap();
+        random_gen.set_num_to_generate(13).unwrap();
+
+        // Weighted lists and scripts are capped the same way
+        let mut list = RandomGenerator::with_config(GeneratorConfig {
+            mode: GeneratorMode::CUSTOM_LIST,
+            custom_list: vec![1, 1, 1, 1, 1, 1, 1, 1, 2],
+            list_duplicates: ListDuplicates::Weighted,
+            num_to_generate: 4,
+            max_repeats: Some(2),
+            ..config.clone()
+        })
+        .unwrap();
+        list.generate_numbers().unwrap();
+        assert_eq!((count(list.get_numbers(), 1), count(list.get_numbers(), 2)), (2, 2));
+        let mut script = RandomGenerator::with_source(Box::new(ScriptSource { script: "roll(1, 2)".to_string() }), GeneratorConfig {
+            mode: GeneratorMode::SCRIPT,
+            num_to_generate: 5,
+            max_repeats: Some(2),
+            ..config.clone()
+        })
+        .unwrap();
+        assert!(matches!(script.generate_numbers(), Err(RandomGeneratorError::InvalidDraw(_))));
+
+        let bag = RandomGenerator::with_config(GeneratorConfig { shuffle_bag: Some(ShuffleBag::new()), ..config });
+        assert!(matches!(bag, Err(RandomGeneratorError::InvalidDraw(_))));
+    }
+
+    #[test]
+    fn test_max_streak() {
+        let config = GeneratorConfig {
+            lower_bound: 1,
+            upper_bound: 2,
+            num_to_generate: 200,
+            allow_duplicates: true,
+            max_streak: Some(2),
+            ..GeneratorConfig::default()
+        };
+        let mut random_gen = RandomGenerator::with_config(config.clone()).unwrap();
+        random_gen.generate_numbers().unwrap();
+        assert!(random_gen.get_numbers().windows(3).all(|run| !(run[0] == run[1] && run[1] == run[2])));
+        assert!(random_gen.get_numbers().windows(2).any(|pair| pair[0] == pair[1]));
+
+        // A single value can only fill the streak
+        let single = GeneratorConfig { upper_bound: 1, num_to_generate: 3, ..config.clone() };
+        assert!(matches!(RandomGenerator::with_config(single.clone()), Err(RandomGeneratorError::TooManyNumbers)));
+        assert_eq!(random_gen.capacity(&single), Some(2));
+        assert!(matches!(random_gen.set_max_streak(Some(0)), Err(RandomGeneratorError::InvalidDraw(_))));
+
+        let sorted = RandomGenerator::with_config(GeneratorConfig { order: ResultOrder::Sorted, ..config.clone() });
+        assert!(matches!(sorted, Err(RandomGeneratorError::InvalidDraw(_))));
+        // Without duplicates the limit has nothing to do
+        RandomGenerator::with_config(GeneratorConfig { allow_duplicates: false, num_to_generate: 2, order: ResultOrder::Sorted, ..config }).unwrap();
+    }
+
+    #[test]
+    fn test_stratified() {
+        let text = "1..10: 10  # all of them\n-5, 50: 0; 100..1000: 3";
+        let config = GeneratorConfig { mode: GeneratorMode::STRATIFIED, ..GeneratorConfig::default() };
+        let mut random_gen = RandomGenerator::with_source(Box::new(StratifiedSource { strata: text.to_string() }), config.clone()).unwrap();
+        random_gen.generate_numbers().unwrap();
+        let numbers = random_gen.get_numbers();
+        assert_eq!(numbers.len(), 13);
+        let mut first = numbers[..10].to_vec();
+        first.sort();
+        assert_eq!(first, (1..=10).collect::<Vec<_>>());
+        assert!(numbers[10..].iter().all(|n| (100..=1000).contains(n)));
+
+        let strata = Strata::<i64>::parse(text).unwrap();
+        assert_eq!(strata.total(), 13);
+        assert_eq!(strata.strata()[1].values, StratumValues::List(vec![-5, 50]));
+        assert_eq!(strata.strata()[1].count, 0);
+        assert_eq!(strata.strata()[2].line, 2);
+
+        // Each stratum is checked on its own, and they can't overlap
+        let line = |strata: &str, duplicates: bool| {
+            let source = StratifiedSource { strata: strata.to_string() };
+            let problems = RandomGenerator::validate_with(&source, &GeneratorConfig { allow_duplicates: duplicates, ..config.clone() });
+            match problems[..] {
+                [RandomGeneratorError::InvalidStrata { line, .. }] => Some(line),
+                _ => None,
+            }
+        };
+        assert_eq!(line("1..10: 2\n11..12: 3", false), Some(2));
+        assert_eq!(line("1..10: 2\n11..12: 3", true), None);
+        assert_eq!(line("1..10: 2\n\n3, 20: 1", false), Some(3));
+        assert_eq!(line("1..10: 2\n5..20: 1", false), Some(2));
+        assert_eq!(line("7, 7: 1", false), Some(1));
+        assert_eq!(line("10..1: 1", false), Some(1));
+        assert_eq!(line("1..10", false), Some(1));
+        assert_eq!(line("# nothing", false), Some(0));
+    }
+
+    #[test]
+    fn test_partition() {
+        let hundred = PartitionSource { total: 100 };
+        let config = GeneratorConfig {
+            mode: GeneratorMode::PARTITION,
+            num_to_generate: 7,
+            lower_bound: 0,
+            upper_bound: 100,
+            ..GeneratorConfig::default()
+        };
+        for (lower, upper) in [(0, 100), (10, 20), (14, 15), (-50, 100)] {
+            let mut random_gen = RandomGenerator::with_source(Box::new(hundred), GeneratorConfig { lower_bound: lower, upper_bound: upper, ..config.clone() }).unwrap();
+            random_gen.generate_numbers().unwrap();
+            let parts = random_gen.get_numbers();
+            assert_eq!(parts.len(), 7);
+            assert_eq!(parts.iter().sum::<i64>(), 100);
+            assert!(parts.iter().all(|part| (lower..=upper).contains(part)), "{:?}", parts);
+        }
+
+        // Every split of 4 into 3 parts comes up about equally often
+        let mut counts = HashMap::new();
+        let mut random_gen = RandomGenerator::with_source(Box::new(PartitionSource { total: 4 }), GeneratorConfig { num_to_generate: 3, seed: None, ..config.clone() }).unwrap();
+        for _ in 0..15_000 {
+            random_gen.generate_numbers().unwrap();
+            *counts.entry(random_gen.get_numbers().to_vec()).or_insert(0) += 1;
+        }
+        assert_eq!(counts.len(), 15);
+        assert!(counts.values().all(|&count| (800..1200).contains(&count)), "{:?}", counts);
+
+        let problem = |config: GeneratorConfig| match RandomGenerator::validate_with(&hundred, &config)[..] {
+            [RandomGeneratorError::InvalidPartition(ref message)] => Some(message.clone()),
+            _ => None,
+        };
+        assert!(problem(GeneratorConfig { lower_bound: 15, ..config.clone() }).is_some_and(|message| message.contains("at least 15")));
+        assert!(problem(GeneratorConfig { upper_bound: 14, ..config.clone() }).is_some_and(|message| message.contains("at most 14")));
+        assert!(problem(GeneratorConfig { num_to_generate: 0, ..config.clone() }).is_some());
+        assert!(problem(GeneratorConfig { allow_duplicates: true, max_repeats: Some(2), ..config.clone() }).is_some());
+        // Parts may be equal without allowing duplicates, and there can be more parts than values
+        assert!(problem(GeneratorConfig { num_to_generate: 100, upper_bound: 1, ..config.clone() }).is_none());
+        assert!(RandomGenerator::validate_with(&hundred, &GeneratorConfig { num_to_generate: 100, upper_bound: 1, ..config.clone() }).is_empty());
+        assert!(matches!(
+            Generator::<f64>::new().validate(&Config { mode: GeneratorMode::PARTITION, ..Config::default() })[..],
+            [RandomGeneratorError::InvalidPartition(_)]
+        ));
+    }
+
+    #[test]
+    fn test_trials() {
+        let geometric = |chance| Box::new(GeometricSource { chance });
+        let config = GeneratorConfig { mode: GeneratorMode::GEOMETRIC, num_to_generate: 100_000, ..GeneratorConfig::default() };
+        let mut random_gen = RandomGenerator::with_source(geometric(0.25), config.clone()).unwrap();
+        random_gen.generate_numbers().unwrap();
+        let moments = |numbers: &[i64]| {
+            let mean = numbers.iter().sum::<i64>() as f64 / numbers.len() as f64;
+            let variance = numbers.iter().map(|&n| (n as f64 - mean).powi(2)).sum::<f64>() / numbers.len() as f64;
+            (numbers.iter().min().copied(), mean, variance)
+        };
+        // Tries until the first success: at least 1, mean 1/p = 4, variance (1 - p)/p² = 12
+        let (min, mean, variance) = moments(random_gen.get_numbers());
+        assert_eq!(min, Some(1));
+        assert!((mean - 4.0).abs() < 0.05 && (variance - 12.0).abs() < 0.5, "{} {}", mean, variance);
+        let ones = random_gen.get_numbers().iter().filter(|&&tries| tries == 1).count();
+        assert!((24_000..26_000).contains(&ones), "{}", ones);
+
+        // Tries until the 5th success: at least 5, mean r/p
+        let negative_binomial = |chance, successes| Box::new(NegativeBinomialSource { chance, successes });
+        let negative = GeneratorConfig { mode: GeneratorMode::NEGATIVE_BINOMIAL, ..config.clone() };
+        let mut random_gen = RandomGenerator::with_source(negative_binomial(0.7, 5), negative.clone()).unwrap();
+        random_gen.generate_numbers().unwrap();
+        let (min, mean, _) = moments(random_gen.get_numbers());
+        assert_eq!(min, Some(5));
+        assert!((mean - 5.0 / 0.7).abs() < 0.03, "{}", mean);
+
+        let mut certain = RandomGenerator::with_source(negative_binomial(1.0, 5), GeneratorConfig { num_to_generate: 10, ..negative.clone() }).unwrap();
+        certain.generate_numbers().unwrap();
+        assert_eq!(certain.get_numbers(), [5; 10]);
+        // A tiny chance needs more tries than an i64 holds; the error gives the size, not 300 digits
+        let mut hopeless = RandomGenerator::with_source(geometric(1e-300), GeneratorConfig { num_to_generate: 1, ..config.clone() }).unwrap();
+        let error = hopeless.generate_numbers().unwrap_err().to_string();
+        assert!(error.starts_with("Trials: too many trials (about ") && error.len() < 100, "{}", error);
+        let mut floats = Generator::<f64>::with_config(Config { mode: GeneratorMode::GEOMETRIC, num_to_generate: 10, ..Config::default() }).unwrap();
+        floats.generate_numbers().unwrap();
+        assert!(floats.get_numbers().iter().all(|&tries| tries >= 1.0 && tries.fract() == 0.0));
+
+        let problems = |source: &dyn GeneratorSource<i64>, config: &GeneratorConfig| RandomGenerator::validate_with(source, config);
+        for chance in [0.0, -0.5, 1.5, f64::NAN] {
+            assert!(matches!(problems(&GeometricSource { chance }, &config)[..], [RandomGeneratorError::InvalidTrials(_)]), "{}", chance);
+        }
+        for successes in [0, MAX_SUCCESSES + 1] {
+            assert!(matches!(problems(&NegativeBinomialSource { chance: 0.7, successes }, &negative)[..], [RandomGeneratorError::InvalidTrials(_)]));
+        }
+        assert!(matches!(
+            problems(&GeometricSource { chance: 0.25 }, &GeneratorConfig { allow_duplicates: true, max_repeats: Some(2), ..config.clone() })[..],
+            [RandomGeneratorError::InvalidTrials(_)]
+        ));
+        let mut hopeless = RandomGenerator::with_source(geometric(1e-300), GeneratorConfig { num_to_generate: 1, ..config }).unwrap();
+        assert!(matches!(hopeless.generate_numbers(), Err(RandomGeneratorError::InvalidTrials(_))));
+    }
+
+    #[test]
+    fn test_sample_indices_in_bounded_memory() {
+        let mut rng = RngBackend::Thread.create();
+        let mut progress = Progress { cancel: &AtomicBool::new(false), on_progress: &mut |_, _| {} };
+
+        let mut all = source::sample_indices(&mut *rng, 10, 10, &mut progress).unwrap();
+        all.sort();
+        assert_eq!(all, (0..10).collect::<Vec<u128>>());
+
+        let huge = 1u128 << 100;
+        let picked = source::sample_indices(&mut *rng, huge, 1000, &mut progress).unwrap();
+        assert_eq!(picked.iter().collect::<HashSet<_>>().len(), 1000);
+        assert!(picked.iter().all(|&i| i < huge));
+
+        let sparse = source::floyd_indices(&mut *rng, huge, 1000, &mut progress).unwrap();
+        assert_eq!(sparse.iter().collect::<HashSet<_>>().len(), 1000);
+        assert!(sparse.iter().all(|&i| i < huge));
+        let mut whole = source::floyd_indices(&mut *rng, 6, 6, &mut progress).unwrap();
+        whole.sort();
+        assert_eq!(whole, [0, 1, 2, 3, 4, 5]);
+
+        // 接近整个 i64 范围时不会溢出,也不会尝试列出所有数字
+        let mut random_gen = RandomGenerator::with_config(GeneratorConfig {
+            lower_bound: i64::MIN,
+            upper_bound: i64::MAX,
+            num_to_generate: 5,
+            ..GeneratorConfig::default()
+        })
+        .unwrap();
+        random_gen.generate_numbers().unwrap();
+        assert_eq!(random_gen.get_numbers().len(), 5);
+    }
+
+    #[test]
+    fn test_self_test() {
+        let mut random_gen = RandomGenerator::new();
+        let report = random_gen.self_test(50_000).unwrap();
+        assert_eq!(report.samples, 50_000);
+        let chi = report.chi_squared.as_ref().unwrap();
+        assert_eq!(chi.degrees_of_freedom, 19);
+        // 远宽于报告所用的阈值,避免偶然失败
+        assert!(chi.p_value > 1e-6 && report.runs.p_value > 1e-6, "{}", report);
+        assert!(random_gen.get_numbers().is_empty());
+
+        random_gen
+            .set_config(GeneratorConfig {
+                mode: GeneratorMode::CUSTOM_LIST,
+                custom_list: vec![1, 1, 1, 2],
+                ..GeneratorConfig::default()
+            })
+            .unwrap();
+        let report = random_gen.self_test(20_000).unwrap();
+        assert!(report.chi_squared.unwrap().p_value > 1e-6);
+
+        /// 总是交替抽出上下界
+        struct Alternating;
+
+        impl GeneratorSource<i64> for Alternating {
+            fn mode(&self) -> GeneratorMode {
+                GeneratorMode::RANGE
+            }
+
+            fn input(&self) -> SourceInput {
+                SourceInput::Bounds
+            }
+
+            fn validate(&self, _config: &GeneratorConfig) -> Vec<RandomGeneratorError> {
+                Vec::new()
+            }
+
+            fn available(&self, config: &GeneratorConfig) -> Option<u128> {
+                RangeSource.available(config)
+            }
+
+            fn generate(
+                &self,
+                config: &GeneratorConfig,
+                _rng: &mut dyn RngCore,
+                _progress: &mut Progress<'_>,
+            ) -> Result<Vec<i64>, RandomGeneratorError> {
+                let bounds = [config.lower_bound, config.upper_bound];
+                Ok((0..config.num_to_generate).map(|i| bounds[i % 2]).collect())
+            }
+        }
+
+        random_gen.register_source(Box::new(Alternating));
+        random_gen.set_mode(GeneratorMode::RANGE).unwrap();
+        assert!(!random_gen.self_test(10_000).unwrap().is_healthy());
+    }
+
+    #[test]
+    fn test_seed_replay() {
+        let mut random_gen = RandomGenerator::with_config(GeneratorConfig {
+            upper_bound: 1_000_000,
+            num_to_generate: 20,
+            ..GeneratorConfig::default()
+        })
+        .unwrap();
+        assert!(random_gen.last_seed().is_none());
+        random_gen.generate_numbers().unwrap();
+        let first = random_gen.get_numbers().to_vec();
+        let seed = random_gen.last_seed().unwrap();
+
+        random_gen.generate_numbers().unwrap();
+        assert_ne!(random_gen.last_seed(), Some(seed));
+        random_gen.replay(seed).unwrap();
+        assert_eq!(random_gen.get_numbers(), first);
+        assert!(random_gen.get_config().seed.is_none());
+
+        // 种子以文本记录,解析回来后在新的生成器上同样可以重现
+        let text = seed.to_string();
+        assert_eq!(text.len(), 64);
+        let mut other = RandomGenerator::with_config(GeneratorConfig {
+            seed: Some(text.parse().unwrap()),
+            ..random_gen.get_config().clone()
+        })
+        .unwrap();
+        other.generate_numbers().unwrap();
+        assert_eq!(other.get_numbers(), first);
+        assert!("xyz".parse::<Seed>().is_err());
+        assert!(text[..62].parse::<Seed>().is_err());
+    }
+
+    #[cfg(feature = "plugins")]
+    #[test]
+    fn test_plugin_mode_and_exporter() {
+        use plugin::{RtExporter, RtMode, RtPlugin, RtRandom, RtRequest, RtSink, PLUGIN_ABI_VERSION, RT_INPUT_LIST};
+        use std::ffi::c_char;
+
+        // 每个数取列表中的一项再乘以 10
+        unsafe extern "C" fn tens(request: *const RtRequest, random: *const RtRandom, out: *mut i64) -> i32 {
+            let (request, random) = (&*request, &*random);
+            let list = std::slice::from_raw_parts(request.list, request.list_len);
+            for i in 0..request.count {
+                let pick = (random.next_u64)(random.context) as usize % list.len();
+                *out.add(i) = list[pick] * 10;
+            }
+            0
+        }
+        unsafe extern "C" fn sum(numbers: *const i64, len: usize, sink: *const RtSink) -> i32 {
+            let total: i64 = std::slice::from_raw_parts(numbers, len).iter().sum();
+            let text = format!("sum={}", total);
+            ((*sink).write)((*sink).context, text.as_ptr(), text.len());
+            0
+        }
+
+        let modes = [RtMode { name: c"Tens".as_ptr() as *const c_char, input: RT_INPUT_LIST, generate: tens }];
+        let exporters = [RtExporter {
+            name: c"Sum".as_ptr() as *const c_char,
+            extension: c"sum".as_ptr() as *const c_char,
+            export_numbers: sum,
+        }];
+        let mut descriptor = RtPlugin {
+            abi_version: PLUGIN_ABI_VERSION,
+            name: c"Test".as_ptr() as *const c_char,
+            modes: modes.as_ptr(),
+            mode_count: modes.len(),
+            exporters: exporters.as_ptr(),
+            exporter_count: exporters.len(),
+        };
+        let plugin = unsafe { Plugin::from_descriptor(&descriptor, None) }.unwrap();
+        assert_eq!(plugin.name(), "Test");
+        assert_eq!(plugin.formats(), ["Sum"]);
+
+        let mut random_gen = RandomGenerator::new();
+        plugin.install(&mut random_gen);
+        let tens_mode = plugin.modes()[0];
+        assert!(random_gen.modes().contains(&tens_mode));
+        assert_eq!(random_gen.source(tens_mode).unwrap().input(), SourceInput::List);
+
+        random_gen.set_config(GeneratorConfig {
+            mode: tens_mode,
+            custom_list: vec![1, 2, 3],
+            num_to_generate: 50,
+            allow_duplicates: true,
+            ..GeneratorConfig::default()
+        }).unwrap();
+        random_gen.generate_numbers().unwrap();
+        let first = random_gen.get_numbers().to_vec();
+        assert!(first.iter().all(|n| [10, 20, 30].contains(n)));
+        // 插件使用生成器的随机数,同一种子得到同样的结果
+        random_gen.replay(random_gen.last_seed().unwrap()).unwrap();
+        assert_eq!(random_gen.get_numbers(), first);
+
+        let filename = "test_plugin_export.sum";
+        random_gen.save_numbers_with(filename, "Sum", &NumberFormat::default()).unwrap();
+        assert_eq!(fs::read_to_string(filename).unwrap(), format!("sum={}", first.iter().sum::<i64>()));
+        fs::remove_file(filename).unwrap();
+        assert!(matches!(
+            random_gen.save_numbers_with(filename, "Nope", &NumberFormat::default()),
+            Err(RandomGeneratorError::UnknownFormat(_))
+        ));
+
+        // 另一个插件的同名模式不会替换先加载的那个
+        let again = unsafe { Plugin::from_descriptor(&descriptor, None) }.unwrap();
+        assert_eq!(again.clash(&plugin).unwrap(), "Test: mode 'Tens' is already provided by Test");
+        // 也不能换掉内置模式
+        let builtin = [RtMode { name: c"range".as_ptr() as *const c_char, input: RT_INPUT_LIST, generate: tens }];
+        descriptor.modes = builtin.as_ptr();
+        assert!(matches!(unsafe { Plugin::from_descriptor(&descriptor, None) }, Err(RandomGeneratorError::Plugin(message)) if message == "Test: mode 'range' is built in"));
+
+        descriptor.modes = modes.as_ptr();
+        descriptor.abi_version = PLUGIN_ABI_VERSION + 1;
+        assert!(unsafe { Plugin::from_descriptor(&descriptor, None) }.is_err());
+    }
+
+    #[test]
+    fn test_dice_script() {
+        let source = |text: &str| ScriptSource { script: text.to_string() };
+        let config = GeneratorConfig { mode: GeneratorMode::SCRIPT, num_to_generate: 500, ..GeneratorConfig::default() };
+        let text = "let dice = roll(2, 6); // 两颗骰子\ndice.reroll(1);\ndice.sum() + 3";
+        let mut random_gen = RandomGenerator::with_source(Box::new(source(text)), config.clone()).unwrap();
+        random_gen.generate_numbers().unwrap();
+        assert!(random_gen.get_numbers().iter().all(|n| (5..=15).contains(n)));
+
+        // 只保留最大的一颗四面骰,结果总在 1..=4
+        let script = Script::parse("let d = roll(4, 4); d.keep_highest(1); d").unwrap();
+        let mut rng = rand::thread_rng();
+        assert!((0..200).map(|_| script.run(&mut rng).unwrap()).all(|n| (1..=4).contains(&n)));
+        let script = Script::parse("let d = roll(3, 6); d.drop_lowest(3); (d.sum() + 2) * 10 + (roll(1, 1) + roll(2, 1)).len").unwrap();
+        assert_eq!(script.run(&mut rng).unwrap(), 23);
+        // 同一个种子得到同样的结果
+        let script = Script::parse("random(1, 1000000)").unwrap();
+        assert_eq!(script.run(&mut Seed::from([7; 32]).rng()).unwrap(), script.run(&mut Seed::from([7; 32]).rng()).unwrap());
+
+        for (bad, line) in [("roll(2, 6", 1), ("let d = roll(1, 6);\nd.explode(6)", 2), ("undefined + 1", 1), ("\"six\"", 0)] {
+            let error = Script::parse(bad).and_then(|script| script.run(&mut rng));
+            match error {
+                Err(RandomGeneratorError::InvalidScript { line: found, .. }) => assert_eq!(found, line, "{}", bad),
+                other => panic!("{} ran as {:?}", bad, other),
+            }
+        }
+        // 文件、死循环和过多的骰子都会被拦下
+        for bad in ["import \"fs\" as fs; 1", "eval(\"1\")", "loop {}", "let n = 0; for i in 0..2000 { n += roll(1, 6).sum() } n"] {
+            assert!(!RandomGenerator::validate_with(&source(bad), &config).is_empty(), "{}", bad);
+        }
+        assert_eq!(
+            GeneratorSource::<i64>::parameters(&source("let d = roll(2, 6);\n\nd.sum() + 3\n")),
+            [("script", "let d = roll(2, 6);\\nd.sum() + 3".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_benchmark() {
+        let mut random_gen = RandomGenerator::with_config(GeneratorConfig {
+            lower_bound: 1,
+            upper_bound: 100,
+            num_to_generate: 95,
+            ..GeneratorConfig::default()
+        })
+        .unwrap();
+        let report = random_gen.benchmark(3).unwrap();
+        assert_eq!(report.results.len(), UniqueStrategy::ALL.len());
+        let chosen: Vec<_> = report.results.iter().filter(|r| r.chosen).map(|r| r.strategy).collect();
+        assert_eq!(chosen, vec![Some(UniqueStrategy::Shuffle)]);
+        // 数量接近范围大小时跳过集合法
+        let set = report.results.iter().find(|r| r.strategy == Some(UniqueStrategy::Set)).unwrap();
+        assert!(set.elapsed.is_none());
+        assert!(random_gen.get_numbers().is_empty());
+
+        random_gen.set_allow_duplicates(true).unwrap();
+        let report = random_gen.benchmark(3).unwrap();
+        assert_eq!(report.results.len(), 1);
+        assert_eq!(report.results[0].strategy, None);
+    }
+
+    #[test]
+    fn test_number_format() {
+        let plain = NumberFormat::default();
+        assert_eq!(plain.format(-1234), "-1234");
+
+        let grouped = NumberFormat { separator: ThousandsSeparator::Comma, min_width: 0, ..Default::default() };
+        assert_eq!(grouped.format(1234567), "1,234,567");
+        assert_eq!(grouped.format(-999), "-999");
+
+        let padded = NumberFormat { separator: ThousandsSeparator::None, min_width: 5, ..Default::default() };
+        assert_eq!(padded.format(42), "00042");
+        assert_eq!(padded.format(-42), "-00042");
+
+        let european = NumberFormat { separator: ThousandsSeparator::Period, min_width: 0, ..Default::default() };
+        assert_eq!(european.format(-1234.5), "-1.234,5");
+    }
+
+    #[test]
+    fn test_grouped_export() {
+        let groups = [vec![4821i64, 17], vec![9]];
+        let header: GroupHeader = " Round {n}, {count} drawn ".parse().unwrap();
+        let format = NumberFormat { numbered: true, ..Default::default() };
+        assert_eq!(
+            ExportFormat::Text.render_groups(&groups, &header, &format),
+            "Round 1, 2 drawn\n#1: 4821\n#2: 17\n\nRound 2, 1 drawn\n#1: 9"
+        );
+        assert_eq!(
+            ExportFormat::Csv.render_groups(&groups, &header, &format),
+            "group,index,value\n\"Round 1, 2 drawn\",1,4821\n\"Round 1, 2 drawn\",2,17\n\"Round 2, 1 drawn\",1,9\n"
+        );
+        assert_eq!(
+            ExportFormat::Json.render_groups(&groups, &GroupHeader::default(), &format),
+            r#"[{"group": "Round 1", "numbers": [4821, 17]}, {"group": "Round 2", "numbers": [9]}]"#
+        );
+        assert_eq!(ExportFormat::Text.render_groups::<i64>(&[], &header, &format), "");
+
+        assert!(matches!("Round".parse::<GroupHeader>(), Err(RandomGeneratorError::InvalidGroupHeader(_))));
+        assert!("Round {n}\nnext".parse::<GroupHeader>().is_err());
+        assert_eq!(header.to_string(), "Round {n}, {count} drawn");
+    }
+
+    #[test]
+    fn test_read_saved_results() {
+        let numbers = [1_234_567i64, -42, 7];
+        let groups = [vec![4821i64, 17], vec![9]];
+        let header: GroupHeader = "Round {n}, {count} drawn".parse().unwrap();
+        let formats = [
+            NumberFormat::default(),
+            NumberFormat { separator: ThousandsSeparator::Comma, numbered: true, ..Default::default() },
+            NumberFormat { separator: ThousandsSeparator::Period, min_width: 8, ..Default::default() },
+            NumberFormat { separator: ThousandsSeparator::Apostrophe, ..Default::default() },
+        ];
+        // 保存后读回,数字不变
+        for format in ExportFormat::ALL {
+            for number_format in &formats {
+                assert_eq!(format.read_numbers::<i64>(&format.render(&numbers, number_format)).unwrap(), numbers, "{} {:?}", format, number_format);
+                let grouped = format.render_groups(&groups, &header, number_format);
+                assert_eq!(format.read_numbers::<i64>(&grouped).unwrap(), [4821, 17, 9], "{} {:?}", format, number_format);
+            }
+        }
+        assert_eq!(ExportFormat::Text.read_numbers::<f64>("0.5\n1.5").unwrap(), [0.5, 1.5]);
+        assert_eq!(ExportFormat::Text.read_numbers::<i64>("1.234.567\n-4.821").unwrap(), [1_234_567, -4821]);
+        // 小数不是千位分隔,整数读不出来就报错
+        for decimal in ["2.5", "1.2345", ".500", "1..000"] {
+            assert!(
+                matches!(ExportFormat::Text.read_numbers::<i64>(&format!("1\n{}", decimal)), Err(RandomGeneratorError::InvalidResultsFile { line: 2, .. })),
+                "{}",
+                decimal
+            );
+        }
+        assert!(ExportFormat::Csv.read_numbers::<i64>("").unwrap().is_empty());
+
+        // 显示为名字的值读不回来,并指出是第几行
+        let labels = NumberFormat { labels: LabelMap::parse("7: lucky").unwrap(), ..Default::default() };
+        let text = ExportFormat::Text.render(&numbers, &labels);
+        assert!(matches!(ExportFormat::Text.read_numbers::<i64>(&text), Err(RandomGeneratorError::InvalidResultsFile { line: 3, .. })));
+        let csv = ExportFormat::Csv.render(&numbers, &labels);
+        assert!(matches!(ExportFormat::Csv.read_numbers::<i64>(&csv), Err(RandomGeneratorError::InvalidResultsFile { line: 4, .. })));
+        assert!(ExportFormat::Json.read_numbers::<i64>(&ExportFormat::Json.render(&numbers, &labels)).is_err());
+        assert!(ExportFormat::Csv.read_numbers::<i64>("a,b\n1,2").is_err());
+
+        assert_eq!(ExportFormat::from_path("draws/monday.CSV"), ExportFormat::Csv);
+        assert_eq!(ExportFormat::from_path("results.json"), ExportFormat::Json);
+        assert_eq!(ExportFormat::from_path("results"), ExportFormat::Text);
+
+        let files = vec![("a.txt".to_string(), vec![5i64, 2, 5]), ("b.csv".to_string(), vec![2, 9])];
+        let merged = MergedResults::new(files.clone(), false);
+        assert_eq!(merged.numbers, [5, 2, 5, 2, 9]);
+        assert_eq!(merged.duplicates_removed, 0);
+        let unique = MergedResults::new(files, true);
+        assert_eq!(unique.numbers, [5, 2, 9]);
+        assert_eq!(unique.stats().sum, Some(16));
+        assert_eq!(
+            unique.to_text(),
+            "a.txt: 3 numbers\nb.csv: 2 numbers\n\nMerged: 3 numbers (2 duplicates removed)\nMin: 2\nMax: 9\nSum: 16\nMean: 5.3333\n"
+        );
+        assert_eq!(MergedResults::<i64>::new(Vec::new(), true).to_text(), "\nMerged: 0 numbers\n");
+
+        // 总和超出 i64 时没有总和,平均值仍然算得出来
+        let files = vec![("a.txt".to_string(), vec![i64::MAX - 1]), ("b.txt".to_string(), vec![i64::MAX - 1])];
+        let stats = MergedResults::new(files.clone(), false).stats();
+        assert_eq!(stats.sum, None);
+        assert_eq!(stats.avg, i64::MAX as f64);
+        assert!(MergedResults::new(files, false).to_text().contains("\nSum: out of range\n"));
+        assert_eq!(Stats::of(&[i64::MAX, 1]).sum, None);
+        assert_eq!(Stats::of(&[i64::MIN, -1]).sum, None);
+        assert_eq!(Stats::of(&[i64::MAX, i64::MIN]).sum, Some(-1));
+    }
+
+    #[test]
+    fn test_labels() {
+        let labels = LabelMap::parse("# teams\n1: red team\n\n2..4 : blue team\n-1: none").unwrap();
+        assert_eq!(labels.len(), 3);
+        assert_eq!([0, 1, 3, -1].map(|value| labels.get(value)), [None, Some("red team"), Some("blue team"), Some("none")]);
+        assert_eq!(labels.get(1.5), None);
+        assert_eq!(labels.to_string(), "1: red team\n2..4: blue team\n-1: none\n");
+        assert_eq!(LabelMap::parse(&labels.to_string()).unwrap(), labels);
+
+        // 名字不补零、不分组
+        let format = NumberFormat { separator: ThousandsSeparator::Comma, min_width: 3, labels, ..Default::default() };
+        assert_eq!(format.format(2), "blue team");
+        assert_eq!(format.format(1000), "1,000");
+
+        for (text, line) in [("1 red", 1), ("1: a\nx: b", 2), ("5..3: a", 1), ("1:", 1), ("1..5: a\n\n3: b", 3)] {
+            match LabelMap::parse(text) {
+                Err(RandomGeneratorError::InvalidLabels { line: found, .. }) => assert_eq!(found, line, "{}", text),
+                other => panic!("{}: {:?}", text, other),
+            }
+        }
+        assert!(LabelMap::parse("1..5: a\n3: b").unwrap_err().to_string().contains("line 1"));
+    }
+
+    #[test]
+    fn test_export_formats() {
+        let mut random_gen = RandomGenerator::new();
+        random_gen.get_numbers_mut().extend([1234, -5]);
+        let format = NumberFormat { separator: ThousandsSeparator::Comma, min_width: 0, ..Default::default() };
+        let path = std::env::temp_dir().join(format!("random-tool-export-{}", std::process::id()));
+        let filename = path.to_str().unwrap();
+
+        random_gen.save_numbers_as(filename, ExportFormat::Csv, &format).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "index,value\n1,\"1,234\"\n2,-5\n");
+
+        random_gen.save_numbers_as(filename, ExportFormat::Json, &format).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "[1234, -5]");
+
+        let labeled = NumberFormat { labels: LabelMap::parse("1234: \"A\", the team").unwrap(), ..format };
+        random_gen.save_numbers_as(filename, ExportFormat::Csv, &labeled).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "index,value\n1,\"\"\"A\"\", the team\"\n2,-5\n");
+        random_gen.save_numbers_as(filename, ExportFormat::Json, &labeled).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "[\"\\\"A\\\", the team\", -5]");
+
+        // 序号只加在文本中
+        let numbered = NumberFormat { numbered: true, ..format };
+        random_gen.save_numbers_as(filename, ExportFormat::Text, &numbered).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "#1: 1,234\n#2: -5");
+        random_gen.save_numbers_as(filename, ExportFormat::Csv, &numbered).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "index,value\n1,\"1,234\"\n2,-5\n");
+        random_gen.save_numbers_as(filename, ExportFormat::Json, &numbered).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "[1234, -5]");
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_load_numbers() {
+        let mut random_gen = RandomGenerator::new();
+        random_gen.get_numbers_mut().extend([1234, -5, 1234]);
+        let format = NumberFormat { separator: ThousandsSeparator::Comma, numbered: true, ..Default::default() };
+        for export in ExportFormat::ALL {
+            let path = std::env::temp_dir().join(format!("random-tool-load-{}.{}", std::process::id(), export.extension()));
+            let filename = path.to_str().unwrap();
+            random_gen.save_numbers_as(filename, export, &format).unwrap();
+
+            // 按扩展名读回,替换原有的数字
+            let mut loaded = RandomGenerator::new();
+            loaded.get_numbers_mut().push(7);
+            loaded.load_numbers(filename).unwrap();
+            assert_eq!(loaded.get_numbers(), random_gen.get_numbers(), "{}", export);
+
+            fs::write(&path, "not numbers\nat all").unwrap();
+            assert!(loaded.load_numbers(filename).is_err());
+            assert_eq!(loaded.get_numbers(), random_gen.get_numbers());
+            fs::remove_file(&path).unwrap();
+        }
+        assert!(matches!(random_gen.load_numbers("/nonexistent/results.txt"), Err(RandomGeneratorError::IoError(_))));
+    }
+
+    #[test]
+    fn test_custom_list_generation() {
+        let mut random_gen = RandomGenerator::new();
+        random_gen.set_mode(GeneratorMode::CUSTOM_LIST).unwrap();
+        random_gen.set_custom_list_input("1,2,3,4,5".to_string()).unwrap();
+        random_gen.set_num_to_generate(3).unwrap();
+        random_gen.generate_numbers().unwrap();
+
+        assert_eq!(random_gen.get_numbers().len(), 3);
+
+        let numbers = random_gen.get_numbers();
+        for &num in numbers {
+            assert!((1..=5).contains(&num), "数字 {} 不在自定义列表中", num);
+        }
+    }
+
+    #[test]
+    fn test_draw_space() {
+        let random_gen = RandomGenerator::new();
+        let config = GeneratorConfig { lower_bound: 1, upper_bound: 10, num_to_generate: 3, ..Default::default() };
+        assert_eq!(random_gen.draw_space(&config), DrawSpace { values: Some(10), capacity: Some(10), collision_chance: None });
+
+        // 允许重复时 3 次都不同的概率是 1 × 0.9 × 0.8
+        let duplicates = GeneratorConfig { allow_duplicates: true, ..config.clone() };
+        let space = random_gen.draw_space(&duplicates);
+        assert_eq!(space.capacity, None);
+        assert!((space.collision_chance.unwrap() - 0.28).abs() < 1e-12);
+        let crowded = GeneratorConfig { num_to_generate: 11, ..duplicates.clone() };
+        assert_eq!(random_gen.draw_space(&crowded).collision_chance, Some(1.0));
+        let huge = GeneratorConfig { lower_bound: i64::MIN, upper_bound: i64::MAX, num_to_generate: 1000, ..duplicates.clone() };
+        assert!(random_gen.draw_space(&huge).collision_chance.unwrap() < 1e-13);
+
+        // 排除的数字不算在内
+        let mut excluded = ExclusionSet::new();
+        excluded.extend([2, 4, 6, 8]);
+        let space = random_gen.draw_space(&GeneratorConfig { exclude_drawn: Some(excluded), ..config.clone() });
+        assert_eq!((space.values, space.capacity), (Some(6), Some(6)));
+
+        // 按权重的列表:抽两次都是 7 的概率是 0.75²,都是 3 的是 0.25²
+        let list = GeneratorConfig {
+            mode: GeneratorMode::CUSTOM_LIST,
+            custom_list: vec![7, 7, 7, 3],
+            num_to_generate: 2,
+            allow_duplicates: true,
+            ..Default::default()
+        };
+        let space = random_gen.draw_space(&list);
+        assert_eq!(space.values, Some(2));
+        assert!((space.collision_chance.unwrap() - 0.625).abs() < 1e-12);
+        let dedupe = GeneratorConfig { list_duplicates: ListDuplicates::Dedupe, ..list };
+        assert!((random_gen.draw_space(&dedupe).collision_chance.unwrap() - 0.5).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_lenient_list() {
+        let mut random_gen = RandomGenerator::new();
+        random_gen.set_mode(GeneratorMode::CUSTOM_LIST).unwrap();
+        assert!(matches!(random_gen.set_custom_list_input("4, x, 8; 1.5\n15".to_string()), Err(RandomGeneratorError::InvalidInputFormat)));
+
+        // 宽松模式跳过不是数字的项,并能列出它们
+        random_gen.set_lenient_list(true).unwrap();
+        assert_eq!(random_gen.get_config().custom_list, [4, 8, 15]);
+        assert_eq!(random_gen.skipped_list_items(), ["x", "1.5"]);
+        random_gen.set_num_to_generate(3).unwrap();
+        random_gen.generate_numbers().unwrap();
+        let mut numbers = random_gen.get_numbers().to_vec();
+        numbers.sort();
+        assert_eq!(numbers, [4, 8, 15]);
+
+        // 只有不是数字的项时列表为空
+        assert!(matches!(random_gen.set_custom_list_input("x y".to_string()), Err(RandomGeneratorError::EmptyList)));
+        random_gen.set_custom_list_input("1 2 3".to_string()).unwrap();
+        assert!(random_gen.skipped_list_items().is_empty());
+
+        random_gen.set_custom_list_input("1 2 3 four".to_string()).unwrap();
+        assert!(random_gen.set_lenient_list(false).is_err());
+        assert!(!random_gen.get_config().lenient_list);
+    }
+
+    #[test]
+    fn test_fake_data() {
+        let seed = Seed::from([3; 32]);
+        let schema = FieldSchema::parse("name, email,postcode", FakeLocale::Chinese).unwrap();
+        let rows = schema.generate(20, seed).unwrap();
+        assert_eq!(rows.len(), 20);
+        for row in &rows {
+            assert_eq!(row.len(), 3);
+            assert!(row[1].ends_with("@example.com") && row[1].is_ascii());
+            assert_eq!(row[2].len(), 6);
+        }
+        // 增加一列不影响同一种子下其他列的内容
+        let wider = FieldSchema::new(vec![FakeField::FullName, FakeField::Phone], FakeLocale::Chinese);
+        let wider_rows = wider.generate(20, seed).unwrap();
+        assert!(rows.iter().zip(&wider_rows).all(|(row, wider_row)| row[0] == wider_row[0]));
+
+        let schema = FieldSchema::new(vec![FakeField::City, FakeField::Email], FakeLocale::English);
+        let rows = vec![vec!["Austin, TX".to_string(), "a\"b@example.com".to_string()]];
+        assert_eq!(schema.to_csv(&rows), "city,email\n\"Austin, TX\",\"a\"\"b@example.com\"\n");
+        assert_eq!(schema.to_json(&rows), "[\n  {\"city\": \"Austin, TX\", \"email\": \"a\\\"b@example.com\"}\n]\n");
+
+        assert!(FieldSchema::parse("name,age", FakeLocale::English).is_err());
+        assert!(FieldSchema::new(Vec::new(), FakeLocale::English).generate(1, seed).is_err());
+        assert_eq!("ZH".parse::<FakeLocale>().unwrap(), FakeLocale::Chinese);
+    }
+
+    #[cfg(feature = "schema")]
+    #[test]
+    fn test_json_schema() {
+        let seed = Seed::from([5; 32]);
+        let schema = JsonSchema::parse(
+            r#"{
+                "$schema": "https://json-schema.org/draft/2020-12/schema",
+                "type": "object",
+                "properties": {
+                    "id": {"type": "string", "format": "uuid"},
+                    "age": {"type": "integer", "minimum": 18, "exclusiveMaximum": 21.5},
+                    "price": {"type": "number", "minimum": 1, "maximum": 2, "multipleOf": 0.25},
+                    "status": {"enum": ["open", "closed"]},
+                    "born": {"type": "string", "format": "date"},
+                    "code": {"type": "string", "minLength": 3, "maxLength": 3},
+                    "tags": {"type": "array", "items": {"enum": ["a", "b"]}, "maxItems": 3, "uniqueItems": true},
+                    "note": {"type": ["string", "null"]}
+                }
+            }"#,
+        )
+        .unwrap();
+        for document in schema.generate(200, seed) {
+            assert_eq!(document["id"].as_str().unwrap().len(), 36);
+            assert_eq!(&document["id"].as_str().unwrap()[14..15], "4");
+            assert!((18..=21).contains(&document["age"].as_i64().unwrap()));
+            assert!([1.0, 1.25, 1.5, 1.75, 2.0].contains(&document["price"].as_f64().unwrap()));
+            assert!(["open", "closed"].contains(&document["status"].as_str().unwrap()));
+            let born = document["born"].as_str().unwrap();
+            assert!(born.len() == 10 && ("1970".."2038").contains(&&born[..4]));
+            assert_eq!(document["code"].as_str().unwrap().len(), 3);
+            let tags = document["tags"].as_array().unwrap();
+            assert!((1..=2).contains(&tags.len()) && (tags.len() < 2 || tags[0] != tags[1]));
+            assert!(document["note"].is_string() || document["note"].is_null());
+        }
+
+        let template = JsonSchema::parse(r#"{"user": {"name": "name", "email": "email"}, "scores": ["integer"], "version": 2}"#).unwrap();
+        let document = &template.generate(1, seed)[0];
+        let first_name = document["user"]["name"].as_str().unwrap().split(' ').next().unwrap().to_lowercase();
+        assert!(document["user"]["email"].as_str().unwrap().starts_with(&first_name));
+        assert!(!document["scores"].as_array().unwrap().is_empty());
+        assert_eq!(document["version"], 2);
+
+        for bad in [
+            "{",
+            r##"{"type": "object", "properties": {"a": {"$ref": "#/defs/a"}}}"##,
+            r#"{"type": "integer", "minimum": 5, "maximum": 4}"#,
+            r#"{"type": "integer", "minimum": 1, "maximum": 4, "multipleOf": 5}"#,
+            r#"{"type": "string", "format": "ipv7"}"#,
+            r#"{"name": "nickname"}"#,
+        ] {
+            assert!(matches!(JsonSchema::parse(bad), Err(RandomGeneratorError::InvalidSchema(_))), "{}", bad);
+        }
+    }
+
+    #[test]
+    fn test_string_pattern() {
+        let seed = Seed::from([9; 32]);
+        let pattern: StringPattern = "AA-####".parse().unwrap();
+        assert_eq!(pattern.combinations(), 26 * 26 * 10_000);
+        for code in pattern.generate(300, true, seed).unwrap() {
+            let bytes = code.as_bytes();
+            assert_eq!(bytes.len(), 7);
+            assert!(bytes[..2].iter().all(u8::is_ascii_uppercase) && bytes[2] == b'-');
+            assert!(bytes[3..].iter().all(u8::is_ascii_digit));
+        }
+
+        let pattern: StringPattern = r"(VIP|STD)\-[a-c]{2,3}\#".parse().unwrap();
+        let mut rng = rand::thread_rng();
+        for _ in 0..100 {
+            let code = pattern.sample(&mut rng);
+            assert!(code.starts_with("VIP-") || code.starts_with("STD-"), "{}", code);
+            assert!(code.ends_with('#') && (7..=8).contains(&code.len()), "{}", code);
+        }
+
+        // 只有 4 种可能时,不重复地要 4 个一定全部拿到
+        let pattern: StringPattern = "[xy]{2}".parse().unwrap();
+        let mut all = pattern.generate(4, true, seed).unwrap();
+        all.sort();
+        assert_eq!(all, ["xx", "xy", "yx", "yy"]);
+        assert!(pattern.generate(5, true, seed).is_err());
+        assert_eq!(pattern.generate(5, false, seed).unwrap().len(), 5);
+
+        for bad in ["", "A{3", "A{5,2}", "[^0]", "[z-a]", "(AB", "AB)", "#{2000}", "\\"] {
+            assert!(matches!(bad.parse::<StringPattern>(), Err(RandomGeneratorError::InvalidPattern(_))), "{}", bad);
+        }
+    }
+
+    #[test]
+    fn test_coupon_codes() {
+        let seed = Seed::from([6; 32]);
+        let spec = CodeSpec { length: 8, check: CheckCharacter::Luhn, group: 3, ..Default::default() };
+        let codes = spec.generate(500, seed, &HashSet::new()).unwrap();
+        assert_eq!(codes.iter().map(|code| CodeSpec::normalize(code)).collect::<HashSet<_>>().len(), 500);
+        for code in &codes {
+            assert_eq!(code.matches('-').count(), 2);
+            assert!(!code.contains(['0', 'O', '1', 'I']), "{}", code);
+            assert!(spec.is_valid(code) && spec.is_valid(&code.to_lowercase()));
+        }
+
+        // 改掉任一字符,或对调相邻的不同字符,校验都会失败
+        let code = CodeSpec::normalize(&codes[0]);
+        let alphabet: Vec<char> = CODE_ALPHABET.chars().collect();
+        let mut chars: Vec<char> = code.chars().collect();
+        for i in 0..chars.len() {
+            let original = chars[i];
+            for &c in alphabet.iter().filter(|&&c| c != original) {
+                chars[i] = c;
+                assert!(!spec.is_valid(&chars.iter().collect::<String>()));
+            }
+            chars[i] = original;
+        }
+        assert!(!spec.is_valid(&code[1..]));
+
+        let crc = CodeSpec { check: CheckCharacter::Crc, ..spec.clone() };
+        let codes = crc.generate(50, seed, &HashSet::new()).unwrap();
+        assert!(codes.iter().all(|code| crc.is_valid(code)));
+
+        // 只有 4 种可能,排除 3 种后只剩 1 种
+        let tiny = CodeSpec { length: 2, alphabet: "xy".to_string(), ..Default::default() };
+        let exclude = CodeSpec::collect_codes("[\"xx\", \"x-y\",\n\"YX\"]");
+        assert_eq!(exclude.len(), 3);
+        assert_eq!(tiny.generate(1, seed, &exclude).unwrap(), ["YY"]);
+        assert!(matches!(tiny.generate(2, seed, &exclude), Err(RandomGeneratorError::InvalidCode(_))));
+        for bad in ["", "A", "AB-C"] {
+            let spec = CodeSpec { alphabet: bad.to_string(), ..Default::default() };
+            assert!(matches!(spec.generate(1, seed, &HashSet::new()), Err(RandomGeneratorError::InvalidCode(_))));
+        }
+    }
+
+    #[test]
+    fn test_pins() {
+        let seed = Seed::from([8; 32]);
+        let spec = PinSpec::default();
+        let pins = spec.generate(2000, seed).unwrap();
+        assert_eq!(pins.iter().collect::<HashSet<_>>().len(), 2000);
+        assert!(pins.iter().all(|pin| pin.len() == 6 && spec.is_allowed(pin)));
+
+        for bad in ["000000", "125551", "204567", "309876", "551999", "320251", "12345", "12a456"] {
+            assert!(!spec.is_allowed(bad), "{}", bad);
+        }
+        for good in ["135792", "180054", "246802", "113355"] {
+            assert!(spec.is_allowed(good), "{}", good);
+        }
+        let lenient = PinSpec { length: 4, no_repeats: false, no_years: false, no_sequences: false };
+        assert!(lenient.is_allowed("1999") && lenient.is_allowed("1234") && lenient.is_allowed("0000"));
+
+        // 四位且不加规则时恰好有 10000 个
+        assert_eq!(lenient.generate(10_000, seed).unwrap().len(), 10_000);
+        assert!(lenient.generate(10_001, seed).is_err());
+        for length in [3, 13] {
+            assert!(matches!(PinSpec { length, ..spec }.generate(1, seed), Err(RandomGeneratorError::InvalidCode(_))));
+        }
+    }
+
+    #[test]
+    fn test_passphrases() {
+        let seed = Seed::from([2; 32]);
+        let bundled = Wordlist::bundled();
+        assert_eq!(bundled.len(), 6usize.pow(4));
+        assert_eq!(Wordlist::parse(&bundled.words().join("\n")).unwrap(), bundled);
+
+        let spec = PassphraseSpec { words: 4, separator: ".".to_string(), capitalization: Capitalization::Upper };
+        let phrases = spec.generate(&bundled, 50, seed).unwrap();
+        assert_eq!(phrases, spec.generate(&bundled, 50, seed).unwrap());
+        for phrase in &phrases {
+            let words: Vec<&str> = phrase.split('.').collect();
+            assert_eq!(words.len(), 4);
+            assert!(words.iter().all(|word| bundled.words().contains(&word.to_lowercase()) && *word == word.to_uppercase()));
+        }
+
+        // Diceware 格式的词表只取最后一列,重复的词只算一次
+        let dice = Wordlist::parse("11111\tApple\n11112\tpear\n\n11113 apple\n").unwrap();
+        assert_eq!(dice.len(), 2);
+        let spec = PassphraseSpec { words: 10, separator: String::new(), capitalization: Capitalization::Random };
+        assert!((spec.entropy_bits(&dice) - 20.0).abs() < 1e-9);
+        let phrase = &spec.generate(&dice, 1, seed).unwrap()[0];
+        assert!(phrase.contains(['A', 'P']) && phrase.contains(['a', 'p']), "{}", phrase);
+
+        assert!(matches!(Wordlist::parse(" \n"), Err(RandomGeneratorError::EmptyList)));
+        assert!(Wordlist::parse("same\nSAME").is_err());
+        assert!(PassphraseSpec { words: 0, ..Default::default() }.generate(&bundled, 1, seed).is_err());
+        assert_eq!("Title".parse::<Capitalization>().unwrap(), Capitalization::Title);
+    }
+
+    #[test]
+    fn test_random_bytes() {
+        for (text, bytes) in [("4096", 4096), ("512 KB", 512 << 10), ("1.5GB", 3 << 29), ("2 mib", 2 << 20), ("0", 0)] {
+            assert_eq!(text.parse::<ByteSize>().unwrap(), ByteSize(bytes), "{}", text);
+        }
+        for bad in ["", "MB", "ten MB", "5 TB", "1.2.3 KB", "-1 KB"] {
+            assert!(matches!(bad.parse::<ByteSize>(), Err(RandomGeneratorError::InvalidSize(_))), "{}", bad);
+        }
+        assert_eq!(ByteSize(10 << 20).to_string(), "10 MB");
+        assert_eq!(ByteSize(1536 << 10).to_string(), "1536 KB");
+        assert_eq!(ByteSize(1000).to_string(), "1000 B");
+
+        // 不是块大小整数倍的长度,同一个种子写出同样的内容
+        let bytes = RandomBytes { size: ByteSize(200_000), seed: Seed::from([7; 32]) };
+        let mut first = Vec::new();
+        let mut reports = Vec::new();
+        bytes.write_with_progress(&mut first, &AtomicBool::new(false), &mut |done, total| reports.push((done, total))).unwrap();
+        assert_eq!(first.len(), 200_000);
+        assert_eq!(reports.first(), Some(&(0, 200_000)));
+        assert_eq!(reports.last(), Some(&(200_000, 200_000)));
+        let mut second = Vec::new();
+        bytes.write_with_progress(&mut second, &AtomicBool::new(false), &mut |_, _| {}).unwrap();
+        assert_eq!(first, second);
+        // 每种字节值都应出现,约 781 次
+        let mut counts = [0usize; 256];
+        first.iter().for_each(|&byte| counts[byte as usize] += 1);
+        assert!(counts.iter().all(|&count| (500..1100).contains(&count)));
+
+        let mut cancelled = Vec::new();
+        let result = bytes.write_with_progress(&mut cancelled, &AtomicBool::new(true), &mut |_, _| {});
+        assert!(matches!(result, Err(RandomGeneratorError::Cancelled)));
+        assert!(cancelled.is_empty());
+    }
+
+    #[test]
+    fn test_network_addresses() {
+        let seed = Seed::from([4; 32]);
+        let random_mac = AddressSpec::parse(AddressKind::Mac, "").unwrap();
+        for mac in random_mac.generate(200, true, seed).unwrap() {
+            let first = u8::from_str_radix(&mac[..2], 16).unwrap();
+            assert_eq!(mac.len(), 17);
+            assert_eq!(first & 0b11, 0b10, "{}", mac);
+        }
+        let vendor = AddressSpec::parse(AddressKind::Mac, "00-1a-2b-3c").unwrap();
+        assert_eq!(vendor.combinations(), 65_536);
+        assert!(vendor.generate(50, true, seed).unwrap().iter().all(|mac| mac.starts_with("00:1a:2b:3c:")));
+
+        // /30 只有两个可用地址,/31 和 /32 没有网络地址和广播地址之分
+        let small = AddressSpec::parse(AddressKind::Ipv4, "10.9.8.7/30").unwrap();
+        let mut hosts = small.generate(2, true, seed).unwrap();
+        hosts.sort();
+        assert_eq!(hosts, ["10.9.8.5", "10.9.8.6"]);
+        assert!(small.generate(3, true, seed).is_err());
+        assert_eq!(small.generate(10, false, seed).unwrap().len(), 10);
+        assert_eq!(AddressSpec::parse(AddressKind::Ipv4, "10.0.0.1/31").unwrap().combinations(), 2);
+        assert_eq!(AddressSpec::parse(AddressKind::Ipv4, "10.0.0.1").unwrap().generate(1, true, seed).unwrap(), ["10.0.0.1"]);
+        assert_eq!(AddressSpec::parse(AddressKind::Ipv4, "").unwrap().combinations(), (1 << 32) - 2);
+
+        let v6 = AddressSpec::parse(AddressKind::Ipv6, "2001:db8::/32").unwrap();
+        for address in v6.generate(100, true, seed).unwrap() {
+            let address: std::net::Ipv6Addr = address.parse().unwrap();
+            assert_eq!(address.segments()[..2], [0x2001, 0x0db8]);
+        }
+        assert_eq!(AddressSpec::parse(AddressKind::Ipv6, "").unwrap().combinations(), u128::MAX);
+
+        for (kind, bad) in [
+            (AddressKind::Mac, "00:1A:2"),
+            (AddressKind::Mac, "00:11:22:33:44:55"),
+            (AddressKind::Mac, "zz"),
+            (AddressKind::Ipv4, "10.0.0.0/33"),
+            (AddressKind::Ipv4, "10.0.0/8"),
+            (AddressKind::Ipv4, "2001:db8::/32"),
+            (AddressKind::Ipv6, "2001:db8::/129"),
+        ] {
+            assert!(matches!(AddressSpec::parse(kind, bad), Err(RandomGeneratorError::InvalidAddress(_))), "{}", bad);
+        }
+    }
+
+    #[test]
+    fn test_primes() {
+        let small: Vec<u128> = (0..50).filter(|&n| is_prime(n)).collect();
+        assert_eq!(small, [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47]);
+        // Carmichael 数和强伪素数
+        for composite in [561u128, 3_215_031_751, 3_825_123_056_546_413_051, (1 << 64) + 1] {
+            assert!(!is_prime(composite), "{}", composite);
+        }
+        for prime in [u64::MAX as u128 - 58, (1 << 89) - 1, (1 << 127) - 1] {
+            assert!(is_prime(prime), "{}", prime);
+        }
+
+        // 小区间数出全部素数,不重复时可以抽完
+        let mut generator = RandomGenerator::with_config(GeneratorConfig {
+            mode: GeneratorMode::PRIMES,
+            lower_bound: -10,
+            upper_bound: 30,
+            num_to_generate: 10,
+            ..Default::default()
+        })
+        .unwrap();
+        generator.generate_numbers().unwrap();
+        let mut numbers = generator.get_numbers().to_vec();
+        numbers.sort();
+        assert_eq!(numbers, [2, 3, 5, 7, 11, 13, 17, 19, 23, 29]);
+        assert!(matches!(generator.set_num_to_generate(11), Err(RandomGeneratorError::TooManyNumbers)));
+        generator.set_allow_duplicates(true).unwrap();
+        generator.set_num_to_generate(100).unwrap();
+        generator.generate_numbers().unwrap();
+        assert!(generator.get_numbers().iter().all(|&n| is_prime(n as u128)));
+
+        let empty = GeneratorConfig { mode: GeneratorMode::PRIMES, lower_bound: 24, upper_bound: 28, ..Default::default() };
+        assert!(matches!(generator.validate(&empty)[..], [RandomGeneratorError::InvalidPrimes(_)]));
+        let float = Config::<f64> { mode: GeneratorMode::PRIMES, ..Default::default() };
+        assert!(matches!(Generator::<f64>::new().validate(&float)[..], [RandomGeneratorError::InvalidPrimes(_)]));
+
+        // 按位数抽取的大素数
+        let (lower, upper) = PrimeSource::bit_range(100).unwrap();
+        assert_eq!((lower, upper), (1 << 99, (1 << 100) - 1));
+        let mut large = Generator::<i128>::with_config(Config {
+            mode: GeneratorMode::PRIMES,
+            lower_bound: lower,
+            upper_bound: upper,
+            num_to_generate: 5,
+            seed: Some(Seed::from([9; 32])),
+            ..Default::default()
+        })
+        .unwrap();
+        large.generate_numbers().unwrap();
+        let primes = large.get_numbers().to_vec();
+        assert!(primes.iter().all(|&p| (lower..=upper).contains(&p) && is_prime(p as u128)));
+        assert_eq!(primes.iter().map(|p| p.key()).collect::<HashSet<_>>().len(), 5);
+        large.generate_numbers().unwrap();
+        assert_eq!(large.get_numbers(), primes);
+        assert!(PrimeSource::bit_range(1).is_err() && PrimeSource::bit_range(128).is_err());
+
+        // 大到不数的区间同样遵守重复次数的上限;2262 个素数中抽 500 个,不限时几乎必然重复
+        let mut wide = RandomGenerator::with_config(GeneratorConfig {
+            mode: GeneratorMode::PRIMES,
+            lower_bound: 1,
+            upper_bound: 20_000,
+            num_to_generate: 500,
+            allow_duplicates: true,
+            max_repeats: Some(1),
+            seed: Some(Seed::from([3; 32])),
+            ..Default::default()
+        })
+        .unwrap();
+        wide.generate_numbers().unwrap();
+        assert_eq!(wide.get_numbers().iter().collect::<HashSet<_>>().len(), 500);
+    }
+
+    #[test]
+    fn test_secret_santa() {
+        let names = "Alice\nBob\n\nCarol\nDave\nErin\nFrank\n";
+        let mut santa = SecretSanta::parse(names).unwrap();
+        santa.exclude_pairs("Alice, Bob\ncarol,DAVE\n").unwrap();
+        for n in 0..50 {
+            let seed = Seed::from([n; 32]);
+            let assignments = santa.draw(seed).unwrap();
+            assert_eq!(assignments.iter().map(|a| a.giver.as_str()).collect::<Vec<_>>(), santa.names());
+            let receivers: HashSet<&str> = assignments.iter().map(|a| a.receiver.as_str()).collect();
+            assert_eq!(receivers.len(), 6);
+            for a in &assignments {
+                assert_ne!(a.giver, a.receiver);
+                let pair = [a.giver.as_str(), a.receiver.as_str()];
+                assert!(!matches!(pair, ["Alice", "Bob"] | ["Bob", "Alice"] | ["Carol", "Dave"] | ["Dave", "Carol"]));
+            }
+            assert_eq!(santa.draw(seed).unwrap(), assignments);
+        }
+
+        // Alice 只能送给 Dave,随机排列几乎不可能碰上,由匹配算法找到
+        let mut tight = SecretSanta::parse("Alice\nBob\nCarol\nDave").unwrap();
+        tight.exclude_pairs("Alice, Bob\nAlice, Carol").unwrap();
+        let assignments = tight.draw(Seed::from([1; 32])).unwrap();
+        assert_eq!(assignments[0].receiver, "Dave");
+        assert_eq!(assignments[3].receiver, "Alice");
+
+        // 两个人互相排除后无法抽签
+        let mut couple = SecretSanta::parse("Alice\nBob").unwrap();
+        couple.exclude("alice", "bob").unwrap();
+        assert!(matches!(couple.draw(Seed::from([1; 32])), Err(RandomGeneratorError::InvalidDraw(_))));
+
+        for bad in ["Alice", "Alice\nalice", ""] {
+            assert!(matches!(SecretSanta::parse(bad), Err(RandomGeneratorError::InvalidDraw(_))), "{:?}", bad);
+        }
+        assert!(santa.exclude("Alice", "Zoe").is_err());
+        assert!(santa.exclude("Alice", "alice").is_err());
+        assert!(santa.exclude_pairs("Alice Bob").is_err());
+
+        let dir = std::env::temp_dir().join(format!("santa-test-{}", std::process::id()));
+        let assignments = SecretSanta::parse("Ann\nBo/b\nBo?b").unwrap().draw(Seed::from([2; 32])).unwrap();
+        let paths = SecretSanta::write_assignments(&assignments, &dir).unwrap();
+        let files: Vec<String> = paths.iter().map(|path| path.file_name().unwrap().to_string_lossy().into_owned()).collect();
+        assert_eq!(files, ["Ann.txt", "Bo_b.txt", "Bo_b (2).txt"]);
+        let note = fs::read_to_string(&paths[0]).unwrap();
+        assert_eq!(note, format!("Ann, you are giving a gift to: {}\n", assignments[0].receiver));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_bracket() {
+        let names: Vec<String> = (1..=6).map(|n| format!("P{}", n)).collect();
+        let bracket = Bracket::draw(names.clone(), Seed::from([8; 32])).unwrap();
+        assert_eq!((bracket.size(), bracket.rounds()), (8, 3));
+        assert_eq!(bracket, Bracket::draw(names.clone(), Seed::from([8; 32])).unwrap());
+
+        let matches = bracket.matches();
+        assert_eq!(matches.iter().map(|m| (m.round, m.number)).collect::<Vec<_>>(), [(1, 1), (1, 2), (1, 3), (1, 4), (2, 5), (2, 6), (3, 7)]);
+        let first: Vec<&str> = matches[..4].iter().flat_map(|m| [m.top.as_str(), m.bottom.as_str()]).collect();
+        let mut entrants: Vec<&str> = first.iter().copied().filter(|&name| name != "(bye)").collect();
+        entrants.sort();
+        assert_eq!(entrants, names);
+        // 两个轮空分在两个半区,不会互相比赛,对手直接进入第二轮
+        let byes: Vec<&BracketMatch> = matches[..4].iter().filter(|m| m.bottom == "(bye)").collect();
+        assert_eq!(byes.len(), 2);
+        assert_ne!(byes[0].number.div_ceil(2), byes[1].number.div_ceil(2));
+        for bye in byes {
+            let next = &matches[4 + (bye.number - 1) / 2];
+            assert!(next.top == bye.top || next.bottom == bye.top);
+        }
+        assert_eq!((matches[6].top.as_str(), matches[6].bottom.as_str()), ("Winner of 5", "Winner of 6"));
+
+        let text = bracket.to_text();
+        assert_eq!(text.lines().count(), 15);
+        assert!(names.iter().all(|name| text.contains(name.as_str())));
+        assert_eq!(text.matches('├').count(), 7);
+        let csv = bracket.to_csv();
+        assert_eq!(csv.lines().next(), Some("round,match,top,bottom"));
+        assert_eq!(csv.lines().count(), 8);
+
+        let pair = Bracket::draw(vec!["A".to_string(), "B".to_string()], Seed::from([1; 32])).unwrap();
+        assert_eq!(pair.to_text().lines().collect::<Vec<_>>().len(), 3);
+        assert!(matches!(Bracket::draw(vec!["A".to_string(), " ".to_string()], Seed::from([1; 32])), Err(RandomGeneratorError::InvalidDraw(_))));
+    }
+
+    #[test]
+    fn test_pairing() {
+        let people = "Ann\nBo\n\nCy\n";
+        let items = (1..=8).map(|n| format!("Paper {}", n)).collect::<Vec<_>>().join("\n");
+        let mut pairing = Pairing::parse(people, &items).unwrap();
+        pairing.forbid_pairs("Ann, Paper 1\nbo, paper 2\n").unwrap();
+        for n in 0..50 {
+            let seed = Seed::from([n; 32]);
+            let pairs = pairing.draw(seed).unwrap();
+            assert_eq!(pairs.len(), 8);
+            let items: HashSet<&str> = pairs.iter().map(|pair| pair.item.as_str()).collect();
+            assert_eq!(items.len(), 8);
+            // 8 项分给 3 人,每人 2 或 3 项
+            for person in pairing.people() {
+                let load = pairs.iter().filter(|pair| &pair.person == person).count();
+                assert!((2..=3).contains(&load), "{} got {}", person, load);
+            }
+            assert!(!pairs.iter().any(|pair| (pair.person.as_str(), pair.item.as_str()) == ("Ann", "Paper 1")));
+            assert!(!pairs.iter().any(|pair| (pair.person.as_str(), pair.item.as_str()) == ("Bo", "Paper 2")));
+            assert_eq!(pairing.draw(seed).unwrap(), pairs);
+        }
+
+        // 一对一,Ann 只能分到 Z,随机分配几乎不可能碰上,由匹配算法找到
+        let mut tight = Pairing::parse("Ann\nBo\nCy\nDi\nEd\nFay\nGus", "T\nU\nV\nW\nX\nY\nZ").unwrap();
+        tight.forbid_pairs("Ann, T\nAnn, U\nAnn, V\nAnn, W\nAnn, X\nAnn, Y\nBo, Z").unwrap();
+        let pairs = tight.draw(Seed::from([3; 32])).unwrap();
+        assert_eq!(pairs[0], Pair { person: "Ann".to_string(), item: "Z".to_string() });
+        assert_eq!(tight.to_text(&pairs).lines().count(), 7);
+
+        // 项比人少时有人分不到
+        let pairs = Pairing::parse("Ann\nBo\nCy", "X").unwrap().draw(Seed::from([4; 32])).unwrap();
+        assert_eq!(pairs.len(), 1);
+
+        // 两个人都不能分到 X,而 X 必须分出去
+        let mut blocked = Pairing::parse("Ann\nBo", "X\nY").unwrap();
+        blocked.forbid_pairs("Ann, X\nBo, X").unwrap();
+        assert!(matches!(blocked.draw(Seed::from([1; 32])), Err(RandomGeneratorError::InvalidDraw(_))));
+
+        assert!(Pairing::parse("", "X").is_err());
+        assert!(Pairing::parse("Ann\nann", "X").is_err());
+        assert!(pairing.forbid("Zoe", "Paper 1").is_err());
+        assert!(pairing.forbid("Ann", "Paper 9").is_err());
+        let pairs = [Pair { person: "Ann".to_string(), item: "A, \"B\"".to_string() }];
+        assert_eq!(Pairing::to_csv(&pairs), "person,item\nAnn,\"A, \"\"B\"\"\"\n");
+    }
+
+    #[test]
+    fn test_keno() {
+        let spec = KenoSpec { pool: 20, picks: 5 };
+        let tickets = spec.tickets(200, Seed::from([9; 32])).unwrap();
+        assert_eq!(tickets, spec.tickets(200, Seed::from([9; 32])).unwrap());
+        for (i, ticket) in tickets.iter().enumerate() {
+            assert!(ticket.numbers.windows(2).all(|w| w[0] < w[1]));
+            assert!(ticket.numbers.iter().all(|n| (1..=20).contains(n)));
+            if let Some(first) = ticket.duplicate_of {
+                assert!(first < i);
+                assert_eq!(tickets[first].numbers, ticket.numbers);
+                assert_eq!(tickets[first].duplicate_of, None);
+            }
+        }
+
+        // 只有 3 种不同的票,必然有重复
+        let small = KenoSpec { pool: 3, picks: 2 };
+        let tickets = small.tickets(10, Seed::from([1; 32])).unwrap();
+        assert_eq!(tickets.iter().filter(|t| t.duplicate_of.is_none()).count(), 3);
+        let text = small.to_text(&tickets[..1]);
+        assert_eq!(text.lines().count(), 1);
+        let csv = small.to_csv(&tickets);
+        assert!(csv.starts_with("ticket,n1,n2,same_as\n1,"));
+        // 表头和 7 张重复的票不以逗号结尾
+        assert_eq!(csv.lines().filter(|line| !line.ends_with(',')).count(), 8);
+
+        assert_eq!(KenoSpec { pool: 80, picks: 10 }.to_text(&[KenoTicket { numbers: vec![3, 45], duplicate_of: Some(0) }]), "1. 03 45  (same as ticket 1)\n");
+        for bad in [KenoSpec { pool: 0, picks: 1 }, KenoSpec { pool: 5, picks: 0 }, KenoSpec { pool: 5, picks: 6 }] {
+            assert!(matches!(bad.tickets(1, Seed::from([1; 32])), Err(RandomGeneratorError::InvalidGame(_))), "{:?}", bad);
+        }
+    }
+
+    #[test]
+    fn test_gacha() {
+        let table = LootTable::parse("Common, 80%\nRare, 15\n\nLegendary, 5%\n").unwrap();
+        assert_eq!(table.tiers()[1], RarityTier { name: "Rare".to_string(), weight: 15.0, pity: None });
+        let report = table.simulate(100_000, Seed::from([2; 32])).unwrap();
+        assert_eq!(report, table.simulate(100_000, Seed::from([2; 32])).unwrap());
+        for tier in &report.tiers {
+            let share = tier.count as f64 / 100_000.0;
+            assert!((share - tier.rate).abs() < 0.01, "{}: {} vs {}", tier.name, share, tier.rate);
+            assert_eq!(tier.pity_hits, 0);
+        }
+
+        // 1% 的掉落率,保底 10 次:每 10 次至少掉落一次,几乎都靠保底
+        let pity = LootTable::parse("Common, 99\nLegendary, 1, 10").unwrap();
+        let report = pity.simulate(1000, Seed::from([5; 32])).unwrap();
+        let legendary = &report.tiers[1];
+        assert!(legendary.longest_gap <= 9);
+        assert!(legendary.count >= 100 && legendary.pity_hits > 0 && legendary.pity_hits <= legendary.count);
+
+        // 同时触发时掉落率低的一档优先
+        let both = LootTable::parse("Common, 98\nRare, 1.5, 2\nLegendary, 0.5, 2").unwrap();
+        let report = both.simulate(2, Seed::from([1; 32])).unwrap();
+        assert_eq!(report.tiers[2].pity_hits, 1);
+
+        let text = report.to_text();
+        assert!(text.starts_with("Tier") && text.lines().count() == 4);
+        assert!(report.to_csv().starts_with("tier,rate,count,share,pity_hits,longest_gap\nCommon,0.98,"));
+
+        for bad in ["", "Common", "Common, x", "Common, 0", "Common, 5, 0", "Common, 5\ncommon, 5", "A, 1, 2, 3"] {
+            assert!(matches!(LootTable::parse(bad), Err(RandomGeneratorError::InvalidGame(_))), "{:?}", bad);
+        }
+    }
+
+    #[test]
+    fn test_dice_pool() {
+        let pool: DicePool = "8d10>=8!".parse().unwrap();
+        assert_eq!(pool.to_string(), "8d10>=8!");
+        assert_eq!("d6>=5!5".parse::<DicePool>().unwrap(), DicePool { dice: 1, sides: 6, target: 5, explode: Some(5) });
+        assert_eq!("3d6>=4".parse::<DicePool>().unwrap().to_string(), "3d6>=4");
+
+        let rolls = pool.rolls(2000, Seed::from([4; 32])).unwrap();
+        assert_eq!(rolls, pool.rolls(2000, Seed::from([4; 32])).unwrap());
+        let mut bonus = 0;
+        for roll in &rolls {
+            assert_eq!(roll.dice.iter().filter(|die| !die.bonus).count(), 8);
+            assert!(!roll.dice[0].bonus);
+            // 追加的骰子前面一颗一定爆骰了
+            for pair in roll.dice.windows(2) {
+                if pair[1].bonus {
+                    assert_eq!(pair[0].value, 10);
+                    bonus += 1;
+                }
+            }
+            assert_eq!(roll.successes, roll.dice.iter().filter(|die| die.value >= 8).count());
+        }
+        // 平均每次约 8 × 0.1 / 0.9 ≈ 0.89 颗追加的骰子
+        assert!((1400..2200).contains(&bonus), "{}", bonus);
+
+        // 追加的骰子总数有上限
+        let wild = DicePool { dice: 999, sides: 2, target: 2, explode: Some(2) };
+        assert!(wild.rolls(5, Seed::from([1; 32])).unwrap().iter().all(|roll| roll.dice.len() <= 1000));
+
+        let die = |value, bonus| PoolDie { value, bonus };
+        let roll = PoolRoll { dice: vec![die(10, false), die(3, true), die(8, false)], successes: 2 };
+        assert_eq!(pool.to_text(std::slice::from_ref(&roll)), "1. 10 +3 8  → 2 successes\n");
+        assert_eq!(pool.to_csv(&[roll]), "roll,dice,successes\n1,10 +3 8,2\n");
+
+        for bad in ["6d10", "6x10>=8", "0d10>=8", "6d1>=1", "6d10>=11", "6d10>=8!1", "6d10>=8!11", "6d10>=8!x"] {
+            assert!(matches!(bad.parse::<DicePool>(), Err(RandomGeneratorError::InvalidGame(_))), "{:?}", bad);
+        }
+    }
+
+    #[test]
+    fn test_line_sample() {
+        let text: String = (1..=10_000).map(|n| format!("line {}\r\n", n)).collect();
+        let sample = LineSample { count: 50, seed: Seed::from([3; 32]) };
+        let mut reports = Vec::new();
+        let first = sample.sample_with_progress(&mut text.as_bytes(), &AtomicBool::new(false), &mut |read| reports.push(read)).unwrap();
+        assert_eq!(first, sample.sample(&mut text.as_bytes()).unwrap());
+        assert_eq!((first.lines.len(), first.total), (50, 10_000));
+        assert_eq!(reports.last(), Some(&(text.len() as u64)));
+        let distinct: HashSet<&String> = first.lines.iter().collect();
+        assert_eq!(distinct.len(), 50);
+        assert!(first.lines.iter().all(|line| line.starts_with("line ") && !line.ends_with('\r')));
+
+        // 每行被抽中的机会相同:抽 3 行重复 6000 次,10 行中每行约 1800 次
+        let short = "a\nb\nc\nd\ne\nf\ng\nh\ni\nj";
+        let mut counts = HashMap::new();
+        for round in 0..6000u32 {
+            let mut seed = [0; 32];
+            seed[..4].copy_from_slice(&round.to_le_bytes());
+            let sample = LineSample { count: 3, seed: Seed::from(seed) }.sample(&mut short.as_bytes()).unwrap();
+            for line in sample.lines {
+                *counts.entry(line).or_insert(0) += 1;
+            }
+        }
+        assert_eq!(counts.len(), 10);
+        assert!(counts.values().all(|&count| (1650..1950).contains(&count)), "{:?}", counts);
+
+        // 行数不够时全部返回,空白行不算
+        let few = LineSample { count: 5, seed: Seed::from([3; 32]) }.sample(&mut "x\n\n  \ny".as_bytes()).unwrap();
+        assert_eq!(few.total, 2);
+        assert_eq!(few.lines.iter().collect::<HashSet<_>>(), HashSet::from([&"x".to_string(), &"y".to_string()]));
+        assert!(matches!(LineSample { count: 0, seed: Seed::from([3; 32]) }.sample(&mut "x".as_bytes()), Err(RandomGeneratorError::InvalidLineSample(_))));
+        let big = "x\n".repeat(1 << 20);
+        assert!(matches!(sample.sample_with_progress(&mut big.as_bytes(), &AtomicBool::new(true), &mut |_| {}), Err(RandomGeneratorError::Cancelled)));
+    }
+
+    #[test]
+    fn test_random_graph() {
+        // 10 个节点共 45 条可能的边,全选时就是完全图
+        let full = RandomGraph { nodes: 10, edges: 45, ..Default::default() }.generate(Seed::from([1; 32])).unwrap();
+        assert_eq!(full.edges.iter().collect::<HashSet<_>>().len(), 45);
+        assert!(full.degrees().iter().all(|&degree| degree == 9));
+
+        let spec = RandomGraph { nodes: 1000, edges: 5000, ..Default::default() };
+        let graph = spec.generate(Seed::from([2; 32])).unwrap();
+        assert_eq!(graph, spec.generate(Seed::from([2; 32])).unwrap());
+        assert_eq!(graph.edges.iter().collect::<HashSet<_>>().len(), 5000);
+        assert!(graph.edges.iter().all(|&(a, b)| a < b && b < 1000));
+
+        // 优先连接:完全图 K4 之后每个节点连 3 条边,早的节点度数远大于晚的
+        let spec = RandomGraph { model: GraphModel::BarabasiAlbert, nodes: 2000, edges: 3 };
+        let graph = spec.generate(Seed::from([3; 32])).unwrap();
+        assert_eq!(Some(graph.edges.len()), spec.edge_count());
+        assert_eq!(graph.edges.len(), 6 + 1996 * 3);
+        assert_eq!(graph.edges.iter().collect::<HashSet<_>>().len(), graph.edges.len());
+        let degrees = graph.degrees();
+        assert!(degrees.iter().all(|&degree| degree >= 3));
+        assert!(degrees[..10].iter().sum::<usize>() > 10 * degrees[1990..].iter().sum::<usize>(), "{:?}", &degrees[..10]);
+
+        let mut reported = 0;
+        assert_eq!(spec.generate_with_progress(Seed::from([3; 32]), &AtomicBool::new(false), &mut |_, _| reported += 1).unwrap(), graph);
+        assert_eq!(reported, 0);
+        let spec = RandomGraph { model: GraphModel::BarabasiAlbert, nodes: 100_000, edges: 2 };
+        let mut reported = Vec::new();
+        spec.generate_with_progress(Seed::from([3; 32]), &AtomicBool::new(false), &mut |done, total| reported.push((done, total))).unwrap();
+        assert_eq!(reported.len(), 3);
+        assert!(reported.iter().all(|&(done, total)| done >= PROGRESS_INTERVAL && total == spec.edge_count().unwrap()));
+        assert!(matches!(spec.generate_with_progress(Seed::from([3; 32]), &AtomicBool::new(true), &mut |_, _| {}), Err(RandomGeneratorError::Cancelled)));
+        let spec = RandomGraph { nodes: 10_000, edges: 200_000, ..Default::default() };
+        assert!(matches!(spec.generate_with_progress(Seed::from([3; 32]), &AtomicBool::new(true), &mut |_, _| {}), Err(RandomGeneratorError::Cancelled)));
+
+        let small = Graph { nodes: 3, edges: vec![(0, 2)] };
+        assert_eq!(small.to_edge_list(), "0 2\n");
+        assert_eq!(small.to_dot(), "graph G {\n  0;\n  1;\n  2;\n  0 -- 2;\n}\n");
+        assert_eq!("BA".parse::<GraphModel>().unwrap(), GraphModel::BarabasiAlbert);
+        assert_eq!("Erdős–Rényi".parse::<GraphModel>().unwrap(), GraphModel::ErdosRenyi);
+        assert!(matches!("lattice".parse::<GraphModel>(), Err(RandomGeneratorError::InvalidGraph(_))));
+        for bad in [
+            RandomGraph { nodes: 0, edges: 0, ..Default::default() },
+            RandomGraph { nodes: 4, edges: 7, ..Default::default() },
+            RandomGraph { model: GraphModel::BarabasiAlbert, nodes: 4, edges: 4 },
+            RandomGraph { model: GraphModel::BarabasiAlbert, nodes: 4, edges: 0 },
+            RandomGraph { nodes: 10_000, edges: 2_000_000, ..Default::default() },
+        ] {
+            assert!(matches!(bad.generate(Seed::from([1; 32])), Err(RandomGeneratorError::InvalidGraph(_))), "{:?}", bad);
+        }
+    }
+
+    #[test]
+    fn test_experiment_design() {
+        let design = ExperimentDesign::parse("Placebo\nLow, High\n\nMax").unwrap();
+        assert_eq!(design.treatments(), ["Placebo", "Low", "High", "Max"]);
+        let square = design.generate(DesignKind::LatinSquare, 0, Seed::from([1; 32])).unwrap();
+        assert_eq!(square, design.generate(DesignKind::LatinSquare, 0, Seed::from([1; 32])).unwrap());
+        for column in 0..4 {
+            let treatments: HashSet<&String> = square.rows.iter().map(|row| &row[column]).collect();
+            assert_eq!(treatments.len(), 4);
+        }
+
+        // 平衡拉丁方中每个处理紧跟在其他每个处理之后的次数相同:偶数时 1 次,奇数时 2N 行中 2 次
+        for (order, times) in [(4, 1), (6, 1), (5, 2)] {
+            let design = ExperimentDesign::of_order(order).unwrap();
+            let table = design.generate(DesignKind::Balanced, 0, Seed::from([2; 32])).unwrap();
+            assert_eq!(table.rows.len(), design.row_count(DesignKind::Balanced, 0));
+            let mut follows = HashMap::new();
+            for row in &table.rows {
+                assert_eq!(row.iter().collect::<HashSet<_>>().len(), order);
+                for pair in row.windows(2) {
+                    *follows.entry((&pair[0], &pair[1])).or_insert(0) += 1;
+                }
+            }
+            assert_eq!(follows.len(), order * (order - 1));
+            assert!(follows.values().all(|&count| count == times), "{} {:?}", order, follows);
+        }
+
+        let blocks = ExperimentDesign::parse("3").unwrap().generate(DesignKind::Blocks, 2, Seed::from([3; 32])).unwrap();
+        assert_eq!(blocks.rows.len(), 2);
+        let fixed = DesignTable { kind: DesignKind::Blocks, rows: vec![vec!["A".to_string(), "Long, name".to_string()]] };
+        assert_eq!(fixed.to_csv(), "block,1,2\n1,A,\"Long, name\"\n");
+        assert_eq!(fixed.to_text(), "Block 1  A           Long, name\n");
+        assert_eq!(ExperimentDesign::of_order(28).unwrap().treatments()[26..], ["AA", "AB"]);
+        assert_eq!("williams".parse::<DesignKind>().unwrap(), DesignKind::Balanced);
+
+        for bad in ["1", "A, B, a", "Only", "1001"] {
+            assert!(matches!(ExperimentDesign::parse(bad), Err(RandomGeneratorError::InvalidDesign(_))), "{}", bad);
+        }
+        assert!(matches!(design.generate(DesignKind::Blocks, 0, Seed::from([1; 32])), Err(RandomGeneratorError::InvalidDesign(_))));
+        assert!(matches!(design.generate(DesignKind::Blocks, 300_000, Seed::from([1; 32])), Err(RandomGeneratorError::InvalidDesign(_))));
+
+        let design = ExperimentDesign::of_order(100).unwrap();
+        let mut reported = Vec::new();
+        let table = design
+            .generate_with_progress(DesignKind::Blocks, 5000, Seed::from([4; 32]), &AtomicBool::new(false), &mut |done, total| reported.push((done, total)))
+            .unwrap();
+        assert_eq!(table, design.generate(DesignKind::Blocks, 5000, Seed::from([4; 32])).unwrap());
+        assert!(reported.windows(2).all(|pair| pair[0].0 < pair[1].0) && reported.iter().all(|&(_, total)| total == 1_000_000), "{:?}", reported);
+        assert!(reported.contains(&(500_000, 1_000_000)));
+        assert!(matches!(
+            design.generate_with_progress(DesignKind::LatinSquare, 0, Seed::from([4; 32]), &AtomicBool::new(true), &mut |_, _| {}),
+            Err(RandomGeneratorError::Cancelled)
+        ));
+    }
+
+    #[test]
+    fn test_truncated_distribution() {
+        let normal = Distribution::Normal { mean: 100.0, sd: 15.0 };
+        assert!((normal.cdf(130.0) - 0.977_249_868_051_821).abs() < 1e-12);
+        assert!((normal.sf(190.0) - 9.865_876_450_376_946e-10).abs() < 1e-20);
+        for p in [1e-300, 1e-12, 0.01, 0.3, 0.5, 0.9, 0.999_999] {
+            assert!((normal.cdf(normal.quantile(p)) / p - 1.0).abs() < 1e-9, "{}", p);
+            assert!((normal.sf(normal.upper_quantile(p)) / p - 1.0).abs() < 1e-9, "{}", p);
+        }
+
+        // 截断到 ±1 个标准差:平均数不变,标准差约为 0.5379 × 15
+        let iq = TruncatedDistribution::new(normal, Some(85.0), Some(115.0)).unwrap();
+        let report = iq.sample(200_000, Seed::from([1; 32])).unwrap();
+        assert_eq!(report, iq.sample(200_000, Seed::from([1; 32])).unwrap());
+        let mut reported = Vec::new();
+        assert_eq!(iq.sample_with_progress(200_000, Seed::from([1; 32]), &AtomicBool::new(false), &mut |done, total| reported.push((done, total))).unwrap(), report);
+        assert_eq!(reported, [(65_536, 200_000), (131_072, 200_000), (196_608, 200_000)]);
+        assert!(matches!(iq.sample_with_progress(200_000, Seed::from([1; 32]), &AtomicBool::new(true), &mut |_, _| {}), Err(RandomGeneratorError::Cancelled)));
+        assert!(report.values.iter().all(|value| (85.0..=115.0).contains(value)));
+        assert!((report.mean() - 100.0).abs() < 0.05 && (report.standard_deviation() - 8.068).abs() < 0.05, "{} {}", report.mean(), report.standard_deviation());
+
+        // 上尾 3 个标准差以外:截断正态的平均数是 3 + φ(3) / (1 - Φ(3)) ≈ 3.2831
+        let tail = TruncatedDistribution::new(Distribution::default(), Some(3.0), None).unwrap();
+        assert!((tail.mass() - 0.001_349_898).abs() < 1e-9);
+        let report = tail.sample(100_000, Seed::from([2; 32])).unwrap();
+        assert!(report.values.iter().all(|&value| value >= 3.0));
+        assert!((report.mean() - 3.2831).abs() < 0.01, "{}", report.mean());
+        // 下尾同样
+        let low = TruncatedDistribution::new(Distribution::default(), None, Some(-3.0)).unwrap().sample(100_000, Seed::from([3; 32])).unwrap();
+        assert!((low.mean() + 3.2831).abs() < 0.01, "{}", low.mean());
+
+        let uniform: Distribution = " Uniform(0, 10) ".parse().unwrap();
+        assert_eq!(uniform, Distribution::Uniform { lower: 0.0, upper: 10.0 });
+        let report = TruncatedDistribution::new(uniform, Some(9.0), None).unwrap().sample(1000, Seed::from([4; 32])).unwrap();
+        assert!(report.values.iter().all(|value| (9.0..10.0).contains(value)));
+        assert_eq!(report.distribution.to_string(), "uniform(0, 10) in 9..=");
+        assert_eq!("normal(100,15)".parse::<Distribution>().unwrap().to_string(), "normal(100, 15)");
+        assert!(report.to_text().contains("Values: 1000"));
+        assert!(report.to_csv().starts_with("index,value\n1,9."));
+
+        for bad in ["normal(0)", "normal(0, 0)", "cauchy(0, 1)", "uniform(2, 1)", "normal 0, 1", "normal(0, x)"] {
+            assert!(matches!(bad.parse::<Distribution>(), Err(RandomGeneratorError::InvalidDistribution(_))), "{}", bad);
+        }
+        for (lower, upper) in [(Some(2.0), Some(1.0)), (Some(50.0), None), (Some(f64::NAN), None)] {
+            assert!(matches!(TruncatedDistribution::new(Distribution::default(), lower, upper), Err(RandomGeneratorError::InvalidDistribution(_))));
+        }
+        assert!(TruncatedDistribution::new(uniform, Some(20.0), Some(30.0)).is_err());
+        assert!(Distribution::new(DistributionKind::Normal, &[1.0]).is_err());
+    }
+
+    #[test]
+    fn test_beta_gamma_distributions() {
+        // 整数参数时有封闭形式:Gamma(2, 1) 的 cdf(1) = 1 - 2/e,Gamma(3, 2) 的 sf(20) = 61 e^-10,
+        // Beta(2, 5) 的 cdf(0.3) = 1 - 0.7^6 - 6 × 0.3 × 0.7^5
+        let gamma = Distribution::new(DistributionKind::Gamma, &[2.0, 1.0]).unwrap();
+        assert!((gamma.cdf(1.0) - 0.264_241_117_657_115_3).abs() < 1e-12);
+        assert!((Distribution::Gamma { shape: 3.0, scale: 2.0 }.sf(20.0) - 0.002_769_395_715_511_576).abs() < 1e-14);
+        let beta: Distribution = "beta(2, 5)".parse().unwrap();
+        assert_eq!(beta, Distribution::Beta { alpha: 2.0, beta: 5.0 });
+        assert!((beta.cdf(0.3) - 0.579_825).abs() < 1e-12);
+        assert!((beta.cdf(0.3) + beta.sf(0.3) - 1.0).abs() < 1e-12);
+        assert_eq!((gamma.cdf(-1.0), gamma.sf(f64::INFINITY), beta.cdf(1.5)), (0.0, 0.0, 1.0));
+        for distribution in [gamma, beta, Distribution::Gamma { shape: 0.5, scale: 3.0 }, Distribution::Beta { alpha: 0.5, beta: 2.0 }] {
+            for p in [1e-10, 0.01, 0.5, 0.9, 0.999_999] {
+                assert!((distribution.cdf(distribution.quantile(p)) / p - 1.0).abs() < 1e-8, "{} {}", distribution, p);
+                assert!((distribution.sf(distribution.upper_quantile(p)) / p - 1.0).abs() < 1e-8, "{} {}", distribution, p);
+            }
+        }
+
+        let unbounded = |distribution| TruncatedDistribution::new(distribution, None, None).unwrap();
+        let report = unbounded(beta).sample(100_000, Seed::from([1; 32])).unwrap();
+        assert!(report.values.iter().all(|value| (0.0..=1.0).contains(value)));
+        assert!((report.mean() - 2.0 / 7.0).abs() < 0.005, "{}", report.mean());
+        let report = unbounded(Distribution::Gamma { shape: 2.0, scale: 3.0 }).sample(100_000, Seed::from([2; 32])).unwrap();
+        assert!((report.mean() - 6.0).abs() < 0.05 && (report.standard_deviation() - 18.0_f64.sqrt()).abs() < 0.05);
+        let report = unbounded(Distribution::Gamma { shape: 0.5, scale: 1.0 }).sample(100_000, Seed::from([3; 32])).unwrap();
+        assert!(report.values.iter().all(|&value| value >= 0.0) && (report.mean() - 0.5).abs() < 0.01, "{}", report.mean());
+        // 参数很小时值几乎都贴近 0 或 1,但不会是 NaN
+        let report = unbounded(Distribution::Beta { alpha: 0.01, beta: 0.01 }).sample(1000, Seed::from([4; 32])).unwrap();
+        assert!(report.values.iter().all(|value| (0.0..=1.0).contains(value)));
+
+        // 远在上尾:Gamma(2, 1) 超过 30 的概率是 31 e^-30,这部分的平均数是 962 / 31 ≈ 31.03
+        let tail = TruncatedDistribution::new(gamma, Some(30.0), None).unwrap();
+        assert!((tail.mass() / (31.0 * (-30.0_f64).exp()) - 1.0).abs() < 1e-10);
+        let report = tail.sample(10_000, Seed::from([5; 32])).unwrap();
+        assert!(report.values.iter().all(|&value| value >= 30.0));
+        assert!((report.mean() - 31.03).abs() < 0.05, "{}", report.mean());
+
+        for bad in ["beta(0, 1)", "beta(1)", "gamma(2, -1)", "gamma(0, 1)"] {
+            assert!(matches!(bad.parse::<Distribution>(), Err(RandomGeneratorError::InvalidDistribution(_))), "{}", bad);
+        }
+        assert!(TruncatedDistribution::new(beta, Some(1.5), None).is_err());
+    }
+
+    #[test]
+    fn test_dirichlet() {
+        let dirichlet = Dirichlet::parse(Some(3), "2, 3, 5").unwrap();
+        let sample = dirichlet.sample(50_000, Seed::from([1; 32])).unwrap();
+        assert_eq!(sample, dirichlet.sample(50_000, Seed::from([1; 32])).unwrap());
+        assert!(sample.vectors.iter().all(|vector| vector.len() == 3 && vector.iter().all(|&p| p >= 0.0) && (vector.iter().sum::<f64>() - 1.0).abs() < 1e-12));
+        for (mean, expected) in sample.means().into_iter().zip([0.2, 0.3, 0.5]) {
+            assert!((mean - expected).abs() < 0.005, "{} {}", mean, expected);
+        }
+        // Var(p₁) = α₁(Σα - α₁) / (Σα² (Σα + 1)) = 2 × 8 / (100 × 11)
+        let variance = sample.vectors.iter().map(|vector| (vector[0] - 0.2).powi(2)).sum::<f64>() / sample.vectors.len() as f64;
+        assert!((variance - 16.0 / 1100.0).abs() < 0.0005, "{}", variance);
+
+        // Tiny concentrations put nearly everything on one component, but never 0 / 0
+        let sparse = Dirichlet::symmetric(5, 0.001).unwrap().sample(1000, Seed::from([2; 32])).unwrap();
+        assert!(sparse.vectors.iter().all(|vector| (vector.iter().sum::<f64>() - 1.0).abs() < 1e-12));
+        assert!(sparse.vectors.iter().filter(|vector| vector.iter().any(|&p| p > 0.99)).count() > 950);
+        // Almost-zero weights are written in scientific notation rather than as hundreds of digits
+        assert!(sparse.to_csv().lines().skip(1).flat_map(|row| row.split(',').skip(1)).all(|cell| cell.len() <= 10), "{}", sparse.to_csv());
+        assert_eq!(DirichletSample::format_weight(0.25), "0.250000");
+        assert_eq!(DirichletSample::format_weight(1.5e-150), "1.500e-150");
+
+        assert_eq!(Dirichlet::parse(Some(4), "0.5").unwrap(), Dirichlet::symmetric(4, 0.5).unwrap());
+        assert_eq!(Dirichlet::parse(None, "1 1").unwrap().to_string(), "dirichlet(1, 1)");
+        let text = sample.to_text();
+        assert!(text.contains("Vectors: 50000") && text.contains("Expected  0.2000  0.3000  0.5000"));
+        assert!(sample.to_csv().starts_with("vector,p1,p2,p3\n1,0."));
+
+        for (components, concentration) in [(None, "1"), (Some(3), "1, 2"), (Some(1), "1"), (Some(3), "0"), (Some(3), "-1"), (Some(3), ""), (Some(3), "x")] {
+            assert!(matches!(Dirichlet::parse(components, concentration), Err(RandomGeneratorError::InvalidDistribution(_))), "{:?} {}", components, concentration);
+        }
+        assert!(Dirichlet::symmetric(usize::MAX, 1.0).is_err());
+        assert!(dirichlet.sample(0, Seed::from([3; 32])).is_err());
+        assert!(dirichlet.sample(distribution::MAX_SAMPLES, Seed::from([3; 32])).is_err());
+    }
+
+    #[test]
+    fn test_cdf_table() {
+        // Points out of order; 0.2 of the mass sits on 0 and 0.3 on 5
+        let table = CdfTable::parse("# value, cumulative\n10 0.6\n0, 0.2; 5 0.3\n5, 0.6\n20, 1").unwrap();
+        assert_eq!(table.points(), &[(0.0, 0.2), (5.0, 0.3), (5.0, 0.6), (10.0, 0.6), (20.0, 1.0)]);
+        assert_eq!((table.quantile(0.0), table.quantile(0.1), table.quantile(0.25)), (0.0, 0.0, 2.5));
+        assert_eq!((table.quantile(0.45), table.quantile(0.6), table.quantile(1.0)), (5.0, 10.0, 20.0));
+        assert!((table.quantile(0.8) - 15.0).abs() < 1e-12);
+        // 0.2 × 0 + 0.1 × 2.5 + 0.3 × 5 + 0 + 0.4 × 15
+        assert!((table.mean() - 7.75).abs() < 1e-12);
+
+        let sample = table.sample(100_000, Seed::from([1; 32])).unwrap();
+        assert_eq!(sample, table.sample(100_000, Seed::from([1; 32])).unwrap());
+        assert!((sample.mean() - 7.75).abs() < 0.05, "{}", sample.mean());
+        let share = |test: fn(f64) -> bool| sample.values.iter().filter(|&&value| test(value)).count() as f64 / 100_000.0;
+        assert!((share(|value| value == 0.0) - 0.2).abs() < 0.005);
+        assert!((share(|value| value == 5.0) - 0.3).abs() < 0.005);
+        assert_eq!(share(|value| value > 5.0 && value < 10.0), 0.0);
+        assert!(sample.to_text().contains("Distribution: table of 5 points from 0 to 20") && sample.to_text().contains("Expected mean: 7.7500"));
+        assert!(sample.to_csv().starts_with("index,value\n1,"));
+
+        for bad in ["", "0 1", "0 0\n1 0.5", "0 0.5\n1 0.4\n2 1", "0 0\n1 1.5", "0 0\nx 1", "0 0 0\n1 1", "0 0\ninf 1"] {
+            assert!(matches!(CdfTable::parse(bad), Err(RandomGeneratorError::InvalidDistribution(_))), "{}", bad);
+        }
+        assert!(table.sample(0, Seed::from([2; 32])).is_err());
+    }
+
+    #[test]
+    fn test_column_schema() {
+        let schema = ColumnSchema::parse("# test table\nid: 1..1000\n\nscore: normal(50, 10) round 1\nsize: S, M, L, XL\nratio: beta(2, 5)").unwrap();
+        assert_eq!(schema.columns.iter().map(|column| column.name.as_str()).collect::<Vec<_>>(), ["id", "score", "size", "ratio"]);
+        assert_eq!(schema.columns[0].values, ColumnValues::Range(1, 1000));
+        assert_eq!(schema.columns[1].values.to_string(), "normal(50, 10) round 1");
+        assert_eq!(schema.columns[2].values, ColumnValues::Choice(["S", "M", "L", "XL"].map(String::from).to_vec()));
+
+        let rows = schema.generate(20_000, Seed::from([1; 32])).unwrap();
+        assert_eq!(rows, schema.generate(20_000, Seed::from([1; 32])).unwrap());
+        let ids: Vec<i64> = rows.iter().map(|row| row[0].parse().unwrap()).collect();
+        assert!(ids.iter().all(|id| (1..=1000).contains(id)) && ids.contains(&1) && ids.contains(&1000));
+        let scores: Vec<f64> = rows.iter().map(|row| row[1].parse().unwrap()).collect();
+        assert!(rows.iter().all(|row| row[1].split_once('.').is_some_and(|(_, decimals)| decimals.len() == 1)));
+        assert!((scores.iter().sum::<f64>() / 20_000.0 - 50.0).abs() < 0.3);
+        let small = rows.iter().filter(|row| row[2] == "S").count();
+        assert!((small as f64 / 20_000.0 - 0.25).abs() < 0.02, "{}", small);
+        assert!(rows.iter().all(|row| row[3].parse::<f64>().is_ok_and(|ratio| (0.0..=1.0).contains(&ratio))));
+        let csv = schema.to_csv(&rows[..1]);
+        assert!(csv.starts_with("id,score,size,ratio\n") && csv.lines().count() == 2);
+
+        for (bad, line) in [("", 0), ("die 1..6", 1), ("a: 1..6\n: 1..6", 2), ("a: 1..6\na: 2..3", 2), ("a: 6..1", 1), ("a: 1..x", 1), ("a: normall(1, 2)", 1), ("a: normal(0, 1) round", 1), ("a: normal(0, -1)", 1), ("a: ,", 1)] {
+            assert!(matches!(ColumnSchema::parse(bad), Err(RandomGeneratorError::InvalidColumns { line: l, .. }) if l == line), "{}", bad);
+        }
+        assert_eq!(ColumnSchema::parse("a: x\nb: 2..1").unwrap_err().to_string(), "Columns line 2: 2..1 goes backwards");
+    }
+
+    #[test]
+    fn test_multivariate_normal() {
+        let normal = MultivariateNormal::parse("10, 0, -5", "2", "1, 0.8, -0.3\n0.8, 1, 0; -0.3, 0, 1").unwrap();
+        assert_eq!((normal.dimensions(), normal.sds()), (3, &[2.0, 2.0, 2.0][..]));
+        let sample = normal.sample(100_000, Seed::from([1; 32])).unwrap();
+        assert_eq!(sample, normal.sample(100_000, Seed::from([1; 32])).unwrap());
+        let mut reported = Vec::new();
+        assert_eq!(normal.sample_with_progress(100_000, Seed::from([1; 32]), &AtomicBool::new(false), &mut |done, total| reported.push((done, total))).unwrap(), sample);
+        assert_eq!(reported, [(65_535, 300_000), (131_070, 300_000), (196_605, 300_000), (262_140, 300_000)]);
+        assert!(matches!(normal.sample_with_progress(100_000, Seed::from([1; 32]), &AtomicBool::new(true), &mut |_, _| {}), Err(RandomGeneratorError::Cancelled)));
+        for (mean, expected) in sample.means().into_iter().zip([10.0, 0.0, -5.0]) {
+            assert!((mean - expected).abs() < 0.03, "{} {}", mean, expected);
+        }
+        assert!(sample.sds().iter().all(|sd| (sd - 2.0).abs() < 0.03), "{:?}", sample.sds());
+        let correlation = sample.correlation();
+        for (i, j, expected) in [(0, 1, 0.8), (0, 2, -0.3), (1, 2, 0.0)] {
+            assert!((correlation[i][j] - expected).abs() < 0.01 && correlation[i][j] == correlation[j][i], "{:?}", correlation);
+        }
+
+        // Perfectly correlated variables are allowed: the second is always the first mirrored
+        let mirrored = MultivariateNormal::parse("", "", "1, -1; -1, 1").unwrap().sample(100, Seed::from([2; 32])).unwrap();
+        assert!(mirrored.rows.iter().all(|row| (row[0] + row[1]).abs() < 1e-12));
+
+        let text = sample.to_text();
+        assert!(text.contains("Variables: 3") && text.contains("Sample correlation"));
+        let csv = sample.to_csv();
+        assert!(csv.starts_with("x1,x2,x3\n") && csv.lines().count() == 100_001);
+
+        for (means, sds, matrix) in [
+            ("", "", "1"),
+            ("", "", "1, 0.5; 0.4, 1"),
+            ("", "", "1, 0.5; 0.5, 0.9"),
+            ("", "", "1, 1.5; 1.5, 1"),
+            ("", "", "1, 0.5, 0; 0.5, 1"),
+            ("", "", "1, -0.9, -0.9; -0.9, 1, -0.9; -0.9, -0.9, 1"),
+            ("1, 2, 3", "", "1, 0; 0, 1"),
+            ("", "0", "1, 0; 0, 1"),
+            ("", "", "1, x; x, 1"),
+        ] {
+            assert!(matches!(MultivariateNormal::parse(means, sds, matrix), Err(RandomGeneratorError::InvalidDistribution(_))), "{}", matrix);
+        }
+        assert!(normal.sample(0, Seed::from([3; 32])).is_err());
+    }
+
+    #[test]
+    fn test_bootstrap() {
+        let text: Vec<String> = (1..=100).map(|n| n.to_string()).collect();
+        let data = Bootstrap::parse(&text.join("\n")).unwrap();
+        let report = data.resample(2000, None, Seed::from([6; 32])).unwrap();
+        assert_eq!(report, data.resample(2000, None, Seed::from([6; 32])).unwrap());
+        assert_eq!((report.data_len, report.size, report.data_mean), (100, 100, 50.5));
+        // 总体标准差约 28.87,抽 100 个时平均数的标准误约 2.89
+        assert!((report.standard_error() - 2.887).abs() < 0.3, "{}", report.standard_error());
+        assert!((report.mean() - 50.5).abs() < 0.5);
+        let (low, high) = report.interval(0.95);
+        assert!(low < 50.5 && 50.5 < high && (high - low - 4.0 * 2.887).abs() < 2.0, "{} to {}", low, high);
+        assert_eq!(report.histogram(20).iter().map(|&(_, _, count)| count).sum::<usize>(), 2000);
+        assert_eq!(report.to_text().lines().count(), 6 + 20);
+        assert!(report.to_csv().starts_with("resample,mean\n1,"));
+        assert_eq!(report.to_csv().lines().count(), 2001);
+
+        // 重复的值按出现的次数计;所有值相同时只有一组
+        let same = Bootstrap::parse("7, 7 # 注释\n7").unwrap();
+        let report = same.resample(10, Some(3), Seed::from([1; 32])).unwrap();
+        assert_eq!(report.histogram(20), vec![(7.0, 7.0, 10)]);
+        assert_eq!((report.standard_error(), report.percentile(0.5)), (0.0, 7.0));
+
+        assert!(matches!(Bootstrap::parse("1\n2 x"), Err(RandomGeneratorError::InvalidBootstrap(message)) if message.starts_with("line 2:")));
+        assert!(matches!(Bootstrap::parse("# 空"), Err(RandomGeneratorError::InvalidBootstrap(_))));
+        for (resamples, size) in [(0, None), (1, Some(0)), (10_000_000, Some(2))] {
+            assert!(matches!(same.resample(resamples, size, Seed::from([1; 32])), Err(RandomGeneratorError::InvalidBootstrap(_))));
+        }
+
+        let mut reported = Vec::new();
+        let report = data.resample_with_progress(2000, None, Seed::from([6; 32]), &AtomicBool::new(false), &mut |done, total| reported.push((done, total)));
+        assert_eq!(report.unwrap(), data.resample(2000, None, Seed::from([6; 32])).unwrap());
+        assert_eq!(reported.first(), Some(&(0, 200_000)));
+        let cancelled = data.resample_with_progress(2000, None, Seed::from([6; 32]), &AtomicBool::new(true), &mut |_, _| {});
+        assert!(matches!(cancelled, Err(RandomGeneratorError::Cancelled)));
+    }
+
+    #[test]
+    fn test_random_walk() {
+        let walk = RandomWalk { length: 1000, start: 5.0, drift: 0.5, step: StepDistribution::Uniform, scale: 0.25, ..Default::default() };
+        let series = walk.series(Seed::from([3; 32])).unwrap();
+        assert_eq!(series, walk.series(Seed::from([3; 32])).unwrap());
+        assert_eq!((series.len(), series[0]), (1000, 5.0));
+        assert!(series.windows(2).all(|pair| (pair[1] - pair[0] - 0.5).abs() <= 0.25));
+
+        // φ = 0.5、漂移 1 时围绕 2 波动,方差为 1 / (1 - 0.25)
+        let ar = RandomWalk { length: 100_000, drift: 1.0, phi: 0.5, ..Default::default() };
+        let series = ar.series(Seed::from([4; 32])).unwrap();
+        let mean = series.iter().sum::<f64>() / series.len() as f64;
+        let variance = series.iter().map(|value| (value - mean).powi(2)).sum::<f64>() / series.len() as f64;
+        assert!((mean - 2.0).abs() < 0.05 && (variance - 4.0 / 3.0).abs() < 0.05, "{} {}", mean, variance);
+
+        assert_eq!(RandomWalk::to_csv(&[1.0, -0.5]), "index,value\n0,1\n1,-0.5\n");
+        assert_eq!("normal".parse::<StepDistribution>().unwrap(), StepDistribution::Normal);
+        assert!(matches!("cauchy".parse::<StepDistribution>(), Err(RandomGeneratorError::InvalidWalk(_))));
+        for bad in [
+            RandomWalk { length: 0, ..Default::default() },
+            RandomWalk { scale: 0.0, ..Default::default() },
+            RandomWalk { phi: 1.5, ..Default::default() },
+            RandomWalk { drift: f64::NAN, ..Default::default() },
+        ] {
+            assert!(matches!(bad.series(Seed::from([1; 32])), Err(RandomGeneratorError::InvalidWalk(_))), "{:?}", bad);
+        }
+
+        let mut reported = 0;
+        assert_eq!(ar.series_with_progress(Seed::from([4; 32]), &AtomicBool::new(false), &mut |_, _| reported += 1).unwrap(), series);
+        assert_eq!(reported, 1);
+        assert!(matches!(ar.series_with_progress(Seed::from([4; 32]), &AtomicBool::new(true), &mut |_, _| {}), Err(RandomGeneratorError::Cancelled)));
+    }
+
+    #[test]
+    fn test_formula() {
+        let cases = [("1 + 2 * 3", 0.0, 7.0), ("-x^2", 3.0, -9.0), ("2^3^2", 0.0, 512.0), ("(1 + x) / 2", 5.0, 3.0), ("abs(x) + LOG(100)", -4.0, 6.0), ("2^-1", 0.0, 0.5)];
+        for (text, x, expected) in cases {
+            assert_eq!(text.parse::<Formula>().unwrap().eval(x), expected, "{}", text);
+        }
+        assert!("sqrt(x)".parse::<Formula>().unwrap().eval(-1.0).is_nan());
+        assert_eq!(" x^2 ".parse::<Formula>().unwrap().to_string(), "x^2");
+        for bad in ["", "2x", "x +", "(x", "sin x", "foo(x)", "y", "1..2", "x $ 2"] {
+            assert!(matches!(bad.parse::<Formula>(), Err(RandomGeneratorError::InvalidFormula(_))), "{:?}", bad);
+        }
+    }
+
+    #[test]
+    fn test_monte_carlo() {
+        let pi = MonteCarlo { target: MonteCarloTarget::Pi, samples: 200_000 };
+        let report = pi.run(Seed::from([7; 32])).unwrap();
+        assert_eq!(report, pi.run(Seed::from([7; 32])).unwrap());
+        // 每个样本的标准差约 1.64
+        assert!((report.standard_error - 1.642 / 200_000f64.sqrt()).abs() < 1e-4, "{}", report.standard_error);
+        assert!((report.estimate - std::f64::consts::PI).abs() < 4.0 * report.standard_error);
+        assert_eq!(report.convergence.len(), 1000);
+        assert_eq!(report.convergence.last(), Some(&(200_000, report.estimate)));
+        assert_eq!(report.exact, Some(std::f64::consts::PI));
+        assert!(report.to_string().contains("Exact: 3.141593"));
+        assert!(report.to_csv().starts_with("samples,estimate\n200,"));
+
+        let mut seen = Vec::new();
+        let sine = MonteCarlo {
+            target: MonteCarloTarget::Integral { formula: "sin(x)".parse().unwrap(), lower: 0.0, upper: std::f64::consts::PI },
+            samples: 2500,
+        };
+        let report = sine.run_with_progress(Seed::from([1; 32]), &AtomicBool::new(false), &mut |n, estimate| seen.push((n, estimate))).unwrap();
+        assert_eq!(seen, report.convergence);
+        assert_eq!(report.convergence.len(), 834);
+        assert!((report.estimate - 2.0).abs() < 0.1 && report.exact.is_none());
+        assert!(matches!(sine.run_with_progress(Seed::from([1; 32]), &AtomicBool::new(true), &mut |_, _| {}), Err(RandomGeneratorError::Cancelled)));
+
+        let undefined = MonteCarlo { target: MonteCarloTarget::Integral { formula: "ln(x)".parse().unwrap(), lower: -1.0, upper: 1.0 }, samples: 1000 };
+        assert!(matches!(undefined.run(Seed::from([1; 32])), Err(RandomGeneratorError::InvalidMonteCarlo(_))));
+        let backwards = MonteCarlo { target: MonteCarloTarget::Integral { formula: "x".parse().unwrap(), lower: 1.0, upper: 0.0 }, samples: 10 };
+        assert!(matches!(backwards.run(Seed::from([1; 32])), Err(RandomGeneratorError::InvalidMonteCarlo(_))));
+        assert!(MonteCarlo { target: MonteCarloTarget::Pi, samples: 0 }.validate().is_err());
+    }
+}
@@ -0,0 +1,100 @@
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::io::BufRead;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::{RandomGeneratorError, Seed};
+
+/// 每读这么多字节回报一次进度、检查一次取消
+const PROGRESS_BYTES: u64 = 1 << 20;
+
+/// 从文本中随机抽取 `count` 行,每行被抽中的机会相同
+///
+/// 用蓄水池抽样逐行读取,只保留抽中的行,适合内存放不下的大文件。
+/// 空白行不算;行尾的换行符去掉,不是 UTF-8 的字节换成 `�`
+///
+/// ```
+/// use random_generator_core::{LineSample, Seed};
+///
+/// let text = "alpha\nbeta\n\ngamma\ndelta\n";
+/// let sample = LineSample { count: 2, seed: Seed::from([1; 32]) }.sample(&mut text.as_bytes())?;
+/// assert_eq!((sample.lines.len(), sample.total), (2, 4));
+/// assert!(sample.lines.iter().all(|line| text.lines().any(|known| known == line)));
+/// # Ok::<(), random_generator_core::RandomGeneratorError>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LineSample {
+    /// 抽取几行
+    pub count: usize,
+    /// 随机数种子
+    pub seed: Seed,
+}
+
+/// 抽取的结果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SampledLines {
+    /// 抽中的行,顺序随机;文本不够 `count` 行时就是全部的行
+    pub lines: Vec<String>,
+    /// 文本中不是空白的行数
+    pub total: usize,
+}
+
+impl LineSample {
+    /// 从 `reader` 中抽取
+    pub fn sample(&self, reader: &mut dyn BufRead) -> Result<SampledLines, RandomGeneratorError> {
+        self.sample_with_progress(reader, &AtomicBool::new(false), &mut |_| {})
+    }
+
+    /// 从 `reader` 中抽取,以已读字节数回报进度
+    ///
+    /// 每读约 1 MB 调用一次 `on_progress` 并检查 `cancel`,被置位时返回 `Cancelled`
+    pub fn sample_with_progress(
+        &self,
+        reader: &mut dyn BufRead,
+        cancel: &AtomicBool,
+        on_progress: &mut dyn FnMut(u64),
+    ) -> Result<SampledLines, RandomGeneratorError> {
+        if self.count == 0 {
+            return Err(RandomGeneratorError::InvalidLineSample("sample at least one line".to_string()));
+        }
+        let mut rng = self.seed.rng();
+        let mut reservoir: Vec<String> = Vec::new();
+        let mut buffer = Vec::new();
+        let (mut total, mut read, mut reported) = (0usize, 0u64, 0u64);
+        loop {
+            buffer.clear();
+            let length = reader.read_until(b'\n', &mut buffer)?;
+            if length == 0 {
+                break;
+            }
+            read += length as u64;
+            if read - reported >= PROGRESS_BYTES {
+                if cancel.load(Ordering::Relaxed) {
+                    return Err(RandomGeneratorError::Cancelled);
+                }
+                reported = read;
+                on_progress(read);
+            }
+            if buffer.iter().all(u8::is_ascii_whitespace) {
+                continue;
+            }
+
+            // 第 total 行以 count / total 的机会换掉蓄水池中随机的一行;只有抽中时才转换成文本
+            total += 1;
+            let slot = if reservoir.len() < self.count { reservoir.len() } else { rng.gen_range(0..total) };
+            if slot < self.count {
+                let line = String::from_utf8_lossy(&buffer).trim_end_matches(['\n', '\r']).to_string();
+                if slot == reservoir.len() {
+                    reservoir.push(line);
+                } else {
+                    reservoir[slot] = line;
+                }
+            }
+        }
+        on_progress(read);
+
+        // 蓄水池中的顺序与行在文本中的位置有关,打乱后每种排列的机会相同
+        reservoir.shuffle(&mut rng);
+        Ok(SampledLines { lines: reservoir, total })
+    }
+}
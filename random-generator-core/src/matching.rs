@@ -0,0 +1,85 @@
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::collections::HashSet;
+
+use crate::RandomGeneratorError;
+
+/// 检查名单:名字不能为空,不区分大小写也不能重复
+pub(crate) fn check_names(names: &[String]) -> Result<(), RandomGeneratorError> {
+    let mut seen = HashSet::new();
+    for name in names {
+        if name.trim().is_empty() {
+            return Err(RandomGeneratorError::InvalidDraw("names can't be empty".to_string()));
+        }
+        if !seen.insert(name.to_lowercase()) {
+            return Err(RandomGeneratorError::InvalidDraw(format!("'{}' is in the list twice", name)));
+        }
+    }
+    Ok(())
+}
+
+/// 每行一个名字,跳过空行
+pub(crate) fn parse_names(text: &str) -> Vec<String> {
+    text.lines().map(str::trim).filter(|line| !line.is_empty()).map(String::from).collect()
+}
+
+/// 名字在名单中的位置,不区分大小写
+pub(crate) fn position(names: &[String], name: &str) -> Result<usize, RandomGeneratorError> {
+    let name = name.trim();
+    names
+        .iter()
+        .position(|other| other.to_lowercase() == name.to_lowercase())
+        .ok_or_else(|| RandomGeneratorError::InvalidDraw(format!("'{}' is not in the list", name)))
+}
+
+/// 每行一对用逗号分隔的名字,例如 `Alice, Bob`,跳过空行
+pub(crate) fn parse_pairs(text: &str) -> Result<Vec<(&str, &str)>, RandomGeneratorError> {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            line.split_once(',')
+                .ok_or_else(|| RandomGeneratorError::InvalidDraw(format!("'{}' is not a pair like 'Alice, Bob'", line)))
+        })
+        .collect()
+}
+
+/// 二分图匹配(Kuhn 算法):为每个来源找一个不同的目标,`candidates[i]` 是来源 `i` 可以用的目标
+///
+/// 候选目标和来源的处理顺序都是随机的;不能让每个来源都有目标时返回 `None`,
+/// 否则返回每个来源的目标
+pub(crate) fn match_all(mut candidates: Vec<Vec<usize>>, targets: usize, rng: &mut impl Rng) -> Option<Vec<usize>> {
+    for list in &mut candidates {
+        list.shuffle(rng);
+    }
+    let mut source_of = vec![None; targets];
+    let mut order: Vec<usize> = (0..candidates.len()).collect();
+    order.shuffle(rng);
+    for source in order {
+        if !augment(source, &candidates, &mut source_of, &mut vec![false; targets]) {
+            return None;
+        }
+    }
+    let mut target_of = vec![0; candidates.len()];
+    for (target, source) in source_of.into_iter().enumerate() {
+        if let Some(source) = source {
+            target_of[source] = target;
+        }
+    }
+    Some(target_of)
+}
+
+/// 为 `source` 找一个目标,必要时让已匹配的来源换一个
+fn augment(source: usize, candidates: &[Vec<usize>], source_of: &mut [Option<usize>], visited: &mut [bool]) -> bool {
+    for &target in &candidates[source] {
+        if visited[target] {
+            continue;
+        }
+        visited[target] = true;
+        if source_of[target].is_none_or(|other| augment(other, candidates, source_of, visited)) {
+            source_of[target] = Some(source);
+            return true;
+        }
+    }
+    false
+}
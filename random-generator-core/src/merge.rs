@@ -0,0 +1,71 @@
+use std::collections::HashSet;
+
+use crate::{Number, Stats};
+
+/// 几个结果文件合并后的数字,例如汇总几次会话的抽奖结果
+///
+/// 各文件的数字按文件的先后接在一起;去重时每个值保留第一次出现的位置
+///
+/// ```
+/// use random_generator_core::MergedResults;
+///
+/// let files = vec![("monday.txt".to_string(), vec![3_i64, 8]), ("tuesday.txt".to_string(), vec![8, 5])];
+/// let merged = MergedResults::new(files, true);
+/// assert_eq!(merged.numbers, [3, 8, 5]);
+/// assert_eq!(merged.duplicates_removed, 1);
+/// assert_eq!(merged.stats().max, Some(8));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct MergedResults<T: Number> {
+    /// 合并后的数字
+    pub numbers: Vec<T>,
+    /// 每个文件的名字和其中的数字个数,按合并的先后
+    pub files: Vec<(String, usize)>,
+    /// 去重时去掉的个数,不去重时为 0
+    pub duplicates_removed: usize,
+}
+
+impl<T: Number> MergedResults<T> {
+    /// 合并各文件读到的数字;`remove_duplicates` 时只保留每个值第一次出现的
+    pub fn new(files: Vec<(String, Vec<T>)>, remove_duplicates: bool) -> Self {
+        let mut merged = MergedResults { numbers: Vec::new(), files: Vec::new(), duplicates_removed: 0 };
+        let mut seen = HashSet::new();
+        for (name, numbers) in files {
+            merged.files.push((name, numbers.len()));
+            for value in numbers {
+                if remove_duplicates && !seen.insert(value.key()) {
+                    merged.duplicates_removed += 1;
+                    continue;
+                }
+                merged.numbers.push(value);
+            }
+        }
+        merged
+    }
+
+    /// 合并后的统计信息
+    pub fn stats(&self) -> Stats<T> {
+        Stats::of(&self.numbers)
+    }
+
+    /// 文字摘要:每个文件的个数和合并后的统计
+    pub fn to_text(&self) -> String {
+        let mut text = String::new();
+        for (name, count) in &self.files {
+            text.push_str(&format!("{}: {} numbers\n", name, count));
+        }
+        let stats = self.stats();
+        text.push_str(&format!("\nMerged: {} numbers", stats.count));
+        match self.duplicates_removed {
+            0 => {}
+            1 => text.push_str(" (1 duplicate removed)"),
+            removed => text.push_str(&format!(" ({} duplicates removed)", removed)),
+        }
+        text.push('\n');
+        if let (Some(min), Some(max)) = (stats.min, stats.max) {
+            let sum = stats.sum.map_or("out of range".to_string(), |sum| sum.to_string());
+            text.push_str(&format!("Min: {}\nMax: {}\nSum: {}\nMean: {:.4}\n", min, max, sum, stats.avg));
+        }
+        text
+    }
+}
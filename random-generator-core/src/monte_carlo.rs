@@ -0,0 +1,164 @@
+use rand::Rng;
+use std::f64::consts::PI;
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::{Formula, RandomGeneratorError, Seed};
+
+/// 一次最多抽取的样本数
+const MAX_SAMPLES: usize = 1_000_000_000;
+
+/// 收敛过程最多记录几个点
+const CONVERGENCE_POINTS: usize = 1000;
+
+/// 蒙特卡洛要估计的量
+#[derive(Debug, Clone, PartialEq)]
+pub enum MonteCarloTarget {
+    /// 在单位正方形中撒点,落在四分之一圆内的比例乘以 4
+    Pi,
+    /// `formula` 从 `lower` 到 `upper` 的定积分:随机取 `x`,函数值的平均数乘以区间长度
+    Integral {
+        /// 被积函数
+        formula: Formula,
+        /// 下限
+        lower: f64,
+        /// 上限
+        upper: f64,
+    },
+}
+
+/// 蒙特卡洛估计的演示:抽取越多,估计越接近真实值,误差大约按 1/√N 缩小
+///
+/// ```
+/// use random_generator_core::{MonteCarlo, MonteCarloTarget, Seed};
+///
+/// let demo = MonteCarlo { target: MonteCarloTarget::Pi, samples: 100_000 };
+/// let report = demo.run(Seed::from([2; 32]))?;
+/// assert!((report.estimate - std::f64::consts::PI).abs() < 4.0 * report.standard_error);
+///
+/// let area = MonteCarlo {
+///     target: MonteCarloTarget::Integral { formula: "x^2".parse()?, lower: 0.0, upper: 3.0 },
+///     samples: 100_000,
+/// };
+/// assert!((area.run(Seed::from([2; 32]))?.estimate - 9.0).abs() < 0.2);
+/// # Ok::<(), random_generator_core::RandomGeneratorError>(())
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct MonteCarlo {
+    /// 估计什么
+    pub target: MonteCarloTarget,
+    /// 抽取几个样本
+    pub samples: usize,
+}
+
+/// 一次估计的结果
+#[derive(Debug, Clone, PartialEq)]
+pub struct MonteCarloReport {
+    /// 抽取的样本数
+    pub samples: usize,
+    /// 估计值
+    pub estimate: f64,
+    /// 估计值的标准误
+    pub standard_error: f64,
+    /// 已知的真实值,只有 π 有
+    pub exact: Option<f64>,
+    /// 收敛过程:每隔一段记录 `(已抽样本数, 当时的估计值)`,最后一项是全部样本
+    pub convergence: Vec<(usize, f64)>,
+}
+
+impl MonteCarlo {
+    /// 检查样本数和积分区间
+    pub fn validate(&self) -> Result<(), RandomGeneratorError> {
+        if !(1..=MAX_SAMPLES).contains(&self.samples) {
+            return Err(RandomGeneratorError::InvalidMonteCarlo(format!("draw 1 to {} samples", MAX_SAMPLES)));
+        }
+        if let MonteCarloTarget::Integral { lower, upper, .. } = self.target {
+            if !(lower.is_finite() && upper.is_finite() && lower < upper) {
+                return Err(RandomGeneratorError::InvalidMonteCarlo("the integral needs finite limits with the lower one first".to_string()));
+            }
+        }
+        Ok(())
+    }
+
+    /// 用种子估计
+    pub fn run(&self, seed: Seed) -> Result<MonteCarloReport, RandomGeneratorError> {
+        self.run_with_progress(seed, &AtomicBool::new(false), &mut |_, _| {})
+    }
+
+    /// 用种子估计,并在记录收敛过程时以 `(已抽样本数, 当时的估计值)` 回报进度
+    ///
+    /// 每次回报前检查 `cancel`,被置位时返回 `Cancelled`
+    pub fn run_with_progress(
+        &self,
+        seed: Seed,
+        cancel: &AtomicBool,
+        on_progress: &mut dyn FnMut(usize, f64),
+    ) -> Result<MonteCarloReport, RandomGeneratorError> {
+        self.validate()?;
+        let mut rng = seed.rng();
+        let sample = |rng: &mut dyn rand::RngCore| match &self.target {
+            MonteCarloTarget::Pi => {
+                let (x, y) = (rng.gen::<f64>(), rng.gen::<f64>());
+                Ok(if x * x + y * y <= 1.0 { 4.0 } else { 0.0 })
+            }
+            MonteCarloTarget::Integral { formula, lower, upper } => {
+                let x = rng.gen_range(*lower..=*upper);
+                let value = formula.eval(x);
+                if !value.is_finite() {
+                    return Err(RandomGeneratorError::InvalidMonteCarlo(format!("{} is not a number at x = {}", formula, x)));
+                }
+                Ok(value * (upper - lower))
+            }
+        };
+
+        // 用 Welford 算法累计平均数和方差,避免大量样本时的舍入误差
+        let every = self.samples.div_ceil(CONVERGENCE_POINTS);
+        let mut convergence = Vec::with_capacity(CONVERGENCE_POINTS + 1);
+        let (mut mean, mut squares) = (0.0, 0.0);
+        for n in 1..=self.samples {
+            let value = sample(&mut rng)?;
+            let delta = value - mean;
+            mean += delta / n as f64;
+            squares += delta * (value - mean);
+            if n.is_multiple_of(every) || n == self.samples {
+                if cancel.load(Ordering::Relaxed) {
+                    return Err(RandomGeneratorError::Cancelled);
+                }
+                convergence.push((n, mean));
+                on_progress(n, mean);
+            }
+        }
+
+        let variance = if self.samples > 1 { squares / (self.samples - 1) as f64 } else { 0.0 };
+        Ok(MonteCarloReport {
+            samples: self.samples,
+            estimate: mean,
+            standard_error: (variance / self.samples as f64).sqrt(),
+            exact: matches!(self.target, MonteCarloTarget::Pi).then_some(PI),
+            convergence,
+        })
+    }
+}
+
+impl MonteCarloReport {
+    /// 收敛过程的 CSV,带表头 `samples,estimate`
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("samples,estimate\n");
+        for (samples, estimate) in &self.convergence {
+            csv.push_str(&format!("{},{}\n", samples, estimate));
+        }
+        csv
+    }
+}
+
+impl fmt::Display for MonteCarloReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Samples: {}", self.samples)?;
+        writeln!(f, "Estimate: {:.6}", self.estimate)?;
+        write!(f, "Standard error: {:.6}", self.standard_error)?;
+        if let Some(exact) = self.exact {
+            write!(f, "\nExact: {:.6}, off by {:.6}", exact, (self.estimate - exact).abs())?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,260 @@
+use std::sync::atomic::AtomicBool;
+
+use crate::distribution::{standard_normal, MAX_SAMPLES};
+use crate::{Progress, RandomGeneratorError, Seed, PROGRESS_INTERVAL};
+
+/// 最多的变量数
+pub const MAX_DIMENSIONS: usize = 100;
+
+/// Cholesky 分解时把绝对值小于这个数的负主元当作 0,允许完全相关的变量
+const PIVOT_TOLERANCE: f64 = 1e-10;
+
+/// 相关的多元正态分布:每个变量有自己的平均数和标准差,变量两两之间的相关系数由相关矩阵给出
+///
+/// 相关矩阵要对称、对角线为 1、其余在 -1 和 1 之间,并且半正定(例如三个变量不能两两都是 -0.9)。
+/// 抽样时先对相关矩阵做 Cholesky 分解 R = LLᵀ,再把独立的标准正态值 z 变换为 μ + σ · (Lz)
+///
+/// ```
+/// use random_generator_core::{MultivariateNormal, Seed};
+///
+/// let heights_weights = MultivariateNormal::parse("170, 65", "10, 12", "1, 0.7; 0.7, 1")?;
+/// let sample = heights_weights.sample(10_000, Seed::from([4; 32]))?;
+/// assert_eq!(sample.rows[0].len(), 2);
+/// assert!((sample.correlation()[0][1] - 0.7).abs() < 0.03);
+/// # Ok::<(), random_generator_core::RandomGeneratorError>(())
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct MultivariateNormal {
+    means: Vec<f64>,
+    sds: Vec<f64>,
+    correlation: Vec<Vec<f64>>,
+    /// 相关矩阵的 Cholesky 因子,下三角
+    cholesky: Vec<Vec<f64>>,
+}
+
+/// 抽到的值,每行一组
+#[derive(Debug, Clone, PartialEq)]
+pub struct MultivariateSample {
+    /// 抽样用的分布
+    pub normal: MultivariateNormal,
+    /// 每组的各变量,按抽取的先后
+    pub rows: Vec<Vec<f64>>,
+}
+
+impl MultivariateNormal {
+    /// 检查参数并分解相关矩阵;变量数由相关矩阵决定,平均数和标准差的个数要与它相同
+    pub fn new(means: Vec<f64>, sds: Vec<f64>, correlation: Vec<Vec<f64>>) -> Result<Self, RandomGeneratorError> {
+        let invalid = |message: String| Err(RandomGeneratorError::InvalidDistribution(message));
+        let n = correlation.len();
+        if !(2..=MAX_DIMENSIONS).contains(&n) {
+            return invalid(format!("a correlation matrix needs 2 to {} rows", MAX_DIMENSIONS));
+        }
+        if let Some(i) = correlation.iter().position(|row| row.len() != n) {
+            return invalid(format!("row {} of the correlation matrix has {} values, not {}", i + 1, correlation[i].len(), n));
+        }
+        if means.len() != n || sds.len() != n {
+            return invalid(format!("give {} means and {} standard deviations, one per variable", n, n));
+        }
+        if !means.iter().chain(&sds).all(|value| value.is_finite()) || sds.iter().any(|&sd| sd <= 0.0) {
+            return invalid("the means must be numbers and the standard deviations above 0".to_string());
+        }
+        for (i, row) in correlation.iter().enumerate() {
+            if row[i] != 1.0 {
+                return invalid(format!("the correlation of variable {} with itself must be 1", i + 1));
+            }
+            for (j, &r) in row[..i].iter().enumerate() {
+                if !(-1.0..=1.0).contains(&r) {
+                    return invalid(format!("correlations must be from -1 to 1, not {}", r));
+                }
+                if r != correlation[j][i] {
+                    return invalid(format!("the correlation matrix must be symmetric: row {} column {} differs from row {} column {}", i + 1, j + 1, j + 1, i + 1));
+                }
+            }
+        }
+        let Some(cholesky) = cholesky(&correlation) else {
+            return invalid("these correlations can't all hold at once; the matrix isn't positive semidefinite".to_string());
+        };
+        Ok(MultivariateNormal { means, sds, correlation, cholesky })
+    }
+
+    /// 由文本创建:相关矩阵各行用分号或换行分隔,行内用逗号或空白分隔;
+    /// 平均数和标准差可以是一个数字(所有变量相同)、每个变量一个,或留空(0 和 1)
+    pub fn parse(means: &str, sds: &str, correlation: &str) -> Result<Self, RandomGeneratorError> {
+        let correlation = correlation
+            .split([';', '\n'])
+            .filter(|row| !row.trim().is_empty())
+            .map(numbers)
+            .collect::<Result<Vec<_>, _>>()?;
+        let n = correlation.len();
+        let expand = |text: &str, default: f64| -> Result<Vec<f64>, RandomGeneratorError> {
+            let values = numbers(text)?;
+            Ok(match values.as_slice() {
+                [] => vec![default; n],
+                &[value] => vec![value; n],
+                _ => values,
+            })
+        };
+        Self::new(expand(means, 0.0)?, expand(sds, 1.0)?, correlation)
+    }
+
+    /// 变量数
+    pub fn dimensions(&self) -> usize {
+        self.correlation.len()
+    }
+
+    /// 各变量的平均数
+    pub fn means(&self) -> &[f64] {
+        &self.means
+    }
+
+    /// 各变量的标准差
+    pub fn sds(&self) -> &[f64] {
+        &self.sds
+    }
+
+    /// 相关矩阵
+    pub fn correlation(&self) -> &[Vec<f64>] {
+        &self.correlation
+    }
+
+    /// 用种子抽 `count` 组
+    pub fn sample(&self, count: usize, seed: Seed) -> Result<MultivariateSample, RandomGeneratorError> {
+        self.sample_with_progress(count, seed, &AtomicBool::new(false), &mut |_, _| {})
+    }
+
+    /// 用种子抽 `count` 组,并以 `(已抽的值数, 总数)` 回报进度
+    ///
+    /// 每次回报前检查 `cancel`,被置位时返回 `Cancelled`
+    pub fn sample_with_progress(
+        &self,
+        count: usize,
+        seed: Seed,
+        cancel: &AtomicBool,
+        on_progress: &mut dyn FnMut(usize, usize),
+    ) -> Result<MultivariateSample, RandomGeneratorError> {
+        if count == 0 || count.checked_mul(self.dimensions()).is_none_or(|values| values > MAX_SAMPLES) {
+            return Err(RandomGeneratorError::InvalidDistribution(format!("draw 1 to {} values in all", MAX_SAMPLES)));
+        }
+        let mut progress = Progress { cancel, on_progress };
+        let mut rng = seed.rng();
+        let n = self.dimensions();
+        let every = (PROGRESS_INTERVAL / n).max(1);
+        let mut rows = Vec::with_capacity(count);
+        for row in 0..count {
+            if row > 0 && row.is_multiple_of(every) {
+                progress.checkpoint(row * n, count * n)?;
+            }
+            let z: Vec<f64> = (0..n).map(|_| standard_normal(&mut rng)).collect();
+            rows.push(
+                (0..n)
+                    .map(|i| {
+                        let correlated: f64 = (0..=i).map(|k| self.cholesky[i][k] * z[k]).sum();
+                        self.means[i] + self.sds[i] * correlated
+                    })
+                    .collect(),
+            );
+        }
+        Ok(MultivariateSample { normal: self.clone(), rows })
+    }
+}
+
+/// 用逗号或空白分隔的数字
+fn numbers(text: &str) -> Result<Vec<f64>, RandomGeneratorError> {
+    text.split([',', ' ', '\t'])
+        .filter(|value| !value.trim().is_empty())
+        .map(|value| value.trim().parse::<f64>().map_err(|_| RandomGeneratorError::InvalidDistribution(format!("'{}' is not a number", value.trim()))))
+        .collect()
+}
+
+/// 对称矩阵的 Cholesky 因子 L(下三角,A = LLᵀ);不是半正定时为 `None`
+///
+/// 主元为 0(变量完全相关)时这一列取 0
+fn cholesky(matrix: &[Vec<f64>]) -> Option<Vec<Vec<f64>>> {
+    let n = matrix.len();
+    let mut l = vec![vec![0.0; n]; n];
+    for j in 0..n {
+        let pivot = matrix[j][j] - (0..j).map(|k| l[j][k] * l[j][k]).sum::<f64>();
+        if pivot < -PIVOT_TOLERANCE {
+            return None;
+        }
+        let diagonal = pivot.max(0.0).sqrt();
+        l[j][j] = diagonal;
+        for i in j + 1..n {
+            let rest = matrix[i][j] - (0..j).map(|k| l[i][k] * l[j][k]).sum::<f64>();
+            if diagonal > PIVOT_TOLERANCE {
+                l[i][j] = rest / diagonal;
+            } else if rest.abs() > PIVOT_TOLERANCE {
+                return None;
+            }
+        }
+    }
+    Some(l)
+}
+
+impl MultivariateSample {
+    /// 各变量的样本平均数
+    pub fn means(&self) -> Vec<f64> {
+        let n = self.normal.dimensions();
+        (0..n).map(|i| self.rows.iter().map(|row| row[i]).sum::<f64>() / self.rows.len() as f64).collect()
+    }
+
+    /// 各变量的样本标准差;只有一组时为 0
+    pub fn sds(&self) -> Vec<f64> {
+        let means = self.means();
+        let degrees = self.rows.len().saturating_sub(1).max(1) as f64;
+        (0..means.len()).map(|i| (self.rows.iter().map(|row| (row[i] - means[i]).powi(2)).sum::<f64>() / degrees).sqrt()).collect()
+    }
+
+    /// 样本相关矩阵;标准差为 0 的变量与其他变量的相关系数记为 0
+    pub fn correlation(&self) -> Vec<Vec<f64>> {
+        let (means, sds) = (self.means(), self.sds());
+        let n = means.len();
+        let degrees = self.rows.len().saturating_sub(1).max(1) as f64;
+        (0..n)
+            .map(|i| {
+                (0..n)
+                    .map(|j| match i == j {
+                        true => 1.0,
+                        false if sds[i] == 0.0 || sds[j] == 0.0 => 0.0,
+                        false => self.rows.iter().map(|row| (row[i] - means[i]) * (row[j] - means[j])).sum::<f64>() / degrees / (sds[i] * sds[j]),
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// 摘要:各变量的平均数和标准差,以及要求的和样本的相关矩阵
+    pub fn to_text(&self) -> String {
+        let n = self.normal.dimensions();
+        let row = |label: &str, values: &[f64], decimals: usize| {
+            let cells: Vec<String> = values.iter().map(|value| format!("{:>9.*}", decimals, value)).collect();
+            format!("{:<10}{}\n", label, cells.join(" "))
+        };
+        let names: Vec<String> = (1..=n).map(|i| format!("{:>9}", format!("x{}", i))).collect();
+        let mut text = format!("Variables: {}\nRows: {}\n\n{:<10}{}\n", n, self.rows.len(), "", names.join(" "));
+        text.push_str(&row("Mean", &self.normal.means, 4));
+        text.push_str(&row("  sample", &self.means(), 4));
+        text.push_str(&row("SD", &self.normal.sds, 4));
+        text.push_str(&row("  sample", &self.sds(), 4));
+        for (title, matrix) in [("Correlation", self.normal.correlation.clone()), ("Sample correlation", self.correlation())] {
+            text.push_str(&format!("\n{}\n", title));
+            for (i, values) in matrix.iter().enumerate() {
+                text.push_str(&row(&format!("x{}", i + 1), values, 3));
+            }
+        }
+        text
+    }
+
+    /// 每组一行的 CSV,表头是 `x1,x2 …`
+    pub fn to_csv(&self) -> String {
+        let names: Vec<String> = (1..=self.normal.dimensions()).map(|i| format!("x{}", i)).collect();
+        let mut csv = names.join(",");
+        csv.push('\n');
+        for row in &self.rows {
+            let values: Vec<String> = row.iter().map(f64::to_string).collect();
+            csv.push_str(&values.join(","));
+            csv.push('\n');
+        }
+        csv
+    }
+}
@@ -0,0 +1,201 @@
+use rand::Rng;
+use std::collections::HashSet;
+use std::fmt;
+use std::net::{Ipv4Addr, Ipv6Addr};
+use std::str::FromStr;
+
+use crate::{RandomGeneratorError, Seed};
+
+/// 每要一个不重复的地址,平均最多尝试的次数
+const UNIQUE_ATTEMPTS: usize = 50;
+
+/// 网络地址的种类
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AddressKind {
+    /// 48 位 MAC 地址
+    #[default]
+    Mac,
+    /// IPv4 地址
+    Ipv4,
+    /// IPv6 地址
+    Ipv6,
+}
+
+impl AddressKind {
+    /// 所有种类
+    pub const ALL: [AddressKind; 3] = [AddressKind::Mac, AddressKind::Ipv4, AddressKind::Ipv6];
+
+    fn bits(self) -> u32 {
+        match self {
+            AddressKind::Mac => 48,
+            AddressKind::Ipv4 => 32,
+            AddressKind::Ipv6 => 128,
+        }
+    }
+}
+
+impl fmt::Display for AddressKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AddressKind::Mac => write!(f, "MAC"),
+            AddressKind::Ipv4 => write!(f, "IPv4"),
+            AddressKind::Ipv6 => write!(f, "IPv6"),
+        }
+    }
+}
+
+impl FromStr for AddressKind {
+    type Err = RandomGeneratorError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.trim();
+        AddressKind::ALL
+            .into_iter()
+            .find(|kind| kind.to_string().eq_ignore_ascii_case(name))
+            .ok_or_else(|| RandomGeneratorError::InvalidAddress(format!("unknown address kind '{}'", name)))
+    }
+}
+
+/// 随机网络地址:可指定厂商前缀的 MAC 地址、某个 CIDR 网段中的 IPv4 或 IPv6 地址
+///
+/// 不指定厂商前缀的 MAC 地址是本地管理的单播地址,不会与真实网卡冲突;
+/// IPv4 网段中不生成网络地址和广播地址(`/31`、`/32` 除外)
+///
+/// ```
+/// use random_generator_core::{AddressKind, AddressSpec, Seed};
+///
+/// let spec = AddressSpec::parse(AddressKind::Ipv4, "192.168.1.0/24")?;
+/// assert_eq!(spec.combinations(), 254);
+/// let addresses = spec.generate(3, true, Seed::from([1; 32]))?;
+/// assert!(addresses.iter().all(|address| address.starts_with("192.168.1.")));
+///
+/// let vendor = AddressSpec::parse(AddressKind::Mac, "00:1A:2B")?;
+/// assert!(vendor.generate(1, true, Seed::from([1; 32]))?[0].starts_with("00:1a:2b:"));
+/// # Ok::<(), random_generator_core::RandomGeneratorError>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AddressSpec {
+    kind: AddressKind,
+    /// 固定的高位,其余位为 0
+    network: u128,
+    /// 固定的高位个数
+    prefix_len: u32,
+}
+
+impl AddressSpec {
+    /// `text` 对 MAC 是厂商前缀,如 `00:1A:2B`,对 IP 是网段,如 `10.0.0.0/8`、`2001:db8::/32`;
+    /// 为空时不限定
+    ///
+    /// 网段中超出前缀长度的位会被忽略,不写前缀长度时只有这一个地址
+    pub fn parse(kind: AddressKind, text: &str) -> Result<Self, RandomGeneratorError> {
+        let text = text.trim();
+        let invalid = |message: &str| RandomGeneratorError::InvalidAddress(format!("'{}' {}", text, message));
+        let (network, prefix_len) = match kind {
+            _ if text.is_empty() => (0, 0),
+            AddressKind::Mac => {
+                let digits: String = text.chars().filter(|c| !matches!(c, ':' | '-' | '.')).collect();
+                let hex = digits.chars().all(|c| c.is_ascii_hexdigit());
+                if !hex || digits.is_empty() || !digits.len().is_multiple_of(2) || digits.len() > 10 {
+                    return Err(invalid("is not a vendor prefix like 00:1A:2B"));
+                }
+                let prefix = u128::from_str_radix(&digits, 16).unwrap();
+                let prefix_len = digits.len() as u32 * 4;
+                (prefix << (48 - prefix_len), prefix_len)
+            }
+            AddressKind::Ipv4 | AddressKind::Ipv6 => {
+                let (address, length) = text.split_once('/').unwrap_or((text, ""));
+                let network = match kind {
+                    AddressKind::Ipv4 => address.parse::<Ipv4Addr>().map(|address| u128::from(u32::from(address))),
+                    _ => address.parse::<Ipv6Addr>().map(u128::from),
+                }
+                .map_err(|_| invalid("is not a CIDR block like 10.0.0.0/8"))?;
+                let prefix_len = match length {
+                    "" => kind.bits(),
+                    length => length
+                        .parse::<u32>()
+                        .ok()
+                        .filter(|&length| length <= kind.bits())
+                        .ok_or_else(|| invalid("has an invalid prefix length"))?,
+                };
+                (network & !host_mask(kind.bits() - prefix_len), prefix_len)
+            }
+        };
+        Ok(AddressSpec { kind, network, prefix_len })
+    }
+
+    /// 地址的种类
+    pub fn kind(&self) -> AddressKind {
+        self.kind
+    }
+
+    /// 能生成多少个不同的地址,超过 `u128` 时取最大值
+    pub fn combinations(&self) -> u128 {
+        let host_bits = self.host_bits();
+        let all = 1u128.checked_shl(host_bits).unwrap_or(u128::MAX);
+        match self.kind {
+            // 本地管理位和组播位是固定的
+            AddressKind::Mac if self.prefix_len == 0 => all >> 2,
+            AddressKind::Ipv4 if host_bits >= 2 => all - 2,
+            _ => all,
+        }
+    }
+
+    /// 随机生成一个地址
+    pub fn sample(&self, rng: &mut impl Rng) -> String {
+        let host_bits = self.host_bits();
+        let host = match self.kind {
+            AddressKind::Ipv4 if host_bits >= 2 => rng.gen_range(1..host_mask(host_bits)),
+            _ => rng.gen::<u128>() & host_mask(host_bits),
+        };
+        let mut address = self.network | host;
+        if self.kind == AddressKind::Mac && self.prefix_len == 0 {
+            // 第一个字节的最低位为 0(单播),次低位为 1(本地管理)
+            address = address & !(1 << 40) | 1 << 41;
+        }
+        match self.kind {
+            AddressKind::Mac => {
+                let bytes = address.to_be_bytes();
+                bytes[10..].iter().map(|byte| format!("{:02x}", byte)).collect::<Vec<_>>().join(":")
+            }
+            AddressKind::Ipv4 => Ipv4Addr::from(address as u32).to_string(),
+            AddressKind::Ipv6 => Ipv6Addr::from(address).to_string(),
+        }
+    }
+
+    /// 用种子生成 `count` 个地址;`unique` 时保证这一批中没有重复
+    pub fn generate(&self, count: usize, unique: bool, seed: Seed) -> Result<Vec<String>, RandomGeneratorError> {
+        let mut rng = seed.rng();
+        if !unique {
+            return Ok((0..count).map(|_| self.sample(&mut rng)).collect());
+        }
+        if count as u128 > self.combinations() {
+            return Err(RandomGeneratorError::InvalidAddress(format!(
+                "only {} different addresses are possible",
+                self.combinations()
+            )));
+        }
+        let mut seen = HashSet::with_capacity(count);
+        let mut addresses = Vec::with_capacity(count);
+        let mut attempts = count.saturating_mul(UNIQUE_ATTEMPTS).max(1000);
+        while addresses.len() < count {
+            if attempts == 0 {
+                return Err(RandomGeneratorError::InvalidAddress(format!("could not find {} different addresses", count)));
+            }
+            attempts -= 1;
+            let address = self.sample(&mut rng);
+            if seen.insert(address.clone()) {
+                addresses.push(address);
+            }
+        }
+        Ok(addresses)
+    }
+
+    fn host_bits(&self) -> u32 {
+        self.kind.bits() - self.prefix_len
+    }
+}
+
+/// 低 `bits` 位全为 1
+fn host_mask(bits: u32) -> u128 {
+    1u128.checked_shl(bits).map_or(u128::MAX, |value| value - 1)
+}
@@ -0,0 +1,188 @@
+use rand::distributions::uniform::SampleUniform;
+use std::fmt;
+use std::iter::Sum;
+use std::str::FromStr;
+
+/// 生成器可以输出的数字类型
+///
+/// 已为 `i64`、`u64`、`i128` 和 `f64` 实现。整数是离散的,可以不重复地抽完整个区间;
+/// 浮点数是连续的,不重复抽取时最多抽到区间内可表示的值的个数。
+pub trait Number: SampleUniform + Copy + PartialOrd + Sum + FromStr + fmt::Display + fmt::Debug + Send + Sync + 'static {
+    /// 默认区间下界
+    const DEFAULT_LOWER: Self;
+    /// 默认区间上界
+    const DEFAULT_UPPER: Self;
+
+    /// 是否是可以使用的值(浮点数要求有限)
+    fn is_finite(self) -> bool {
+        true
+    }
+
+    /// `lower..=upper` 是否是可以抽取的区间
+    fn is_valid_range(lower: Self, upper: Self) -> bool {
+        lower.is_finite() && upper.is_finite() && lower <= upper
+    }
+
+    /// 区间内不同取值的个数,超出 `u128` 时取 `u128::MAX`;连续类型返回 `None`
+    fn values_between(lower: Self, upper: Self) -> Option<u128>;
+
+    /// 区间内能抽到的不同值的个数:离散类型同 `values_between`,浮点数是可表示的值的个数
+    fn representable_between(lower: Self, upper: Self) -> u128 {
+        Self::values_between(lower, upper).unwrap_or(u128::MAX)
+    }
+
+    /// 从 `lower` 起的第 `index` 个值;浮点数是第 `index` 个可表示的值
+    fn nth_from(lower: Self, index: u128) -> Self;
+
+    /// 去重用的键,不同的值对应不同的键
+    fn key(self) -> u128;
+
+    /// 转换为浮点数,用于统计
+    fn as_f64(self) -> f64;
+
+    /// 由整数转换,超出类型范围时为 `None`
+    fn from_i64(value: i64) -> Option<Self>;
+
+    /// 加法,结果超出类型范围时为 `None`;用于 [`Transform`](crate::Transform)
+    fn checked_add(self, other: Self) -> Option<Self>;
+
+    /// 乘法,结果超出类型范围时为 `None`
+    fn checked_mul(self, other: Self) -> Option<Self>;
+
+    /// 绝对值,超出类型范围时为 `None`
+    fn checked_abs(self) -> Option<Self>;
+
+    /// 除以 `modulus` 的非负余数,`modulus` 为 0 时为 `None`
+    fn checked_rem_euclid(self, modulus: Self) -> Option<Self>;
+
+    /// 最接近的 `step`(大于 0)的倍数,正好在中间时取较大的;超出类型范围时为 `None`
+    fn checked_round_to(self, step: Self) -> Option<Self>;
+}
+
+macro_rules! impl_integer {
+    ($($t:ty => $unsigned:ty),*) => {$(
+        impl Number for $t {
+            const DEFAULT_LOWER: Self = 0;
+            const DEFAULT_UPPER: Self = 1024;
+
+            fn values_between(lower: Self, upper: Self) -> Option<u128> {
+                // 回绕相减再转为无符号数,得到的就是准确的距离
+                Some((upper.wrapping_sub(lower) as $unsigned as u128).saturating_add(1))
+            }
+
+            fn nth_from(lower: Self, index: u128) -> Self {
+                lower.wrapping_add(index as $t)
+            }
+
+            fn key(self) -> u128 {
+                self as $unsigned as u128
+            }
+
+            fn as_f64(self) -> f64 {
+                self as f64
+            }
+
+            fn from_i64(value: i64) -> Option<Self> {
+                <$t>::try_from(value).ok()
+            }
+
+            fn checked_add(self, other: Self) -> Option<Self> {
+                <$t>::checked_add(self, other)
+            }
+
+            fn checked_mul(self, other: Self) -> Option<Self> {
+                <$t>::checked_mul(self, other)
+            }
+
+            fn checked_abs(self) -> Option<Self> {
+                // 经过 i128,无符号类型也一样
+                <$t>::try_from(i128::try_from(self).ok()?.checked_abs()?).ok()
+            }
+
+            fn checked_rem_euclid(self, modulus: Self) -> Option<Self> {
+                <$t>::checked_rem_euclid(self, modulus)
+            }
+
+            fn checked_round_to(self, step: Self) -> Option<Self> {
+                let (value, step) = (i128::try_from(self).ok()?, i128::try_from(step).ok()?);
+                if step <= 0 {
+                    return None;
+                }
+                let remainder = value.rem_euclid(step);
+                let down = value.checked_sub(remainder)?;
+                let rounded = if remainder >= step - remainder { down.checked_add(step)? } else { down };
+                <$t>::try_from(rounded).ok()
+            }
+        }
+    )*};
+}
+
+impl_integer!(i64 => u64, u64 => u64, i128 => u128);
+
+/// 浮点数按大小排列的序号,相邻的可表示的值相差 1,`-0.0` 和 `0.0` 都是 0
+fn ordered(value: f64) -> i128 {
+    let bits = value.to_bits();
+    let magnitude = i128::from(bits & !(1 << 63));
+    if bits >> 63 == 1 { -magnitude } else { magnitude }
+}
+
+impl Number for f64 {
+    const DEFAULT_LOWER: Self = 0.0;
+    const DEFAULT_UPPER: Self = 1.0;
+
+    fn is_finite(self) -> bool {
+        f64::is_finite(self)
+    }
+
+    fn is_valid_range(lower: Self, upper: Self) -> bool {
+        // 区间宽度溢出为无穷大时无法均匀抽取
+        lower.is_finite() && upper.is_finite() && (upper - lower).is_finite() && lower <= upper
+    }
+
+    fn values_between(_lower: Self, _upper: Self) -> Option<u128> {
+        None
+    }
+
+    fn representable_between(lower: Self, upper: Self) -> u128 {
+        u128::try_from(ordered(upper) - ordered(lower) + 1).unwrap_or(0)
+    }
+
+    fn nth_from(lower: Self, index: u128) -> Self {
+        let position = ordered(lower) + index as i128;
+        let bits = position.unsigned_abs() as u64;
+        if position < 0 { f64::from_bits(bits | 1 << 63) } else { f64::from_bits(bits) }
+    }
+
+    fn key(self) -> u128 {
+        // 加 0.0 把 -0.0 变成 0.0,两者是同一个值
+        (self + 0.0).to_bits() as u128
+    }
+
+    fn as_f64(self) -> f64 {
+        self
+    }
+
+    fn from_i64(value: i64) -> Option<Self> {
+        Some(value as f64)
+    }
+
+    fn checked_add(self, other: Self) -> Option<Self> {
+        Some(self + other).filter(|sum| sum.is_finite())
+    }
+
+    fn checked_mul(self, other: Self) -> Option<Self> {
+        Some(self * other).filter(|product| product.is_finite())
+    }
+
+    fn checked_abs(self) -> Option<Self> {
+        Some(self.abs())
+    }
+
+    fn checked_rem_euclid(self, modulus: Self) -> Option<Self> {
+        Some(self.rem_euclid(modulus)).filter(|remainder| remainder.is_finite())
+    }
+
+    fn checked_round_to(self, step: Self) -> Option<Self> {
+        Some((self / step + 0.5).floor() * step).filter(|rounded| rounded.is_finite() && step > 0.0)
+    }
+}
@@ -0,0 +1,174 @@
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::collections::HashSet;
+
+use crate::matching::{check_names, match_all, parse_names, parse_pairs, position};
+use crate::{RandomGeneratorError, Seed};
+
+/// 先尝试这么多次完全随机的分配,都不满足限制时再用匹配算法找一个
+const SHUFFLE_ATTEMPTS: usize = 1000;
+
+/// 有人要多分一项时,匹配算法最多换这么多种“谁多分一项”的分法
+const MATCH_ATTEMPTS: usize = 100;
+
+/// 分给一个人的一项
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pair {
+    /// 人,例如审稿人
+    pub person: String,
+    /// 分给这个人的一项,例如论文
+    pub item: String,
+}
+
+/// 两个名单之间的随机分配,例如把论文分给审稿人
+///
+/// 每一项恰好分给一个人,每人分到的项数最多相差 1:两个名单一样长时就是一对一,
+/// 项比人多时每人分到几项,项比人少时有人分不到。可以限制某人不能分到某项
+///
+/// ```
+/// use random_generator_core::{Pairing, Seed};
+///
+/// let mut pairing = Pairing::parse("Alice\nBob", "Paper 1\nPaper 2\nPaper 3\nPaper 4\nPaper 5")?;
+/// pairing.forbid("Alice", "Paper 3")?;
+/// let pairs = pairing.draw(Seed::from([6; 32]))?;
+/// assert_eq!(pairs.len(), 5);
+/// let alice = pairs.iter().filter(|pair| pair.person == "Alice").count();
+/// assert!(alice == 2 || alice == 3);
+/// assert!(!pairs.iter().any(|pair| pair.person == "Alice" && pair.item == "Paper 3"));
+/// # Ok::<(), random_generator_core::RandomGeneratorError>(())
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Pairing {
+    people: Vec<String>,
+    items: Vec<String>,
+    /// 不能分配的人和项的位置
+    forbidden: HashSet<(usize, usize)>,
+}
+
+impl Pairing {
+    /// 两个名单都不能为空,名单中的名字不能为空或重复
+    pub fn new(people: Vec<String>, items: Vec<String>) -> Result<Self, RandomGeneratorError> {
+        check_names(&people)?;
+        check_names(&items)?;
+        if people.is_empty() || items.is_empty() {
+            return Err(RandomGeneratorError::InvalidDraw("both lists need at least one name".to_string()));
+        }
+        Ok(Pairing { people, items, forbidden: HashSet::new() })
+    }
+
+    /// 两个名单都是每行一个名字,跳过空行
+    pub fn parse(people: &str, items: &str) -> Result<Self, RandomGeneratorError> {
+        Self::new(parse_names(people), parse_names(items))
+    }
+
+    /// 让 `person` 不会分到 `item`;名字不区分大小写
+    pub fn forbid(&mut self, person: &str, item: &str) -> Result<(), RandomGeneratorError> {
+        let pair = (position(&self.people, person)?, position(&self.items, item)?);
+        self.forbidden.insert(pair);
+        Ok(())
+    }
+
+    /// 每行一个人和一项,用逗号分隔,例如 `Alice, Paper 3`,跳过空行
+    pub fn forbid_pairs(&mut self, text: &str) -> Result<(), RandomGeneratorError> {
+        for (person, item) in parse_pairs(text)? {
+            self.forbid(person, item)?;
+        }
+        Ok(())
+    }
+
+    /// 人的名单
+    pub fn people(&self) -> &[String] {
+        &self.people
+    }
+
+    /// 要分配的项
+    pub fn items(&self) -> &[String] {
+        &self.items
+    }
+
+    /// 用种子分配,按人的名单顺序返回,同一人的项按项的名单顺序
+    ///
+    /// 限制不多时在所有满足限制的分配中均匀地抽取;限制多到随机分配很难满足时,
+    /// 改为按随机顺序寻找一个满足限制的分配。找不到时返回错误
+    pub fn draw(&self, seed: Seed) -> Result<Vec<Pair>, RandomGeneratorError> {
+        let mut rng = seed.rng();
+        let mut slots = Vec::new();
+        let mut found = false;
+        for _ in 0..SHUFFLE_ATTEMPTS {
+            slots = self.slots(&mut rng);
+            if slots.iter().enumerate().all(|(item, &person)| self.allowed(person, item)) {
+                found = true;
+                break;
+            }
+        }
+        if !found {
+            // 每人的项数只有一种分法时换分法也没有用
+            let attempts = if self.items.len().is_multiple_of(self.people.len()) { 1 } else { MATCH_ATTEMPTS };
+            let matched = (0..attempts).find_map(|_| {
+                let slots = self.slots(&mut rng);
+                let candidates = (0..self.items.len())
+                    .map(|item| (0..slots.len()).filter(|&slot| self.allowed(slots[slot], item)).collect())
+                    .collect();
+                match_all(candidates, slots.len(), &mut rng)
+                    .map(|chosen| chosen.into_iter().map(|slot| slots[slot]).collect::<Vec<_>>())
+            });
+            slots = matched.ok_or_else(|| {
+                RandomGeneratorError::InvalidDraw("the restrictions leave no even way to hand out every item".to_string())
+            })?;
+        }
+
+        let mut pairs: Vec<(usize, usize)> = slots.into_iter().enumerate().map(|(item, person)| (person, item)).collect();
+        pairs.sort_unstable();
+        Ok(pairs
+            .into_iter()
+            .map(|(person, item)| Pair { person: self.people[person].clone(), item: self.items[item].clone() })
+            .collect())
+    }
+
+    /// 每人一行,例如 `Alice: Paper 1, Paper 4`;没分到的人也列出
+    pub fn to_text(&self, pairs: &[Pair]) -> String {
+        self.people
+            .iter()
+            .map(|person| {
+                let items: Vec<&str> = pairs.iter().filter(|pair| &pair.person == person).map(|pair| pair.item.as_str()).collect();
+                format!("{}: {}\n", person, if items.is_empty() { "-".to_string() } else { items.join(", ") })
+            })
+            .collect()
+    }
+
+    /// 每项一行的 CSV,带表头 `person,item`
+    pub fn to_csv(pairs: &[Pair]) -> String {
+        let cell = |text: &str| {
+            if text.contains([',', '"', '\n', '\r']) {
+                format!("\"{}\"", text.replace('"', "\"\""))
+            } else {
+                text.to_string()
+            }
+        };
+        let mut csv = String::from("person,item\n");
+        for pair in pairs {
+            csv.push_str(&format!("{},{}\n", cell(&pair.person), cell(&pair.item)));
+        }
+        csv
+    }
+
+    /// 随机的一种分配:第 `i` 项分给 `slots[i]`
+    ///
+    /// 随机选出多分一项的人,每人按项数占几个位置,再打乱位置
+    fn slots(&self, rng: &mut impl Rng) -> Vec<usize> {
+        let (each, extra) = (self.items.len() / self.people.len(), self.items.len() % self.people.len());
+        let mut order: Vec<usize> = (0..self.people.len()).collect();
+        order.shuffle(rng);
+        let mut slots: Vec<usize> = order
+            .into_iter()
+            .enumerate()
+            .flat_map(|(rank, person)| std::iter::repeat_n(person, each + usize::from(rank < extra)))
+            .collect();
+        slots.shuffle(rng);
+        slots
+    }
+
+    fn allowed(&self, person: usize, item: usize) -> bool {
+        !self.forbidden.contains(&(person, item))
+    }
+}
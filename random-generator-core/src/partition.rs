@@ -0,0 +1,174 @@
+use rand::{Rng, RngCore};
+
+use crate::source::UniqueStrategy;
+use crate::{Config, GeneratorMode, GeneratorSource, Number, Progress, RandomGeneratorError, SourceInput};
+
+/// 有上限时,拒绝超出上限的拆分最多重抽几次,之后把超出的部分分给还有余量的份
+const PARTITION_ATTEMPTS: usize = 20;
+
+/// 把 `total` 拆成 `num_to_generate` 份,每份在 `lower_bound..=upper_bound` 中
+///
+/// 例如把 100 分拆给 7 个人,每人 0 到 100 分。上限不起作用时,所有拆法的机会相同;
+/// 上限很紧、重抽几次都有份超出时,超出的部分随机分给还有余量的份,结果仍然满足上下限。
+/// 各份可以相等,不受“允许重复”的影响;只适用于整数类型
+///
+/// ```
+/// use random_generator_core::{Generator, GeneratorConfig, GeneratorMode, PartitionSource};
+///
+/// let mut generator = Generator::with_source(Box::new(PartitionSource { total: 100 }), GeneratorConfig {
+///     mode: GeneratorMode::PARTITION,
+///     num_to_generate: 7,
+///     lower_bound: 5,
+///     upper_bound: 30,
+///     ..Default::default()
+/// })?;
+/// generator.generate_numbers()?;
+/// let parts = generator.get_numbers();
+/// assert_eq!(parts.len(), 7);
+/// assert_eq!(parts.iter().sum::<i64>(), 100);
+/// assert!(parts.iter().all(|part| (5..=30).contains(part)));
+/// # Ok::<(), random_generator_core::RandomGeneratorError>(())
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct PartitionSource<T: Number> {
+    /// 要拆开的总和
+    pub total: T,
+}
+
+impl<T: Number> Default for PartitionSource<T> {
+    fn default() -> Self {
+        Self { total: T::DEFAULT_UPPER }
+    }
+}
+
+/// 换算成以下界为零点的问题:`parts` 份,每份 `0..=room`,加起来是 `spare`
+struct Offsets {
+    parts: usize,
+    room: u128,
+    spare: u128,
+}
+
+impl<T: Number> PartitionSource<T> {
+    fn offsets(&self, config: &Config<T>) -> Result<Offsets, RandomGeneratorError> {
+        let (lower, upper, total) = (config.lower_bound, config.upper_bound, self.total);
+        let parts = config.num_to_generate;
+        let (Some(room), Some(lower_value), Some(total_value)) =
+            (T::values_between(lower, upper), signed(lower), signed(total))
+        else {
+            return Err(RandomGeneratorError::InvalidPartition("a partition needs whole numbers".to_string()));
+        };
+        if parts == 0 {
+            return Err(RandomGeneratorError::InvalidPartition("split into at least one part".to_string()));
+        }
+        let too_large = || RandomGeneratorError::InvalidPartition("the numbers are too large to split".to_string());
+        // 每份先拿下界,剩下的再分
+        let spare = (parts as i128).checked_mul(lower_value).and_then(|floor| total_value.checked_sub(floor)).ok_or_else(too_large)?;
+        if spare < 0 {
+            return Err(RandomGeneratorError::InvalidPartition(format!("{} parts of at least {} add up to more than {}", parts, lower, total)));
+        }
+        let room = room - 1;
+        if room.checked_mul(parts as u128).is_some_and(|most| most < spare as u128) {
+            return Err(RandomGeneratorError::InvalidPartition(format!("{} parts of at most {} can't add up to {}", parts, upper, total)));
+        }
+        let spare = spare as u128;
+        spare.checked_add(parts as u128).ok_or_else(too_large)?;
+        Ok(Offsets { parts, room, spare })
+    }
+}
+
+/// 整数类型的值,超出 `i128` 或不是整数类型时为 `None`
+fn signed<T: Number>(value: T) -> Option<i128> {
+    let zero = T::from_i64(0)?;
+    if value >= zero {
+        i128::try_from(T::values_between(zero, value)? - 1).ok()
+    } else {
+        i128::try_from(T::values_between(value, zero)? - 1).ok().map(|distance| -distance)
+    }
+}
+
+/// 把 `spare` 拆成 `parts` 个非负整数,每种拆法的机会相同
+///
+/// 隔板法:在 `spare + parts - 1` 个位置中不重复地选 `parts - 1` 个放隔板,隔板之间的空位数就是各份
+fn composition(spare: u128, parts: usize, rng: &mut dyn RngCore, progress: &mut Progress<'_>) -> Result<Vec<u128>, RandomGeneratorError> {
+    let slots = spare + parts as u128 - 1;
+    let bars = parts - 1;
+    let mut cuts = UniqueStrategy::for_range(bars, slots).indices(rng, slots, bars, progress)?;
+    cuts.sort_unstable();
+    let mut sizes = Vec::with_capacity(parts);
+    let mut start = 0;
+    for cut in cuts {
+        sizes.push(cut - start);
+        start = cut + 1;
+    }
+    sizes.push(slots - start);
+    Ok(sizes)
+}
+
+impl<T: Number> GeneratorSource<T> for PartitionSource<T> {
+    fn mode(&self) -> GeneratorMode {
+        GeneratorMode::PARTITION
+    }
+
+    fn input(&self) -> SourceInput {
+        SourceInput::Bounds
+    }
+
+    fn parameters(&self) -> Vec<(&'static str, String)> {
+        vec![("total", self.total.to_string())]
+    }
+
+    fn validate(&self, config: &Config<T>) -> Vec<RandomGeneratorError> {
+        if !T::is_valid_range(config.lower_bound, config.upper_bound) {
+            return vec![RandomGeneratorError::InvalidBounds];
+        }
+        let mut problems: Vec<_> = self.offsets(config).err().into_iter().collect();
+        if config.allow_duplicates && (config.max_repeats.is_some() || config.max_streak.is_some()) {
+            problems.push(RandomGeneratorError::InvalidPartition("repeat limits don't apply to a partition".to_string()));
+        }
+        problems
+    }
+
+    /// 各份本来就可以相等,个数不受不同值个数的限制
+    fn available(&self, _config: &Config<T>) -> Option<u128> {
+        None
+    }
+
+    fn generate(
+        &self,
+        config: &Config<T>,
+        rng: &mut dyn RngCore,
+        progress: &mut Progress<'_>,
+    ) -> Result<Vec<T>, RandomGeneratorError> {
+        let Offsets { parts, room, spare } = self.offsets(config)?;
+        let mut sizes = composition(spare, parts, rng, progress)?;
+        for _ in 1..PARTITION_ATTEMPTS {
+            if sizes.iter().all(|&size| size <= room) {
+                break;
+            }
+            sizes = composition(spare, parts, rng, progress)?;
+        }
+
+        // 每轮把超出上限的部分随机分给还有余量的份;每轮至少有一份被填满或全部分完,最多 `parts` 轮
+        loop {
+            let mut excess = 0;
+            for size in sizes.iter_mut().filter(|size| **size > room) {
+                excess += *size - room;
+                *size = room;
+            }
+            if excess == 0 {
+                break;
+            }
+            let open: Vec<usize> = (0..parts).filter(|&i| sizes[i] < room).collect();
+            let extra = composition(excess, open.len(), rng, progress)?;
+            for (i, extra) in open.into_iter().zip(extra) {
+                sizes[i] += extra;
+            }
+        }
+
+        // 填满的份集中在前面轮次选中的位置,最后打乱一次,让每个位置的机会相同
+        for i in (1..sizes.len()).rev() {
+            sizes.swap(i, rng.gen_range(0..=i));
+        }
+        Ok(sizes.into_iter().map(|size| T::nth_from(config.lower_bound, size)).collect())
+    }
+}
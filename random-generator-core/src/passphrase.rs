@@ -0,0 +1,176 @@
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::collections::HashSet;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::{RandomGeneratorError, Seed};
+
+/// 自带的词表:1296 个简短的常用英文单词,相当于掷 4 次骰子选一个词
+const BUNDLED_WORDS: &str = include_str!("wordlist.txt");
+
+/// 口令中单词的大小写
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Capitalization {
+    /// 全部小写
+    #[default]
+    Lower,
+    /// 每个单词首字母大写
+    Title,
+    /// 全部大写
+    Upper,
+    /// 每个单词随机选择小写或首字母大写,每个词多 1 比特熵
+    Random,
+}
+
+impl Capitalization {
+    /// 所有可选的大小写方式
+    pub const ALL: [Capitalization; 4] =
+        [Capitalization::Lower, Capitalization::Title, Capitalization::Upper, Capitalization::Random];
+
+    fn apply(self, word: &str, rng: &mut impl Rng) -> String {
+        let title = |word: &str| {
+            let mut chars = word.chars();
+            chars.next().map_or_else(String::new, |first| first.to_uppercase().chain(chars).collect())
+        };
+        match self {
+            Capitalization::Lower => word.to_string(),
+            Capitalization::Title => title(word),
+            Capitalization::Upper => word.to_uppercase(),
+            Capitalization::Random if rng.gen() => title(word),
+            Capitalization::Random => word.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for Capitalization {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Capitalization::Lower => write!(f, "lower"),
+            Capitalization::Title => write!(f, "title"),
+            Capitalization::Upper => write!(f, "upper"),
+            Capitalization::Random => write!(f, "random"),
+        }
+    }
+}
+
+impl FromStr for Capitalization {
+    type Err = RandomGeneratorError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.trim();
+        Capitalization::ALL
+            .into_iter()
+            .find(|case| case.to_string().eq_ignore_ascii_case(name))
+            .ok_or_else(|| RandomGeneratorError::InvalidCode(format!("unknown capitalization '{}'", name)))
+    }
+}
+
+/// 生成口令用的词表,其中的词互不相同且都是小写
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Wordlist {
+    words: Vec<String>,
+}
+
+impl Wordlist {
+    /// 自带的词表
+    pub fn bundled() -> Self {
+        Wordlist { words: BUNDLED_WORDS.lines().map(String::from).collect() }
+    }
+
+    /// 读取用户的词表:每行一个词,也可以是 `11111 word` 这样带骰子编号的 Diceware 格式
+    ///
+    /// 重复的词只保留一个,至少要有 2 个不同的词
+    pub fn parse(text: &str) -> Result<Self, RandomGeneratorError> {
+        let mut words: Vec<String> = Vec::new();
+        let mut seen = HashSet::new();
+        for line in text.lines() {
+            let Some(word) = line.split_whitespace().last() else {
+                continue;
+            };
+            let word = word.to_lowercase();
+            if seen.insert(word.clone()) {
+                words.push(word);
+            }
+        }
+        match words.len() {
+            0 => Err(RandomGeneratorError::EmptyList),
+            1 => Err(RandomGeneratorError::InvalidCode("a wordlist needs at least 2 different words".to_string())),
+            _ => Ok(Wordlist { words }),
+        }
+    }
+
+    /// 所有的词
+    pub fn words(&self) -> &[String] {
+        &self.words
+    }
+
+    /// 词的个数
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    /// 词表是否为空;[`Wordlist::bundled`] 和 [`Wordlist::parse`] 都不会返回空词表
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+}
+
+/// Diceware 式的口令:从词表中随机选几个词,用分隔符连接
+///
+/// ```
+/// use random_generator_core::{Capitalization, PassphraseSpec, Seed, Wordlist};
+///
+/// let spec = PassphraseSpec { words: 5, separator: "-".to_string(), capitalization: Capitalization::Title };
+/// let wordlist = Wordlist::bundled();
+/// let phrases = spec.generate(&wordlist, 2, Seed::from([3; 32]))?;
+/// assert_eq!(phrases[0].split('-').count(), 5);
+/// assert!(spec.entropy_bits(&wordlist) > 51.0); // 5 × log2(1296)
+/// # Ok::<(), random_generator_core::RandomGeneratorError>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PassphraseSpec {
+    /// 每个口令的词数
+    pub words: usize,
+    /// 词之间的分隔符,可以为空
+    pub separator: String,
+    /// 大小写
+    pub capitalization: Capitalization,
+}
+
+impl Default for PassphraseSpec {
+    fn default() -> Self {
+        PassphraseSpec { words: 6, separator: " ".to_string(), capitalization: Capitalization::Lower }
+    }
+}
+
+impl PassphraseSpec {
+    /// 用种子从词表生成 `count` 个口令
+    pub fn generate(&self, wordlist: &Wordlist, count: usize, seed: Seed) -> Result<Vec<String>, RandomGeneratorError> {
+        if self.words == 0 {
+            return Err(RandomGeneratorError::InvalidCode("a passphrase needs at least 1 word".to_string()));
+        }
+        if wordlist.is_empty() {
+            return Err(RandomGeneratorError::EmptyList);
+        }
+        let mut rng = seed.rng();
+        Ok((0..count)
+            .map(|_| {
+                (0..self.words)
+                    .map(|_| {
+                        let word = wordlist.words.choose(&mut rng).unwrap();
+                        self.capitalization.apply(word, &mut rng)
+                    })
+                    .collect::<Vec<_>>()
+                    .join(&self.separator)
+            })
+            .collect())
+    }
+
+    /// 假设对方知道词表和所有设置时,一个口令的熵(比特)
+    pub fn entropy_bits(&self, wordlist: &Wordlist) -> f64 {
+        let per_word = (wordlist.len() as f64).log2() + if self.capitalization == Capitalization::Random { 1.0 } else { 0.0 };
+        self.words as f64 * per_word
+    }
+}
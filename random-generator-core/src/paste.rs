@@ -0,0 +1,127 @@
+use crate::export::csv_row;
+use crate::{LabelMap, Number, RandomGeneratorError};
+
+/// 从剪贴板粘贴的一列数字或名字,例如从电子表格复制的一列
+///
+/// 有制表符时按电子表格的格读,否则按 CSV 读(支持带引号的格);
+/// 只有一行时整行就是列表。取第一列全是数字的列,它的第一格不是数字时当作表头跳过;
+/// 没有这样的列时,第一列的格都当作名字
+///
+/// ```
+/// use random_generator_core::PastedList;
+///
+/// let pasted = PastedList::<i64>::parse("Name\tTicket\nAnn\t104\nBo\t1,250\n")?;
+/// assert_eq!(pasted, PastedList::Numbers(vec![104, 1250]));
+/// assert_eq!(pasted.to_list_input(), "104, 1250");
+///
+/// let names = PastedList::<i64>::parse("\"Lee, Ann\"\nBo\n")?;
+/// assert_eq!(names.to_list_input(), "1, 2");
+/// assert_eq!(names.labels().get(1), Some("Lee, Ann"));
+/// # Ok::<(), random_generator_core::RandomGeneratorError>(())
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum PastedList<T: Number> {
+    /// 一列数字,按行的先后
+    Numbers(Vec<T>),
+    /// 一列名字,按行的先后;抽取时用 1 到名字个数的编号
+    Names(Vec<String>),
+}
+
+impl<T: Number> PastedList<T> {
+    /// 读粘贴的文本;没有任何内容时返回 [`RandomGeneratorError::EmptyList`]
+    pub fn parse(text: &str) -> Result<Self, RandomGeneratorError> {
+        let tabbed = text.contains('\t');
+        let rows: Vec<Vec<String>> = text
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| {
+                let cells = match tabbed {
+                    true => line.split('\t').map(unquote).collect(),
+                    false => csv_row(line),
+                };
+                cells.into_iter().map(|cell| cell.trim().to_string()).collect()
+            })
+            .collect();
+
+        // 只有一行时把这一行当作一列;一格里用空格或分号隔开的几个数字也拆开
+        let columns: Vec<Vec<String>> = match rows.as_slice() {
+            [row] => {
+                let cells: Vec<String> = row.iter().filter(|cell| !cell.is_empty()).cloned().collect();
+                match cells.as_slice() {
+                    [cell] if cell_value::<T>(cell).is_none() && cell.split([' ', ';']).filter(|part| !part.is_empty()).all(|part| cell_value::<T>(part).is_some()) => {
+                        vec![cell.split([' ', ';']).filter(|part| !part.is_empty()).map(str::to_string).collect()]
+                    }
+                    _ => vec![cells],
+                }
+            }
+            _ => {
+                let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+                (0..width)
+                    .map(|index| rows.iter().filter_map(|row| row.get(index)).filter(|cell| !cell.is_empty()).cloned().collect())
+                    .collect()
+            }
+        };
+
+        for column in &columns {
+            let values: Option<Vec<T>> = column.iter().map(|cell| cell_value(cell)).collect();
+            if let Some(values) = values.filter(|values| !values.is_empty()) {
+                return Ok(PastedList::Numbers(values));
+            }
+            // 第一格是表头
+            if column.len() > 1 && cell_value::<T>(&column[0]).is_none() {
+                if let Some(values) = column[1..].iter().map(|cell| cell_value(cell)).collect::<Option<Vec<T>>>() {
+                    return Ok(PastedList::Numbers(values));
+                }
+            }
+        }
+        match columns.into_iter().find(|column| !column.is_empty()) {
+            Some(names) => Ok(PastedList::Names(names)),
+            None => Err(RandomGeneratorError::EmptyList),
+        }
+    }
+
+    /// 数字或名字的个数
+    pub fn len(&self) -> usize {
+        match self {
+            PastedList::Numbers(numbers) => numbers.len(),
+            PastedList::Names(names) => names.len(),
+        }
+    }
+
+    /// 没有任何数字或名字
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// 自定义列表的输入,用逗号隔开;名字换成它们的编号
+    pub fn to_list_input(&self) -> String {
+        let values: Vec<String> = match self {
+            PastedList::Numbers(numbers) => numbers.iter().map(T::to_string).collect(),
+            PastedList::Names(names) => (1..=names.len()).map(|number| number.to_string()).collect(),
+        };
+        values.join(", ")
+    }
+
+    /// 名字的编号和名字的对照表;数字没有名字,对照表为空
+    pub fn labels(&self) -> LabelMap {
+        match self {
+            PastedList::Numbers(_) => LabelMap::default(),
+            PastedList::Names(names) => LabelMap::numbered(names),
+        }
+    }
+}
+
+/// 电子表格复制出的格,带特殊字符时整格加了引号
+fn unquote(cell: &str) -> String {
+    let trimmed = cell.trim();
+    match trimmed.strip_prefix('"').and_then(|rest| rest.strip_suffix('"')) {
+        Some(inner) => inner.replace("\"\"", "\""),
+        None => cell.to_string(),
+    }
+}
+
+/// 格中的数字;允许千位分隔符
+fn cell_value<T: Number>(cell: &str) -> Option<T> {
+    let parse = |text: &str| text.parse::<T>().ok().filter(|value| value.is_finite());
+    parse(cell).or_else(|| parse(&cell.replace([',', '\''], "")))
+}
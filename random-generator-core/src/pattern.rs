@@ -0,0 +1,296 @@
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::collections::HashSet;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::{RandomGeneratorError, Seed};
+
+/// 一个量词最多重复的次数
+const MAX_REPEAT: usize = 1000;
+/// 每要一个不重复的字符串,平均最多尝试的次数
+const UNIQUE_ATTEMPTS: usize = 50;
+
+/// 按模式生成随机字符串,用于序列号、优惠码等
+///
+/// 模式是掩码和简化正则的组合:
+///
+/// | 写法 | 含义 |
+/// |------|------|
+/// | `#` 或 `\d` | 一位数字 |
+/// | `A` | 一个大写字母 |
+/// | `a` | 一个小写字母 |
+/// | `*` | 一个大写字母或数字 |
+/// | `\w` | 一个字母、数字或下划线 |
+/// | `[A-F0-9]` | 方括号中的任一字符,可以写范围 |
+/// | `(VIP\|STD)` | 竖线分隔的任一选项 |
+/// | `{4}`、`{2,5}` | 前一项重复 4 次、2 到 5 次 |
+/// | `\` | 让下一个字符按原样输出 |
+///
+/// 其他字符原样输出,例如 `AA-####` 生成 `QK-4821`
+///
+/// ```
+/// use random_generator_core::{Seed, StringPattern};
+///
+/// let pattern: StringPattern = "SN-[A-F0-9]{8}".parse()?;
+/// let serials = pattern.generate(5, true, Seed::from([2; 32]))?;
+/// assert!(serials.iter().all(|serial| serial.len() == 11 && serial.starts_with("SN-")));
+/// # Ok::<(), random_generator_core::RandomGeneratorError>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StringPattern {
+    text: String,
+    sequence: Vec<Piece>,
+}
+
+/// 模式中的一项和它的重复次数
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Piece {
+    atom: Atom,
+    min: usize,
+    max: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Atom {
+    Literal(char),
+    /// 任一字符
+    Class(Vec<char>),
+    /// 任一选项
+    Group(Vec<Vec<Piece>>),
+}
+
+impl StringPattern {
+    /// 随机生成一个匹配的字符串
+    pub fn sample(&self, rng: &mut impl Rng) -> String {
+        let mut text = String::new();
+        write_sequence(&self.sequence, rng, &mut text);
+        text
+    }
+
+    /// 用种子生成 `count` 个字符串;`unique` 时保证这一批中没有重复
+    pub fn generate(&self, count: usize, unique: bool, seed: Seed) -> Result<Vec<String>, RandomGeneratorError> {
+        let mut rng = seed.rng();
+        if !unique {
+            return Ok((0..count).map(|_| self.sample(&mut rng)).collect());
+        }
+        self.generate_unique(count, &mut rng, |_| true)
+    }
+
+    /// 生成 `count` 个互不相同、且 `accept` 接受的字符串
+    pub(crate) fn generate_unique(
+        &self,
+        count: usize,
+        rng: &mut impl Rng,
+        mut accept: impl FnMut(&str) -> bool,
+    ) -> Result<Vec<String>, RandomGeneratorError> {
+        let combinations = self.combinations();
+        if (count as u128) > combinations {
+            return Err(RandomGeneratorError::InvalidPattern(format!(
+                "only {} different strings match '{}'",
+                combinations, self.text
+            )));
+        }
+        let mut seen = HashSet::with_capacity(count);
+        let mut strings = Vec::with_capacity(count);
+        let mut attempts = count.saturating_mul(UNIQUE_ATTEMPTS).max(1000);
+        while strings.len() < count {
+            if attempts == 0 {
+                return Err(RandomGeneratorError::InvalidPattern(format!(
+                    "could not find {} different strings for '{}'",
+                    count, self.text
+                )));
+            }
+            attempts -= 1;
+            let text = self.sample(rng);
+            if accept(&text) && seen.insert(text.clone()) {
+                strings.push(text);
+            }
+        }
+        Ok(strings)
+    }
+
+    /// 最多能生成多少种不同的字符串;选项有重叠时会偏大,超过 `u128` 时取最大值
+    pub fn combinations(&self) -> u128 {
+        sequence_combinations(&self.sequence)
+    }
+}
+
+fn write_sequence(sequence: &[Piece], rng: &mut impl Rng, text: &mut String) {
+    for piece in sequence {
+        for _ in 0..rng.gen_range(piece.min..=piece.max) {
+            match &piece.atom {
+                Atom::Literal(c) => text.push(*c),
+                Atom::Class(chars) => text.push(*chars.choose(rng).unwrap()),
+                Atom::Group(options) => write_sequence(options.choose(rng).unwrap(), rng, text),
+            }
+        }
+    }
+}
+
+fn sequence_combinations(sequence: &[Piece]) -> u128 {
+    sequence.iter().fold(1u128, |total, piece| {
+        let choices = match &piece.atom {
+            Atom::Literal(_) => 1,
+            Atom::Class(chars) => chars.len() as u128,
+            Atom::Group(options) => options.iter().map(|option| sequence_combinations(option)).fold(0, u128::saturating_add),
+        };
+        let repeated = (piece.min..=piece.max)
+            .map(|times| choices.checked_pow(times as u32).unwrap_or(u128::MAX))
+            .fold(0, u128::saturating_add);
+        total.saturating_mul(repeated)
+    })
+}
+
+impl FromStr for StringPattern {
+    type Err = RandomGeneratorError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser { chars: text.chars().collect(), position: 0 };
+        let mut options = parser.alternatives()?;
+        if let Some(c) = parser.peek() {
+            return Err(parser.error(format!("unexpected '{}'", c)));
+        }
+        let sequence = if options.len() == 1 {
+            options.remove(0)
+        } else {
+            vec![Piece { atom: Atom::Group(options), min: 1, max: 1 }]
+        };
+        if sequence.is_empty() {
+            return Err(RandomGeneratorError::InvalidPattern("the pattern is empty".to_string()));
+        }
+        Ok(StringPattern { text: text.to_string(), sequence })
+    }
+}
+
+impl fmt::Display for StringPattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.text)
+    }
+}
+
+struct Parser {
+    chars: Vec<char>,
+    position: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.position).copied()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek();
+        self.position += 1;
+        c
+    }
+
+    fn error(&self, message: String) -> RandomGeneratorError {
+        RandomGeneratorError::InvalidPattern(format!("{} at position {}", message, self.position.min(self.chars.len()) + 1))
+    }
+
+    /// 竖线分隔的若干序列,直到 `)` 或结尾
+    fn alternatives(&mut self) -> Result<Vec<Vec<Piece>>, RandomGeneratorError> {
+        let mut options = vec![self.sequence()?];
+        while self.peek() == Some('|') {
+            self.position += 1;
+            options.push(self.sequence()?);
+        }
+        Ok(options)
+    }
+
+    fn sequence(&mut self) -> Result<Vec<Piece>, RandomGeneratorError> {
+        let mut sequence = Vec::new();
+        while let Some(c) = self.peek() {
+            if c == '|' || c == ')' {
+                break;
+            }
+            let atom = self.atom()?;
+            let (min, max) = self.quantifier()?;
+            sequence.push(Piece { atom, min, max });
+        }
+        Ok(sequence)
+    }
+
+    fn atom(&mut self) -> Result<Atom, RandomGeneratorError> {
+        let atom = match self.next() {
+            Some('#') => Atom::Class(('0'..='9').collect()),
+            Some('A') => Atom::Class(('A'..='Z').collect()),
+            Some('a') => Atom::Class(('a'..='z').collect()),
+            Some('*') => Atom::Class(('A'..='Z').chain('0'..='9').collect()),
+            Some('\\') => match self.next() {
+                Some('d') => Atom::Class(('0'..='9').collect()),
+                Some('w') => Atom::Class(('a'..='z').chain('A'..='Z').chain('0'..='9').chain(['_']).collect()),
+                Some(c) => Atom::Literal(c),
+                None => return Err(self.error("'\\' at the end".to_string())),
+            },
+            Some('[') => self.class()?,
+            Some('(') => {
+                let options = self.alternatives()?;
+                if self.next() != Some(')') {
+                    return Err(self.error("missing ')'".to_string()));
+                }
+                Atom::Group(options)
+            }
+            Some(c @ ('{' | '}' | ']')) => return Err(self.error(format!("unexpected '{}'", c))),
+            Some(c) => Atom::Literal(c),
+            None => unreachable!("atom() is only called before another character"),
+        };
+        Ok(atom)
+    }
+
+    /// `[...]` 的内容,开头的 `[` 已读过
+    fn class(&mut self) -> Result<Atom, RandomGeneratorError> {
+        let mut chars = Vec::new();
+        loop {
+            let c = match self.next() {
+                Some(']') => break,
+                Some('\\') => self.next().ok_or_else(|| self.error("'\\' at the end".to_string()))?,
+                Some('^') if chars.is_empty() => return Err(self.error("negated classes like [^...] are not supported".to_string())),
+                Some(c) => c,
+                None => return Err(self.error("missing ']'".to_string())),
+            };
+            if self.peek() == Some('-') && self.chars.get(self.position + 1).is_some_and(|&end| end != ']') {
+                self.position += 1;
+                let end = self.next().unwrap();
+                if end < c {
+                    return Err(self.error(format!("the range {}-{} is backwards", c, end)));
+                }
+                chars.extend(c..=end);
+            } else {
+                chars.push(c);
+            }
+        }
+        chars.sort_unstable();
+        chars.dedup();
+        if chars.is_empty() {
+            return Err(self.error("empty '[]'".to_string()));
+        }
+        Ok(Atom::Class(chars))
+    }
+
+    /// `{n}` 或 `{n,m}`,没有时为一次
+    fn quantifier(&mut self) -> Result<(usize, usize), RandomGeneratorError> {
+        if self.peek() != Some('{') {
+            return Ok((1, 1));
+        }
+        self.position += 1;
+        let start = self.position;
+        while self.peek().is_some_and(|c| c != '}') {
+            self.position += 1;
+        }
+        if self.next() != Some('}') {
+            return Err(self.error("missing '}'".to_string()));
+        }
+        let inside: String = self.chars[start..self.position - 1].iter().collect();
+        let number = |text: &str| text.trim().parse::<usize>().ok();
+        let (min, max) = match inside.split_once(',') {
+            Some((min, max)) => (number(min), number(max)),
+            None => (number(&inside), number(&inside)),
+        };
+        match (min, max) {
+            (Some(min), Some(max)) if min <= max && max <= MAX_REPEAT => Ok((min, max)),
+            _ => Err(self.error(format!("invalid repetition {{{}}}", inside))),
+        }
+    }
+}
@@ -0,0 +1,377 @@
+//! 从动态库加载的第三方模式和导出格式。
+//!
+//! 插件是导出 `random_tool_plugin` 函数的动态库(`.so`、`.dll` 或 `.dylib`),
+//! 该函数返回一个 [`RtPlugin`],列出插件提供的模式和导出格式。接口只使用 C 类型,
+//! 因此插件可以用 C、C++、Rust 或任何能导出 C 函数的语言编写,
+//! 声明见 `random-generator-core/include/random_tool_plugin.h`。
+//!
+//! 插件的模式只输出 `i64`,由生成器提供随机数,因此种子和重放对插件模式同样有效。
+//! 插件在本进程中运行,和其他动态库一样拥有完整的权限,只应加载可信的插件。
+
+use libloading::Library;
+use rand::RngCore;
+use std::ffi::{c_char, c_void, CStr};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::{fs, slice};
+
+use crate::{
+    Config, ExportFormat, Exporter, Generator, GeneratorMode, GeneratorSource, ListSource, ModeRegistry, NumberFormat, Progress,
+    RandomGeneratorError, RangeSource, SourceInput,
+};
+
+/// 当前的插件接口版本,版本不同的插件不会被加载
+pub const PLUGIN_ABI_VERSION: u32 = 1;
+
+/// 插件必须导出的函数名
+pub const ENTRY_POINT: &str = "random_tool_plugin";
+
+/// [`RtMode::input`]:使用上下界
+pub const RT_INPUT_BOUNDS: u32 = 0;
+/// [`RtMode::input`]:使用自定义列表
+pub const RT_INPUT_LIST: u32 = 1;
+
+/// 插件的入口函数,返回的描述在库卸载前必须一直有效
+pub type RtEntryPoint = unsafe extern "C" fn() -> *const RtPlugin;
+
+/// 插件的描述
+#[repr(C)]
+pub struct RtPlugin {
+    /// 必须是 [`PLUGIN_ABI_VERSION`]
+    pub abi_version: u32,
+    /// 插件名称,UTF-8
+    pub name: *const c_char,
+    /// 提供的模式
+    pub modes: *const RtMode,
+    /// `modes` 的长度
+    pub mode_count: usize,
+    /// 提供的导出格式
+    pub exporters: *const RtExporter,
+    /// `exporters` 的长度
+    pub exporter_count: usize,
+}
+
+/// 插件提供的一种模式
+#[repr(C)]
+pub struct RtMode {
+    /// 显示在模式列表中的名称,UTF-8
+    pub name: *const c_char,
+    /// [`RT_INPUT_BOUNDS`] 或 [`RT_INPUT_LIST`]
+    pub input: u32,
+    /// 把 `request.count` 个结果写入 `out`,成功时返回 0
+    pub generate: unsafe extern "C" fn(request: *const RtRequest, random: *const RtRandom, out: *mut i64) -> i32,
+}
+
+/// 插件提供的一种导出格式
+#[repr(C)]
+pub struct RtExporter {
+    /// 显示在格式列表中的名称,UTF-8
+    pub name: *const c_char,
+    /// 文件扩展名,不带句点
+    pub extension: *const c_char,
+    /// 把 `numbers` 转换为文件内容,分一次或多次交给 `write`,成功时返回 0
+    pub export_numbers: unsafe extern "C" fn(numbers: *const i64, len: usize, sink: *const RtSink) -> i32,
+}
+
+/// 一次抽取的设置
+#[repr(C)]
+pub struct RtRequest {
+    /// 下界,只用于上下界模式
+    pub lower: i64,
+    /// 上界,只用于上下界模式
+    pub upper: i64,
+    /// 自定义列表,只用于列表模式
+    pub list: *const i64,
+    /// `list` 的长度
+    pub list_len: usize,
+    /// 要抽取的个数,即 `out` 的长度
+    pub count: usize,
+    /// 是否允许重复
+    pub allow_duplicates: bool,
+}
+
+/// 生成器的随机数来源
+#[repr(C)]
+pub struct RtRandom {
+    /// 传给 `next_u64` 的上下文
+    pub context: *mut c_void,
+    /// 取一个均匀分布的 64 位随机数
+    pub next_u64: unsafe extern "C" fn(context: *mut c_void) -> u64,
+}
+
+/// 接收导出内容的回调
+#[repr(C)]
+pub struct RtSink {
+    /// 传给 `write` 的上下文
+    pub context: *mut c_void,
+    /// 追加 `len` 个字节,内容整体必须是 UTF-8
+    pub write: unsafe extern "C" fn(context: *mut c_void, bytes: *const u8, len: usize),
+}
+
+/// 一个已加载的插件
+pub struct Plugin {
+    name: String,
+    sources: Vec<PluginSource>,
+    exporters: Vec<PluginExporter>,
+}
+
+impl Plugin {
+    /// 加载动态库形式的插件
+    ///
+    /// # Safety
+    ///
+    /// 加载动态库会运行其中的初始化代码,插件函数也会在本进程中运行;
+    /// 调用方必须信任该文件,并保证它遵守 [`RtPlugin`] 描述的接口
+    pub unsafe fn load(path: &Path) -> Result<Self, RandomGeneratorError> {
+        let failed = |e: libloading::Error| RandomGeneratorError::Plugin(e.to_string());
+        let library = Arc::new(Library::new(path).map_err(failed)?);
+        let entry = library.get::<RtEntryPoint>(ENTRY_POINT.as_bytes()).map_err(failed)?;
+        let descriptor = entry();
+        if descriptor.is_null() {
+            return Err(RandomGeneratorError::Plugin("no plugin description".to_string()));
+        }
+        Self::from_descriptor(&*descriptor, Some(library))
+    }
+
+    /// 加载目录中的所有插件,返回成功加载的插件和每个失败的文件及原因
+    ///
+    /// 目录不存在时两者都为空
+    ///
+    /// # Safety
+    ///
+    /// 同 [`load`](Self::load),目录中的每个动态库都会被加载
+    pub unsafe fn load_dir(dir: &Path) -> (Vec<Plugin>, Vec<(PathBuf, RandomGeneratorError)>) {
+        let mut paths: Vec<PathBuf> = fs::read_dir(dir)
+            .map(|entries| entries.filter_map(|entry| entry.ok().map(|entry| entry.path())).collect())
+            .unwrap_or_default();
+        paths.retain(|path| path.extension().and_then(|ext| ext.to_str()) == Some(std::env::consts::DLL_EXTENSION));
+        paths.sort();
+
+        let mut plugins = Vec::new();
+        let mut failures = Vec::new();
+        for path in paths {
+            // 先加载的插件优先,后面的同名模式或格式不会替换它
+            match Self::load(&path).and_then(|plugin| match plugins.iter().find_map(|earlier| plugin.clash(earlier)) {
+                Some(clash) => Err(RandomGeneratorError::Plugin(clash)),
+                None => Ok(plugin),
+            }) {
+                Ok(plugin) => plugins.push(plugin),
+                Err(e) => failures.push((path, e)),
+            }
+        }
+        (plugins, failures)
+    }
+
+    /// 由描述创建插件,`library` 是描述所在的动态库,插件存在期间不会卸载
+    ///
+    /// # Safety
+    ///
+    /// 描述中的指针和函数必须有效,且在 `library` 卸载前(没有库时在整个进程期间)保持有效
+    pub unsafe fn from_descriptor(descriptor: &RtPlugin, library: Option<Arc<Library>>) -> Result<Self, RandomGeneratorError> {
+        if descriptor.abi_version != PLUGIN_ABI_VERSION {
+            return Err(RandomGeneratorError::Plugin(format!(
+                "plugin interface version {} is not supported (expected {})",
+                descriptor.abi_version, PLUGIN_ABI_VERSION
+            )));
+        }
+        let name = text(descriptor.name, "plugin name")?;
+
+        let mut sources = Vec::new();
+        for mode in slice_or_empty(descriptor.modes, descriptor.mode_count) {
+            let input = match mode.input {
+                RT_INPUT_BOUNDS => SourceInput::Bounds,
+                RT_INPUT_LIST => SourceInput::List,
+                other => return Err(RandomGeneratorError::Plugin(format!("{}: unknown input kind {}", name, other))),
+            };
+            // 模式名称需要 'static;插件只在启动时加载一次,泄漏的只是几个名称
+            let mode_name = text(mode.name, "mode name")?;
+            // 插件只能添加模式,不能换掉内置的区间、名单等抽取方式
+            if ModeRegistry::<i64>::default().modes().iter().any(|builtin| builtin.name().eq_ignore_ascii_case(&mode_name)) {
+                return Err(RandomGeneratorError::Plugin(format!("{}: mode '{}' is built in", name, mode_name)));
+            }
+            let mode_name: &'static str = Box::leak(mode_name.into_boxed_str());
+            sources.push(PluginSource {
+                mode: GeneratorMode::new(mode_name),
+                input,
+                generate: mode.generate,
+                _library: library.clone(),
+            });
+        }
+
+        let mut exporters = Vec::new();
+        for exporter in slice_or_empty(descriptor.exporters, descriptor.exporter_count) {
+            let format_name = text(exporter.name, "format name")?;
+            if ExportFormat::ALL.iter().any(|builtin| builtin.to_string().eq_ignore_ascii_case(&format_name)) {
+                return Err(RandomGeneratorError::Plugin(format!("{}: export format '{}' is built in", name, format_name)));
+            }
+            exporters.push(PluginExporter {
+                name: format_name,
+                extension: text(exporter.extension, "file extension")?,
+                export: exporter.export_numbers,
+                _library: library.clone(),
+            });
+        }
+
+        Ok(Plugin { name, sources, exporters })
+    }
+
+    /// 插件名称
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// 插件提供的模式
+    pub fn modes(&self) -> Vec<GeneratorMode> {
+        self.sources.iter().map(|source| source.mode).collect()
+    }
+
+    /// 插件提供的导出格式名称
+    pub fn formats(&self) -> Vec<&str> {
+        self.exporters.iter().map(|exporter| exporter.name.as_str()).collect()
+    }
+
+    /// 与 `other` 同名的第一个模式或导出格式,说明写成错误信息
+    pub(crate) fn clash(&self, other: &Plugin) -> Option<String> {
+        if let Some(mode) = self.modes().into_iter().find(|mode| other.modes().contains(mode)) {
+            return Some(format!("{}: mode '{}' is already provided by {}", self.name, mode, other.name));
+        }
+        let format = self.formats().into_iter().find(|format| other.formats().contains(format))?;
+        Some(format!("{}: export format '{}' is already provided by {}", self.name, format, other.name))
+    }
+
+    /// 把插件的模式和导出格式注册到生成器
+    pub fn install(&self, generator: &mut Generator<i64>) {
+        for source in &self.sources {
+            generator.register_source(Box::new(source.clone()));
+        }
+        for exporter in &self.exporters {
+            generator.register_exporter(Box::new(exporter.clone()));
+        }
+    }
+}
+
+/// 插件提供的模式
+#[derive(Clone)]
+pub struct PluginSource {
+    mode: GeneratorMode,
+    input: SourceInput,
+    generate: unsafe extern "C" fn(*const RtRequest, *const RtRandom, *mut i64) -> i32,
+    _library: Option<Arc<Library>>,
+}
+
+impl GeneratorSource<i64> for PluginSource {
+    fn mode(&self) -> GeneratorMode {
+        self.mode
+    }
+
+    fn input(&self) -> SourceInput {
+        self.input
+    }
+
+    fn validate(&self, config: &Config<i64>) -> Vec<RandomGeneratorError> {
+        match self.input {
+            SourceInput::List => ListSource.validate(config),
+            _ => RangeSource.validate(config),
+        }
+    }
+
+    /// 插件自己决定能抽多少个,无法满足时返回错误
+    fn available(&self, _config: &Config<i64>) -> Option<u128> {
+        None
+    }
+
+    fn generate(
+        &self,
+        config: &Config<i64>,
+        mut rng: &mut dyn RngCore,
+        progress: &mut Progress<'_>,
+    ) -> Result<Vec<i64>, RandomGeneratorError> {
+        progress.checkpoint(0, config.num_to_generate)?;
+        let request = RtRequest {
+            lower: config.lower_bound,
+            upper: config.upper_bound,
+            list: config.custom_list.as_ptr(),
+            list_len: config.custom_list.len(),
+            count: config.num_to_generate,
+            allow_duplicates: config.allow_duplicates,
+        };
+        let random = RtRandom {
+            context: &mut rng as *mut &mut dyn RngCore as *mut c_void,
+            next_u64,
+        };
+        let mut numbers = vec![0; config.num_to_generate];
+        // SAFETY: 请求、随机数来源和输出在调用期间都有效,输出正好有 count 个位置
+        let status = unsafe { (self.generate)(&request, &random, numbers.as_mut_ptr()) };
+        if status != 0 {
+            return Err(RandomGeneratorError::Plugin(format!("{} failed with code {}", self.mode, status)));
+        }
+        progress.checkpoint(numbers.len(), numbers.len())?;
+        Ok(numbers)
+    }
+}
+
+/// 插件提供的导出格式
+#[derive(Clone)]
+pub struct PluginExporter {
+    name: String,
+    extension: String,
+    export: unsafe extern "C" fn(*const i64, usize, *const RtSink) -> i32,
+    _library: Option<Arc<Library>>,
+}
+
+impl Exporter<i64> for PluginExporter {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn extension(&self) -> &str {
+        &self.extension
+    }
+
+    /// 插件收到的是原始数字,数字格式不起作用
+    fn export(&self, numbers: &[i64], _format: &NumberFormat) -> Result<String, RandomGeneratorError> {
+        let mut content: Vec<u8> = Vec::new();
+        let sink = RtSink {
+            context: &mut content as *mut Vec<u8> as *mut c_void,
+            write,
+        };
+        // SAFETY: 数字和接收回调在调用期间都有效
+        let status = unsafe { (self.export)(numbers.as_ptr(), numbers.len(), &sink) };
+        if status != 0 {
+            return Err(RandomGeneratorError::Plugin(format!("{} failed with code {}", self.name, status)));
+        }
+        String::from_utf8(content).map_err(|_| RandomGeneratorError::Plugin(format!("{} wrote invalid UTF-8", self.name)))
+    }
+}
+
+unsafe extern "C" fn next_u64(context: *mut c_void) -> u64 {
+    let rng = &mut *(context as *mut &mut dyn RngCore);
+    rng.next_u64()
+}
+
+unsafe extern "C" fn write(context: *mut c_void, bytes: *const u8, len: usize) {
+    let content = &mut *(context as *mut Vec<u8>);
+    content.extend_from_slice(slice_or_empty(bytes, len));
+}
+
+/// 允许空指针表示空数组
+unsafe fn slice_or_empty<'a, T>(ptr: *const T, len: usize) -> &'a [T] {
+    if ptr.is_null() || len == 0 {
+        &[]
+    } else {
+        slice::from_raw_parts(ptr, len)
+    }
+}
+
+unsafe fn text(ptr: *const c_char, what: &str) -> Result<String, RandomGeneratorError> {
+    if ptr.is_null() {
+        return Err(RandomGeneratorError::Plugin(format!("missing {}", what)));
+    }
+    let text = CStr::from_ptr(ptr)
+        .to_str()
+        .map_err(|_| RandomGeneratorError::Plugin(format!("{} is not UTF-8", what)))?;
+    if text.trim().is_empty() {
+        return Err(RandomGeneratorError::Plugin(format!("empty {}", what)));
+    }
+    Ok(text.to_string())
+}
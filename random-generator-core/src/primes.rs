@@ -0,0 +1,238 @@
+use rand::{Rng, RngCore};
+use std::collections::HashSet;
+
+use crate::source::{draw_with_duplicates, RepeatLimits, RepeatTracker, UniqueStrategy};
+use crate::{Config, GeneratorMode, GeneratorSource, Number, Progress, RandomGeneratorError, SourceInput};
+
+/// 区间不超过这么多个数时逐个检验,得到全部素数;更大的区间随机抽取后检验
+const SCAN_LIMIT: u128 = 10_000;
+
+/// 大区间中平均每要一个素数最多检验的候选数
+const PRIME_ATTEMPTS: usize = 10_000;
+
+/// 大区间中每抽多少次回报一次进度;检验候选数比普通抽取慢得多,间隔也短得多
+const PRIME_PROGRESS_INTERVAL: usize = 64;
+
+/// Miller–Rabin 检验的底,也用来先做试除
+const BASES: [u128; 20] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71];
+
+/// 以前 13 个素数为底的检验对小于这个数的整数是确定的
+const DETERMINISTIC_LIMIT: u128 = 3_317_044_064_679_887_385_961_981;
+
+/// 判断 `n` 是否是素数
+///
+/// 使用 Miller–Rabin 检验:小于 3.3 × 10²⁴ 的数结果是确定的;
+/// 更大的数以前 20 个素数为底,合数被误判的机会可以忽略
+///
+/// ```
+/// use random_generator_core::is_prime;
+///
+/// assert!(is_prime(2) && is_prime(97) && is_prime(170_141_183_460_469_231_731_687_303_715_884_105_727));
+/// assert!(!is_prime(1) && !is_prime(561)); // 561 是 Carmichael 数
+/// ```
+pub fn is_prime(n: u128) -> bool {
+    if n < 2 {
+        return false;
+    }
+    for p in BASES {
+        if n == p {
+            return true;
+        }
+        if n.is_multiple_of(p) {
+            return false;
+        }
+    }
+
+    let s = (n - 1).trailing_zeros();
+    let d = (n - 1) >> s;
+    let rounds = if n < DETERMINISTIC_LIMIT { 13 } else { BASES.len() };
+    'bases: for a in BASES.into_iter().take(rounds) {
+        let mut x = pow_mod(a, d, n);
+        if x == 1 || x == n - 1 {
+            continue;
+        }
+        for _ in 1..s {
+            x = mul_mod(x, x, n);
+            if x == n - 1 {
+                continue 'bases;
+            }
+        }
+        return false;
+    }
+    true
+}
+
+/// `a * b % n`,不会溢出
+fn mul_mod(a: u128, b: u128, n: u128) -> u128 {
+    if n <= u64::MAX as u128 {
+        return a * b % n;
+    }
+    // 逐位相乘,每一步都先取模
+    let (mut a, mut b, mut product) = (a % n, b, 0u128);
+    while b > 0 {
+        if b & 1 == 1 {
+            product = add_mod(product, a, n);
+        }
+        a = add_mod(a, a, n);
+        b >>= 1;
+    }
+    product
+}
+
+/// `a + b` 对 `n` 取模,要求 `a`、`b` 都小于 `n`
+fn add_mod(a: u128, b: u128, n: u128) -> u128 {
+    if a >= n - b {
+        a - (n - b)
+    } else {
+        a + b
+    }
+}
+
+/// `base ^ exponent % n`
+fn pow_mod(base: u128, mut exponent: u128, n: u128) -> u128 {
+    let (mut base, mut result) = (base % n, 1u128);
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = mul_mod(result, base, n);
+        }
+        base = mul_mod(base, base, n);
+        exponent >>= 1;
+    }
+    result
+}
+
+/// 整数类型的值是否是素数;负数不是
+fn is_prime_value<T: Number>(value: T) -> bool {
+    T::from_i64(2).is_some_and(|two| value >= two) && is_prime(value.key())
+}
+
+/// 在 `lower_bound..=upper_bound` 中抽取素数
+///
+/// 小区间先找出全部素数再抽取,大区间随机抽取候选数并检验,每个素数被抽中的机会相同。
+/// 只适用于整数类型
+///
+/// ```
+/// use random_generator_core::{is_prime, Generator, GeneratorConfig, GeneratorMode};
+///
+/// let mut generator = Generator::with_config(GeneratorConfig {
+///     mode: GeneratorMode::PRIMES,
+///     lower_bound: 1_000_000_000,
+///     upper_bound: 2_000_000_000,
+///     num_to_generate: 5,
+///     ..Default::default()
+/// })?;
+/// generator.generate_numbers()?;
+/// assert!(generator.get_numbers().iter().all(|&n| is_prime(n as u128)));
+/// # Ok::<(), random_generator_core::RandomGeneratorError>(())
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PrimeSource;
+
+impl PrimeSource {
+    /// 恰好 `bits` 位的整数所在的区间,即最高位为 1 的数;`bits` 为 2 到 127
+    pub fn bit_range(bits: u32) -> Result<(i128, i128), RandomGeneratorError> {
+        if !(2..=127).contains(&bits) {
+            return Err(RandomGeneratorError::InvalidPrimes(format!("{} bits is out of range, use 2 to 127", bits)));
+        }
+        let lower = 1i128 << (bits - 1);
+        Ok((lower, lower - 1 + lower))
+    }
+
+    /// 小区间中的全部素数,大区间返回 `None`
+    fn scan<T: Number>(config: &Config<T>) -> Option<Vec<T>> {
+        let size = T::values_between(config.lower_bound, config.upper_bound)?;
+        (size <= SCAN_LIMIT).then(|| {
+            (0..size)
+                .map(|i| T::nth_from(config.lower_bound, i))
+                .filter(|&value| is_prime_value(value))
+                .collect()
+        })
+    }
+
+    /// 在大区间中反复抽取候选数,直到抽到素数;尝试完 `attempts` 次仍没有时返回 `None`
+    fn sample<T: Number>(config: &Config<T>, size: u128, rng: &mut dyn RngCore, attempts: &mut usize) -> Option<T> {
+        while *attempts > 0 {
+            *attempts -= 1;
+            let value = T::nth_from(config.lower_bound, rng.gen_range(0..size));
+            if is_prime_value(value) {
+                return Some(value);
+            }
+        }
+        None
+    }
+}
+
+impl<T: Number> GeneratorSource<T> for PrimeSource {
+    fn mode(&self) -> GeneratorMode {
+        GeneratorMode::PRIMES
+    }
+
+    fn input(&self) -> SourceInput {
+        SourceInput::Bounds
+    }
+
+    fn validate(&self, config: &Config<T>) -> Vec<RandomGeneratorError> {
+        let (lower, upper) = (config.lower_bound, config.upper_bound);
+        if !T::is_valid_range(lower, upper) {
+            return vec![RandomGeneratorError::InvalidBounds];
+        }
+        if T::values_between(lower, upper).is_none() {
+            return vec![RandomGeneratorError::InvalidPrimes("primes need whole numbers".to_string())];
+        }
+        if Self::scan(config).is_some_and(|primes| primes.is_empty()) {
+            return vec![RandomGeneratorError::InvalidPrimes(format!("there are no primes from {} to {}", lower, upper))];
+        }
+        Vec::new()
+    }
+
+    fn available(&self, config: &Config<T>) -> Option<u128> {
+        // 大区间不去数,抽不够时生成会报错
+        Self::scan(config).map(|primes| primes.len() as u128)
+    }
+
+    fn pool(&self, config: &Config<T>) -> Option<Vec<T>> {
+        Self::scan(config)
+    }
+
+    fn generate(
+        &self,
+        config: &Config<T>,
+        rng: &mut dyn RngCore,
+        progress: &mut Progress<'_>,
+    ) -> Result<Vec<T>, RandomGeneratorError> {
+        let count = config.num_to_generate;
+        if let Some(primes) = Self::scan(config) {
+            if primes.is_empty() {
+                return Err(RandomGeneratorError::InvalidPrimes("there are no primes in the range".to_string()));
+            }
+            if config.allow_duplicates {
+                return draw_with_duplicates(count, RepeatLimits::of(config), progress, || primes[rng.gen_range(0..primes.len())]);
+            }
+            let size = primes.len() as u128;
+            let indices = UniqueStrategy::for_range(count, size).indices(rng, size, count, progress)?;
+            return Ok(indices.into_iter().map(|i| primes[i as usize]).collect());
+        }
+
+        let size = T::values_between(config.lower_bound, config.upper_bound)
+            .ok_or_else(|| RandomGeneratorError::InvalidPrimes("primes need whole numbers".to_string()))?;
+        let mut attempts = count.saturating_mul(PRIME_ATTEMPTS);
+        let mut tracker = RepeatTracker::new(RepeatLimits::of(config), count);
+        let mut seen = HashSet::new();
+        let mut numbers = Vec::new();
+        let mut draws = 0usize;
+        while numbers.len() < count {
+            if draws.is_multiple_of(PRIME_PROGRESS_INTERVAL) {
+                progress.checkpoint(numbers.len(), count)?;
+            }
+            draws += 1;
+            let Some(prime) = Self::sample(config, size, rng, &mut attempts) else {
+                return Err(RandomGeneratorError::InvalidPrimes(format!("could not find {} primes in the range", count)));
+            };
+            let accepted = if config.allow_duplicates { tracker.accept(prime)? } else { seen.insert(prime.key()) };
+            if accepted {
+                numbers.push(prime);
+            }
+        }
+        Ok(numbers)
+    }
+}
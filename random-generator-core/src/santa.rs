@@ -0,0 +1,141 @@
+use rand::seq::SliceRandom;
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::matching::{check_names, match_all, parse_names, parse_pairs, position};
+use crate::{RandomGeneratorError, Seed};
+
+/// 先尝试这么多次完全随机的排列,都不满足限制时再用匹配算法找一个
+const SHUFFLE_ATTEMPTS: usize = 1000;
+
+/// 一个人要给谁送礼物
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Assignment {
+    /// 送礼物的人
+    pub giver: String,
+    /// 收礼物的人
+    pub receiver: String,
+}
+
+/// 秘密圣诞老人(交换礼物)的抽签:每人给另一个人送礼物,每人也恰好收到一份
+///
+/// 没有人会抽到自己;排除的两个人(例如夫妻)互相不会抽到对方
+///
+/// ```
+/// use random_generator_core::{SecretSanta, Seed};
+///
+/// let mut santa = SecretSanta::parse("Alice\nBob\nCarol\nDave")?;
+/// santa.exclude("Alice", "Bob")?;
+/// let assignments = santa.draw(Seed::from([5; 32]))?;
+/// assert_eq!(assignments.len(), 4);
+/// assert!(assignments.iter().all(|a| a.giver != a.receiver));
+/// assert!(!assignments.iter().any(|a| a.giver == "Alice" && a.receiver == "Bob"));
+/// # Ok::<(), random_generator_core::RandomGeneratorError>(())
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SecretSanta {
+    names: Vec<String>,
+    /// 互相不能抽到的两个人的位置,较小的在前
+    exclusions: HashSet<(usize, usize)>,
+}
+
+impl SecretSanta {
+    /// 参加的人,名字不能为空或重复,至少 2 人
+    pub fn new(names: Vec<String>) -> Result<Self, RandomGeneratorError> {
+        check_names(&names)?;
+        if names.len() < 2 {
+            return Err(RandomGeneratorError::InvalidDraw("a gift exchange needs at least 2 people".to_string()));
+        }
+        Ok(SecretSanta { names, exclusions: HashSet::new() })
+    }
+
+    /// 每行一个名字,跳过空行
+    pub fn parse(text: &str) -> Result<Self, RandomGeneratorError> {
+        Self::new(parse_names(text))
+    }
+
+    /// 让两个人互相不会抽到对方;名字不区分大小写
+    pub fn exclude(&mut self, first: &str, second: &str) -> Result<(), RandomGeneratorError> {
+        let (first, second) = (position(&self.names, first)?, position(&self.names, second)?);
+        if first == second {
+            return Err(RandomGeneratorError::InvalidDraw(format!("'{}' is excluded from themselves", self.names[first])));
+        }
+        self.exclusions.insert((first.min(second), first.max(second)));
+        Ok(())
+    }
+
+    /// 每行一对用逗号分隔的名字,例如 `Alice, Bob`,跳过空行
+    pub fn exclude_pairs(&mut self, text: &str) -> Result<(), RandomGeneratorError> {
+        for (first, second) in parse_pairs(text)? {
+            self.exclude(first, second)?;
+        }
+        Ok(())
+    }
+
+    /// 参加的人
+    pub fn names(&self) -> &[String] {
+        &self.names
+    }
+
+    /// 用种子抽签,按名单的顺序返回每个人要送礼物的对象
+    ///
+    /// 限制不多时在所有满足限制的分配中均匀地抽取;限制多到随机排列很难满足时,
+    /// 改为按随机顺序寻找一个满足限制的分配。没有这样的分配时返回错误
+    pub fn draw(&self, seed: Seed) -> Result<Vec<Assignment>, RandomGeneratorError> {
+        let mut rng = seed.rng();
+        let n = self.names.len();
+        let mut receivers: Vec<usize> = (0..n).collect();
+        let mut found = false;
+        for _ in 0..SHUFFLE_ATTEMPTS {
+            receivers.shuffle(&mut rng);
+            if (0..n).all(|giver| self.allowed(giver, receivers[giver])) {
+                found = true;
+                break;
+            }
+        }
+        if !found {
+            let candidates = (0..n).map(|giver| (0..n).filter(|&receiver| self.allowed(giver, receiver)).collect()).collect();
+            // 限制多到随机排列几乎都不满足时,按随机顺序找一个满足限制的分配
+            receivers = match_all(candidates, n, &mut rng).ok_or_else(|| {
+                RandomGeneratorError::InvalidDraw("the exclusions leave no way for everyone to give and receive a gift".to_string())
+            })?;
+        }
+        Ok(receivers
+            .into_iter()
+            .enumerate()
+            .map(|(giver, receiver)| Assignment { giver: self.names[giver].clone(), receiver: self.names[receiver].clone() })
+            .collect())
+    }
+
+    /// 每人一个文本文件,文件名是名字,内容只有这个人要送礼物的对象,便于单独发给本人
+    ///
+    /// 返回写出的文件;名字中不能用于文件名的字符换成 `_`
+    pub fn write_assignments(assignments: &[Assignment], dir: &Path) -> Result<Vec<PathBuf>, RandomGeneratorError> {
+        fs::create_dir_all(dir)?;
+        let mut used = HashSet::new();
+        let mut paths = Vec::with_capacity(assignments.len());
+        for assignment in assignments {
+            let stem: String = assignment
+                .giver
+                .chars()
+                .map(|c| if c.is_alphanumeric() || matches!(c, ' ' | '-' | '_' | '.') { c } else { '_' })
+                .collect();
+            // 换掉字符后可能与别人的名字相同
+            let mut file_name = format!("{}.txt", stem.trim_matches('.'));
+            let mut n = 2;
+            while !used.insert(file_name.to_lowercase()) {
+                file_name = format!("{} ({}).txt", stem.trim_matches('.'), n);
+                n += 1;
+            }
+            let path = dir.join(file_name);
+            fs::write(&path, format!("{}, you are giving a gift to: {}\n", assignment.giver, assignment.receiver))?;
+            paths.push(path);
+        }
+        Ok(paths)
+    }
+
+    fn allowed(&self, giver: usize, receiver: usize) -> bool {
+        giver != receiver && !self.exclusions.contains(&(giver.min(receiver), giver.max(receiver)))
+    }
+}
@@ -0,0 +1,422 @@
+use rand::seq::SliceRandom;
+use rand::Rng;
+use serde_json::{Map, Number as JsonNumber, Value};
+
+use crate::faker::Person;
+use crate::{FakeField, FakeLocale, RandomGeneratorError, Seed};
+
+/// 数组没有给出长度时的默认最大长度
+const DEFAULT_MAX_ITEMS: usize = 5;
+/// `uniqueItems` 时为每个元素重新生成的最多次数
+const UNIQUE_ATTEMPTS: usize = 100;
+
+/// 按 JSON Schema 或字段模板生成随机 JSON 文档
+///
+/// 支持 JSON Schema 中常用的一部分:`type`、`properties`、`items`、`enum`、`const`、
+/// `oneOf`/`anyOf`、`minimum`/`maximum`(含 exclusive 形式)、`multipleOf`、
+/// `minLength`/`maxLength`、`minItems`/`maxItems`/`uniqueItems`,以及 `format`
+/// 为 `email`、`date`、`date-time`、`uuid`、`uri` 或测试数据列名(如 `name`、`phone`)的字符串。
+/// 遇到不支持的关键字(如 `$ref`、`allOf`、`pattern`)时返回错误,而不是生成不符合的文档
+///
+/// 不是 Schema 的 JSON 按字段模板理解:字符串值是生成器名称(`integer`、`number`、
+/// `boolean`、`string`、`uuid`、`date`、`date-time` 或测试数据列名),只有一个元素的数组表示
+/// 1 到 5 个这样的元素,其他值原样保留,例如 `{"id": "uuid", "name": "name", "tags": ["string"]}`
+///
+/// ```
+/// use random_generator_core::{JsonSchema, Seed};
+///
+/// let schema = JsonSchema::parse(r#"{
+///     "type": "object",
+///     "properties": {
+///         "id": {"type": "integer", "minimum": 1, "maximum": 9},
+///         "email": {"type": "string", "format": "email"}
+///     }
+/// }"#)?;
+/// let documents = schema.generate(2, Seed::from([1; 32]));
+/// assert!((1..=9).contains(&documents[0]["id"].as_i64().unwrap()));
+/// assert!(documents[1]["email"].as_str().unwrap().ends_with("@example.com"));
+/// # Ok::<(), random_generator_core::RandomGeneratorError>(())
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct JsonSchema {
+    root: Node,
+    /// 姓名、电话等测试数据字段使用的地区
+    pub locale: FakeLocale,
+}
+
+/// 解析后的 Schema,生成时不再检查
+#[derive(Debug, Clone, PartialEq)]
+enum Node {
+    Const(Value),
+    Enum(Vec<Value>),
+    OneOf(Vec<Node>),
+    Null,
+    Boolean,
+    Integer { min: i64, max: i64, step: i64 },
+    Number { min: f64, max: f64, step: Option<f64> },
+    String { min_length: usize, max_length: usize },
+    Format(StringFormat),
+    Array { items: Box<Node>, min_items: usize, max_items: usize, unique: bool },
+    Object(Vec<(String, Node)>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum StringFormat {
+    Date,
+    DateTime,
+    Uuid,
+    Uri,
+    Fake(FakeField),
+}
+
+impl StringFormat {
+    fn parse(name: &str) -> Option<StringFormat> {
+        match name {
+            "date" => Some(StringFormat::Date),
+            "date-time" => Some(StringFormat::DateTime),
+            "uuid" => Some(StringFormat::Uuid),
+            "uri" | "url" => Some(StringFormat::Uri),
+            other => other.parse().ok().map(StringFormat::Fake),
+        }
+    }
+}
+
+/// Schema 中表明"这是一个 Schema"而不是字段模板的关键字
+const SCHEMA_KEYWORDS: [&str; 7] = ["$schema", "type", "properties", "items", "enum", "const", "oneOf"];
+
+impl JsonSchema {
+    /// 解析 JSON Schema 或字段模板
+    pub fn parse(text: &str) -> Result<Self, RandomGeneratorError> {
+        let value: Value = serde_json::from_str(text)
+            .map_err(|e| RandomGeneratorError::InvalidSchema(format!("not valid JSON: {}", e)))?;
+        let is_schema = value
+            .as_object()
+            .is_some_and(|object| SCHEMA_KEYWORDS.iter().any(|keyword| object.contains_key(*keyword)));
+        let root = if is_schema { schema_node(&value, "")? } else { template_node(&value, "")? };
+        Ok(JsonSchema { root, locale: FakeLocale::default() })
+    }
+
+    /// 用种子生成 `count` 个文档
+    pub fn generate(&self, count: usize, seed: Seed) -> Vec<Value> {
+        let mut rng = seed.rng();
+        (0..count).map(|_| self.root.generate(&mut rng, self.locale)).collect()
+    }
+}
+
+fn invalid(path: &str, message: impl std::fmt::Display) -> RandomGeneratorError {
+    let path = if path.is_empty() { "/" } else { path };
+    RandomGeneratorError::InvalidSchema(format!("{}: {}", path, message))
+}
+
+fn schema_node(schema: &Value, path: &str) -> Result<Node, RandomGeneratorError> {
+    let object = match schema {
+        Value::Object(object) => object,
+        // `true` 和 `{}` 一样接受任何值
+        Value::Bool(true) => return Ok(Node::String { min_length: 5, max_length: 12 }),
+        _ => return Err(invalid(path, "a schema must be an object")),
+    };
+    for keyword in ["$ref", "allOf", "not", "pattern", "patternProperties", "if"] {
+        if object.contains_key(keyword) {
+            return Err(invalid(path, format!("'{}' is not supported", keyword)));
+        }
+    }
+
+    if let Some(value) = object.get("const") {
+        return Ok(Node::Const(value.clone()));
+    }
+    if let Some(values) = object.get("enum") {
+        return match values.as_array() {
+            Some(values) if !values.is_empty() => Ok(Node::Enum(values.clone())),
+            _ => Err(invalid(path, "'enum' must be a non-empty array")),
+        };
+    }
+    if let Some(choices) = object.get("oneOf").or_else(|| object.get("anyOf")) {
+        let choices = choices.as_array().filter(|choices| !choices.is_empty());
+        let choices = choices.ok_or_else(|| invalid(path, "'oneOf' must be a non-empty array"))?;
+        return choices
+            .iter()
+            .enumerate()
+            .map(|(i, choice)| schema_node(choice, &format!("{}/oneOf/{}", path, i)))
+            .collect::<Result<_, _>>()
+            .map(Node::OneOf);
+    }
+
+    let kind = match object.get("type") {
+        Some(Value::String(kind)) => kind.as_str(),
+        // 多个类型时每次随机选一个
+        Some(Value::Array(kinds)) => {
+            let nodes = kinds
+                .iter()
+                .map(|kind| {
+                    let mut single = object.clone();
+                    single.insert("type".to_string(), kind.clone());
+                    schema_node(&Value::Object(single), path)
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            return if nodes.is_empty() { Err(invalid(path, "'type' is an empty list")) } else { Ok(Node::OneOf(nodes)) };
+        }
+        Some(_) => return Err(invalid(path, "'type' must be a string")),
+        None if object.contains_key("properties") => "object",
+        None if object.contains_key("items") => "array",
+        None if object.contains_key("format") => "string",
+        None => return Err(invalid(path, "no 'type'")),
+    };
+
+    match kind {
+        "null" => Ok(Node::Null),
+        "boolean" => Ok(Node::Boolean),
+        "integer" => integer_node(object, path),
+        "number" => number_node(object, path),
+        "string" => string_node(object, path),
+        "array" => {
+            let items = match object.get("items") {
+                Some(items) => schema_node(items, &format!("{}/items", path))?,
+                None => return Err(invalid(path, "an array needs 'items'")),
+            };
+            let min_items = usize_keyword(object, "minItems", path)?;
+            let max_items = usize_keyword(object, "maxItems", path)?;
+            let min_items = min_items.unwrap_or(1.min(max_items.unwrap_or(1)));
+            let max_items = max_items.unwrap_or(min_items.max(DEFAULT_MAX_ITEMS));
+            if min_items > max_items {
+                return Err(invalid(path, "'minItems' is larger than 'maxItems'"));
+            }
+            let unique = object.get("uniqueItems").and_then(Value::as_bool).unwrap_or(false);
+            Ok(Node::Array { items: Box::new(items), min_items, max_items, unique })
+        }
+        "object" => {
+            let properties = match object.get("properties") {
+                Some(Value::Object(properties)) => properties,
+                Some(_) => return Err(invalid(path, "'properties' must be an object")),
+                None => return Ok(Node::Object(Vec::new())),
+            };
+            properties
+                .iter()
+                .map(|(name, property)| Ok((name.clone(), schema_node(property, &format!("{}/{}", path, name))?)))
+                .collect::<Result<_, _>>()
+                .map(Node::Object)
+        }
+        other => Err(invalid(path, format!("unknown type '{}'", other))),
+    }
+}
+
+fn integer_node(object: &Map<String, Value>, path: &str) -> Result<Node, RandomGeneratorError> {
+    let bound = |keyword: &str| -> Result<Option<f64>, RandomGeneratorError> {
+        object
+            .get(keyword)
+            .map(|value| value.as_f64().ok_or_else(|| invalid(path, format!("'{}' must be a number", keyword))))
+            .transpose()
+    };
+    // 小数边界取能满足它的整数
+    let lower = [bound("minimum")?.map(f64::ceil), bound("exclusiveMinimum")?.map(|bound| bound.floor() + 1.0)]
+        .into_iter()
+        .flatten()
+        .reduce(f64::max)
+        .map(|bound| bound as i64);
+    let upper = [bound("maximum")?.map(f64::floor), bound("exclusiveMaximum")?.map(|bound| bound.ceil() - 1.0)]
+        .into_iter()
+        .flatten()
+        .reduce(f64::min)
+        .map(|bound| bound as i64);
+    let (min, max) = match (lower, upper) {
+        (Some(min), Some(max)) => (min, max),
+        (Some(min), None) => (min, min.saturating_add(100)),
+        (None, Some(max)) => (max.saturating_sub(100), max),
+        (None, None) => (0, 100),
+    };
+    let step = match object.get("multipleOf") {
+        None => 1,
+        Some(step) => step.as_i64().filter(|&step| step > 0).ok_or_else(|| invalid(path, "'multipleOf' must be a positive integer"))?,
+    };
+    // 区间内至少要有一个倍数
+    if min > max || min.div_euclid(step) + i64::from(min.rem_euclid(step) != 0) > max.div_euclid(step) {
+        return Err(invalid(path, "no integer fits 'minimum' and 'maximum'"));
+    }
+    Ok(Node::Integer { min, max, step })
+}
+
+fn number_node(object: &Map<String, Value>, path: &str) -> Result<Node, RandomGeneratorError> {
+    let bound = |keyword: &str| -> Result<Option<f64>, RandomGeneratorError> {
+        object
+            .get(keyword)
+            .map(|value| value.as_f64().ok_or_else(|| invalid(path, format!("'{}' must be a number", keyword))))
+            .transpose()
+    };
+    let lower = bound("minimum")?.or(bound("exclusiveMinimum")?);
+    let upper = bound("maximum")?.or(bound("exclusiveMaximum")?);
+    let (min, max) = match (lower, upper) {
+        (Some(min), Some(max)) => (min, max),
+        (Some(min), None) => (min, min + 100.0),
+        (None, Some(max)) => (max - 100.0, max),
+        (None, None) => (0.0, 1.0),
+    };
+    if min > max {
+        return Err(invalid(path, "'minimum' is larger than 'maximum'"));
+    }
+    let step = bound("multipleOf")?;
+    if let Some(step) = step {
+        if step <= 0.0 || (min / step).ceil() > (max / step).floor() {
+            return Err(invalid(path, "no multiple of 'multipleOf' fits 'minimum' and 'maximum'"));
+        }
+    }
+    Ok(Node::Number { min, max, step })
+}
+
+fn string_node(object: &Map<String, Value>, path: &str) -> Result<Node, RandomGeneratorError> {
+    if let Some(format) = object.get("format") {
+        let name = format.as_str().unwrap_or_default();
+        return StringFormat::parse(name)
+            .map(Node::Format)
+            .ok_or_else(|| invalid(path, format!("unknown string format '{}'", name)));
+    }
+    let min_length = usize_keyword(object, "minLength", path)?;
+    let max_length = usize_keyword(object, "maxLength", path)?;
+    let min_length = min_length.unwrap_or(5.min(max_length.unwrap_or(5)));
+    let max_length = max_length.unwrap_or(min_length.max(12));
+    if min_length > max_length {
+        return Err(invalid(path, "'minLength' is larger than 'maxLength'"));
+    }
+    Ok(Node::String { min_length, max_length })
+}
+
+fn usize_keyword(object: &Map<String, Value>, keyword: &str, path: &str) -> Result<Option<usize>, RandomGeneratorError> {
+    object
+        .get(keyword)
+        .map(|value| {
+            value
+                .as_u64()
+                .map(|value| value as usize)
+                .ok_or_else(|| invalid(path, format!("'{}' must be a non-negative integer", keyword)))
+        })
+        .transpose()
+}
+
+fn template_node(template: &Value, path: &str) -> Result<Node, RandomGeneratorError> {
+    match template {
+        Value::String(name) => match name.as_str() {
+            "integer" => Ok(Node::Integer { min: 0, max: 100, step: 1 }),
+            "number" => Ok(Node::Number { min: 0.0, max: 1.0, step: None }),
+            "boolean" => Ok(Node::Boolean),
+            "string" => Ok(Node::String { min_length: 5, max_length: 12 }),
+            other => StringFormat::parse(other)
+                .map(Node::Format)
+                .ok_or_else(|| invalid(path, format!("unknown generator '{}'", other))),
+        },
+        Value::Array(items) if items.len() == 1 => Ok(Node::Array {
+            items: Box::new(template_node(&items[0], &format!("{}/0", path))?),
+            min_items: 1,
+            max_items: DEFAULT_MAX_ITEMS,
+            unique: false,
+        }),
+        Value::Array(_) => Err(invalid(path, "a template array has exactly one element")),
+        Value::Object(properties) => properties
+            .iter()
+            .map(|(name, property)| Ok((name.clone(), template_node(property, &format!("{}/{}", path, name))?)))
+            .collect::<Result<_, _>>()
+            .map(Node::Object),
+        other => Ok(Node::Const(other.clone())),
+    }
+}
+
+impl Node {
+    fn generate(&self, rng: &mut impl Rng, locale: FakeLocale) -> Value {
+        match self {
+            Node::Const(value) => value.clone(),
+            Node::Enum(values) => values.choose(rng).cloned().unwrap_or(Value::Null),
+            Node::OneOf(nodes) => nodes.choose(rng).map_or(Value::Null, |node| node.generate(rng, locale)),
+            Node::Null => Value::Null,
+            Node::Boolean => Value::Bool(rng.gen()),
+            Node::Integer { min, max, step } => {
+                let first = min.div_euclid(*step) + i64::from(min.rem_euclid(*step) != 0);
+                Value::from(rng.gen_range(first..=max.div_euclid(*step)) * step)
+            }
+            Node::Number { min, max, step } => {
+                let number = match step {
+                    Some(step) => rng.gen_range((min / step).ceil() as i64..=(max / step).floor() as i64) as f64 * step,
+                    None if min == max => *min,
+                    None => rng.gen_range(*min..*max),
+                };
+                JsonNumber::from_f64(number).map_or(Value::Null, Value::Number)
+            }
+            Node::String { min_length, max_length } => {
+                let length = rng.gen_range(*min_length..=*max_length);
+                Value::String((0..length).map(|_| rng.gen_range(b'a'..=b'z') as char).collect())
+            }
+            Node::Format(format) => Value::String(format.generate(rng, locale)),
+            Node::Array { items, min_items, max_items, unique } => {
+                let length = rng.gen_range(*min_items..=*max_items);
+                let mut values: Vec<Value> = Vec::with_capacity(length);
+                for _ in 0..length {
+                    let mut value = items.generate(rng, locale);
+                    // 可选值太少时放弃唯一性,返回较短的数组
+                    for _ in 0..UNIQUE_ATTEMPTS {
+                        if !*unique || !values.contains(&value) {
+                            break;
+                        }
+                        value = items.generate(rng, locale);
+                    }
+                    if !*unique || !values.contains(&value) {
+                        values.push(value);
+                    }
+                }
+                Value::Array(values)
+            }
+            Node::Object(properties) => {
+                // 同一个对象里的姓名、邮箱等字段属于同一个虚构人物
+                let person = Person::random(rng, locale);
+                let object = properties.iter().map(|(name, node)| {
+                    let value = match node {
+                        Node::Format(StringFormat::Fake(field)) => Value::String(person.field(*field)),
+                        node => node.generate(rng, locale),
+                    };
+                    (name.clone(), value)
+                });
+                Value::Object(object.collect())
+            }
+        }
+    }
+}
+
+impl StringFormat {
+    fn generate(self, rng: &mut impl Rng, locale: FakeLocale) -> String {
+        match self {
+            StringFormat::Fake(field) => Person::random(rng, locale).field(field),
+            StringFormat::Date => {
+                let (year, month, day) = random_date(rng);
+                format!("{:04}-{:02}-{:02}", year, month, day)
+            }
+            StringFormat::DateTime => {
+                let (year, month, day) = random_date(rng);
+                let seconds = rng.gen_range(0..86_400);
+                format!("{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z", year, month, day, seconds / 3600, seconds / 60 % 60, seconds % 60)
+            }
+            StringFormat::Uuid => {
+                let mut bytes: [u8; 16] = rng.gen();
+                // 版本 4,RFC 4122 变体
+                bytes[6] = (bytes[6] & 0x0f) | 0x40;
+                bytes[8] = (bytes[8] & 0x3f) | 0x80;
+                let hex: String = bytes.iter().map(|byte| format!("{:02x}", byte)).collect();
+                format!("{}-{}-{}-{}-{}", &hex[..8], &hex[8..12], &hex[12..16], &hex[16..20], &hex[20..])
+            }
+            StringFormat::Uri => {
+                let length = rng.gen_range(4..=10);
+                let path: String = (0..length).map(|_| rng.gen_range(b'a'..=b'z') as char).collect();
+                format!("https://example.com/{}", path)
+            }
+        }
+    }
+}
+
+/// 1970 年到 2037 年之间的一天
+fn random_date(rng: &mut impl Rng) -> (i64, u32, u32) {
+    // 自 1970-01-01 起的天数转换为公历日期(Howard Hinnant 的 civil_from_days)
+    let days = rng.gen_range(0..24_837) + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days - era * 146_097;
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * month_index + 2) / 5 + 1) as u32;
+    let month = if month_index < 10 { month_index + 3 } else { month_index - 9 } as u32;
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
@@ -0,0 +1,244 @@
+use rand::{Rng, RngCore};
+use rand_chacha::ChaCha20Rng;
+use rhai::{Array, Dynamic, Engine, EvalAltResult, Position, AST, INT};
+use std::cell::RefCell;
+use std::fmt;
+use std::rc::Rc;
+
+use crate::source::{try_draw_with_duplicates, RepeatLimits};
+use crate::{Config, GeneratorMode, GeneratorSource, Number, Progress, RandomGeneratorError, Seed, SourceInput};
+
+/// 一次最多掷的骰子数
+pub(crate) const MAX_DICE: u32 = 1000;
+
+/// 运行一次脚本最多执行的操作数,死循环到这里就停下
+const MAX_OPERATIONS: u64 = 100_000;
+
+/// 用 [Rhai](https://rhai.rs) 写的抽取脚本,脚本最后一个表达式的值就是结果
+///
+/// ```text
+/// let dice = roll(2, 6);   // 掷两颗六面骰
+/// dice.reroll(1);          // 掷出 1 的骰子重掷一次
+/// dice.sum() + 3           // 结果是点数之和加 3
+/// ```
+///
+/// 除了 Rhai 本身的语法和函数,脚本还可以用:
+///
+/// - `roll(count, sides)`:掷一把骰子,得到骰池
+/// - `random(low, high)`:`low..=high` 中的随机整数
+/// - 骰池的 `reroll(value)`、`reroll(low, high)`:点数在范围内的骰子重掷一次
+/// - 骰池的 `keep_highest(n)`、`keep_lowest(n)`、`drop_highest(n)`、`drop_lowest(n)`
+/// - 骰池的 `sum()`、`values()` 和 `len`,两个骰池可以用 `+` 合并
+///
+/// 结果可以是整数或骰池,骰池取点数之和。脚本不能 `import` 模块或 `eval` 文本,
+/// 因而不能访问文件或网络;每次运行的操作数、骰子数和调用深度都有上限
+pub struct Script {
+    text: String,
+    engine: Engine,
+    ast: AST,
+    state: Rc<RefCell<RunState>>,
+}
+
+/// 一次运行中内置函数共用的状态
+struct RunState {
+    rng: ChaCha20Rng,
+    /// 已经掷过的骰子数
+    dice: u32,
+}
+
+/// 一颗骰子:点数和面数
+#[derive(Debug, Clone, Copy)]
+struct Die {
+    value: INT,
+    sides: INT,
+}
+
+/// 脚本中的骰池
+#[derive(Debug, Clone, Default)]
+struct Dice(Vec<Die>);
+
+impl Dice {
+    fn sum(&self) -> INT {
+        self.0.iter().fold(0, |total, die| total.saturating_add(die.value))
+    }
+
+    /// 按点数排序,`highest` 时大的在前
+    fn sort(&mut self, highest: bool) {
+        if highest {
+            self.0.sort_by_key(|die| std::cmp::Reverse(die.value));
+        } else {
+            self.0.sort_by_key(|die| die.value);
+        }
+    }
+
+    fn keep(&mut self, highest: bool, count: INT) {
+        self.sort(highest);
+        self.0.truncate(usize::try_from(count).unwrap_or(0));
+    }
+
+    fn discard(&mut self, highest: bool, count: INT) {
+        self.sort(highest);
+        let count = usize::try_from(count).unwrap_or(0).min(self.0.len());
+        self.0.drain(..count);
+    }
+}
+
+impl Script {
+    /// 编译脚本,出错时指出是第几行
+    pub fn parse(text: &str) -> Result<Self, RandomGeneratorError> {
+        let state = Rc::new(RefCell::new(RunState { rng: Seed::from([0; 32]).rng(), dice: 0 }));
+        let engine = engine(&state);
+        let ast = engine.compile(text).map_err(|e| script_error(e.1, e.0.to_string()))?;
+        Ok(Script { text: text.to_string(), engine, ast, state })
+    }
+
+    /// 运行一次脚本
+    pub fn run(&self, rng: &mut dyn RngCore) -> Result<i64, RandomGeneratorError> {
+        *self.state.borrow_mut() = RunState { rng: Seed::random(rng).rng(), dice: 0 };
+        let result = self.engine.eval_ast::<Dynamic>(&self.ast).map_err(|mut e| {
+            let position = e.take_position();
+            script_error(position, e.to_string())
+        })?;
+        if let Some(dice) = result.clone().try_cast::<Dice>() {
+            return Ok(dice.sum());
+        }
+        result.as_int().map_err(|found| RandomGeneratorError::InvalidScript {
+            line: 0,
+            message: format!("the script must end with a number, found {}", found),
+        })
+    }
+}
+
+/// 注册了骰子函数、设好上限的引擎
+fn engine(state: &Rc<RefCell<RunState>>) -> Engine {
+    let mut engine = Engine::new();
+    engine
+        .disable_symbol("eval")
+        .on_print(|_| {})
+        .on_debug(|_, _, _| {})
+        .set_strict_variables(true)
+        .set_max_operations(MAX_OPERATIONS)
+        .set_max_call_levels(32)
+        .set_max_expr_depths(64, 32)
+        .set_max_string_size(10_000)
+        .set_max_array_size(MAX_DICE as usize)
+        .set_max_map_size(MAX_DICE as usize);
+
+    engine.register_type_with_name::<Dice>("Dice");
+    let roll_state = Rc::clone(state);
+    engine.register_fn("roll", move |count: INT, sides: INT| -> Result<Dice, Box<EvalAltResult>> {
+        if sides < 1 {
+            return Err("a die needs at least one side".into());
+        }
+        let mut state = roll_state.borrow_mut();
+        let count = u32::try_from(count).map_err(|_| "a count can't be negative")?;
+        state.dice = state.dice.saturating_add(count);
+        if state.dice > MAX_DICE {
+            return Err(format!("at most {} dice in total", MAX_DICE).into());
+        }
+        Ok(Dice((0..count).map(|_| Die { value: state.rng.gen_range(1..=sides), sides }).collect()))
+    });
+    let random_state = Rc::clone(state);
+    engine.register_fn("random", move |low: INT, high: INT| -> Result<INT, Box<EvalAltResult>> {
+        if low > high {
+            return Err(format!("random({}, {}) has an empty range", low, high).into());
+        }
+        Ok(random_state.borrow_mut().rng.gen_range(low..=high))
+    });
+    let reroll_state = Rc::clone(state);
+    engine.register_fn("reroll", move |dice: &mut Dice, low: INT, high: INT| {
+        let rng = &mut reroll_state.borrow_mut().rng;
+        for die in dice.0.iter_mut().filter(|die| (low..=high).contains(&die.value)) {
+            die.value = rng.gen_range(1..=die.sides);
+        }
+    });
+    let reroll_state = Rc::clone(state);
+    engine.register_fn("reroll", move |dice: &mut Dice, value: INT| {
+        let rng = &mut reroll_state.borrow_mut().rng;
+        for die in dice.0.iter_mut().filter(|die| die.value == value) {
+            die.value = rng.gen_range(1..=die.sides);
+        }
+    });
+    engine
+        .register_fn("keep_highest", |dice: &mut Dice, count: INT| dice.keep(true, count))
+        .register_fn("keep_lowest", |dice: &mut Dice, count: INT| dice.keep(false, count))
+        .register_fn("drop_highest", |dice: &mut Dice, count: INT| dice.discard(true, count))
+        .register_fn("drop_lowest", |dice: &mut Dice, count: INT| dice.discard(false, count))
+        .register_fn("sum", |dice: &mut Dice| dice.sum())
+        .register_fn("values", |dice: &mut Dice| dice.0.iter().map(|die| Dynamic::from_int(die.value)).collect::<Array>())
+        .register_get("len", |dice: &mut Dice| dice.0.len() as INT)
+        .register_fn("+", |a: Dice, b: Dice| Dice(a.0.into_iter().chain(b.0).collect()));
+    engine
+}
+
+/// 把 Rhai 的错误转成指向出错行的错误
+fn script_error(position: Position, message: String) -> RandomGeneratorError {
+    RandomGeneratorError::InvalidScript { line: position.line().unwrap_or(0), message }
+}
+
+impl fmt::Debug for Script {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Script").field("text", &self.text).finish()
+    }
+}
+
+impl fmt::Display for Script {
+    /// 脚本原文
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.text)
+    }
+}
+
+/// 按抽取脚本抽取,每个结果是脚本独立运行一次的结果
+///
+/// 结果可能重复,与是否允许重复的设置无关
+#[derive(Debug, Clone, Default)]
+pub struct ScriptSource {
+    /// 脚本原文,见 [`Script`]
+    pub script: String,
+}
+
+impl<T: Number> GeneratorSource<T> for ScriptSource {
+    fn mode(&self) -> GeneratorMode {
+        GeneratorMode::SCRIPT
+    }
+
+    fn input(&self) -> SourceInput {
+        SourceInput::Own
+    }
+
+    /// 去掉空行后以 `\n` 字样连成一行
+    fn parameters(&self) -> Vec<(&'static str, String)> {
+        let lines: Vec<&str> = self.script.lines().map(str::trim_end).filter(|line| !line.trim().is_empty()).collect();
+        vec![("script", lines.join("\\n"))]
+    }
+
+    /// 编译后用固定的种子试运行一次,运行时的错误也能提前发现
+    fn validate(&self, _config: &Config<T>) -> Vec<RandomGeneratorError> {
+        match Script::parse(&self.script).and_then(|script| script.run(&mut Seed::from([0; 32]).rng())) {
+            Ok(_) => Vec::new(),
+            Err(e) => vec![e],
+        }
+    }
+
+    fn available(&self, _config: &Config<T>) -> Option<u128> {
+        None
+    }
+
+    fn generate(
+        &self,
+        config: &Config<T>,
+        rng: &mut dyn RngCore,
+        progress: &mut Progress<'_>,
+    ) -> Result<Vec<T>, RandomGeneratorError> {
+        let script = Script::parse(&self.script)?;
+        try_draw_with_duplicates(config.num_to_generate, RepeatLimits::of(config), progress, || {
+            let total = script.run(rng)?;
+            // 例如结果为负而输出类型是无符号数
+            T::from_i64(total).ok_or_else(|| RandomGeneratorError::InvalidScript {
+                line: 0,
+                message: format!("result {} doesn't fit the number type", total),
+            })
+        })
+    }
+}
@@ -0,0 +1,76 @@
+use rand::{RngCore, SeedableRng};
+use rand_chacha::ChaCha20Rng;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::RandomGeneratorError;
+
+/// 一次生成所用的种子
+///
+/// 每次生成都从配置的随机数来源取一个新种子,再用它初始化 ChaCha20 抽取;
+/// 记下种子和配置,就能在任何机器上重现同一次抽取。以 64 位十六进制文本显示和解析
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Seed([u8; 32]);
+
+impl Seed {
+    /// 从随机数来源取一个新种子
+    pub fn random(rng: &mut dyn RngCore) -> Self {
+        let mut bytes = [0u8; 32];
+        rng.fill_bytes(&mut bytes);
+        Seed(bytes)
+    }
+
+    /// 种子的原始字节
+    pub fn bytes(&self) -> [u8; 32] {
+        self.0
+    }
+
+    /// 由这个种子确定的随机数生成器
+    pub(crate) fn rng(self) -> ChaCha20Rng {
+        ChaCha20Rng::from_seed(self.0)
+    }
+}
+
+impl From<[u8; 32]> for Seed {
+    fn from(bytes: [u8; 32]) -> Self {
+        Seed(bytes)
+    }
+}
+
+impl fmt::Display for Seed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.iter().try_for_each(|byte| write!(f, "{:02x}", byte))
+    }
+}
+
+impl FromStr for Seed {
+    type Err = RandomGeneratorError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.len() != 64 || !s.is_ascii() {
+            return Err(RandomGeneratorError::InvalidSeed);
+        }
+        let mut bytes = [0u8; 32];
+        for (byte, pair) in bytes.iter_mut().zip(s.as_bytes().chunks(2)) {
+            let pair = std::str::from_utf8(pair).map_err(|_| RandomGeneratorError::InvalidSeed)?;
+            *byte = u8::from_str_radix(pair, 16).map_err(|_| RandomGeneratorError::InvalidSeed)?;
+        }
+        Ok(Seed(bytes))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Seed {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Seed {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        text.parse().map_err(serde::de::Error::custom)
+    }
+}
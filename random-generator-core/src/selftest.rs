@@ -0,0 +1,212 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::atomic::AtomicBool;
+
+use crate::{Config, Generator, GeneratorMode, ListDuplicates, Number, Progress, RandomGeneratorError};
+
+/// 区间模式下分组的最大数量
+const MAX_BINS: u128 = 20;
+
+/// 低于这个 p 值就认为分布可疑;取得较严,正常的生成器很少误报
+const SIGNIFICANCE: f64 = 0.001;
+
+/// 统计自检的结果
+#[derive(Debug, Clone, PartialEq)]
+pub struct SelfTestReport {
+    /// 抽取的样本数
+    pub samples: usize,
+    /// 卡方均匀性检验,当前模式无法分组时为 `None`
+    pub chi_squared: Option<ChiSquared>,
+    /// 游程检验
+    pub runs: RunsTest,
+}
+
+/// 卡方均匀性检验:各组的实际次数与期望次数是否相符
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChiSquared {
+    /// 卡方统计量
+    pub statistic: f64,
+    /// 自由度
+    pub degrees_of_freedom: usize,
+    /// 近似 p 值
+    pub p_value: f64,
+}
+
+/// 游程检验:相对中位数的高低是否像独立抽取那样交替
+#[derive(Debug, Clone, PartialEq)]
+pub struct RunsTest {
+    /// 实际的游程数
+    pub runs: usize,
+    /// 独立抽取时期望的游程数
+    pub expected: f64,
+    /// 标准分
+    pub z: f64,
+    /// 双侧 p 值
+    pub p_value: f64,
+}
+
+impl SelfTestReport {
+    /// 所有检验都没有发现异常
+    pub fn is_healthy(&self) -> bool {
+        self.chi_squared.as_ref().is_none_or(|chi| chi.p_value >= SIGNIFICANCE) && self.runs.p_value >= SIGNIFICANCE
+    }
+}
+
+impl fmt::Display for SelfTestReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Samples: {}", self.samples)?;
+        match &self.chi_squared {
+            Some(chi) => writeln!(
+                f,
+                "Chi-squared uniformity: {:.2} with {} degrees of freedom, p = {:.4}",
+                chi.statistic, chi.degrees_of_freedom, chi.p_value
+            )?,
+            None => writeln!(f, "Chi-squared uniformity: not available for this mode")?,
+        }
+        writeln!(
+            f,
+            "Runs test: {} runs, {:.1} expected, z = {:.2}, p = {:.4}",
+            self.runs.runs, self.runs.expected, self.runs.z, self.runs.p_value
+        )?;
+        write!(f, "Result: {}", if self.is_healthy() { "looks healthy" } else { "looks suspicious" })
+    }
+}
+
+impl<T: Number> Generator<T> {
+    /// 按当前配置抽取 `samples` 个样本做统计自检
+    ///
+    /// 样本总是允许重复地抽取,这样每次抽取相互独立;已生成的结果和事件回调都不受影响
+    pub fn self_test(&mut self, samples: usize) -> Result<SelfTestReport, RandomGeneratorError> {
+        let config = Config { allow_duplicates: true, num_to_generate: samples, ..self.config.clone() };
+        self.validate_config(&config)?;
+        let source = self.registry.get(config.mode).ok_or(RandomGeneratorError::UnknownMode(config.mode))?;
+
+        let cancel = AtomicBool::new(false);
+        let mut progress = Progress { cancel: &cancel, on_progress: &mut |_, _| {} };
+        let values = source.generate(&config, &mut *self.rng, &mut progress)?;
+
+        let chi_squared = bins(&config).map(|(expected, bin_of)| {
+            let mut observed = vec![0usize; expected.len()];
+            for &value in &values {
+                if let Some(bin) = bin_of(value) {
+                    observed[bin] += 1;
+                }
+            }
+            chi_squared(&observed, &expected)
+        });
+
+        Ok(SelfTestReport { samples: values.len(), chi_squared, runs: runs_test(&values) })
+    }
+}
+
+type BinOf<T> = Box<dyn Fn(T) -> Option<usize>>;
+
+/// 每组的期望概率,以及把数字映射到组的函数
+fn bins<T: Number>(config: &Config<T>) -> Option<(Vec<f64>, BinOf<T>)> {
+    let (lower, upper) = (config.lower_bound, config.upper_bound);
+    match config.mode {
+        GeneratorMode::RANGE => match T::values_between(lower, upper) {
+            // 离散范围:按偏移量均分,整数个数不能整除时按实际个数计算期望
+            Some(size) if size <= u64::MAX as u128 => {
+                let count = size.min(MAX_BINS);
+                let expected = (0..count)
+                    .map(|bin| ((bin + 1) * size / count - bin * size / count) as f64 / size as f64)
+                    .collect();
+                let bin_of = move |value: T| T::values_between(lower, value).map(|offset| ((offset - 1) * count / size) as usize);
+                Some((expected, Box::new(bin_of) as BinOf<T>))
+            }
+            // 连续或极大的范围:按数值等宽分组
+            _ => {
+                let (low, width) = (lower.as_f64(), upper.as_f64() - lower.as_f64());
+                if width <= 0.0 {
+                    return None;
+                }
+                let count = MAX_BINS as usize;
+                let bin_of = move |value: T| Some((((value.as_f64() - low) / width * count as f64) as usize).min(count - 1));
+                Some((vec![1.0 / count as f64; count], Box::new(bin_of) as BinOf<T>))
+            }
+        },
+        GeneratorMode::CUSTOM_LIST => {
+            let mut weights: HashMap<u128, (usize, f64)> = HashMap::new();
+            for value in &config.custom_list {
+                let next = weights.len();
+                let entry = weights.entry(value.key()).or_insert((next, 0.0));
+                entry.1 = match config.list_duplicates {
+                    ListDuplicates::Weighted => entry.1 + 1.0,
+                    ListDuplicates::Dedupe | ListDuplicates::Reject => 1.0,
+                };
+            }
+            let total: f64 = weights.values().map(|&(_, weight)| weight).sum();
+            let mut expected = vec![0.0; weights.len()];
+            for &(bin, weight) in weights.values() {
+                expected[bin] = weight / total;
+            }
+            let bin_of = move |value: T| weights.get(&value.key()).map(|&(bin, _)| bin);
+            Some((expected, Box::new(bin_of) as BinOf<T>))
+        }
+        _ => None,
+    }
+}
+
+fn chi_squared(observed: &[usize], expected: &[f64]) -> ChiSquared {
+    let total: usize = observed.iter().sum();
+    let statistic = observed
+        .iter()
+        .zip(expected)
+        .filter(|(_, &p)| p > 0.0)
+        .map(|(&o, &p)| {
+            let e = p * total as f64;
+            (o as f64 - e).powi(2) / e
+        })
+        .sum();
+    let degrees_of_freedom = expected.len().saturating_sub(1);
+    let p_value = if degrees_of_freedom == 0 { 1.0 } else { chi_squared_tail(statistic, degrees_of_freedom as f64) };
+    ChiSquared { statistic, degrees_of_freedom, p_value }
+}
+
+/// Wald-Wolfowitz 游程检验,以中位数为界,等于中位数的值不计
+fn runs_test<T: Number>(values: &[T]) -> RunsTest {
+    let mut sorted: Vec<f64> = values.iter().map(|v| v.as_f64()).collect();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    let median = sorted.get(sorted.len() / 2).copied().unwrap_or_default();
+
+    let signs: Vec<bool> = values.iter().map(|v| v.as_f64()).filter(|&v| v != median).map(|v| v > median).collect();
+    let above = signs.iter().filter(|&&s| s).count() as f64;
+    let below = signs.len() as f64 - above;
+    let runs = if signs.is_empty() { 0 } else { 1 + signs.windows(2).filter(|pair| pair[0] != pair[1]).count() };
+
+    let n = above + below;
+    if above == 0.0 || below == 0.0 {
+        // 全部在中位数同一侧(例如只有一个可选值),没有可检验的交替
+        return RunsTest { runs, expected: runs as f64, z: 0.0, p_value: 1.0 };
+    }
+    let expected = 2.0 * above * below / n + 1.0;
+    let variance = 2.0 * above * below * (2.0 * above * below - n) / (n * n * (n - 1.0));
+    let z = if variance > 0.0 { (runs as f64 - expected) / variance.sqrt() } else { 0.0 };
+    RunsTest { runs, expected, z, p_value: 2.0 * normal_tail(z.abs()) }
+}
+
+/// 卡方分布的右尾概率,Wilson-Hilferty 近似
+fn chi_squared_tail(statistic: f64, degrees_of_freedom: f64) -> f64 {
+    let k = degrees_of_freedom;
+    let z = ((statistic / k).cbrt() - (1.0 - 2.0 / (9.0 * k))) / (2.0 / (9.0 * k)).sqrt();
+    normal_tail(z)
+}
+
+/// 标准正态分布的右尾概率
+fn normal_tail(z: f64) -> f64 {
+    0.5 * erfc(z / std::f64::consts::SQRT_2)
+}
+
+/// 互补误差函数,Numerical Recipes 中的切比雪夫近似,相对误差小于 1.2e-7
+fn erfc(x: f64) -> f64 {
+    let t = 1.0 / (1.0 + 0.5 * x.abs());
+    let y = t * (-x * x - 1.26551223
+        + t * (1.00002368
+            + t * (0.37409196
+                + t * (0.09678418
+                    + t * (-0.18628806
+                        + t * (0.27886807 + t * (-1.13520398 + t * (1.48851587 + t * (-0.82215223 + t * 0.17087277)))))))))
+        .exp();
+    if x >= 0.0 { y } else { 2.0 - y }
+}
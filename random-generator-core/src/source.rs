@@ -0,0 +1,610 @@
+use rand::{Rng, RngCore};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use crate::exclusion::skip_to;
+use crate::{Config, ExclusionSet, GeometricSource, ListDuplicates, NegativeBinomialSource, Number, PartitionSource, PrimeSource, Progress, RandomGeneratorError, ScriptSource, StratifiedSource, TicketSource, PROGRESS_INTERVAL};
+
+/// 洗牌袋和冷却最多列出的数字个数,更大的区间只能独立抽取
+pub const MAX_POOL: u128 = 1_000_000;
+
+/// 限制重复次数或不重复抽取时,连续这么多次都抽到不能用的值就放弃
+const REPEAT_REJECTIONS: usize = 1_000_000;
+
+/// 生成器模式,即注册表中某个 [`GeneratorSource`] 的名称
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct GeneratorMode(&'static str);
+
+impl GeneratorMode {
+    /// 在 `lower_bound..=upper_bound` 中抽取
+    pub const RANGE: GeneratorMode = GeneratorMode("Range");
+    /// 在自定义列表中抽取
+    pub const CUSTOM_LIST: GeneratorMode = GeneratorMode("Custom List");
+    /// 运行抽取脚本,见 [`Script`](crate::Script)
+    pub const SCRIPT: GeneratorMode = GeneratorMode("Dice Script");
+    /// 在 `lower_bound..=upper_bound` 中抽取素数,见 [`PrimeSource`](crate::PrimeSource)
+    pub const PRIMES: GeneratorMode = GeneratorMode("Primes");
+    /// 把区间或列表分成几层,每层各抽指定的个数,见 [`Strata`](crate::Strata)
+    pub const STRATIFIED: GeneratorMode = GeneratorMode("Stratified");
+    /// 把总和随机拆成几份,每份在 `lower_bound..=upper_bound` 中,见 [`PartitionSource`](crate::PartitionSource)
+    pub const PARTITION: GeneratorMode = GeneratorMode("Partition");
+    /// 抽取直到第一次成功的试验次数,见 [`GeometricSource`](crate::GeometricSource)
+    pub const GEOMETRIC: GeneratorMode = GeneratorMode("Geometric");
+    /// 抽取直到第 N 次成功的试验次数,见 [`NegativeBinomialSource`](crate::NegativeBinomialSource)
+    pub const NEGATIVE_BINOMIAL: GeneratorMode = GeneratorMode("Negative Binomial");
+    /// 在已经发出的票号中抽取,见 [`TicketSource`](crate::TicketSource)
+    pub const TICKETS: GeneratorMode = GeneratorMode("Tickets");
+
+    /// 以名称创建模式,名称同时用于显示
+    pub const fn new(name: &'static str) -> Self {
+        GeneratorMode(name)
+    }
+
+    /// 模式名称
+    pub fn name(self) -> &'static str {
+        self.0
+    }
+}
+
+impl fmt::Display for GeneratorMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// 一种模式需要用户填写的输入
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SourceInput {
+    /// 使用配置中的上下界
+    Bounds,
+    /// 使用配置中的自定义列表
+    List,
+    /// 只使用抽取方式自己的参数,例如骰子脚本;改动参数时注册一个新的抽取方式
+    Own,
+}
+
+/// 一种抽取方式
+///
+/// 实现后通过 [`Generator::register_source`](crate::Generator::register_source) 注册,
+/// 就可以像内置模式一样选择和使用,无需修改核心代码
+pub trait GeneratorSource<T: Number>: Send + Sync {
+    /// 对应的模式
+    fn mode(&self) -> GeneratorMode;
+
+    /// 需要用户填写的输入
+    fn input(&self) -> SourceInput;
+
+    /// 抽取方式自己的参数,以名称和值列出,用于记录抽取;默认没有
+    fn parameters(&self) -> Vec<(&'static str, String)> {
+        Vec::new()
+    }
+
+    /// 列出配置中不适用于这种模式的所有问题,没有问题时返回空列表;
+    /// 数量是否足够由生成器统一检查
+    fn validate(&self, config: &Config<T>) -> Vec<RandomGeneratorError>;
+
+    /// 可以抽到的不同数字个数,`None` 表示不限
+    fn available(&self, config: &Config<T>) -> Option<u128>;
+
+    /// 可以抽到的全部数字,用于装满 [`ShuffleBag`](crate::ShuffleBag);按权重抽取的数字重复出现。
+    /// 默认为 `None`,表示这种模式不能用洗牌袋
+    fn pool(&self, _config: &Config<T>) -> Option<Vec<T>> {
+        None
+    }
+
+    /// 按配置抽取数字
+    ///
+    /// 较长的循环应定期调用 [`Progress::checkpoint`],以便回报进度和响应取消
+    fn generate(
+        &self,
+        config: &Config<T>,
+        rng: &mut dyn RngCore,
+        progress: &mut Progress<'_>,
+    ) -> Result<Vec<T>, RandomGeneratorError>;
+}
+
+/// 模式注册表,按注册顺序保存所有可用的抽取方式
+pub struct ModeRegistry<T: Number> {
+    sources: Vec<Box<dyn GeneratorSource<T>>>,
+}
+
+impl<T: Number> ModeRegistry<T> {
+    /// 空注册表
+    pub fn empty() -> Self {
+        Self { sources: Vec::new() }
+    }
+
+    /// 注册一种抽取方式,同名的旧方式会被替换
+    pub fn register(&mut self, source: Box<dyn GeneratorSource<T>>) {
+        match self.sources.iter().position(|s| s.mode() == source.mode()) {
+            Some(index) => self.sources[index] = source,
+            None => self.sources.push(source),
+        }
+    }
+
+    /// 查找模式对应的抽取方式
+    pub fn get(&self, mode: GeneratorMode) -> Option<&dyn GeneratorSource<T>> {
+        self.sources.iter().find(|s| s.mode() == mode).map(|s| s.as_ref())
+    }
+
+    /// 所有已注册的模式
+    pub fn modes(&self) -> Vec<GeneratorMode> {
+        self.sources.iter().map(|s| s.mode()).collect()
+    }
+}
+
+impl<T: Number> Default for ModeRegistry<T> {
+    /// 包含内置的区间、自定义列表、骰子脚本、素数、分层抽样、拆分、几何分布、负二项分布和发票抽奖模式,
+    /// 带参数的模式使用默认参数
+    fn default() -> Self {
+        let mut registry = Self::empty();
+        registry.register(Box::new(RangeSource));
+        registry.register(Box::new(ListSource));
+        registry.register(Box::new(ScriptSource::default()));
+        registry.register(Box::new(PrimeSource));
+        registry.register(Box::new(StratifiedSource::default()));
+        registry.register(Box::new(PartitionSource::<T>::default()));
+        registry.register(Box::new(GeometricSource::default()));
+        registry.register(Box::new(NegativeBinomialSource::default()));
+        registry.register(Box::new(TicketSource));
+        registry
+    }
+}
+
+/// 在 `lower_bound..=upper_bound` 中抽取
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RangeSource;
+
+impl<T: Number> GeneratorSource<T> for RangeSource {
+    fn mode(&self) -> GeneratorMode {
+        GeneratorMode::RANGE
+    }
+
+    fn input(&self) -> SourceInput {
+        SourceInput::Bounds
+    }
+
+    fn validate(&self, config: &Config<T>) -> Vec<RandomGeneratorError> {
+        let (lower, upper) = (config.lower_bound, config.upper_bound);
+        if !T::is_valid_range(lower, upper) {
+            return vec![RandomGeneratorError::InvalidBounds];
+        }
+        // 连续的区间按可表示的值计算,只有一个值时也会抽完
+        let left = T::representable_between(lower, upper);
+        let excluded = config.exclude_drawn.as_ref().map_or(0, |excluded| excluded.count_between(lower, upper));
+        if excluded as u128 >= left {
+            return vec![RandomGeneratorError::InvalidDraw("every value in the range was drawn already; clear the excluded values".to_string())];
+        }
+        Vec::new()
+    }
+
+    fn available(&self, config: &Config<T>) -> Option<u128> {
+        let size = T::representable_between(config.lower_bound, config.upper_bound);
+        let excluded = config.exclude_drawn.as_ref().map_or(0, |excluded| excluded.count_between(config.lower_bound, config.upper_bound));
+        Some(size.saturating_sub(excluded as u128))
+    }
+
+    fn pool(&self, config: &Config<T>) -> Option<Vec<T>> {
+        let size = self.available(config).filter(|&size| size <= MAX_POOL)?;
+        Some((0..size).map(|i| T::nth_from(config.lower_bound, i)).collect())
+    }
+
+    fn generate(
+        &self,
+        config: &Config<T>,
+        rng: &mut dyn RngCore,
+        progress: &mut Progress<'_>,
+    ) -> Result<Vec<T>, RandomGeneratorError> {
+        let (lower, upper) = (config.lower_bound, config.upper_bound);
+        if let Some(excluded) = config.exclude_drawn.as_ref().filter(|excluded| !excluded.is_empty()) {
+            return Self::generate_excluding(config, excluded, rng, progress);
+        }
+        if config.allow_duplicates {
+            return draw_with_duplicates(config.num_to_generate, RepeatLimits::of(config), progress, || rng.gen_range(lower..=upper));
+        }
+
+        // 连续类型按位置抽取就不均匀了,总是用集合
+        let count = config.num_to_generate;
+        if T::values_between(lower, upper).is_none() {
+            return draw_distinct(count, progress, || rng.gen_range(lower..=upper));
+        }
+        let range_size = self.available(config).unwrap_or_default();
+        let indices = UniqueStrategy::for_range(count, range_size).indices(rng, range_size, count, progress)?;
+        Ok(indices.into_iter().map(|i| T::nth_from(lower, i)).collect())
+    }
+}
+
+impl RangeSource {
+    /// 跳过排除集合中的值抽取
+    ///
+    /// 离散的区间按位置抽取,再跳过被排除的位置,不需要反复重抽;连续的区间几乎不会抽到相同的值,抽到时重抽
+    fn generate_excluding<T: Number>(
+        config: &Config<T>,
+        excluded: &ExclusionSet<T>,
+        rng: &mut dyn RngCore,
+        progress: &mut Progress<'_>,
+    ) -> Result<Vec<T>, RandomGeneratorError> {
+        let (lower, upper, count) = (config.lower_bound, config.upper_bound, config.num_to_generate);
+        let Some(size) = T::values_between(lower, upper) else {
+            let draw = || loop {
+                let value = rng.gen_range(lower..=upper);
+                if !excluded.contains(value) {
+                    return value;
+                }
+            };
+            return if config.allow_duplicates {
+                draw_with_duplicates(count, RepeatLimits::of(config), progress, draw)
+            } else {
+                draw_distinct(count, progress, draw)
+            };
+        };
+
+        let skipped = excluded.indices_between(lower, upper);
+        let left = size - skipped.len() as u128;
+        if left == 0 {
+            return Err(RandomGeneratorError::InvalidDraw("every value in the range was drawn already; clear the excluded values".to_string()));
+        }
+        if config.allow_duplicates {
+            return draw_with_duplicates(count, RepeatLimits::of(config), progress, || T::nth_from(lower, skip_to(&skipped, rng.gen_range(0..left))));
+        }
+        let indices = UniqueStrategy::for_range(count, left).indices(rng, left, count, progress)?;
+        Ok(indices.into_iter().map(|i| T::nth_from(lower, skip_to(&skipped, i))).collect())
+    }
+}
+
+/// 从离散范围中不重复抽取的算法
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UniqueStrategy {
+    /// 按位置部分洗牌,适合数量接近范围大小的情况
+    Shuffle,
+    /// 反复抽取并丢弃已有的数字
+    Set,
+    /// Floyd 算法,每个数字只抽一次,适合范围远大于数量的情况
+    Floyd,
+}
+
+impl UniqueStrategy {
+    /// 所有算法
+    pub const ALL: [UniqueStrategy; 3] = [UniqueStrategy::Shuffle, UniqueStrategy::Set, UniqueStrategy::Floyd];
+
+    /// 生成时自动选用的算法:数量接近范围大小时部分洗牌,避免反复抽到已有的数字;
+    /// 否则用 Floyd 算法,抽取次数与范围大小无关
+    pub fn for_range(count: usize, range_size: u128) -> Self {
+        if count as f64 > range_size as f64 * 0.5 {
+            UniqueStrategy::Shuffle
+        } else {
+            UniqueStrategy::Floyd
+        }
+    }
+
+    /// 用这种算法从 `0..size` 中不重复地抽取 `count` 个位置
+    pub(crate) fn indices(
+        self,
+        rng: &mut dyn RngCore,
+        size: u128,
+        count: usize,
+        progress: &mut Progress<'_>,
+    ) -> Result<Vec<u128>, RandomGeneratorError> {
+        match self {
+            UniqueStrategy::Shuffle => sample_indices(rng, size, count, progress),
+            UniqueStrategy::Set => set_indices(rng, size, count, progress),
+            UniqueStrategy::Floyd => floyd_indices(rng, size, count, progress),
+        }
+    }
+}
+
+impl fmt::Display for UniqueStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UniqueStrategy::Shuffle => write!(f, "Partial shuffle"),
+            UniqueStrategy::Set => write!(f, "Rejection set"),
+            UniqueStrategy::Floyd => write!(f, "Floyd"),
+        }
+    }
+}
+
+/// 在自定义列表中抽取
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ListSource;
+
+impl ListSource {
+    /// 每个不同的值和它被抽中的相对机会,按第一次出现的顺序
+    ///
+    /// 重复的条目按权重计时,出现几次机会就是几倍;否则每个值的机会相同
+    pub fn weights<T: Number>(config: &Config<T>) -> Vec<(T, usize)> {
+        let mut counts = HashMap::new();
+        for value in &config.custom_list {
+            *counts.entry(value.key()).or_insert(0) += 1;
+        }
+        distinct(&config.custom_list)
+            .into_iter()
+            .map(|value| {
+                let weight = if config.list_duplicates == ListDuplicates::Weighted { counts[&value.key()] } else { 1 };
+                (value, weight)
+            })
+            .collect()
+    }
+}
+
+impl<T: Number> GeneratorSource<T> for ListSource {
+    fn mode(&self) -> GeneratorMode {
+        GeneratorMode::CUSTOM_LIST
+    }
+
+    fn input(&self) -> SourceInput {
+        SourceInput::List
+    }
+
+    fn validate(&self, config: &Config<T>) -> Vec<RandomGeneratorError> {
+        if config.custom_list.is_empty() {
+            return vec![RandomGeneratorError::EmptyList];
+        }
+        if config.list_duplicates == ListDuplicates::Reject && distinct(&config.custom_list).len() < config.custom_list.len() {
+            return vec![RandomGeneratorError::DuplicateEntries];
+        }
+        Vec::new()
+    }
+
+    fn available(&self, config: &Config<T>) -> Option<u128> {
+        // 重复的条目只能抽到一次
+        Some(distinct(&config.custom_list).len() as u128)
+    }
+
+    fn pool(&self, config: &Config<T>) -> Option<Vec<T>> {
+        Some(match config.list_duplicates {
+            ListDuplicates::Weighted => config.custom_list.clone(),
+            ListDuplicates::Dedupe | ListDuplicates::Reject => distinct(&config.custom_list),
+        })
+    }
+
+    fn generate(
+        &self,
+        config: &Config<T>,
+        rng: &mut dyn RngCore,
+        progress: &mut Progress<'_>,
+    ) -> Result<Vec<T>, RandomGeneratorError> {
+        // 按权重时保留重复的条目,重复几次就有几倍的机会被抽中
+        let list = self.pool(config).unwrap_or_default();
+        if config.allow_duplicates {
+            return draw_with_duplicates(config.num_to_generate, RepeatLimits::of(config), progress, || list[rng.gen_range(0..list.len())]);
+        }
+
+        // 如果需要生成的数量接近可选数字的个数,使用洗牌算法;
+        // 打乱后按第一次出现的顺序取不同的值,相当于按权重逐个不放回地抽取
+        let values = self.available(config).unwrap_or_default();
+        if config.num_to_generate as f64 > values as f64 * 0.5 {
+            let mut shuffled_list = list;
+            shuffle(rng, &mut shuffled_list, progress)?;
+            let mut numbers = distinct(&shuffled_list);
+            numbers.truncate(config.num_to_generate);
+            Ok(numbers)
+        } else {
+            draw_distinct(config.num_to_generate, progress, || list[rng.gen_range(0..list.len())])
+        }
+    }
+}
+
+/// 去掉重复的值,保留每个值第一次出现的位置
+fn distinct<T: Number>(values: &[T]) -> Vec<T> {
+    let mut seen = HashSet::with_capacity(values.len());
+    values.iter().copied().filter(|value| seen.insert(value.key())).collect()
+}
+
+/// 允许重复时对重复的限制,见 [`Config::max_repeats`](crate::Config::max_repeats) 和 [`Config::max_streak`](crate::Config::max_streak)
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct RepeatLimits {
+    /// 每个值最多出现几次
+    pub(crate) per_value: Option<usize>,
+    /// 同一个值最多连续出现几次
+    pub(crate) streak: Option<usize>,
+}
+
+impl RepeatLimits {
+    pub(crate) fn of<T: Number>(config: &Config<T>) -> Self {
+        Self { per_value: config.max_repeats, streak: config.max_streak }
+    }
+
+    /// 一次生成最多能抽到几个数字,`available` 是不同数字的个数;`None` 表示不限,上限为 0 另外报错
+    pub(crate) fn capacity(self, available: Option<u128>) -> Option<u128> {
+        let available = available?;
+        let per_value = self.per_value.filter(|&max| max > 0).map(|max| available.saturating_mul(max as u128));
+        // 只有一个值时只能一直抽到它
+        let streak = self.streak.filter(|&max| max > 0 && available == 1).map(|max| max as u128);
+        match (per_value, streak) {
+            (Some(per_value), Some(streak)) => Some(per_value.min(streak)),
+            (per_value, streak) => per_value.or(streak),
+        }
+    }
+}
+
+/// 抽取 `total` 个允许重复的数字
+///
+/// 有限制时记下每个值出现的次数和最后一个值连续出现的次数,抽到超出限制的值就重抽
+pub(crate) fn draw_with_duplicates<T: Number>(
+    total: usize,
+    limits: RepeatLimits,
+    progress: &mut Progress<'_>,
+    mut draw: impl FnMut() -> T,
+) -> Result<Vec<T>, RandomGeneratorError> {
+    try_draw_with_duplicates(total, limits, progress, || Ok(draw()))
+}
+
+/// 同 [`draw_with_duplicates`],但每次抽取都可能出错,出错即停止
+pub(crate) fn try_draw_with_duplicates<T: Number>(
+    total: usize,
+    limits: RepeatLimits,
+    progress: &mut Progress<'_>,
+    mut draw: impl FnMut() -> Result<T, RandomGeneratorError>,
+) -> Result<Vec<T>, RandomGeneratorError> {
+    let mut tracker = RepeatTracker::new(limits, total);
+    let mut numbers = Vec::new();
+    let mut attempts = 0usize;
+    while numbers.len() < total {
+        if attempts.is_multiple_of(PROGRESS_INTERVAL) {
+            progress.checkpoint(numbers.len(), total)?;
+        }
+        attempts += 1;
+        let num = draw()?;
+        if tracker.accept(num)? {
+            numbers.push(num);
+        }
+    }
+    Ok(numbers)
+}
+
+/// 允许重复时的重复限制记录:每个值出现的次数和最后一个值连续出现的次数
+pub(crate) struct RepeatTracker {
+    limits: RepeatLimits,
+    /// 要抽的个数,只用于报错
+    total: usize,
+    times: HashMap<u128, usize>,
+    last: Option<u128>,
+    run: usize,
+    /// 连续被拒绝的次数
+    rejected: usize,
+}
+
+impl RepeatTracker {
+    pub(crate) fn new(limits: RepeatLimits, total: usize) -> Self {
+        Self { limits, total, times: HashMap::new(), last: None, run: 0, rejected: 0 }
+    }
+
+    /// 记下抽到的值并返回是否可用;超出限制时返回 `false`,应当重抽,连续太多次超出限制时报错
+    pub(crate) fn accept<T: Number>(&mut self, num: T) -> Result<bool, RandomGeneratorError> {
+        let key = num.key();
+        let repeated = self.limits.per_value.is_some_and(|max| self.times.get(&key).copied().unwrap_or(0) >= max);
+        let streak = self.limits.streak.is_some_and(|max| self.last == Some(key) && self.run >= max);
+        if repeated || streak {
+            // 骰子脚本可能只有几种结果,抽到后面也可能只剩连着的那个值,事先都无从检查
+            self.rejected += 1;
+            if self.rejected > REPEAT_REJECTIONS {
+                return Err(RandomGeneratorError::InvalidDraw(format!("could not draw {} values within the repeat limits", self.total)));
+            }
+            return Ok(false);
+        }
+        self.rejected = 0;
+        if self.limits.per_value.is_some() {
+            *self.times.entry(key).or_insert(0) += 1;
+        }
+        self.run = if self.last == Some(key) { self.run + 1 } else { 1 };
+        self.last = Some(key);
+        Ok(true)
+    }
+}
+
+/// 反复抽取,直到得到 `total` 个不同的数字,按第一次抽到的先后排列
+pub(crate) fn draw_distinct<T: Number>(
+    total: usize,
+    progress: &mut Progress<'_>,
+    mut draw: impl FnMut() -> T,
+) -> Result<Vec<T>, RandomGeneratorError> {
+    let mut seen = HashSet::new();
+    let mut numbers = Vec::new();
+    let mut attempts = 0usize;
+    let mut rejected = 0usize;
+
+    while numbers.len() < total {
+        if attempts.is_multiple_of(PROGRESS_INTERVAL) {
+            progress.checkpoint(numbers.len(), total)?;
+        }
+        attempts += 1;
+        let num = draw();
+        if seen.insert(num.key()) {
+            rejected = 0;
+            numbers.push(num);
+        } else {
+            // 可表示的值很少的浮点区间,或者脚本只有几种结果,都可能一直抽到已有的值
+            rejected += 1;
+            if rejected > REPEAT_REJECTIONS {
+                return Err(RandomGeneratorError::InvalidDraw(format!("could not draw {} different values", total)));
+            }
+        }
+    }
+
+    Ok(numbers)
+}
+
+/// 从 `0..size` 中反复抽取位置并丢弃已有的,直到得到 `count` 个
+fn set_indices(
+    rng: &mut dyn RngCore,
+    size: u128,
+    count: usize,
+    progress: &mut Progress<'_>,
+) -> Result<Vec<u128>, RandomGeneratorError> {
+    let mut seen = HashSet::new();
+    let mut indices = Vec::new();
+    let mut attempts = 0usize;
+    while indices.len() < count {
+        if attempts.is_multiple_of(PROGRESS_INTERVAL) {
+            progress.checkpoint(indices.len(), count)?;
+        }
+        attempts += 1;
+        let index = rng.gen_range(0..size);
+        if seen.insert(index) {
+            indices.push(index);
+        }
+    }
+    Ok(indices)
+}
+
+/// 从 `0..size` 中不重复地抽取 `count` 个位置,按抽出的顺序排列
+///
+/// 部分 Fisher-Yates 洗牌,只记录被交换过的位置,内存与 `count` 成正比而不是与 `size` 成正比
+pub(crate) fn sample_indices(
+    rng: &mut dyn RngCore,
+    size: u128,
+    count: usize,
+    progress: &mut Progress<'_>,
+) -> Result<Vec<u128>, RandomGeneratorError> {
+    let mut swapped: HashMap<u128, u128> = HashMap::new();
+    let mut indices = Vec::new();
+    for i in 0..count {
+        if i.is_multiple_of(PROGRESS_INTERVAL) {
+            progress.checkpoint(i, count)?;
+        }
+        let i = i as u128;
+        let j = rng.gen_range(i..size);
+        let picked = swapped.get(&j).copied().unwrap_or(j);
+        let current = swapped.get(&i).copied().unwrap_or(i);
+        swapped.insert(j, current);
+        indices.push(picked);
+    }
+    Ok(indices)
+}
+
+/// 从 `0..size` 中不重复地抽取 `count` 个位置,适合 `size` 远大于 `count` 的情况
+///
+/// Floyd 算法只需要 `count` 次抽取;它得到的先后顺序并不均匀,因此最后再洗牌一次
+pub(crate) fn floyd_indices(
+    rng: &mut dyn RngCore,
+    size: u128,
+    count: usize,
+    progress: &mut Progress<'_>,
+) -> Result<Vec<u128>, RandomGeneratorError> {
+    let mut seen = HashSet::new();
+    let mut indices = Vec::new();
+    for (n, j) in (size - count as u128..size).enumerate() {
+        if n.is_multiple_of(PROGRESS_INTERVAL) {
+            progress.checkpoint(n, count)?;
+        }
+        let t = rng.gen_range(0..=j);
+        let picked = if seen.insert(t) { t } else { j };
+        seen.insert(picked);
+        indices.push(picked);
+    }
+    shuffle(rng, &mut indices, progress)?;
+    Ok(indices)
+}
+
+/// Fisher-Yates 洗牌算法
+pub(crate) fn shuffle<T>(rng: &mut dyn RngCore, values: &mut [T], progress: &mut Progress<'_>) -> Result<(), RandomGeneratorError> {
+    let total = values.len();
+    for i in (1..total).rev() {
+        if i.is_multiple_of(PROGRESS_INTERVAL) {
+            progress.checkpoint(total - i, total)?;
+        }
+        let j = rng.gen_range(0..=i);
+        values.swap(i, j);
+    }
+    Ok(())
+}
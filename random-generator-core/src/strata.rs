@@ -0,0 +1,247 @@
+use rand::RngCore;
+use std::collections::HashSet;
+
+use crate::source::RepeatLimits;
+use crate::{Config, GeneratorMode, GeneratorSource, ListDuplicates, ListSource, Number, Progress, RandomGeneratorError, RangeSource, SourceInput, MAX_COUNT};
+
+/// 分层抽样:把区间或列表分成几层,每层各抽指定的个数
+///
+/// 每行(或以 `;` 分隔的)一层,写成 `区间或数字: 个数`,`#` 之后是注释:
+///
+/// ```text
+/// 1..100: 3      # 从 1 到 100 中抽 3 个
+/// 101..200: 2
+/// 7, 13, 42: 1   # 从这几个数字中抽 1 个
+/// ```
+///
+/// 各层不能重叠。结果按层的先后排列,同一层抽到的数字在一起
+///
+/// ```
+/// use random_generator_core::{GeneratorConfig, GeneratorMode, RandomGenerator, StratifiedSource};
+///
+/// let strata = StratifiedSource { strata: "1..10: 2\n11..20: 3".to_string() };
+/// let mut generator = RandomGenerator::with_source(Box::new(strata), GeneratorConfig {
+///     mode: GeneratorMode::STRATIFIED,
+///     ..Default::default()
+/// })?;
+/// generator.generate_numbers()?;
+/// let numbers = generator.get_numbers();
+/// assert!(numbers[..2].iter().all(|n| (1..=10).contains(n)));
+/// assert!(numbers[2..].iter().all(|n| (11..=20).contains(n)));
+/// # Ok::<(), random_generator_core::RandomGeneratorError>(())
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Strata<T: Number> {
+    strata: Vec<Stratum<T>>,
+}
+
+/// 分层抽样中的一层
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stratum<T: Number> {
+    /// 这一层的数字
+    pub values: StratumValues<T>,
+    /// 要从中抽几个
+    pub count: usize,
+    /// 在文本中的第几行,从 1 开始
+    pub line: usize,
+}
+
+/// 一层包含的数字
+#[derive(Debug, Clone, PartialEq)]
+pub enum StratumValues<T: Number> {
+    /// `lower..upper`,包含两端
+    Range(T, T),
+    /// 列出的几个数字
+    List(Vec<T>),
+}
+
+impl<T: Number> Strata<T> {
+    /// 解析分层设置,出错时指出是第几行;各层重叠也是错误
+    pub fn parse(text: &str) -> Result<Self, RandomGeneratorError> {
+        let mut strata: Vec<Stratum<T>> = Vec::new();
+        let lines = text
+            .lines()
+            .enumerate()
+            .flat_map(|(index, line)| line.split('#').next().unwrap_or_default().split(';').map(move |part| (index, part.trim())))
+            .filter(|(_, line)| !line.is_empty());
+        for (index, line) in lines {
+            let invalid = |message: String| RandomGeneratorError::InvalidStrata { line: index + 1, message };
+            let stratum = Stratum::parse(line, index + 1).map_err(invalid)?;
+            if let Some(other) = strata.iter().find(|other| other.overlaps(&stratum)) {
+                return Err(invalid(format!("overlaps line {}", other.line)));
+            }
+            strata.push(stratum);
+        }
+        if strata.is_empty() {
+            return Err(RandomGeneratorError::InvalidStrata { line: 0, message: "write one stratum per line, like 1..100: 3".to_string() });
+        }
+        Ok(Self { strata })
+    }
+
+    /// 所有的层,按文本中的先后
+    pub fn strata(&self) -> &[Stratum<T>] {
+        &self.strata
+    }
+
+    /// 一共要抽几个
+    pub fn total(&self) -> usize {
+        self.strata.iter().fold(0, |total, stratum| total.saturating_add(stratum.count))
+    }
+
+    /// 按配置中是否允许重复和重复的限制,列出抽不够个数的层
+    pub(crate) fn check(&self, config: &Config<T>) -> Vec<RandomGeneratorError> {
+        if self.total() > MAX_COUNT {
+            return vec![RandomGeneratorError::InvalidStrata { line: 0, message: format!("at most {} numbers in all", MAX_COUNT) }];
+        }
+        let limits = RepeatLimits::of(config);
+        self.strata
+            .iter()
+            .filter_map(|stratum| {
+                let size = stratum.size();
+                let capacity = if config.allow_duplicates { limits.capacity(size) } else { size };
+                let capacity = capacity.filter(|&capacity| (stratum.count as u128) > capacity)?;
+                let message = if config.allow_duplicates {
+                    format!("only {} values can be drawn within the repeat limits, not {}", capacity, stratum.count)
+                } else {
+                    format!("only {} different values to draw {} from", capacity, stratum.count)
+                };
+                Some(RandomGeneratorError::InvalidStrata { line: stratum.line, message })
+            })
+            .collect()
+    }
+
+    /// 逐层抽取,每层沿用区间或列表模式的抽法
+    pub(crate) fn draw(&self, config: &Config<T>, rng: &mut dyn RngCore, progress: &mut Progress<'_>) -> Result<Vec<T>, RandomGeneratorError> {
+        let mut numbers = Vec::new();
+        for stratum in &self.strata {
+            // 只带上抽法相关的设置,洗牌袋、冷却等不适用于各层
+            let layer = Config {
+                num_to_generate: stratum.count,
+                allow_duplicates: config.allow_duplicates,
+                max_repeats: config.max_repeats,
+                max_streak: config.max_streak,
+                ..Config::default()
+            };
+            let drawn = match &stratum.values {
+                &StratumValues::Range(lower, upper) => {
+                    RangeSource.generate(&Config { lower_bound: lower, upper_bound: upper, ..layer }, rng, progress)?
+                }
+                StratumValues::List(values) => {
+                    let layer = Config {
+                        mode: GeneratorMode::CUSTOM_LIST,
+                        custom_list: values.clone(),
+                        list_duplicates: ListDuplicates::Dedupe,
+                        ..layer
+                    };
+                    ListSource.generate(&layer, rng, progress)?
+                }
+            };
+            numbers.extend(drawn);
+        }
+        Ok(numbers)
+    }
+}
+
+impl<T: Number> Stratum<T> {
+    fn parse(line: &str, number: usize) -> Result<Self, String> {
+        let (values, count) = line.rsplit_once(':').ok_or_else(|| format!("'{}' needs a count, like 1..100: 3", line))?;
+        let count = count.trim().parse().map_err(|_| format!("'{}' is not a count", count.trim()))?;
+        let values = values.trim();
+        let number_at = |text: &str| text.trim().parse::<T>().ok().filter(|value| value.is_finite()).ok_or_else(|| format!("'{}' is not a number", text.trim()));
+        let values = match values.split_once("..") {
+            Some((lower, upper)) => {
+                let (lower, upper) = (number_at(lower)?, number_at(upper)?);
+                if !T::is_valid_range(lower, upper) {
+                    return Err(format!("{}..{} goes backwards", lower, upper));
+                }
+                StratumValues::Range(lower, upper)
+            }
+            None => {
+                let mut seen = HashSet::new();
+                let mut list = Vec::new();
+                for text in values.split([',', ' ', '\t']).filter(|text| !text.trim().is_empty()) {
+                    let value = number_at(text)?;
+                    if !seen.insert(value.key()) {
+                        return Err(format!("{} is listed twice", value));
+                    }
+                    list.push(value);
+                }
+                if list.is_empty() {
+                    return Err("list a range like 1..100 or some numbers before the ':'".to_string());
+                }
+                StratumValues::List(list)
+            }
+        };
+        Ok(Self { values, count, line: number })
+    }
+
+    /// 这一层有几个不同的数字,`None` 表示连续的区间
+    pub fn size(&self) -> Option<u128> {
+        match &self.values {
+            &StratumValues::Range(lower, upper) => T::values_between(lower, upper),
+            StratumValues::List(values) => Some(values.len() as u128),
+        }
+    }
+
+    /// 这个数字是否属于这一层
+    pub fn contains(&self, value: T) -> bool {
+        match &self.values {
+            &StratumValues::Range(lower, upper) => lower <= value && value <= upper,
+            StratumValues::List(values) => values.iter().any(|&other| other.key() == value.key()),
+        }
+    }
+
+    fn overlaps(&self, other: &Stratum<T>) -> bool {
+        match (&self.values, &other.values) {
+            (&StratumValues::Range(lower, upper), &StratumValues::Range(other_lower, other_upper)) => lower <= other_upper && other_lower <= upper,
+            (StratumValues::List(values), _) => values.iter().any(|&value| other.contains(value)),
+            (_, StratumValues::List(values)) => values.iter().any(|&value| self.contains(value)),
+        }
+    }
+}
+
+/// 按分层设置逐层抽取,见 [`Strata`]
+///
+/// 要抽的总数由各层的个数决定,与配置中的数量无关
+#[derive(Debug, Clone, Default)]
+pub struct StratifiedSource {
+    /// 各层的原文,每行或每个 `;` 一层
+    pub strata: String,
+}
+
+impl<T: Number> GeneratorSource<T> for StratifiedSource {
+    fn mode(&self) -> GeneratorMode {
+        GeneratorMode::STRATIFIED
+    }
+
+    fn input(&self) -> SourceInput {
+        SourceInput::Own
+    }
+
+    /// 去掉空行,各行以 `; ` 连成一行
+    fn parameters(&self) -> Vec<(&'static str, String)> {
+        let strata: Vec<&str> = self.strata.lines().map(str::trim).filter(|line| !line.is_empty()).collect();
+        vec![("strata", strata.join("; "))]
+    }
+
+    fn validate(&self, config: &Config<T>) -> Vec<RandomGeneratorError> {
+        match Strata::parse(&self.strata) {
+            Ok(strata) => strata.check(config),
+            Err(e) => vec![e],
+        }
+    }
+
+    /// 每层各自检查个数是否足够
+    fn available(&self, _config: &Config<T>) -> Option<u128> {
+        None
+    }
+
+    fn generate(
+        &self,
+        config: &Config<T>,
+        rng: &mut dyn RngCore,
+        progress: &mut Progress<'_>,
+    ) -> Result<Vec<T>, RandomGeneratorError> {
+        Strata::parse(&self.strata)?.draw(config, rng, progress)
+    }
+}
@@ -0,0 +1,60 @@
+use rand::RngCore;
+
+use crate::{Config, GeneratorMode, GeneratorSource, Number, Progress, RandomGeneratorError, RangeSource, SourceInput};
+
+/// 现场抽奖:票号从 `lower_bound` 起连续发放,`upper_bound` 是最后发出的票号,只在发出的票中抽取
+///
+/// 每发一张票 `upper_bound` 加一;还没有发票时 `upper_bound` 比 `lower_bound` 小一。
+/// 可以排除之前的中奖票号,见 [`Config::exclude_drawn`];只适用于整数类型
+///
+/// ```
+/// use random_generator_core::{Generator, GeneratorConfig, GeneratorMode};
+///
+/// let no_tickets = GeneratorConfig { mode: GeneratorMode::TICKETS, lower_bound: 101, upper_bound: 100, ..Default::default() };
+/// assert!(Generator::with_config(no_tickets.clone()).is_err());
+///
+/// // 发出了 101 到 140 号
+/// let mut generator = Generator::with_config(GeneratorConfig { upper_bound: 140, ..no_tickets })?;
+/// generator.generate_numbers()?;
+/// assert!((101..=140).contains(&generator.get_numbers()[0]));
+/// # Ok::<(), random_generator_core::RandomGeneratorError>(())
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TicketSource;
+
+impl<T: Number> GeneratorSource<T> for TicketSource {
+    fn mode(&self) -> GeneratorMode {
+        GeneratorMode::TICKETS
+    }
+
+    fn input(&self) -> SourceInput {
+        SourceInput::Bounds
+    }
+
+    fn validate(&self, config: &Config<T>) -> Vec<RandomGeneratorError> {
+        if T::values_between(config.lower_bound, config.lower_bound).is_none() {
+            return vec![RandomGeneratorError::InvalidDraw("ticket numbers must be whole numbers".to_string())];
+        }
+        if config.upper_bound < config.lower_bound {
+            return vec![RandomGeneratorError::InvalidDraw("no tickets have been issued yet".to_string())];
+        }
+        RangeSource.validate(config)
+    }
+
+    fn available(&self, config: &Config<T>) -> Option<u128> {
+        RangeSource.available(config)
+    }
+
+    fn pool(&self, config: &Config<T>) -> Option<Vec<T>> {
+        RangeSource.pool(config)
+    }
+
+    fn generate(
+        &self,
+        config: &Config<T>,
+        rng: &mut dyn RngCore,
+        progress: &mut Progress<'_>,
+    ) -> Result<Vec<T>, RandomGeneratorError> {
+        RangeSource.generate(config, rng, progress)
+    }
+}
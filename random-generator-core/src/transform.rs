@@ -0,0 +1,122 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::{Number, RandomGeneratorError};
+
+/// 生成后对每个结果依次做的变换,在排序、显示和导出之前
+///
+/// 写成文本是 `scale 10`、`offset -5`、`abs`、`mod 7`、`round 5` 这样的形式,多个变换用逗号分隔,
+/// 按先后进行,见 [`Transform::parse_list`]。结果超出数字类型的范围时生成报错,而不是回绕
+///
+/// ```
+/// use random_generator_core::{Generator, GeneratorConfig, Transform};
+///
+/// // 1 到 10 的整数变为 5、10 … 50,再减 1
+/// let mut generator = Generator::with_config(GeneratorConfig {
+///     lower_bound: 1,
+///     upper_bound: 10,
+///     num_to_generate: 10,
+///     transforms: Transform::parse_list("scale 5, offset -1")?,
+///     ..Default::default()
+/// })?;
+/// generator.generate_numbers()?;
+/// assert!(generator.get_numbers().iter().all(|&value| value % 5 == 4));
+/// # Ok::<(), random_generator_core::RandomGeneratorError>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Transform<T> {
+    /// 乘以这个数
+    Scale(T),
+    /// 加上这个数
+    Offset(T),
+    /// 取绝对值
+    Abs,
+    /// 除以这个数(大于 0)的余数,总在 0 和它之间,负数也一样
+    Modulo(T),
+    /// 取最接近的这个数(大于 0)的倍数,正好在中间时取较大的
+    RoundTo(T),
+}
+
+impl<T: Number> Transform<T> {
+    /// 解析逗号或分号分隔的变换,空文本表示不变换
+    pub fn parse_list(text: &str) -> Result<Vec<Self>, RandomGeneratorError> {
+        text.split([',', ';']).filter(|step| !step.trim().is_empty()).map(str::parse).collect()
+    }
+
+    /// 检查参数:取余和取倍数的数要大于 0,浮点数要有限
+    pub fn validate(&self) -> Result<(), RandomGeneratorError> {
+        let zero = T::from_i64(0).expect("every number type has 0");
+        match *self {
+            Transform::Scale(value) | Transform::Offset(value) if !value.is_finite() => {
+                Err(RandomGeneratorError::InvalidTransform(format!("{} needs a number", self)))
+            }
+            Transform::Modulo(value) | Transform::RoundTo(value) if !(value.is_finite() && value > zero) => {
+                Err(RandomGeneratorError::InvalidTransform(format!("{} needs a number above 0", self)))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// 变换一个值,超出数字类型的范围时为 `None`
+    pub fn apply(&self, value: T) -> Option<T> {
+        match *self {
+            Transform::Scale(factor) => value.checked_mul(factor),
+            Transform::Offset(offset) => value.checked_add(offset),
+            Transform::Abs => value.checked_abs(),
+            Transform::Modulo(modulus) => value.checked_rem_euclid(modulus),
+            Transform::RoundTo(step) => value.checked_round_to(step),
+        }
+    }
+}
+
+/// 按先后做完所有变换;没有变换时原样返回
+pub(crate) fn apply_all<T: Number>(transforms: &[Transform<T>], mut values: Vec<T>) -> Result<Vec<T>, RandomGeneratorError> {
+    for transform in transforms {
+        for value in values.iter_mut() {
+            *value = transform.apply(*value).ok_or_else(|| {
+                RandomGeneratorError::InvalidTransform(format!("{} of {} doesn't fit the number type", transform, value))
+            })?;
+        }
+    }
+    Ok(values)
+}
+
+impl<T: fmt::Display> fmt::Display for Transform<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Transform::Scale(factor) => write!(f, "scale {}", factor),
+            Transform::Offset(offset) => write!(f, "offset {}", offset),
+            Transform::Abs => write!(f, "abs"),
+            Transform::Modulo(modulus) => write!(f, "mod {}", modulus),
+            Transform::RoundTo(step) => write!(f, "round {}", step),
+        }
+    }
+}
+
+impl<T: Number> FromStr for Transform<T> {
+    type Err = RandomGeneratorError;
+
+    /// 例如 `scale 10`,名字不区分大小写
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let text = s.trim();
+        let (name, argument) = text.split_once(char::is_whitespace).map_or((text, ""), |(name, argument)| (name, argument.trim()));
+        let number = || {
+            argument.parse::<T>().map_err(|_| RandomGeneratorError::InvalidTransform(format!("'{}' needs a number, like {} 2", text, name)))
+        };
+        let transform = match name.to_ascii_lowercase().as_str() {
+            "scale" => Transform::Scale(number()?),
+            "offset" => Transform::Offset(number()?),
+            "abs" if argument.is_empty() => Transform::Abs,
+            "mod" => Transform::Modulo(number()?),
+            "round" => Transform::RoundTo(number()?),
+            _ => {
+                return Err(RandomGeneratorError::InvalidTransform(format!(
+                    "'{}' is not scale N, offset N, abs, mod N or round N",
+                    text
+                )))
+            }
+        };
+        transform.validate()?;
+        Ok(transform)
+    }
+}
@@ -0,0 +1,185 @@
+use rand::{Rng, RngCore};
+
+use crate::{Config, GeneratorMode, GeneratorSource, Number, Progress, RandomGeneratorError, SourceInput, PROGRESS_INTERVAL};
+
+/// 负二项模式最多要求的成功次数
+pub const MAX_SUCCESSES: u64 = 10_000;
+
+/// 几何分布:每次试验以 `chance` 的概率成功,抽出直到第一次成功共试了几次,至少为 1
+///
+/// 例如掷骰子直到掷出 6(`chance` 为 1/6)要掷几次。各次结果可以相同,不受“允许重复”的影响
+///
+/// ```
+/// use random_generator_core::{Generator, GeneratorConfig, GeneratorMode, GeometricSource};
+///
+/// let mut generator = Generator::with_source(Box::new(GeometricSource { chance: 1.0 / 6.0 }), GeneratorConfig {
+///     mode: GeneratorMode::GEOMETRIC,
+///     num_to_generate: 1000,
+///     ..Default::default()
+/// })?;
+/// generator.generate_numbers()?;
+/// let rolls = generator.get_numbers();
+/// assert!(rolls.iter().all(|&rolls| rolls >= 1));
+/// # Ok::<(), random_generator_core::RandomGeneratorError>(())
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct GeometricSource {
+    /// 每次试验成功的概率
+    pub chance: f64,
+}
+
+impl Default for GeometricSource {
+    fn default() -> Self {
+        Self { chance: 0.5 }
+    }
+}
+
+/// 负二项分布:每次试验以 `chance` 的概率成功,抽出直到第 `successes` 次成功共试了几次,
+/// 至少为 `successes`;`successes` 为 1 时就是几何分布
+///
+/// 例如罚球命中率 70%,投进 5 个要投几次
+///
+/// ```
+/// use random_generator_core::{Generator, GeneratorConfig, GeneratorMode, NegativeBinomialSource};
+///
+/// let mut generator = Generator::with_source(Box::new(NegativeBinomialSource { chance: 0.7, successes: 5 }), GeneratorConfig {
+///     mode: GeneratorMode::NEGATIVE_BINOMIAL,
+///     num_to_generate: 1000,
+///     ..Default::default()
+/// })?;
+/// generator.generate_numbers()?;
+/// assert!(generator.get_numbers().iter().all(|&shots| shots >= 5));
+/// # Ok::<(), random_generator_core::RandomGeneratorError>(())
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct NegativeBinomialSource {
+    /// 每次试验成功的概率
+    pub chance: f64,
+    /// 要等到的成功次数
+    pub successes: u64,
+}
+
+impl Default for NegativeBinomialSource {
+    fn default() -> Self {
+        Self { chance: 0.5, successes: 3 }
+    }
+}
+
+/// 两种模式共用的检查,`successes` 是要求的成功次数
+fn validate<T: Number>(config: &Config<T>, chance: f64, successes: u64) -> Vec<RandomGeneratorError> {
+    let mut problems = Vec::new();
+    if !(chance > 0.0 && chance <= 1.0) {
+        problems.push(RandomGeneratorError::InvalidTrials("the chance of success must be above 0 and at most 1".to_string()));
+    }
+    if !(1..=MAX_SUCCESSES).contains(&successes) {
+        problems.push(RandomGeneratorError::InvalidTrials(format!("wait for 1 to {} successes", MAX_SUCCESSES)));
+    }
+    if T::from_i64(successes as i64).is_none() {
+        problems.push(RandomGeneratorError::InvalidTrials("the counts don't fit the number type".to_string()));
+    }
+    if config.allow_duplicates && (config.max_repeats.is_some() || config.max_streak.is_some()) {
+        problems.push(RandomGeneratorError::InvalidTrials("repeat limits don't apply to counts of trials".to_string()));
+    }
+    problems
+}
+
+/// 抽出 `num_to_generate` 个直到第 `successes` 次成功的试验次数
+fn generate<T: Number>(config: &Config<T>, chance: f64, successes: u64, rng: &mut dyn RngCore, progress: &mut Progress<'_>) -> Result<Vec<T>, RandomGeneratorError> {
+    if let Some(problem) = validate(config, chance, successes).into_iter().next() {
+        return Err(problem);
+    }
+    let total = config.num_to_generate;
+    let mut counts = Vec::new();
+    let mut steps = 0usize;
+    for done in 0..total {
+        let mut trials = 0.0;
+        for _ in 0..successes {
+            if steps.is_multiple_of(PROGRESS_INTERVAL) {
+                progress.checkpoint(done, total)?;
+            }
+            steps += 1;
+            trials += geometric(chance, rng);
+        }
+        // `as` 在超出范围时取到 i64::MAX,成功机会极小时可能如此
+        let count = T::from_i64(trials as i64).filter(|_| trials < i64::MAX as f64).ok_or_else(|| {
+            RandomGeneratorError::InvalidTrials(format!("too many trials (about {:.1e}) for the number type; raise the chance of success", trials))
+        })?;
+        counts.push(count);
+    }
+    Ok(counts)
+}
+
+/// 直到第一次成功的试验次数
+///
+/// 逆变换:次数超过 k 的概率是 (1 - p)^k,所以取 1 + ⌊ln U / ln(1 - p)⌋;U 落在 (0, 1] 中
+fn geometric(chance: f64, rng: &mut dyn RngCore) -> f64 {
+    if chance >= 1.0 {
+        return 1.0;
+    }
+    let u = 1.0 - rng.gen::<f64>();
+    1.0 + (u.ln() / (-chance).ln_1p()).floor()
+}
+
+impl<T: Number> GeneratorSource<T> for GeometricSource {
+    fn mode(&self) -> GeneratorMode {
+        GeneratorMode::GEOMETRIC
+    }
+
+    fn input(&self) -> SourceInput {
+        SourceInput::Own
+    }
+
+    fn parameters(&self) -> Vec<(&'static str, String)> {
+        vec![("chance", self.chance.to_string())]
+    }
+
+    fn validate(&self, config: &Config<T>) -> Vec<RandomGeneratorError> {
+        validate(config, self.chance, 1)
+    }
+
+    /// 次数本来就会重复,个数不受不同值个数的限制
+    fn available(&self, _config: &Config<T>) -> Option<u128> {
+        None
+    }
+
+    fn generate(
+        &self,
+        config: &Config<T>,
+        rng: &mut dyn RngCore,
+        progress: &mut Progress<'_>,
+    ) -> Result<Vec<T>, RandomGeneratorError> {
+        generate(config, self.chance, 1, rng, progress)
+    }
+}
+
+impl<T: Number> GeneratorSource<T> for NegativeBinomialSource {
+    fn mode(&self) -> GeneratorMode {
+        GeneratorMode::NEGATIVE_BINOMIAL
+    }
+
+    fn input(&self) -> SourceInput {
+        SourceInput::Own
+    }
+
+    fn parameters(&self) -> Vec<(&'static str, String)> {
+        vec![("chance", self.chance.to_string()), ("successes", self.successes.to_string())]
+    }
+
+    fn validate(&self, config: &Config<T>) -> Vec<RandomGeneratorError> {
+        validate(config, self.chance, self.successes)
+    }
+
+    /// 次数本来就会重复,个数不受不同值个数的限制
+    fn available(&self, _config: &Config<T>) -> Option<u128> {
+        None
+    }
+
+    fn generate(
+        &self,
+        config: &Config<T>,
+        rng: &mut dyn RngCore,
+        progress: &mut Progress<'_>,
+    ) -> Result<Vec<T>, RandomGeneratorError> {
+        generate(config, self.chance, self.successes, rng, progress)
+    }
+}
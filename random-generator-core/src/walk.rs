@@ -0,0 +1,159 @@
+use rand::{Rng, RngCore};
+use std::f64::consts::TAU;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::atomic::AtomicBool;
+
+use crate::{Progress, RandomGeneratorError, Seed, PROGRESS_INTERVAL};
+
+/// 一个序列最多的值数
+pub const MAX_LENGTH: usize = 1_000_000;
+
+/// 随机游走每一步的分布,大小由 [`RandomWalk::scale`] 决定
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum StepDistribution {
+    /// 等概率地走 `+scale` 或 `-scale`
+    Coin,
+    /// 在 `-scale` 到 `scale` 之间均匀分布
+    Uniform,
+    /// 平均数为 0、标准差为 `scale` 的正态分布
+    #[default]
+    Normal,
+}
+
+impl StepDistribution {
+    /// 所有可选的分布
+    pub const ALL: [StepDistribution; 3] = [StepDistribution::Coin, StepDistribution::Uniform, StepDistribution::Normal];
+
+    /// 抽一步
+    fn sample(self, scale: f64, rng: &mut dyn RngCore) -> f64 {
+        match self {
+            StepDistribution::Coin => if rng.gen_bool(0.5) { scale } else { -scale },
+            StepDistribution::Uniform => rng.gen_range(-scale..=scale),
+            StepDistribution::Normal => {
+                // Box-Muller 变换;1 - u 落在 (0, 1] 中,避免对 0 取对数
+                let (u, v) = (1.0 - rng.gen::<f64>(), rng.gen::<f64>());
+                scale * (-2.0 * u.ln()).sqrt() * (TAU * v).cos()
+            }
+        }
+    }
+}
+
+impl fmt::Display for StepDistribution {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StepDistribution::Coin => write!(f, "Coin"),
+            StepDistribution::Uniform => write!(f, "Uniform"),
+            StepDistribution::Normal => write!(f, "Normal"),
+        }
+    }
+}
+
+impl FromStr for StepDistribution {
+    type Err = RandomGeneratorError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.trim();
+        StepDistribution::ALL
+            .into_iter()
+            .find(|step| step.to_string().eq_ignore_ascii_case(name))
+            .ok_or_else(|| RandomGeneratorError::InvalidWalk(format!("unknown step distribution '{}'", name)))
+    }
+}
+
+/// 随机游走或 AR(1) 时间序列
+///
+/// 第一个值是 `start`,之后每个值是 `drift + phi × 前一个值 + 一步`。
+/// `phi` 为 1 时是随机游走,`drift` 是每步的平均漂移;
+/// `phi` 在 -1 和 1 之间时是平稳的 AR(1) 序列,围绕 `drift / (1 - phi)` 上下波动
+///
+/// ```
+/// use random_generator_core::{RandomWalk, Seed, StepDistribution};
+///
+/// let walk = RandomWalk { length: 50, start: 10.0, step: StepDistribution::Coin, ..Default::default() };
+/// let series = walk.series(Seed::from([8; 32]))?;
+/// assert_eq!(series.len(), 50);
+/// assert_eq!(series[0], 10.0);
+/// assert!(series.windows(2).all(|pair| (pair[1] - pair[0]).abs() == 1.0));
+/// # Ok::<(), random_generator_core::RandomGeneratorError>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RandomWalk {
+    /// 序列有几个值,包括起点
+    pub length: usize,
+    /// 起点
+    pub start: f64,
+    /// 每步加上的常数
+    pub drift: f64,
+    /// 前一个值的系数,从 -1 到 1
+    pub phi: f64,
+    /// 每一步的分布
+    pub step: StepDistribution,
+    /// 每一步的大小
+    pub scale: f64,
+}
+
+impl Default for RandomWalk {
+    fn default() -> Self {
+        RandomWalk { length: 100, start: 0.0, drift: 0.0, phi: 1.0, step: StepDistribution::default(), scale: 1.0 }
+    }
+}
+
+impl RandomWalk {
+    /// 检查长度、步长和系数
+    pub fn validate(&self) -> Result<(), RandomGeneratorError> {
+        if !(1..=MAX_LENGTH).contains(&self.length) {
+            return Err(RandomGeneratorError::InvalidWalk(format!("the series needs 1 to {} values", MAX_LENGTH)));
+        }
+        if !(self.start.is_finite() && self.drift.is_finite()) {
+            return Err(RandomGeneratorError::InvalidWalk("the start and drift must be numbers".to_string()));
+        }
+        if !(self.scale.is_finite() && self.scale > 0.0) {
+            return Err(RandomGeneratorError::InvalidWalk("the step size must be above 0".to_string()));
+        }
+        if !(-1.0..=1.0).contains(&self.phi) {
+            return Err(RandomGeneratorError::InvalidWalk("phi must be from -1 to 1; beyond that the series blows up".to_string()));
+        }
+        Ok(())
+    }
+
+    /// 用种子生成序列
+    pub fn series(&self, seed: Seed) -> Result<Vec<f64>, RandomGeneratorError> {
+        self.series_with_progress(seed, &AtomicBool::new(false), &mut |_, _| {})
+    }
+
+    /// 用种子生成序列,并以 `(已生成的值数, 总数)` 回报进度
+    ///
+    /// 每次回报前检查 `cancel`,被置位时返回 `Cancelled`
+    pub fn series_with_progress(
+        &self,
+        seed: Seed,
+        cancel: &AtomicBool,
+        on_progress: &mut dyn FnMut(usize, usize),
+    ) -> Result<Vec<f64>, RandomGeneratorError> {
+        self.validate()?;
+        let mut progress = Progress { cancel, on_progress };
+        let mut rng = seed.rng();
+        let mut series = Vec::with_capacity(self.length);
+        let mut value = self.start;
+        series.push(value);
+        for i in 1..self.length {
+            if i.is_multiple_of(PROGRESS_INTERVAL) {
+                progress.checkpoint(i, self.length)?;
+            }
+            value = self.drift + self.phi * value + self.step.sample(self.scale, &mut rng);
+            series.push(value);
+        }
+        Ok(series)
+    }
+
+    /// 每个值一行的 CSV,带表头 `index,value`,序号从 0 开始
+    pub fn to_csv(series: &[f64]) -> String {
+        let mut csv = String::from("index,value\n");
+        for (i, value) in series.iter().enumerate() {
+            csv.push_str(&format!("{},{}\n", i, value));
+        }
+        csv
+    }
+}
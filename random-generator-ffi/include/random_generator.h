@@ -0,0 +1,91 @@
+/*
+ * C interface to random-generator-core.
+ *
+ *     RgGenerator *gen = rg_generator_new();
+ *     RgConfig config = rg_config_default();
+ *     config.lower_bound = 1;
+ *     config.upper_bound = 6;
+ *     config.count = 3;
+ *     int64_t numbers[3];
+ *     if (rg_configure(gen, &config) != RG_OK || rg_generate(gen) != RG_OK) {
+ *         fprintf(stderr, "%s\n", rg_last_error(gen));
+ *         rg_generator_free(gen);
+ *         return 1;
+ *     }
+ *     rg_copy_results(gen, numbers, 3);
+ *     rg_generator_free(gen);
+ *
+ * Link against the random_generator_ffi library built by
+ * `cargo build -p random-generator-ffi --release`.
+ */
+#ifndef RANDOM_GENERATOR_H
+#define RANDOM_GENERATOR_H
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef enum RgStatus {
+    RG_OK = 0,
+    RG_NULL_POINTER = 1,
+    RG_INVALID_BOUNDS = 2,
+    RG_TOO_MANY_NUMBERS = 3,
+    RG_EMPTY_LIST = 4,
+    RG_DUPLICATE_ENTRIES = 5,
+    RG_INVALID_SEED = 6,
+    RG_BUFFER_TOO_SMALL = 7,
+    RG_INVALID_ARGUMENT = 8,
+    RG_COUNT_TOO_LARGE = 9,
+    RG_OTHER = 99,
+} RgStatus;
+
+/* list_duplicates values */
+#define RG_LIST_WEIGHTED 0
+#define RG_LIST_DEDUPE 1
+#define RG_LIST_REJECT 2
+
+/* Length of a seed as text, without the terminating NUL */
+#define RG_SEED_LEN 64
+
+typedef struct RgGenerator RgGenerator;
+
+typedef struct RgConfig {
+    int64_t lower_bound;
+    int64_t upper_bound;
+    size_t count;
+    bool allow_duplicates;
+    /* When not NULL, draw from these list_len numbers instead of the range;
+       NULL with a non-zero list_len is RG_INVALID_ARGUMENT */
+    const int64_t *list;
+    size_t list_len;
+    uint32_t list_duplicates;
+    bool sorted;
+    /* NUL-terminated hex seed to repeat an earlier draw, or NULL */
+    const char *seed;
+} RgConfig;
+
+RgConfig rg_config_default(void);
+RgGenerator *rg_generator_new(void);
+void rg_generator_free(RgGenerator *generator);
+
+/* All settings are checked together; on failure the previous config stays */
+RgStatus rg_configure(RgGenerator *generator, const RgConfig *config);
+RgStatus rg_generate(RgGenerator *generator);
+
+size_t rg_result_len(const RgGenerator *generator);
+RgStatus rg_copy_results(const RgGenerator *generator, int64_t *out, size_t capacity);
+/* Needs RG_SEED_LEN + 1 bytes */
+RgStatus rg_last_seed(const RgGenerator *generator, char *out, size_t capacity);
+
+/* Message for the last failure, or NULL; valid until the next call on the generator */
+const char *rg_last_error(const RgGenerator *generator);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
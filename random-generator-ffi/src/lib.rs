@@ -0,0 +1,333 @@
+//! `random-generator-core` 的 C 接口，供 C、C#、Swift 等语言调用。
+//!
+//! 声明见 `include/random_generator.h`。用法是：`rg_generator_new` 创建生成器，
+//! 填好 [`RgConfig`] 后交给 `rg_configure`，`rg_generate` 抽取，
+//! 再用 `rg_result_len` 和 `rg_copy_results` 取出结果，最后 `rg_generator_free`。
+//!
+//! 除取值函数外，每个函数都返回 [`RgStatus`] 中的状态码，`RG_OK`（0）表示成功；
+//! 失败时 `rg_last_error` 给出最近一次失败的说明。
+
+#![warn(missing_docs)]
+
+use std::ffi::{c_char, CStr, CString};
+use std::ptr;
+
+use random_generator_core::{
+    GeneratorConfig, GeneratorMode, ListDuplicates, RandomGenerator, RandomGeneratorError, ResultOrder, Seed,
+};
+
+/// 状态码
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RgStatus {
+    /// 成功
+    Ok = 0,
+    /// 传入了空指针
+    NullPointer = 1,
+    /// 下界大于上界
+    InvalidBounds = 2,
+    /// 不允许重复时数量超过可选数字的个数
+    TooManyNumbers = 3,
+    /// 列表为空
+    EmptyList = 4,
+    /// 列表有重复而处理方式为拒绝
+    DuplicateEntries = 5,
+    /// 种子不是 64 位十六进制文本
+    InvalidSeed = 6,
+    /// 输出缓冲区太小
+    BufferTooSmall = 7,
+    /// 参数取值无效,例如未知的重复处理方式
+    InvalidArgument = 8,
+    /// 数量超过一次最多生成的个数
+    CountTooLarge = 9,
+    /// 其他错误
+    Other = 99,
+}
+
+impl From<&RandomGeneratorError> for RgStatus {
+    fn from(error: &RandomGeneratorError) -> Self {
+        match error {
+            RandomGeneratorError::InvalidBounds => RgStatus::InvalidBounds,
+            RandomGeneratorError::TooManyNumbers => RgStatus::TooManyNumbers,
+            RandomGeneratorError::CountTooLarge => RgStatus::CountTooLarge,
+            RandomGeneratorError::EmptyList => RgStatus::EmptyList,
+            RandomGeneratorError::DuplicateEntries => RgStatus::DuplicateEntries,
+            RandomGeneratorError::InvalidSeed => RgStatus::InvalidSeed,
+            // 同时有多个问题时报告第一个
+            RandomGeneratorError::Multiple(errors) => errors.first().map_or(RgStatus::Other, RgStatus::from),
+            _ => RgStatus::Other,
+        }
+    }
+}
+
+/// 一次配置的全部设置,一并校验,因此设置的先后不影响结果
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct RgConfig {
+    /// 区间下界(包含)
+    pub lower_bound: i64,
+    /// 区间上界(包含)
+    pub upper_bound: i64,
+    /// 要生成的数量
+    pub count: usize,
+    /// 是否允许重复
+    pub allow_duplicates: bool,
+    /// 非空时改为从这 `list_len` 个数字中抽取
+    pub list: *const i64,
+    /// `list` 的长度;`list` 为空时必须为 0,否则返回 `RG_INVALID_ARGUMENT`
+    pub list_len: usize,
+    /// 列表中重复数字的处理方式:0 按权重,1 去重,2 拒绝
+    pub list_duplicates: u32,
+    /// 是否把结果从小到大排列
+    pub sorted: bool,
+    /// 以 NUL 结尾的 64 位十六进制种子,用于重现以前的抽取;为空时每次取新种子
+    pub seed: *const c_char,
+}
+
+/// 不透明的生成器句柄
+pub struct RgGenerator {
+    generator: RandomGenerator,
+    last_error: Option<CString>,
+}
+
+impl RgGenerator {
+    fn fail(&mut self, status: RgStatus, message: impl ToString) -> RgStatus {
+        self.last_error = CString::new(message.to_string()).ok();
+        status
+    }
+
+    fn check(&mut self, result: Result<(), RandomGeneratorError>) -> RgStatus {
+        match result {
+            Ok(()) => {
+                self.last_error = None;
+                RgStatus::Ok
+            }
+            Err(e) => self.fail(RgStatus::from(&e), e),
+        }
+    }
+}
+
+/// 默认配置:0 到 1024 中不重复地抽取 1 个
+#[no_mangle]
+pub extern "C" fn rg_config_default() -> RgConfig {
+    let config = GeneratorConfig::default();
+    RgConfig {
+        lower_bound: config.lower_bound,
+        upper_bound: config.upper_bound,
+        count: config.num_to_generate,
+        allow_duplicates: config.allow_duplicates,
+        list: ptr::null(),
+        list_len: 0,
+        list_duplicates: 0,
+        sorted: false,
+        seed: ptr::null(),
+    }
+}
+
+/// 创建生成器,用完后以 [`rg_generator_free`] 释放
+#[no_mangle]
+pub extern "C" fn rg_generator_new() -> *mut RgGenerator {
+    Box::into_raw(Box::new(RgGenerator { generator: RandomGenerator::new(), last_error: None }))
+}
+
+/// 释放生成器,传入空指针时什么也不做
+///
+/// # Safety
+///
+/// `generator` 必须为空或来自 [`rg_generator_new`],且之后不再使用
+#[no_mangle]
+pub unsafe extern "C" fn rg_generator_free(generator: *mut RgGenerator) {
+    if !generator.is_null() {
+        drop(Box::from_raw(generator));
+    }
+}
+
+/// 应用配置;失败时原配置保持不变
+///
+/// # Safety
+///
+/// `generator` 必须来自 [`rg_generator_new`];`config` 必须指向有效的 [`RgConfig`],
+/// 其中非空的 `list` 指向至少 `list_len` 个数字,非空的 `seed` 是以 NUL 结尾的字符串
+#[no_mangle]
+pub unsafe extern "C" fn rg_configure(generator: *mut RgGenerator, config: *const RgConfig) -> RgStatus {
+    let (Some(handle), Some(config)) = (generator.as_mut(), config.as_ref()) else {
+        return RgStatus::NullPointer;
+    };
+
+    let list_duplicates = match config.list_duplicates {
+        0 => ListDuplicates::Weighted,
+        1 => ListDuplicates::Dedupe,
+        2 => ListDuplicates::Reject,
+        other => return handle.fail(RgStatus::InvalidArgument, format!("unknown list_duplicates value {}", other)),
+    };
+    if config.list.is_null() && config.list_len != 0 {
+        return handle.fail(RgStatus::InvalidArgument, format!("list is NULL but list_len is {}", config.list_len));
+    }
+    let seed = if config.seed.is_null() {
+        None
+    } else {
+        match CStr::from_ptr(config.seed).to_str().map(str::parse::<Seed>) {
+            Ok(Ok(seed)) => Some(seed),
+            _ => return handle.fail(RgStatus::InvalidSeed, RandomGeneratorError::InvalidSeed),
+        }
+    };
+    let mut new_config = GeneratorConfig {
+        lower_bound: config.lower_bound,
+        upper_bound: config.upper_bound,
+        num_to_generate: config.count,
+        allow_duplicates: config.allow_duplicates,
+        list_duplicates,
+        order: if config.sorted { ResultOrder::Sorted } else { ResultOrder::Drawn },
+        seed,
+        ..GeneratorConfig::default()
+    };
+    if !config.list.is_null() {
+        new_config.mode = GeneratorMode::CUSTOM_LIST;
+        new_config.custom_list = std::slice::from_raw_parts(config.list, config.list_len).to_vec();
+    }
+
+    let result = handle.generator.set_config(new_config);
+    handle.check(result)
+}
+
+/// 按当前配置抽取,结果替换上一次的结果
+///
+/// # Safety
+///
+/// `generator` 必须来自 [`rg_generator_new`]
+#[no_mangle]
+pub unsafe extern "C" fn rg_generate(generator: *mut RgGenerator) -> RgStatus {
+    let Some(handle) = generator.as_mut() else {
+        return RgStatus::NullPointer;
+    };
+    let result = handle.generator.generate_numbers();
+    handle.check(result)
+}
+
+/// 最近一次抽取的结果个数,传入空指针时为 0
+///
+/// # Safety
+///
+/// `generator` 必须为空或来自 [`rg_generator_new`]
+#[no_mangle]
+pub unsafe extern "C" fn rg_result_len(generator: *const RgGenerator) -> usize {
+    generator.as_ref().map_or(0, |handle| handle.generator.get_numbers().len())
+}
+
+/// 把结果复制到 `out`,`capacity` 小于结果个数时不复制并返回 `RG_BUFFER_TOO_SMALL`
+///
+/// # Safety
+///
+/// `generator` 必须来自 [`rg_generator_new`];`out` 必须可写入 `capacity` 个 `int64_t`
+#[no_mangle]
+pub unsafe extern "C" fn rg_copy_results(generator: *const RgGenerator, out: *mut i64, capacity: usize) -> RgStatus {
+    let Some(handle) = generator.as_ref() else {
+        return RgStatus::NullPointer;
+    };
+    let numbers = handle.generator.get_numbers();
+    if numbers.is_empty() {
+        return RgStatus::Ok;
+    }
+    if out.is_null() {
+        return RgStatus::NullPointer;
+    }
+    if capacity < numbers.len() {
+        return RgStatus::BufferTooSmall;
+    }
+    ptr::copy_nonoverlapping(numbers.as_ptr(), out, numbers.len());
+    RgStatus::Ok
+}
+
+/// 把最近一次抽取的种子以 64 位十六进制文本写入 `out`(含结尾的 NUL 共 65 字节)
+///
+/// # Safety
+///
+/// `generator` 必须来自 [`rg_generator_new`];`out` 必须可写入 `capacity` 字节
+#[no_mangle]
+pub unsafe extern "C" fn rg_last_seed(generator: *const RgGenerator, out: *mut c_char, capacity: usize) -> RgStatus {
+    let (Some(handle), false) = (generator.as_ref(), out.is_null()) else {
+        return RgStatus::NullPointer;
+    };
+    let Some(seed) = handle.generator.last_seed() else {
+        return RgStatus::Other;
+    };
+    let text = seed.to_string();
+    if capacity <= text.len() {
+        return RgStatus::BufferTooSmall;
+    }
+    ptr::copy_nonoverlapping(text.as_ptr().cast::<c_char>(), out, text.len());
+    *out.add(text.len()) = 0;
+    RgStatus::Ok
+}
+
+/// 最近一次失败的说明,没有失败时为空指针;指针在下一次调用该生成器前有效
+///
+/// # Safety
+///
+/// `generator` 必须为空或来自 [`rg_generator_new`]
+#[no_mangle]
+pub unsafe extern "C" fn rg_last_error(generator: *const RgGenerator) -> *const c_char {
+    generator
+        .as_ref()
+        .and_then(|handle| handle.last_error.as_ref())
+        .map_or(ptr::null(), |message| message.as_ptr())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        unsafe {
+            let generator = rg_generator_new();
+            let mut config = rg_config_default();
+            config.lower_bound = 1;
+            config.upper_bound = 6;
+            config.count = 6;
+            config.sorted = true;
+            assert_eq!(rg_configure(generator, &config), RgStatus::Ok);
+            assert_eq!(rg_generate(generator), RgStatus::Ok);
+            assert_eq!(rg_result_len(generator), 6);
+
+            let mut small = [0i64; 3];
+            assert_eq!(rg_copy_results(generator, small.as_mut_ptr(), small.len()), RgStatus::BufferTooSmall);
+            let mut out = [0i64; 6];
+            assert_eq!(rg_copy_results(generator, out.as_mut_ptr(), out.len()), RgStatus::Ok);
+            assert_eq!(out, [1, 2, 3, 4, 5, 6]);
+
+            // 记下种子再配置回去,得到同样的结果
+            let mut seed = [0 as c_char; 65];
+            config.sorted = false;
+            assert_eq!(rg_configure(generator, &config), RgStatus::Ok);
+            assert_eq!(rg_generate(generator), RgStatus::Ok);
+            assert_eq!(rg_last_seed(generator, seed.as_mut_ptr(), seed.len()), RgStatus::Ok);
+            assert_eq!(rg_copy_results(generator, out.as_mut_ptr(), out.len()), RgStatus::Ok);
+            config.seed = seed.as_ptr();
+            assert_eq!(rg_configure(generator, &config), RgStatus::Ok);
+            assert_eq!(rg_generate(generator), RgStatus::Ok);
+            let mut replayed = [0i64; 6];
+            assert_eq!(rg_copy_results(generator, replayed.as_mut_ptr(), replayed.len()), RgStatus::Ok);
+            assert_eq!(replayed, out);
+
+            // 失败时保留原配置,并给出说明
+            config.count = 7;
+            assert_eq!(rg_configure(generator, &config), RgStatus::TooManyNumbers);
+            assert!(!rg_last_error(generator).is_null());
+            config.count = 1_000_000_000_000;
+            config.allow_duplicates = true;
+            assert_eq!(rg_configure(generator, &config), RgStatus::CountTooLarge);
+            let list = [3i64, 3, 4];
+            config = rg_config_default();
+            config.list = list.as_ptr();
+            config.list_len = list.len();
+            config.list_duplicates = 2;
+            assert_eq!(rg_configure(generator, &config), RgStatus::DuplicateEntries);
+            config.list = ptr::null();
+            assert_eq!(rg_configure(generator, &config), RgStatus::InvalidArgument);
+            assert_eq!(rg_configure(ptr::null_mut(), &config), RgStatus::NullPointer);
+
+            rg_generator_free(generator);
+        }
+    }
+}
@@ -0,0 +1,186 @@
+//! Keyboard navigation and the high-contrast look.
+//!
+//! Text inputs are visited with Tab / Shift+Tab in the order they appear on screen;
+//! the buttons that can't take focus get shortcuts instead.
+
+use iced::keyboard::{self, key};
+use iced::widget::{button, checkbox, pick_list, text_input};
+use iced::{event, window, Background, Border, Color, Padding, Subscription};
+
+use crate::{presentation, Message, RandomGeneratorApp};
+
+/// Key presses from every window, tagged with the window they happened in
+pub fn key_events() -> Subscription<Message> {
+    event::listen_with(key_press)
+}
+
+fn key_press(event: iced::Event, _status: event::Status, window: window::Id) -> Option<Message> {
+    match event {
+        iced::Event::Keyboard(keyboard::Event::KeyPressed { key, modifiers, .. }) => {
+            Some(Message::KeyPressed(window, key, modifiers))
+        }
+        _ => None,
+    }
+}
+
+impl RandomGeneratorApp {
+    /// Action for a key press in this window, depending on what is on screen
+    pub(crate) fn handle_key(&self, key: keyboard::Key, modifiers: keyboard::Modifiers) -> Option<Message> {
+        if self.presentation_open {
+            return presentation::handle_key(key, modifiers);
+        }
+        match key.as_ref() {
+            keyboard::Key::Named(key::Named::Tab) if modifiers.shift() => Some(Message::FocusPrevious),
+            keyboard::Key::Named(key::Named::Tab) => Some(Message::FocusNext),
+            keyboard::Key::Named(key::Named::Escape) if self.settings_open => Some(Message::CloseSettings),
+            keyboard::Key::Named(key::Named::Escape) if self.about_open => Some(Message::CloseAbout),
+            keyboard::Key::Named(key::Named::Enter) if modifiers.command() => Some(Message::Generate),
+            keyboard::Key::Character("s") if modifiers.command() => Some(Message::Save),
+            keyboard::Key::Character("l") if modifiers.command() => Some(Message::Clear),
+            keyboard::Key::Character(",") if modifiers.command() => Some(Message::ShowSettings),
+            // Diagnostic only, deliberately not listed anywhere
+            keyboard::Key::Character("b") if modifiers.command() && modifiers.shift() => Some(Message::RunBenchmark),
+            _ => None,
+        }
+    }
+
+    /// Checkbox size; bigger targets in high-contrast mode
+    pub(crate) fn checkbox_size(&self) -> f32 {
+        if self.settings.high_contrast { 20.0 } else { 14.0 }
+    }
+
+    /// Button padding; taller targets in high-contrast mode
+    pub(crate) fn button_padding(&self) -> Padding {
+        if self.settings.high_contrast {
+            Padding::from([12, 10])
+        } else {
+            Padding::from([5, 10])
+        }
+    }
+}
+
+/// Thick outline marking the focused input
+pub fn focus_border(dark_mode: bool, high_contrast: bool) -> Border {
+    Border {
+        color: match (high_contrast, dark_mode) {
+            (true, true) => Color::from_rgb(1.0, 0.85, 0.0),
+            (true, false) => Color::from_rgb(0.0, 0.2, 0.8),
+            (false, true) => Color::from_rgb(0.4, 0.65, 1.0),
+            (false, false) => Color::from_rgb(0.2, 0.6, 0.9),
+        },
+        width: if high_contrast { 3.0 } else { 2.0 },
+        radius: 6.0.into(),
+    }
+}
+
+/// Secondary text such as counts and hints
+pub fn dim_text_color(dark_mode: bool, high_contrast: bool) -> Color {
+    match (high_contrast, dark_mode) {
+        (true, true) => Color::WHITE,
+        (true, false) => Color::BLACK,
+        (false, true) => Color::from_rgb(0.6, 0.6, 0.6),
+        (false, false) => Color::from_rgb(0.5, 0.5, 0.5),
+    }
+}
+
+fn foreground(dark_mode: bool) -> Color {
+    if dark_mode { Color::WHITE } else { Color::BLACK }
+}
+
+fn background(dark_mode: bool) -> Color {
+    if dark_mode { Color::BLACK } else { Color::WHITE }
+}
+
+fn solid_border(dark_mode: bool, radius: f32) -> Border {
+    Border {
+        color: foreground(dark_mode),
+        width: 2.0,
+        radius: radius.into(),
+    }
+}
+
+pub fn high_contrast_input(dark_mode: bool) -> text_input::Style {
+    text_input::Style {
+        background: Background::Color(background(dark_mode)),
+        border: solid_border(dark_mode, 6.0),
+        icon: Color::TRANSPARENT,
+        placeholder: if dark_mode {
+            Color::from_rgb(0.8, 0.8, 0.8)
+        } else {
+            Color::from_rgb(0.3, 0.3, 0.3)
+        },
+        value: foreground(dark_mode),
+        selection: if dark_mode {
+            Color::from_rgb(0.0, 0.4, 0.9)
+        } else {
+            Color::from_rgb(0.6, 0.8, 1.0)
+        },
+    }
+}
+
+pub fn high_contrast_pick_list(dark_mode: bool) -> pick_list::Style {
+    pick_list::Style {
+        placeholder_color: foreground(dark_mode),
+        handle_color: foreground(dark_mode),
+        text_color: foreground(dark_mode),
+        background: Background::Color(background(dark_mode)),
+        border: solid_border(dark_mode, 6.0),
+    }
+}
+
+pub fn high_contrast_checkbox(dark_mode: bool) -> checkbox::Style {
+    checkbox::Style {
+        background: Background::Color(background(dark_mode)),
+        icon_color: foreground(dark_mode),
+        border: solid_border(dark_mode, 4.0),
+        text_color: Some(foreground(dark_mode)),
+    }
+}
+
+/// Plain outlined button; hovering or pressing inverts it
+pub fn high_contrast_button(dark_mode: bool, status: button::Status) -> button::Style {
+    let inverted = matches!(status, button::Status::Hovered | button::Status::Pressed);
+    let (fill, content) = if inverted {
+        (foreground(dark_mode), background(dark_mode))
+    } else {
+        (background(dark_mode), foreground(dark_mode))
+    };
+    button::Style {
+        background: Some(Background::Color(if status == button::Status::Disabled {
+            Color::from_rgb(0.5, 0.5, 0.5)
+        } else {
+            fill
+        })),
+        border: solid_border(dark_mode, 8.0),
+        text_color: content,
+        ..Default::default()
+    }
+}
+
+/// Result tab; the selected one is drawn inverted
+pub fn high_contrast_tab(dark_mode: bool, active: bool, status: button::Status) -> button::Style {
+    let mut style = high_contrast_button(dark_mode, if active { button::Status::Pressed } else { status });
+    style.border.radius = 6.0.into();
+    style
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keys_follow_what_is_on_screen() {
+        let mut app = RandomGeneratorApp::default();
+        let tab = keyboard::Key::Named(key::Named::Tab);
+        let escape = keyboard::Key::Named(key::Named::Escape);
+
+        assert!(matches!(app.handle_key(tab.clone(), keyboard::Modifiers::SHIFT), Some(Message::FocusPrevious)));
+        assert!(app.handle_key(escape.clone(), keyboard::Modifiers::empty()).is_none());
+
+        app.settings_open = true;
+        assert!(matches!(app.handle_key(escape, keyboard::Modifiers::empty()), Some(Message::CloseSettings)));
+
+        app.presentation_open = true;
+        assert!(app.handle_key(tab, keyboard::Modifiers::empty()).is_none());
+    }
+}
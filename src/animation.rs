@@ -0,0 +1,89 @@
+use std::time::{Duration, Instant};
+
+/// Slot-machine style reveal: digits spin and land one by one, left to right
+#[derive(Debug, Clone)]
+pub struct SlotReveal {
+    started: Instant,
+    now: Instant,
+    first_index: usize,
+    step: u64,
+}
+
+impl SlotReveal {
+    /// Total time from the first spin until the value is fully shown
+    pub const DURATION: Duration = Duration::from_millis(2000);
+
+    /// How often the spinning digits change
+    const SPIN_INTERVAL_MS: u128 = 60;
+
+    /// Start a reveal for every result at or after `first_index`
+    pub fn new(now: Instant, first_index: usize) -> Self {
+        Self { started: now, now, first_index, step: 0 }
+    }
+
+    /// Advance the animation clock to the current frame.
+    ///
+    /// Returns `true` when the reels moved on to a new spin step.
+    pub fn tick(&mut self, now: Instant) -> bool {
+        self.now = now;
+        let step = self.spin_step();
+        let advanced = step != self.step;
+        self.step = step;
+        advanced
+    }
+
+    /// Whether the result at `index` is part of this reveal
+    pub fn applies_to(&self, index: usize) -> bool {
+        index >= self.first_index
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.elapsed() >= Self::DURATION
+    }
+
+    /// Text to show for `value` (rendered as `target`) at the current point of the animation
+    pub fn display(&self, value: i64, target: &str) -> String {
+        let digit_count = target.chars().filter(|c| c.is_ascii_digit()).count();
+
+        // Every digit gets its own landing slot, the last one leaves a short pause
+        // with the final value visible before the animation ends.
+        let progress = self.elapsed().as_secs_f32() / Self::DURATION.as_secs_f32();
+        let landed = (progress * (digit_count as f32 + 1.0)).floor() as usize;
+        let frame = self.spin_step();
+
+        let mut position = 0;
+        target
+            .chars()
+            .map(|c| {
+                if !c.is_ascii_digit() {
+                    return c;
+                }
+                let shown = if position < landed {
+                    c
+                } else {
+                    spin_digit(frame, position, value)
+                };
+                position += 1;
+                shown
+            })
+            .collect()
+    }
+
+    fn spin_step(&self) -> u64 {
+        (self.elapsed().as_millis() / Self::SPIN_INTERVAL_MS) as u64
+    }
+
+    fn elapsed(&self) -> Duration {
+        self.now.saturating_duration_since(self.started)
+    }
+}
+
+/// Cheap deterministic "random" digit for a spinning reel position
+fn spin_digit(frame: u64, position: usize, value: i64) -> char {
+    let mut x = frame
+        .wrapping_mul(0x9E37_79B9_7F4A_7C15)
+        ^ (position as u64).wrapping_mul(0xBF58_476D_1CE4_E5B9)
+        ^ value as u64;
+    x ^= x >> 31;
+    char::from(b'0' + (x % 10) as u8)
+}
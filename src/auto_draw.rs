@@ -0,0 +1,259 @@
+//! Automatic draws every few seconds or minutes, for door prizes drawn
+//! throughout an event. Each draw is an ordinary Generate with the inputs as
+//! they are at that moment, so the next draw picks up any change to them.
+//! A row under the buttons counts down to the next draw and stops the run.
+
+use std::fmt;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use iced::futures::{executor, SinkExt, Stream};
+use iced::widget::{button, container, pick_list, row, text, text_input, Space};
+use iced::{alignment, Element, Length, Task, Theme};
+
+use crate::{accessibility, field_hint, get_link_button_style, get_pick_list_style, get_validated_input_style, i18n, Message, RandomGeneratorApp, Status};
+
+/// How often the countdown is refreshed
+const TICK: Duration = Duration::from_millis(250);
+/// Longest interval, a day
+const MAX_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Unit of the interval between draws
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum IntervalUnit {
+    Seconds,
+    #[default]
+    Minutes,
+}
+
+impl IntervalUnit {
+    pub const ALL: [IntervalUnit; 2] = [IntervalUnit::Seconds, IntervalUnit::Minutes];
+
+    fn seconds(self) -> u64 {
+        match self {
+            IntervalUnit::Seconds => 1,
+            IntervalUnit::Minutes => 60,
+        }
+    }
+}
+
+impl fmt::Display for IntervalUnit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IntervalUnit::Seconds => write!(f, "seconds"),
+            IntervalUnit::Minutes => write!(f, "minutes"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct AutoDraw {
+    interval: String,
+    unit: IntervalUnit,
+    /// When the next draw is due, `None` while stopped
+    next: Option<Instant>,
+    /// Draws made since the run started
+    draws: usize,
+}
+
+impl Default for AutoDraw {
+    fn default() -> Self {
+        AutoDraw {
+            interval: "5".to_string(),
+            unit: IntervalUnit::default(),
+            next: None,
+            draws: 0,
+        }
+    }
+}
+
+impl AutoDraw {
+    /// Time between draws, or why the typed interval can't be used
+    fn interval(&self) -> Result<Duration, &'static str> {
+        let amount: u64 = self.interval.trim().parse().map_err(|_| "Must be a whole number")?;
+        let interval = Duration::from_secs(amount.saturating_mul(self.unit.seconds()));
+        if amount == 0 {
+            Err("Must be at least 1")
+        } else if interval > MAX_INTERVAL {
+            Err("At most a day")
+        } else {
+            Ok(interval)
+        }
+    }
+
+    pub fn set_interval(&mut self, interval: String) {
+        self.interval = interval;
+    }
+
+    pub fn set_unit(&mut self, unit: IntervalUnit) {
+        self.unit = unit;
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.next.is_some()
+    }
+}
+
+/// Time left shown as `m:ss`, or `h:mm:ss` from an hour up, rounded up to the second
+fn countdown(left: Duration) -> String {
+    let seconds = left.as_secs() + u64::from(left.subsec_nanos() > 0);
+    match seconds / 3600 {
+        0 => format!("{}:{:02}", seconds / 60, seconds % 60),
+        hours => format!("{}:{:02}:{:02}", hours, seconds / 60 % 60, seconds % 60),
+    }
+}
+
+/// Clock ticks while automatic draws run, from a thread since no async timer is available
+pub fn ticks() -> impl Stream<Item = Instant> {
+    iced::stream::channel(1, |mut output| async move {
+        thread::spawn(move || loop {
+            thread::sleep(TICK);
+            if executor::block_on(output.send(Instant::now())).is_err() {
+                break;
+            }
+        });
+    })
+}
+
+impl RandomGeneratorApp {
+    /// Start counting down to the first draw
+    pub(crate) fn start_auto_draw(&mut self, now: Instant) {
+        if let Ok(interval) = self.auto_draw.interval() {
+            self.auto_draw.next = Some(now + interval);
+            self.auto_draw.draws = 0;
+        }
+    }
+
+    /// Stop the run, saying why when it didn't stop on request
+    pub(crate) fn stop_auto_draw(&mut self, reason: Option<String>) {
+        if self.auto_draw.next.take().is_some() {
+            if let Some(reason) = reason {
+                self.status = Some(Status::Error(i18n::fill(self.tr("Automatic draws stopped: {}"), &[&reason])));
+            }
+        }
+    }
+
+    /// Draw when the countdown is over; a draw still running or counting down holds the next one back until it finishes
+    pub(crate) fn auto_draw_tick(&mut self, now: Instant) -> Task<Message> {
+        let (Some(next), Ok(interval)) = (self.auto_draw.next, self.auto_draw.interval()) else {
+            return Task::none();
+        };
+        if now < next || self.generation_progress.is_some() || self.locked_draw.is_some() {
+            return Task::none();
+        }
+        self.auto_draw.next = Some(now + interval);
+        let task = self.update(Message::Generate);
+        if self.generation_progress.is_none() && self.locked_draw.is_none() {
+            // The inputs were turned into something that can't be drawn from
+            let reason = self.status.take().map(|status| status.to_string()).unwrap_or_default();
+            self.stop_auto_draw(Some(reason));
+            return task;
+        }
+        self.auto_draw.draws += 1;
+        task
+    }
+
+    /// Interval and start button, or the countdown to the next draw and a stop button while running
+    pub(crate) fn auto_draw_view(&self) -> Element<'_, Message> {
+        let dark_mode = self.settings.dark_mode;
+        let high_contrast = self.settings.high_contrast;
+        let dim = accessibility::dim_text_color(dark_mode, high_contrast);
+
+        let content = match self.auto_draw.next {
+            Some(next) => {
+                let left = next.saturating_duration_since(Instant::now());
+                row![
+                    text(i18n::fill(self.tr("Next draw in {}"), &[&countdown(left)]))
+                        .size(14)
+                        .font(iced::Font::MONOSPACE),
+                    text(i18n::fill(self.tr("{} drawn so far"), &[&self.auto_draw.draws]))
+                        .size(13)
+                        .color(dim),
+                    Space::with_width(Length::Fill),
+                    button(text(self.tr("Stop")).size(14))
+                        .on_press(Message::StopAutoDraw)
+                        .padding(self.button_padding())
+                        .style(move |_theme: &Theme, status| get_link_button_style(dark_mode, high_contrast, status)),
+                ]
+            }
+            None => {
+                let interval = self.auto_draw.interval();
+                let valid = interval.is_ok();
+                row![
+                    text(self.tr("Draw every")).size(14),
+                    text_input("", &self.auto_draw.interval)
+                        .on_input(Message::AutoDrawIntervalChanged)
+                        .on_submit(Message::StartAutoDraw)
+                        .width(Length::Fixed(56.0))
+                        .size(14)
+                        .style(move |_theme: &Theme, status| get_validated_input_style(dark_mode, high_contrast, valid, status)),
+                    pick_list(&IntervalUnit::ALL[..], Some(self.auto_draw.unit), Message::AutoDrawUnitChanged)
+                        .text_size(14)
+                        .style(move |_theme: &Theme, _status| get_pick_list_style(dark_mode, high_contrast)),
+                    field_hint(interval.err().map(|error| self.tr(error).to_string())),
+                    Space::with_width(Length::Fill),
+                    button(text(self.tr("Start drawing")).size(14))
+                        .on_press_maybe(valid.then_some(Message::StartAutoDraw))
+                        .padding(self.button_padding())
+                        .style(move |_theme: &Theme, status| get_link_button_style(dark_mode, high_contrast, status)),
+                ]
+            }
+        };
+        container(content.spacing(6).align_y(alignment::Vertical::Center))
+            .padding([0, 4])
+            .into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_auto_draw_every_interval() {
+        assert_eq!(countdown(Duration::from_millis(4_200)), "0:05");
+        assert_eq!(countdown(Duration::from_secs(299)), "4:59");
+        assert_eq!(countdown(Duration::from_secs(3_725)), "1:02:05");
+
+        let mut app = RandomGeneratorApp::default();
+        let _ = app.update(Message::AutoDrawIntervalChanged("0".to_string()));
+        assert_eq!(app.auto_draw.interval(), Err("Must be at least 1"));
+        let _ = app.update(Message::AutoDrawIntervalChanged("2".to_string()));
+        let _ = app.update(Message::AutoDrawUnitChanged(IntervalUnit::Seconds));
+
+        let start = Instant::now();
+        app.start_auto_draw(start);
+        assert_eq!(app.auto_draw.next, Some(start + Duration::from_secs(2)));
+
+        // Nothing is drawn before the countdown is over
+        let _ = app.auto_draw_tick(start + Duration::from_secs(1));
+        assert!(app.generation_progress.is_none());
+        assert_eq!(app.auto_draw.draws, 0);
+
+        let _ = app.auto_draw_tick(start + Duration::from_secs(2));
+        assert!(app.generation_progress.is_some());
+        assert_eq!(app.auto_draw.draws, 1);
+        assert_eq!(app.auto_draw.next, Some(start + Duration::from_secs(4)));
+
+        // A draw that is still running holds the next one back
+        let _ = app.auto_draw_tick(start + Duration::from_secs(5));
+        assert_eq!(app.auto_draw.draws, 1);
+
+        app.generation_progress = None;
+        let _ = app.update(Message::StopAutoDraw);
+        assert!(!app.auto_draw.is_running());
+        let _ = app.auto_draw_tick(start + Duration::from_secs(10));
+        assert!(app.generation_progress.is_none());
+    }
+
+    #[test]
+    fn test_auto_draw_stops_on_bad_inputs() {
+        let mut app = RandomGeneratorApp::default();
+        let start = Instant::now();
+        app.start_auto_draw(start);
+        let _ = app.update(Message::LowerBoundChanged("ten".to_string()));
+        let _ = app.auto_draw_tick(start + Duration::from_secs(300));
+        assert!(!app.auto_draw.is_running());
+        assert_eq!(app.status, Some(Status::Error("Automatic draws stopped: Please fix the highlighted fields".to_string())));
+    }
+}
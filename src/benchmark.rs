@@ -0,0 +1,26 @@
+use iced::widget::{button, container, row, text, Space};
+use iced::{alignment, Element, Font, Length, Theme};
+
+use crate::{accessibility, get_link_button_style, Message, RandomGeneratorApp};
+
+impl RandomGeneratorApp {
+    /// Diagnostic benchmark report (Ctrl+Shift+B), nothing when none has been run
+    pub(crate) fn benchmark_view(&self) -> Element<'_, Message> {
+        let Some(report) = &self.benchmark_report else {
+            return Space::with_height(Length::Fixed(0.0)).into();
+        };
+        let color = accessibility::dim_text_color(self.settings.dark_mode, self.settings.high_contrast);
+        container(
+            row![
+                text(report).size(12).font(Font::MONOSPACE).color(color),
+                Space::with_width(Length::Fill),
+                button(text("×").size(14))
+                    .on_press(Message::CloseBenchmark)
+                    .style(move |_theme: &Theme, status| get_link_button_style(self.settings.dark_mode, self.settings.high_contrast, status)),
+            ]
+            .align_y(alignment::Vertical::Top),
+        )
+        .padding(4)
+        .into()
+    }
+}
@@ -0,0 +1,256 @@
+//! Panel for bootstrap resampling.
+//!
+//! A dataset of numbers is loaded from a file and resampled with
+//! replacement many times; the panel shows how the mean varies between the
+//! resamples, with its standard error, a 95% percentile interval and a
+//! histogram. Handy for showing sampling error in a statistics class. The
+//! resampling is `Bootstrap` in the core and runs on a worker thread with a
+//! progress bar; the report can be saved as text or as CSV with one mean per
+//! resample.
+
+use std::fs;
+
+use iced::widget::{button, column, container, row, scrollable, text, text_input, Space};
+use iced::{alignment, Background, Border, Color, Element, Font, Length, Shadow, Task, Theme, Vector};
+use random_generator_core::{Bootstrap, BootstrapReport, ExportFormat, Seed};
+
+use crate::export_panel::{settings_line, ExportFile, ExportPanel, PanelSeed};
+use crate::worker::{PanelEvent, PanelRun};
+use crate::{accessibility, get_link_button_style, get_text_input_style, Message, RandomGeneratorApp};
+
+/// Formats the report can be saved in
+const FILE_FORMATS: [ExportFormat; 2] = [ExportFormat::Text, ExportFormat::Csv];
+
+#[derive(Debug)]
+pub struct BootstrapPanel {
+    data_path: String,
+    resamples: String,
+    /// Empty to draw as many values as the dataset has
+    size: String,
+    seed: PanelSeed,
+    file: ExportFile,
+    running: PanelRun,
+    report: Option<BootstrapReport>,
+    status: String,
+}
+
+impl Default for BootstrapPanel {
+    fn default() -> Self {
+        Self {
+            data_path: String::new(),
+            resamples: "1000".to_string(),
+            size: String::new(),
+            seed: PanelSeed::default(),
+            file: ExportFile::new("bootstrap.txt", ExportFormat::Text),
+            running: PanelRun::default(),
+            report: None,
+            status: String::new(),
+        }
+    }
+}
+
+impl BootstrapPanel {
+    pub fn set_data_path(&mut self, path: String) {
+        self.data_path = path;
+    }
+
+    pub fn set_resamples(&mut self, resamples: String) {
+        self.resamples = resamples;
+    }
+
+    pub fn set_size(&mut self, size: String) {
+        self.size = size;
+    }
+
+    pub fn file_mut(&mut self) -> &mut ExportFile {
+        &mut self.file
+    }
+
+    pub fn seed_mut(&mut self) -> &mut PanelSeed {
+        &mut self.seed
+    }
+
+    /// How the last draw or export went
+    pub fn status(&self) -> &str {
+        &self.status
+    }
+
+    /// Settings of the draw, for the history
+    pub fn settings(&self) -> String {
+        settings_line(&[
+            ("data", self.data_path.clone()),
+            ("resamples", self.resamples.clone()),
+            ("size", self.size.clone()),
+        ])
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.running.is_running()
+    }
+
+    /// Reads the file again on every run, so edits to the dataset show up; reading
+    /// and resampling happen on a worker thread
+    pub fn run(&mut self) -> Task<Message> {
+        if self.is_running() {
+            return Task::none();
+        }
+        self.report = None;
+        let seed = match self.seed.next() {
+            Ok(seed) => seed,
+            Err(message) => {
+                self.status = message;
+                return Task::none();
+            }
+        };
+        let size = match self.size.trim() {
+            "" => Ok(None),
+            size => size.parse().map(Some),
+        };
+        let (Ok(resamples), Ok(size)) = (self.resamples.trim().parse(), size) else {
+            self.status = "Enter whole numbers for the resamples and size".to_string();
+            return Task::none();
+        };
+        let path = self.data_path.trim().to_string();
+        if path.is_empty() {
+            self.status = "Enter the path of a file of numbers".to_string();
+            return Task::none();
+        }
+        self.status.clear();
+        let draw = self.running.start(move |cancel, on_progress| {
+            let data = fs::read_to_string(&path).map_err(|e| format!("Could not read {}: {}", path, e))?;
+            Bootstrap::parse(&data)
+                .and_then(|data| data.resample_with_progress(resamples, size, seed, cancel, on_progress))
+                .map(|report| (seed, report))
+                .map_err(|e| e.to_string())
+        });
+        Task::run(draw, Message::Bootstrap)
+    }
+
+    pub fn cancel(&self) {
+        self.running.cancel();
+    }
+
+    /// Follow the running draw; the report shows once it has finished
+    pub fn handle(&mut self, event: PanelEvent<(Seed, BootstrapReport)>) {
+        let Some(result) = self.running.update(event) else {
+            return;
+        };
+        self.status = match result {
+            Ok((seed, report)) => {
+                self.seed.keep(seed);
+                let (low, high) = report.interval(0.95);
+                let status = format!("Standard error {:.4}, 95% of the means from {:.4} to {:.4}", report.standard_error(), low, high);
+                self.report = Some(report);
+                status
+            }
+            Err(e) => e,
+        };
+    }
+
+    pub fn export(&mut self) {
+        let csv = self.file.format() == ExportFormat::Csv;
+        self.status = self.file.save(self.report.as_ref().map(|report| if csv { report.to_csv() } else { report.to_text() }));
+    }
+}
+
+impl RandomGeneratorApp {
+    pub(crate) fn bootstrap_view(&self) -> Element<'_, Message> {
+        let panel = &self.bootstrap;
+        let dark_mode = self.settings.dark_mode;
+        let high_contrast = self.settings.high_contrast;
+        let label = |content: &'static str| text(self.tr(content)).size(14).width(Length::Fixed(80.0));
+        let action = |content: &'static str, message: Option<Message>| {
+            button(text(self.tr(content)).size(14))
+                .on_press_maybe(message)
+                .padding(self.button_padding())
+                .style(move |_theme: &Theme, status| get_link_button_style(dark_mode, high_contrast, status))
+        };
+        let number_input = |placeholder: &'static str, value: &str, on_input: fn(String) -> Message| {
+            text_input(self.tr(placeholder), value)
+                .on_input(on_input)
+                .on_submit(Message::RunBootstrap)
+                .width(Length::Fixed(80.0))
+                .size(14)
+                .style(move |_theme: &Theme, status| get_text_input_style(dark_mode, high_contrast, status))
+        };
+
+        let preview = panel.report.as_ref().map(|report| {
+            let lines = report.to_text().lines().map(|line| text(line.to_string()).size(12).font(Font::MONOSPACE).into()).collect::<Vec<_>>();
+            container(
+                scrollable(column(lines))
+                    .direction(scrollable::Direction::Both {
+                        vertical: scrollable::Scrollbar::default(),
+                        horizontal: scrollable::Scrollbar::default(),
+                    })
+                    .height(Length::Fixed(220.0)),
+            )
+                .padding(6)
+                .width(Length::Fill)
+        });
+
+        let content = column![
+            text(self.tr("Bootstrap"))
+                .size(20)
+                .color(if dark_mode { Color::from_rgb(0.9, 0.9, 0.9) } else { Color::BLACK }),
+            Space::with_height(Length::Fixed(10.0)),
+            row![
+                label("Data file"),
+                text_input("", &panel.data_path)
+                    .on_input(Message::BootstrapDataChanged)
+                    .on_submit(Message::RunBootstrap)
+                    .width(Length::Fill)
+                    .size(14)
+                    .style(move |_theme: &Theme, status| get_text_input_style(dark_mode, high_contrast, status)),
+            ]
+                .spacing(6)
+                .align_y(alignment::Vertical::Center),
+            row![
+                label("Resamples"),
+                number_input("", &panel.resamples, Message::BootstrapResamplesChanged),
+                label("Values each"),
+                number_input("All", &panel.size, Message::BootstrapSizeChanged),
+                Space::with_width(Length::Fill),
+                action("Generate", (!panel.is_running()).then_some(Message::RunBootstrap)),
+            ]
+                .spacing(6)
+                .align_y(alignment::Vertical::Center),
+            text(self.tr("Numbers separated by commas, spaces or new lines; each resample draws from them with replacement"))
+                .size(11)
+                .color(accessibility::dim_text_color(dark_mode, high_contrast)),
+        ]
+            .push_maybe(self.progress_row(&panel.running, Message::CancelBootstrap))
+            .push_maybe(preview)
+            .push(self.seed_row(ExportPanel::Bootstrap, &panel.seed))
+            .push(self.export_row(ExportPanel::Bootstrap, &panel.file, &FILE_FORMATS, "Export"))
+            .push(
+                row![
+                    text(&panel.status).size(13).color(accessibility::dim_text_color(dark_mode, high_contrast)),
+                    Space::with_width(Length::Fill),
+                    action("Close", Some(Message::CloseBootstrap)),
+                ]
+                    .align_y(alignment::Vertical::Center),
+            )
+            .spacing(6)
+            .padding(24);
+
+        container(content)
+            .width(Length::Fixed(460.0))
+            .style(move |_theme: &Theme| {
+                iced::widget::container::Style {
+                    background: Some(Background::Color(if dark_mode { Color::from_rgb(0.2, 0.2, 0.25) } else { Color::WHITE })),
+                    border: Border {
+                        color: if dark_mode { Color::from_rgb(0.4, 0.4, 0.4) } else { Color::from_rgb(0.8, 0.8, 0.8) },
+                        width: 1.0,
+                        radius: 16.0.into(),
+                    },
+                    shadow: Shadow {
+                        color: Color::from_rgba(0.0, 0.0, 0.0, 0.3),
+                        offset: Vector::new(0.0, 4.0),
+                        blur_radius: 20.0,
+                    },
+                    ..Default::default()
+                }
+            })
+            .into()
+    }
+}
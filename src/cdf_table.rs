@@ -0,0 +1,193 @@
+//! Panel for a custom distribution given as a table.
+//!
+//! Paste (value, cumulative probability) points, one per line, and draw
+//! values by inverse transform: the cumulative probability is interpolated
+//! linearly between the points, so any shape the tool has no formula for can
+//! be described by enough points, e.g. a percentile table from a report. A
+//! summary and a histogram show the shape; the report is saved as text or the
+//! values as CSV. The sampling is `CdfTable` in the core.
+
+use iced::widget::{button, column, container, row, scrollable, text, text_editor, text_input, Space};
+use iced::{alignment, Background, Border, Color, Element, Font, Length, Shadow, Theme, Vector};
+use random_generator_core::{CdfSample, CdfTable, ExportFormat};
+
+use crate::export_panel::{settings_line, ExportFile, ExportPanel, PanelSeed};
+use crate::{accessibility, get_link_button_style, get_text_input_style, Message, RandomGeneratorApp};
+
+/// Formats the report can be saved in
+const FILE_FORMATS: [ExportFormat; 2] = [ExportFormat::Text, ExportFormat::Csv];
+
+/// Table a new panel starts with: half the values from 0 to 10, the rest up to 100
+const EXAMPLE: &str = "0, 0\n10, 0.5\n100, 1\n";
+
+#[derive(Debug)]
+pub struct CdfTablePanel {
+    table: text_editor::Content,
+    count: String,
+    seed: PanelSeed,
+    file: ExportFile,
+    sample: Option<CdfSample>,
+    status: String,
+}
+
+impl Default for CdfTablePanel {
+    fn default() -> Self {
+        Self {
+            table: text_editor::Content::with_text(EXAMPLE),
+            count: "1000".to_string(),
+            seed: PanelSeed::default(),
+            file: ExportFile::new("custom.txt", ExportFormat::Text),
+            sample: None,
+            status: String::new(),
+        }
+    }
+}
+
+impl CdfTablePanel {
+    pub fn edit_table(&mut self, action: text_editor::Action) {
+        self.table.perform(action);
+    }
+
+    pub fn set_count(&mut self, count: String) {
+        self.count = count;
+    }
+
+    pub fn file_mut(&mut self) -> &mut ExportFile {
+        &mut self.file
+    }
+
+    pub fn seed_mut(&mut self) -> &mut PanelSeed {
+        &mut self.seed
+    }
+
+    /// How the last draw or export went
+    pub fn status(&self) -> &str {
+        &self.status
+    }
+
+    /// Settings of the draw, for the history
+    pub fn settings(&self) -> String {
+        settings_line(&[("table", self.table.text()), ("count", self.count.clone())])
+    }
+
+    pub fn run(&mut self) {
+        self.sample = None;
+        let seed = match self.seed.next() {
+            Ok(seed) => seed,
+            Err(message) => {
+                self.status = message;
+                return;
+            }
+        };
+        let Ok(count) = self.count.trim().parse() else {
+            self.status = "Enter a whole number of values".to_string();
+            return;
+        };
+        let sample = CdfTable::parse(&self.table.text()).and_then(|table| table.sample(count, seed));
+        self.status = match sample {
+            Ok(sample) => {
+                self.seed.keep(seed);
+                let status = format!("Mean {:.4}, expected {:.4}", sample.mean(), sample.table.mean());
+                self.sample = Some(sample);
+                status
+            }
+            Err(e) => e.to_string(),
+        };
+    }
+
+    pub fn export(&mut self) {
+        let csv = self.file.format() == ExportFormat::Csv;
+        self.status = self.file.save(self.sample.as_ref().map(|sample| if csv { sample.to_csv() } else { sample.to_text() }));
+    }
+}
+
+impl RandomGeneratorApp {
+    pub(crate) fn cdf_table_view(&self) -> Element<'_, Message> {
+        let panel = &self.cdf_table;
+        let dark_mode = self.settings.dark_mode;
+        let high_contrast = self.settings.high_contrast;
+        let label = |content: &'static str| text(self.tr(content)).size(14).width(Length::Fixed(80.0));
+        let action = |content: &'static str, message: Message| {
+            button(text(self.tr(content)).size(14))
+                .on_press(message)
+                .padding(self.button_padding())
+                .style(move |_theme: &Theme, status| get_link_button_style(dark_mode, high_contrast, status))
+        };
+
+        let preview = panel.sample.as_ref().map(|sample| {
+            let lines = sample.to_text().lines().map(|line| text(line.to_string()).size(12).font(Font::MONOSPACE).into()).collect::<Vec<_>>();
+            container(
+                scrollable(column(lines))
+                    .direction(scrollable::Direction::Both {
+                        vertical: scrollable::Scrollbar::default(),
+                        horizontal: scrollable::Scrollbar::default(),
+                    })
+                    .height(Length::Fixed(200.0)),
+            )
+                .padding(6)
+                .width(Length::Fill)
+        });
+
+        let content = column![
+            text(self.tr("Custom distribution"))
+                .size(20)
+                .color(if dark_mode { Color::from_rgb(0.9, 0.9, 0.9) } else { Color::BLACK }),
+            Space::with_height(Length::Fixed(10.0)),
+            text(self.tr("Points (one per line, value, cumulative probability):")).size(14),
+            text_editor(&panel.table)
+                .on_action(Message::CdfTableEdited)
+                .placeholder(self.tr("e.g. 10, 0.5"))
+                .height(Length::Fixed(120.0))
+                .size(14),
+            text(self.tr("The cumulative probability is the chance of a value up to that one; it must not go down and must reach 1. Values between two points are spread evenly"))
+                .size(11)
+                .color(accessibility::dim_text_color(dark_mode, high_contrast)),
+            row![
+                label("Count"),
+                text_input("", &panel.count)
+                    .on_input(Message::CdfTableCountChanged)
+                    .on_submit(Message::RunCdfTable)
+                    .width(Length::Fixed(80.0))
+                    .size(14)
+                    .style(move |_theme: &Theme, status| get_text_input_style(dark_mode, high_contrast, status)),
+                Space::with_width(Length::Fill),
+                action("Generate", Message::RunCdfTable),
+            ]
+                .spacing(6)
+                .align_y(alignment::Vertical::Center),
+        ]
+            .push_maybe(preview)
+            .push(self.seed_row(ExportPanel::CdfTable, &panel.seed))
+            .push(self.export_row(ExportPanel::CdfTable, &panel.file, &FILE_FORMATS, "Export"))
+            .push(
+                row![
+                    text(&panel.status).size(13).color(accessibility::dim_text_color(dark_mode, high_contrast)),
+                    Space::with_width(Length::Fill),
+                    action("Close", Message::CloseCdfTable),
+                ]
+                    .align_y(alignment::Vertical::Center),
+            )
+            .spacing(6)
+            .padding(24);
+
+        container(content)
+            .width(Length::Fixed(480.0))
+            .style(move |_theme: &Theme| {
+                iced::widget::container::Style {
+                    background: Some(Background::Color(if dark_mode { Color::from_rgb(0.2, 0.2, 0.25) } else { Color::WHITE })),
+                    border: Border {
+                        color: if dark_mode { Color::from_rgb(0.4, 0.4, 0.4) } else { Color::from_rgb(0.8, 0.8, 0.8) },
+                        width: 1.0,
+                        radius: 16.0.into(),
+                    },
+                    shadow: Shadow {
+                        color: Color::from_rgba(0.0, 0.0, 0.0, 0.3),
+                        offset: Vector::new(0.0, 4.0),
+                        blur_radius: 20.0,
+                    },
+                    ..Default::default()
+                }
+            })
+            .into()
+    }
+}
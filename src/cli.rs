@@ -0,0 +1,1349 @@
+//! Command-line mode for scripts and shell pipelines.
+//!
+//! Any command-line arguments switch the program from the window to this mode:
+//! `cat names.txt | random-tool pick -n 3` picks lines from stdin and
+//! `random-tool range --from 1 --to 100 -n 5` prints numbers, one per line.
+
+use std::error::Error;
+use std::io::{self, BufRead, BufWriter, IsTerminal, Write};
+use std::path::Path;
+use std::sync::atomic::AtomicBool;
+
+use random_generator_core::{AddressKind, CdfTable, ColumnSchema, AddressSpec, Bootstrap, RandomWalk, DesignKind, Dirichlet, DirichletSample, Distribution, MultivariateNormal, ExperimentDesign, Formula, TruncatedDistribution, LineSample, RandomGraph, MonteCarlo, MonteCarloTarget, Bracket, ByteSize, CodeSpec, DicePool, KenoSpec, LootTable, Pairing, RandomBytes, PassphraseSpec, PinSpec, PartitionSource, PrimeSource, GeometricSource, NegativeBinomialSource, StratifiedSource, GeneratorSource, SecretSanta, Transform, Wordlist, Config, FakeLocale, FieldSchema, Generator, GeneratorConfig, JsonSchema, LabelMap, MergedResults, NumberFormat, ExportFormat, GeneratorMode, RandomGenerator, Seed, StringPattern};
+
+use crate::{daemon, deep_link, grpc, server};
+
+const USAGE: &str = "\
+Usage:
+  random-tool pick [-n COUNT] [-d]                 pick lines read from stdin
+  random-tool sample [FILE] [-n COUNT] [--seed SEED]
+                                                   pick lines from FILE or stdin without loading it all,
+                                                   for files too large to fit in memory
+  random-tool santa [--exclude A,B]... [-o DIR] [--seed SEED]
+                                                   draw Secret Santa for the names read from stdin
+  random-tool bracket [--csv] [--seed SEED]       seed the names read from stdin into a knockout bracket
+  random-tool pair FILE [--exclude A,X]... [--csv] [--seed SEED]
+                                                   hand out the lines of FILE evenly to the names read from stdin
+  random-tool range [--from A] [--to B] [-n COUNT] [-d] [--transform LIST] [--labels FILE] [--seed SEED]
+                                                   print random numbers in A..=B
+  random-tool primes [--from A] [--to B] [--bits N] [-n COUNT] [-d] [--seed SEED]
+                                                   print random primes in A..=B or with exactly N bits
+  random-tool partition TOTAL -n PARTS [--from A] [--to B] [--seed SEED]
+                                                   split TOTAL into random parts, each from A (default 0)
+                                                   to B (default TOTAL), e.g. 100 points among 7 players
+  random-tool trials CHANCE [--successes R] [-n COUNT] [--seed SEED]
+                                                   print how many tries it takes to succeed when each try
+                                                   succeeds with probability CHANCE, or to succeed R times
+  random-tool strata [-d] [--seed SEED]            draw from each stratum read from stdin, one per line
+                                                   like '1..100: 3' or '7, 13, 42: 1'
+  random-tool keno [--pool N] [--picks K] [-n COUNT] [--csv] [--seed SEED]
+                                                   print keno quick-pick tickets and flag repeated ones
+  random-tool gacha [-n PULLS] [--csv] [--seed SEED]
+                                                   simulate pulls from the loot table read from stdin
+  random-tool pool POOL [-n COUNT] [--csv] [--seed SEED]
+                                                   roll a dice pool like 6d10>=8 and count the successes;
+                                                   end with ! to explode on the highest side, or !N on N and up
+  random-tool bootstrap FILE [-n RESAMPLES] [--size M] [--csv] [--seed SEED]
+                                                   resample the numbers in FILE with replacement and
+                                                   report how the mean varies (default 1000 resamples)
+  random-tool merge FILE... [--unique] [--summary]
+                                                   print the numbers from saved result files one after another;
+                                                   .csv and .json files are read as those formats, others as text
+  random-tool walk [-n LENGTH] [--start X] [--drift D] [--phi P] [--step coin|uniform|normal]
+                   [--scale S] [--seed SEED]
+                                                   print a random walk or AR(1) series as CSV with an index column
+                                                   (default 100 values)
+  random-tool dist DISTRIBUTION [--min A] [--max B] [-n COUNT] [--summary] [--seed SEED]
+                                                   print values from a distribution like 'normal(100, 15)',
+                                                   'uniform(0, 1)', 'beta(2, 5)' or 'gamma(2, 1)' (shape, scale),
+                                                   truncated to A..=B when given
+  random-tool cdf [FILE] [-n COUNT] [--summary] [--seed SEED]
+                                                   print values from a custom distribution: lines like '10, 0.5'
+                                                   in FILE or stdin give each value's cumulative probability,
+                                                   interpolated linearly in between
+  random-tool weights [N] [--alpha A] [-n COUNT] [--csv] [--seed SEED]
+                                                   print random weights: N values adding up to 1 per line,
+                                                   from a Dirichlet distribution
+  random-tool mvnorm --corr MATRIX [--mean M] [--sd S] [-n COUNT] [--summary] [--seed SEED]
+                                                   print rows of correlated normal values as CSV, one column
+                                                   per variable, e.g. --corr '1, 0.5; 0.5, 1'
+  random-tool montecarlo [-n SAMPLES] [--integral FORMULA --from A --to B] [--csv] [--seed SEED]
+                                                   estimate pi, or the integral of a formula in x, from random
+                                                   samples (default 1000000) and compare with the exact value
+  random-tool graph [--model er|ba] [--nodes N] [--edges M] [--dot] [--seed SEED]
+                                                   print a random graph as an edge list, one 'A B' pair per line
+  random-tool design latin|balanced|blocks TREATMENTS [-n BLOCKS] [--csv] [--seed SEED]
+                                                   lay out a randomized Latin square, a Williams square balanced
+                                                   for carryover, or BLOCKS randomized blocks (default 1);
+                                                   TREATMENTS is a number like 4 or names like 'Placebo,Low,High'
+  random-tool fake [-n COUNT] [--fields LIST] [--locale en|zh] [--json] [--seed SEED]
+                                                   print rows of fake names, emails, phones and addresses
+  random-tool columns [FILE] [-n COUNT] [--seed SEED]
+                                                   print CSV rows of test data with the columns in FILE or stdin,
+                                                   one per line like 'die: 1..6', 'score: normal(50, 10) round 1'
+                                                   or 'size: S, M, L'
+  random-tool fake --schema FILE [-n COUNT] [--locale en|zh] [--seed SEED]
+                                                   print JSON documents matching a JSON Schema or template
+  random-tool pattern PATTERN [-n COUNT] [-d] [--seed SEED]
+                                                   print strings like serial numbers, e.g. 'AA-####'
+  random-tool codes [-n COUNT] [--length N] [--group N] [--check none|luhn|crc] [--exclude FILE] [--seed SEED]
+                                                   print distinct coupon codes without 0, O, 1 or I
+  random-tool pins [-n COUNT] [--length N] [--allow-repeats] [--allow-years] [--allow-sequences] [--seed SEED]
+                                                   print distinct PINs that are hard to guess
+  random-tool passphrase [-n COUNT] [--words N] [--separator S] [--case lower|title|upper|random]
+                         [--wordlist FILE] [--seed SEED]
+                                                   print Diceware passphrases and their entropy
+  random-tool address mac|ipv4|ipv6 [--in RANGE] [-n COUNT] [-d] [--seed SEED]
+                                                   print random MAC, IPv4 or IPv6 addresses
+  random-tool bytes --size SIZE [-o FILE] [--seed SEED]
+                                                   write SIZE random bytes, e.g. 10MB, to FILE or stdout
+  random-tool selftest [--from A] [--to B] [-n SAMPLES]
+                                                   check that draws from A..=B look uniform
+  random-tool serve [--addr HOST:PORT]             answer draw requests over HTTP
+  random-tool grpc [--addr HOST:PORT]              answer draw requests over gRPC, with the saved presets
+  random-tool daemon [--socket PATH]               answer draw requests from local programs
+  random-tool register-url                         open randomtool:// links with this program
+  random-tool 'randomtool://generate?min=1&max=100&count=5'
+                                                   open the window set up by a link
+  random-tool help                                 show this message
+
+Options:
+  -n, --count COUNT   how many results to print (default 1; selftest draws 100000)
+  -d, --duplicates    allow the same line, number or string more than once
+      --from A        lowest number (default 0)
+      --to B          highest number (default 1024)
+      --transform LIST
+                      for range, change each number in turn with steps like 'scale 10, offset 5';
+                      the steps are scale K, offset K, abs, mod K and round K (to the nearest K)
+      --labels FILE   for range, print the label from FILE instead of the number, one per line
+                      like '1: red team' or '3..5: spectators'
+      --bits N        for primes, draw N-bit primes, from 2 to 127 bits, instead of using --from and --to
+      --seed SEED     draw with a seed from the history to repeat that draw
+      --fields LIST   columns for fake, from name, first_name, last_name, email, phone,
+                      address, city, postcode (default name,email,phone)
+      --locale CODE   en for US-style data (default), zh for Chinese
+      --json          print fake rows as JSON instead of CSV
+      --csv           print the bracket's matches as CSV instead of a tree,
+                      or the pairs as CSV instead of one line per person, or the keno tickets
+                      or gacha counts or pool rolls as CSV, or one bootstrap mean per line,
+                      or how the Monte Carlo estimate converged
+      --pool N        for keno, numbers go from 1 to N (default 80)
+      --picks K       for keno, numbers per ticket (default 10)
+      --schema FILE   JSON Schema or field template like {\"id\": \"uuid\", \"name\": \"name\"}
+      --length N      random characters per coupon code, not counting the check character (default 8),
+                      or digits per PIN from 4 to 12 (default 6)
+      --group N       put a dash every N characters of a code (default 0, no dashes)
+      --check NAME    append a luhn or crc check character to each code (default none)
+      --exclude FILE  never print a code found in FILE, e.g. an earlier batch
+      --exclude A,B   for santa, A and B never draw each other; repeat for more pairs
+      --exclude A,X   for pair, A never gets X; repeat for more pairs
+      --allow-repeats let PINs have a digit three times in a row, like 777
+      --allow-years   let PINs contain a year from 1900 to 2099
+      --allow-sequences
+                      let PINs contain runs like 345 or 987
+      --words N       words per passphrase (default 6)
+      --separator S   text between passphrase words (default a space)
+      --case NAME     lower (default), title, upper, or random per word
+      --wordlist FILE one word per line, or Diceware lines like '11111 word' (default: 1296 bundled words)
+      --in RANGE      for address, a MAC vendor prefix like 00:1A:2B or a CIDR block like
+                      10.0.0.0/8 (default: any; MACs are then locally administered)
+      --size SIZE     bytes to write, with an optional unit: B, KB, MB or GB (1 KB = 1024 bytes)
+      --size M        for bootstrap, values per resample (default: as many as FILE has)
+      --start X       first value of a walk (default 0)
+      --drift D       added at every step of a walk (default 0)
+      --phi P         for walk, how much of the last value carries over, from -1 to 1;
+                      1 (default) is a random walk, below 1 an AR(1) series that returns to D / (1 - P)
+      --step NAME     step distribution of a walk: coin for +S or -S, uniform in -S..S, normal (default)
+      --scale S       step size of a walk, the standard deviation for normal steps (default 1)
+      --min A         for dist, never print a value below A; the rest keep their relative chances
+      --max B         for dist, never print a value above B
+      --summary       for dist, print the mean, spread and a histogram instead of the values;
+                      for cdf, the mean, spread and a histogram;
+                      for mvnorm, the means, standard deviations and correlations;
+                      for merge, how many numbers each file had and the min, max, sum and mean
+      --unique        for merge, keep only the first of numbers found more than once
+      --alpha A       for weights, concentration of every component (default 1), or one per
+                      component like 1,2,7, which also sets N
+      --corr MATRIX   for mvnorm, the correlation matrix with its rows separated by semicolons
+      --mean M        for mvnorm, mean of every variable (default 0), or one per variable like 170,65
+      --sd S          for mvnorm, standard deviation of every variable (default 1), or one per variable
+      --integral F    for montecarlo, integrate a formula like 'x^2 + sin(x)' instead of estimating pi
+      --from A        for montecarlo, lower limit of the integral (default 0)
+      --to B          for montecarlo, upper limit of the integral (default 1)
+      --model NAME    for graph, er for Erdős–Rényi with M edges in all (default),
+                      or ba for Barabási–Albert where each new node links to M others by degree
+      --nodes N       nodes in a graph, numbered from 0 (default 20)
+      --edges M       edges in an er graph, or per new node in a ba graph (default 30)
+      --dot           print the graph in Graphviz DOT format instead of an edge list
+      --from A        for partition, smallest part (default 0)
+      --to B          for partition, largest part (default TOTAL)
+      --successes R   for trials, count the tries until the R-th success (default 1)
+  -o, --output FILE   file to write the bytes to instead of stdout
+  -o, --output DIR    for santa, write one file per person into DIR instead of printing the pairs
+      --addr ADDR     where serve listens (default 127.0.0.1:7878; 0.0.0.0:7878 for the LAN)
+                      or grpc listens (default 127.0.0.1:50051)
+      --socket PATH   Unix socket or named pipe the daemon listens on
+
+Without arguments the graphical interface starts.
+";
+
+/// Rounds per strategy for `bench`
+const BENCH_ROUNDS: usize = 10;
+
+#[derive(Debug, PartialEq)]
+enum Command {
+    Pick { count: usize, duplicates: bool },
+    Sample { path: Option<String>, count: usize, seed: Option<Seed> },
+    Bracket { csv: bool, seed: Option<Seed> },
+    Santa { exclusions: Vec<String>, output: Option<String>, seed: Option<Seed> },
+    Pair { items: String, exclusions: Vec<String>, csv: bool, seed: Option<Seed> },
+    Range { lower: i64, upper: i64, count: usize, duplicates: bool, transforms: Vec<Transform<i64>>, labels: Option<String>, seed: Option<Seed> },
+    Primes { lower: i128, upper: i128, count: usize, duplicates: bool, seed: Option<Seed> },
+    Strata { duplicates: bool, seed: Option<Seed> },
+    Partition { total: i64, parts: usize, lower: i64, upper: Option<i64>, seed: Option<Seed> },
+    Trials { chance: f64, successes: u64, count: usize, seed: Option<Seed> },
+    Keno { spec: KenoSpec, count: usize, csv: bool, seed: Option<Seed> },
+    Gacha { pulls: usize, csv: bool, seed: Option<Seed> },
+    Pool { pool: DicePool, count: usize, csv: bool, seed: Option<Seed> },
+    Bootstrap { data: String, resamples: usize, size: Option<usize>, csv: bool, seed: Option<Seed> },
+    Merge { files: Vec<String>, unique: bool, summary: bool },
+    Walk { walk: RandomWalk, seed: Option<Seed> },
+    MonteCarlo { demo: MonteCarlo, csv: bool, seed: Option<Seed> },
+    Distribution { truncated: TruncatedDistribution, count: usize, summary: bool, seed: Option<Seed> },
+    Columns { path: Option<String>, count: usize, seed: Option<Seed> },
+    Cdf { path: Option<String>, count: usize, summary: bool, seed: Option<Seed> },
+    Weights { dirichlet: Dirichlet, count: usize, csv: bool, seed: Option<Seed> },
+    Correlated { normal: MultivariateNormal, count: usize, summary: bool, seed: Option<Seed> },
+    Graph { spec: RandomGraph, dot: bool, seed: Option<Seed> },
+    Design { design: ExperimentDesign, kind: DesignKind, blocks: usize, csv: bool, seed: Option<Seed> },
+    SelfTest { lower: i64, upper: i64, samples: usize },
+    Fake { schema: FieldSchema, count: usize, json: bool, seed: Option<Seed> },
+    Documents { path: String, locale: FakeLocale, count: usize, seed: Option<Seed> },
+    Pattern { pattern: StringPattern, count: usize, duplicates: bool, seed: Option<Seed> },
+    Codes { spec: CodeSpec, count: usize, exclude: Option<String>, seed: Option<Seed> },
+    Pins { spec: PinSpec, count: usize, seed: Option<Seed> },
+    Passphrase { spec: PassphraseSpec, wordlist: Option<String>, count: usize, seed: Option<Seed> },
+    Bytes { size: ByteSize, output: Option<String>, seed: Option<Seed> },
+    Address { spec: AddressSpec, count: usize, duplicates: bool, seed: Option<Seed> },
+    /// Diagnostic, not listed in the usage text
+    Bench { lower: i64, upper: i64, count: usize, duplicates: bool },
+    Serve { addr: String },
+    Grpc { addr: String },
+    Daemon { socket: String },
+    RegisterUrl,
+    Help,
+}
+
+/// Run the command line and return the process exit code
+pub fn run(args: &[String]) -> i32 {
+    let command = match parse(args) {
+        Ok(command) => command,
+        Err(e) => {
+            eprintln!("error: {}\n\n{}", e, USAGE);
+            return 2;
+        }
+    };
+
+    let result = match command {
+        Command::Help => {
+            print!("{}", USAGE);
+            return 0;
+        }
+        Command::Sample { path, count, seed } => sample_lines(path.as_deref(), count, seed),
+        Command::Pick { count, duplicates } => read_items(io::stdin().lock())
+            .map_err(Into::into)
+            .and_then(|items| pick(&items, count, duplicates)),
+        Command::Bracket { csv, seed } => read_items(io::stdin().lock())
+            .map_err(Into::into)
+            .and_then(|names| bracket(names, csv, seed)),
+        Command::Santa { exclusions, output, seed } => read_items(io::stdin().lock())
+            .map_err(Into::into)
+            .and_then(|names| santa(names, &exclusions, output.as_deref(), seed)),
+        Command::Pair { items, exclusions, csv, seed } => read_items(io::stdin().lock())
+            .map_err(Into::into)
+            .and_then(|names| pair(names, &items, &exclusions, csv, seed)),
+        Command::Range { lower, upper, count, duplicates, transforms, labels, seed } => {
+            range(lower, upper, count, duplicates, transforms, labels.as_deref(), seed)
+        }
+        Command::Primes { lower, upper, count, duplicates, seed } => primes(lower, upper, count, duplicates, seed),
+        Command::Partition { total, parts, lower, upper, seed } => partition(total, parts, lower, upper, seed),
+        Command::Trials { chance, successes, count, seed } => trials(chance, successes, count, seed),
+        Command::Strata { duplicates, seed } => io::read_to_string(io::stdin().lock())
+            .map_err(Into::into)
+            .and_then(|strata| stratified(strata, duplicates, seed)),
+        Command::Keno { spec, count, csv, seed } => keno(spec, count, csv, seed),
+        Command::Gacha { pulls, csv, seed } => read_items(io::stdin().lock())
+            .map_err(Into::into)
+            .and_then(|tiers| gacha(&tiers, pulls, csv, seed)),
+        Command::Pool { pool, count, csv, seed } => dice_pool(pool, count, csv, seed),
+        Command::Bootstrap { data, resamples, size, csv, seed } => bootstrap(&data, resamples, size, csv, seed),
+        Command::Merge { files, unique, summary } => merge(&files, unique, summary),
+        Command::Walk { walk, seed } => walk
+            .series(seed.unwrap_or_else(|| Seed::random(&mut rand::thread_rng())))
+            .map(|series| RandomWalk::to_csv(&series).lines().map(String::from).collect())
+            .map_err(Into::into),
+        Command::MonteCarlo { demo, csv, seed } => demo
+            .run(seed.unwrap_or_else(|| Seed::random(&mut rand::thread_rng())))
+            .map(|report| if csv { report.to_csv() } else { report.to_string() }.lines().map(String::from).collect())
+            .map_err(Into::into),
+        Command::Distribution { truncated, count, summary, seed } => truncated
+            .sample(count, seed.unwrap_or_else(|| Seed::random(&mut rand::thread_rng())))
+            .map(|report| match summary {
+                true => report.to_text().lines().map(String::from).collect(),
+                false => report.values.iter().map(f64::to_string).collect(),
+            })
+            .map_err(Into::into),
+        Command::Columns { path, count, seed } => match path {
+            Some(path) => std::fs::read_to_string(&path).map_err(|e| format!("could not read {}: {}", path, e).into()),
+            None => io::read_to_string(io::stdin().lock()).map_err(Into::into),
+        }
+            .and_then(|columns| columns_table(&columns, count, seed)),
+        Command::Cdf { path, count, summary, seed } => match path {
+            Some(path) => std::fs::read_to_string(&path).map_err(|e| format!("could not read {}: {}", path, e).into()),
+            None => io::read_to_string(io::stdin().lock()).map_err(Into::into),
+        }
+            .and_then(|table| cdf(&table, count, summary, seed)),
+        Command::Weights { dirichlet, count, csv, seed } => dirichlet
+            .sample(count, seed.unwrap_or_else(|| Seed::random(&mut rand::thread_rng())))
+            .map(|sample| match csv {
+                true => sample.to_csv().lines().map(String::from).collect(),
+                false => sample.vectors.iter().map(|vector| vector.iter().map(|&value| DirichletSample::format_weight(value)).collect::<Vec<_>>().join(" ")).collect(),
+            })
+            .map_err(Into::into),
+        Command::Correlated { normal, count, summary, seed } => normal
+            .sample(count, seed.unwrap_or_else(|| Seed::random(&mut rand::thread_rng())))
+            .map(|sample| if summary { sample.to_text() } else { sample.to_csv() }.lines().map(String::from).collect())
+            .map_err(Into::into),
+        Command::Graph { spec, dot, seed } => spec
+            .generate(seed.unwrap_or_else(|| Seed::random(&mut rand::thread_rng())))
+            .map(|graph| if dot { graph.to_dot() } else { graph.to_edge_list() }.lines().map(String::from).collect())
+            .map_err(Into::into),
+        Command::Design { design, kind, blocks, csv, seed } => design
+            .generate(kind, blocks, seed.unwrap_or_else(|| Seed::random(&mut rand::thread_rng())))
+            .map(|table| if csv { table.to_csv() } else { table.to_text() }.lines().map(String::from).collect())
+            .map_err(Into::into),
+        Command::SelfTest { lower, upper, samples } => return self_test(lower, upper, samples),
+        Command::Bytes { size, output, seed } => return write_bytes(size, output.as_deref(), seed),
+        Command::Fake { schema, count, json, seed } => fake(&schema, count, json, seed),
+        Command::Documents { path, locale, count, seed } => documents(&path, locale, count, seed),
+        Command::Pattern { pattern, count, duplicates, seed } => {
+            let seed = seed.unwrap_or_else(|| Seed::random(&mut rand::thread_rng()));
+            pattern.generate(count, !duplicates, seed).map_err(Into::into)
+        }
+        Command::Codes { spec, count, exclude, seed } => codes(&spec, count, exclude.as_deref(), seed),
+        Command::Pins { spec, count, seed } => {
+            let seed = seed.unwrap_or_else(|| Seed::random(&mut rand::thread_rng()));
+            spec.generate(count, seed).map_err(Into::into)
+        }
+        Command::Passphrase { spec, wordlist, count, seed } => passphrases(&spec, wordlist.as_deref(), count, seed),
+        Command::Address { spec, count, duplicates, seed } => {
+            let seed = seed.unwrap_or_else(|| Seed::random(&mut rand::thread_rng()));
+            spec.generate(count, !duplicates, seed).map_err(Into::into)
+        }
+        Command::Serve { addr } => {
+            return match server::serve(&addr) {
+                Ok(()) => 0,
+                Err(e) => {
+                    eprintln!("error: could not serve on {}: {}", addr, e);
+                    1
+                }
+            }
+        }
+        Command::Grpc { addr } => {
+            return match grpc::serve(&addr) {
+                Ok(()) => 0,
+                Err(e) => {
+                    eprintln!("error: could not serve gRPC on {}: {}", addr, e);
+                    1
+                }
+            }
+        }
+        Command::Daemon { socket } => {
+            return match daemon::run(&socket) {
+                Ok(()) => 0,
+                Err(e) => {
+                    eprintln!("error: could not listen on {}: {}", socket, e);
+                    1
+                }
+            }
+        }
+        Command::Bench { lower, upper, count, duplicates } => bench(lower, upper, count, duplicates),
+        Command::RegisterUrl => deep_link::register().map(|done| vec![done]).map_err(Into::into),
+    };
+
+    match result.and_then(|lines| write_lines(&lines).map_err(Into::into)) {
+        Ok(()) => 0,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            1
+        }
+    }
+}
+
+fn parse(args: &[String]) -> Result<Command, String> {
+    let Some((name, options)) = args.split_first() else {
+        return Ok(Command::Help);
+    };
+
+    let mut count = None;
+    let mut duplicates = false;
+    let mut lower = GeneratorConfig::default().lower_bound;
+    let mut upper = GeneratorConfig::default().upper_bound;
+    let mut seed = None;
+    let mut addr = None;
+    let mut socket = None;
+    let mut fields = "name,email,phone".to_string();
+    let mut locale = FakeLocale::default();
+    let mut json = false;
+    let mut csv = false;
+    let mut schema_path = None;
+    let mut pattern = None;
+    let mut code_spec = CodeSpec::default();
+    let mut exclude = None;
+    let mut pin_spec = PinSpec::default();
+    let mut passphrase = PassphraseSpec::default();
+    let mut wordlist = None;
+    let mut size = None;
+    let mut address_kind = None;
+    let mut address_range = String::new();
+    let mut output = None;
+    let mut exclusions = Vec::new();
+    let mut items = None;
+    let mut data = None;
+    let mut resample_size = None;
+    let mut walk = RandomWalk::default();
+    let mut formula = None;
+    let (mut integral_lower, mut integral_upper) = (0.0, 1.0);
+    let mut graph = RandomGraph::default();
+    let mut dot = false;
+    let mut design_kind = None;
+    let mut distribution = None;
+    let (mut min, mut max) = (None, None);
+    let mut summary = false;
+    let mut components = None;
+    let mut alpha = "1".to_string();
+    let mut correlation = None;
+    let (mut means, mut sds) = (String::new(), String::new());
+    let mut treatments = None;
+    let mut keno = KenoSpec::default();
+    let mut dice_pool = None;
+    let mut total = None;
+    let mut part_lower = 0;
+    let mut part_upper = None;
+    let mut chance = None;
+    let mut successes = 1;
+    let mut prime_lower = None;
+    let mut prime_upper = None;
+    let mut bits = None;
+    let mut transforms = Vec::new();
+    let mut labels = None;
+    let mut files = Vec::new();
+    let mut unique = false;
+    let is_range = matches!(name.as_str(), "range" | "selftest" | "bench");
+
+    let mut options = options.iter();
+    while let Some(option) = options.next() {
+        let mut value = || options.next().ok_or_else(|| format!("{} needs a value", option));
+        match option.as_str() {
+            "-n" | "--count" => count = Some(parse_value(option, value()?)?),
+            "-d" | "--duplicates" => duplicates = true,
+            "--from" if is_range => lower = parse_value(option, value()?)?,
+            "--to" if is_range => upper = parse_value(option, value()?)?,
+            "--from" if name == "primes" => prime_lower = Some(parse_value(option, value()?)?),
+            "--to" if name == "primes" => prime_upper = Some(parse_value(option, value()?)?),
+            "--from" if name == "partition" => part_lower = parse_value(option, value()?)?,
+            "--to" if name == "partition" => part_upper = Some(parse_value(option, value()?)?),
+            "--successes" if name == "trials" => successes = parse_value(option, value()?)?,
+            "--transform" if name == "range" => transforms = Transform::parse_list(value()?).map_err(|e| e.to_string())?,
+            "--labels" if name == "range" => labels = Some(value()?.clone()),
+            "--bits" if name == "primes" => bits = Some(parse_value(option, value()?)?),
+            "--seed" if matches!(name.as_str(), "sample" | "santa" | "bracket" | "pair" | "range" | "primes" | "partition" | "trials" | "strata" | "keno" | "gacha" | "pool" | "bootstrap" | "walk" | "dist" | "cdf" | "weights" | "mvnorm" | "montecarlo" | "graph" | "design" | "fake" | "columns" | "pattern" | "codes" | "pins" | "passphrase" | "bytes" | "address") => seed = Some(parse_value(option, value()?)?),
+            "--addr" if matches!(name.as_str(), "serve" | "--serve" | "grpc") => addr = Some(value()?.clone()),
+            "--socket" if name == "daemon" => socket = Some(value()?.clone()),
+            "--fields" if name == "fake" => fields = value()?.clone(),
+            "--locale" if name == "fake" => locale = parse_value(option, value()?)?,
+            "--json" if name == "fake" => json = true,
+            "--schema" if name == "fake" => schema_path = Some(value()?.clone()),
+            "--length" if name == "codes" => code_spec.length = parse_value(option, value()?)?,
+            "--group" if name == "codes" => code_spec.group = parse_value(option, value()?)?,
+            "--check" if name == "codes" => code_spec.check = parse_value(option, value()?)?,
+            "--exclude" if name == "codes" => exclude = Some(value()?.clone()),
+            "--length" if name == "pins" => pin_spec.length = parse_value(option, value()?)?,
+            "--allow-repeats" if name == "pins" => pin_spec.no_repeats = false,
+            "--allow-years" if name == "pins" => pin_spec.no_years = false,
+            "--allow-sequences" if name == "pins" => pin_spec.no_sequences = false,
+            "--words" if name == "passphrase" => passphrase.words = parse_value(option, value()?)?,
+            "--separator" if name == "passphrase" => passphrase.separator = value()?.clone(),
+            "--case" if name == "passphrase" => passphrase.capitalization = parse_value(option, value()?)?,
+            "--wordlist" if name == "passphrase" => wordlist = Some(value()?.clone()),
+            "--size" if name == "bytes" => size = Some(value()?.parse::<ByteSize>().map_err(|e| e.to_string())?),
+            "--size" if name == "bootstrap" => resample_size = Some(parse_value(option, value()?)?),
+            "--start" if name == "walk" => walk.start = parse_value(option, value()?)?,
+            "--drift" if name == "walk" => walk.drift = parse_value(option, value()?)?,
+            "--phi" if name == "walk" => walk.phi = parse_value(option, value()?)?,
+            "--step" if name == "walk" => walk.step = parse_value(option, value()?)?,
+            "--scale" if name == "walk" => walk.scale = parse_value(option, value()?)?,
+            "--integral" if name == "montecarlo" => formula = Some(value()?.parse::<Formula>().map_err(|e| e.to_string())?),
+            "--from" if name == "montecarlo" => integral_lower = parse_value(option, value()?)?,
+            "--to" if name == "montecarlo" => integral_upper = parse_value(option, value()?)?,
+            "--min" if name == "dist" => min = Some(parse_value(option, value()?)?),
+            "--max" if name == "dist" => max = Some(parse_value(option, value()?)?),
+            "--summary" if matches!(name.as_str(), "dist" | "cdf" | "mvnorm" | "merge") => summary = true,
+            "--unique" if name == "merge" => unique = true,
+            "--alpha" if name == "weights" => alpha = value()?.clone(),
+            "--corr" if name == "mvnorm" => correlation = Some(value()?.clone()),
+            "--mean" if name == "mvnorm" => means = value()?.clone(),
+            "--sd" if name == "mvnorm" => sds = value()?.clone(),
+            "--model" if name == "graph" => graph.model = parse_value(option, value()?)?,
+            "--nodes" if name == "graph" => graph.nodes = parse_value(option, value()?)?,
+            "--edges" if name == "graph" => graph.edges = parse_value(option, value()?)?,
+            "--dot" if name == "graph" => dot = true,
+            "--csv" if matches!(name.as_str(), "bracket" | "pair" | "keno" | "gacha" | "pool" | "bootstrap" | "montecarlo" | "design" | "weights") => csv = true,
+            "--pool" if name == "keno" => keno.pool = parse_value(option, value()?)?,
+            "--picks" if name == "keno" => keno.picks = parse_value(option, value()?)?,
+            "--exclude" if name == "santa" || name == "pair" => exclusions.push(value()?.clone()),
+            "-o" | "--output" if name == "bytes" || name == "santa" => output = Some(value()?.clone()),
+            "--in" if name == "address" => address_range = value()?.clone(),
+            text if name == "address" && address_kind.is_none() && !text.starts_with('-') => {
+                address_kind = Some(text.parse::<AddressKind>().map_err(|e| e.to_string())?)
+            }
+            text if name == "pattern" && pattern.is_none() && !text.starts_with('-') => {
+                pattern = Some(text.parse::<StringPattern>().map_err(|e| e.to_string())?)
+            }
+            text if name == "dist" && distribution.is_none() && !text.starts_with("--") => {
+                distribution = Some(text.parse::<Distribution>().map_err(|e| e.to_string())?)
+            }
+            text if name == "weights" && components.is_none() && !text.starts_with('-') => components = Some(parse_value("N", text)?),
+            text if name == "design" && design_kind.is_none() && !text.starts_with('-') => {
+                design_kind = Some(text.parse::<DesignKind>().map_err(|e| e.to_string())?)
+            }
+            text if name == "design" && treatments.is_none() && !text.starts_with('-') => {
+                treatments = Some(ExperimentDesign::parse(text).map_err(|e| e.to_string())?)
+            }
+            text if name == "pool" && dice_pool.is_none() && !text.starts_with('-') => {
+                dice_pool = Some(text.parse::<DicePool>().map_err(|e| e.to_string())?)
+            }
+            text if name == "partition" && total.is_none() && !text.starts_with("--") => total = Some(parse_value("TOTAL", text)?),
+            text if name == "trials" && chance.is_none() && !text.starts_with('-') => chance = Some(parse_value("CHANCE", text)?),
+            text if name == "pair" && items.is_none() && !text.starts_with('-') => items = Some(text.to_string()),
+            text if name == "sample" && data.is_none() && !text.starts_with('-') => data = Some(text.to_string()),
+            text if name == "columns" && data.is_none() && !text.starts_with('-') => data = Some(text.to_string()),
+            text if name == "cdf" && data.is_none() && !text.starts_with('-') => data = Some(text.to_string()),
+            text if name == "bootstrap" && data.is_none() && !text.starts_with('-') => data = Some(text.to_string()),
+            text if name == "merge" && !text.starts_with('-') => files.push(text.to_string()),
+            _ => return Err(format!("unknown option '{}'", option)),
+        }
+    }
+
+    match name.as_str() {
+        "pick" => Ok(Command::Pick { count: count.unwrap_or(1), duplicates }),
+        "sample" => Ok(Command::Sample { path: data, count: count.unwrap_or(1), seed }),
+        "bracket" => Ok(Command::Bracket { csv, seed }),
+        "santa" => Ok(Command::Santa { exclusions, output, seed }),
+        "pair" => Ok(Command::Pair {
+            items: items.ok_or("pair needs a file of items to hand out")?,
+            exclusions,
+            csv,
+            seed,
+        }),
+        "range" => Ok(Command::Range { lower, upper, count: count.unwrap_or(1), duplicates, transforms, labels, seed }),
+        "primes" => {
+            let (lower, upper) = match bits {
+                Some(_) if prime_lower.is_some() || prime_upper.is_some() => return Err("use either --bits or --from and --to".to_string()),
+                Some(bits) => PrimeSource::bit_range(bits).map_err(|e| e.to_string())?,
+                None => (prime_lower.unwrap_or(lower.into()), prime_upper.unwrap_or(upper.into())),
+            };
+            Ok(Command::Primes { lower, upper, count: count.unwrap_or(1), duplicates, seed })
+        }
+        "partition" => Ok(Command::Partition {
+            total: total.ok_or("partition needs a total to split")?,
+            parts: count.ok_or("partition needs the number of parts, e.g. -n 7")?,
+            lower: part_lower,
+            upper: part_upper,
+            seed,
+        }),
+        "trials" => Ok(Command::Trials {
+            chance: chance.ok_or("trials needs the chance of success of each try, e.g. 0.25")?,
+            successes,
+            count: count.unwrap_or(1),
+            seed,
+        }),
+        "strata" if count.is_none() => Ok(Command::Strata { duplicates, seed }),
+        "strata" => Err("the strata set how many to draw; leave out -n".to_string()),
+        "keno" => {
+            keno.validate().map_err(|e| e.to_string())?;
+            Ok(Command::Keno { spec: keno, count: count.unwrap_or(1), csv, seed })
+        }
+        "pool" => Ok(Command::Pool {
+            pool: dice_pool.ok_or("pool needs dice like 6d10>=8")?,
+            count: count.unwrap_or(1),
+            csv,
+            seed,
+        }),
+        "gacha" => Ok(Command::Gacha { pulls: count.unwrap_or(1), csv, seed }),
+        "walk" => {
+            walk.length = count.unwrap_or(walk.length);
+            walk.validate().map_err(|e| e.to_string())?;
+            Ok(Command::Walk { walk, seed })
+        }
+        "montecarlo" => {
+            let target = match formula {
+                Some(formula) => MonteCarloTarget::Integral { formula, lower: integral_lower, upper: integral_upper },
+                None if (integral_lower, integral_upper) != (0.0, 1.0) => return Err("--from and --to need --integral".to_string()),
+                None => MonteCarloTarget::Pi,
+            };
+            let demo = MonteCarlo { target, samples: count.unwrap_or(1_000_000) };
+            demo.validate().map_err(|e| e.to_string())?;
+            Ok(Command::MonteCarlo { demo, csv, seed })
+        }
+        "dist" => {
+            let distribution = distribution.ok_or("dist needs a distribution, e.g. 'normal(100, 15)'")?;
+            Ok(Command::Distribution {
+                truncated: TruncatedDistribution::new(distribution, min, max).map_err(|e| e.to_string())?,
+                count: count.unwrap_or(1),
+                summary,
+                seed,
+            })
+        }
+        "cdf" => Ok(Command::Cdf { path: data, count: count.unwrap_or(1), summary, seed }),
+        "weights" => Ok(Command::Weights {
+            dirichlet: Dirichlet::parse(components, &alpha).map_err(|e| e.to_string())?,
+            count: count.unwrap_or(1),
+            csv,
+            seed,
+        }),
+        "mvnorm" => {
+            let correlation = correlation.ok_or("mvnorm needs a correlation matrix, e.g. --corr '1, 0.5; 0.5, 1'")?;
+            Ok(Command::Correlated {
+                normal: MultivariateNormal::parse(&means, &sds, &correlation).map_err(|e| e.to_string())?,
+                count: count.unwrap_or(1),
+                summary,
+                seed,
+            })
+        }
+        "design" => {
+            let kind = design_kind.ok_or("design needs a kind: latin, balanced or blocks")?;
+            if kind != DesignKind::Blocks && count.is_some() {
+                return Err("-n sets the number of blocks; a square has one row per treatment".to_string());
+            }
+            Ok(Command::Design {
+                design: treatments.ok_or("design needs the treatments, e.g. 4 or 'Placebo,Low,High'")?,
+                kind,
+                blocks: count.unwrap_or(1),
+                csv,
+                seed,
+            })
+        }
+        "graph" if count.is_some() => Err("set the size of a graph with --nodes and --edges".to_string()),
+        "graph" => {
+            graph.validate().map_err(|e| e.to_string())?;
+            Ok(Command::Graph { spec: graph, dot, seed })
+        }
+        "bootstrap" => Ok(Command::Bootstrap {
+            data: data.ok_or("bootstrap needs a file of numbers")?,
+            resamples: count.unwrap_or(1000),
+            size: resample_size,
+            csv,
+            seed,
+        }),
+        "merge" if files.is_empty() => Err("merge needs the result files to merge".to_string()),
+        "merge" => Ok(Command::Merge { files, unique, summary }),
+        "columns" => Ok(Command::Columns { path: data, count: count.unwrap_or(1), seed }),
+        "fake" => match schema_path {
+            Some(path) => Ok(Command::Documents { path, locale, count: count.unwrap_or(1), seed }),
+            None => Ok(Command::Fake {
+                schema: FieldSchema::parse(&fields, locale).map_err(|e| e.to_string())?,
+                count: count.unwrap_or(1),
+                json,
+                seed,
+            }),
+        },
+        "pattern" => Ok(Command::Pattern {
+            pattern: pattern.ok_or("pattern needs a pattern, e.g. 'AA-####'")?,
+            count: count.unwrap_or(1),
+            duplicates,
+            seed,
+        }),
+        "codes" => Ok(Command::Codes { spec: code_spec, count: count.unwrap_or(1), exclude, seed }),
+        "pins" => Ok(Command::Pins { spec: pin_spec, count: count.unwrap_or(1), seed }),
+        "passphrase" => Ok(Command::Passphrase { spec: passphrase, wordlist, count: count.unwrap_or(1), seed }),
+        "bytes" => Ok(Command::Bytes { size: size.ok_or("bytes needs --size, e.g. --size 10MB")?, output, seed }),
+        "address" => Ok(Command::Address {
+            spec: AddressSpec::parse(address_kind.ok_or("address needs a kind: mac, ipv4 or ipv6")?, &address_range)
+                .map_err(|e| e.to_string())?,
+            count: count.unwrap_or(1),
+            duplicates,
+            seed,
+        }),
+        "selftest" => Ok(Command::SelfTest { lower, upper, samples: count.unwrap_or(100_000) }),
+        "bench" => Ok(Command::Bench { lower, upper, count: count.unwrap_or(1000), duplicates }),
+        "serve" | "--serve" => Ok(Command::Serve { addr: addr.unwrap_or_else(|| server::DEFAULT_ADDR.to_string()) }),
+        "grpc" => Ok(Command::Grpc { addr: addr.unwrap_or_else(|| grpc::DEFAULT_ADDR.to_string()) }),
+        "daemon" => Ok(Command::Daemon { socket: socket.unwrap_or_else(daemon::default_endpoint) }),
+        "register-url" => Ok(Command::RegisterUrl),
+        "help" | "-h" | "--help" => Ok(Command::Help),
+        _ => Err(format!("unknown command '{}'", name)),
+    }
+}
+
+fn parse_value<T: std::str::FromStr>(option: &str, value: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("invalid value '{}' for {}", value, option))
+}
+
+/// Non-empty lines of the input, trimmed
+fn read_items(input: impl BufRead) -> io::Result<Vec<String>> {
+    let mut items = Vec::new();
+    for line in input.lines() {
+        let line = line?;
+        let line = line.trim();
+        if !line.is_empty() {
+            items.push(line.to_string());
+        }
+    }
+    Ok(items)
+}
+
+/// Pick lines by drawing their positions with the regular generator
+fn pick(items: &[String], count: usize, duplicates: bool) -> Result<Vec<String>, Box<dyn Error>> {
+    if items.is_empty() {
+        return Err("nothing to pick from: stdin had no lines".into());
+    }
+    let indices = generate(GeneratorConfig {
+        lower_bound: 0,
+        upper_bound: items.len() as i64 - 1,
+        num_to_generate: count,
+        allow_duplicates: duplicates,
+        ..Default::default()
+    })?;
+    Ok(indices.into_iter().map(|i| items[i as usize].clone()).collect())
+}
+
+fn range(
+    lower: i64,
+    upper: i64,
+    count: usize,
+    duplicates: bool,
+    transforms: Vec<Transform<i64>>,
+    labels: Option<&str>,
+    seed: Option<Seed>,
+) -> Result<Vec<String>, Box<dyn Error>> {
+    let labels = match labels {
+        Some(path) => LabelMap::parse(&std::fs::read_to_string(path).map_err(|e| format!("could not read {}: {}", path, e))?)?,
+        None => LabelMap::default(),
+    };
+    let format = NumberFormat { labels, ..Default::default() };
+    let numbers = generate(GeneratorConfig {
+        lower_bound: lower,
+        upper_bound: upper,
+        num_to_generate: count,
+        allow_duplicates: duplicates,
+        transforms,
+        seed,
+        ..Default::default()
+    })?;
+    Ok(numbers.iter().map(|&num| format.format(num)).collect())
+}
+
+/// Secret Santa pairs as `giver -> receiver`, or one file per giver in `output`
+fn santa(names: Vec<String>, exclusions: &[String], output: Option<&str>, seed: Option<Seed>) -> Result<Vec<String>, Box<dyn Error>> {
+    let mut santa = SecretSanta::new(names)?;
+    santa.exclude_pairs(&exclusions.join("\n"))?;
+    let assignments = santa.draw(seed.unwrap_or_else(|| Seed::random(&mut rand::thread_rng())))?;
+    match output {
+        Some(dir) => {
+            let paths = SecretSanta::write_assignments(&assignments, Path::new(dir))?;
+            Ok(paths.iter().map(|path| path.display().to_string()).collect())
+        }
+        None => Ok(assignments.iter().map(|a| format!("{} -> {}", a.giver, a.receiver)).collect()),
+    }
+}
+
+/// A knockout bracket drawn as a tree, or its matches as CSV
+fn bracket(names: Vec<String>, csv: bool, seed: Option<Seed>) -> Result<Vec<String>, Box<dyn Error>> {
+    let bracket = Bracket::draw(names, seed.unwrap_or_else(|| Seed::random(&mut rand::thread_rng())))?;
+    let text = if csv { bracket.to_csv() } else { bracket.to_text() };
+    Ok(text.lines().map(String::from).collect())
+}
+
+/// The lines of `items` handed out to `names`, one line per person or CSV
+fn pair(names: Vec<String>, path: &str, exclusions: &[String], csv: bool, seed: Option<Seed>) -> Result<Vec<String>, Box<dyn Error>> {
+    let text = std::fs::read_to_string(path).map_err(|e| format!("could not read {}: {}", path, e))?;
+    let items = read_items(text.as_bytes())?;
+    let mut pairing = Pairing::new(names, items)?;
+    pairing.forbid_pairs(&exclusions.join("\n"))?;
+    let pairs = pairing.draw(seed.unwrap_or_else(|| Seed::random(&mut rand::thread_rng())))?;
+    let text = if csv { Pairing::to_csv(&pairs) } else { pairing.to_text(&pairs) };
+    Ok(text.lines().map(String::from).collect())
+}
+
+/// Keno tickets, one per line with repeats flagged, or as CSV
+fn keno(spec: KenoSpec, count: usize, csv: bool, seed: Option<Seed>) -> Result<Vec<String>, Box<dyn Error>> {
+    let tickets = spec.tickets(count, seed.unwrap_or_else(|| Seed::random(&mut rand::thread_rng())))?;
+    let text = if csv { spec.to_csv(&tickets) } else { spec.to_text(&tickets) };
+    Ok(text.lines().map(String::from).collect())
+}
+
+/// Counts per rarity tier after `pulls` pulls from the loot table in `tiers`
+fn gacha(tiers: &[String], pulls: usize, csv: bool, seed: Option<Seed>) -> Result<Vec<String>, Box<dyn Error>> {
+    let table = LootTable::parse(&tiers.join("\n"))?;
+    let report = table.simulate(pulls, seed.unwrap_or_else(|| Seed::random(&mut rand::thread_rng())))?;
+    let text = if csv { report.to_csv() } else { report.to_text() };
+    Ok(text.lines().map(String::from).collect())
+}
+
+/// Dice pool rolls with their successes, one per line or as CSV
+fn dice_pool(pool: DicePool, count: usize, csv: bool, seed: Option<Seed>) -> Result<Vec<String>, Box<dyn Error>> {
+    let rolls = pool.rolls(count, seed.unwrap_or_else(|| Seed::random(&mut rand::thread_rng())))?;
+    let text = if csv { pool.to_csv(&rolls) } else { pool.to_text(&rolls) };
+    Ok(text.lines().map(String::from).collect())
+}
+
+/// The distribution of the mean over resamples of the numbers in FILE, as a summary with a histogram or one mean per line of CSV
+fn bootstrap(path: &str, resamples: usize, size: Option<usize>, csv: bool, seed: Option<Seed>) -> Result<Vec<String>, Box<dyn Error>> {
+    let text = std::fs::read_to_string(path).map_err(|e| format!("could not read {}: {}", path, e))?;
+    let report = Bootstrap::parse(&text)?.resample(resamples, size, seed.unwrap_or_else(|| Seed::random(&mut rand::thread_rng())))?;
+    let text = if csv { report.to_csv() } else { report.to_text() };
+    Ok(text.lines().map(String::from).collect())
+}
+
+/// The numbers from saved result files in turn, or how many each had and the stats of them all
+fn merge(paths: &[String], unique: bool, summary: bool) -> Result<Vec<String>, Box<dyn Error>> {
+    let mut files = Vec::new();
+    for path in paths {
+        let text = std::fs::read_to_string(path).map_err(|e| format!("could not read {}: {}", path, e))?;
+        let numbers = ExportFormat::from_path(path).read_numbers::<i64>(&text).map_err(|e| format!("{}: {}", path, e))?;
+        files.push((path.clone(), numbers));
+    }
+    let merged = MergedResults::new(files, unique);
+    Ok(match summary {
+        true => merged.to_text().lines().map(String::from).collect(),
+        false => merged.numbers.iter().map(i64::to_string).collect(),
+    })
+}
+
+/// Rows of test data with a header
+fn columns_table(columns: &str, count: usize, seed: Option<Seed>) -> Result<Vec<String>, Box<dyn Error>> {
+    let schema = ColumnSchema::parse(columns)?;
+    let rows = schema.generate(count, seed.unwrap_or_else(|| Seed::random(&mut rand::thread_rng())))?;
+    Ok(schema.to_csv(&rows).lines().map(String::from).collect())
+}
+
+/// Values from a table of cumulative probabilities
+fn cdf(table: &str, count: usize, summary: bool, seed: Option<Seed>) -> Result<Vec<String>, Box<dyn Error>> {
+    let sample = CdfTable::parse(table)?.sample(count, seed.unwrap_or_else(|| Seed::random(&mut rand::thread_rng())))?;
+    Ok(match summary {
+        true => sample.to_text().lines().map(String::from).collect(),
+        false => sample.values.iter().map(f64::to_string).collect(),
+    })
+}
+
+/// Random primes; `i128` so that `--bits` can go up to 127
+fn primes(lower: i128, upper: i128, count: usize, duplicates: bool, seed: Option<Seed>) -> Result<Vec<String>, Box<dyn Error>> {
+    let mut generator = Generator::<i128>::with_config(Config {
+        mode: GeneratorMode::PRIMES,
+        lower_bound: lower,
+        upper_bound: upper,
+        num_to_generate: count,
+        allow_duplicates: duplicates,
+        seed,
+        ..Default::default()
+    })?;
+    generator.generate_numbers()?;
+    Ok(generator.get_numbers().iter().map(|prime| prime.to_string()).collect())
+}
+
+/// Lines sampled from a file, or from stdin without a path
+fn sample_lines(path: Option<&str>, count: usize, seed: Option<Seed>) -> Result<Vec<String>, Box<dyn Error>> {
+    let sample = LineSample { count, seed: seed.unwrap_or_else(|| Seed::random(&mut rand::thread_rng())) };
+    let sampled = match path {
+        Some(path) => {
+            let file = std::fs::File::open(path).map_err(|e| format!("could not read {}: {}", path, e))?;
+            sample.sample(&mut io::BufReader::new(file))?
+        }
+        None => sample.sample(&mut io::stdin().lock())?,
+    };
+    Ok(sampled.lines)
+}
+
+/// Random parts adding up to `total`; without an upper limit a part may take all of it
+fn partition(total: i64, parts: usize, lower: i64, upper: Option<i64>, seed: Option<Seed>) -> Result<Vec<String>, Box<dyn Error>> {
+    let mut generator = RandomGenerator::with_source(Box::new(PartitionSource { total }), GeneratorConfig {
+        mode: GeneratorMode::PARTITION,
+        num_to_generate: parts,
+        lower_bound: lower,
+        upper_bound: upper.unwrap_or(total.max(lower)),
+        seed,
+        ..Default::default()
+    })?;
+    generator.generate_numbers()?;
+    Ok(generator.get_numbers().iter().map(|part| part.to_string()).collect())
+}
+
+/// Tries until the first success, or until `successes` of them: the geometric or negative binomial distribution
+fn trials(chance: f64, successes: u64, count: usize, seed: Option<Seed>) -> Result<Vec<String>, Box<dyn Error>> {
+    let source: Box<dyn GeneratorSource<i64>> = match successes {
+        1 => Box::new(GeometricSource { chance }),
+        _ => Box::new(NegativeBinomialSource { chance, successes }),
+    };
+    let mut generator = RandomGenerator::with_source(source, GeneratorConfig {
+        mode: if successes == 1 { GeneratorMode::GEOMETRIC } else { GeneratorMode::NEGATIVE_BINOMIAL },
+        num_to_generate: count,
+        seed,
+        ..Default::default()
+    })?;
+    generator.generate_numbers()?;
+    Ok(generator.get_numbers().iter().map(|trials| trials.to_string()).collect())
+}
+
+/// Numbers from each stratum in turn
+fn stratified(strata: String, duplicates: bool, seed: Option<Seed>) -> Result<Vec<String>, Box<dyn Error>> {
+    let mut generator = RandomGenerator::with_source(Box::new(StratifiedSource { strata }), GeneratorConfig {
+        mode: GeneratorMode::STRATIFIED,
+        allow_duplicates: duplicates,
+        seed,
+        ..Default::default()
+    })?;
+    generator.generate_numbers()?;
+    Ok(generator.get_numbers().iter().map(|num| num.to_string()).collect())
+}
+
+/// Rows of fake data as CSV with a header, or as a JSON array
+fn fake(schema: &FieldSchema, count: usize, json: bool, seed: Option<Seed>) -> Result<Vec<String>, Box<dyn Error>> {
+    let seed = seed.unwrap_or_else(|| Seed::random(&mut rand::thread_rng()));
+    let rows = schema.generate(count, seed)?;
+    let text = if json { schema.to_json(&rows) } else { schema.to_csv(&rows) };
+    Ok(text.lines().map(String::from).collect())
+}
+
+/// Documents generated from a JSON Schema or template file, as a JSON array
+fn documents(path: &str, locale: FakeLocale, count: usize, seed: Option<Seed>) -> Result<Vec<String>, Box<dyn Error>> {
+    let text = std::fs::read_to_string(path).map_err(|e| format!("could not read {}: {}", path, e))?;
+    let mut schema = JsonSchema::parse(&text)?;
+    schema.locale = locale;
+    let seed = seed.unwrap_or_else(|| Seed::random(&mut rand::thread_rng()));
+    let documents = schema.generate(count, seed);
+    Ok(serde_json::to_string_pretty(&documents)?.lines().map(String::from).collect())
+}
+
+fn codes(spec: &CodeSpec, count: usize, exclude: Option<&str>, seed: Option<Seed>) -> Result<Vec<String>, Box<dyn Error>> {
+    let exclude = match exclude {
+        Some(path) => {
+            let text = std::fs::read_to_string(path).map_err(|e| format!("could not read {}: {}", path, e))?;
+            CodeSpec::collect_codes(&text)
+        }
+        None => Default::default(),
+    };
+    let seed = seed.unwrap_or_else(|| Seed::random(&mut rand::thread_rng()));
+    Ok(spec.generate(count, seed, &exclude)?)
+}
+
+/// The entropy goes to stderr so the passphrases can be piped on their own
+fn passphrases(spec: &PassphraseSpec, wordlist: Option<&str>, count: usize, seed: Option<Seed>) -> Result<Vec<String>, Box<dyn Error>> {
+    let wordlist = match wordlist {
+        Some(path) => {
+            let text = std::fs::read_to_string(path).map_err(|e| format!("could not read {}: {}", path, e))?;
+            Wordlist::parse(&text)?
+        }
+        None => Wordlist::bundled(),
+    };
+    let seed = seed.unwrap_or_else(|| Seed::random(&mut rand::thread_rng()));
+    let phrases = spec.generate(&wordlist, count, seed)?;
+    eprintln!("about {:.1} bits of entropy each ({} words)", spec.entropy_bits(&wordlist), wordlist.len());
+    Ok(phrases)
+}
+
+/// Write random bytes to `output` or stdout; shows a percentage on a terminal while writing a file
+fn write_bytes(size: ByteSize, output: Option<&str>, seed: Option<Seed>) -> i32 {
+    let seed = seed.unwrap_or_else(|| Seed::random(&mut rand::rngs::OsRng));
+    let bytes = RandomBytes { size, seed };
+    let show_progress = output.is_some() && io::stderr().is_terminal();
+    let mut shown = None;
+    let mut on_progress = |done: u64, total: u64| {
+        let percent = (done * 100).checked_div(total).unwrap_or(100);
+        if show_progress && shown != Some(percent) {
+            shown = Some(percent);
+            eprint!("\r{}%", percent);
+        }
+    };
+    let cancel = AtomicBool::new(false);
+    let result = match output {
+        Some(path) => std::fs::File::create(path)
+            .map_err(|e| format!("could not create {}: {}", path, e))
+            .and_then(|file| {
+                bytes.write_with_progress(&mut BufWriter::new(file), &cancel, &mut on_progress).map_err(|e| e.to_string())
+            }),
+        None => bytes
+            .write_with_progress(&mut BufWriter::new(io::stdout().lock()), &cancel, &mut on_progress)
+            .map_err(|e| e.to_string()),
+    };
+    if show_progress {
+        eprintln!();
+    }
+    match result {
+        Ok(()) => 0,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            1
+        }
+    }
+}
+
+/// Print the statistical self-test report; exit code 1 if the draws look suspicious
+fn self_test(lower: i64, upper: i64, samples: usize) -> i32 {
+    let report = RandomGenerator::with_config(GeneratorConfig {
+        lower_bound: lower,
+        upper_bound: upper,
+        ..Default::default()
+    })
+    .and_then(|mut generator| generator.self_test(samples));
+    match report {
+        Ok(report) => {
+            println!("{}", report);
+            if report.is_healthy() { 0 } else { 1 }
+        }
+        Err(e) => {
+            eprintln!("error: {}", e);
+            1
+        }
+    }
+}
+
+/// Time the unique-draw strategies for a range, for spotting slow settings and regressions
+fn bench(lower: i64, upper: i64, count: usize, duplicates: bool) -> Result<Vec<String>, Box<dyn Error>> {
+    let mut generator = RandomGenerator::with_config(GeneratorConfig {
+        lower_bound: lower,
+        upper_bound: upper,
+        num_to_generate: count,
+        allow_duplicates: duplicates,
+        ..Default::default()
+    })?;
+    let report = generator.benchmark(BENCH_ROUNDS)?;
+    Ok(report.to_string().lines().map(String::from).collect())
+}
+
+fn generate(config: GeneratorConfig) -> Result<Vec<i64>, Box<dyn Error>> {
+    let mut generator = RandomGenerator::with_config(GeneratorConfig { mode: GeneratorMode::RANGE, ..config })?;
+    generator.generate_numbers()?;
+    Ok(std::mem::take(generator.get_numbers_mut()))
+}
+
+fn write_lines(lines: &[String]) -> io::Result<()> {
+    let mut out = BufWriter::new(io::stdout().lock());
+    let result = lines.iter().try_for_each(|line| writeln!(out, "{}", line)).and_then(|_| out.flush());
+    match result {
+        // The reader went away (e.g. `| head -1`), which is not our error
+        Err(e) if e.kind() == io::ErrorKind::BrokenPipe => Ok(()),
+        other => other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use random_generator_core::{Capitalization, CheckCharacter, GraphModel, StepDistribution};
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn test_parse_commands() {
+        assert_eq!(parse(&args("pick -n 3")), Ok(Command::Pick { count: 3, duplicates: false }));
+        assert_eq!(parse(&args("sample access.log -n 20")), Ok(Command::Sample { path: Some("access.log".to_string()), count: 20, seed: None }));
+        assert_eq!(parse(&args("sample")), Ok(Command::Sample { path: None, count: 1, seed: None }));
+        assert!(parse(&args("sample a.log b.log")).is_err());
+        assert_eq!(
+            parse(&args("range --from -5 --to 5 -d")),
+            Ok(Command::Range { lower: -5, upper: 5, count: 1, duplicates: true, transforms: vec![], labels: None, seed: None })
+        );
+        let mut transformed = args("range --transform");
+        transformed.push("scale 10, offset 5".to_string());
+        assert_eq!(
+            parse(&transformed),
+            Ok(Command::Range { lower: 0, upper: 1024, count: 1, duplicates: false, transforms: vec![Transform::Scale(10), Transform::Offset(5)], labels: None, seed: None })
+        );
+        assert!(parse(&args("range --transform abs")).is_ok());
+        assert!(parse(&args("range --transform mod")).is_err());
+        assert!(parse(&args("primes --transform abs")).is_err());
+        assert!(matches!(parse(&args("range --to 2 --labels teams.txt")), Ok(Command::Range { labels: Some(path), .. }) if path == "teams.txt"));
+        let seed = "0f".repeat(32);
+        assert_eq!(
+            parse(&args(&format!("range --seed {}", seed))),
+            Ok(Command::Range { lower: 0, upper: 1024, count: 1, duplicates: false, transforms: vec![], labels: None, seed: Some(seed.parse().unwrap()) })
+        );
+        assert!(parse(&args("range --seed 12")).is_err());
+        assert!(parse(&args(&format!("selftest --seed {}", seed))).is_err());
+        assert!(parse(&args("pick --from 1")).is_err());
+        assert!(parse(&args("pick -n")).is_err());
+        assert_eq!(
+            parse(&args("selftest --to 6")),
+            Ok(Command::SelfTest { lower: 0, upper: 6, samples: 100_000 })
+        );
+        assert_eq!(
+            parse(&args("bench --to 1000000 -n 500")),
+            Ok(Command::Bench { lower: 0, upper: 1_000_000, count: 500, duplicates: false })
+        );
+        assert_eq!(
+            parse(&args("--serve --addr 0.0.0.0:9000")),
+            Ok(Command::Serve { addr: "0.0.0.0:9000".to_string() })
+        );
+        assert_eq!(
+            parse(&args("daemon --socket /tmp/draws.sock")),
+            Ok(Command::Daemon { socket: "/tmp/draws.sock".to_string() })
+        );
+        assert!(parse(&args("serve --socket /tmp/draws.sock")).is_err());
+        assert_eq!(parse(&args("grpc")), Ok(Command::Grpc { addr: grpc::DEFAULT_ADDR.to_string() }));
+        assert_eq!(
+            parse(&args("fake -n 5 --fields email,city --locale zh --json")),
+            Ok(Command::Fake {
+                schema: FieldSchema::parse("email,city", FakeLocale::Chinese).unwrap(),
+                count: 5,
+                json: true,
+                seed: None,
+            })
+        );
+        assert!(parse(&args("fake --fields age")).is_err());
+        assert_eq!(parse(&args("columns people.txt -n 100")), Ok(Command::Columns { path: Some("people.txt".to_string()), count: 100, seed: None }));
+        assert_eq!(parse(&args("columns")), Ok(Command::Columns { path: None, count: 1, seed: None }));
+        assert!(parse(&args("columns --json")).is_err());
+        assert_eq!(
+            parse(&args("pattern SN-[0-9A-F]{6} -n 20")),
+            Ok(Command::Pattern { pattern: "SN-[0-9A-F]{6}".parse().unwrap(), count: 20, duplicates: false, seed: None })
+        );
+        assert!(parse(&args("pattern -n 2")).is_err());
+        assert!(parse(&args("pattern A{9,1}")).is_err());
+        assert!(parse(&args("pattern AA ##")).is_err());
+        assert_eq!(
+            parse(&args("codes -n 100 --length 10 --group 5 --check LUHN --exclude batch1.txt")),
+            Ok(Command::Codes {
+                spec: CodeSpec { length: 10, group: 5, check: CheckCharacter::Luhn, ..Default::default() },
+                count: 100,
+                exclude: Some("batch1.txt".to_string()),
+                seed: None,
+            })
+        );
+        assert!(parse(&args("codes --check md5")).is_err());
+        assert_eq!(
+            parse(&args("pins -n 50 --length 4 --allow-years")),
+            Ok(Command::Pins { spec: PinSpec { length: 4, no_years: false, ..Default::default() }, count: 50, seed: None })
+        );
+        assert!(parse(&args("codes --allow-years")).is_err());
+        assert_eq!(
+            parse(&args("passphrase --words 4 --separator - --case title")),
+            Ok(Command::Passphrase {
+                spec: PassphraseSpec { words: 4, separator: "-".to_string(), capitalization: Capitalization::Title },
+                wordlist: None,
+                count: 1,
+                seed: None,
+            })
+        );
+        assert!(parse(&args("passphrase --case camel")).is_err());
+        assert_eq!(
+            parse(&args("bytes --size 1.5MB -o fixture.bin")),
+            Ok(Command::Bytes { size: ByteSize(1536 << 10), output: Some("fixture.bin".to_string()), seed: None })
+        );
+        assert!(parse(&args("bytes")).is_err());
+        assert_eq!(
+            parse(&args("address ipv4 --in 10.1.0.0/16 -n 3")),
+            Ok(Command::Address {
+                spec: AddressSpec::parse(AddressKind::Ipv4, "10.1.0.0/16").unwrap(),
+                count: 3,
+                duplicates: false,
+                seed: None,
+            })
+        );
+        assert!(parse(&args("address")).is_err());
+        assert!(parse(&args("address ipx")).is_err());
+        assert!(parse(&args("address mac --in 10.0.0.0/8")).is_err());
+        assert!(parse(&args("bytes --size 3TB")).is_err());
+        assert_eq!(
+            parse(&args("santa --exclude Alice,Bob --exclude Carol,Dave -o notes")),
+            Ok(Command::Santa {
+                exclusions: vec!["Alice,Bob".to_string(), "Carol,Dave".to_string()],
+                output: Some("notes".to_string()),
+                seed: None,
+            })
+        );
+        assert!(parse(&args("santa --check crc")).is_err());
+        assert_eq!(parse(&args("bracket --csv")), Ok(Command::Bracket { csv: true, seed: None }));
+        assert_eq!(
+            parse(&args("pair papers.txt --exclude Alice,P1 --csv")),
+            Ok(Command::Pair { items: "papers.txt".to_string(), exclusions: vec!["Alice,P1".to_string()], csv: true, seed: None })
+        );
+        assert!(parse(&args("pair --csv")).is_err());
+        assert_eq!(
+            parse(&args("keno --pool 40 --picks 6 -n 3 --csv")),
+            Ok(Command::Keno { spec: KenoSpec { pool: 40, picks: 6 }, count: 3, csv: true, seed: None })
+        );
+        assert!(parse(&args("keno --pool 5 --picks 6")).is_err());
+        assert_eq!(parse(&args("gacha -n 1000")), Ok(Command::Gacha { pulls: 1000, csv: false, seed: None }));
+        assert_eq!(
+            parse(&args("pool 6d10>=8! -n 2")),
+            Ok(Command::Pool { pool: DicePool { dice: 6, sides: 10, target: 8, explode: Some(10) }, count: 2, csv: false, seed: None })
+        );
+        assert!(parse(&args("pool")).is_err());
+        assert!(parse(&args("pool 6d10")).is_err());
+        assert_eq!(
+            parse(&args("bootstrap heights.txt --size 30 --csv")),
+            Ok(Command::Bootstrap { data: "heights.txt".to_string(), resamples: 1000, size: Some(30), csv: true, seed: None })
+        );
+        assert!(parse(&args("bootstrap -n 500")).is_err());
+        assert_eq!(
+            parse(&args("merge monday.txt tuesday.csv --unique --summary")),
+            Ok(Command::Merge { files: vec!["monday.txt".to_string(), "tuesday.csv".to_string()], unique: true, summary: true })
+        );
+        assert!(parse(&args("merge --unique")).is_err());
+        assert!(parse(&args("range --unique")).is_err());
+        assert_eq!(
+            parse(&args("walk -n 250 --phi 0.8 --step coin --drift 0.5")),
+            Ok(Command::Walk {
+                walk: RandomWalk { length: 250, phi: 0.8, step: StepDistribution::Coin, drift: 0.5, ..Default::default() },
+                seed: None,
+            })
+        );
+        assert!(parse(&args("walk --phi 2")).is_err());
+        assert!(parse(&args("walk --step cauchy")).is_err());
+        assert_eq!(
+            parse(&args("montecarlo -n 5000 --csv")),
+            Ok(Command::MonteCarlo { demo: MonteCarlo { target: MonteCarloTarget::Pi, samples: 5000 }, csv: true, seed: None })
+        );
+        assert_eq!(
+            parse(&args("montecarlo --integral x^2 --to 3")),
+            Ok(Command::MonteCarlo {
+                demo: MonteCarlo {
+                    target: MonteCarloTarget::Integral { formula: "x^2".parse().unwrap(), lower: 0.0, upper: 3.0 },
+                    samples: 1_000_000,
+                },
+                csv: false,
+                seed: None,
+            })
+        );
+        assert!(parse(&args("montecarlo --integral 2x")).is_err());
+        assert!(parse(&args("montecarlo --integral x --from 2 --to 1")).is_err());
+        assert!(parse(&args("montecarlo --to 3")).is_err());
+        assert_eq!(
+            parse(&args("dist normal(100,15) --min 60 --max 140 -n 50")),
+            Ok(Command::Distribution {
+                truncated: TruncatedDistribution { distribution: Distribution::Normal { mean: 100.0, sd: 15.0 }, lower: Some(60.0), upper: Some(140.0) },
+                count: 50,
+                summary: false,
+                seed: None,
+            })
+        );
+        assert!(parse(&args("dist normal(0,1) --min 2 --max 1")).is_err());
+        assert!(parse(&args("dist --min 0")).is_err());
+        assert!(parse(&args("dist lognormal(0,1)")).is_err());
+        assert_eq!(
+            parse(&args("dist gamma(2,3) --max 10")),
+            Ok(Command::Distribution {
+                truncated: TruncatedDistribution { distribution: Distribution::Gamma { shape: 2.0, scale: 3.0 }, lower: None, upper: Some(10.0) },
+                count: 1,
+                summary: false,
+                seed: None,
+            })
+        );
+        assert!(parse(&args("dist beta(0,1)")).is_err());
+        assert_eq!(
+            parse(&args("cdf incomes.txt -n 500 --summary")),
+            Ok(Command::Cdf { path: Some("incomes.txt".to_string()), count: 500, summary: true, seed: None })
+        );
+        assert_eq!(parse(&args("cdf")), Ok(Command::Cdf { path: None, count: 1, summary: false, seed: None }));
+        assert!(parse(&args("cdf a.txt b.txt")).is_err());
+        assert_eq!(
+            parse(&args("weights 4 --alpha 0.5 -n 3 --csv")),
+            Ok(Command::Weights { dirichlet: Dirichlet::symmetric(4, 0.5).unwrap(), count: 3, csv: true, seed: None })
+        );
+        assert_eq!(
+            parse(&args("weights --alpha 1,2,7")),
+            Ok(Command::Weights { dirichlet: Dirichlet::new(vec![1.0, 2.0, 7.0]).unwrap(), count: 1, csv: false, seed: None })
+        );
+        assert!(parse(&args("weights")).is_err());
+        assert!(parse(&args("weights 3 --alpha 1,2")).is_err());
+        assert_eq!(
+            parse(&args("mvnorm --corr 1,0.5;0.5,1 --mean 170,65 --sd 10 -n 5")),
+            Ok(Command::Correlated {
+                normal: MultivariateNormal::new(vec![170.0, 65.0], vec![10.0, 10.0], vec![vec![1.0, 0.5], vec![0.5, 1.0]]).unwrap(),
+                count: 5,
+                summary: false,
+                seed: None,
+            })
+        );
+        assert!(matches!(parse(&args("mvnorm --corr 1,0;0,1 --summary")), Ok(Command::Correlated { count: 1, summary: true, .. })));
+        assert!(parse(&args("mvnorm")).is_err());
+        assert!(parse(&args("mvnorm --corr 1,0.5;0.4,1")).is_err());
+        assert_eq!(
+            parse(&args("graph --model ba --nodes 500 --edges 2 --dot")),
+            Ok(Command::Graph { spec: RandomGraph { model: GraphModel::BarabasiAlbert, nodes: 500, edges: 2 }, dot: true, seed: None })
+        );
+        assert_eq!(parse(&args("graph")), Ok(Command::Graph { spec: RandomGraph::default(), dot: false, seed: None }));
+        assert!(parse(&args("graph --nodes 5 --edges 11")).is_err());
+        assert!(parse(&args("graph --model lattice")).is_err());
+        assert!(parse(&args("graph -n 3")).is_err());
+        assert_eq!(
+            parse(&args("design blocks Placebo,Low,High -n 6 --csv")),
+            Ok(Command::Design {
+                design: ExperimentDesign::parse("Placebo,Low,High").unwrap(),
+                kind: DesignKind::Blocks,
+                blocks: 6,
+                csv: true,
+                seed: None,
+            })
+        );
+        assert_eq!(
+            parse(&args("design williams 4")),
+            Ok(Command::Design { design: ExperimentDesign::of_order(4).unwrap(), kind: DesignKind::Balanced, blocks: 1, csv: false, seed: None })
+        );
+        assert!(parse(&args("design latin")).is_err());
+        assert!(parse(&args("design latin 4 -n 2")).is_err());
+        assert!(parse(&args("design square 4")).is_err());
+        assert!(parse(&args("design latin A,B,a")).is_err());
+        assert!(parse(&args("bootstrap data.txt --size 1MB")).is_err());
+        assert!(parse(&args("range --csv")).is_err());
+        assert_eq!(
+            parse(&args("primes --to 100 -n 5")),
+            Ok(Command::Primes { lower: 0, upper: 100, count: 5, duplicates: false, seed: None })
+        );
+        assert_eq!(
+            parse(&args("primes --bits 64 -d")),
+            Ok(Command::Primes { lower: 1 << 63, upper: u64::MAX as i128, count: 1, duplicates: true, seed: None })
+        );
+        assert!(parse(&args("primes --bits 128")).is_err());
+        assert_eq!(
+            parse(&args("partition 100 -n 7 --to 30")),
+            Ok(Command::Partition { total: 100, parts: 7, lower: 0, upper: Some(30), seed: None })
+        );
+        assert_eq!(
+            parse(&args("partition -20 -n 2 --from -50")),
+            Ok(Command::Partition { total: -20, parts: 2, lower: -50, upper: None, seed: None })
+        );
+        assert!(parse(&args("partition 100")).is_err());
+        assert!(parse(&args("partition -n 7")).is_err());
+        assert!(parse(&args("partition lots -n 7")).is_err());
+        assert_eq!(parse(&args("trials 0.25 -n 20")), Ok(Command::Trials { chance: 0.25, successes: 1, count: 20, seed: None }));
+        assert_eq!(parse(&args("trials --successes 5 0.7")), Ok(Command::Trials { chance: 0.7, successes: 5, count: 1, seed: None }));
+        assert!(parse(&args("trials -n 3")).is_err());
+        assert!(parse(&args("trials 0.5 --successes many")).is_err());
+        assert_eq!(parse(&args("strata -d")), Ok(Command::Strata { duplicates: true, seed: None }));
+        assert!(parse(&args("strata -n 3")).is_err());
+        assert!(parse(&args("primes --bits 8 --from 3")).is_err());
+        assert_eq!(
+            parse(&args("fake --schema user.json -n 3")),
+            Ok(Command::Documents { path: "user.json".to_string(), locale: FakeLocale::English, count: 3, seed: None })
+        );
+        assert!(parse(&args("range --json")).is_err());
+        assert!(parse(&args("shuffle")).is_err());
+    }
+
+    #[test]
+    fn test_pick_distinct_lines() {
+        let items = read_items("alice\n\n  bob \ncarol\n".as_bytes()).unwrap();
+        assert_eq!(items, ["alice", "bob", "carol"]);
+
+        let mut picked = pick(&items, 3, false).unwrap();
+        picked.sort();
+        assert_eq!(picked, items);
+        assert!(pick(&items, 4, false).is_err());
+    }
+}
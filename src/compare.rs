@@ -0,0 +1,288 @@
+//! Panel comparing the current results with another set.
+//!
+//! The other set is an earlier result tab or a file of numbers; the panel
+//! lists the numbers found in both, highlighted, and those found in only
+//! one of them. Handy for checking whether anyone won twice across rounds.
+//! The comparison itself is `Comparison` in the core.
+
+use std::fmt;
+use std::fs;
+
+use iced::widget::{button, column, container, pick_list, row, scrollable, text, text_input, Space};
+use iced::{alignment, Background, Border, Color, Element, Font, Length, Shadow, Theme, Vector};
+use random_generator_core::{Comparison, RandomGenerator};
+
+use crate::{accessibility, get_link_button_style, get_pick_list_style, get_text_input_style, Message, RandomGeneratorApp};
+
+/// What the current results are compared with
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompareTarget {
+    /// The result tab with this number
+    Tab(usize),
+    File,
+}
+
+impl fmt::Display for CompareTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CompareTarget::Tab(number) => write!(f, "#{}", number),
+            CompareTarget::File => write!(f, "File"),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct ComparePanel {
+    target: Option<CompareTarget>,
+    data_path: String,
+    comparison: Option<Comparison<i64>>,
+    /// Names of the two sets in the last comparison
+    names: (String, String),
+    status: String,
+}
+
+impl ComparePanel {
+    pub fn set_target(&mut self, target: CompareTarget) {
+        self.target = Some(target);
+    }
+
+    pub fn set_data_path(&mut self, path: String) {
+        self.data_path = path;
+    }
+}
+
+impl RandomGeneratorApp {
+    /// Other tabs, newest first, then a file
+    fn compare_targets(&self) -> Vec<CompareTarget> {
+        let active = self.tabs.get(self.active_tab).map(|tab| tab.number);
+        self.tabs
+            .iter()
+            .rev()
+            .filter(|tab| Some(tab.number) != active)
+            .map(|tab| CompareTarget::Tab(tab.number))
+            .chain([CompareTarget::File])
+            .collect()
+    }
+
+    /// Open the panel on the tab before the active one, or a file when there's only one tab
+    pub(crate) fn open_compare(&mut self) {
+        self.compare_open = true;
+        let still_there = self.compare.target.is_some_and(|target| self.compare_targets().contains(&target));
+        if !still_there {
+            let previous = self.active_tab.checked_sub(1).and_then(|index| self.tabs.get(index));
+            self.compare.target = Some(previous.map_or(CompareTarget::File, |tab| CompareTarget::Tab(tab.number)));
+        }
+        self.run_compare();
+    }
+
+    /// Reads the file again on every run, so edits to it show up
+    pub(crate) fn run_compare(&mut self) {
+        let panel = &mut self.compare;
+        panel.comparison = None;
+        let Some(current) = self.tabs.get(self.active_tab) else {
+            panel.status = "Generate some results to compare first".to_string();
+            return;
+        };
+        let other = match panel.target {
+            Some(CompareTarget::Tab(number)) => match self.tabs.iter().find(|tab| tab.number == number) {
+                Some(tab) => tab.numbers.clone(),
+                None => {
+                    panel.status = format!("Tab #{} was closed", number);
+                    return;
+                }
+            },
+            Some(CompareTarget::File) | None => {
+                let path = panel.data_path.trim();
+                if path.is_empty() {
+                    panel.status = "Enter the path of a file of numbers".to_string();
+                    return;
+                }
+                let numbers = fs::read_to_string(path)
+                    .map_err(|e| format!("Could not read {}: {}", path, e))
+                    .and_then(|data| RandomGenerator::parse_custom_list_input(&data).map_err(|e| format!("{}: {}", path, e)));
+                match numbers {
+                    Ok(numbers) => numbers,
+                    Err(e) => {
+                        panel.status = e;
+                        return;
+                    }
+                }
+            }
+        };
+        let other_name = match panel.target {
+            Some(CompareTarget::Tab(number)) => format!("#{}", number),
+            _ => panel.data_path.trim().to_string(),
+        };
+        let comparison = Comparison::new(&current.numbers, &other);
+        panel.status = match (comparison.both.len(), comparison.is_same()) {
+            (_, true) => "Both sets hold the same numbers".to_string(),
+            (0, _) => "No number appears in both".to_string(),
+            (1, _) => "1 number appears in both".to_string(),
+            (both, _) => format!("{} numbers appear in both", both),
+        };
+        panel.names = (format!("#{}", current.number), other_name);
+        panel.comparison = Some(comparison);
+    }
+
+    pub(crate) fn compare_view(&self) -> Element<'_, Message> {
+        let panel = &self.compare;
+        let dark_mode = self.settings.dark_mode;
+        let high_contrast = self.settings.high_contrast;
+        let label = |content: &'static str| text(self.tr(content)).size(14).width(Length::Fixed(80.0));
+        let action = |content: &'static str, message: Message| {
+            button(text(self.tr(content)).size(14))
+                .on_press(message)
+                .padding(self.button_padding())
+                .style(move |_theme: &Theme, status| get_link_button_style(dark_mode, high_contrast, status))
+        };
+        // A titled block of numbers; the shared ones are highlighted like filter matches
+        let section = |title: String, values: &[i64], highlighted: bool| {
+            let rows = values.chunks(8).map(|chunk| {
+                row(chunk.iter().map(|&value| {
+                    container(text(self.number_format.format(value)).size(13).font(Font::MONOSPACE))
+                        .padding(3)
+                        .style(move |_theme: &Theme| iced::widget::container::Style {
+                            background: Some(Background::Color(match (highlighted, dark_mode) {
+                                (true, true) => Color::from_rgb(0.6, 0.5, 0.15),
+                                (true, false) => Color::from_rgb(1.0, 0.85, 0.4),
+                                (false, true) => Color::from_rgb(0.25, 0.25, 0.3),
+                                (false, false) => Color::from_rgb(0.92, 0.92, 0.92),
+                            })),
+                            border: Border {
+                                color: Color::TRANSPARENT,
+                                width: 0.0,
+                                radius: 4.0.into(),
+                            },
+                            ..Default::default()
+                        })
+                        .into()
+                }))
+                    .spacing(3)
+                    .into()
+            });
+            column![text(format!("{} ({})", title, values.len())).size(13)]
+                .extend(rows)
+                .spacing(3)
+        };
+
+        let results = panel.comparison.as_ref().map(|comparison| {
+            let (first, second) = &panel.names;
+            container(
+                scrollable(
+                    column![
+                        section(self.tr("In both").to_string(), &comparison.both, true),
+                        section(format!("{} {}", self.tr("Only in"), first), &comparison.only_first, false),
+                        section(format!("{} {}", self.tr("Only in"), second), &comparison.only_second, false),
+                    ]
+                        .spacing(10),
+                )
+                    .height(Length::Fixed(240.0)),
+            )
+                .padding(6)
+                .width(Length::Fill)
+        });
+
+        let targets = self.compare_targets();
+        let content = column![
+            text(self.tr("Compare results"))
+                .size(20)
+                .color(if dark_mode { Color::from_rgb(0.9, 0.9, 0.9) } else { Color::BLACK }),
+            Space::with_height(Length::Fixed(10.0)),
+            row![
+                label("Compare with"),
+                pick_list(targets, panel.target, Message::CompareTargetChanged)
+                    .text_size(14)
+                    .style(move |_theme: &Theme, _status| get_pick_list_style(dark_mode, high_contrast)),
+                Space::with_width(Length::Fill),
+                action("Compare", Message::RunCompare),
+            ]
+                .spacing(6)
+                .align_y(alignment::Vertical::Center),
+        ]
+            .push_maybe((panel.target == Some(CompareTarget::File)).then(|| {
+                column![
+                    row![
+                        label("Data file"),
+                        text_input("", &panel.data_path)
+                            .on_input(Message::CompareFileChanged)
+                            .on_submit(Message::RunCompare)
+                            .width(Length::Fill)
+                            .size(14)
+                            .style(move |_theme: &Theme, status| get_text_input_style(dark_mode, high_contrast, status)),
+                    ]
+                        .spacing(6)
+                        .align_y(alignment::Vertical::Center),
+                    text(self.tr("Numbers separated by commas, spaces or new lines"))
+                        .size(11)
+                        .color(accessibility::dim_text_color(dark_mode, high_contrast)),
+                ]
+                    .spacing(6)
+            }))
+            .push_maybe(results)
+            .push(
+                row![
+                    text(&panel.status).size(13).color(accessibility::dim_text_color(dark_mode, high_contrast)),
+                    Space::with_width(Length::Fill),
+                    action("Close", Message::CloseCompare),
+                ]
+                    .align_y(alignment::Vertical::Center),
+            )
+            .spacing(6)
+            .padding(24);
+
+        container(content)
+            .width(Length::Fixed(460.0))
+            .style(move |_theme: &Theme| {
+                iced::widget::container::Style {
+                    background: Some(Background::Color(if dark_mode { Color::from_rgb(0.2, 0.2, 0.25) } else { Color::WHITE })),
+                    border: Border {
+                        color: if dark_mode { Color::from_rgb(0.4, 0.4, 0.4) } else { Color::from_rgb(0.8, 0.8, 0.8) },
+                        width: 1.0,
+                        radius: 16.0.into(),
+                    },
+                    shadow: Shadow {
+                        color: Color::from_rgba(0.0, 0.0, 0.0, 0.3),
+                        offset: Vector::new(0.0, 4.0),
+                        blur_radius: 20.0,
+                    },
+                    ..Default::default()
+                }
+            })
+            .into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compare_with_previous_tab_and_file() {
+        let mut app = RandomGeneratorApp::default();
+        app.open_tab(vec![7, 2, 9], None);
+        app.open_tab(vec![9, 4, 7, 1], None);
+        let _ = app.update(Message::ShowCompare);
+        assert_eq!(app.compare.target, Some(CompareTarget::Tab(1)));
+        let comparison = app.compare.comparison.clone().unwrap();
+        assert_eq!(comparison.both, [9, 7]);
+        assert_eq!(comparison.only_first, [4, 1]);
+        assert_eq!(comparison.only_second, [2]);
+        assert_eq!(app.compare.status, "2 numbers appear in both");
+
+        let path = std::env::temp_dir().join(format!("random-tool-compare-{}.txt", std::process::id()));
+        std::fs::write(&path, "1, 4\n7 9").unwrap();
+        let _ = app.update(Message::CompareTargetChanged(CompareTarget::File));
+        let _ = app.update(Message::CompareFileChanged(path.to_string_lossy().into_owned()));
+        let _ = app.update(Message::RunCompare);
+        assert_eq!(app.compare.status, "Both sets hold the same numbers");
+
+        std::fs::write(&path, "1, four").unwrap();
+        let _ = app.update(Message::RunCompare);
+        assert!(app.compare.comparison.is_none());
+        std::fs::remove_file(&path).unwrap();
+
+        app.close_tab(0);
+        let _ = app.update(Message::ShowCompare);
+        assert_eq!(app.compare.target, Some(CompareTarget::File));
+    }
+}
@@ -0,0 +1,107 @@
+use iced::mouse;
+use iced::widget::canvas::{self, Frame, Geometry, Path};
+use iced::{Color, Point, Rectangle, Renderer, Size, Theme, Vector};
+use rand::Rng;
+use std::time::{Duration, Instant};
+
+/// Confetti burst drawn on top of the presentation view when a draw completes
+#[derive(Debug, Clone)]
+pub struct Confetti {
+    particles: Vec<Particle>,
+    started: Instant,
+    now: Instant,
+}
+
+#[derive(Debug, Clone)]
+struct Particle {
+    /// Start position, relative to the canvas size (0.0 - 1.0)
+    origin: Point,
+    /// Velocity in canvas sizes per second
+    velocity: Vector,
+    spin: f32,
+    size: f32,
+    color: Color,
+}
+
+impl Confetti {
+    const DURATION: Duration = Duration::from_millis(3500);
+    const PARTICLES: usize = 160;
+    const GRAVITY: f32 = 0.55;
+
+    const COLORS: [Color; 6] = [
+        Color::from_rgb(0.95, 0.3, 0.3),
+        Color::from_rgb(0.2, 0.6, 0.9),
+        Color::from_rgb(0.4, 0.8, 0.4),
+        Color::from_rgb(0.98, 0.8, 0.2),
+        Color::from_rgb(0.7, 0.4, 0.9),
+        Color::from_rgb(1.0, 0.55, 0.2),
+    ];
+
+    pub fn new(now: Instant) -> Self {
+        let mut rng = rand::thread_rng();
+        let particles = (0..Self::PARTICLES)
+            .map(|_| Particle {
+                origin: Point::new(rng.gen_range(0.0..1.0), rng.gen_range(-0.25..0.0)),
+                velocity: Vector::new(rng.gen_range(-0.15..0.15), rng.gen_range(0.0..0.25)),
+                spin: rng.gen_range(-8.0..8.0),
+                size: rng.gen_range(6.0..12.0),
+                color: Self::COLORS[rng.gen_range(0..Self::COLORS.len())],
+            })
+            .collect();
+
+        Self { particles, started: now, now }
+    }
+
+    pub fn tick(&mut self, now: Instant) {
+        self.now = now;
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.elapsed() >= Self::DURATION
+    }
+
+    fn elapsed(&self) -> Duration {
+        self.now.saturating_duration_since(self.started)
+    }
+}
+
+impl<Message> canvas::Program<Message> for Confetti {
+    type State = ();
+
+    fn draw(
+        &self,
+        _state: &Self::State,
+        renderer: &Renderer,
+        _theme: &Theme,
+        bounds: Rectangle,
+        _cursor: mouse::Cursor,
+    ) -> Vec<Geometry> {
+        let mut frame = Frame::new(renderer, bounds.size());
+        let t = self.elapsed().as_secs_f32();
+        // Fade out over the last second
+        let alpha = (Self::DURATION.as_secs_f32() - t).clamp(0.0, 1.0);
+
+        for particle in &self.particles {
+            let x = (particle.origin.x + particle.velocity.x * t) * bounds.width;
+            let y = (particle.origin.y + particle.velocity.y * t + 0.5 * Self::GRAVITY * t * t)
+                * bounds.height;
+            if y > bounds.height + particle.size {
+                continue;
+            }
+
+            frame.with_save(|frame| {
+                frame.translate(Vector::new(x, y));
+                frame.rotate(particle.spin * t);
+                frame.fill(
+                    &Path::rectangle(
+                        Point::new(-particle.size / 2.0, -particle.size / 4.0),
+                        Size::new(particle.size, particle.size / 2.0),
+                    ),
+                    Color { a: alpha, ..particle.color },
+                );
+            });
+        }
+
+        vec![frame.into_geometry()]
+    }
+}
@@ -0,0 +1,249 @@
+//! Panel for correlated normal values.
+//!
+//! Enter a correlation matrix, one row per line or separated by semicolons,
+//! with the means and standard deviations of the variables, and draw rows of
+//! values that are each normal and correlated as asked. The summary compares
+//! the sample's correlations with the requested ones; the rows are saved as
+//! multi-column CSV. The sampling is `MultivariateNormal` in the core and
+//! runs on a worker thread with a progress bar.
+
+use iced::widget::{button, column, container, row, scrollable, text, text_input, Space};
+use iced::{alignment, Background, Border, Color, Element, Font, Length, Shadow, Task, Theme, Vector};
+use random_generator_core::{ExportFormat, MultivariateNormal, MultivariateSample, Seed};
+
+use crate::export_panel::{settings_line, ExportFile, ExportPanel, PanelSeed};
+use crate::worker::{PanelEvent, PanelRun};
+use crate::{accessibility, get_link_button_style, get_text_input_style, Message, RandomGeneratorApp};
+
+/// Formats the values can be saved in: the summary as text, the rows as CSV
+const FILE_FORMATS: [ExportFormat; 2] = [ExportFormat::Csv, ExportFormat::Text];
+
+#[derive(Debug)]
+pub struct CorrelatedPanel {
+    /// One per variable, or one for all; empty for 0
+    means: String,
+    /// One per variable, or one for all; empty for 1
+    sds: String,
+    correlation: String,
+    count: String,
+    seed: PanelSeed,
+    file: ExportFile,
+    running: PanelRun,
+    sample: Option<MultivariateSample>,
+    status: String,
+}
+
+impl Default for CorrelatedPanel {
+    fn default() -> Self {
+        Self {
+            means: "0, 0".to_string(),
+            sds: "1, 1".to_string(),
+            correlation: "1, 0.5; 0.5, 1".to_string(),
+            count: "1000".to_string(),
+            seed: PanelSeed::default(),
+            file: ExportFile::new("correlated.csv", ExportFormat::Csv),
+            running: PanelRun::default(),
+            sample: None,
+            status: String::new(),
+        }
+    }
+}
+
+impl CorrelatedPanel {
+    pub fn set_means(&mut self, means: String) {
+        self.means = means;
+    }
+
+    pub fn set_sds(&mut self, sds: String) {
+        self.sds = sds;
+    }
+
+    pub fn set_correlation(&mut self, correlation: String) {
+        self.correlation = correlation;
+    }
+
+    pub fn set_count(&mut self, count: String) {
+        self.count = count;
+    }
+
+    pub fn file_mut(&mut self) -> &mut ExportFile {
+        &mut self.file
+    }
+
+    pub fn seed_mut(&mut self) -> &mut PanelSeed {
+        &mut self.seed
+    }
+
+    /// How the last draw or export went
+    pub fn status(&self) -> &str {
+        &self.status
+    }
+
+    /// Settings of the draw, for the history
+    pub fn settings(&self) -> String {
+        settings_line(&[
+            ("means", self.means.clone()),
+            ("sds", self.sds.clone()),
+            ("correlation", self.correlation.clone()),
+            ("count", self.count.clone()),
+        ])
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.running.is_running()
+    }
+
+    /// Draw the rows on a worker thread
+    pub fn run(&mut self) -> Task<Message> {
+        if self.is_running() {
+            return Task::none();
+        }
+        self.sample = None;
+        let seed = match self.seed.next() {
+            Ok(seed) => seed,
+            Err(message) => {
+                self.status = message;
+                return Task::none();
+            }
+        };
+        let Ok(count) = self.count.trim().parse() else {
+            self.status = "Enter a whole number of rows".to_string();
+            return Task::none();
+        };
+        let normal = match MultivariateNormal::parse(&self.means, &self.sds, &self.correlation) {
+            Ok(normal) => normal,
+            Err(e) => {
+                self.status = e.to_string();
+                return Task::none();
+            }
+        };
+        self.status.clear();
+        let draw = self.running.start(move |cancel, on_progress| {
+            normal.sample_with_progress(count, seed, cancel, on_progress).map(|sample| (seed, sample)).map_err(|e| e.to_string())
+        });
+        Task::run(draw, Message::Correlated)
+    }
+
+    pub fn cancel(&self) {
+        self.running.cancel();
+    }
+
+    /// Follow the running draw; the summary shows once it has finished
+    pub fn handle(&mut self, event: PanelEvent<(Seed, MultivariateSample)>) {
+        let Some(result) = self.running.update(event) else {
+            return;
+        };
+        self.status = match result {
+            Ok((seed, sample)) => {
+                self.seed.keep(seed);
+                let status = format!("{} rows of {} variables", sample.rows.len(), sample.normal.dimensions());
+                self.sample = Some(sample);
+                status
+            }
+            Err(e) => e,
+        };
+    }
+
+    pub fn export(&mut self) {
+        let csv = self.file.format() == ExportFormat::Csv;
+        self.status = self.file.save(self.sample.as_ref().map(|sample| if csv { sample.to_csv() } else { sample.to_text() }));
+    }
+}
+
+impl RandomGeneratorApp {
+    pub(crate) fn correlated_view(&self) -> Element<'_, Message> {
+        let panel = &self.correlated;
+        let dark_mode = self.settings.dark_mode;
+        let high_contrast = self.settings.high_contrast;
+        let label = |content: &'static str| text(self.tr(content)).size(14).width(Length::Fixed(100.0));
+        let action = |content: &'static str, message: Option<Message>| {
+            button(text(self.tr(content)).size(14))
+                .on_press_maybe(message)
+                .padding(self.button_padding())
+                .style(move |_theme: &Theme, status| get_link_button_style(dark_mode, high_contrast, status))
+        };
+        let input = |value: &str, on_input: fn(String) -> Message, width: Length| {
+            text_input("", value)
+                .on_input(on_input)
+                .on_submit(Message::RunCorrelated)
+                .width(width)
+                .size(14)
+                .style(move |_theme: &Theme, status| get_text_input_style(dark_mode, high_contrast, status))
+        };
+
+        let preview = panel.sample.as_ref().map(|sample| {
+            let lines = sample.to_text().lines().map(|line| text(line.to_string()).size(12).font(Font::MONOSPACE).into()).collect::<Vec<_>>();
+            container(
+                scrollable(column(lines))
+                    .direction(scrollable::Direction::Both {
+                        vertical: scrollable::Scrollbar::default(),
+                        horizontal: scrollable::Scrollbar::default(),
+                    })
+                    .height(Length::Fixed(200.0)),
+            )
+                .padding(6)
+                .width(Length::Fill)
+        });
+
+        let content = column![
+            text(self.tr("Correlated normal"))
+                .size(20)
+                .color(if dark_mode { Color::from_rgb(0.9, 0.9, 0.9) } else { Color::BLACK }),
+            Space::with_height(Length::Fixed(10.0)),
+            row![label("Correlation"), input(&panel.correlation, Message::CorrelatedMatrixChanged, Length::Fill)]
+                .spacing(6)
+                .align_y(alignment::Vertical::Center),
+            text(self.tr("One row of the matrix per semicolon, e.g. 1, 0.5; 0.5, 1. The diagonal is 1 and the matrix must be symmetric"))
+                .size(11)
+                .color(accessibility::dim_text_color(dark_mode, high_contrast)),
+            row![label("Means"), input(&panel.means, Message::CorrelatedMeansChanged, Length::Fill)]
+                .spacing(6)
+                .align_y(alignment::Vertical::Center),
+            row![label("SDs"), input(&panel.sds, Message::CorrelatedSdsChanged, Length::Fill)]
+                .spacing(6)
+                .align_y(alignment::Vertical::Center),
+            row![
+                label("Rows"),
+                input(&panel.count, Message::CorrelatedCountChanged, Length::Fixed(100.0)),
+                Space::with_width(Length::Fill),
+                action("Generate", (!panel.is_running()).then_some(Message::RunCorrelated)),
+            ]
+                .spacing(6)
+                .align_y(alignment::Vertical::Center),
+        ]
+            .push_maybe(self.progress_row(&panel.running, Message::CancelCorrelated))
+            .push_maybe(preview)
+            .push(self.seed_row(ExportPanel::Correlated, &panel.seed))
+            .push(self.export_row(ExportPanel::Correlated, &panel.file, &FILE_FORMATS, "Export"))
+            .push(
+                row![
+                    text(&panel.status).size(13).color(accessibility::dim_text_color(dark_mode, high_contrast)),
+                    Space::with_width(Length::Fill),
+                    action("Close", Some(Message::CloseCorrelated)),
+                ]
+                    .align_y(alignment::Vertical::Center),
+            )
+            .spacing(6)
+            .padding(24);
+
+        container(content)
+            .width(Length::Fixed(480.0))
+            .style(move |_theme: &Theme| {
+                iced::widget::container::Style {
+                    background: Some(Background::Color(if dark_mode { Color::from_rgb(0.2, 0.2, 0.25) } else { Color::WHITE })),
+                    border: Border {
+                        color: if dark_mode { Color::from_rgb(0.4, 0.4, 0.4) } else { Color::from_rgb(0.8, 0.8, 0.8) },
+                        width: 1.0,
+                        radius: 16.0.into(),
+                    },
+                    shadow: Shadow {
+                        color: Color::from_rgba(0.0, 0.0, 0.0, 0.3),
+                        offset: Vector::new(0.0, 4.0),
+                        blur_radius: 20.0,
+                    },
+                    ..Default::default()
+                }
+            })
+            .into()
+    }
+}
@@ -0,0 +1,521 @@
+//! Daemon mode: `random-tool daemon` answers draw requests from local programs.
+//!
+//! Clients connect to a Unix socket (a named pipe on Windows) and send one JSON
+//! request per line, e.g. `{"id": 1, "method": "generate", "params": {"from": 1, "to": 6}}`.
+//! Each gets one line back: `{"id": 1, "result": {"numbers": [...], "seed": "...", "stats": {...}}}`
+//! or `{"id": 1, "error": "..."}`. The params are the HTTP server's `/generate` body
+//! and `{"method": "health"}` checks that the daemon is up; `metrics` answers
+//! with the server's Prometheus metrics as a string. Draws are logged like
+//! the server's, and the window can send its draws here instead of drawing itself.
+
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::thread;
+
+use random_generator_core::{GeneratorConfig, GeneratorMode};
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::server::{GenerateRequest, Server};
+
+/// Longest request line accepted
+const MAX_LINE: usize = 1 << 20;
+
+/// One request line
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Call {
+    /// Echoed back so clients can match answers to requests
+    #[serde(default)]
+    id: Value,
+    method: String,
+    #[serde(default)]
+    params: Option<Value>,
+}
+
+/// Where the daemon listens unless `--socket` says otherwise
+///
+/// `$XDG_RUNTIME_DIR` is private to the user; without one the socket goes in the
+/// user's data directory, never in the shared temp directory.
+#[cfg(unix)]
+pub fn default_endpoint() -> String {
+    let path = match dirs::runtime_dir() {
+        Some(dir) => dir.join("random-tool.sock"),
+        None => dirs::data_dir().unwrap_or_default().join("random-tool").join("daemon.sock"),
+    };
+    path.to_string_lossy().into_owned()
+}
+
+/// Where the daemon listens unless `--socket` says otherwise
+#[cfg(windows)]
+pub fn default_endpoint() -> String {
+    r"\\.\pipe\random-tool".to_string()
+}
+
+/// Answer requests until the process is stopped
+#[cfg(unix)]
+pub fn run(endpoint: &str) -> io::Result<()> {
+    use std::os::unix::net::UnixStream;
+
+    if std::path::Path::new(endpoint).exists() {
+        if UnixStream::connect(endpoint).is_ok() {
+            return Err(io::Error::new(io::ErrorKind::AddrInUse, "a daemon is already running there"));
+        }
+        // Left behind by a daemon that didn't shut down cleanly
+        std::fs::remove_file(endpoint)?;
+    }
+    let listener = bind_private(std::path::Path::new(endpoint))?;
+    eprintln!("listening on {}", endpoint);
+
+    let server = Server::new(true);
+    for stream in listener.incoming() {
+        let Ok(stream) = stream else {
+            continue;
+        };
+        let server = server.clone();
+        thread::spawn(move || {
+            if let Err(e) = stream.try_clone().and_then(|reader| handle(reader, stream, &server)) {
+                eprintln!("connection error: {}", e);
+            }
+        });
+    }
+    Ok(())
+}
+
+/// Listen at `path` so that only this user may ask for draws
+///
+/// The socket is bound in a new directory only this user can enter, made private
+/// there and then moved to `path`, so nobody can connect while it still has the
+/// umask's permissions. Missing parent directories are created private too.
+#[cfg(unix)]
+fn bind_private(path: &std::path::Path) -> io::Result<std::os::unix::net::UnixListener> {
+    use std::fs::{self, DirBuilder, Permissions};
+    use std::os::unix::fs::{DirBuilderExt, PermissionsExt};
+
+    let dir = path.parent().filter(|dir| !dir.as_os_str().is_empty()).unwrap_or(std::path::Path::new("."));
+    DirBuilder::new().recursive(true).mode(0o700).create(dir)?;
+    let staging = dir.join(format!(".random-tool-{}", std::process::id()));
+    // Left behind by an earlier daemon with the same process id
+    let _ = fs::remove_dir_all(&staging);
+    DirBuilder::new().mode(0o700).create(&staging)?;
+
+    let bound = staging.join("daemon.sock");
+    let listener = std::os::unix::net::UnixListener::bind(&bound).and_then(|listener| {
+        fs::set_permissions(&bound, Permissions::from_mode(0o600))?;
+        fs::rename(&bound, path)?;
+        Ok(listener)
+    });
+    let _ = fs::remove_dir_all(&staging);
+    listener
+}
+
+/// Answer requests until the process is stopped
+#[cfg(windows)]
+pub fn run(endpoint: &str) -> io::Result<()> {
+    eprintln!("listening on {}", endpoint);
+    let server = Server::new(true);
+    loop {
+        let pipe = pipe::accept(endpoint)?;
+        let server = server.clone();
+        thread::spawn(move || {
+            if let Err(e) = pipe.try_clone().and_then(|reader| handle(reader, pipe, &server)) {
+                eprintln!("connection error: {}", e);
+            }
+        });
+    }
+}
+
+/// Answer each line of one connection until the client hangs up
+fn handle(reader: impl Read, mut writer: impl Write, server: &Server) -> io::Result<()> {
+    let mut reader = BufReader::new(reader);
+    let mut line = String::new();
+    loop {
+        line.clear();
+        if (&mut reader).take(MAX_LINE as u64 + 1).read_line(&mut line)? == 0 {
+            return Ok(());
+        }
+        if line.len() > MAX_LINE {
+            writeln!(writer, "{}", json!({ "id": null, "error": "request too long" }))?;
+            return writer.flush();
+        }
+        if line.trim().is_empty() {
+            continue;
+        }
+        writeln!(writer, "{}", answer(server, &line))?;
+        writer.flush()?;
+    }
+}
+
+/// Answer line for one request line
+fn answer(server: &Server, line: &str) -> Value {
+    let call: Call = match serde_json::from_str(line) {
+        Ok(call) => call,
+        Err(e) => {
+            server.metrics().record_error();
+            return json!({ "id": null, "error": format!("invalid request: {}", e) });
+        }
+    };
+    let result = match call.method.as_str() {
+        "health" => Ok(json!({ "status": "ok", "version": env!("CARGO_PKG_VERSION") })),
+        "metrics" => Ok(json!(server.metrics().render())),
+        "generate" => match serde_json::from_value::<GenerateRequest>(call.params.unwrap_or_else(|| json!({}))) {
+            Ok(request) => server.generate(request),
+            Err(e) => {
+                server.metrics().record_error();
+                Err(format!("invalid params: {}", e))
+            }
+        },
+        other => {
+            server.metrics().record_error();
+            Err(format!("unknown method '{}'", other))
+        }
+    };
+    match result {
+        Ok(result) => json!({ "id": call.id, "result": result }),
+        Err(e) => json!({ "id": call.id, "error": e }),
+    }
+}
+
+/// Send one request to a running daemon and wait for the result
+pub fn call(endpoint: &str, method: &str, params: Value) -> Result<Value, String> {
+    let unreachable = |e: io::Error| format!("daemon not reachable at {}: {}", endpoint, e);
+    let (reader, mut writer) = connect(endpoint).map_err(unreachable)?;
+    writeln!(writer, "{}", json!({ "id": 1, "method": method, "params": params })).map_err(unreachable)?;
+    writer.flush().map_err(unreachable)?;
+
+    let mut line = String::new();
+    BufReader::new(reader).read_line(&mut line).map_err(unreachable)?;
+    let mut answer: Value = serde_json::from_str(&line).map_err(|e| format!("unexpected answer from the daemon: {}", e))?;
+    match answer.get("error").and_then(Value::as_str) {
+        Some(e) => Err(e.to_string()),
+        None => Ok(answer["result"].take()),
+    }
+}
+
+/// Draw `config` on the daemon; returns the numbers and the config as the daemon left it,
+/// with the seed that reproduces them and the shuffle bag, cooldown and exclusions after the draw
+pub fn generate(endpoint: &str, config: &GeneratorConfig) -> Result<(Vec<i64>, GeneratorConfig), String> {
+    let result = call(endpoint, "generate", params(config)?)?;
+    after(config, result).map_err(|e| format!("unexpected answer from the daemon: {}", e))
+}
+
+/// The `generate` params for `config`
+fn params(config: &GeneratorConfig) -> Result<Value, String> {
+    let mut params = json!({
+        "from": config.lower_bound,
+        "to": config.upper_bound,
+        "count": config.num_to_generate,
+        "duplicates": config.allow_duplicates,
+        "max_repeats": config.max_repeats,
+        "max_streak": config.max_streak,
+        "repeats": config.list_duplicates,
+        "order": config.order,
+        "seed": config.seed,
+    });
+    if !config.transforms.is_empty() {
+        let steps: Vec<String> = config.transforms.iter().map(ToString::to_string).collect();
+        params["transforms"] = json!(steps.join(", "));
+    }
+    match config.mode {
+        GeneratorMode::RANGE => {}
+        // Already parsed here, so items a lenient list skips never reach the daemon
+        GeneratorMode::CUSTOM_LIST => params["list"] = json!(config.custom_list),
+        mode => return Err(format!("the daemon can't draw in {} mode", mode)),
+    }
+    if let Some(bag) = &config.shuffle_bag {
+        params["bag"] = json!(bag);
+    }
+    if let Some(cooldown) = &config.cooldown {
+        params["cooldown"] = json!(cooldown);
+    }
+    if let Some(excluded) = &config.exclude_drawn {
+        params["excluded"] = json!(excluded);
+    }
+    Ok(params)
+}
+
+/// The numbers in a `generate` result and `config` updated by it
+fn after(config: &GeneratorConfig, mut result: Value) -> serde_json::Result<(Vec<i64>, GeneratorConfig)> {
+    let numbers = serde_json::from_value(result["numbers"].take())?;
+    let mut config = GeneratorConfig { seed: result["seed"].as_str().and_then(|seed| seed.parse().ok()), ..config.clone() };
+    if config.shuffle_bag.is_some() {
+        config.shuffle_bag = Some(serde_json::from_value(result["bag"].take())?);
+    }
+    if config.cooldown.is_some() {
+        config.cooldown = Some(serde_json::from_value(result["cooldown"].take())?);
+    }
+    if config.exclude_drawn.is_some() {
+        config.exclude_drawn = Some(serde_json::from_value(result["excluded"].take())?);
+    }
+    Ok((numbers, config))
+}
+
+#[cfg(unix)]
+fn connect(endpoint: &str) -> io::Result<(impl Read, impl Write)> {
+    let stream = std::os::unix::net::UnixStream::connect(endpoint)?;
+    stream.set_read_timeout(Some(std::time::Duration::from_secs(30)))?;
+    Ok((stream.try_clone()?, stream))
+}
+
+#[cfg(windows)]
+fn connect(endpoint: &str) -> io::Result<(impl Read, impl Write)> {
+    let pipe = std::fs::OpenOptions::new().read(true).write(true).open(endpoint)?;
+    Ok((pipe.try_clone()?, pipe))
+}
+
+/// The server side of named pipes, which std doesn't cover
+#[cfg(windows)]
+mod pipe {
+    use std::ffi::{c_void, OsStr};
+    use std::fs::File;
+    use std::io;
+    use std::iter;
+    use std::os::windows::ffi::OsStrExt;
+    use std::os::windows::io::FromRawHandle;
+    use std::ptr;
+
+    type Handle = *mut c_void;
+
+    const PIPE_ACCESS_DUPLEX: u32 = 0x0000_0003;
+    const PIPE_TYPE_BYTE: u32 = 0x0000_0000;
+    const PIPE_REJECT_REMOTE_CLIENTS: u32 = 0x0000_0008;
+    const PIPE_UNLIMITED_INSTANCES: u32 = 255;
+    const BUFFER_SIZE: u32 = 4096;
+    const ERROR_PIPE_CONNECTED: i32 = 535;
+
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn CreateNamedPipeW(
+            name: *const u16,
+            open_mode: u32,
+            pipe_mode: u32,
+            max_instances: u32,
+            out_buffer_size: u32,
+            in_buffer_size: u32,
+            default_timeout: u32,
+            security_attributes: *mut c_void,
+        ) -> Handle;
+        fn ConnectNamedPipe(pipe: Handle, overlapped: *mut c_void) -> i32;
+    }
+
+    /// Wait for the next client on a new instance of the pipe
+    pub fn accept(name: &str) -> io::Result<File> {
+        let wide: Vec<u16> = OsStr::new(name).encode_wide().chain(iter::once(0)).collect();
+        // SAFETY: the name is NUL-terminated and outlives the call; no security attributes
+        let handle = unsafe {
+            CreateNamedPipeW(
+                wide.as_ptr(),
+                PIPE_ACCESS_DUPLEX,
+                PIPE_TYPE_BYTE | PIPE_REJECT_REMOTE_CLIENTS,
+                PIPE_UNLIMITED_INSTANCES,
+                BUFFER_SIZE,
+                BUFFER_SIZE,
+                0,
+                ptr::null_mut(),
+            )
+        };
+        // INVALID_HANDLE_VALUE
+        if handle as isize == -1 {
+            return Err(io::Error::last_os_error());
+        }
+        // SAFETY: the handle was just created and is owned by the file from here on
+        let pipe = unsafe { File::from_raw_handle(handle) };
+        // SAFETY: a valid pipe handle, used synchronously
+        if unsafe { ConnectNamedPipe(handle, ptr::null_mut()) } == 0 {
+            let e = io::Error::last_os_error();
+            // The client connected between creating and waiting, which is fine
+            if e.raw_os_error() != Some(ERROR_PIPE_CONNECTED) {
+                return Err(e);
+            }
+        }
+        Ok(pipe)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use random_generator_core::{Cooldown, ExclusionSet, RandomGenerator, ShuffleBag};
+
+    /// Draw `config` on `server` as the window does, without a socket in between
+    fn draw_on(server: &Server, config: &GeneratorConfig) -> (Vec<i64>, GeneratorConfig) {
+        let line = json!({ "id": 1, "method": "generate", "params": params(config).unwrap() }).to_string();
+        let mut answer = answer(server, &line);
+        assert!(answer["error"].is_null(), "{}", answer["error"]);
+        after(config, answer["result"].take()).unwrap()
+    }
+
+    #[test]
+    fn test_answers_each_line() {
+        let requests = concat!(
+            r#"{"id": 7, "method": "health"}"#,
+            "\n\n",
+            r#"{"id": "a", "method": "generate", "params": {"from": 1, "to": 3, "count": 3, "order": "Sorted"}}"#,
+            "\n",
+            r#"{"id": 8, "method": "generate", "params": {"from": 3, "to": 1}}"#,
+            "\n",
+            r#"{"method": "shuffle"}"#,
+            "\nnot json\n",
+        );
+        let mut output = Vec::new();
+        handle(requests.as_bytes(), &mut output, &Server::new(false)).unwrap();
+        let answers: Vec<Value> = output.split(|&byte| byte == b'\n')
+            .filter(|line| !line.is_empty())
+            .map(|line| serde_json::from_slice(line).unwrap())
+            .collect();
+
+        assert_eq!(answers.len(), 5);
+        assert_eq!(answers[0], json!({ "id": 7, "result": { "status": "ok", "version": env!("CARGO_PKG_VERSION") } }));
+        assert_eq!(answers[1]["id"], "a");
+        assert_eq!(answers[1]["result"]["numbers"], json!([1, 2, 3]));
+        assert_eq!(answers[2]["id"], 8);
+        assert!(answers[2]["error"].is_string());
+        assert_eq!(answers[3]["error"], "unknown method 'shuffle'");
+        assert!(answers[4]["id"].is_null() && answers[4]["error"].is_string());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_window_draws_through_socket() {
+        use std::os::unix::net::UnixListener;
+
+        let path = std::env::temp_dir().join(format!("random-tool-test-{}.sock", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let listener = UnixListener::bind(&path).unwrap();
+        let daemon = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            handle(stream.try_clone().unwrap(), stream, &Server::new(false)).unwrap();
+        });
+
+        let config = GeneratorConfig {
+            mode: GeneratorMode::CUSTOM_LIST,
+            custom_list: vec![4, 8, 15],
+            num_to_generate: 3,
+            ..Default::default()
+        };
+        let (mut numbers, after) = generate(&path.to_string_lossy(), &config).unwrap();
+        daemon.join().unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        numbers.sort();
+        assert_eq!(numbers, [4, 8, 15]);
+        assert!(after.seed.is_some());
+        let script = GeneratorConfig { mode: GeneratorMode::SCRIPT, ..Default::default() };
+        assert!(generate("/nonexistent/random-tool.sock", &script).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_socket_is_private() {
+        use std::os::unix::fs::PermissionsExt;
+        use std::os::unix::net::UnixStream;
+
+        let dir = std::env::temp_dir().join(format!("random-tool-test-{}-private", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let path = dir.join("daemon.sock");
+        let _listener = bind_private(&path).unwrap();
+
+        assert!(UnixStream::connect(&path).is_ok());
+        assert_eq!(std::fs::metadata(&path).unwrap().permissions().mode() & 0o777, 0o600);
+        assert_eq!(std::fs::metadata(&dir).unwrap().permissions().mode() & 0o077, 0);
+        // Only the socket is left, not the directory it was bound in
+        assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 1);
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert!(!std::path::Path::new(&default_endpoint()).starts_with(std::env::temp_dir()));
+    }
+
+    #[test]
+    fn test_forwards_repeat_limits() {
+        let server = Server::new(false);
+        let config = GeneratorConfig {
+            lower_bound: 1,
+            upper_bound: 2,
+            num_to_generate: 20,
+            allow_duplicates: true,
+            max_repeats: Some(10),
+            ..Default::default()
+        };
+        let (numbers, _) = draw_on(&server, &config);
+        assert_eq!(numbers.iter().filter(|&&n| n == 1).count(), 10);
+
+        let config = GeneratorConfig { max_repeats: None, max_streak: Some(1), ..config };
+        let (numbers, _) = draw_on(&server, &config);
+        assert!(numbers.windows(2).all(|pair| pair[0] != pair[1]));
+    }
+
+    #[test]
+    fn test_carries_shuffle_bag() {
+        let server = Server::new(false);
+        let mut config = GeneratorConfig {
+            lower_bound: 1,
+            upper_bound: 5,
+            num_to_generate: 2,
+            shuffle_bag: Some(ShuffleBag::new()),
+            ..Default::default()
+        };
+        let mut drawn = Vec::new();
+        for _ in 0..2 {
+            let (numbers, after) = draw_on(&server, &config);
+            drawn.extend(numbers);
+            config.shuffle_bag = after.shuffle_bag;
+        }
+        drawn.sort();
+        drawn.dedup();
+        assert_eq!(drawn.len(), 4);
+        assert_eq!(config.shuffle_bag.as_ref().map(ShuffleBag::left), Some(1));
+    }
+
+    #[test]
+    fn test_carries_cooldown() {
+        let server = Server::new(false);
+        let mut config = GeneratorConfig {
+            lower_bound: 1,
+            upper_bound: 3,
+            num_to_generate: 1,
+            cooldown: Some(Cooldown::new(2)),
+            ..Default::default()
+        };
+        let mut drawn = Vec::new();
+        for _ in 0..3 {
+            let (numbers, after) = draw_on(&server, &config);
+            drawn.extend(numbers);
+            config.cooldown = after.cooldown;
+        }
+        drawn.sort();
+        assert_eq!(drawn, [1, 2, 3]);
+        assert_eq!(config.cooldown.as_ref().map(|cooldown| cooldown.recent().count()), Some(2));
+    }
+
+    #[test]
+    fn test_carries_exclusions() {
+        let server = Server::new(false);
+        let mut config = GeneratorConfig {
+            lower_bound: 1,
+            upper_bound: 10,
+            num_to_generate: 5,
+            exclude_drawn: Some(ExclusionSet::new()),
+            ..Default::default()
+        };
+        let (first, after) = draw_on(&server, &config);
+        config.exclude_drawn = after.exclude_drawn;
+        let (second, after) = draw_on(&server, &config);
+        assert!(second.iter().all(|n| !first.contains(n)));
+        assert_eq!(after.exclude_drawn.as_ref().map(ExclusionSet::len), Some(10));
+        // The config sent keeps the state from before the draw
+        assert_eq!(config.exclude_drawn.as_ref().map(ExclusionSet::len), Some(5));
+    }
+
+    #[test]
+    fn test_sends_lenient_list_parsed() {
+        let mut generator = RandomGenerator::new();
+        generator.set_lenient_list(true).unwrap();
+        generator.set_mode(GeneratorMode::CUSTOM_LIST).unwrap();
+        generator.set_custom_list_input("4, x, 8".to_string()).unwrap();
+        let config = GeneratorConfig { num_to_generate: 2, ..generator.get_config().clone() };
+
+        assert_eq!(params(&config).unwrap()["list"], json!([4, 8]));
+        let (mut numbers, _) = draw_on(&Server::new(false), &config);
+        numbers.sort();
+        assert_eq!(numbers, [4, 8]);
+    }
+}
@@ -0,0 +1,313 @@
+//! `randomtool://` links that open the window pre-configured.
+//!
+//! `randomtool://open?min=1&max=100&count=5` fills in the inputs and
+//! `randomtool://generate?...` also offers to draw, so a wiki page or a chat
+//! message can carry a ready-made draw. Links can come from anyone, so a
+//! count above [`MAX_COUNT`] is refused and nothing is drawn until the user
+//! confirms. `random-tool register-url` tells the desktop to hand such links
+//! to this program.
+
+use std::io;
+#[cfg(any(target_os = "linux", windows))]
+use std::process::Command;
+
+use iced::widget::{button, column, container, row, text, Space};
+use iced::{alignment, Background, Border, Color, Element, Length, Shadow, Theme, Vector};
+use random_generator_core::{GeneratorMode, SourceInput, MAX_COUNT};
+
+use crate::{accessibility, get_link_button_style, i18n, Message, RandomGeneratorApp, Status};
+
+pub const SCHEME: &str = "randomtool";
+
+/// Settings carried by a link; anything left out keeps its default
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct DeepLink {
+    pub lower: Option<i64>,
+    pub upper: Option<i64>,
+    pub count: Option<usize>,
+    pub duplicates: Option<bool>,
+    /// Numbers to draw from, switching to the custom list mode
+    pub list: Option<String>,
+    /// Offer to draw as soon as the window opens
+    pub generate: bool,
+}
+
+/// Whether a command-line argument is a link rather than a command
+pub fn is_link(arg: &str) -> bool {
+    arg.get(..SCHEME.len()).is_some_and(|scheme| scheme.eq_ignore_ascii_case(SCHEME)) && arg[SCHEME.len()..].starts_with(':')
+}
+
+/// Read a `randomtool://` link
+pub fn parse(url: &str) -> Result<DeepLink, String> {
+    if !is_link(url) {
+        return Err(format!("not a {}:// link", SCHEME));
+    }
+    let rest = url[SCHEME.len() + 1..].trim_start_matches('/');
+    let (action, query) = rest.split_once('?').unwrap_or((rest, ""));
+
+    let mut link = DeepLink {
+        generate: match action.trim_end_matches('/') {
+            "generate" => true,
+            "" | "open" => false,
+            other => return Err(format!("unknown link action '{}'", other)),
+        },
+        ..Default::default()
+    };
+    for pair in query.split('&').filter(|pair| !pair.is_empty()) {
+        let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+        let value = decode(value);
+        match key {
+            "min" | "from" => link.lower = Some(parse_value(key, &value)?),
+            "max" | "to" => link.upper = Some(parse_value(key, &value)?),
+            "count" | "n" => link.count = Some(parse_value(key, &value)?),
+            "duplicates" => {
+                link.duplicates = Some(match value.as_str() {
+                    "" | "1" | "true" | "yes" => true,
+                    "0" | "false" | "no" => false,
+                    _ => return Err(format!("invalid value '{}' for {}", value, key)),
+                })
+            }
+            "list" => link.list = Some(value),
+            _ => return Err(format!("unknown link parameter '{}'", key)),
+        }
+    }
+    if link.count.is_some_and(|count| count > MAX_COUNT) {
+        return Err(format!("count must be at most {}", MAX_COUNT));
+    }
+    if let Some((lower, upper)) = link.lower.zip(link.upper).filter(|(lower, upper)| lower > upper) {
+        return Err(format!("min {} is above max {}", lower, upper));
+    }
+    Ok(link)
+}
+
+fn parse_value<T: std::str::FromStr>(key: &str, value: &str) -> Result<T, String> {
+    value.trim().parse().map_err(|_| format!("invalid value '{}' for {}", value, key))
+}
+
+/// Undo percent-encoding, with `+` standing for a space as in form data
+fn decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' => decoded.push(b' '),
+            b'%' => match value
+                .get(i + 1..i + 3)
+                .filter(|hex| hex.bytes().all(|byte| byte.is_ascii_hexdigit()))
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+            {
+                Some(byte) => {
+                    decoded.push(byte);
+                    i += 2;
+                }
+                // A lone percent sign stays as it is
+                None => decoded.push(b'%'),
+            },
+            byte => decoded.push(byte),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+impl RandomGeneratorApp {
+    /// Fill in the inputs from a link; a link that asks to draw waits for the user to confirm
+    pub(crate) fn open_link(&mut self, link: Result<DeepLink, String>) {
+        let link = match link {
+            Ok(link) => link,
+            Err(e) => {
+                self.status = Some(Status::Error(format!("Could not open link: {}", e)));
+                return;
+            }
+        };
+
+        if let Some(list) = link.list {
+            let _ = self.update(Message::ModeChanged(GeneratorMode::CUSTOM_LIST));
+            if let Err(e) = self.set_custom_list(list) {
+                self.status = Some(Status::Error(e.to_string()));
+            }
+        } else if (link.lower.is_some() || link.upper.is_some()) && self.mode_input() != SourceInput::Bounds {
+            let _ = self.update(Message::ModeChanged(GeneratorMode::RANGE));
+        }
+        if let Some(lower) = link.lower {
+            self.lower_bound = lower.to_string();
+        }
+        if let Some(upper) = link.upper {
+            self.upper_bound = upper.to_string();
+        }
+        if let Some(count) = link.count {
+            self.num_to_generate = count.to_string();
+        }
+        if let Some(duplicates) = link.duplicates {
+            // Together with the new inputs, which may only fit once duplicates are allowed
+            let mut config = self.input_config();
+            config.allow_duplicates = duplicates;
+            if let Err(e) = self.generator.set_config(config) {
+                self.status = Some(Status::Error(e.to_string()));
+            }
+        }
+
+        // Inputs the link got wrong are shown on the fields instead
+        self.link_draw_pending = link.generate && self.field_errors().is_empty();
+    }
+
+    /// What a link is about to draw, with buttons to draw it or not
+    pub(crate) fn link_draw_view(&self) -> Element<'_, Message> {
+        let dark_mode = self.settings.dark_mode;
+        let high_contrast = self.settings.high_contrast;
+        let action = |content: &'static str, message: Message| {
+            button(text(self.tr(content)).size(14))
+                .on_press(message)
+                .padding(self.button_padding())
+                .style(move |_theme: &Theme, status| get_link_button_style(dark_mode, high_contrast, status))
+        };
+        let count = self.num_to_generate.trim();
+        let mut summary = match self.mode_input() {
+            SourceInput::Bounds => i18n::fill(self.tr("{} numbers from {} to {}"), &[&count, &self.lower_bound.trim(), &self.upper_bound.trim()]),
+            _ => i18n::fill(self.tr("{} numbers from the list"), &[&count]),
+        };
+        if self.generator.get_allow_duplicates() {
+            summary = i18n::fill(self.tr("{}, duplicates allowed"), &[&summary]);
+        }
+
+        let content = column![
+            text(self.tr("Draw from link?"))
+                .size(20)
+                .color(if dark_mode { Color::from_rgb(0.9, 0.9, 0.9) } else { Color::BLACK }),
+            Space::with_height(Length::Fixed(10.0)),
+            text(self.tr("A link asks to draw right away:")).size(14),
+            text(summary).size(14),
+            text(self.tr("The inputs are filled in; check them before drawing."))
+                .size(12)
+                .color(accessibility::dim_text_color(dark_mode, high_contrast)),
+            Space::with_height(Length::Fixed(10.0)),
+            row![
+                Space::with_width(Length::Fill),
+                action("Cancel", Message::CancelLinkDraw),
+                action("Draw", Message::ConfirmLinkDraw),
+            ]
+                .spacing(6)
+                .align_y(alignment::Vertical::Center),
+        ]
+            .spacing(4)
+            .padding(24);
+
+        container(content)
+            .width(Length::Fixed(360.0))
+            .style(move |_theme: &Theme| {
+                iced::widget::container::Style {
+                    background: Some(Background::Color(if dark_mode { Color::from_rgb(0.2, 0.2, 0.25) } else { Color::WHITE })),
+                    border: Border {
+                        color: if dark_mode { Color::from_rgb(0.4, 0.4, 0.4) } else { Color::from_rgb(0.8, 0.8, 0.8) },
+                        width: 1.0,
+                        radius: 16.0.into(),
+                    },
+                    shadow: Shadow {
+                        color: Color::from_rgba(0.0, 0.0, 0.0, 0.3),
+                        offset: Vector::new(0.0, 4.0),
+                        blur_radius: 20.0,
+                    },
+                    ..Default::default()
+                }
+            })
+            .into()
+    }
+}
+
+/// Make this program the desktop's handler for `randomtool://` links
+///
+/// Returns a description of what was registered.
+pub fn register() -> io::Result<String> {
+    let exe = std::env::current_exe()?;
+    register_for(&exe.to_string_lossy())
+}
+
+#[cfg(target_os = "linux")]
+fn register_for(exe: &str) -> io::Result<String> {
+    const DESKTOP_FILE: &str = "random-tool-url.desktop";
+    let dir = dirs::data_dir()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no data directory"))?
+        .join("applications");
+    std::fs::create_dir_all(&dir)?;
+    let entry = format!(
+        "[Desktop Entry]\nType=Application\nName=Random Generator\nExec=\"{}\" %u\nMimeType=x-scheme-handler/{};\nNoDisplay=true\n",
+        exe, SCHEME
+    );
+    std::fs::write(dir.join(DESKTOP_FILE), entry)?;
+    run(Command::new("xdg-mime").args(["default", DESKTOP_FILE, &format!("x-scheme-handler/{}", SCHEME)]))?;
+    Ok(format!("{} opens {}:// links", dir.join(DESKTOP_FILE).display(), SCHEME))
+}
+
+#[cfg(windows)]
+fn register_for(exe: &str) -> io::Result<String> {
+    let key = format!(r"HKCU\Software\Classes\{}", SCHEME);
+    let command = format!("\"{}\" \"%1\"", exe);
+    run(Command::new("reg").args(["add", &key, "/ve", "/d", &format!("URL:{}", SCHEME), "/f"]))?;
+    run(Command::new("reg").args(["add", &key, "/v", "URL Protocol", "/d", "", "/f"]))?;
+    run(Command::new("reg").args(["add", &format!(r"{}\shell\open\command", key), "/ve", "/d", &command, "/f"]))?;
+    Ok(format!("{} opens {}:// links", key, SCHEME))
+}
+
+#[cfg(not(any(target_os = "linux", windows)))]
+fn register_for(_exe: &str) -> io::Result<String> {
+    // macOS reads URL schemes from the app bundle's Info.plist (CFBundleURLTypes)
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        format!("add {} to CFBundleURLSchemes in the app bundle's Info.plist instead", SCHEME),
+    ))
+}
+
+#[cfg(any(target_os = "linux", windows))]
+fn run(command: &mut Command) -> io::Result<()> {
+    let status = command.status().map_err(|e| {
+        io::Error::new(e.kind(), format!("could not run {}: {}", command.get_program().to_string_lossy(), e))
+    })?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(io::Error::other(format!("{:?} failed with {}", command, status)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_links() {
+        assert_eq!(
+            parse("randomtool://generate?min=1&max=100&count=5"),
+            Ok(DeepLink { lower: Some(1), upper: Some(100), count: Some(5), generate: true, ..Default::default() })
+        );
+        assert_eq!(
+            parse("RandomTool:open/?list=1%2C%202+3&duplicates=no"),
+            Ok(DeepLink { list: Some("1, 2 3".to_string()), duplicates: Some(false), ..Default::default() })
+        );
+        assert_eq!(parse("randomtool://"), Ok(DeepLink::default()));
+        assert!(parse("randomtool://generate?min=one").is_err());
+        assert!(parse("randomtool://generate?colour=red").is_err());
+        assert!(parse("randomtool://delete").is_err());
+        assert!(parse("randomtool://generate?count=1000000000000&duplicates=1").is_err());
+        assert!(parse("randomtool://open?min=10&max=1").is_err());
+        assert!(!is_link("range"));
+        assert!(!is_link("随机数生成器"));
+        assert_eq!(decode("100%"), "100%");
+    }
+
+    #[test]
+    fn test_link_waits_for_confirmation() {
+        let mut app = RandomGeneratorApp::default();
+        app.open_link(parse("randomtool://generate?min=1&max=6&count=2"));
+        assert!(app.link_draw_pending);
+        assert_eq!(app.upper_bound, "6");
+        assert!(app.generator.get_numbers().is_empty());
+        let _ = app.update(Message::CancelLinkDraw);
+        assert!(!app.link_draw_pending);
+        assert!(app.generator.get_numbers().is_empty());
+
+        app.open_link(parse("randomtool://generate?count=1000000000000&duplicates=1"));
+        assert!(!app.link_draw_pending);
+        assert!(matches!(&app.status, Some(Status::Error(message)) if message.starts_with("Could not open link: count must be at most")));
+    }
+}
@@ -0,0 +1,257 @@
+//! Panel for experiment designs.
+//!
+//! Researchers running counterbalanced studies enter a number of treatments
+//! or their names and get a randomized Latin square, a Williams square that
+//! also balances which treatment comes right after which, or a randomized
+//! block design with every treatment once per block. The layout is shown as
+//! a table and saved as text or CSV. The designs come from
+//! `ExperimentDesign` in the core and are laid out on a worker thread with a
+//! progress bar.
+
+use iced::widget::{button, column, container, pick_list, row, scrollable, text, text_input, Space};
+use iced::{alignment, Background, Border, Color, Element, Font, Length, Shadow, Task, Theme, Vector};
+use random_generator_core::{DesignKind, DesignTable, ExperimentDesign, ExportFormat, Seed};
+
+use crate::export_panel::{settings_line, ExportFile, ExportPanel, PanelSeed};
+use crate::worker::{PanelEvent, PanelRun};
+use crate::{accessibility, get_link_button_style, get_pick_list_style, get_text_input_style, Message, RandomGeneratorApp};
+
+/// Formats a design can be saved in
+const FILE_FORMATS: [ExportFormat; 2] = [ExportFormat::Text, ExportFormat::Csv];
+
+#[derive(Debug)]
+pub struct DesignPanel {
+    kind: DesignKind,
+    /// A number of treatments, or their names separated by commas
+    treatments: String,
+    blocks: String,
+    seed: PanelSeed,
+    file: ExportFile,
+    running: PanelRun,
+    table: Option<DesignTable>,
+    status: String,
+}
+
+impl Default for DesignPanel {
+    fn default() -> Self {
+        Self {
+            kind: DesignKind::default(),
+            treatments: "4".to_string(),
+            blocks: "5".to_string(),
+            seed: PanelSeed::default(),
+            file: ExportFile::new("design.txt", ExportFormat::Text),
+            running: PanelRun::default(),
+            table: None,
+            status: String::new(),
+        }
+    }
+}
+
+impl DesignPanel {
+    pub fn set_kind(&mut self, kind: DesignKind) {
+        self.kind = kind;
+    }
+
+    pub fn set_treatments(&mut self, treatments: String) {
+        self.treatments = treatments;
+    }
+
+    pub fn set_blocks(&mut self, blocks: String) {
+        self.blocks = blocks;
+    }
+
+    pub fn file_mut(&mut self) -> &mut ExportFile {
+        &mut self.file
+    }
+
+    pub fn seed_mut(&mut self) -> &mut PanelSeed {
+        &mut self.seed
+    }
+
+    /// How the last draw or export went
+    pub fn status(&self) -> &str {
+        &self.status
+    }
+
+    /// Settings of the draw, for the history
+    pub fn settings(&self) -> String {
+        let mut fields = vec![("kind", self.kind.to_string()), ("treatments", self.treatments.clone())];
+        if self.kind == DesignKind::Blocks {
+            fields.push(("blocks", self.blocks.clone()));
+        }
+        settings_line(&fields)
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.running.is_running()
+    }
+
+    /// Lay out the design on a worker thread
+    pub fn run(&mut self) -> Task<Message> {
+        if self.is_running() {
+            return Task::none();
+        }
+        self.table = None;
+        let seed = match self.seed.next() {
+            Ok(seed) => seed,
+            Err(message) => {
+                self.status = message;
+                return Task::none();
+            }
+        };
+        let blocks = match self.kind {
+            DesignKind::Blocks => match self.blocks.trim().parse() {
+                Ok(blocks) => blocks,
+                Err(_) => {
+                    self.status = "Enter a whole number of blocks".to_string();
+                    return Task::none();
+                }
+            },
+            _ => 0,
+        };
+        let (kind, treatments) = (self.kind, self.treatments.clone());
+        self.status.clear();
+        let draw = self.running.start(move |cancel, on_progress| {
+            ExperimentDesign::parse(&treatments)
+                .and_then(|design| design.generate_with_progress(kind, blocks, seed, cancel, on_progress))
+                .map(|table| (seed, table))
+                .map_err(|e| e.to_string())
+        });
+        Task::run(draw, Message::Design)
+    }
+
+    pub fn cancel(&self) {
+        self.running.cancel();
+    }
+
+    /// Follow the running draw; the table shows once it has finished
+    pub fn handle(&mut self, event: PanelEvent<(Seed, DesignTable)>) {
+        let Some(result) = self.running.update(event) else {
+            return;
+        };
+        self.status = match result {
+            Ok((seed, table)) => {
+                self.seed.keep(seed);
+                let status = format!("{} rows of {} treatments", table.rows.len(), table.rows.first().map_or(0, Vec::len));
+                self.table = Some(table);
+                status
+            }
+            Err(e) => e,
+        };
+    }
+
+    pub fn export(&mut self) {
+        let csv = self.file.format() == ExportFormat::Csv;
+        self.status = self.file.save(self.table.as_ref().map(|table| if csv { table.to_csv() } else { table.to_text() }));
+    }
+}
+
+impl RandomGeneratorApp {
+    pub(crate) fn design_view(&self) -> Element<'_, Message> {
+        let panel = &self.design;
+        let dark_mode = self.settings.dark_mode;
+        let high_contrast = self.settings.high_contrast;
+        let label = |content: &'static str| text(self.tr(content)).size(14).width(Length::Fixed(80.0));
+        let action = |content: &'static str, message: Option<Message>| {
+            button(text(self.tr(content)).size(14))
+                .on_press_maybe(message)
+                .padding(self.button_padding())
+                .style(move |_theme: &Theme, status| get_link_button_style(dark_mode, high_contrast, status))
+        };
+        let input = |value: &str, on_input: fn(String) -> Message, width: Length| {
+            text_input("", value)
+                .on_input(on_input)
+                .on_submit(Message::RunDesign)
+                .width(width)
+                .size(14)
+                .style(move |_theme: &Theme, status| get_text_input_style(dark_mode, high_contrast, status))
+        };
+        let hint = match panel.kind {
+            DesignKind::LatinSquare => "Each row is one group's order of treatments; every treatment appears once in each row and each period",
+            DesignKind::Balanced => "Also balances carryover: each treatment follows every other equally often. An odd number of treatments needs twice as many rows",
+            DesignKind::Blocks => "Every block gets each treatment once, in its own random order",
+        };
+
+        let blocks = (panel.kind == DesignKind::Blocks).then(|| {
+            row![label("Blocks"), input(&panel.blocks, Message::DesignBlocksChanged, Length::Fixed(100.0))]
+                .spacing(6)
+                .align_y(alignment::Vertical::Center)
+        });
+
+        let preview = panel.table.as_ref().map(|table| {
+            let lines = table.to_text().lines().map(|line| text(line.to_string()).size(12).font(Font::MONOSPACE).into()).collect::<Vec<_>>();
+            container(
+                scrollable(column(lines))
+                    .direction(scrollable::Direction::Both {
+                        vertical: scrollable::Scrollbar::default(),
+                        horizontal: scrollable::Scrollbar::default(),
+                    })
+                    .height(Length::Fixed(180.0)),
+            )
+                .padding(6)
+                .width(Length::Fill)
+        });
+
+        let content = column![
+            text(self.tr("Experiment design"))
+                .size(20)
+                .color(if dark_mode { Color::from_rgb(0.9, 0.9, 0.9) } else { Color::BLACK }),
+            Space::with_height(Length::Fixed(10.0)),
+            row![
+                label("Design"),
+                pick_list(&DesignKind::ALL[..], Some(panel.kind), Message::DesignKindChanged)
+                    .text_size(14)
+                    .width(Length::Fixed(200.0))
+                    .style(move |_theme: &Theme, _status| get_pick_list_style(dark_mode, high_contrast)),
+                Space::with_width(Length::Fill),
+                action("Generate", (!panel.is_running()).then_some(Message::RunDesign)),
+            ]
+                .spacing(6)
+                .align_y(alignment::Vertical::Center),
+            row![label("Treatments"), input(&panel.treatments, Message::DesignTreatmentsChanged, Length::Fill)]
+                .spacing(6)
+                .align_y(alignment::Vertical::Center),
+        ]
+            .push_maybe(blocks)
+            .push(
+                text(self.tr("A number like 4 for treatments A to D, or names separated by commas"))
+                    .size(11)
+                    .color(accessibility::dim_text_color(dark_mode, high_contrast)),
+            )
+            .push(text(self.tr(hint)).size(11).color(accessibility::dim_text_color(dark_mode, high_contrast)))
+            .push_maybe(self.progress_row(&panel.running, Message::CancelDesign))
+            .push_maybe(preview)
+            .push(self.seed_row(ExportPanel::Design, &panel.seed))
+            .push(self.export_row(ExportPanel::Design, &panel.file, &FILE_FORMATS, "Export"))
+            .push(
+                row![
+                    text(&panel.status).size(13).color(accessibility::dim_text_color(dark_mode, high_contrast)),
+                    Space::with_width(Length::Fill),
+                    action("Close", Some(Message::CloseDesign)),
+                ]
+                    .align_y(alignment::Vertical::Center),
+            )
+            .spacing(6)
+            .padding(24);
+
+        container(content)
+            .width(Length::Fixed(480.0))
+            .style(move |_theme: &Theme| {
+                iced::widget::container::Style {
+                    background: Some(Background::Color(if dark_mode { Color::from_rgb(0.2, 0.2, 0.25) } else { Color::WHITE })),
+                    border: Border {
+                        color: if dark_mode { Color::from_rgb(0.4, 0.4, 0.4) } else { Color::from_rgb(0.8, 0.8, 0.8) },
+                        width: 1.0,
+                        radius: 16.0.into(),
+                    },
+                    shadow: Shadow {
+                        color: Color::from_rgba(0.0, 0.0, 0.0, 0.3),
+                        offset: Vector::new(0.0, 4.0),
+                        blur_radius: 20.0,
+                    },
+                    ..Default::default()
+                }
+            })
+            .into()
+    }
+}
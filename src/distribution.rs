@@ -0,0 +1,292 @@
+//! Panel for drawing from a continuous distribution.
+//!
+//! Pick a distribution and its parameters, optionally with a minimum and a
+//! maximum: the values are drawn from the distribution truncated to that
+//! interval, renormalized rather than clipped, so "normal(100, 15) from 60 to
+//! 140" needs no filtering afterwards. A summary and a histogram show the
+//! shape; the report is saved as text or the values as CSV. The sampling is
+//! `TruncatedDistribution` in the core and runs on a worker thread with a
+//! progress bar.
+
+use iced::widget::{button, column, container, pick_list, row, scrollable, text, text_input, Row, Space};
+use iced::{alignment, Background, Border, Color, Element, Font, Length, Shadow, Task, Theme, Vector};
+use random_generator_core::{Distribution, DistributionKind, DistributionReport, ExportFormat, Seed, TruncatedDistribution};
+
+use crate::export_panel::{settings_line, ExportFile, ExportPanel, PanelSeed};
+use crate::worker::{PanelEvent, PanelRun};
+use crate::{accessibility, get_link_button_style, get_pick_list_style, get_text_input_style, Message, RandomGeneratorApp};
+
+/// Formats the report can be saved in
+const FILE_FORMATS: [ExportFormat; 2] = [ExportFormat::Text, ExportFormat::Csv];
+
+#[derive(Debug)]
+pub struct DistributionPanel {
+    kind: DistributionKind,
+    /// In the order of `DistributionKind::parameters`
+    parameters: Vec<String>,
+    /// Empty for no truncation on that side
+    min: String,
+    max: String,
+    count: String,
+    seed: PanelSeed,
+    file: ExportFile,
+    running: PanelRun,
+    report: Option<DistributionReport>,
+    status: String,
+}
+
+impl Default for DistributionPanel {
+    fn default() -> Self {
+        let kind = DistributionKind::default();
+        Self {
+            kind,
+            parameters: kind.defaults().iter().map(f64::to_string).collect(),
+            min: String::new(),
+            max: String::new(),
+            count: "1000".to_string(),
+            seed: PanelSeed::default(),
+            file: ExportFile::new("distribution.txt", ExportFormat::Text),
+            running: PanelRun::default(),
+            report: None,
+            status: String::new(),
+        }
+    }
+}
+
+impl DistributionPanel {
+    /// Also puts back the new distribution's default parameters
+    pub fn set_kind(&mut self, kind: DistributionKind) {
+        if kind != self.kind {
+            self.kind = kind;
+            self.parameters = kind.defaults().iter().map(f64::to_string).collect();
+        }
+    }
+
+    pub fn set_parameter(&mut self, index: usize, value: String) {
+        if let Some(parameter) = self.parameters.get_mut(index) {
+            *parameter = value;
+        }
+    }
+
+    pub fn set_min(&mut self, min: String) {
+        self.min = min;
+    }
+
+    pub fn set_max(&mut self, max: String) {
+        self.max = max;
+    }
+
+    pub fn set_count(&mut self, count: String) {
+        self.count = count;
+    }
+
+    pub fn file_mut(&mut self) -> &mut ExportFile {
+        &mut self.file
+    }
+
+    pub fn seed_mut(&mut self) -> &mut PanelSeed {
+        &mut self.seed
+    }
+
+    /// How the last draw or export went
+    pub fn status(&self) -> &str {
+        &self.status
+    }
+
+    /// Settings of the draw, for the history
+    pub fn settings(&self) -> String {
+        let mut fields = vec![("kind", self.kind.to_string())];
+        fields.extend(self.kind.parameters().iter().zip(&self.parameters).map(|(name, value)| (*name, value.clone())));
+        fields.extend([("min", self.min.clone()), ("max", self.max.clone()), ("count", self.count.clone())]);
+        settings_line(&fields)
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.running.is_running()
+    }
+
+    /// Draw the values on a worker thread
+    pub fn run(&mut self) -> Task<Message> {
+        if self.is_running() {
+            return Task::none();
+        }
+        self.report = None;
+        let seed = match self.seed.next() {
+            Ok(seed) => seed,
+            Err(message) => {
+                self.status = message;
+                return Task::none();
+            }
+        };
+        let Ok(parameters) = self.parameters.iter().map(|value| value.trim().parse()).collect::<Result<Vec<f64>, _>>() else {
+            self.status = "Enter numbers for the parameters".to_string();
+            return Task::none();
+        };
+        let bound = |value: &str| match value.trim() {
+            "" => Ok(None),
+            value => value.parse().map(Some),
+        };
+        let (Ok(min), Ok(max)) = (bound(&self.min), bound(&self.max)) else {
+            self.status = "Enter numbers for the minimum and maximum, or leave them empty".to_string();
+            return Task::none();
+        };
+        let Ok(count) = self.count.trim().parse() else {
+            self.status = "Enter a whole number of values".to_string();
+            return Task::none();
+        };
+        let truncated = Distribution::new(self.kind, &parameters)
+            .and_then(|distribution| TruncatedDistribution::new(distribution, min, max));
+        let truncated = match truncated {
+            Ok(truncated) => truncated,
+            Err(e) => {
+                self.status = e.to_string();
+                return Task::none();
+            }
+        };
+        self.status.clear();
+        let draw = self.running.start(move |cancel, on_progress| {
+            truncated.sample_with_progress(count, seed, cancel, on_progress).map(|report| (seed, report)).map_err(|e| e.to_string())
+        });
+        Task::run(draw, Message::Distribution)
+    }
+
+    pub fn cancel(&self) {
+        self.running.cancel();
+    }
+
+    /// Follow the running draw; the report shows once it has finished
+    pub fn handle(&mut self, event: PanelEvent<(Seed, DistributionReport)>) {
+        let Some(result) = self.running.update(event) else {
+            return;
+        };
+        self.status = match result {
+            Ok((seed, report)) => {
+                self.seed.keep(seed);
+                let status = format!("Mean {:.4}, standard deviation {:.4}", report.mean(), report.standard_deviation());
+                self.report = Some(report);
+                status
+            }
+            Err(e) => e,
+        };
+    }
+
+    pub fn export(&mut self) {
+        let csv = self.file.format() == ExportFormat::Csv;
+        self.status = self.file.save(self.report.as_ref().map(|report| if csv { report.to_csv() } else { report.to_text() }));
+    }
+}
+
+impl RandomGeneratorApp {
+    pub(crate) fn distribution_view(&self) -> Element<'_, Message> {
+        let panel = &self.distribution;
+        let dark_mode = self.settings.dark_mode;
+        let high_contrast = self.settings.high_contrast;
+        let label = |content: &'static str| text(self.tr(content)).size(14).width(Length::Fixed(80.0));
+        let action = |content: &'static str, message: Option<Message>| {
+            button(text(self.tr(content)).size(14))
+                .on_press_maybe(message)
+                .padding(self.button_padding())
+                .style(move |_theme: &Theme, status| get_link_button_style(dark_mode, high_contrast, status))
+        };
+        let number_input = |placeholder: &'static str, value: &str, on_input: Box<dyn Fn(String) -> Message>| {
+            text_input(self.tr(placeholder), value)
+                .on_input(on_input)
+                .on_submit(Message::RunDistribution)
+                .width(Length::Fixed(80.0))
+                .size(14)
+                .style(move |_theme: &Theme, status| get_text_input_style(dark_mode, high_contrast, status))
+        };
+
+        let parameters = panel.kind.parameters().iter().zip(&panel.parameters).enumerate().fold(
+            Row::new().spacing(6).align_y(alignment::Vertical::Center),
+            |row, (index, (&name, value))| {
+                row.push(label(name))
+                    .push(number_input("", value, Box::new(move |value| Message::DistributionParameterChanged(index, value))))
+            },
+        );
+
+        let preview = panel.report.as_ref().map(|report| {
+            let lines = report.to_text().lines().map(|line| text(line.to_string()).size(12).font(Font::MONOSPACE).into()).collect::<Vec<_>>();
+            container(
+                scrollable(column(lines))
+                    .direction(scrollable::Direction::Both {
+                        vertical: scrollable::Scrollbar::default(),
+                        horizontal: scrollable::Scrollbar::default(),
+                    })
+                    .height(Length::Fixed(220.0)),
+            )
+                .padding(6)
+                .width(Length::Fill)
+        });
+
+        let content = column![
+            text(self.tr("Distribution"))
+                .size(20)
+                .color(if dark_mode { Color::from_rgb(0.9, 0.9, 0.9) } else { Color::BLACK }),
+            Space::with_height(Length::Fixed(10.0)),
+            row![
+                label("Distribution"),
+                pick_list(&DistributionKind::ALL[..], Some(panel.kind), Message::DistributionKindChanged)
+                    .text_size(14)
+                    .width(Length::Fixed(120.0))
+                    .style(move |_theme: &Theme, _status| get_pick_list_style(dark_mode, high_contrast)),
+            ]
+                .spacing(6)
+                .align_y(alignment::Vertical::Center),
+            parameters,
+            row![
+                label("Minimum"),
+                number_input("None", &panel.min, Box::new(Message::DistributionMinChanged)),
+                label("Maximum"),
+                number_input("None", &panel.max, Box::new(Message::DistributionMaxChanged)),
+            ]
+                .spacing(6)
+                .align_y(alignment::Vertical::Center),
+            row![
+                label("Count"),
+                number_input("", &panel.count, Box::new(Message::DistributionCountChanged)),
+                Space::with_width(Length::Fill),
+                action("Generate", (!panel.is_running()).then_some(Message::RunDistribution)),
+            ]
+                .spacing(6)
+                .align_y(alignment::Vertical::Center),
+            text(self.tr("With a minimum or maximum, values outside never come up and the rest keep their relative chances; nothing is clipped or filtered afterwards"))
+                .size(11)
+                .color(accessibility::dim_text_color(dark_mode, high_contrast)),
+        ]
+            .push_maybe(self.progress_row(&panel.running, Message::CancelDistribution))
+            .push_maybe(preview)
+            .push(self.seed_row(ExportPanel::Distribution, &panel.seed))
+            .push(self.export_row(ExportPanel::Distribution, &panel.file, &FILE_FORMATS, "Export"))
+            .push(
+                row![
+                    text(&panel.status).size(13).color(accessibility::dim_text_color(dark_mode, high_contrast)),
+                    Space::with_width(Length::Fill),
+                    action("Close", Some(Message::CloseDistribution)),
+                ]
+                    .align_y(alignment::Vertical::Center),
+            )
+            .spacing(6)
+            .padding(24);
+
+        container(content)
+            .width(Length::Fixed(480.0))
+            .style(move |_theme: &Theme| {
+                iced::widget::container::Style {
+                    background: Some(Background::Color(if dark_mode { Color::from_rgb(0.2, 0.2, 0.25) } else { Color::WHITE })),
+                    border: Border {
+                        color: if dark_mode { Color::from_rgb(0.4, 0.4, 0.4) } else { Color::from_rgb(0.8, 0.8, 0.8) },
+                        width: 1.0,
+                        radius: 16.0.into(),
+                    },
+                    shadow: Shadow {
+                        color: Color::from_rgba(0.0, 0.0, 0.0, 0.3),
+                        offset: Vector::new(0.0, 4.0),
+                        blur_radius: 20.0,
+                    },
+                    ..Default::default()
+                }
+            })
+            .into()
+    }
+}
@@ -0,0 +1,428 @@
+//! Seed and file rows shared by the panels that draw and save results.
+//!
+//! Weights, custom distribution, correlated, design, distribution, games,
+//! group draw, test data and bootstrap each draw with a seed and keep a file
+//! name and format. `PanelSeed` takes the seed typed in or a fresh one and
+//! keeps the one behind the results on show, so it can be shown, stored in
+//! the history with the panel's settings and drawn again with Replay.
+//! `ExportFile` switches the extension along with the format and reports how
+//! the write went. Panels whose draws take a while run them on a worker
+//! thread and show `progress_row` meanwhile. A panel only supplies its draw
+//! and its contents; `ExportPanel` says which panel a message is for.
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use iced::widget::{button, column, pick_list, progress_bar, row, text, text_input, Row};
+use iced::{alignment, Element, Font, Length, Task, Theme};
+use random_generator_core::{ExportFormat, Seed};
+
+use crate::history;
+use crate::report::{SessionDraw, SessionResults};
+use crate::worker::PanelRun;
+use crate::{accessibility, get_link_button_style, i18n, get_pick_list_style, get_text_input_style, Message, RandomGeneratorApp};
+
+/// Panels with a file row
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportPanel {
+    Weights,
+    CdfTable,
+    Correlated,
+    Design,
+    Distribution,
+    Games,
+    GroupDraw,
+    TestData,
+    Bootstrap,
+}
+
+impl ExportPanel {
+    pub const ALL: [ExportPanel; 9] = [
+        ExportPanel::Weights,
+        ExportPanel::CdfTable,
+        ExportPanel::Correlated,
+        ExportPanel::Design,
+        ExportPanel::Distribution,
+        ExportPanel::Games,
+        ExportPanel::GroupDraw,
+        ExportPanel::TestData,
+        ExportPanel::Bootstrap,
+    ];
+}
+
+/// The panel's title, also its draws' mode in the history
+impl fmt::Display for ExportPanel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExportPanel::Weights => write!(f, "Random weights"),
+            ExportPanel::CdfTable => write!(f, "Custom distribution"),
+            ExportPanel::Correlated => write!(f, "Correlated normal"),
+            ExportPanel::Design => write!(f, "Experiment design"),
+            ExportPanel::Distribution => write!(f, "Distribution"),
+            ExportPanel::Games => write!(f, "Games"),
+            ExportPanel::GroupDraw => write!(f, "Group draw"),
+            ExportPanel::TestData => write!(f, "Test data"),
+            ExportPanel::Bootstrap => write!(f, "Bootstrap"),
+        }
+    }
+}
+
+/// Seed a panel draws with
+#[derive(Debug, Default)]
+pub struct PanelSeed {
+    /// Empty draws with a fresh seed every time
+    text: String,
+    /// Seed of the results on show
+    used: Option<Seed>,
+}
+
+impl PanelSeed {
+    pub fn set_text(&mut self, text: String) {
+        self.text = text;
+    }
+
+    /// Seed for the next draw, the one typed in or a fresh one; the last
+    /// seed is forgotten until that draw works out
+    pub fn next(&mut self) -> Result<Seed, String> {
+        self.used = None;
+        match self.text.trim() {
+            "" => Ok(Seed::random(&mut rand::thread_rng())),
+            text => text.parse().map_err(|_| "Enter a seed of 64 hex digits, or leave it empty".to_string()),
+        }
+    }
+
+    /// The draw with `seed` worked out
+    pub fn keep(&mut self, seed: Seed) {
+        self.used = Some(seed);
+    }
+
+    pub fn used(&self) -> Option<Seed> {
+        self.used
+    }
+
+    /// Type in the seed of the results on show, so the next draw repeats them
+    fn reuse(&mut self) {
+        if let Some(seed) = self.used {
+            self.text = seed.to_string();
+        }
+    }
+}
+
+/// Settings as tab-separated `key=value` fields like those of the main
+/// draws, with values of several lines joined by `; `
+pub fn settings_line(fields: &[(&str, String)]) -> String {
+    fields
+        .iter()
+        .map(|(key, value)| {
+            let lines: Vec<&str> = value.lines().map(str::trim).filter(|line| !line.is_empty()).collect();
+            format!("{}={}", key, lines.join("; "))
+        })
+        .collect::<Vec<_>>()
+        .join("\t")
+}
+
+/// Where a panel saves its results and in which format
+#[derive(Debug)]
+pub struct ExportFile {
+    filename: String,
+    format: ExportFormat,
+}
+
+impl ExportFile {
+    pub fn new(filename: &str, format: ExportFormat) -> Self {
+        Self {
+            filename: filename.to_string(),
+            format,
+        }
+    }
+
+    pub fn format(&self) -> ExportFormat {
+        self.format
+    }
+
+    pub fn set_filename(&mut self, filename: String) {
+        self.filename = filename;
+    }
+
+    /// Also switches the filename's extension
+    pub fn set_format(&mut self, format: ExportFormat) {
+        self.format = format;
+        self.filename = Path::new(&self.filename).with_extension(format.extension()).to_string_lossy().into_owned();
+    }
+
+    /// Renames the file to `new` if it still has the default name `old`,
+    /// keeping its folder and extension
+    pub fn rename_default(&mut self, old: &str, new: &str) {
+        let path = Path::new(&self.filename);
+        if path.file_stem().is_some_and(|stem| stem == old) {
+            self.filename = path.with_file_name(new).with_extension(self.format.extension()).to_string_lossy().into_owned();
+        }
+    }
+
+    /// Writes the contents and returns the line saying where
+    pub fn write(&self, contents: &str) -> io::Result<String> {
+        fs::write(&self.filename, contents).map(|()| format!("Saved to {}", self.filename))
+    }
+
+    /// Status line after saving, `None` when there is nothing to save yet
+    pub fn save(&self, contents: Option<String>) -> String {
+        match contents.map(|contents| self.write(&contents)) {
+            None => "Nothing to export yet".to_string(),
+            Some(Ok(saved)) => saved,
+            Some(Err(e)) => format!("Export error: {}", e),
+        }
+    }
+}
+
+impl RandomGeneratorApp {
+    /// Seed box with Replay, and the seed of the results on show
+    pub(crate) fn seed_row(&self, panel: ExportPanel, seed: &PanelSeed) -> Element<'_, Message> {
+        let dark_mode = self.settings.dark_mode;
+        let high_contrast = self.settings.high_contrast;
+        column![
+            row![
+                text(self.tr("Seed")).size(14).width(Length::Fixed(80.0)),
+                text_input(self.tr("Random"), &seed.text)
+                    .on_input(move |text| Message::PanelSeedChanged(panel, text))
+                    .width(Length::Fill)
+                    .size(14)
+                    .style(move |_theme: &Theme, status| get_text_input_style(dark_mode, high_contrast, status)),
+                button(text(self.tr("Replay")).size(14))
+                    .on_press_maybe(seed.used.map(|_| Message::PanelReplay(panel)))
+                    .padding(self.button_padding())
+                    .style(move |_theme: &Theme, status| get_link_button_style(dark_mode, high_contrast, status)),
+            ]
+                .spacing(6)
+                .align_y(alignment::Vertical::Center),
+        ]
+            .push_maybe(seed.used.map(|used| {
+                text(i18n::fill(self.tr("Drawn with seed {}"), &[&used]))
+                    .size(11)
+                    .font(Font::MONOSPACE)
+                    .color(accessibility::dim_text_color(dark_mode, high_contrast))
+            }))
+            .spacing(4)
+            .into()
+    }
+
+    /// Progress bar with Cancel while the panel draws on a worker thread
+    pub(crate) fn progress_row(&self, run: &PanelRun, cancel: Message) -> Option<Element<'_, Message>> {
+        let dark_mode = self.settings.dark_mode;
+        let high_contrast = self.settings.high_contrast;
+        run.progress().map(|fraction| {
+            row![
+                progress_bar(0.0..=1.0, fraction).height(Length::Fixed(8.0)),
+                text(format!("{:.0}%", fraction * 100.0)).size(13),
+                button(text(self.tr("Cancel")).size(14))
+                    .on_press(cancel)
+                    .padding(self.button_padding())
+                    .style(move |_theme: &Theme, status| get_link_button_style(dark_mode, high_contrast, status)),
+            ]
+                .spacing(8)
+                .align_y(alignment::Vertical::Center)
+                .into()
+        })
+    }
+
+    /// Label, file name and format list; enter in the name saves the file
+    pub(crate) fn export_fields(
+        &self,
+        panel: ExportPanel,
+        file: &ExportFile,
+        formats: &'static [ExportFormat],
+    ) -> Row<'_, Message> {
+        let dark_mode = self.settings.dark_mode;
+        let high_contrast = self.settings.high_contrast;
+        row![
+            text(self.tr("File")).size(14).width(Length::Fixed(80.0)),
+            text_input("", &file.filename)
+                .on_input(move |filename| Message::PanelFilenameChanged(panel, filename))
+                .on_submit(Message::PanelExport(panel))
+                .width(Length::Fill)
+                .size(14)
+                .style(move |_theme: &Theme, status| get_text_input_style(dark_mode, high_contrast, status)),
+            pick_list(formats, Some(file.format), move |format| Message::PanelFileFormatChanged(panel, format))
+                .text_size(14)
+                .style(move |_theme: &Theme, _status| get_pick_list_style(dark_mode, high_contrast)),
+        ]
+            .spacing(6)
+            .align_y(alignment::Vertical::Center)
+    }
+
+    /// The file fields followed by the button that saves
+    pub(crate) fn export_row(
+        &self,
+        panel: ExportPanel,
+        file: &ExportFile,
+        formats: &'static [ExportFormat],
+        action: &'static str,
+    ) -> Element<'_, Message> {
+        let dark_mode = self.settings.dark_mode;
+        let high_contrast = self.settings.high_contrast;
+        self.export_fields(panel, file, formats)
+            .push(
+                button(text(self.tr(action)).size(14))
+                    .on_press(Message::PanelExport(panel))
+                    .padding(self.button_padding())
+                    .style(move |_theme: &Theme, status| get_link_button_style(dark_mode, high_contrast, status)),
+            )
+            .into()
+    }
+
+    pub(crate) fn panel_seed(&mut self, panel: ExportPanel) -> &mut PanelSeed {
+        match panel {
+            ExportPanel::Weights => self.weights.seed_mut(),
+            ExportPanel::CdfTable => self.cdf_table.seed_mut(),
+            ExportPanel::Correlated => self.correlated.seed_mut(),
+            ExportPanel::Design => self.design.seed_mut(),
+            ExportPanel::Distribution => self.distribution.seed_mut(),
+            ExportPanel::Games => self.games.seed_mut(),
+            ExportPanel::GroupDraw => self.group_draw.seed_mut(),
+            ExportPanel::TestData => self.test_data.seed_mut(),
+            ExportPanel::Bootstrap => self.bootstrap.seed_mut(),
+        }
+    }
+
+    /// Draw in the panel; a draw that worked out goes in the history and the session report.
+    /// Long draws run on a worker thread and are recorded when they finish
+    pub(crate) fn run_panel(&mut self, panel: ExportPanel) -> Task<Message> {
+        match panel {
+            ExportPanel::Weights => self.weights.run(),
+            ExportPanel::CdfTable => self.cdf_table.run(),
+            ExportPanel::Correlated => return self.correlated.run(),
+            ExportPanel::Design => return self.design.run(),
+            ExportPanel::Distribution => return self.distribution.run(),
+            ExportPanel::Games => self.games.run(),
+            ExportPanel::GroupDraw => self.group_draw.draw(),
+            ExportPanel::TestData => self.test_data.generate(),
+            ExportPanel::Bootstrap => return self.bootstrap.run(),
+        }
+        self.record_panel_draw(panel);
+        Task::none()
+    }
+
+    /// Put the panel's last draw in the history and the session report, if it worked out
+    pub(crate) fn record_panel_draw(&mut self, panel: ExportPanel) {
+        let Some(seed) = self.panel_seed(panel).used() else {
+            return;
+        };
+        let (settings, summary) = match panel {
+            ExportPanel::Weights => (self.weights.settings(), self.weights.status()),
+            ExportPanel::CdfTable => (self.cdf_table.settings(), self.cdf_table.status()),
+            ExportPanel::Correlated => (self.correlated.settings(), self.correlated.status()),
+            ExportPanel::Design => (self.design.settings(), self.design.status()),
+            ExportPanel::Distribution => (self.distribution.settings(), self.distribution.status()),
+            ExportPanel::Games => (self.games.settings(), self.games.status()),
+            ExportPanel::GroupDraw => (self.group_draw.settings(), self.group_draw.status()),
+            ExportPanel::TestData => (self.test_data.settings(), self.test_data.status()),
+            ExportPanel::Bootstrap => (self.bootstrap.settings(), self.bootstrap.status()),
+        };
+        let draw = SessionDraw {
+            time: history::now(),
+            mode: panel.to_string(),
+            settings,
+            seed: Some(seed),
+            results: SessionResults::Summary(summary.to_string()),
+        };
+        // As with the main draws, failing to write the history doesn't fail the draw
+        let _ = history::record_panel(&draw.mode, &draw.settings, seed, self.settings.history_retention());
+        self.session.push(draw);
+    }
+
+    /// Draw again with the seed of the results on show
+    pub(crate) fn replay_panel(&mut self, panel: ExportPanel) -> Task<Message> {
+        self.panel_seed(panel).reuse();
+        self.run_panel(panel)
+    }
+
+    /// Open the panel with a seed from the history typed in
+    pub(crate) fn show_panel(&mut self, panel: ExportPanel, seed: &str) {
+        self.panel_seed(panel).set_text(seed.to_string());
+        match panel {
+            ExportPanel::Weights => self.weights_open = true,
+            ExportPanel::CdfTable => self.cdf_table_open = true,
+            ExportPanel::Correlated => self.correlated_open = true,
+            ExportPanel::Design => self.design_open = true,
+            ExportPanel::Distribution => self.distribution_open = true,
+            ExportPanel::Games => self.games_open = true,
+            ExportPanel::GroupDraw => self.group_draw_open = true,
+            ExportPanel::TestData => self.test_data_open = true,
+            ExportPanel::Bootstrap => self.bootstrap_open = true,
+        }
+    }
+
+    pub(crate) fn export_file(&mut self, panel: ExportPanel) -> &mut ExportFile {
+        match panel {
+            ExportPanel::Weights => self.weights.file_mut(),
+            ExportPanel::CdfTable => self.cdf_table.file_mut(),
+            ExportPanel::Correlated => self.correlated.file_mut(),
+            ExportPanel::Design => self.design.file_mut(),
+            ExportPanel::Distribution => self.distribution.file_mut(),
+            ExportPanel::Games => self.games.file_mut(),
+            ExportPanel::GroupDraw => self.group_draw.file_mut(),
+            ExportPanel::TestData => self.test_data.file_mut(),
+            ExportPanel::Bootstrap => self.bootstrap.file_mut(),
+        }
+    }
+
+    pub(crate) fn export(&mut self, panel: ExportPanel) {
+        match panel {
+            ExportPanel::Weights => self.weights.export(),
+            ExportPanel::CdfTable => self.cdf_table.export(),
+            ExportPanel::Correlated => self.correlated.export(),
+            ExportPanel::Design => self.design.export(),
+            ExportPanel::Distribution => self.distribution.export(),
+            ExportPanel::Games => self.games.export(),
+            ExportPanel::GroupDraw => self.group_draw.export(),
+            ExportPanel::TestData => self.test_data.export(),
+            ExportPanel::Bootstrap => self.bootstrap.export(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_switches_extension() {
+        let mut file = ExportFile::new("out/weights.txt", ExportFormat::Text);
+        file.set_format(ExportFormat::Csv);
+        assert_eq!(file.filename, "out/weights.csv");
+        file.rename_default("weights", "keno");
+        assert_eq!(file.filename, "out/keno.csv");
+        // A name the user chose stays
+        file.set_filename("mine.csv".to_string());
+        file.rename_default("keno", "gacha");
+        assert_eq!(file.filename, "mine.csv");
+    }
+
+    #[test]
+    fn test_seed_is_kept_for_replay() {
+        let mut seed = PanelSeed::default();
+        let fresh = seed.next().unwrap();
+        assert_eq!(seed.used(), None);
+        seed.keep(fresh);
+        seed.reuse();
+        assert_eq!(seed.next().unwrap(), fresh);
+
+        seed.set_text("not hex".to_string());
+        assert!(seed.next().is_err());
+        assert_eq!(seed.used(), None);
+    }
+
+    #[test]
+    fn test_settings_line() {
+        let line = settings_line(&[("count", "10".to_string()), ("names", "Alice\n\nBob\n".to_string())]);
+        assert_eq!(line, "count=10\tnames=Alice; Bob");
+    }
+
+    #[test]
+    fn test_save_reports_nothing_yet() {
+        let file = ExportFile::new("never-written.txt", ExportFormat::Text);
+        assert_eq!(file.save(None), "Nothing to export yet");
+        assert!(!Path::new("never-written.txt").exists());
+    }
+}
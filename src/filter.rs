@@ -0,0 +1,60 @@
+/// Filter applied to the results view, parsed from the search box.
+///
+/// `10..20` (or `10..=20`) matches values inside the inclusive range,
+/// anything else matches values whose decimal text contains it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ResultFilter {
+    Range(i64, i64),
+    Contains(String),
+}
+
+impl ResultFilter {
+    /// Parse the search box text, `None` when the box is empty
+    pub fn parse(input: &str) -> Option<Self> {
+        let input = input.trim();
+        if input.is_empty() {
+            return None;
+        }
+
+        if let Some((low, high)) = input.split_once("..") {
+            let high = high.strip_prefix('=').unwrap_or(high);
+            if let (Ok(low), Ok(high)) = (low.trim().parse::<i64>(), high.trim().parse::<i64>()) {
+                return Some(ResultFilter::Range(low.min(high), low.max(high)));
+            }
+        }
+
+        Some(ResultFilter::Contains(input.to_string()))
+    }
+
+    pub fn matches(&self, value: i64) -> bool {
+        match self {
+            ResultFilter::Range(low, high) => (*low..=*high).contains(&value),
+            ResultFilter::Contains(needle) => value.to_string().contains(needle.as_str()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_range_and_substring() {
+        assert_eq!(ResultFilter::parse("  "), None);
+        assert_eq!(ResultFilter::parse("10..20"), Some(ResultFilter::Range(10, 20)));
+        assert_eq!(ResultFilter::parse("-5..=-1"), Some(ResultFilter::Range(-5, -1)));
+        assert_eq!(ResultFilter::parse("20..10"), Some(ResultFilter::Range(10, 20)));
+        assert_eq!(ResultFilter::parse("42"), Some(ResultFilter::Contains("42".to_string())));
+    }
+
+    #[test]
+    fn test_matches() {
+        let contains = ResultFilter::parse("42").unwrap();
+        assert!(contains.matches(1426));
+        assert!(!contains.matches(24));
+
+        let range = ResultFilter::parse("10..20").unwrap();
+        assert!(range.matches(10) && range.matches(20));
+        assert!(!range.matches(21));
+    }
+}
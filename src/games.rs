@@ -0,0 +1,425 @@
+//! Panel for game draws.
+//!
+//! Keno quick picks choose a pool size and how many numbers go on each
+//! ticket, make a whole batch of tickets at once and flag the tickets that
+//! came out the same as an earlier one. The gacha simulator pulls from a
+//! loot table of rarity tiers, optionally with pity counters, and reports how
+//! often each tier dropped so drop rates can be checked before shipping.
+//! The dice pool rolls a handful of dice, optionally exploding, and counts
+//! the dice that reach a target. Results can be saved as text or CSV. They
+//! come from `KenoSpec`, `LootTable` and `DicePool` in the core.
+
+use std::fmt;
+
+use iced::widget::{button, column, container, pick_list, row, scrollable, text, text_editor, text_input, Space};
+use iced::{alignment, Background, Border, Color, Element, Font, Length, Shadow, Theme, Vector};
+use random_generator_core::{DicePool, ExportFormat, GachaReport, KenoSpec, KenoTicket, LootTable, PoolRoll};
+
+use crate::export_panel::{settings_line, ExportFile, ExportPanel, PanelSeed};
+use crate::{accessibility, get_link_button_style, get_pick_list_style, get_text_input_style, Message, RandomGeneratorApp};
+
+/// More tickets or rolls than this would make the window stall
+const MAX_ROWS: usize = 100_000;
+/// Pulls are only counted, so many more of them fit
+const MAX_PULLS: usize = 10_000_000;
+/// Loot table the editor starts with
+const EXAMPLE_TABLE: &str = "Common, 80%\nRare, 15%\nLegendary, 5%, 90\n";
+/// Tickets or rolls shown in the panel; the saved file has all of them
+const PREVIEW_ROWS: usize = 200;
+/// Formats the results can be saved in
+const FILE_FORMATS: [ExportFormat; 2] = [ExportFormat::Text, ExportFormat::Csv];
+
+/// Which game the panel draws for
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum GameKind {
+    #[default]
+    Keno,
+    Gacha,
+    DicePool,
+}
+
+impl GameKind {
+    pub const ALL: [GameKind; 3] = [GameKind::Keno, GameKind::Gacha, GameKind::DicePool];
+
+    /// Most tickets or pulls one run makes
+    fn max_count(self) -> usize {
+        match self {
+            GameKind::Keno | GameKind::DicePool => MAX_ROWS,
+            GameKind::Gacha => MAX_PULLS,
+        }
+    }
+
+    /// Default name of the saved file
+    fn file_stem(self) -> &'static str {
+        match self {
+            GameKind::Keno => "tickets",
+            GameKind::Gacha => "drop-rates",
+            GameKind::DicePool => "dice-pool",
+        }
+    }
+}
+
+impl fmt::Display for GameKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GameKind::Keno => write!(f, "Keno quick pick"),
+            GameKind::Gacha => write!(f, "Gacha simulator"),
+            GameKind::DicePool => write!(f, "Dice pool"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct GamesPanel {
+    kind: GameKind,
+    pool: String,
+    picks: String,
+    dice: String,
+    sides: String,
+    target: String,
+    /// Empty for dice that don't explode
+    explode: String,
+    /// One tier per line, like `Legendary, 5%, 90`
+    loot_table: text_editor::Content,
+    count: String,
+    seed: PanelSeed,
+    file: ExportFile,
+    /// The spec the tickets were made with, for saving them
+    keno: Option<(KenoSpec, Vec<KenoTicket>)>,
+    gacha: Option<GachaReport>,
+    dice_pool: Option<(DicePool, Vec<PoolRoll>)>,
+    status: String,
+}
+
+impl Default for GamesPanel {
+    fn default() -> Self {
+        let keno = KenoSpec::default();
+        let dice_pool = DicePool::default();
+        Self {
+            kind: GameKind::default(),
+            pool: keno.pool.to_string(),
+            picks: keno.picks.to_string(),
+            dice: dice_pool.dice.to_string(),
+            sides: dice_pool.sides.to_string(),
+            target: dice_pool.target.to_string(),
+            explode: String::new(),
+            loot_table: text_editor::Content::with_text(EXAMPLE_TABLE),
+            count: "10".to_string(),
+            seed: PanelSeed::default(),
+            file: ExportFile::new("tickets.txt", ExportFormat::Text),
+            keno: None,
+            gacha: None,
+            dice_pool: None,
+            status: String::new(),
+        }
+    }
+}
+
+impl GamesPanel {
+    /// Also renames the file if it still has the old game's default name
+    pub fn set_kind(&mut self, kind: GameKind) {
+        self.file.rename_default(self.kind.file_stem(), kind.file_stem());
+        self.kind = kind;
+    }
+
+    pub fn set_pool(&mut self, pool: String) {
+        self.pool = pool;
+    }
+
+    pub fn set_picks(&mut self, picks: String) {
+        self.picks = picks;
+    }
+
+    pub fn set_dice(&mut self, dice: String) {
+        self.dice = dice;
+    }
+
+    pub fn set_sides(&mut self, sides: String) {
+        self.sides = sides;
+    }
+
+    pub fn set_target(&mut self, target: String) {
+        self.target = target;
+    }
+
+    pub fn set_explode(&mut self, explode: String) {
+        self.explode = explode;
+    }
+
+    pub fn edit_loot_table(&mut self, action: text_editor::Action) {
+        self.loot_table.perform(action);
+    }
+
+    pub fn set_count(&mut self, count: String) {
+        self.count = count;
+    }
+
+    pub fn file_mut(&mut self) -> &mut ExportFile {
+        &mut self.file
+    }
+
+    pub fn seed_mut(&mut self) -> &mut PanelSeed {
+        &mut self.seed
+    }
+
+    /// How the last draw or export went
+    pub fn status(&self) -> &str {
+        &self.status
+    }
+
+    /// Settings of the draw, for the history
+    pub fn settings(&self) -> String {
+        let mut fields = vec![("game", self.kind.to_string()), ("count", self.count.clone())];
+        match self.kind {
+            GameKind::Keno => fields.extend([("pool", self.pool.clone()), ("picks", self.picks.clone())]),
+            GameKind::Gacha => fields.push(("table", self.loot_table.text())),
+            GameKind::DicePool => fields.extend([
+                ("dice", self.dice.clone()),
+                ("sides", self.sides.clone()),
+                ("target", self.target.clone()),
+                ("explode", self.explode.clone()),
+            ]),
+        }
+        settings_line(&fields)
+    }
+
+    pub fn run(&mut self) {
+        let seed = match self.seed.next() {
+            Ok(seed) => seed,
+            Err(message) => {
+                self.status = message;
+                return;
+            }
+        };
+        let count = match self.count.trim().parse::<usize>() {
+            Ok(count) if (1..=self.kind.max_count()).contains(&count) => count,
+            _ => {
+                self.status = format!("Enter a count from 1 to {}", self.kind.max_count());
+                return;
+            }
+        };
+        match self.kind {
+            GameKind::Keno => {
+                self.keno = None;
+                let (Ok(pool), Ok(picks)) = (self.pool.trim().parse(), self.picks.trim().parse()) else {
+                    self.status = "Enter whole numbers for the pool and picks".to_string();
+                    return;
+                };
+                let spec = KenoSpec { pool, picks };
+                self.status = match spec.tickets(count, seed) {
+                    Ok(tickets) => {
+                        self.seed.keep(seed);
+                        let duplicates = tickets.iter().filter(|ticket| ticket.duplicate_of.is_some()).count();
+                        self.keno = Some((spec, tickets));
+                        format!("{} tickets, {} duplicates", count, duplicates)
+                    }
+                    Err(e) => e.to_string(),
+                };
+            }
+            GameKind::Gacha => {
+                self.gacha = None;
+                self.status = match LootTable::parse(&self.loot_table.text()).and_then(|table| table.simulate(count, seed)) {
+                    Ok(report) => {
+                        self.seed.keep(seed);
+                        let pity: usize = report.tiers.iter().map(|tier| tier.pity_hits).sum();
+                        self.gacha = Some(report);
+                        format!("{} pulls, {} from pity", count, pity)
+                    }
+                    Err(e) => e.to_string(),
+                };
+            }
+            GameKind::DicePool => {
+                self.dice_pool = None;
+                let explode = match self.explode.trim() {
+                    "" => Ok(None),
+                    explode => explode.parse().map(Some),
+                };
+                let (Ok(dice), Ok(sides), Ok(target), Ok(explode)) =
+                    (self.dice.trim().parse(), self.sides.trim().parse(), self.target.trim().parse(), explode)
+                else {
+                    self.status = "Enter whole numbers for the dice, sides and targets".to_string();
+                    return;
+                };
+                let pool = DicePool { dice, sides, target, explode };
+                self.status = match pool.rolls(count, seed) {
+                    Ok(rolls) => {
+                        self.seed.keep(seed);
+                        let successes: usize = rolls.iter().map(|roll| roll.successes).sum();
+                        let average = successes as f64 / count as f64;
+                        self.dice_pool = Some((pool, rolls));
+                        format!("{} rolls, {:.2} successes on average", count, average)
+                    }
+                    Err(e) => e.to_string(),
+                };
+            }
+        }
+    }
+
+    pub fn export(&mut self) {
+        let csv = self.file.format() == ExportFormat::Csv;
+        let contents = match (self.kind, &self.keno, &self.gacha, &self.dice_pool) {
+            (GameKind::Keno, Some((spec, tickets)), _, _) => Some(if csv { spec.to_csv(tickets) } else { spec.to_text(tickets) }),
+            (GameKind::Gacha, _, Some(report), _) => Some(if csv { report.to_csv() } else { report.to_text() }),
+            (GameKind::DicePool, _, _, Some((pool, rolls))) => Some(if csv { pool.to_csv(rolls) } else { pool.to_text(rolls) }),
+            _ => None,
+        };
+        self.status = self.file.save(contents);
+    }
+}
+
+impl RandomGeneratorApp {
+    pub(crate) fn games_view(&self) -> Element<'_, Message> {
+        let panel = &self.games;
+        let dark_mode = self.settings.dark_mode;
+        let high_contrast = self.settings.high_contrast;
+        let label = |content: &'static str| text(self.tr(content)).size(14).width(Length::Fixed(80.0));
+        let hint = |content: &'static str| text(self.tr(content)).size(11).color(accessibility::dim_text_color(dark_mode, high_contrast));
+        let action = |content: &'static str, message: Message| {
+            button(text(self.tr(content)).size(14))
+                .on_press(message)
+                .padding(self.button_padding())
+                .style(move |_theme: &Theme, status| get_link_button_style(dark_mode, high_contrast, status))
+        };
+        let number_input = |value: &str, on_input: fn(String) -> Message| {
+            text_input("", value)
+                .on_input(on_input)
+                .on_submit(Message::RunGame)
+                .width(Length::Fixed(80.0))
+                .size(14)
+                .style(move |_theme: &Theme, status| get_text_input_style(dark_mode, high_contrast, status))
+        };
+
+        let mut options = column![].spacing(6);
+        let mut preview_lines: Vec<String> = Vec::new();
+        match panel.kind {
+            GameKind::Keno => {
+                options = options
+                    .push(
+                        row![
+                            label("Pool"),
+                            number_input(&panel.pool, Message::KenoPoolChanged),
+                            label("Picks"),
+                            number_input(&panel.picks, Message::KenoPicksChanged),
+                        ]
+                            .spacing(6)
+                            .align_y(alignment::Vertical::Center),
+                    )
+                    .push(hint("Each ticket picks numbers from 1 to the pool size; tickets the same as an earlier one are flagged"));
+                if let Some((spec, tickets)) = &panel.keno {
+                    preview_lines = spec.to_text(&tickets[..tickets.len().min(PREVIEW_ROWS)]).lines().map(String::from).collect();
+                }
+            }
+            GameKind::Gacha => {
+                options = options
+                    .push(text(self.tr("Loot table")).size(14))
+                    .push(
+                        text_editor(&panel.loot_table)
+                            .on_action(Message::LootTableEdited)
+                            .height(Length::Fixed(90.0))
+                            .size(13),
+                    )
+                    .push(hint("One tier per line: name, rate and an optional pity, like Legendary, 5%, 90 for a drop at the latest on pull 90"));
+                if let Some(report) = &panel.gacha {
+                    preview_lines = report.to_text().lines().map(String::from).collect();
+                }
+            }
+            GameKind::DicePool => {
+                options = options
+                    .push(
+                        row![
+                            label("Dice"),
+                            number_input(&panel.dice, Message::PoolDiceChanged),
+                            label("Sides"),
+                            number_input(&panel.sides, Message::PoolSidesChanged),
+                        ]
+                            .spacing(6)
+                            .align_y(alignment::Vertical::Center),
+                    )
+                    .push(
+                        row![
+                            label("Success at"),
+                            number_input(&panel.target, Message::PoolTargetChanged),
+                            label("Explode at"),
+                            number_input(&panel.explode, Message::PoolExplodeChanged),
+                        ]
+                            .spacing(6)
+                            .align_y(alignment::Vertical::Center),
+                    )
+                    .push(hint("Dice at or above the target count as successes; dice at or above the explode value roll one more die, marked +. Leave explode empty to turn it off"));
+                if let Some((pool, rolls)) = &panel.dice_pool {
+                    preview_lines = pool.to_text(&rolls[..rolls.len().min(PREVIEW_ROWS)]).lines().map(String::from).collect();
+                }
+            }
+        }
+        let preview = (!preview_lines.is_empty()).then(|| {
+            let lines = preview_lines.into_iter().map(|line| text(line).size(12).font(Font::MONOSPACE).into());
+            container(
+                scrollable(column(lines))
+                    .direction(scrollable::Direction::Both {
+                        vertical: scrollable::Scrollbar::default(),
+                        horizontal: scrollable::Scrollbar::default(),
+                    })
+                    .height(Length::Fixed(180.0)),
+            )
+                .padding(6)
+                .width(Length::Fill)
+        });
+
+        let content = column![
+            text(self.tr("Games"))
+                .size(20)
+                .color(if dark_mode { Color::from_rgb(0.9, 0.9, 0.9) } else { Color::BLACK }),
+            Space::with_height(Length::Fixed(10.0)),
+            row![
+                label("Game"),
+                pick_list(&GameKind::ALL[..], Some(panel.kind), Message::GameKindChanged)
+                    .text_size(14)
+                    .style(move |_theme: &Theme, _status| get_pick_list_style(dark_mode, high_contrast)),
+            ]
+                .spacing(6)
+                .align_y(alignment::Vertical::Center),
+            options,
+            row![
+                label("Count"),
+                number_input(&panel.count, Message::GameCountChanged),
+                Space::with_width(Length::Fill),
+                action("Generate", Message::RunGame),
+            ]
+                .spacing(6)
+                .align_y(alignment::Vertical::Center),
+        ]
+            .push_maybe(preview)
+            .push(self.seed_row(ExportPanel::Games, &panel.seed))
+            .push(self.export_row(ExportPanel::Games, &panel.file, &FILE_FORMATS, "Export"))
+            .push(
+                row![
+                    text(&panel.status).size(13).color(accessibility::dim_text_color(dark_mode, high_contrast)),
+                    Space::with_width(Length::Fill),
+                    action("Close", Message::CloseGames),
+                ]
+                    .align_y(alignment::Vertical::Center),
+            )
+            .spacing(6)
+            .padding(24);
+
+        container(content)
+            .width(Length::Fixed(420.0))
+            .style(move |_theme: &Theme| {
+                iced::widget::container::Style {
+                    background: Some(Background::Color(if dark_mode { Color::from_rgb(0.2, 0.2, 0.25) } else { Color::WHITE })),
+                    border: Border {
+                        color: if dark_mode { Color::from_rgb(0.4, 0.4, 0.4) } else { Color::from_rgb(0.8, 0.8, 0.8) },
+                        width: 1.0,
+                        radius: 16.0.into(),
+                    },
+                    shadow: Shadow {
+                        color: Color::from_rgba(0.0, 0.0, 0.0, 0.3),
+                        offset: Vector::new(0.0, 4.0),
+                        blur_radius: 20.0,
+                    },
+                    ..Default::default()
+                }
+            })
+            .into()
+    }
+}
@@ -0,0 +1,266 @@
+//! Panel for random graphs.
+//!
+//! Generates an Erdős–Rényi graph with a fixed number of edges or a
+//! Barabási–Albert graph grown by preferential attachment, as test input for
+//! graph algorithms. The graph is saved as a plain edge list or a Graphviz DOT
+//! file. The graphs come from `RandomGraph` in the core and are generated on
+//! a worker thread with a progress bar.
+
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+use iced::widget::{button, column, container, pick_list, row, text, text_input, Space};
+use iced::{alignment, Background, Border, Color, Element, Length, Shadow, Task, Theme, Vector};
+use random_generator_core::{Graph, GraphModel, RandomGraph, Seed};
+
+use crate::worker::{PanelEvent, PanelRun};
+use crate::{accessibility, get_link_button_style, get_pick_list_style, get_text_input_style, Message, RandomGeneratorApp};
+
+/// How the graph is saved
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum GraphFormat {
+    #[default]
+    EdgeList,
+    Dot,
+}
+
+impl GraphFormat {
+    pub const ALL: [GraphFormat; 2] = [GraphFormat::EdgeList, GraphFormat::Dot];
+
+    fn extension(self) -> &'static str {
+        match self {
+            GraphFormat::EdgeList => "txt",
+            GraphFormat::Dot => "dot",
+        }
+    }
+}
+
+impl fmt::Display for GraphFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GraphFormat::EdgeList => write!(f, "Edge list"),
+            GraphFormat::Dot => write!(f, "DOT"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct GraphPanel {
+    model: GraphModel,
+    nodes: String,
+    edges: String,
+    format: GraphFormat,
+    filename: String,
+    running: PanelRun,
+    graph: Option<Graph>,
+    status: String,
+}
+
+impl Default for GraphPanel {
+    fn default() -> Self {
+        let spec = RandomGraph::default();
+        Self {
+            model: spec.model,
+            nodes: spec.nodes.to_string(),
+            edges: spec.edges.to_string(),
+            format: GraphFormat::default(),
+            filename: "graph.txt".to_string(),
+            running: PanelRun::default(),
+            graph: None,
+            status: String::new(),
+        }
+    }
+}
+
+impl GraphPanel {
+    pub fn set_model(&mut self, model: GraphModel) {
+        self.model = model;
+    }
+
+    pub fn set_nodes(&mut self, nodes: String) {
+        self.nodes = nodes;
+    }
+
+    pub fn set_edges(&mut self, edges: String) {
+        self.edges = edges;
+    }
+
+    pub fn set_format(&mut self, format: GraphFormat) {
+        self.format = format;
+        self.filename = Path::new(&self.filename).with_extension(format.extension()).to_string_lossy().into_owned();
+    }
+
+    pub fn set_filename(&mut self, filename: String) {
+        self.filename = filename;
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.running.is_running()
+    }
+
+    /// Generate the graph on a worker thread
+    pub fn run(&mut self) -> Task<Message> {
+        if self.is_running() {
+            return Task::none();
+        }
+        self.graph = None;
+        let (Ok(nodes), Ok(edges)) = (self.nodes.trim().parse(), self.edges.trim().parse()) else {
+            self.status = "Enter whole numbers for the nodes and edges".to_string();
+            return Task::none();
+        };
+        let spec = RandomGraph { model: self.model, nodes, edges };
+        let seed = Seed::random(&mut rand::thread_rng());
+        self.status.clear();
+        let draw = self.running.start(move |cancel, on_progress| {
+            spec.generate_with_progress(seed, cancel, on_progress).map_err(|e| e.to_string())
+        });
+        Task::run(draw, Message::Graph)
+    }
+
+    pub fn cancel(&self) {
+        self.running.cancel();
+    }
+
+    /// Follow the running draw; the summary shows once it has finished
+    pub fn handle(&mut self, event: PanelEvent<Graph>) {
+        let Some(result) = self.running.update(event) else {
+            return;
+        };
+        self.status = match result {
+            Ok(graph) => {
+                let degrees = graph.degrees();
+                let status = format!(
+                    "{} nodes, {} edges, degree {} to {}",
+                    graph.nodes,
+                    graph.edges.len(),
+                    degrees.iter().min().unwrap_or(&0),
+                    degrees.iter().max().unwrap_or(&0),
+                );
+                self.graph = Some(graph);
+                status
+            }
+            Err(e) => e,
+        };
+    }
+
+    pub fn export(&mut self) {
+        let Some(graph) = &self.graph else {
+            self.status = "Nothing to export yet".to_string();
+            return;
+        };
+        let contents = match self.format {
+            GraphFormat::EdgeList => graph.to_edge_list(),
+            GraphFormat::Dot => graph.to_dot(),
+        };
+        self.status = match fs::write(&self.filename, contents) {
+            Ok(()) => format!("Saved to {}", self.filename),
+            Err(e) => format!("Export error: {}", e),
+        };
+    }
+}
+
+impl RandomGeneratorApp {
+    pub(crate) fn graph_view(&self) -> Element<'_, Message> {
+        let panel = &self.graph;
+        let dark_mode = self.settings.dark_mode;
+        let high_contrast = self.settings.high_contrast;
+        let label = |content: &'static str| text(self.tr(content)).size(14).width(Length::Fixed(80.0));
+        let action = |content: &'static str, message: Option<Message>| {
+            button(text(self.tr(content)).size(14))
+                .on_press_maybe(message)
+                .padding(self.button_padding())
+                .style(move |_theme: &Theme, status| get_link_button_style(dark_mode, high_contrast, status))
+        };
+        let number_input = |value: &str, on_input: fn(String) -> Message| {
+            text_input("", value)
+                .on_input(on_input)
+                .on_submit(Message::RunGraph)
+                .width(Length::Fixed(100.0))
+                .size(14)
+                .style(move |_theme: &Theme, status| get_text_input_style(dark_mode, high_contrast, status))
+        };
+        let hint = match panel.model {
+            GraphModel::ErdosRenyi => "Edges is the total: every set of that many edges is equally likely",
+            GraphModel::BarabasiAlbert => "Edges is per new node: each joins the graph linked to nodes picked by their degree, so early nodes become hubs",
+        };
+
+        let content = column![
+            text(self.tr("Random graph"))
+                .size(20)
+                .color(if dark_mode { Color::from_rgb(0.9, 0.9, 0.9) } else { Color::BLACK }),
+            Space::with_height(Length::Fixed(10.0)),
+            row![
+                label("Model"),
+                pick_list(&GraphModel::ALL[..], Some(panel.model), Message::GraphModelChanged)
+                    .text_size(14)
+                    .width(Length::Fixed(160.0))
+                    .style(move |_theme: &Theme, _status| get_pick_list_style(dark_mode, high_contrast)),
+            ]
+                .spacing(6)
+                .align_y(alignment::Vertical::Center),
+            row![
+                label("Nodes"),
+                number_input(&panel.nodes, Message::GraphNodesChanged),
+                label("Edges"),
+                number_input(&panel.edges, Message::GraphEdgesChanged),
+                Space::with_width(Length::Fill),
+                action("Generate", (!panel.is_running()).then_some(Message::RunGraph)),
+            ]
+                .spacing(6)
+                .align_y(alignment::Vertical::Center),
+            text(self.tr(hint))
+                .size(11)
+                .color(accessibility::dim_text_color(dark_mode, high_contrast)),
+        ]
+            .push_maybe(self.progress_row(&panel.running, Message::CancelGraph))
+            .push(
+                row![
+                    label("Format"),
+                    pick_list(&GraphFormat::ALL[..], Some(panel.format), Message::GraphFormatChanged)
+                        .text_size(14)
+                        .width(Length::Fixed(110.0))
+                        .style(move |_theme: &Theme, _status| get_pick_list_style(dark_mode, high_contrast)),
+                    text_input("", &panel.filename)
+                        .on_input(Message::GraphFilenameChanged)
+                        .on_submit(Message::ExportGraph)
+                        .width(Length::Fill)
+                        .size(14)
+                        .style(move |_theme: &Theme, status| get_text_input_style(dark_mode, high_contrast, status)),
+                    action("Export", Some(Message::ExportGraph)),
+                ]
+                    .spacing(6)
+                    .align_y(alignment::Vertical::Center),
+            )
+            .push(
+                row![
+                    text(&panel.status).size(13).color(accessibility::dim_text_color(dark_mode, high_contrast)),
+                    Space::with_width(Length::Fill),
+                    action("Close", Some(Message::CloseGraph)),
+                ]
+                    .align_y(alignment::Vertical::Center),
+            )
+            .spacing(6)
+            .padding(24);
+
+        container(content)
+            .width(Length::Fixed(480.0))
+            .style(move |_theme: &Theme| {
+                iced::widget::container::Style {
+                    background: Some(Background::Color(if dark_mode { Color::from_rgb(0.2, 0.2, 0.25) } else { Color::WHITE })),
+                    border: Border {
+                        color: if dark_mode { Color::from_rgb(0.4, 0.4, 0.4) } else { Color::from_rgb(0.8, 0.8, 0.8) },
+                        width: 1.0,
+                        radius: 16.0.into(),
+                    },
+                    shadow: Shadow {
+                        color: Color::from_rgba(0.0, 0.0, 0.0, 0.3),
+                        offset: Vector::new(0.0, 4.0),
+                        blur_radius: 20.0,
+                    },
+                    ..Default::default()
+                }
+            })
+            .into()
+    }
+}
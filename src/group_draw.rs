@@ -0,0 +1,397 @@
+//! Panel for draws that work on a list of people rather than numbers.
+//!
+//! Secret Santa gives everyone someone else to buy a gift for, with pairs
+//! such as couples who must not draw each other. The assignments stay hidden
+//! unless asked for, and can be exported as one file per person so each one
+//! only learns their own. A bracket seeds the names at random into a
+//! single-elimination tournament, filling up with byes, and is shown as a
+//! tree that can be saved as text or CSV. A pairing hands out a second list,
+//! such as papers to reviewers, so everyone gets the same number give or take
+//! one, with pairs that must not happen. The draws come from `SecretSanta`,
+//! `Bracket` and `Pairing` in the core.
+
+use std::fmt;
+use std::path::Path;
+
+use iced::widget::{button, checkbox, column, container, pick_list, row, scrollable, text, text_editor, text_input, Space};
+use iced::{alignment, Background, Border, Color, Element, Font, Length, Shadow, Theme, Vector};
+use random_generator_core::{Assignment, Bracket, ExportFormat, Pair, Pairing, SecretSanta};
+
+use crate::export_panel::{settings_line, ExportFile, ExportPanel, PanelSeed};
+use crate::{
+    accessibility, get_checkbox_style, get_link_button_style, get_pick_list_style, get_text_input_style, Message,
+    RandomGeneratorApp,
+};
+
+/// What kind of draw the panel makes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum GroupDrawFormat {
+    #[default]
+    SecretSanta,
+    Bracket,
+    Pairing,
+}
+
+impl GroupDrawFormat {
+    pub const ALL: [GroupDrawFormat; 3] = [GroupDrawFormat::SecretSanta, GroupDrawFormat::Bracket, GroupDrawFormat::Pairing];
+
+    /// Default name of the saved file, for the draws saved as one file
+    fn file_stem(self) -> Option<&'static str> {
+        match self {
+            GroupDrawFormat::SecretSanta => None,
+            GroupDrawFormat::Bracket => Some("bracket"),
+            GroupDrawFormat::Pairing => Some("pairing"),
+        }
+    }
+}
+
+/// Formats a bracket or pairing can be saved in
+const FILE_FORMATS: [ExportFormat; 2] = [ExportFormat::Text, ExportFormat::Csv];
+
+impl fmt::Display for GroupDrawFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GroupDrawFormat::SecretSanta => write!(f, "Secret Santa"),
+            GroupDrawFormat::Bracket => write!(f, "Tournament bracket"),
+            GroupDrawFormat::Pairing => write!(f, "Pairing"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct GroupDrawPanel {
+    format: GroupDrawFormat,
+    /// One name per line
+    names: text_editor::Content,
+    /// One pair per line, like `Alice, Bob`
+    exclusions: text_editor::Content,
+    /// What a pairing hands out to the names, one per line
+    items: text_editor::Content,
+    /// One person and item per line, like `Alice, Paper 3`
+    forbidden: text_editor::Content,
+    /// Show who gives to whom instead of only the givers
+    reveal: bool,
+    /// Where the per-person files go
+    folder: String,
+    seed: PanelSeed,
+    /// Where a bracket or pairing is saved and as what
+    file: ExportFile,
+    assignments: Vec<Assignment>,
+    bracket: Option<Bracket>,
+    pairing: Option<(Pairing, Vec<Pair>)>,
+    status: String,
+}
+
+impl Default for GroupDrawPanel {
+    fn default() -> Self {
+        Self {
+            format: GroupDrawFormat::default(),
+            names: text_editor::Content::new(),
+            exclusions: text_editor::Content::new(),
+            items: text_editor::Content::new(),
+            forbidden: text_editor::Content::new(),
+            reveal: false,
+            folder: "secret-santa".to_string(),
+            seed: PanelSeed::default(),
+            file: ExportFile::new("bracket.txt", ExportFormat::Text),
+            assignments: Vec::new(),
+            bracket: None,
+            pairing: None,
+            status: String::new(),
+        }
+    }
+}
+
+impl GroupDrawPanel {
+    /// Also renames the file if it still has the old format's default name
+    pub fn set_format(&mut self, format: GroupDrawFormat) {
+        if let (Some(old), Some(new)) = (self.format.file_stem(), format.file_stem()) {
+            self.file.rename_default(old, new);
+        }
+        self.format = format;
+    }
+
+    pub fn edit_names(&mut self, action: text_editor::Action) {
+        self.names.perform(action);
+    }
+
+    pub fn edit_exclusions(&mut self, action: text_editor::Action) {
+        self.exclusions.perform(action);
+    }
+
+    pub fn edit_items(&mut self, action: text_editor::Action) {
+        self.items.perform(action);
+    }
+
+    pub fn edit_forbidden(&mut self, action: text_editor::Action) {
+        self.forbidden.perform(action);
+    }
+
+    pub fn set_reveal(&mut self, reveal: bool) {
+        self.reveal = reveal;
+    }
+
+    pub fn set_folder(&mut self, folder: String) {
+        self.folder = folder;
+    }
+
+    pub fn file_mut(&mut self) -> &mut ExportFile {
+        &mut self.file
+    }
+
+    pub fn seed_mut(&mut self) -> &mut PanelSeed {
+        &mut self.seed
+    }
+
+    /// How the last draw or export went
+    pub fn status(&self) -> &str {
+        &self.status
+    }
+
+    /// Settings of the draw, for the history
+    pub fn settings(&self) -> String {
+        let mut fields = vec![("draw", self.format.to_string()), ("names", self.names.text())];
+        match self.format {
+            GroupDrawFormat::SecretSanta => fields.push(("exclusions", self.exclusions.text())),
+            GroupDrawFormat::Bracket => {}
+            GroupDrawFormat::Pairing => fields.extend([("items", self.items.text()), ("forbidden", self.forbidden.text())]),
+        }
+        settings_line(&fields)
+    }
+
+    pub fn draw(&mut self) {
+        let seed = match self.seed.next() {
+            Ok(seed) => seed,
+            Err(message) => {
+                self.status = message;
+                return;
+            }
+        };
+        self.assignments.clear();
+        self.bracket = None;
+        self.pairing = None;
+        let drawn = match self.format {
+            GroupDrawFormat::SecretSanta => SecretSanta::parse(&self.names.text())
+                .and_then(|mut santa| {
+                    santa.exclude_pairs(&self.exclusions.text())?;
+                    santa.draw(seed)
+                })
+                .map(|assignments| {
+                    self.assignments = assignments;
+                    format!("{} people drawn", self.assignments.len())
+                }),
+            GroupDrawFormat::Bracket => Bracket::draw(self.names.text().lines().map(|name| name.trim().to_string()).collect(), seed)
+                .map(|bracket| {
+                    let status = format!("{} rounds, {} byes", bracket.rounds(), bracket.byes());
+                    self.bracket = Some(bracket);
+                    status
+                }),
+            GroupDrawFormat::Pairing => Pairing::parse(&self.names.text(), &self.items.text())
+                .and_then(|mut pairing| {
+                    pairing.forbid_pairs(&self.forbidden.text())?;
+                    let pairs = pairing.draw(seed)?;
+                    Ok((pairing, pairs))
+                })
+                .map(|(pairing, pairs)| {
+                    let status = format!("{} items to {} people", pairs.len(), pairing.people().len());
+                    self.pairing = Some((pairing, pairs));
+                    status
+                }),
+        };
+        self.status = match drawn {
+            Ok(status) => {
+                self.seed.keep(seed);
+                status
+            }
+            Err(e) => e.to_string(),
+        };
+    }
+
+    /// Write one file per person into the folder, or save the bracket or pairing
+    pub fn export(&mut self) {
+        let csv = self.file.format() == ExportFormat::Csv;
+        let exported = match (self.format, &self.bracket, &self.pairing) {
+            (GroupDrawFormat::SecretSanta, _, _) if !self.assignments.is_empty() => {
+                let folder = self.folder.trim();
+                SecretSanta::write_assignments(&self.assignments, Path::new(folder))
+                    .map(|paths| format!("Wrote {} files to {}", paths.len(), folder))
+                    .map_err(|e| e.to_string())
+            }
+            (GroupDrawFormat::Bracket, Some(bracket), _) => {
+                self.file.write(&if csv { bracket.to_csv() } else { bracket.to_text() }).map_err(|e| e.to_string())
+            }
+            (GroupDrawFormat::Pairing, _, Some((pairing, pairs))) => {
+                self.file.write(&if csv { Pairing::to_csv(pairs) } else { pairing.to_text(pairs) }).map_err(|e| e.to_string())
+            }
+            _ => {
+                self.status = "Nothing to export yet".to_string();
+                return;
+            }
+        };
+        self.status = match exported {
+            Ok(done) => done,
+            Err(e) => format!("Export error: {}", e),
+        };
+    }
+}
+
+impl RandomGeneratorApp {
+    pub(crate) fn group_draw_view(&self) -> Element<'_, Message> {
+        let panel = &self.group_draw;
+        let dark_mode = self.settings.dark_mode;
+        let high_contrast = self.settings.high_contrast;
+        let label = |content: &'static str| text(self.tr(content)).size(14).width(Length::Fixed(80.0));
+        let hint = |content: &'static str| text(self.tr(content)).size(11).color(accessibility::dim_text_color(dark_mode, high_contrast));
+        let action = |content: &'static str, message: Message| {
+            button(text(self.tr(content)).size(14))
+                .on_press(message)
+                .padding(self.button_padding())
+                .style(move |_theme: &Theme, status| get_link_button_style(dark_mode, high_contrast, status))
+        };
+
+        let mut options = column![].spacing(6);
+        let mut output = row![].spacing(6).align_y(alignment::Vertical::Center);
+        let mut preview_lines: Vec<String> = Vec::new();
+        match panel.format {
+            GroupDrawFormat::SecretSanta => {
+                options = options
+                    .push(text(self.tr("Never draw each other")).size(14))
+                    .push(
+                        text_editor(&panel.exclusions)
+                            .on_action(Message::GroupDrawExclusionsEdited)
+                            .placeholder(self.tr("One pair per line, like Alice, Bob"))
+                            .height(Length::Fixed(60.0))
+                            .size(13),
+                    )
+                    .push(
+                        checkbox(self.tr("Show assignments"), panel.reveal)
+                            .on_toggle(Message::GroupDrawRevealToggled)
+                            .size(self.checkbox_size())
+                            .text_size(14)
+                            .style(move |_theme: &Theme, _status| get_checkbox_style(dark_mode, high_contrast)),
+                    );
+                output = output.push(label("Folder")).push(
+                    text_input("", &panel.folder)
+                        .on_input(Message::GroupDrawFolderChanged)
+                        .on_submit(Message::PanelExport(ExportPanel::GroupDraw))
+                        .width(Length::Fill)
+                        .size(14)
+                        .style(move |_theme: &Theme, status| get_text_input_style(dark_mode, high_contrast, status)),
+                );
+                preview_lines = panel
+                    .assignments
+                    .iter()
+                    .map(|assignment| {
+                        let receiver = if panel.reveal { assignment.receiver.as_str() } else { "?" };
+                        format!("{} → {}", assignment.giver, receiver)
+                    })
+                    .collect();
+            }
+            GroupDrawFormat::Bracket => {
+                options = options.push(hint("Names are placed at random; byes go to the first seeds so no match is bye against bye"));
+                if let Some(bracket) = &panel.bracket {
+                    preview_lines = bracket.to_text().lines().map(String::from).collect();
+                }
+            }
+            GroupDrawFormat::Pairing => {
+                options = options
+                    .push(text(self.tr("Hand out (one per line)")).size(14))
+                    .push(
+                        text_editor(&panel.items)
+                            .on_action(Message::GroupDrawItemsEdited)
+                            .placeholder("Paper 1\nPaper 2\nPaper 3")
+                            .height(Length::Fixed(120.0))
+                            .size(13),
+                    )
+                    .push(text(self.tr("Never assign")).size(14))
+                    .push(
+                        text_editor(&panel.forbidden)
+                            .on_action(Message::GroupDrawForbiddenEdited)
+                            .placeholder(self.tr("One per line, like Alice, Paper 3"))
+                            .height(Length::Fixed(60.0))
+                            .size(13),
+                    )
+                    .push(hint("Each item goes to one person; everyone gets the same number, give or take one"));
+                if let Some((pairing, pairs)) = &panel.pairing {
+                    preview_lines = pairing.to_text(pairs).lines().map(String::from).collect();
+                }
+            }
+        }
+        if panel.format.file_stem().is_some() {
+            output = self.export_fields(ExportPanel::GroupDraw, &panel.file, &FILE_FORMATS);
+        }
+        let preview = (!preview_lines.is_empty()).then(|| {
+            let lines = preview_lines.into_iter().map(|line| text(line).size(12).font(Font::MONOSPACE).into());
+            container(
+                scrollable(column(lines))
+                    .direction(scrollable::Direction::Both {
+                        vertical: scrollable::Scrollbar::default(),
+                        horizontal: scrollable::Scrollbar::default(),
+                    })
+                    .height(Length::Fixed(180.0)),
+            )
+                .padding(6)
+                .width(Length::Fill)
+        });
+
+        let content = column![
+            text(self.tr("Group draw"))
+                .size(20)
+                .color(if dark_mode { Color::from_rgb(0.9, 0.9, 0.9) } else { Color::BLACK }),
+            Space::with_height(Length::Fixed(10.0)),
+            row![
+                label("Format"),
+                pick_list(&GroupDrawFormat::ALL[..], Some(panel.format), Message::GroupDrawFormatChanged)
+                    .text_size(14)
+                    .style(move |_theme: &Theme, _status| get_pick_list_style(dark_mode, high_contrast)),
+                Space::with_width(Length::Fill),
+                action("Draw", Message::RunGroupDraw),
+            ]
+                .align_y(alignment::Vertical::Center),
+            text(self.tr(if panel.format == GroupDrawFormat::Pairing { "People (one per line)" } else { "Names (one per line)" })).size(14),
+            text_editor(&panel.names)
+                .on_action(Message::GroupDrawNamesEdited)
+                .placeholder("Alice\nBob\nCarol")
+                .height(Length::Fixed(120.0))
+                .size(13),
+            options,
+        ]
+            .push_maybe(preview)
+            .push(self.seed_row(ExportPanel::GroupDraw, &panel.seed))
+            .push(output.push(action("Export", Message::PanelExport(ExportPanel::GroupDraw))))
+            .push_maybe(
+                (panel.format == GroupDrawFormat::SecretSanta)
+                    .then(|| hint("Each person gets a file named after them that only says who they give to")),
+            )
+            .push(
+                row![
+                    text(&panel.status).size(13).color(accessibility::dim_text_color(dark_mode, high_contrast)),
+                    Space::with_width(Length::Fill),
+                    action("Close", Message::CloseGroupDraw),
+                ]
+                    .align_y(alignment::Vertical::Center),
+            )
+            .spacing(6)
+            .padding(24);
+
+        container(content)
+            .width(Length::Fixed(420.0))
+            .style(move |_theme: &Theme| {
+                iced::widget::container::Style {
+                    background: Some(Background::Color(if dark_mode { Color::from_rgb(0.2, 0.2, 0.25) } else { Color::WHITE })),
+                    border: Border {
+                        color: if dark_mode { Color::from_rgb(0.4, 0.4, 0.4) } else { Color::from_rgb(0.8, 0.8, 0.8) },
+                        width: 1.0,
+                        radius: 16.0.into(),
+                    },
+                    shadow: Shadow {
+                        color: Color::from_rgba(0.0, 0.0, 0.0, 0.3),
+                        offset: Vector::new(0.0, 4.0),
+                        blur_radius: 20.0,
+                    },
+                    ..Default::default()
+                }
+            })
+            .into()
+    }
+}
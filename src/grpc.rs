@@ -0,0 +1,293 @@
+//! gRPC mode: `random-tool grpc` serves `proto/random_generator.proto`.
+//!
+//! `Generate` takes the same settings as the HTTP server's `POST /generate` and
+//! draws through the same [`Server`], so draws are logged, counted and streamed
+//! to `/live` clients alike and a seed repeats a draw on either transport.
+//! `ListPresets` lists the saved settings of [`presets`](crate::presets) for
+//! clients to draw with. The shuffle bag, cooldown and exclusions are HTTP and
+//! daemon only.
+
+use std::io;
+use std::path::PathBuf;
+
+use random_generator_core::{ListDuplicates, ResultOrder, Stats};
+use serde_json::Value;
+use tonic::transport::server::TcpIncoming;
+use tonic::{Request, Response, Status};
+
+use crate::presets::Presets;
+use crate::server::{GenerateRequest, Server};
+
+include!(concat!(env!("OUT_DIR"), "/random_tool.v1.RandomGenerator.rs"));
+
+use random_generator_server::{RandomGenerator, RandomGeneratorServer};
+
+/// Address used when `--addr` isn't given; only reachable from this machine
+pub const DEFAULT_ADDR: &str = "127.0.0.1:50051";
+
+/// The messages of `proto/random_generator.proto`, tag for tag
+pub mod proto {
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, prost::Enumeration)]
+    #[repr(i32)]
+    pub enum ResultOrder {
+        Drawn = 0,
+        Sorted = 1,
+    }
+
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, prost::Enumeration)]
+    #[repr(i32)]
+    pub enum ListDuplicates {
+        Weighted = 0,
+        Dedupe = 1,
+        Reject = 2,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct GenerateRequest {
+        #[prost(int64, optional, tag = "1")]
+        pub from: Option<i64>,
+        #[prost(int64, optional, tag = "2")]
+        pub to: Option<i64>,
+        #[prost(uint64, optional, tag = "3")]
+        pub count: Option<u64>,
+        #[prost(bool, tag = "4")]
+        pub duplicates: bool,
+        #[prost(int64, repeated, tag = "5")]
+        pub list: Vec<i64>,
+        #[prost(enumeration = "ListDuplicates", tag = "6")]
+        pub repeats: i32,
+        #[prost(enumeration = "ResultOrder", tag = "7")]
+        pub order: i32,
+        #[prost(string, optional, tag = "8")]
+        pub seed: Option<String>,
+        #[prost(string, optional, tag = "9")]
+        pub transforms: Option<String>,
+        #[prost(uint64, optional, tag = "10")]
+        pub max_repeats: Option<u64>,
+        #[prost(uint64, optional, tag = "11")]
+        pub max_streak: Option<u64>,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct GenerateResponse {
+        #[prost(int64, repeated, tag = "1")]
+        pub numbers: Vec<i64>,
+        #[prost(string, tag = "2")]
+        pub seed: String,
+        #[prost(message, optional, tag = "3")]
+        pub stats: Option<Stats>,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct GetStatsRequest {
+        #[prost(int64, repeated, tag = "1")]
+        pub numbers: Vec<i64>,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct Stats {
+        #[prost(uint64, tag = "1")]
+        pub count: u64,
+        #[prost(int64, optional, tag = "2")]
+        pub min: Option<i64>,
+        #[prost(int64, optional, tag = "3")]
+        pub max: Option<i64>,
+        #[prost(int64, tag = "4")]
+        pub sum: i64,
+        #[prost(double, tag = "5")]
+        pub avg: f64,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct ListPresetsRequest {}
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct Preset {
+        #[prost(string, tag = "1")]
+        pub name: String,
+        #[prost(message, optional, tag = "2")]
+        pub request: Option<GenerateRequest>,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct ListPresetsResponse {
+        #[prost(message, repeated, tag = "1")]
+        pub presets: Vec<Preset>,
+    }
+}
+
+/// The service behind `random-tool grpc`
+#[derive(Debug, Clone)]
+struct DrawService {
+    server: Server,
+    /// `presets.json`; `None` when there is no config directory
+    presets: Option<PathBuf>,
+}
+
+#[tonic::async_trait]
+impl RandomGenerator for DrawService {
+    async fn generate(&self, request: Request<proto::GenerateRequest>) -> Result<Response<proto::GenerateResponse>, Status> {
+        let request = from_proto(request.into_inner()).map_err(Status::invalid_argument)?;
+        let server = self.server.clone();
+        // Up to a million numbers and a history write: too slow for the async threads
+        let answer = tokio::task::spawn_blocking(move || server.generate(request))
+            .await
+            .map_err(|e| Status::internal(e.to_string()))?
+            .map_err(Status::invalid_argument)?;
+        Ok(Response::new(response(&answer)))
+    }
+
+    async fn get_stats(&self, request: Request<proto::GetStatsRequest>) -> Result<Response<proto::Stats>, Status> {
+        let stats = stats(&request.into_inner().numbers).ok_or_else(|| Status::invalid_argument("the sum is out of the int64 range"))?;
+        Ok(Response::new(stats))
+    }
+
+    async fn list_presets(&self, _: Request<proto::ListPresetsRequest>) -> Result<Response<proto::ListPresetsResponse>, Status> {
+        let presets = match &self.presets {
+            Some(path) => Presets::load(path).map_err(Status::failed_precondition)?,
+            None => Presets::default(),
+        };
+        let presets = presets
+            .iter()
+            .map(|(name, request)| proto::Preset { name: name.to_string(), request: Some(to_proto(request)) })
+            .collect();
+        Ok(Response::new(proto::ListPresetsResponse { presets }))
+    }
+}
+
+/// Serve requests until the process is stopped
+pub fn serve(addr: &str) -> io::Result<()> {
+    let service = DrawService { server: Server::new(true), presets: Presets::path() };
+    tokio::runtime::Runtime::new()?.block_on(async {
+        let listener = tokio::net::TcpListener::bind(addr).await?;
+        eprintln!("listening for gRPC on {}", listener.local_addr()?);
+        serve_on(listener, service).await
+    })
+}
+
+async fn serve_on(listener: tokio::net::TcpListener, service: DrawService) -> io::Result<()> {
+    let incoming = TcpIncoming::from_listener(listener, true, None).map_err(io::Error::other)?;
+    tonic::transport::Server::builder()
+        .add_service(RandomGeneratorServer::new(service))
+        .serve_with_incoming(incoming)
+        .await
+        .map_err(io::Error::other)
+}
+
+/// The server's request for a gRPC one; unset fields keep the HTTP defaults
+fn from_proto(request: proto::GenerateRequest) -> Result<GenerateRequest, String> {
+    let size = |value: u64, name: &str| usize::try_from(value).map_err(|_| format!("{} is too large", name));
+    let defaults = GenerateRequest::default();
+    Ok(GenerateRequest {
+        from: request.from.unwrap_or(defaults.from),
+        to: request.to.unwrap_or(defaults.to),
+        count: request.count.map_or(Ok(defaults.count), |count| size(count, "count"))?,
+        duplicates: request.duplicates,
+        max_repeats: request.max_repeats.map(|max| size(max, "max_repeats")).transpose()?,
+        max_streak: request.max_streak.map(|max| size(max, "max_streak")).transpose()?,
+        list: (!request.list.is_empty()).then(|| request.list.clone()),
+        repeats: match request.repeats() {
+            proto::ListDuplicates::Weighted => ListDuplicates::Weighted,
+            proto::ListDuplicates::Dedupe => ListDuplicates::Dedupe,
+            proto::ListDuplicates::Reject => ListDuplicates::Reject,
+        },
+        order: match request.order() {
+            proto::ResultOrder::Drawn => ResultOrder::Drawn,
+            proto::ResultOrder::Sorted => ResultOrder::Sorted,
+        },
+        seed: request.seed.map(|seed| seed.parse().map_err(|_| format!("invalid seed '{}'", seed))).transpose()?,
+        transforms: request.transforms,
+        ..defaults
+    })
+}
+
+/// A preset as a gRPC request, every setting filled in
+fn to_proto(request: &GenerateRequest) -> proto::GenerateRequest {
+    let repeats = match request.repeats {
+        ListDuplicates::Weighted => proto::ListDuplicates::Weighted,
+        ListDuplicates::Dedupe => proto::ListDuplicates::Dedupe,
+        ListDuplicates::Reject => proto::ListDuplicates::Reject,
+    };
+    let order = match request.order {
+        ResultOrder::Drawn => proto::ResultOrder::Drawn,
+        ResultOrder::Sorted => proto::ResultOrder::Sorted,
+    };
+    proto::GenerateRequest {
+        from: Some(request.from),
+        to: Some(request.to),
+        count: Some(request.count as u64),
+        duplicates: request.duplicates,
+        list: request.list.clone().unwrap_or_default(),
+        repeats: repeats as i32,
+        order: order as i32,
+        seed: request.seed.map(|seed| seed.to_string()),
+        transforms: request.transforms.clone(),
+        max_repeats: request.max_repeats.map(|max| max as u64),
+        max_streak: request.max_streak.map(|max| max as u64),
+    }
+}
+
+/// The server's JSON answer as a gRPC response
+fn response(answer: &Value) -> proto::GenerateResponse {
+    let numbers: Vec<i64> = answer["numbers"].as_array().into_iter().flatten().filter_map(Value::as_i64).collect();
+    proto::GenerateResponse {
+        seed: answer["seed"].as_str().unwrap_or_default().to_string(),
+        stats: stats(&numbers),
+        numbers,
+    }
+}
+
+/// Summary of `numbers`; `None` when their sum doesn't fit in an int64
+fn stats(numbers: &[i64]) -> Option<proto::Stats> {
+    let stats = Stats::of(numbers);
+    Some(proto::Stats { count: stats.count as u64, min: stats.min, max: stats.max, sum: stats.sum?, avg: stats.avg })
+}
+
+#[cfg(test)]
+mod tests {
+    use tonic::transport::Channel;
+
+    use super::random_generator_client::RandomGeneratorClient;
+    use super::*;
+
+    #[tokio::test]
+    async fn test_generate_and_list_presets() {
+        let dir = std::env::temp_dir().join(format!("random-tool-grpc-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("presets.json");
+        std::fs::write(&path, r#"{"lotto": {"from": 1, "to": 49, "count": 6, "order": "Sorted"}, "coin": {"to": 1}}"#).unwrap();
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let service = DrawService { server: Server::new(false), presets: Some(path) };
+        tokio::spawn(serve_on(listener, service));
+        let channel = Channel::from_shared(format!("http://{}", addr)).unwrap().connect().await.unwrap();
+        let mut client = RandomGeneratorClient::new(channel);
+
+        let presets = client.list_presets(proto::ListPresetsRequest {}).await.unwrap().into_inner().presets;
+        assert_eq!(presets.iter().map(|preset| preset.name.as_str()).collect::<Vec<_>>(), ["coin", "lotto"]);
+        let lotto = presets[1].request.clone().unwrap();
+        assert_eq!((lotto.from, lotto.to, lotto.count, lotto.order()), (Some(1), Some(49), Some(6), proto::ResultOrder::Sorted));
+
+        let drawn = client.generate(lotto.clone()).await.unwrap().into_inner();
+        assert_eq!(drawn.numbers.len(), 6);
+        assert!(drawn.numbers.windows(2).all(|pair| pair[0] < pair[1] && (1..=49).contains(&pair[1])));
+        assert_eq!(drawn.stats.unwrap().sum, drawn.numbers.iter().sum::<i64>());
+        let again = client.generate(proto::GenerateRequest { seed: Some(drawn.seed.clone()), ..lotto }).await.unwrap().into_inner();
+        assert_eq!(again.numbers, drawn.numbers);
+
+        let invalid = proto::GenerateRequest { from: Some(5), to: Some(1), ..Default::default() };
+        assert_eq!(client.generate(invalid).await.unwrap_err().code(), tonic::Code::InvalidArgument);
+        let stats = client.get_stats(proto::GetStatsRequest { numbers: vec![3, 1, 2] }).await.unwrap().into_inner();
+        assert_eq!((stats.count, stats.min, stats.max, stats.sum), (3, Some(1), Some(3), 6));
+        let overflow = client.get_stats(proto::GetStatsRequest { numbers: vec![i64::MAX, 1] }).await.unwrap_err();
+        assert_eq!(overflow.code(), tonic::Code::InvalidArgument);
+        let drawn = proto::GenerateRequest { list: vec![i64::MAX], count: Some(2), duplicates: true, ..Default::default() };
+        let drawn = client.generate(drawn).await.unwrap().into_inner();
+        assert_eq!((drawn.numbers, drawn.stats), (vec![i64::MAX; 2], None));
+
+        std::fs::write(dir.join("presets.json"), r#"{"bag": {"bag": {"pool": [1], "left": []}}}"#).unwrap();
+        assert_eq!(client.list_presets(proto::ListPresetsRequest {}).await.unwrap_err().code(), tonic::Code::FailedPrecondition);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
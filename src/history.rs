@@ -0,0 +1,390 @@
+//! History of every draw with the seed that reproduces it.
+//!
+//! Each successful generation is stored as one row of `history.db`, an
+//! SQLite database in the user data directory: the time, the mode, the
+//! settings as tab-separated `key=value` fields, the seed and the numbers.
+//! Feeding a row's settings and seed back in (`random-tool range --seed ...`,
+//! or Replay on the result tab) draws exactly the same numbers, so a draw can
+//! be shown to have been fair after the fact. Draws from the panels, such as
+//! random weights or a group draw, are stored with the panel's title as the
+//! mode, its settings and seed and no numbers; opening one opens the panel
+//! with the seed typed in. Old draws are pruned after each new one as set in
+//! the settings.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use chrono::{Local, TimeZone};
+use iced::futures::channel::oneshot;
+use random_generator_core::{GeneratorConfig, GeneratorSource, Seed};
+use rusqlite::{params, Connection, OptionalExtension};
+
+/// Schema version kept in `PRAGMA user_version`
+const SCHEMA_VERSION: i64 = 1;
+/// How times are shown everywhere, in local time: the history panel and the session report
+const TIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+/// How long a write waits for another window or the server to finish theirs
+const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How much history is kept; `None` keeps everything
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Retention {
+    /// Drop draws older than this many days
+    pub days: Option<u32>,
+    /// Keep only this many of the latest draws
+    pub draws: Option<u32>,
+}
+
+/// One stored draw
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    /// Seconds since the Unix epoch
+    pub time: i64,
+    /// Local date and time, e.g. `2026-10-17 14:05:09`
+    pub when: String,
+    pub mode: String,
+    /// Tab-separated `key=value` fields, see [`settings`]
+    pub settings: String,
+    pub seed: Option<String>,
+    pub numbers: Vec<i64>,
+}
+
+/// Which draws a search returns
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Filter {
+    /// A number matches draws that drew it; any text matches the mode, settings and seed
+    pub text: String,
+    /// Only draws from this time on, in seconds since the Unix epoch
+    pub since: Option<i64>,
+}
+
+/// Open database of past draws
+#[derive(Debug)]
+pub struct History {
+    conn: Connection,
+}
+
+impl History {
+    /// Open the database in the user data directory, creating it on first use
+    pub fn open() -> io::Result<History> {
+        let path = path().ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no data directory"))?;
+        History::open_at(&path)
+    }
+
+    /// Open the database at `path`, creating it if needed
+    pub fn open_at(path: &Path) -> io::Result<History> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let conn = Connection::open(path).map_err(io::Error::other)?;
+        conn.busy_timeout(BUSY_TIMEOUT).map_err(io::Error::other)?;
+        let history = History { conn };
+        let version: i64 = history.conn.query_row("PRAGMA user_version", [], |row| row.get(0)).map_err(io::Error::other)?;
+        if version < SCHEMA_VERSION {
+            history.create()?;
+        }
+        Ok(history)
+    }
+
+    fn create(&self) -> io::Result<()> {
+        self.conn
+            .execute_batch(&format!(
+                "BEGIN;
+                CREATE TABLE IF NOT EXISTS draws (
+                    id INTEGER PRIMARY KEY,
+                    time INTEGER NOT NULL,
+                    mode TEXT NOT NULL,
+                    settings TEXT NOT NULL,
+                    seed TEXT,
+                    numbers TEXT NOT NULL
+                );
+                CREATE INDEX IF NOT EXISTS draws_time ON draws (time);
+                PRAGMA user_version = {};
+                COMMIT;",
+                SCHEMA_VERSION
+            ))
+            .map_err(io::Error::other)
+    }
+
+    /// Store a draw made at `time`; `source` is the one it drew from when it had parameters of its own
+    pub fn insert(&self, time: i64, config: &GeneratorConfig, source: Option<&dyn GeneratorSource<i64>>, numbers: &[i64]) -> io::Result<()> {
+        self.insert_row(time, &config.mode.to_string(), &settings(config, source), config.seed, &join(numbers))
+    }
+
+    /// Store a draw a panel made at `time`; its results aren't numbers, so
+    /// only the settings and seed that draw them again are kept
+    pub fn insert_panel(&self, time: i64, panel: &str, settings: &str, seed: Seed) -> io::Result<()> {
+        self.insert_row(time, panel, settings, Some(seed), "")
+    }
+
+    fn insert_row(&self, time: i64, mode: &str, settings: &str, seed: Option<Seed>, numbers: &str) -> io::Result<()> {
+        self.conn
+            .execute(
+                "INSERT INTO draws (time, mode, settings, seed, numbers) VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![time, mode, settings, seed.map(|seed| seed.to_string()), numbers],
+            )
+            .map(|_| ())
+            .map_err(io::Error::other)
+    }
+
+    /// Latest draws matching `filter`, newest first, at most `limit` of them
+    pub fn search(&self, filter: &Filter, limit: usize) -> io::Result<Vec<Entry>> {
+        let text = filter.text.trim();
+        let pattern = format!("%{}%", escape_like(text));
+        // Numbers are matched whole, so 7 doesn't find 17
+        let number = format!("%,{},%", escape_like(text));
+        let mut statement = self
+            .conn
+            .prepare(
+                "SELECT time, mode, settings, seed, numbers
+                FROM draws
+                WHERE time >= ?1
+                    AND (?2 = '' OR mode LIKE ?3 ESCAPE '\\' OR settings LIKE ?3 ESCAPE '\\' OR seed LIKE ?3 ESCAPE '\\'
+                        OR ',' || numbers || ',' LIKE ?4 ESCAPE '\\')
+                ORDER BY time DESC, id DESC
+                LIMIT ?5",
+            )
+            .map_err(io::Error::other)?;
+        let rows = statement
+            .query_map(
+                params![filter.since.unwrap_or(i64::MIN), text, pattern, number, i64::try_from(limit).unwrap_or(i64::MAX)],
+                |row| {
+                    let time = row.get(0)?;
+                    let numbers: String = row.get(4)?;
+                    Ok(Entry {
+                        time,
+                        when: local_time(time),
+                        mode: row.get(1)?,
+                        settings: row.get(2)?,
+                        seed: row.get(3)?,
+                        numbers: numbers.split(',').filter_map(|number| number.parse().ok()).collect(),
+                    })
+                },
+            )
+            .map_err(io::Error::other)?;
+        rows.collect::<Result<_, _>>().map_err(io::Error::other)
+    }
+
+    /// Drop the draws `keep` no longer covers as of `now`, returning how many went
+    pub fn prune(&self, keep: Retention, now: i64) -> io::Result<usize> {
+        let mut removed = 0;
+        if let Some(days) = keep.days {
+            let cutoff = now.saturating_sub(i64::from(days) * 24 * 60 * 60);
+            removed += self.conn.execute("DELETE FROM draws WHERE time < ?1", [cutoff]).map_err(io::Error::other)?;
+        }
+        if let Some(draws) = keep.draws {
+            // The id of the oldest draw still kept, if there are more than that many
+            let oldest: Option<(i64, i64)> = self
+                .conn
+                .query_row(
+                    "SELECT time, id FROM draws ORDER BY time DESC, id DESC LIMIT 1 OFFSET ?1",
+                    [i64::from(draws)],
+                    |row| Ok((row.get(0)?, row.get(1)?)),
+                )
+                .optional()
+                .map_err(io::Error::other)?;
+            if let Some((time, id)) = oldest {
+                removed += self
+                    .conn
+                    .execute("DELETE FROM draws WHERE time < ?1 OR (time = ?1 AND id <= ?2)", [time, id])
+                    .map_err(io::Error::other)?;
+            }
+        }
+        Ok(removed)
+    }
+}
+
+/// Store a finished draw, then prune what `keep` no longer covers
+pub fn record(config: &GeneratorConfig, source: Option<&dyn GeneratorSource<i64>>, numbers: &[i64], keep: Retention) -> io::Result<()> {
+    let history = History::open()?;
+    let time = now();
+    history.insert(time, config, source, numbers)?;
+    history.prune(keep, time).map(|_| ())
+}
+
+/// Store a draw made at `time` on a worker thread, then prune what `keep` no longer covers
+///
+/// The window records its draws this way: writing ten million numbers and pruning
+/// would hold it up. `settings` are the draw's [`settings`].
+pub async fn record_in_background(
+    time: i64,
+    mode: String,
+    settings: String,
+    seed: Option<Seed>,
+    numbers: Arc<[i64]>,
+    keep: Retention,
+) -> Result<(), String> {
+    let (sender, receiver) = oneshot::channel();
+    thread::spawn(move || {
+        let result = History::open().and_then(|history| {
+            history.insert_row(time, &mode, &settings, seed, &join(&numbers))?;
+            history.prune(keep, time).map(|_| ())
+        });
+        let _ = sender.send(result.map_err(|e| format!("could not record the draw: {}", e)));
+    });
+    receiver.await.unwrap_or_else(|_| Err("recording stopped unexpectedly".to_string()))
+}
+
+/// Store a finished panel draw, then prune what `keep` no longer covers
+pub fn record_panel(panel: &str, settings: &str, seed: Seed, keep: Retention) -> io::Result<()> {
+    let history = History::open()?;
+    let time = now();
+    history.insert_panel(time, panel, settings, seed)?;
+    history.prune(keep, time).map(|_| ())
+}
+
+/// Current time in seconds since the Unix epoch
+pub fn now() -> i64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |elapsed| i64::try_from(elapsed.as_secs()).unwrap_or(i64::MAX))
+}
+
+/// `time`, in seconds since the Unix epoch, as a local date and time like the history shows
+pub fn local_time(time: i64) -> String {
+    match Local.timestamp_opt(time, 0).earliest() {
+        Some(local) => local.format(TIME_FORMAT).to_string(),
+        None => time.to_string(),
+    }
+}
+
+fn path() -> Option<PathBuf> {
+    dirs::data_dir().map(|dir| dir.join("random-tool").join("history.db"))
+}
+
+fn join(values: &[i64]) -> String {
+    values.iter().map(|value| value.to_string()).collect::<Vec<_>>().join(",")
+}
+
+/// Escape `%`, `_` and the escape character itself for a `LIKE ... ESCAPE '\'` pattern
+fn escape_like(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_")
+}
+
+/// Settings of a draw as tab-separated fields; the list is only written when the
+/// draw used it, and the parameters of `source` follow the config's fields
+pub fn settings(config: &GeneratorConfig, source: Option<&dyn GeneratorSource<i64>>) -> String {
+    let mut fields = vec![
+        format!("from={}", config.lower_bound),
+        format!("to={}", config.upper_bound),
+        format!("count={}", config.num_to_generate),
+        format!("duplicates={}", config.allow_duplicates),
+        format!("order={}", config.order),
+    ];
+    if let Some(max) = config.max_repeats.filter(|_| config.allow_duplicates) {
+        fields.push(format!("max_repeats={}", max));
+    }
+    if let Some(max) = config.max_streak.filter(|_| config.allow_duplicates) {
+        fields.push(format!("max_streak={}", max));
+    }
+    if !config.custom_list.is_empty() {
+        fields.push(format!("list={}", join(&config.custom_list)));
+        fields.push(format!("repeats={}", config.list_duplicates));
+    }
+    // Each on one line, like the script with its line breaks written as \n
+    for (name, value) in source.map(|source| source.parameters()).unwrap_or_default() {
+        fields.push(format!("{}={}", name, value));
+    }
+    if !config.transforms.is_empty() {
+        let steps: Vec<String> = config.transforms.iter().map(ToString::to_string).collect();
+        fields.push(format!("transforms={}", steps.join(", ")));
+    }
+    fields.join("\t")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use random_generator_core::{GeneratorMode, ScriptSource, Transform};
+
+    /// Fresh database in the temp directory
+    fn temp_history(name: &str) -> (History, PathBuf) {
+        let path = std::env::temp_dir().join(format!("random-tool-history-{}-{}.db", name, std::process::id()));
+        let _ = fs::remove_file(&path);
+        (History::open_at(&path).unwrap(), path)
+    }
+
+    #[test]
+    fn test_settings_record_the_draw() {
+        let config = GeneratorConfig { lower_bound: 1, upper_bound: 6, num_to_generate: 2, ..Default::default() };
+        let line = settings(&config, None);
+        let fields: Vec<&str> = line.split('\t').collect();
+        assert!(fields.contains(&"from=1") && fields.contains(&"to=6") && fields.contains(&"count=2"));
+        assert!(!line.contains("list=") && !line.contains("transforms="));
+
+        let config = GeneratorConfig {
+            mode: GeneratorMode::SCRIPT,
+            transforms: vec![Transform::Scale(10), Transform::Abs],
+            ..Default::default()
+        };
+        let script = ScriptSource { script: "let d = roll(2, 6);\nd.sum() + 3\n".to_string() };
+        let line = settings(&config, Some(&script));
+        assert!(!line.contains('\n'));
+        assert!(line.split('\t').any(|field| field == "script=let d = roll(2, 6);\\nd.sum() + 3"));
+        assert!(line.split('\t').any(|field| field == "transforms=scale 10, abs"));
+    }
+
+    #[test]
+    fn test_search_draws() {
+        let (history, path) = temp_history("search");
+        let seeded = GeneratorConfig { seed: Some(Seed::from([0xab; 32])), ..Default::default() };
+        history.insert(1_700_000_000, &seeded, None, &[4, 17]).unwrap();
+        let list = GeneratorConfig { mode: GeneratorMode::CUSTOM_LIST, custom_list: vec![7, 70], ..Default::default() };
+        history.insert(1_700_086_400, &list, None, &[70]).unwrap();
+
+        let all = history.search(&Filter::default(), 10).unwrap();
+        assert_eq!(all.len(), 2);
+        assert_eq!(all[0].numbers, [70]);
+        assert_eq!(all[1].seed, Some("ab".repeat(32)));
+
+        // A number finds the draws that drew it, not ones that merely contain its digits
+        let search = |text: &str, since| history.search(&Filter { text: text.to_string(), since }, 10).unwrap();
+        assert_eq!(search("7", None).len(), 1);
+        assert_eq!(search("17", None)[0].numbers, [4, 17]);
+        assert_eq!(search("range", None).len(), 1);
+        assert_eq!(search("abab", None).len(), 1);
+        assert!(search("100%", None).is_empty());
+        assert_eq!(search("", Some(1_700_000_001))[0].numbers, [70]);
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_panel_draws() {
+        let (history, path) = temp_history("panel");
+        history.insert_panel(1_700_000_000, "Random weights", "components=3\tcount=10", Seed::from([0xcd; 32])).unwrap();
+        let found = history.search(&Filter { text: "weights".to_string(), since: None }, 10).unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].mode, "Random weights");
+        assert_eq!(found[0].seed, Some("cd".repeat(32)));
+        assert!(found[0].numbers.is_empty());
+        assert_eq!(found[0].when, local_time(1_700_000_000));
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_local_time() {
+        let shown = local_time(1_700_000_000);
+        // The hour depends on the time zone; the shape doesn't
+        assert_eq!(shown.len(), "2023-11-14 22:13:20".len());
+        assert!(shown.starts_with("2023-11-1") && shown.ends_with(":20"));
+    }
+
+    #[test]
+    fn test_prune_by_age_and_count() {
+        let (history, path) = temp_history("prune");
+        let day = 24 * 60 * 60;
+        for (time, number) in [(0, 1), (10 * day, 2), (20 * day, 3), (20 * day, 4)] {
+            history.insert(time, &GeneratorConfig::default(), None, &[number]).unwrap();
+        }
+        assert_eq!(history.prune(Retention::default(), 30 * day).unwrap(), 0);
+        assert_eq!(history.prune(Retention { days: Some(25), ..Default::default() }, 30 * day).unwrap(), 1);
+        assert_eq!(history.prune(Retention { draws: Some(2), ..Default::default() }, 30 * day).unwrap(), 1);
+        let kept: Vec<i64> = history.search(&Filter::default(), 10).unwrap().iter().flat_map(|entry| entry.numbers.clone()).collect();
+        assert_eq!(kept, [4, 3]);
+        assert_eq!(history.prune(Retention { draws: Some(0), ..Default::default() }, 30 * day).unwrap(), 2);
+        assert!(history.search(&Filter::default(), 10).unwrap().is_empty());
+        fs::remove_file(path).unwrap();
+    }
+}
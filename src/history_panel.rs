@@ -0,0 +1,256 @@
+//! Panel searching the history of past draws.
+//!
+//! Lists the latest draws stored by [`history`](crate::history), newest
+//! first, narrowed by a search and a time period while typing. A number
+//! finds the draws that drew it; other text is looked up in the mode,
+//! settings and seed. Any draw can be opened again as a result tab, or a
+//! panel's draw in its panel with the seed typed in.
+
+use std::fmt;
+use std::path::PathBuf;
+
+use iced::widget::{button, column, container, pick_list, row, scrollable, text, text_input, Space};
+use iced::{alignment, Background, Border, Color, Element, Font, Length, Shadow, Theme, Vector};
+
+use crate::export_panel::ExportPanel;
+use crate::history::{self, Entry, Filter, History};
+use crate::{accessibility, get_link_button_style, get_pick_list_style, get_text_input_style, Message, RandomGeneratorApp};
+
+/// Most draws listed at once
+const MAX_SHOWN: usize = 200;
+/// Numbers shown per draw before the rest are cut off
+const PREVIEW_NUMBERS: usize = 20;
+
+/// How far back the panel looks
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Period {
+    #[default]
+    AllTime,
+    Day,
+    Week,
+    Month,
+}
+
+impl Period {
+    pub const ALL: [Period; 4] = [Period::AllTime, Period::Day, Period::Week, Period::Month];
+
+    fn seconds(self) -> Option<i64> {
+        const DAY: i64 = 24 * 60 * 60;
+        match self {
+            Period::AllTime => None,
+            Period::Day => Some(DAY),
+            Period::Week => Some(7 * DAY),
+            Period::Month => Some(30 * DAY),
+        }
+    }
+}
+
+impl fmt::Display for Period {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Period::AllTime => write!(f, "All time"),
+            Period::Day => write!(f, "Last 24 hours"),
+            Period::Week => write!(f, "Last 7 days"),
+            Period::Month => write!(f, "Last 30 days"),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct HistoryPanel {
+    search: String,
+    period: Period,
+    entries: Vec<Entry>,
+    status: String,
+    /// Database to search, the one in the user data directory when `None`
+    path: Option<PathBuf>,
+}
+
+impl HistoryPanel {
+    pub fn set_search(&mut self, search: String) {
+        self.search = search;
+        self.refresh();
+    }
+
+    pub fn set_period(&mut self, period: Period) {
+        self.period = period;
+        self.refresh();
+    }
+
+    /// Search the database again, so draws made since show up
+    pub fn refresh(&mut self) {
+        let filter = Filter {
+            text: self.search.clone(),
+            since: self.period.seconds().map(|seconds| history::now() - seconds),
+        };
+        let history = match &self.path {
+            Some(path) => History::open_at(path),
+            None => History::open(),
+        };
+        match history.and_then(|history| history.search(&filter, MAX_SHOWN)) {
+            Ok(entries) => {
+                self.status = match entries.len() {
+                    0 => "No draws found".to_string(),
+                    1 => "1 draw".to_string(),
+                    MAX_SHOWN => format!("Showing the latest {} draws", MAX_SHOWN),
+                    shown => format!("{} draws", shown),
+                };
+                self.entries = entries;
+            }
+            Err(e) => {
+                self.entries.clear();
+                self.status = format!("Could not read the history: {}", e);
+            }
+        }
+    }
+}
+
+impl RandomGeneratorApp {
+    /// Show a past draw as a result tab; its config isn't kept whole, so there's nothing to replay.
+    /// A panel's draw opens the panel instead, with the seed that draws it again
+    pub(crate) fn open_history_entry(&mut self, index: usize) {
+        let Some(entry) = self.history.entries.get(index) else {
+            return;
+        };
+        if let Some(panel) = ExportPanel::ALL.into_iter().find(|panel| panel.to_string() == entry.mode) {
+            let seed = entry.seed.clone().unwrap_or_default();
+            self.show_panel(panel, &seed);
+            self.history_open = false;
+            return;
+        }
+        let numbers = entry.numbers.clone();
+        self.open_tab(numbers, None);
+        self.revealed_count = self.generator.get_numbers().len();
+        self.history_open = false;
+    }
+
+    pub(crate) fn history_view(&self) -> Element<'_, Message> {
+        let panel = &self.history;
+        let dark_mode = self.settings.dark_mode;
+        let high_contrast = self.settings.high_contrast;
+        let dim = accessibility::dim_text_color(dark_mode, high_contrast);
+        let action = |content: &'static str, message: Message| {
+            button(text(self.tr(content)).size(14))
+                .on_press(message)
+                .padding(self.button_padding())
+                .style(move |_theme: &Theme, status| get_link_button_style(dark_mode, high_contrast, status))
+        };
+
+        let entries = panel.entries.iter().enumerate().map(|(index, entry)| {
+            let mut numbers: Vec<String> = entry.numbers.iter().take(PREVIEW_NUMBERS).map(|&num| self.number_format.format(num)).collect();
+            if entry.numbers.len() > PREVIEW_NUMBERS {
+                numbers.push(format!("… ({})", entry.numbers.len()));
+            }
+            let mut details = entry.settings.replace('\t', "  ");
+            if let Some(seed) = &entry.seed {
+                details.push_str(&format!("  seed={}", seed));
+            }
+            column![
+                row![
+                    text(&entry.when).size(13).font(Font::MONOSPACE).color(dim),
+                    text(&entry.mode).size(13),
+                    Space::with_width(Length::Fill),
+                    action("Open", Message::OpenHistoryEntry(index)),
+                ]
+                    .spacing(8)
+                    .align_y(alignment::Vertical::Center),
+                text(numbers.join(", ")).size(14).font(Font::MONOSPACE),
+                text(details).size(11).color(dim),
+            ]
+                .spacing(2)
+                .padding([4, 0])
+                .into()
+        });
+
+        let content = column![
+            text(self.tr("History"))
+                .size(20)
+                .color(if dark_mode { Color::from_rgb(0.9, 0.9, 0.9) } else { Color::BLACK }),
+            Space::with_height(Length::Fixed(10.0)),
+            row![
+                text_input(self.tr("Search numbers, modes or seeds"), &panel.search)
+                    .on_input(Message::HistorySearchChanged)
+                    .width(Length::Fill)
+                    .size(14)
+                    .style(move |_theme: &Theme, status| get_text_input_style(dark_mode, high_contrast, status)),
+                pick_list(&Period::ALL[..], Some(panel.period), Message::HistoryPeriodChanged)
+                    .text_size(14)
+                    .style(move |_theme: &Theme, _status| get_pick_list_style(dark_mode, high_contrast)),
+            ]
+                .spacing(6)
+                .align_y(alignment::Vertical::Center),
+            container(scrollable(column(entries)).height(Length::Fixed(320.0)))
+                .padding(6)
+                .width(Length::Fill),
+            text(self.tr("Older draws are pruned as set under Keep history in the settings"))
+                .size(11)
+                .color(dim),
+            row![
+                text(&panel.status).size(13).color(dim),
+                Space::with_width(Length::Fill),
+                action("Close", Message::CloseHistory),
+            ]
+                .spacing(6)
+                .align_y(alignment::Vertical::Center),
+        ]
+            .spacing(6)
+            .padding(24);
+
+        container(content)
+            .width(Length::Fixed(560.0))
+            .style(move |_theme: &Theme| {
+                iced::widget::container::Style {
+                    background: Some(Background::Color(if dark_mode { Color::from_rgb(0.2, 0.2, 0.25) } else { Color::WHITE })),
+                    border: Border {
+                        color: if dark_mode { Color::from_rgb(0.4, 0.4, 0.4) } else { Color::from_rgb(0.8, 0.8, 0.8) },
+                        width: 1.0,
+                        radius: 16.0.into(),
+                    },
+                    shadow: Shadow {
+                        color: Color::from_rgba(0.0, 0.0, 0.0, 0.3),
+                        offset: Vector::new(0.0, 4.0),
+                        blur_radius: 20.0,
+                    },
+                    ..Default::default()
+                }
+            })
+            .into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use random_generator_core::{GeneratorConfig, GeneratorMode};
+
+    #[test]
+    fn test_search_history_and_open_a_draw() {
+        let path = std::env::temp_dir().join(format!("random-tool-history-panel-{}.db", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let history = History::open_at(&path).unwrap();
+        let now = history::now();
+        history.insert(now - 40 * 24 * 60 * 60, &GeneratorConfig::default(), None, &[3, 12]).unwrap();
+        let tickets = GeneratorConfig { mode: GeneratorMode::TICKETS, lower_bound: 1, upper_bound: 50, ..Default::default() };
+        history.insert(now, &tickets, None, &[12, 40]).unwrap();
+
+        let mut app = RandomGeneratorApp::default();
+        app.history.path = Some(path.clone());
+        let _ = app.update(Message::ShowHistory);
+        assert!(app.history_open);
+        assert_eq!(app.history.status, "2 draws");
+        assert_eq!(app.history.entries[0].mode, "Tickets");
+
+        let _ = app.update(Message::HistorySearchChanged("3".to_string()));
+        assert_eq!(app.history.entries.len(), 1);
+        assert_eq!(app.history.entries[0].numbers, [3, 12]);
+        let _ = app.update(Message::HistoryPeriodChanged(Period::Month));
+        assert_eq!(app.history.status, "No draws found");
+
+        let _ = app.update(Message::HistorySearchChanged("12".to_string()));
+        let _ = app.update(Message::OpenHistoryEntry(0));
+        assert_eq!(app.generator.get_numbers(), &[12, 40]);
+        assert!(app.tabs[0].draw.is_none());
+        assert!(!app.history_open);
+        std::fs::remove_file(path).unwrap();
+    }
+}
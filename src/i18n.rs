@@ -0,0 +1,379 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// Language of the interface labels
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Language {
+    #[default]
+    English,
+    Chinese,
+}
+
+impl Language {
+    pub const ALL: [Language; 2] = [Language::English, Language::Chinese];
+
+    /// Translate an English label; labels without a translation stay in English
+    pub fn tr(self, text: &'static str) -> &'static str {
+        match self {
+            Language::English => text,
+            Language::Chinese => chinese(text).unwrap_or(text),
+        }
+    }
+}
+
+// Languages are listed by their own name so they can be found in either language
+impl fmt::Display for Language {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Language::English => write!(f, "English"),
+            Language::Chinese => write!(f, "中文"),
+        }
+    }
+}
+
+/// Replace each `{}` in a translated template with the next argument
+pub fn fill(template: &str, args: &[&dyn fmt::Display]) -> String {
+    let mut result = String::with_capacity(template.len());
+    let mut args = args.iter();
+    let mut parts = template.split("{}");
+    if let Some(first) = parts.next() {
+        result.push_str(first);
+    }
+    for part in parts {
+        if let Some(arg) = args.next() {
+            result.push_str(&arg.to_string());
+        }
+        result.push_str(part);
+    }
+    result
+}
+
+fn chinese(text: &str) -> Option<&'static str> {
+    Some(match text {
+        "Random Generator" => "随机数生成器",
+        "Light" => "浅色",
+        "Dark" => "深色",
+        "Mode:" => "模式：",
+        "From" => "从",
+        "To" => "到",
+        "Count" => "数量",
+        "Total" => "总和",
+        "Parts" => "份数",
+        "Chance of success" => "成功概率",
+        "Successes" => "成功次数",
+        "Each value is how many tries it took to get the first success" => "每个值是第一次成功时共试了几次",
+        "Each value is how many tries it took to get that many successes" => "每个值是达到这么多次成功时共试了几次",
+        "Max" => "最大",
+        "Numbers (comma/space separated):" => "数字（逗号或空格分隔）：",
+        "e.g. 1, 2, 3, 4, 5" => "例如 1, 2, 3, 4, 5",
+        "Separator:" => "分隔符：",
+        "Zero-pad:" => "补零：",
+        "Number results" => "显示序号",
+        "Allow duplicates" => "允许重复",
+        "At most per value:" => "每个值最多：",
+        "No limit" => "不限",
+        "In a row:" => "最多连续：",
+        "Shuffle bag" => "洗牌袋",
+        "Refill" => "装回",
+        "The bag is full" => "袋子是满的",
+        "{} of {} left in the bag" => "袋中还剩 {} / {} 个",
+        "Cooldown (draws):" => "冷却（次）：",
+        "Transform:" => "变换：",
+        "e.g. scale 10, offset 5" => "例如 scale 10、offset 5",
+        "Sitting out: {}" => "冷却中：{}",
+        "Pool: {} values" => "可抽取 {} 个值",
+        "at most {} per draw" => "每次最多 {} 个",
+        "chance of a repeat: {}" => "出现重复的概率 {}",
+        "Draw every" => "自动抽取，每隔",
+        "Next draw in {}" => "距下次抽取 {}",
+        "{} drawn so far" => "已抽取 {} 次",
+        "Start drawing" => "开始",
+        "Stop" => "停止",
+        "Automatic draws stopped: {}" => "自动抽取已停止：{}",
+        "Countdown (s)" => "倒计时（秒）",
+        "off" => "关闭",
+        "Keep history" => "保留记录",
+        "all" => "全部",
+        "days" => "天",
+        "draws" => "条",
+        "Inputs locked, drawing in" => "输入已锁定，即将抽取",
+        "Mode: {}" => "模式：{}",
+        "From {} to {}" => "从 {} 到 {}",
+        "{} numbers in the list" => "列表中有 {} 个数字",
+        "Drawing {}" => "抽取 {} 个",
+        "Duplicates allowed" => "允许重复",
+        "Exclude earlier results" => "排除之前的结果",
+        "Show results as labels" => "结果显示为名字",
+        "e.g. 1: red team" => "例如 1: 红队",
+        "One per line, a number or a range like 3..5, a ':' and the label; other values stay numbers" => "每行一项：数字或 3..5 这样的范围、冒号和名字；其他值仍显示为数字",
+        "{} excluded" => "已排除 {} 个",
+        "Reset" => "重置",
+        "Count slider" => "数量滑块",
+        "Generate" => "生成",
+        "Clear" => "清除",
+        "Save" => "保存",
+        "File:" => "文件：",
+        "Revealed {} of {}, {} remaining" => "已揭晓 {}/{}，剩余 {} 个",
+        "Next" => "下一个",
+        "Filter:" => "筛选：",
+        "e.g. 42 or 10..20" => "例如 42 或 10..20",
+        "Only matches" => "仅显示匹配",
+        "Generating... {}%" => "生成中… {}%",
+        "Cancel" => "取消",
+        "Click Next to reveal the first result" => "点击“下一个”揭晓第一个结果",
+        "Click Generate to start" => "点击“生成”开始",
+        "Enter numbers and click Generate" => "输入数字后点击“生成”",
+        "Total: {}" => "共 {} 个",
+        "Total: {}, matches: {}" => "共 {} 个，匹配 {} 个",
+        "Copy" => "复制",
+        "About" => "关于",
+        "Present" => "演示",
+        "New Window" => "新窗口",
+        "Settings" => "设置",
+        "License: MPL-2.0" => "许可证：MPL-2.0",
+        "Built with Rust" => "使用 Rust 构建",
+        "Powered by Iced" => "基于 Iced",
+        "Close" => "关闭",
+        "No results yet" => "暂无结果",
+        "Space: next    Esc: exit" => "空格：下一个    Esc：退出",
+        "Esc: exit" => "Esc：退出",
+        "Exit" => "退出",
+        "Dark theme" => "深色主题",
+        "High contrast" => "高对比度",
+        "Language" => "语言",
+        "Random source" => "随机数来源",
+        "Result order" => "结果顺序",
+        "Repeated entries" => "重复条目",
+        "Benchmarking..." => "正在测速...",
+        "Replay" => "重放",
+        "Seed" => "种子",
+        "Random" => "随机",
+        "Drawn with seed {}" => "所用种子 {}",
+        "Sort ↑" => "升序",
+        "Sort ↓" => "降序",
+        "Shuffle" => "打乱",
+        "Reverse" => "倒序",
+        "Remove duplicates" => "去除重复",
+        "Use as pool" => "作为抽取范围",
+        "Paste list" => "粘贴列表",
+        "valid item parsed" => "项已识别",
+        "valid items parsed" => "项已识别",
+        "not a number (in red)" => "项不是数字（标红）",
+        "not numbers (in red)" => "项不是数字（标红）",
+        "skipped" => "项已跳过",
+        "Stop on list items that aren't numbers" => "列表中有非数字的项时报错",
+        "Compare" => "比较",
+        "Open…" => "打开…",
+        "Exclude current results" => "排除当前结果",
+        "Merge files" => "合并文件",
+        "Merge" => "合并",
+        "One result file per line" => "每行一个结果文件",
+        ".csv and .json files are read as those formats, others as text with one number per line" => ".csv 和 .json 文件按各自的格式读取，其余按每行一个数字的文本读取",
+        "Open as tab" => "在标签页中打开",
+        "History" => "历史记录",
+        "Search numbers, modes or seeds" => "搜索数字、模式或种子",
+        "Older draws are pruned as set under Keep history in the settings" => "更早的记录按设置中的“保留记录”自动删除",
+        "Open" => "打开",
+        "Export session report" => "导出本次抽取报告",
+        "Draw from link?" => "按链接抽取？",
+        "A link asks to draw right away:" => "链接要求立即抽取：",
+        "{} numbers from {} to {}" => "{} 个数字，从 {} 到 {}",
+        "{} numbers from the list" => "从列表中抽取 {} 个数字",
+        "{}, duplicates allowed" => "{}，允许重复",
+        "The inputs are filled in; check them before drawing." => "已填好输入，抽取前请检查。",
+        "Compare results" => "比较结果",
+        "Compare with" => "比较对象",
+        "In both" => "两组都有",
+        "Only in" => "仅在",
+        "Numbers separated by commas, spaces or new lines" => "数字之间用逗号、空格或换行分隔",
+        "random.org key" => "random.org 密钥",
+        "random.org draws" => "由 random.org 抽取",
+        "Range and custom list draws come from random.org; other modes, and draws while it's offline or out of quota, are drawn locally. random.org draws can't be replayed" => "区间和自定义列表的抽取由 random.org 完成；其他模式以及离线或配额用完时在本地抽取。random.org 的抽取不能重放",
+        "Quota left:" => "剩余配额：",
+        "Draw with the daemon" => "通过守护进程抽取",
+        "MQTT broker" => "MQTT 服务器",
+        "MQTT topic" => "MQTT 主题",
+        "OSC target" => "OSC 目标",
+        "OSC address" => "OSC 地址",
+        "Test data" => "测试数据",
+        "Locale" => "地区",
+        "Rows" => "行数",
+        "File" => "文件",
+        "Full name" => "姓名",
+        "First name" => "名",
+        "Last name" => "姓",
+        "Email" => "邮箱",
+        "Phone" => "电话",
+        "Street address" => "街道地址",
+        "City" => "城市",
+        "Postal code" => "邮编",
+        "Generate from" => "生成方式",
+        "Pattern" => "模式",
+        "# digit, A/a letter, * letter or digit, [A-F] one of, (X|Y) either, {4} repeat" => "# 数字，A/a 字母，* 字母或数字，[A-F] 其中之一，(X|Y) 任选，{4} 重复",
+        "No duplicates" => "不重复",
+        "Length" => "长度",
+        "Dash every" => "每组字符数",
+        "Check" => "校验",
+        "Skip codes in" => "排除文件",
+        "Earlier export (optional)" => "以前导出的文件（可选）",
+        "Codes use A-Z and 2-9 without O and I, and never repeat in a batch" => "兑换码使用 A-Z 和 2-9，不含 O 和 I，同一批内不重复",
+        "Digits" => "位数",
+        "No digit three times in a row (777)" => "同一数字不连续出现三次（777）",
+        "No years 1900-2099" => "不含 1900-2099 的年份",
+        "No runs like 345 or 987" => "不含 345、987 这样的连续数字",
+        "Words" => "词数",
+        "Separator" => "分隔符",
+        "Case" => "大小写",
+        "Wordlist" => "词表",
+        "Bundled list of 1296 words" => "自带的 1296 词词表",
+        "Kind" => "种类",
+        "Vendor prefix" => "厂商前缀",
+        "Network" => "网段",
+        "Any (locally administered)" => "不限（本地管理地址）",
+        "Any, or a CIDR block like 10.0.0.0/8" => "不限，或 10.0.0.0/8 这样的网段",
+        "Random file" => "随机文件",
+        "Size" => "大小",
+        "Write" => "写入",
+        "Bytes come from ChaCha20 seeded by the operating system; 1 KB is 1024 bytes" => "字节由操作系统播种的 ChaCha20 生成；1 KB 为 1024 字节",
+        "Group draw" => "分组抽签",
+        "Format" => "形式",
+        "Names (one per line)" => "名单（每行一个）",
+        "Never draw each other" => "互不抽中",
+        "One pair per line, like Alice, Bob" => "每行一对，例如 Alice, Bob",
+        "Show assignments" => "显示结果",
+        "Draw" => "抽签",
+        "Folder" => "文件夹",
+        "Export" => "导出",
+        "Names are placed at random; byes go to the first seeds so no match is bye against bye" => "名单随机排位；轮空分给前几号种子，不会出现轮空对轮空",
+        "Games" => "游戏",
+        "Game" => "游戏",
+        "Pool" => "号码池",
+        "Picks" => "每注个数",
+        "Each ticket picks numbers from 1 to the pool size; tickets the same as an earlier one are flagged" => "每注从 1 到号码池大小中选号；与前面某注完全相同的会标出",
+        "Loot table" => "掉落表",
+        "One tier per line: name, rate and an optional pity, like Legendary, 5%, 90 for a drop at the latest on pull 90" => "每行一档：名称、掉落率和可选的保底，例如 Legendary, 5%, 90 表示最迟第 90 抽必出",
+        "Dice" => "骰子数",
+        "Sides" => "面数",
+        "Success at" => "成功线",
+        "Explode at" => "爆骰线",
+        "Dice at or above the target count as successes; dice at or above the explode value roll one more die, marked +. Leave explode empty to turn it off" => "点数达到成功线的骰子算成功；达到爆骰线的骰子再追加掷一颗，标为 +。爆骰线留空则不爆骰",
+        "People (one per line)" => "人员（每行一个）",
+        "Hand out (one per line)" => "待分配项（每行一个）",
+        "Never assign" => "不能分配",
+        "One per line, like Alice, Paper 3" => "每行一组，例如 Alice, Paper 3",
+        "Each item goes to one person; everyone gets the same number, give or take one" => "每项分给一个人；每人分到的项数最多相差一",
+        "Each person gets a file named after them that only says who they give to" => "每人一个以名字命名的文件，只写着要送礼物给谁",
+        "JSON Schema or a template like {\"id\": \"uuid\", \"name\": \"name\"}" => "JSON Schema 或模板，例如 {\"id\": \"uuid\", \"name\": \"name\"}",
+        "Rhai script (the last value is the result):" => "Rhai 脚本（最后一个值就是结果）：",
+        "e.g. roll(2, 6).sum() + 3" => "例如 roll(2, 6).sum() + 3",
+        "roll(count, sides) and random(low, high); dice have reroll, keep_highest/lowest, drop_highest/lowest and sum" => "roll(个数, 面数) 和 random(下限, 上限)；骰池有 reroll、keep_highest/lowest、drop_highest/lowest 和 sum",
+        "Write a script and click Generate" => "编写脚本后点击生成",
+        "Strata (one per line, values: count):" => "分层（每行一层，数字: 个数）：",
+        "e.g. 1..100: 3" => "例如 1..100: 3",
+        "Values are a range like 1..100 or numbers like 7, 13, 42; strata must not overlap" => "数字可以是 1..100 这样的区间或 7, 13, 42 这样的列表；各层不能重叠",
+        "{} in total" => "共 {} 个",
+        "Write the strata and click Generate" => "填写各层后点击生成",
+        "Set the chance of success and click Generate" => "设置成功概率后点击生成",
+        "Issue tickets, then click Generate to draw winners" => "先发票，再点击生成抽出中奖者",
+        "First ticket" => "起始票号",
+        "Issued" => "已发",
+        "Next ticket" => "下一张票",
+        "Issue ticket" => "发票",
+        "Bootstrap" => "自助重抽样",
+        "Data file" => "数据文件",
+        "Resamples" => "重抽样次数",
+        "Values each" => "每次个数",
+        "All" => "全部",
+        "Numbers separated by commas, spaces or new lines; each resample draws from them with replacement" => "数字之间用逗号、空格或换行分隔；每次重抽样都从中有放回地抽取",
+        "Random walk" => "随机游走",
+        "Start" => "起点",
+        "Drift" => "漂移",
+        "Phi" => "系数 φ",
+        "Steps" => "步长分布",
+        "Step size" => "步长",
+        "Each value is drift + phi × the last value + a random step. Phi 1 is a random walk; below 1 the series keeps returning to its mean" => "每个值 = 漂移 + φ × 前一个值 + 随机一步。φ 为 1 时是随机游走；小于 1 时序列会不断回到均值附近",
+        "Export CSV" => "导出 CSV",
+        "Sample lines" => "抽取文件行",
+        "Read from" => "读取文件",
+        "path/to/file.txt" => "文件路径",
+        "Lines" => "行数",
+        "Sample" => "抽取",
+        "… and {} more" => "……还有 {} 行",
+        "Every non-blank line has the same chance; the file is read once and only the chosen lines are kept" => "每个非空行被抽中的机会相同；文件只读一遍，只保留抽中的行，大文件也不会占满内存",
+        "Monte Carlo" => "蒙特卡洛",
+        "Estimate" => "估计",
+        "Formula" => "算式",
+        "Samples" => "样本数",
+        "Run" => "运行",
+        "π: points in a unit square, 4 × the share inside the quarter circle. Integral: the average of the formula at random x, times the width. The error shrinks like 1/√N" => "π：在单位正方形中撒点，落在四分之一圆内的比例乘以 4。积分：随机 x 处算式值的平均数乘以区间宽度。误差大约按 1/√N 缩小",
+        "Random graph" => "随机图",
+        "Model" => "模型",
+        "Nodes" => "节点数",
+        "Edges" => "边数",
+        "Edges is the total: every set of that many edges is equally likely" => "边数是图的总边数：所有可能的边中，每种选法的机会相同",
+        "Edges is per new node: each joins the graph linked to nodes picked by their degree, so early nodes become hubs" => "边数是每个新节点连出的边数：按度数加权选择要连的节点，早加入的节点会成为枢纽",
+        "Experiment design" => "实验设计",
+        "Design" => "设计",
+        "Treatments" => "处理",
+        "Blocks" => "区组数",
+        "A number like 4 for treatments A to D, or names separated by commas" => "填数字（例如 4 表示处理 A 到 D），或用逗号分隔的处理名",
+        "Each row is one group's order of treatments; every treatment appears once in each row and each period" => "每行是一组受试者接受处理的顺序；每个处理在每行、每个时段各出现一次",
+        "Also balances carryover: each treatment follows every other equally often. An odd number of treatments needs twice as many rows" => "同时平衡残留效应：每个处理紧跟在其他每个处理之后的次数相同。处理数为奇数时需要两倍的行数",
+        "Every block gets each treatment once, in its own random order" => "每个区组包含每个处理各一次，各区组内的顺序独立随机",
+        "Distribution" => "分布",
+        "Minimum" => "最小值",
+        "Maximum" => "最大值",
+        "None" => "不限",
+        "Low" => "下限",
+        "High" => "上限",
+        "Mean" => "平均数",
+        "SD" => "标准差",
+        "Alpha" => "α",
+        "Beta" => "β",
+        "Shape" => "形状",
+        "Scale" => "尺度",
+        "With a minimum or maximum, values outside never come up and the rest keep their relative chances; nothing is clipped or filtered afterwards" => "设了最小值或最大值时，区间外的值不会出现，区间内各值的相对机会不变；不是事后截掉或过滤",
+        "Random weights" => "随机权重",
+        "Components" => "分量数",
+        "Vectors" => "向量数",
+        "Concentration" => "集中度",
+        "One number for every component, or one per component separated by commas. 1 makes every vector equally likely; larger values keep the weights close to their proportions, smaller ones pile most weight on a few" => "一个数字用于所有分量，或用逗号分隔、每个分量一个。1 表示每种向量的机会相同；越大各权重越接近集中度的比例，越小权重越集中在少数几个分量上",
+        "Correlated normal" => "相关正态",
+        "Correlation" => "相关矩阵",
+        "Means" => "平均数",
+        "SDs" => "标准差",
+        "One row of the matrix per semicolon, e.g. 1, 0.5; 0.5, 1. The diagonal is 1 and the matrix must be symmetric" => "矩阵每行用分号分隔，例如 1, 0.5; 0.5, 1。对角线为 1，矩阵要对称",
+        "Custom distribution" => "自定义分布",
+        "Points (one per line, value, cumulative probability):" => "各点（每行一个：值, 累积概率）：",
+        "e.g. 10, 0.5" => "例如 10, 0.5",
+        "The cumulative probability is the chance of a value up to that one; it must not go down and must reach 1. Values between two points are spread evenly" => "累积概率是不超过这个值的机会，不能下降，最后要到 1。两点之间的值均匀分布",
+        "One column per line, like die: 1..6" => "每行一列，例如 die: 1..6",
+        "name: 1..6 for whole numbers, normal(50, 10) round 1 for a distribution, red, green, blue to pick one" => "名字: 1..6 为整数，normal(50, 10) round 1 为分布，red, green, blue 为从中任选一个",
+        "Default file" => "默认文件",
+        "Export format" => "导出格式",
+        "Group header" => "分组标题",
+        "All tabs" => "所有标签页",
+        "Animate reveal" => "揭晓动画",
+        "One by one" => "逐个揭晓",
+        "Confetti" => "彩纸",
+        "Spin a wheel for lists" => "名单用转盘抽取",
+        "Sound" => "音效",
+        "Notify when done" => "完成时通知",
+        "Autosave results" => "自动保存结果",
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fill_and_fallback() {
+        let template = Language::Chinese.tr("Total: {}, matches: {}");
+        assert_eq!(fill(template, &[&10, &3]), "共 10 个，匹配 3 个");
+        assert_eq!(Language::Chinese.tr("Not translated"), "Not translated");
+        assert_eq!(fill(Language::English.tr("Generating... {}%"), &[&"42"]), "Generating... 42%");
+    }
+}
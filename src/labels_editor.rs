@@ -0,0 +1,60 @@
+use iced::widget::{checkbox, column, container, text, text_editor};
+use iced::{Color, Element, Length, Theme};
+use random_generator_core::LabelMap;
+
+use crate::{field_hint, get_checkbox_style, Message, RandomGeneratorApp};
+
+/// Labels a new window starts with
+pub const EXAMPLE: &str = "1: red team\n2: blue team\n";
+
+impl RandomGeneratorApp {
+    /// Use the typed labels for display and export; while they don't parse the last valid ones stay
+    pub(crate) fn apply_labels(&mut self) {
+        if !self.labels_enabled {
+            self.number_format.labels = LabelMap::default();
+        } else if let Ok(labels) = LabelMap::parse(&self.labels.text()) {
+            self.number_format.labels = labels;
+        }
+    }
+
+    /// Checkbox for labelling results, with the lookup table editor when it's on
+    pub(crate) fn labels_input(&self) -> Element<'_, Message> {
+        let dark_mode = self.settings.dark_mode;
+        let high_contrast = self.settings.high_contrast;
+        let toggle = checkbox(self.tr("Show results as labels"), self.labels_enabled)
+            .on_toggle(Message::LabelsToggled)
+            .size(self.checkbox_size())
+            .text_size(14)
+            .style(move |_theme: &Theme, _status| get_checkbox_style(dark_mode, high_contrast));
+        if !self.labels_enabled {
+            return toggle.into();
+        }
+
+        let error = self.field_errors().labels;
+        let labels_valid = error.is_none();
+        container(
+            column![
+                toggle,
+                text_editor(&self.labels)
+                    .on_action(Message::LabelsEdited)
+                    .placeholder(self.tr("e.g. 1: red team"))
+                    .height(Length::Fixed(90.0))
+                    .size(14)
+                    .style(move |theme: &Theme, status| {
+                        let mut style = text_editor::default(theme, status);
+                        if !labels_valid {
+                            style.border.color = Color::from_rgb(0.9, 0.3, 0.3);
+                        }
+                        style
+                    }),
+                field_hint(error),
+                text(self.tr("One per line, a number or a range like 3..5, a ':' and the label; other values stay numbers"))
+                    .size(11)
+                    .color(crate::accessibility::dim_text_color(dark_mode, high_contrast)),
+            ]
+                .spacing(4)
+        )
+            .padding(4)
+            .into()
+    }
+}
@@ -0,0 +1,164 @@
+use iced::mouse;
+use iced::widget::canvas::{self, Frame, Geometry, LineDash, Path, Stroke, Text};
+use iced::{alignment, Point, Rectangle, Renderer, Theme};
+
+/// Line chart of a series of numbers against their index.
+///
+/// Long series are folded into buckets before drawing: each bucket keeps
+/// its lowest and highest value, in the order they came, so spikes still
+/// show while the path stays a few thousand points long.
+#[derive(Debug, Clone)]
+pub struct LineChart {
+    /// Index and value of each point to draw
+    points: Vec<(usize, f64)>,
+    len: usize,
+    low: f64,
+    high: f64,
+    /// Labels at the ends of the horizontal axis, the first and last index by default
+    x_labels: Option<(String, String)>,
+    /// Value marked with a dashed line across the plot
+    reference: Option<f64>,
+}
+
+impl LineChart {
+    /// Series longer than this are folded into half as many buckets
+    const MAX_POINTS: usize = 4000;
+    /// Room for the value labels left of the plot
+    const LEFT_MARGIN: f32 = 64.0;
+    /// Room for the index labels under the plot
+    const BOTTOM_MARGIN: f32 = 20.0;
+    const MARGIN: f32 = 8.0;
+
+    pub fn new(values: &[f64]) -> Self {
+        let low = values.iter().copied().fold(f64::INFINITY, f64::min);
+        let high = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        let points = if values.len() <= Self::MAX_POINTS {
+            values.iter().copied().enumerate().collect()
+        } else {
+            let bucket = values.len().div_ceil(Self::MAX_POINTS / 2);
+            values
+                .chunks(bucket)
+                .enumerate()
+                .flat_map(|(chunk, part)| {
+                    let at = |i: usize| (chunk * bucket + i, part[i]);
+                    let lowest = (0..part.len()).min_by(|&a, &b| part[a].total_cmp(&part[b])).unwrap_or(0);
+                    let highest = (0..part.len()).max_by(|&a, &b| part[a].total_cmp(&part[b])).unwrap_or(0);
+                    match lowest.cmp(&highest) {
+                        std::cmp::Ordering::Less => vec![at(lowest), at(highest)],
+                        std::cmp::Ordering::Equal => vec![at(lowest)],
+                        std::cmp::Ordering::Greater => vec![at(highest), at(lowest)],
+                    }
+                })
+                .collect()
+        };
+        Self { points, len: values.len(), low, high, x_labels: None, reference: None }
+    }
+
+    /// Label the ends of the horizontal axis with something other than the index
+    pub fn with_x_labels(mut self, first: String, last: String) -> Self {
+        self.x_labels = Some((first, last));
+        self
+    }
+
+    /// Mark `value` with a dashed line, e.g. the exact answer an estimate should reach
+    pub fn with_reference(mut self, value: f64) -> Self {
+        self.low = self.low.min(value);
+        self.high = self.high.max(value);
+        self.reference = Some(value);
+        self
+    }
+}
+
+impl<Message> canvas::Program<Message> for LineChart {
+    type State = ();
+
+    fn draw(
+        &self,
+        _state: &Self::State,
+        renderer: &Renderer,
+        theme: &Theme,
+        bounds: Rectangle,
+        _cursor: mouse::Cursor,
+    ) -> Vec<Geometry> {
+        let mut frame = Frame::new(renderer, bounds.size());
+        let palette = theme.palette();
+        let plot = Rectangle {
+            x: Self::LEFT_MARGIN,
+            y: Self::MARGIN,
+            width: bounds.width - Self::LEFT_MARGIN - Self::MARGIN,
+            height: bounds.height - Self::BOTTOM_MARGIN - Self::MARGIN,
+        };
+        if plot.width <= 0.0 || plot.height <= 0.0 || self.points.is_empty() {
+            return vec![frame.into_geometry()];
+        }
+
+        // A flat series is drawn through the middle
+        let span = if self.high > self.low { self.high - self.low } else { 1.0 };
+        let middle = if self.high > self.low { 0.0 } else { 0.5 };
+        let last = self.len.saturating_sub(1).max(1) as f32;
+        let to_point = |&(index, value): &(usize, f64)| {
+            let fraction = ((value - self.low) / span) as f32 + middle;
+            Point::new(plot.x + plot.width * index as f32 / last, plot.y + plot.height * (1.0 - fraction))
+        };
+        let line = Path::new(|builder| {
+            let mut points = self.points.iter().map(to_point);
+            if let Some(first) = points.next() {
+                builder.move_to(first);
+                points.for_each(|point| builder.line_to(point));
+            }
+        });
+
+        let axes = Path::new(|builder| {
+            builder.move_to(Point::new(plot.x, plot.y));
+            builder.line_to(Point::new(plot.x, plot.y + plot.height));
+            builder.line_to(Point::new(plot.x + plot.width, plot.y + plot.height));
+        });
+        let dim = iced::Color { a: 0.5, ..palette.text };
+        frame.stroke(&axes, Stroke::default().with_color(dim).with_width(1.0));
+        if let Some(reference) = self.reference {
+            let y = to_point(&(0, reference)).y;
+            let dashed = Stroke { line_dash: LineDash { segments: &[4.0, 4.0], offset: 0 }, ..Stroke::default().with_color(dim).with_width(1.0) };
+            frame.stroke(&Path::line(Point::new(plot.x, y), Point::new(plot.x + plot.width, y)), dashed);
+        }
+        frame.stroke(&line, Stroke::default().with_color(palette.primary).with_width(1.5));
+
+        let label = |content: String, position: Point, horizontal: alignment::Horizontal, vertical: alignment::Vertical| Text {
+            content,
+            position,
+            color: palette.text,
+            size: 11.0.into(),
+            horizontal_alignment: horizontal,
+            vertical_alignment: vertical,
+            ..Text::default()
+        };
+        let right = alignment::Horizontal::Right;
+        frame.fill_text(label(format!("{:.4}", self.high), Point::new(plot.x - 4.0, plot.y), right, alignment::Vertical::Top));
+        frame.fill_text(label(format!("{:.4}", self.low), Point::new(plot.x - 4.0, plot.y + plot.height), right, alignment::Vertical::Bottom));
+        let below = plot.y + plot.height + 4.0;
+        let (first, last) = self.x_labels.clone().unwrap_or_else(|| ("0".to_string(), (self.len - 1).to_string()));
+        frame.fill_text(label(first, Point::new(plot.x, below), alignment::Horizontal::Left, alignment::Vertical::Top));
+        frame.fill_text(label(last, Point::new(plot.x + plot.width, below), right, alignment::Vertical::Top));
+
+        vec![frame.into_geometry()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_long_series_keep_their_extremes() {
+        let short = LineChart::new(&[1.0, 3.0, 2.0]);
+        assert_eq!(short.points, vec![(0, 1.0), (1, 3.0), (2, 2.0)]);
+
+        let mut values: Vec<f64> = (0..100_000).map(|i| (i % 7) as f64).collect();
+        values[54_321] = 50.0;
+        values[77_777] = -50.0;
+        let chart = LineChart::new(&values);
+        assert!(chart.points.len() <= LineChart::MAX_POINTS);
+        assert!(chart.points.contains(&(54_321, 50.0)) && chart.points.contains(&(77_777, -50.0)));
+        assert!(chart.points.windows(2).all(|pair| pair[0].0 < pair[1].0));
+        assert_eq!((chart.low, chart.high, chart.len), (-50.0, 50.0, 100_000));
+    }
+}
@@ -0,0 +1,246 @@
+//! Panel for sampling lines from a text file.
+//!
+//! Logs, word lists and data dumps are often too large to load whole. The
+//! file is read once on a worker thread and only the chosen lines are kept
+//! (reservoir sampling, `LineSample` in the core), so any size works and the
+//! window shows a progress bar. The sample can be saved one line per line.
+
+use std::fs;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use iced::widget::{button, column, container, progress_bar, row, scrollable, text, text_input, Space};
+use iced::{alignment, Background, Border, Color, Element, Font, Length, Shadow, Task, Theme, Vector};
+use random_generator_core::{LineSample, SampledLines, Seed};
+
+use crate::worker::{self, PanelEvent};
+use crate::{accessibility, get_link_button_style, get_text_input_style, i18n, Message, RandomGeneratorApp};
+
+/// Lines listed in the panel; all of them are saved
+const PREVIEW_LINES: usize = 200;
+
+#[derive(Debug)]
+pub struct LineSamplePanel {
+    path: String,
+    count: String,
+    filename: String,
+    /// Fraction read while sampling is running
+    progress: Option<f32>,
+    cancel: Arc<AtomicBool>,
+    sample: Option<SampledLines>,
+    status: String,
+}
+
+impl Default for LineSamplePanel {
+    fn default() -> Self {
+        Self {
+            path: String::new(),
+            count: "10".to_string(),
+            filename: "sample.txt".to_string(),
+            progress: None,
+            cancel: Arc::new(AtomicBool::new(false)),
+            sample: None,
+            status: String::new(),
+        }
+    }
+}
+
+impl LineSamplePanel {
+    pub fn set_path(&mut self, path: String) {
+        self.path = path;
+    }
+
+    pub fn set_count(&mut self, count: String) {
+        self.count = count;
+    }
+
+    pub fn set_filename(&mut self, filename: String) {
+        self.filename = filename;
+    }
+
+    pub fn is_sampling(&self) -> bool {
+        self.progress.is_some()
+    }
+
+    /// Start reading the file in the background
+    pub fn start(&mut self) -> Task<Message> {
+        if self.is_sampling() {
+            return Task::none();
+        }
+        let count = match self.count.trim().parse::<usize>() {
+            Ok(count) if count > 0 => count,
+            _ => {
+                self.status = "Enter how many lines to sample".to_string();
+                return Task::none();
+            }
+        };
+        if self.path.trim().is_empty() {
+            self.status = "Enter the file to sample from".to_string();
+            return Task::none();
+        }
+
+        let sample = LineSample { count, seed: Seed::random(&mut rand::thread_rng()) };
+        self.progress = Some(0.0);
+        self.sample = None;
+        self.status.clear();
+        self.cancel = Arc::new(AtomicBool::new(false));
+        Task::run(worker::sample_lines(self.path.trim().to_string(), sample, self.cancel.clone()), Message::LineSample)
+    }
+
+    pub fn cancel(&mut self) {
+        self.cancel.store(true, Ordering::Relaxed);
+    }
+
+    pub fn handle(&mut self, event: PanelEvent<SampledLines>) {
+        match event {
+            PanelEvent::Progress(fraction) => {
+                if self.progress.is_some() {
+                    self.progress = Some(fraction);
+                }
+            }
+            PanelEvent::Finished(result) => {
+                self.progress = None;
+                match result {
+                    Ok(sample) => {
+                        self.status = format!("{} of {} lines", sample.lines.len(), sample.total);
+                        self.sample = Some(sample);
+                    }
+                    Err(e) => self.status = e,
+                }
+            }
+        }
+    }
+
+    pub fn save(&mut self) {
+        let Some(sample) = self.sample.as_ref().filter(|sample| !sample.lines.is_empty()) else {
+            self.status = "Nothing to save yet".to_string();
+            return;
+        };
+        self.status = match fs::write(&self.filename, sample.lines.join("\n") + "\n") {
+            Ok(()) => format!("Saved to {}", self.filename),
+            Err(e) => format!("Save error: {}", e),
+        };
+    }
+}
+
+impl RandomGeneratorApp {
+    pub(crate) fn line_sample_view(&self) -> Element<'_, Message> {
+        let panel = &self.line_sample;
+        let dark_mode = self.settings.dark_mode;
+        let high_contrast = self.settings.high_contrast;
+        let label = |content: &'static str| text(self.tr(content)).size(14).width(Length::Fixed(80.0));
+        let action = |content: &'static str, message: Option<Message>| {
+            button(text(self.tr(content)).size(14))
+                .on_press_maybe(message)
+                .padding(self.button_padding())
+                .style(move |_theme: &Theme, status| get_link_button_style(dark_mode, high_contrast, status))
+        };
+        let sampling = panel.is_sampling();
+
+        let progress = panel.progress.map(|fraction| {
+            row![
+                progress_bar(0.0..=1.0, fraction).height(Length::Fixed(8.0)),
+                text(format!("{:.0}%", fraction * 100.0)).size(13),
+                action("Cancel", Some(Message::CancelLineSample)),
+            ]
+                .spacing(8)
+                .align_y(alignment::Vertical::Center)
+        });
+
+        let preview = panel.sample.as_ref().filter(|sample| !sample.lines.is_empty()).map(|sample| {
+            let lines = sample.lines.iter().take(PREVIEW_LINES).map(|line| text(line).size(12).font(Font::MONOSPACE).into());
+            let more = (sample.lines.len() > PREVIEW_LINES)
+                .then(|| text(i18n::fill(self.tr("… and {} more"), &[&(sample.lines.len() - PREVIEW_LINES)])).size(12));
+            container(
+                scrollable(column(lines).push_maybe(more).spacing(2))
+                    .direction(scrollable::Direction::Both {
+                        vertical: scrollable::Scrollbar::default(),
+                        horizontal: scrollable::Scrollbar::default(),
+                    })
+                    .height(Length::Fixed(180.0)),
+            )
+                .padding(6)
+                .width(Length::Fill)
+        });
+
+        let content = column![
+            text(self.tr("Sample lines"))
+                .size(20)
+                .color(if dark_mode { Color::from_rgb(0.9, 0.9, 0.9) } else { Color::BLACK }),
+            Space::with_height(Length::Fixed(10.0)),
+            row![
+                label("Read from"),
+                text_input(self.tr("path/to/file.txt"), &panel.path)
+                    .on_input(Message::LineSamplePathChanged)
+                    .on_submit(Message::RunLineSample)
+                    .width(Length::Fill)
+                    .size(14)
+                    .style(move |_theme: &Theme, status| get_text_input_style(dark_mode, high_contrast, status)),
+            ]
+                .spacing(6)
+                .align_y(alignment::Vertical::Center),
+            row![
+                label("Lines"),
+                text_input("", &panel.count)
+                    .on_input(Message::LineSampleCountChanged)
+                    .on_submit(Message::RunLineSample)
+                    .width(Length::Fixed(100.0))
+                    .size(14)
+                    .style(move |_theme: &Theme, status| get_text_input_style(dark_mode, high_contrast, status)),
+                Space::with_width(Length::Fill),
+                action("Sample", (!sampling).then_some(Message::RunLineSample)),
+            ]
+                .spacing(6)
+                .align_y(alignment::Vertical::Center),
+            text(self.tr("Every non-blank line has the same chance; the file is read once and only the chosen lines are kept"))
+                .size(11)
+                .color(accessibility::dim_text_color(dark_mode, high_contrast)),
+        ]
+            .push_maybe(progress)
+            .push_maybe(preview)
+            .push(
+                row![
+                    label("File"),
+                    text_input("", &panel.filename)
+                        .on_input(Message::LineSampleFilenameChanged)
+                        .on_submit(Message::SaveLineSample)
+                        .width(Length::Fill)
+                        .size(14)
+                        .style(move |_theme: &Theme, status| get_text_input_style(dark_mode, high_contrast, status)),
+                    action("Save", Some(Message::SaveLineSample)),
+                ]
+                    .spacing(6)
+                    .align_y(alignment::Vertical::Center),
+            )
+            .push(
+                row![
+                    text(&panel.status).size(13).color(accessibility::dim_text_color(dark_mode, high_contrast)),
+                    Space::with_width(Length::Fill),
+                    action("Close", Some(Message::CloseLineSample)),
+                ]
+                    .align_y(alignment::Vertical::Center),
+            )
+            .spacing(6)
+            .padding(24);
+
+        container(content)
+            .width(Length::Fixed(480.0))
+            .style(move |_theme: &Theme| {
+                iced::widget::container::Style {
+                    background: Some(Background::Color(if dark_mode { Color::from_rgb(0.2, 0.2, 0.25) } else { Color::WHITE })),
+                    border: Border {
+                        color: if dark_mode { Color::from_rgb(0.4, 0.4, 0.4) } else { Color::from_rgb(0.8, 0.8, 0.8) },
+                        width: 1.0,
+                        radius: 16.0.into(),
+                    },
+                    shadow: Shadow {
+                        color: Color::from_rgba(0.0, 0.0, 0.0, 0.3),
+                        offset: Vector::new(0.0, 4.0),
+                        blur_radius: 20.0,
+                    },
+                    ..Default::default()
+                }
+            })
+            .into()
+    }
+}
@@ -0,0 +1,154 @@
+use std::ops::Range;
+
+use iced::advanced::text::highlighter::{self, Highlighter};
+use iced::widget::{button, column, container, row, text, text_editor, text_input, Space};
+use iced::{alignment, Color, Element, Font, Length, Theme};
+use random_generator_core::{RandomGenerator, RandomGeneratorError};
+
+use crate::{field_hint, get_link_button_style, get_validated_input_style, Message, RandomGeneratorApp};
+
+/// Values shown under the editor before the rest are elided
+const PREVIEW_ITEMS: usize = 8;
+
+/// Marks the items of the custom list that aren't numbers, line by line
+pub struct BadItems {
+    current_line: usize,
+}
+
+impl Highlighter for BadItems {
+    type Settings = ();
+    type Highlight = ();
+    type Iterator<'a> = std::vec::IntoIter<(Range<usize>, ())>;
+
+    fn new(_settings: &()) -> Self {
+        BadItems { current_line: 0 }
+    }
+
+    fn update(&mut self, _new_settings: &()) {}
+
+    fn change_line(&mut self, line: usize) {
+        self.current_line = line;
+    }
+
+    fn highlight_line(&mut self, line: &str) -> Self::Iterator<'_> {
+        self.current_line += 1;
+        RandomGenerator::custom_list_items(line)
+            .into_iter()
+            .filter(|(_, value)| value.is_none())
+            .map(|(range, _)| (range, ()))
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    fn current_line(&self) -> usize {
+        self.current_line
+    }
+}
+
+impl RandomGeneratorApp {
+    /// Replace the custom list as if it were typed in
+    pub(crate) fn set_custom_list(&mut self, list: String) -> Result<(), RandomGeneratorError> {
+        self.custom_list_input = text_editor::Content::with_text(&list);
+        self.generator.set_custom_list_input(list)
+    }
+
+    /// How many items the list parses to, and the first few of them as the parser read them
+    pub(crate) fn custom_list_summary(&self) -> (String, Option<String>) {
+        let text = self.custom_list_input.text();
+        let items = RandomGenerator::custom_list_items(&text);
+        let values: Vec<i64> = items.iter().filter_map(|(_, value)| *value).collect();
+        let parsed = match values.len() {
+            1 => format!("1 {}", self.tr("valid item parsed")),
+            parsed => format!("{} {}", parsed, self.tr("valid items parsed")),
+        };
+        let bad: Vec<&str> = items.iter().filter(|(_, value)| value.is_none()).map(|(range, _)| &text[range.clone()]).collect();
+        let summary = match (bad.len(), self.settings.strict_list) {
+            (0, _) => parsed,
+            (1, true) => format!("{}, 1 {}", parsed, self.tr("not a number (in red)")),
+            (count, true) => format!("{}, {} {}", parsed, count, self.tr("not numbers (in red)")),
+            // Name every skipped item, so nothing is dropped unnoticed
+            (count, false) => format!("{}, {} {}: {}", parsed, count, self.tr("skipped"), bad.join(", ")),
+        };
+        let preview = (!values.is_empty()).then(|| {
+            let mut shown: Vec<String> = values.iter().take(PREVIEW_ITEMS).map(i64::to_string).collect();
+            if values.len() > PREVIEW_ITEMS {
+                shown.push("…".to_string());
+            }
+            shown.join(", ")
+        });
+        (summary, preview)
+    }
+
+    /// Editor for the custom-list mode, with the number of items, a preview and the count below it
+    pub(crate) fn custom_list_editor(&self) -> Element<'_, Message> {
+        let field_errors = self.field_errors();
+        let custom_list_valid = field_errors.custom_list.is_none();
+        let count_valid = field_errors.count.is_none();
+        let dark_mode = self.settings.dark_mode;
+        let high_contrast = self.settings.high_contrast;
+
+        let (summary, preview) = self.custom_list_summary();
+
+        container(
+            column![
+                row![
+                    text(self.tr("Numbers (comma/space separated):")).size(14),
+                    Space::with_width(Length::Fill),
+                    // A column copied from a spreadsheet or a CSV file
+                    button(text(self.tr("Paste list")).size(14))
+                        .on_press(Message::PasteList)
+                        .padding(self.button_padding())
+                        .style(move |_theme: &Theme, status| get_link_button_style(dark_mode, high_contrast, status)),
+                ]
+                    .align_y(alignment::Vertical::Center),
+                text_editor(&self.custom_list_input)
+                    .on_action(Message::CustomListEdited)
+                    .placeholder(self.tr("e.g. 1, 2, 3, 4, 5"))
+                    .height(Length::Fixed(90.0))
+                    .size(14)
+                    .highlight_with::<BadItems>((), |_, _| highlighter::Format {
+                        color: Some(Color::from_rgb(0.9, 0.3, 0.3)),
+                        font: Some(Font { weight: iced::font::Weight::Bold, ..Font::DEFAULT }),
+                    })
+                    .style(move |theme: &Theme, status| {
+                        let mut style = text_editor::default(theme, status);
+                        if !custom_list_valid {
+                            style.border.color = Color::from_rgb(0.9, 0.3, 0.3);
+                        }
+                        style
+                    }),
+                field_hint(field_errors.custom_list.clone()),
+                text(summary)
+                    .size(11)
+                    .color(crate::accessibility::dim_text_color(dark_mode, high_contrast)),
+            ]
+                .push_maybe(preview.map(|preview| {
+                    text(preview)
+                        .size(11)
+                        .font(Font::MONOSPACE)
+                        .color(crate::accessibility::dim_text_color(dark_mode, high_contrast))
+                }))
+                .push(Space::with_height(Length::Fixed(4.0)))
+                // Count input for custom list mode
+                .push(
+                    column![
+                        text(self.tr("Count")).size(14),
+                        text_input("", &self.num_to_generate)
+                            .on_input(Message::NumToGenerateChanged)
+                            .on_submit(Message::Generate)
+                            .width(Length::Fixed(96.0))
+                            .size(14)
+                            .style(move |_theme: &Theme, status| {
+                                get_validated_input_style(dark_mode, high_contrast, count_valid, status)
+                            }),
+                        field_hint(field_errors.count.clone()),
+                    ]
+                    .width(Length::Fixed(96.0))
+                    .spacing(2),
+                )
+                .spacing(4)
+        )
+            .padding(4)
+            .into()
+    }
+}
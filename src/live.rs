@@ -0,0 +1,124 @@
+//! Live draw events for overlays: `GET /live` in server mode upgrades to a
+//! WebSocket, and every draw the server makes is then pushed to it as JSON:
+//!
+//! ```text
+//! {"event":"start","count":3}
+//! {"event":"value","index":0,"value":42}
+//! ...
+//! {"event":"complete","numbers":[42,7,93],"seed":"..."}
+//! ```
+//!
+//! The connection only carries events to the client; anything it sends is ignored.
+
+use std::io::{self, Write};
+use std::net::TcpStream;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use serde_json::Value;
+use sha1::{Digest, Sha1};
+
+use crate::server::ConnectionSlot;
+
+/// Appended to the client's key before hashing, fixed by RFC 6455
+const HANDSHAKE_GUID: &str = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
+/// A client that can't take an event this quickly is dropped rather than holding up draws
+const WRITE_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Connected WebSocket clients, shared by every request thread
+///
+/// Each client keeps its connection slot, so it counts towards the server's
+/// connection limit until it is dropped.
+#[derive(Debug, Clone, Default)]
+pub struct Live {
+    clients: Arc<Mutex<Vec<(TcpStream, ConnectionSlot)>>>,
+}
+
+impl Live {
+    /// Finish the WebSocket handshake for `key` and start sending events to the client
+    pub(crate) fn accept(&self, mut stream: TcpStream, slot: ConnectionSlot, key: &str) -> io::Result<()> {
+        stream.set_write_timeout(Some(WRITE_TIMEOUT))?;
+        write!(
+            stream,
+            "HTTP/1.1 101 Switching Protocols\r\nUpgrade: websocket\r\nConnection: Upgrade\r\nSec-WebSocket-Accept: {}\r\n\r\n",
+            accept_key(key)
+        )?;
+        stream.flush()?;
+        self.clients.lock().unwrap_or_else(|e| e.into_inner()).push((stream, slot));
+        Ok(())
+    }
+
+    /// Whether anyone is listening, so callers can skip building events
+    pub fn has_clients(&self) -> bool {
+        !self.clients.lock().unwrap_or_else(|e| e.into_inner()).is_empty()
+    }
+
+    /// Send an event to every client, forgetting those that went away
+    pub fn broadcast(&self, event: &Value) {
+        let mut clients = self.clients.lock().unwrap_or_else(|e| e.into_inner());
+        if clients.is_empty() {
+            return;
+        }
+        let frame = text_frame(&event.to_string());
+        clients.retain_mut(|(client, _)| client.write_all(&frame).is_ok());
+    }
+}
+
+/// `Sec-WebSocket-Accept` answer to a client's `Sec-WebSocket-Key`
+fn accept_key(key: &str) -> String {
+    let digest = Sha1::digest(format!("{}{}", key.trim(), HANDSHAKE_GUID).as_bytes());
+    base64(&digest)
+}
+
+/// A single unmasked text frame, as a server sends them
+fn text_frame(text: &str) -> Vec<u8> {
+    let payload = text.as_bytes();
+    let mut frame = vec![0x81];
+    match payload.len() {
+        len @ 0..=125 => frame.push(len as u8),
+        len @ 126..=0xffff => {
+            frame.push(126);
+            frame.extend_from_slice(&(len as u16).to_be_bytes());
+        }
+        len => {
+            frame.push(127);
+            frame.extend_from_slice(&(len as u64).to_be_bytes());
+        }
+    }
+    frame.extend_from_slice(payload);
+    frame
+}
+
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let b = [chunk[0], chunk.get(1).copied().unwrap_or(0), chunk.get(2).copied().unwrap_or(0)];
+        let n = (b[0] as u32) << 16 | (b[1] as u32) << 8 | b[2] as u32;
+        for i in 0..4 {
+            if i <= chunk.len() {
+                encoded.push(ALPHABET[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_handshake_and_frames() {
+        // Example from RFC 6455, section 1.3
+        assert_eq!(accept_key("dGhlIHNhbXBsZSBub25jZQ=="), "s3pPLMBiTxaQ9kYGzzhZRbK+xOo=");
+        assert_eq!(base64(b"ab"), "YWI=");
+        assert_eq!(base64(b"a"), "YQ==");
+
+        assert_eq!(text_frame("hi"), [0x81, 2, b'h', b'i']);
+        let long = "x".repeat(300);
+        assert_eq!(&text_frame(&long)[..4], &[0x81, 126, 1, 44]);
+    }
+}
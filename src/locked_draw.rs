@@ -0,0 +1,151 @@
+//! Countdown-locked draws. With a countdown set in the settings, Generate
+//! freezes the config as the inputs are at that moment and shows it over the
+//! whole window while counting down, then draws from exactly that config. The
+//! inputs are out of reach until the draw is made or cancelled, so an audience
+//! can see nothing was changed at the last second.
+
+use std::time::{Duration, Instant};
+
+use iced::widget::{button, column, container, text, Space};
+use iced::{alignment, Background, Color, Element, Length, Task, Theme};
+use random_generator_core::{GeneratorConfig, SourceInput};
+
+use crate::{accessibility, get_link_button_style, i18n, Message, RandomGeneratorApp, Status};
+
+/// Longest countdown the settings accept, in seconds
+pub const MAX_COUNTDOWN: u64 = 600;
+
+#[derive(Debug)]
+pub struct LockedDraw {
+    /// Config the draw is made from, as it was when Generate was pressed
+    config: GeneratorConfig,
+    draw_at: Instant,
+}
+
+impl RandomGeneratorApp {
+    /// Freeze the generator's current config and count down `countdown` from `now` before drawing
+    pub(crate) fn lock_draw(&mut self, countdown: Duration, now: Instant) {
+        self.locked_draw = Some(LockedDraw {
+            config: self.generator.get_config().clone(),
+            draw_at: now + countdown,
+        });
+    }
+
+    /// Draw from the frozen config once the countdown is over
+    pub(crate) fn locked_draw_tick(&mut self, now: Instant) -> Task<Message> {
+        let Some(lock) = self.locked_draw.take_if(|lock| now >= lock.draw_at) else {
+            return Task::none();
+        };
+        if let Err(e) = self.generator.set_config(lock.config) {
+            self.status = Some(Status::Error(e.to_string()));
+            self.stop_auto_draw(Some(e.to_string()));
+            return Task::none();
+        }
+        self.start_generation()
+    }
+
+    /// The frozen config, one fact per line
+    fn frozen_config(&self, config: &GeneratorConfig) -> Vec<String> {
+        let source = self.generator.source(config.mode).map_or(SourceInput::Bounds, |source| source.input());
+        let mut lines = vec![i18n::fill(self.tr("Mode: {}"), &[&config.mode])];
+        match source {
+            SourceInput::Bounds => lines.push(i18n::fill(
+                self.tr("From {} to {}"),
+                &[&self.number_format.format(config.lower_bound), &self.number_format.format(config.upper_bound)],
+            )),
+            SourceInput::List => lines.push(i18n::fill(self.tr("{} numbers in the list"), &[&config.custom_list.len()])),
+            SourceInput::Own => {}
+        }
+        lines.push(i18n::fill(self.tr("Drawing {}"), &[&config.num_to_generate]));
+        lines.push(self.tr(if config.allow_duplicates { "Duplicates allowed" } else { "No duplicates" }).to_string());
+        if let Some(excluded) = config.exclude_drawn.as_ref().filter(|excluded| !excluded.is_empty()) {
+            lines.push(i18n::fill(self.tr("{} excluded"), &[&excluded.len()]));
+        }
+        lines
+    }
+
+    /// Full-window countdown over the frozen config, in place of the inputs
+    pub(crate) fn locked_draw_view(&self, lock: &LockedDraw) -> Element<'_, Message> {
+        let dark_mode = self.settings.dark_mode;
+        let high_contrast = self.settings.high_contrast;
+        let dim_color = accessibility::dim_text_color(dark_mode, high_contrast);
+        let main_color = if dark_mode { Color::from_rgb(0.95, 0.95, 0.95) } else { Color::BLACK };
+        let left = lock.draw_at.saturating_duration_since(Instant::now());
+        // Round up, so the last second shows 1 rather than 0
+        let seconds = left.as_secs() + u64::from(left.subsec_nanos() > 0);
+
+        let content = column![
+            text(self.tr("Inputs locked, drawing in")).size(18).color(dim_color),
+            text(seconds.to_string())
+                .size(160)
+                .font(iced::Font::MONOSPACE)
+                .color(main_color),
+        ]
+            .extend(self.frozen_config(&lock.config).into_iter().map(|line| text(line).size(16).color(main_color).into()))
+            .push(Space::with_height(Length::Fixed(14.0)))
+            .push(
+                button(text(self.tr("Cancel")).size(14))
+                    .on_press(Message::CancelLockedDraw)
+                    .padding(self.button_padding())
+                    .style(move |_theme: &Theme, status| get_link_button_style(dark_mode, high_contrast, status)),
+            )
+            .spacing(6)
+            .align_x(alignment::Horizontal::Center);
+
+        container(content)
+            .center_x(Length::Fill)
+            .center_y(Length::Fill)
+            .style(move |_theme: &Theme| {
+                iced::widget::container::Style {
+                    background: Some(Background::Color(
+                        if dark_mode {
+                            Color::from_rgb(0.1, 0.1, 0.13)
+                        } else {
+                            Color::WHITE
+                        }
+                    )),
+                    ..Default::default()
+                }
+            })
+            .into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_locked_draw_uses_the_frozen_config() {
+        let mut app = RandomGeneratorApp::default();
+        app.settings.draw_countdown = "10".to_string();
+        let _ = app.update(Message::LowerBoundChanged("1".to_string()));
+        let _ = app.update(Message::UpperBoundChanged("6".to_string()));
+        let _ = app.update(Message::Generate);
+        assert!(app.generation_progress.is_none());
+        let draw_at = app.locked_draw.as_ref().unwrap().draw_at;
+        assert_eq!(
+            app.frozen_config(&app.locked_draw.as_ref().unwrap().config),
+            ["Mode: Range", "From 1 to 6", "Drawing 1", "No duplicates"]
+        );
+
+        // Pressing Generate again doesn't restart the countdown
+        let _ = app.update(Message::Generate);
+        assert_eq!(app.locked_draw.as_ref().unwrap().draw_at, draw_at);
+
+        // A change that slips through is ignored by the draw
+        let _ = app.update(Message::UpperBoundChanged("1000".to_string()));
+        let _ = app.locked_draw_tick(draw_at - Duration::from_secs(1));
+        assert!(app.generation_progress.is_none());
+        let _ = app.locked_draw_tick(draw_at);
+        assert!(app.locked_draw.is_none());
+        assert!(app.generation_progress.is_some());
+        assert_eq!(app.generator.get_config().upper_bound, 6);
+
+        app.generation_progress = None;
+        let _ = app.update(Message::Generate);
+        let _ = app.update(Message::CancelLockedDraw);
+        assert!(app.locked_draw.is_none());
+        assert!(app.generation_progress.is_none());
+    }
+}
@@ -1,20 +1,124 @@
-mod random_generator;
+mod accessibility;
+mod cli;
+mod animation;
+mod auto_draw;
+mod benchmark;
+mod cdf_table;
+mod confetti;
+mod correlated;
+mod daemon;
+mod deep_link;
+mod design;
+mod distribution;
+mod export_panel;
+mod filter;
+mod history;
+mod history_panel;
+mod i18n;
+mod live;
+mod locked_draw;
+mod metrics;
+mod mqtt;
+mod notification;
+mod osc;
+mod presentation;
+mod plugins;
+mod presets;
+mod random_org;
+mod report;
+mod server;
+mod settings;
+mod script_editor;
+mod strata_editor;
+mod labels_editor;
+mod list_editor;
+mod trials_input;
+mod sound;
+mod source_params;
+mod tabs;
+mod tickets;
+mod games;
+mod graph;
+mod grpc;
+mod bootstrap;
+mod compare;
+mod merge_files;
+mod line_chart;
+mod line_sample;
+mod monte_carlo;
+mod group_draw;
+mod random_file;
+mod test_data;
+#[cfg(feature = "tray")]
+mod tray;
+mod validation;
+mod walk;
+mod weights;
+mod wheel;
+mod windows;
+mod worker;
 
 use iced::widget::{
-    button, checkbox, column, container, horizontal_rule, pick_list, row, scrollable, text, text_input, Space
+    button, checkbox, column, container, horizontal_rule, pick_list, progress_bar, row, scrollable, slider, text, text_editor, text_input, Space
 };
 use iced::{
-    alignment, Element, Length, Theme, Color, Background, Border, Shadow, Vector, Task
+    alignment, Element, Length, Theme, Color, Background, Border, Shadow, Vector, Task, Subscription
 };
-use random_generator::{RandomGenerator, GeneratorMode};
+use iced::{keyboard, window};
+use animation::SlotReveal;
+use auto_draw::{AutoDraw, IntervalUnit};
+use locked_draw::LockedDraw;
+use confetti::Confetti;
+use wheel::Wheel;
+use filter::ResultFilter;
+use i18n::Language;
+use random_generator_core::{AddressKind, Rearrange, BootstrapReport, ByteUnit, DesignKind, DesignTable, DistributionKind, DistributionReport, Graph, GraphModel, StepDistribution, Capitalization, CheckCharacter, ExportFormat, FakeField, FakeLocale, GeneratorConfig, GroupHeader, RandomGenerator, RandomGeneratorError, GeneratorMode, ListDuplicates, ListSource, MultivariateSample, PastedList, SampledLines, ShuffleBag, ExclusionSet, NumberFormat, ResultOrder, RngBackend, Seed, SourceInput, ThousandsSeparator, Transform};
+use settings::{SaveFormat, Settings};
+use sound::{Effect, SoundPlayer};
+use source_params::SourceParams;
+use games::{GameKind, GamesPanel};
+use bootstrap::BootstrapPanel;
+use compare::{ComparePanel, CompareTarget};
+use merge_files::MergePanel;
+use history_panel::{HistoryPanel, Period};
+use report::SessionDraw;
+use validation::FieldErrors;
+use walk::WalkPanel;
+use monte_carlo::{Estimate, MonteCarloPanel};
+use graph::{GraphFormat, GraphPanel};
+use line_chart::LineChart;
+use design::DesignPanel;
+use distribution::DistributionPanel;
+use export_panel::ExportPanel;
+use weights::WeightsPanel;
+use correlated::CorrelatedPanel;
+use cdf_table::CdfTablePanel;
+use line_sample::LineSamplePanel;
+use group_draw::{GroupDrawFormat, GroupDrawPanel};
+use random_file::RandomFilePanel;
+use test_data::{PinRule, TestDataPanel, TestDataSource};
 use std::fmt;
+use std::fs;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Instant;
+use windows::Windows;
+use random_org::DrawSource;
+use worker::{Draw, GenerationEvent, MonteCarloEvent, PanelEvent};
 
-// Implement Display trait for GeneratorMode
-impl fmt::Display for GeneratorMode {
+/// Line under the buttons reporting how the last action went
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Status {
+    /// Done as asked, shown in green
+    Info(String),
+    /// Failed or needs fixing, shown in red
+    Error(String),
+}
+
+impl fmt::Display for Status {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            GeneratorMode::Range => write!(f, "Range"),
-            GeneratorMode::CustomList => write!(f, "Custom List"),
+            Status::Info(message) | Status::Error(message) => write!(f, "{}", message),
         }
     }
 }
@@ -22,70 +126,598 @@ impl fmt::Display for GeneratorMode {
 #[derive(Debug, Clone)]
 pub enum Message {
     LowerBoundChanged(String),
+    PartitionTotalChanged(String),
+    TicketsIssuedChanged(String),
+    /// Hand out the next ticket number in tickets mode
+    IssueTicket,
+    SuccessChanceChanged(String),
+    SuccessesChanged(String),
     UpperBoundChanged(String),
     NumToGenerateChanged(String),
     FilenameChanged(String),
+    /// Ask for a saved results file to open
+    OpenResults,
+    ExportReport,
+    ReportFileChosen(Option<String>),
+    /// Draw what a `randomtool://generate` link filled in
+    ConfirmLinkDraw,
+    CancelLinkDraw,
+    ResultsFileChosen(Option<String>),
+    /// Add the current results to the session's exclusions
+    ExcludeResults,
     AllowDuplicatesToggled(bool),
+    MaxRepeatsChanged(String),
+    MaxStreakChanged(String),
+    ShuffleBagToggled(bool),
+    RefillShuffleBag,
+    CooldownChanged(String),
+    ClearCooldown,
+    TransformChanged(String),
+    ExcludeDrawnToggled(bool),
+    ResetExcluded,
     ModeChanged(GeneratorMode),
-    CustomListChanged(String),
+    CustomListEdited(text_editor::Action),
+    /// Read the custom list from the clipboard
+    PasteList,
+    ListPasted(Option<String>),
+    ScriptEdited(text_editor::Action),
+    StrataEdited(text_editor::Action),
+    LabelsToggled(bool),
+    LabelsEdited(text_editor::Action),
     Generate,
+    Generation(GenerationEvent),
+    CancelGeneration,
+    AutoDrawIntervalChanged(String),
+    AutoDrawUnitChanged(IntervalUnit),
+    /// Draw automatically every interval until stopped
+    StartAutoDraw,
+    StopAutoDraw,
+    AutoDrawTick(Instant),
+    LockedDrawTick(Instant),
+    CancelLockedDraw,
+    NewWindow,
     Clear,
     Save,
+    CopyResults,
+    SelectTab(usize),
+    CloseTab(usize),
     ToggleTheme,
+    ShowSettings,
+    CloseSettings,
+    LanguageChanged(Language),
+    RngBackendChanged(RngBackend),
+    ResultOrderChanged(ResultOrder),
+    ListDuplicatesChanged(ListDuplicates),
+    StrictListToggled(bool),
+    RandomOrgToggled(bool),
+    RandomOrgKeyChanged(String),
+    DaemonToggled(bool),
+    MqttBrokerChanged(String),
+    MqttTopicChanged(String),
+    MqttPublished(Result<(), String>),
+    OscTargetChanged(String),
+    OscAddressChanged(String),
+    OscSent(Result<(), String>),
+    HistoryRecorded(Result<(), String>),
+    DefaultFilenameChanged(String),
+    ExportFormatChanged(SaveFormat),
+    GroupHeaderChanged(String),
+    DrawCountdownChanged(String),
+    HistoryDaysChanged(String),
+    HistoryDrawsChanged(String),
+    SaveAllTabsToggled(bool),
+    AutosaveToggled(bool),
+    HighContrastToggled(bool),
+    KeyPressed(window::Id, keyboard::Key, keyboard::Modifiers),
+    FocusNext,
+    FocusPrevious,
+    ShowTestData,
+    CloseTestData,
+    TestDataFieldToggled(FakeField, bool),
+    TestDataLocaleChanged(FakeLocale),
+    TestDataSourceChanged(TestDataSource),
+    TestDataPatternChanged(String),
+    TestDataUniqueToggled(bool),
+    TestDataCodeLengthChanged(String),
+    TestDataCodeGroupChanged(String),
+    TestDataCodeCheckChanged(CheckCharacter),
+    TestDataCodeExcludeChanged(String),
+    TestDataPinLengthChanged(String),
+    TestDataPinRuleToggled(PinRule, bool),
+    TestDataPassphraseWordsChanged(String),
+    TestDataPassphraseSeparatorChanged(String),
+    TestDataPassphraseCaseChanged(Capitalization),
+    TestDataWordlistChanged(String),
+    TestDataAddressKindChanged(AddressKind),
+    TestDataAddressRangeChanged(String),
+    TestDataSchemaEdited(text_editor::Action),
+    TestDataColumnsEdited(text_editor::Action),
+    TestDataCountChanged(String),
+    GenerateTestData,
+    ShowRandomFile,
+    CloseRandomFile,
+    RandomFileSizeChanged(String),
+    RandomFileUnitChanged(ByteUnit),
+    RandomFileNameChanged(String),
+    WriteRandomFile,
+    CancelRandomFile,
+    RandomFile(PanelEvent<String>),
+    ShowLineSample,
+    CloseLineSample,
+    LineSamplePathChanged(String),
+    LineSampleCountChanged(String),
+    LineSampleFilenameChanged(String),
+    RunLineSample,
+    CancelLineSample,
+    LineSample(PanelEvent<SampledLines>),
+    SaveLineSample,
+    ShowGroupDraw,
+    CloseGroupDraw,
+    GroupDrawFormatChanged(GroupDrawFormat),
+    GroupDrawNamesEdited(text_editor::Action),
+    GroupDrawExclusionsEdited(text_editor::Action),
+    GroupDrawItemsEdited(text_editor::Action),
+    GroupDrawForbiddenEdited(text_editor::Action),
+    GroupDrawRevealToggled(bool),
+    GroupDrawFolderChanged(String),
+    RunGroupDraw,
+    ShowGames,
+    CloseGames,
+    GameKindChanged(GameKind),
+    KenoPoolChanged(String),
+    KenoPicksChanged(String),
+    LootTableEdited(text_editor::Action),
+    PoolDiceChanged(String),
+    PoolSidesChanged(String),
+    PoolTargetChanged(String),
+    PoolExplodeChanged(String),
+    GameCountChanged(String),
+    RunGame,
+    ShowCompare,
+    CloseCompare,
+    CompareTargetChanged(CompareTarget),
+    CompareFileChanged(String),
+    RunCompare,
+    ShowMerge,
+    CloseMerge,
+    MergeFilesEdited(text_editor::Action),
+    MergeUniqueToggled(bool),
+    RunMerge,
+    OpenMergedTab,
+    ShowHistory,
+    CloseHistory,
+    HistorySearchChanged(String),
+    HistoryPeriodChanged(Period),
+    OpenHistoryEntry(usize),
+    ShowBootstrap,
+    CloseBootstrap,
+    BootstrapDataChanged(String),
+    BootstrapResamplesChanged(String),
+    BootstrapSizeChanged(String),
+    RunBootstrap,
+    CancelBootstrap,
+    Bootstrap(PanelEvent<(Seed, BootstrapReport)>),
+    ShowWalk,
+    CloseWalk,
+    WalkLengthChanged(String),
+    WalkStartChanged(String),
+    WalkDriftChanged(String),
+    WalkPhiChanged(String),
+    WalkStepChanged(StepDistribution),
+    WalkScaleChanged(String),
+    WalkFilenameChanged(String),
+    RunWalk,
+    CancelWalk,
+    Walk(PanelEvent<(Vec<f64>, LineChart)>),
+    ExportWalk,
+    ShowMonteCarlo,
+    CloseMonteCarlo,
+    MonteCarloEstimateChanged(Estimate),
+    MonteCarloFormulaChanged(String),
+    MonteCarloLowerChanged(String),
+    MonteCarloUpperChanged(String),
+    MonteCarloSamplesChanged(String),
+    MonteCarloFilenameChanged(String),
+    RunMonteCarlo,
+    CancelMonteCarlo,
+    MonteCarlo(MonteCarloEvent),
+    ExportMonteCarlo,
+    ShowGraph,
+    CloseGraph,
+    GraphModelChanged(GraphModel),
+    GraphNodesChanged(String),
+    GraphEdgesChanged(String),
+    GraphFormatChanged(GraphFormat),
+    GraphFilenameChanged(String),
+    RunGraph,
+    CancelGraph,
+    Graph(PanelEvent<Graph>),
+    ExportGraph,
+    ShowDesign,
+    CloseDesign,
+    DesignKindChanged(DesignKind),
+    DesignTreatmentsChanged(String),
+    DesignBlocksChanged(String),
+    RunDesign,
+    CancelDesign,
+    Design(PanelEvent<(Seed, DesignTable)>),
+    ShowDistribution,
+    CloseDistribution,
+    DistributionKindChanged(DistributionKind),
+    DistributionParameterChanged(usize, String),
+    DistributionMinChanged(String),
+    DistributionMaxChanged(String),
+    DistributionCountChanged(String),
+    RunDistribution,
+    CancelDistribution,
+    Distribution(PanelEvent<(Seed, DistributionReport)>),
+    ShowWeights,
+    CloseWeights,
+    WeightsComponentsChanged(String),
+    WeightsConcentrationChanged(String),
+    WeightsCountChanged(String),
+    RunWeights,
+    ShowCorrelated,
+    CloseCorrelated,
+    CorrelatedMatrixChanged(String),
+    CorrelatedMeansChanged(String),
+    CorrelatedSdsChanged(String),
+    CorrelatedCountChanged(String),
+    RunCorrelated,
+    CancelCorrelated,
+    Correlated(PanelEvent<(Seed, MultivariateSample)>),
+    ShowCdfTable,
+    CloseCdfTable,
+    CdfTableEdited(text_editor::Action),
+    CdfTableCountChanged(String),
+    RunCdfTable,
+    PanelFilenameChanged(ExportPanel, String),
+    PanelFileFormatChanged(ExportPanel, ExportFormat),
+    PanelExport(ExportPanel),
+    PanelSeedChanged(ExportPanel, String),
+    PanelReplay(ExportPanel),
     ShowAbout,
     CloseAbout,
+    EnterPresentation,
+    ExitPresentation,
+    PresentationNext,
+    AnimateRevealToggled(bool),
+    SequentialRevealToggled(bool),
+    RevealNext,
+    SoundToggled(bool),
+    NotifyToggled(bool),
+    ConfettiToggled(bool),
+    WheelToggled(bool),
+    FilterChanged(String),
+    CountSliderToggled(bool),
+    CountSliderChanged(u32),
+    SliderMaxChanged(String),
+    SeparatorChanged(ThousandsSeparator),
+    NumberedToggled(bool),
+    PadWidthChanged(String),
+    FilterOnlyMatchesToggled(bool),
+    AnimationTick(Instant),
+    RunBenchmark,
+    BenchmarkFinished(Result<String, String>),
+    CloseBenchmark,
+    /// Sort, shuffle or reverse the active tab's results
+    Rearrange(Rearrange),
+    RemoveDuplicates,
+    /// Make the active tab's results the custom list to draw from
+    UseAsPool,
+    Replay,
+    /// Replay of the tab with this number
+    Replayed(usize, GenerationEvent),
 }
 
 struct RandomGeneratorApp {
+    window: window::Id,
     gui_version: String,
     generator: RandomGenerator,
+    /// Inputs the generator's source for the mode was installed with, sent to the worker along with its config
+    source_params: SourceParams,
     lower_bound: String,
     upper_bound: String,
     num_to_generate: String,
+    /// Total split up in partition mode
+    partition_total: String,
+    /// Tickets handed out so far in tickets mode, numbered on from `lower_bound`
+    tickets_issued: String,
+    /// Chance of success per try in the geometric and negative-binomial modes
+    success_chance: String,
+    /// Successes to wait for in negative-binomial mode
+    successes: String,
     filename: String,
-    error_message: String,
-    dark_mode: bool,
+    /// Save every tab into the file as a group instead of only the open one
+    save_all_tabs: bool,
+    /// What the last action did, shown under the buttons
+    status: Option<Status>,
+    settings: Settings,
+    settings_open: bool,
     about_open: bool,
-    theme: Theme,
+    test_data_open: bool,
+    test_data: TestDataPanel,
+    random_file_open: bool,
+    random_file: RandomFilePanel,
+    line_sample_open: bool,
+    line_sample: LineSamplePanel,
+    group_draw_open: bool,
+    group_draw: GroupDrawPanel,
+    games_open: bool,
+    games: GamesPanel,
+    bootstrap_open: bool,
+    bootstrap: BootstrapPanel,
+    compare_open: bool,
+    compare: ComparePanel,
+    merge_open: bool,
+    merge: MergePanel,
+    history_open: bool,
+    history: HistoryPanel,
+    /// Every draw made since the window opened, for the session report
+    session: Vec<SessionDraw>,
+    /// When the window opened, in seconds since the Unix epoch
+    session_started: i64,
+    walk_open: bool,
+    walk: WalkPanel,
+    monte_carlo_open: bool,
+    monte_carlo: MonteCarloPanel,
+    graph_open: bool,
+    graph: GraphPanel,
+    design_open: bool,
+    design: DesignPanel,
+    distribution_open: bool,
+    distribution: DistributionPanel,
+    weights_open: bool,
+    weights: WeightsPanel,
+    correlated_open: bool,
+    correlated: CorrelatedPanel,
+    cdf_table_open: bool,
+    cdf_table: CdfTablePanel,
+    /// A link asked to draw and waits for the user to confirm
+    link_draw_pending: bool,
+    presentation_open: bool,
+    presentation_index: usize,
+    reveal: Option<SlotReveal>,
+    generation_progress: Option<f32>,
+    generation_started: Option<Instant>,
+    cancel_flag: Arc<AtomicBool>,
+    revealed_count: usize,
+    sound: SoundPlayer,
+    confetti: Option<Confetti>,
+    /// Spinning wheel standing in for the reveal of a custom list draw in the presentation view
+    wheel: Option<Wheel>,
+    filter_input: String,
+    filter_only_matches: bool,
+    number_format: NumberFormat,
+    pad_width: String,
+    /// How often one value may come up in a batch with duplicates, as typed; empty for no limit
+    max_repeats_input: String,
+    /// How often one value may come up in a row with duplicates, as typed; empty for no limit
+    max_streak_input: String,
+    /// Draws a value sits out after being drawn, as typed; empty for no cooldown
+    cooldown_input: String,
+    /// Steps applied to each result, as typed, e.g. `scale 10, offset 5`
+    transform_input: String,
+    /// Results drawn so far this session, kept out of range draws while the option is on
+    exclude_drawn: Option<ExclusionSet<i64>>,
+    count_slider: bool,
+    slider_max: String,
     mode: GeneratorMode,
-    custom_list_input: String,
+    custom_list_input: text_editor::Content,
+    script: text_editor::Content,
+    strata: text_editor::Content,
+    /// Whether results are shown through the label table below
+    labels_enabled: bool,
+    /// Lookup from values to labels, as typed
+    labels: text_editor::Content,
+    tabs: Vec<tabs::ResultTab>,
+    active_tab: usize,
+    next_tab_number: usize,
+    benchmark_report: Option<String>,
+    auto_draw: AutoDraw,
+    /// Draw counting down with its config frozen, `None` when no countdown is running
+    locked_draw: Option<LockedDraw>,
+    random_org_quota: Option<random_org::Quota>,
 }
 
 impl Default for RandomGeneratorApp {
     fn default() -> Self {
-        let generator = RandomGenerator::new();
+        let mut generator = RandomGenerator::new();
+        // Items that aren't numbers are skipped unless the settings ask for strict lists
+        let _ = generator.set_lenient_list(!Settings::default().strict_list);
         let config = generator.get_config();
         // Extract config values and end borrow
         let lower_bound = config.lower_bound.to_string();
         let upper_bound = config.upper_bound.to_string();
         let num_to_generate = config.num_to_generate.to_string();
-        let mode = config.mode.clone();
-        let custom_list_input = config.custom_list_input.clone();
+        let source_params = SourceParams::default();
+        let partition_total = source_params.partition_total.to_string();
+        let success_chance = source_params.success_chance.to_string();
+        let successes = source_params.successes.to_string();
+        let mode = config.mode;
+        let custom_list_input = text_editor::Content::with_text(&config.custom_list_input);
 
         Self {
+            window: window::Id::unique(),
             gui_version: "v2.0".to_string(),
             generator,
+            source_params,
             lower_bound,
             upper_bound,
             num_to_generate,
+            partition_total,
+            tickets_issued: "0".to_string(),
+            success_chance,
+            successes,
             filename: "numbers.txt".to_owned(),
-            error_message: String::new(),
-            dark_mode: false,
+            save_all_tabs: false,
+            status: None,
+            settings: Settings::default(),
+            settings_open: false,
             about_open: false,
-            theme: Theme::Light,
+            test_data_open: false,
+            test_data: TestDataPanel::default(),
+            random_file_open: false,
+            random_file: RandomFilePanel::default(),
+            line_sample_open: false,
+            line_sample: LineSamplePanel::default(),
+            group_draw_open: false,
+            group_draw: GroupDrawPanel::default(),
+            games_open: false,
+            games: GamesPanel::default(),
+            bootstrap_open: false,
+            bootstrap: BootstrapPanel::default(),
+            compare_open: false,
+            compare: ComparePanel::default(),
+            merge_open: false,
+            merge: MergePanel::default(),
+            history_open: false,
+            history: HistoryPanel::default(),
+            session: Vec::new(),
+            session_started: history::now(),
+            walk_open: false,
+            walk: WalkPanel::default(),
+            monte_carlo_open: false,
+            monte_carlo: MonteCarloPanel::default(),
+            graph_open: false,
+            graph: GraphPanel::default(),
+            design_open: false,
+            design: DesignPanel::default(),
+            distribution_open: false,
+            distribution: DistributionPanel::default(),
+            weights_open: false,
+            weights: WeightsPanel::default(),
+            correlated_open: false,
+            correlated: CorrelatedPanel::default(),
+            cdf_table_open: false,
+            cdf_table: CdfTablePanel::default(),
+            link_draw_pending: false,
+            presentation_open: false,
+            presentation_index: 0,
+            reveal: None,
+            generation_progress: None,
+            generation_started: None,
+            cancel_flag: Arc::new(AtomicBool::new(false)),
+            revealed_count: 0,
+            sound: SoundPlayer::new(),
+            confetti: None,
+            wheel: None,
+            filter_input: String::new(),
+            filter_only_matches: false,
+            number_format: NumberFormat::default(),
+            pad_width: String::new(),
+            max_repeats_input: String::new(),
+            max_streak_input: String::new(),
+            cooldown_input: String::new(),
+            transform_input: String::new(),
+            exclude_drawn: None,
+            count_slider: false,
+            slider_max: "20".to_string(),
             mode,
             custom_list_input,
+            script: text_editor::Content::with_text(script_editor::EXAMPLE),
+            strata: text_editor::Content::with_text(strata_editor::EXAMPLE),
+            labels_enabled: false,
+            labels: text_editor::Content::with_text(labels_editor::EXAMPLE),
+            tabs: Vec::new(),
+            active_tab: 0,
+            next_tab_number: 0,
+            benchmark_report: None,
+            auto_draw: AutoDraw::default(),
+            locked_draw: None,
+            random_org_quota: None,
         }
     }
 }
 
 impl RandomGeneratorApp {
-    fn new() -> (Self, Task<Message>) {
-        (Self::default(), Task::none())
+    fn for_window(window: window::Id) -> Self {
+        let settings = Settings::load();
+        let mut app = Self {
+            window,
+            filename: settings.default_filename.clone(),
+            settings,
+            ..Self::default()
+        };
+        let _ = app.generator.set_lenient_list(!app.settings.strict_list);
+        plugins::install(&mut app.generator);
+        if !plugins::failures().is_empty() {
+            app.status = Some(Status::Error(format!("Some plugins could not be loaded: {}", plugins::failures().join("; "))));
+        }
+        app
+    }
+
+    /// Interface label in the chosen language
+    fn tr(&self, text: &'static str) -> &'static str {
+        self.settings.language.tr(text)
+    }
+
+    /// Which inputs the selected mode reads
+    fn mode_input(&self) -> SourceInput {
+        self.generator.source(self.mode).map_or(SourceInput::Bounds, |source| source.input())
+    }
+
+    /// The generator config with the typed inputs and settings applied
+    fn input_config(&self) -> GeneratorConfig {
+        // Apply bounds and count together so their order of entry doesn't matter
+        let mut config = self.generator.get_config().clone();
+        if self.mode_input() == SourceInput::Bounds {
+            let lower = self.lower_bound.trim().parse().ok();
+            // Tickets mode draws up to the last ticket issued instead of To
+            let upper = match self.mode {
+                GeneratorMode::TICKETS => lower.and_then(|first| tickets::last_ticket(first, &self.tickets_issued).ok()),
+                _ => self.upper_bound.trim().parse().ok(),
+            };
+            if let (Some(lower), Some(upper)) = (lower, upper) {
+                config.lower_bound = lower;
+                config.upper_bound = upper;
+            }
+        }
+        if let Ok(count) = self.num_to_generate.trim().parse() {
+            config.num_to_generate = count;
+        }
+        config.rng_backend = self.settings.rng_backend;
+        config.order = self.settings.result_order;
+        config.list_duplicates = self.settings.list_duplicates;
+        config.lenient_list = !self.settings.strict_list;
+        if let Ok(max) = parse_limit(&self.max_repeats_input) {
+            config.max_repeats = max;
+        }
+        if let Ok(max) = parse_limit(&self.max_streak_input) {
+            config.max_streak = max;
+        }
+        config.exclude_drawn = self.exclusions();
+        config
+    }
+
+    /// The typed inputs of the modes whose parameters live in their source; ones
+    /// that don't parse keep their last installed value and are flagged by `field_errors`
+    fn input_params(&self) -> SourceParams {
+        SourceParams {
+            script: self.script.text(),
+            strata: self.strata.text(),
+            partition_total: self.partition_total.trim().parse().unwrap_or(self.source_params.partition_total),
+            success_chance: self.success_chance.trim().parse().unwrap_or(self.source_params.success_chance),
+            successes: self.successes.trim().parse().unwrap_or(self.source_params.successes),
+        }
+    }
+
+    /// Install the typed source inputs and config in the generator
+    fn apply_inputs(&mut self, config: GeneratorConfig) -> Result<(), RandomGeneratorError> {
+        let params = self.input_params();
+        params.install(config.mode, &mut self.generator);
+        self.generator.set_config(config)?;
+        self.source_params = params;
+        Ok(())
+    }
+
+    /// The session's exclusions, when they apply to the selected mode
+    fn exclusions(&self) -> Option<ExclusionSet<i64>> {
+        self.exclude_drawn.clone().filter(|_| self.mode == GeneratorMode::RANGE || self.mode == GeneratorMode::TICKETS)
     }
 
     fn title(&self) -> String {
-        String::from("Random Generator")
+        self.tr("Random Generator").to_string()
     }
 
     fn update(&mut self, message: Message) -> Task<Message> {
@@ -96,95 +728,961 @@ impl RandomGeneratorApp {
             Message::UpperBoundChanged(value) => {
                 self.upper_bound = value;
             }
+            Message::PartitionTotalChanged(value) => {
+                self.partition_total = value;
+            }
+            Message::TicketsIssuedChanged(value) => {
+                self.tickets_issued = value;
+            }
+            Message::IssueTicket => {
+                self.issue_ticket();
+            }
+            Message::SuccessChanceChanged(value) => {
+                self.success_chance = value;
+            }
+            Message::SuccessesChanged(value) => {
+                self.successes = value;
+            }
             Message::NumToGenerateChanged(value) => {
                 self.num_to_generate = value;
             }
             Message::FilenameChanged(value) => {
                 self.filename = value;
             }
+            Message::OpenResults => {
+                return Task::perform(pick_results_file(self.filename.clone()), Message::ResultsFileChosen);
+            }
+            Message::ResultsFileChosen(Some(path)) => {
+                self.open_results(&path);
+            }
+            Message::ResultsFileChosen(None) => {}
+            Message::ExportReport => {
+                if self.session.is_empty() {
+                    self.status = Some(Status::Error("No draws this session".to_owned()));
+                } else {
+                    return Task::perform(report::pick_report_file(self.filename.clone()), Message::ReportFileChosen);
+                }
+            }
+            Message::ReportFileChosen(Some(path)) => {
+                self.save_report(&path);
+            }
+            Message::ReportFileChosen(None) => {}
+            Message::ConfirmLinkDraw => {
+                self.link_draw_pending = false;
+                return self.update(Message::Generate);
+            }
+            Message::CancelLinkDraw => {
+                self.link_draw_pending = false;
+            }
+            Message::ExcludeResults => {
+                let numbers = self.generator.get_numbers();
+                if let Some(excluded) = &mut self.exclude_drawn {
+                    let added = numbers.iter().filter(|&&num| excluded.insert(num)).count();
+                    self.status = Some(Status::Info(format!("Excluded {} more numbers", added)));
+                }
+            }
             Message::AllowDuplicatesToggled(value) => {
                 if let Err(e) = self.generator.set_allow_duplicates(value) {
-                    self.error_message = e.to_string();
+                    self.status = Some(Status::Error(e.to_string()));
+                }
+            }
+            Message::MaxRepeatsChanged(value) => {
+                self.max_repeats_input = value;
+            }
+            Message::MaxStreakChanged(value) => {
+                self.max_streak_input = value;
+            }
+            Message::ShuffleBagToggled(value) => {
+                self.generator.set_shuffle_bag(value.then(ShuffleBag::new));
+            }
+            Message::RefillShuffleBag => {
+                let mut bag = self.generator.shuffle_bag().cloned();
+                if let Some(bag) = &mut bag {
+                    bag.refill();
+                }
+                self.generator.set_shuffle_bag(bag);
+            }
+            Message::CooldownChanged(value) => {
+                // Keep the last valid cooldown while typing garbage; the field shows the problem
+                let draws = if value.trim().is_empty() { Some(0) } else { value.trim().parse::<usize>().ok() };
+                if let Some(draws) = draws {
+                    let cooldown = (draws > 0).then(|| {
+                        let mut cooldown = self.generator.cooldown().cloned().unwrap_or_default();
+                        cooldown.set_draws(draws);
+                        cooldown
+                    });
+                    self.generator.set_cooldown(cooldown);
+                }
+                self.cooldown_input = value;
+            }
+            Message::TransformChanged(value) => {
+                // Like the cooldown, the last valid steps stay in use until the text parses again
+                if let Ok(transforms) = Transform::parse_list(&value) {
+                    let _ = self.generator.set_transforms(transforms);
+                }
+                self.transform_input = value;
+            }
+            Message::ExcludeDrawnToggled(value) => {
+                self.exclude_drawn = value.then(ExclusionSet::new);
+            }
+            Message::ResetExcluded => {
+                if let Some(excluded) = &mut self.exclude_drawn {
+                    excluded.clear();
+                }
+            }
+            Message::ClearCooldown => {
+                let mut cooldown = self.generator.cooldown().cloned();
+                if let Some(cooldown) = &mut cooldown {
+                    cooldown.clear();
                 }
+                self.generator.set_cooldown(cooldown);
             }
             Message::ModeChanged(mode) => {
-                self.mode = mode.clone();
+                self.mode = mode;
                 if let Err(e) = self.generator.set_mode(mode) {
-                    self.error_message = e.to_string();
+                    self.status = Some(Status::Error(e.to_string()));
                 }
             }
-            Message::CustomListChanged(value) => {
-                self.custom_list_input = value.clone();
-                if let Err(e) = self.generator.set_custom_list_input(value) {
-                    self.error_message = e.to_string();
+            Message::ScriptEdited(action) => {
+                self.script.perform(action);
+            }
+            Message::StrataEdited(action) => {
+                self.strata.perform(action);
+            }
+            Message::LabelsToggled(value) => {
+                self.labels_enabled = value;
+                self.apply_labels();
+            }
+            Message::LabelsEdited(action) => {
+                self.labels.perform(action);
+                self.apply_labels();
+            }
+            Message::CustomListEdited(action) => {
+                let edited = action.is_edit();
+                self.custom_list_input.perform(action);
+                if edited {
+                    if let Err(e) = self.generator.set_custom_list_input(self.custom_list_input.text()) {
+                        self.status = Some(Status::Error(e.to_string()));
+                    }
                 }
             }
+            Message::PasteList => {
+                return iced::clipboard::read().map(Message::ListPasted);
+            }
+            Message::ListPasted(text) => {
+                self.paste_list(text.as_deref().unwrap_or_default());
+            }
             Message::Generate => {
+                if self.generation_progress.is_some() || self.locked_draw.is_some() {
+                    return Task::none();
+                }
+
                 // Clear previous error message
-                self.error_message.clear();
-
-                // If range mode, parse and set bounds
-                if self.mode == GeneratorMode::Range {
-                    // Parse and set lower bound
-                    if let Ok(lower) = self.lower_bound.parse() {
-                        if let Err(e) = self.generator.set_lower_bound(lower) {
-                            self.error_message = e.to_string();
-                            return Task::none();
-                        }
-                    } else {
-                        self.error_message = "Lower bound must be an integer".to_string();
-                        return Task::none();
-                    }
+                self.status = None;
 
-                    // Parse and set upper bound
-                    if let Ok(upper) = self.upper_bound.parse() {
-                        if let Err(e) = self.generator.set_upper_bound(upper) {
-                            self.error_message = e.to_string();
-                            return Task::none();
-                        }
-                    } else {
-                        self.error_message = "Upper bound must be an integer".to_string();
-                        return Task::none();
-                    }
+                if !self.field_errors().is_empty() {
+                    self.status = Some(Status::Error("Please fix the highlighted fields".to_string()));
+                    return Task::none();
                 }
 
-                // Parse and set generation count
-                if let Ok(count) = self.num_to_generate.parse() {
-                    if let Err(e) = self.generator.set_num_to_generate(count) {
-                        self.error_message = e.to_string();
-                        return Task::none();
-                    }
-                } else {
-                    self.error_message = "Count must be an integer".to_string();
+                if let Err(e) = self.apply_inputs(self.input_config()) {
+                    self.status = Some(Status::Error(e.to_string()));
                     return Task::none();
                 }
 
-                // Generate random numbers
-                if let Err(e) = self.generator.generate_numbers() {
-                    self.error_message = e.to_string();
+                if let Some(countdown) = self.settings.draw_countdown() {
+                    self.lock_draw(countdown, Instant::now());
+                    return Task::none();
+                }
+                return self.start_generation();
+            }
+            Message::CancelGeneration => {
+                self.cancel_flag.store(true, Ordering::Relaxed);
+            }
+            Message::AutoDrawIntervalChanged(interval) => {
+                self.auto_draw.set_interval(interval);
+            }
+            Message::AutoDrawUnitChanged(unit) => {
+                self.auto_draw.set_unit(unit);
+            }
+            Message::StartAutoDraw => {
+                self.start_auto_draw(Instant::now());
+            }
+            Message::StopAutoDraw => {
+                self.stop_auto_draw(None);
+            }
+            Message::AutoDrawTick(now) => {
+                return self.auto_draw_tick(now);
+            }
+            Message::LockedDrawTick(now) => {
+                return self.locked_draw_tick(now);
+            }
+            Message::CancelLockedDraw => {
+                self.locked_draw = None;
+                self.stop_auto_draw(None);
+            }
+            Message::NewWindow => {
+                // Opening windows is up to `Windows`, which owns every window's state
+            }
+            Message::Generation(GenerationEvent::Progress(fraction)) => {
+                if self.generation_progress.is_some() {
+                    self.generation_progress = Some(fraction);
+                }
+            }
+            Message::Generation(GenerationEvent::Finished(result)) => {
+                self.generation_progress = None;
+                let started = self.generation_started.take();
+                match result {
+                    Ok(draw) => {
+                        let Draw { numbers, config, params, random_org, bag, cooldown, excluded } = *draw;
+                        match random_org {
+                            Some(DrawSource::RandomOrg(quota)) => {
+                                self.random_org_quota = Some(quota);
+                                self.status = Some(Status::Info("Drawn by random.org".to_string()));
+                            }
+                            Some(DrawSource::Local(reason)) => {
+                                self.status = Some(Status::Info(format!("Drawn with the local generator: {}", reason)))
+                            }
+                            Some(DrawSource::Fallback(reason)) => {
+                                self.status = Some(Status::Error(format!("random.org unavailable, drew with the local generator: {}", reason)))
+                            }
+                            None => {}
+                        }
+                        // One copy for the session, the history and the publishers; the tab keeps the original
+                        let shared: Arc<[i64]> = Arc::from(numbers.as_slice());
+                        let source = params.source(config.mode);
+                        let draw = SessionDraw::numbers(history::now(), &config, source.as_deref(), Arc::clone(&shared));
+                        // The history is a convenience; a draw shouldn't fail because it can't be written
+                        let record = Task::perform(
+                            history::record_in_background(
+                                draw.time,
+                                draw.mode.clone(),
+                                draw.settings.clone(),
+                                draw.seed,
+                                Arc::clone(&shared),
+                                self.settings.history_retention(),
+                            ),
+                            Message::HistoryRecorded,
+                        );
+                        self.session.push(draw);
+                        let slow = started.is_some_and(|started| started.elapsed() >= notification::MIN_DURATION);
+                        if self.settings.notify && slow {
+                            // Only the few values shown are formatted: a slow draw can have millions
+                            notification::results_ready(
+                                numbers.iter().take(notification::PREVIEW_COUNT).map(|&num| self.number_format.format(num)).collect(),
+                                numbers.len(),
+                            );
+                        }
+                        let publish = match mqtt::Broker::parse(&self.settings.mqtt_broker) {
+                            Ok(Some(broker)) => Task::perform(
+                                mqtt::publish_draw(broker, self.settings.mqtt_topic.trim().to_string(), config.clone(), Arc::clone(&shared)),
+                                Message::MqttPublished,
+                            ),
+                            Ok(None) => Task::none(),
+                            Err(e) => Task::done(Message::MqttPublished(Err(e))),
+                        };
+                        let target = self.settings.osc_target.trim();
+                        let send = if target.is_empty() {
+                            Task::none()
+                        } else {
+                            Task::perform(
+                                osc::send_draw(target.to_string(), self.settings.osc_address.trim().to_string(), Arc::clone(&shared)),
+                                Message::OscSent,
+                            )
+                        };
+                        // Turning the bag off while drawing wins over what the draw left in it
+                        if bag.is_some() && self.generator.shuffle_bag().is_some() {
+                            self.generator.set_shuffle_bag(bag);
+                        }
+                        if excluded.is_some() && self.exclude_drawn.is_some() {
+                            self.exclude_drawn = excluded;
+                        }
+                        if let (Some(mut cooldown), Some(current)) = (cooldown, self.generator.cooldown()) {
+                            cooldown.set_draws(current.draws());
+                            self.generator.set_cooldown(Some(cooldown));
+                        }
+                        self.open_tab(numbers, Some((config, params)));
+                        if self.settings.autosave {
+                            self.save_results();
+                        }
+                        if !self.settings.sequential_reveal {
+                            self.start_reveal(0);
+                        }
+                        return Task::batch([record, publish, send]);
+                    }
+                    Err(e) => {
+                        self.status = Some(Status::Error(e.clone()));
+                        self.stop_auto_draw(Some(e));
+                    }
                 }
             }
             Message::Clear => {
-                self.generator.clear_numbers();
-                self.error_message.clear();
+                self.close_tab(self.active_tab);
+                self.status = None;
+            }
+            Message::CopyResults => {
+                return iced::clipboard::write(self.results_text());
+            }
+            Message::SelectTab(index) => {
+                self.select_tab(index);
+            }
+            Message::CloseTab(index) => {
+                self.close_tab(index);
             }
             Message::Save => {
                 if self.generator.get_numbers().is_empty() {
-                    self.error_message = "No numbers to save".to_owned();
+                    self.status = Some(Status::Error("No numbers to save".to_owned()));
                 } else {
-                    match self.generator.save_numbers(&self.filename) {
-                        Ok(_) => self.error_message = format!("Saved to {}", self.filename),
-                        Err(e) => self.error_message = format!("Save error: {}", e),
-                    }
+                    self.save_results();
                 }
             }
             Message::ToggleTheme => {
-                self.dark_mode = !self.dark_mode;
-                self.theme = if self.dark_mode {
-                    Theme::Dark
-                } else {
-                    Theme::Light
-                };
+                self.settings.dark_mode = !self.settings.dark_mode;
+                self.store_settings();
+            }
+            Message::ShowSettings => {
+                self.settings_open = true;
+            }
+            Message::CloseSettings => {
+                self.settings_open = false;
+            }
+            Message::LanguageChanged(language) => {
+                self.settings.language = language;
+                self.store_settings();
+            }
+            Message::RngBackendChanged(backend) => {
+                self.settings.rng_backend = backend;
+                self.store_settings();
+            }
+            Message::ResultOrderChanged(order) => {
+                self.settings.result_order = order;
+                self.store_settings();
+            }
+            Message::ListDuplicatesChanged(policy) => {
+                self.settings.list_duplicates = policy;
+                self.store_settings();
+            }
+            Message::StrictListToggled(value) => {
+                self.settings.strict_list = value;
+                if let Err(e) = self.generator.set_lenient_list(!value) {
+                    self.status = Some(Status::Error(e.to_string()));
+                }
+                self.store_settings();
+            }
+            Message::RandomOrgToggled(value) => {
+                self.settings.use_random_org = value;
+                self.store_settings();
+            }
+            Message::RandomOrgKeyChanged(value) => {
+                self.settings.random_org_key = value;
+                self.random_org_quota = None;
+                self.store_settings();
+            }
+            Message::DaemonToggled(value) => {
+                self.settings.use_daemon = value;
+                self.store_settings();
+            }
+            Message::MqttBrokerChanged(value) => {
+                self.settings.mqtt_broker = value;
+                self.store_settings();
+            }
+            Message::MqttTopicChanged(value) => {
+                self.settings.mqtt_topic = value;
+                self.store_settings();
+            }
+            Message::MqttPublished(result) => {
+                if let Err(e) = result {
+                    self.status = Some(Status::Error(format!("MQTT: {}", e)));
+                }
+            }
+            Message::OscTargetChanged(value) => {
+                self.settings.osc_target = value;
+                self.store_settings();
+            }
+            Message::OscAddressChanged(value) => {
+                self.settings.osc_address = value;
+                self.store_settings();
+            }
+            Message::OscSent(result) => {
+                if let Err(e) = result {
+                    self.status = Some(Status::Error(format!("OSC: {}", e)));
+                }
+            }
+            Message::HistoryRecorded(result) => {
+                if let Err(e) = result {
+                    self.status = Some(Status::Error(format!("History: {}", e)));
+                }
+            }
+            Message::DefaultFilenameChanged(value) => {
+                self.settings.default_filename = value;
+                self.store_settings();
+            }
+            Message::GroupHeaderChanged(value) => {
+                self.settings.group_header = value;
+                self.store_settings();
+            }
+            Message::DrawCountdownChanged(value) => {
+                self.settings.draw_countdown = value;
+                self.store_settings();
+            }
+            Message::HistoryDaysChanged(value) => {
+                self.settings.history_days = value;
+                self.store_settings();
+            }
+            Message::HistoryDrawsChanged(value) => {
+                self.settings.history_draws = value;
+                self.store_settings();
+            }
+            Message::SaveAllTabsToggled(value) => {
+                self.save_all_tabs = value;
+            }
+            Message::ExportFormatChanged(format) => {
+                match format {
+                    SaveFormat::Builtin(format) => {
+                        self.settings.export_format = format;
+                        self.settings.export_plugin = None;
+                    }
+                    SaveFormat::Plugin(name) => self.settings.export_plugin = Some(name),
+                }
+                self.store_settings();
+            }
+            Message::AutosaveToggled(value) => {
+                self.settings.autosave = value;
+                self.store_settings();
+            }
+            Message::HighContrastToggled(value) => {
+                self.settings.high_contrast = value;
+                self.store_settings();
+            }
+            Message::KeyPressed(window, key, modifiers) => {
+                // Every window hears every key press; only react to our own
+                if window == self.window {
+                    if let Some(message) = self.handle_key(key, modifiers) {
+                        return self.update(message);
+                    }
+                }
+            }
+            Message::FocusNext => {
+                return iced::widget::focus_next();
+            }
+            Message::FocusPrevious => {
+                return iced::widget::focus_previous();
+            }
+            Message::ShowTestData => {
+                self.test_data_open = true;
+            }
+            Message::CloseTestData => {
+                self.test_data_open = false;
+            }
+            Message::TestDataFieldToggled(field, selected) => {
+                self.test_data.toggle_field(field, selected);
+            }
+            Message::TestDataLocaleChanged(locale) => {
+                self.test_data.set_locale(locale);
+            }
+            Message::TestDataSourceChanged(source) => {
+                self.test_data.set_source(source);
+            }
+            Message::TestDataPatternChanged(pattern) => {
+                self.test_data.set_pattern(pattern);
+            }
+            Message::TestDataUniqueToggled(value) => {
+                self.test_data.set_unique(value);
+            }
+            Message::TestDataCodeLengthChanged(length) => {
+                self.test_data.set_code_length(length);
+            }
+            Message::TestDataCodeGroupChanged(group) => {
+                self.test_data.set_code_group(group);
+            }
+            Message::TestDataCodeCheckChanged(check) => {
+                self.test_data.set_code_check(check);
+            }
+            Message::TestDataCodeExcludeChanged(path) => {
+                self.test_data.set_code_exclude(path);
+            }
+            Message::TestDataPinLengthChanged(length) => {
+                self.test_data.set_pin_length(length);
+            }
+            Message::TestDataPinRuleToggled(rule, on) => {
+                self.test_data.set_pin_rule(rule, on);
+            }
+            Message::TestDataPassphraseWordsChanged(words) => {
+                self.test_data.set_passphrase_words(words);
+            }
+            Message::TestDataPassphraseSeparatorChanged(separator) => {
+                self.test_data.set_passphrase_separator(separator);
+            }
+            Message::TestDataPassphraseCaseChanged(case) => {
+                self.test_data.set_passphrase_case(case);
+            }
+            Message::TestDataWordlistChanged(path) => {
+                self.test_data.set_wordlist_path(path);
+            }
+            Message::TestDataAddressKindChanged(kind) => {
+                self.test_data.set_address_kind(kind);
+            }
+            Message::TestDataAddressRangeChanged(range) => {
+                self.test_data.set_address_range(range);
+            }
+            Message::TestDataSchemaEdited(action) => {
+                self.test_data.edit_schema(action);
+            }
+            Message::TestDataColumnsEdited(action) => {
+                self.test_data.edit_columns(action);
+            }
+            Message::TestDataCountChanged(count) => {
+                self.test_data.set_count(count);
+            }
+            Message::GenerateTestData => {
+                return self.run_panel(ExportPanel::TestData);
+            }
+            Message::ShowRandomFile => {
+                self.random_file_open = true;
+            }
+            Message::CloseRandomFile => {
+                // A running write carries on and reports when the panel is opened again
+                self.random_file_open = false;
+            }
+            Message::RandomFileSizeChanged(size) => {
+                self.random_file.set_size(size);
+            }
+            Message::RandomFileUnitChanged(unit) => {
+                self.random_file.set_unit(unit);
+            }
+            Message::RandomFileNameChanged(filename) => {
+                self.random_file.set_filename(filename);
+            }
+            Message::WriteRandomFile => {
+                return self.random_file.start();
+            }
+            Message::CancelRandomFile => {
+                self.random_file.cancel();
+            }
+            Message::RandomFile(event) => {
+                self.random_file.handle(event);
+            }
+            Message::ShowLineSample => {
+                self.line_sample_open = true;
+            }
+            Message::CloseLineSample => {
+                // A running sample carries on and shows its lines when the panel is opened again
+                self.line_sample_open = false;
+            }
+            Message::LineSamplePathChanged(path) => {
+                self.line_sample.set_path(path);
+            }
+            Message::LineSampleCountChanged(count) => {
+                self.line_sample.set_count(count);
+            }
+            Message::LineSampleFilenameChanged(filename) => {
+                self.line_sample.set_filename(filename);
+            }
+            Message::RunLineSample => {
+                return self.line_sample.start();
+            }
+            Message::CancelLineSample => {
+                self.line_sample.cancel();
+            }
+            Message::LineSample(event) => {
+                self.line_sample.handle(event);
+            }
+            Message::SaveLineSample => {
+                self.line_sample.save();
+            }
+            Message::ShowGroupDraw => {
+                self.group_draw_open = true;
+            }
+            Message::CloseGroupDraw => {
+                self.group_draw_open = false;
+            }
+            Message::GroupDrawFormatChanged(format) => {
+                self.group_draw.set_format(format);
+            }
+            Message::GroupDrawNamesEdited(action) => {
+                self.group_draw.edit_names(action);
+            }
+            Message::GroupDrawExclusionsEdited(action) => {
+                self.group_draw.edit_exclusions(action);
+            }
+            Message::GroupDrawItemsEdited(action) => {
+                self.group_draw.edit_items(action);
+            }
+            Message::GroupDrawForbiddenEdited(action) => {
+                self.group_draw.edit_forbidden(action);
+            }
+            Message::GroupDrawRevealToggled(reveal) => {
+                self.group_draw.set_reveal(reveal);
+            }
+            Message::GroupDrawFolderChanged(folder) => {
+                self.group_draw.set_folder(folder);
+            }
+            Message::RunGroupDraw => {
+                return self.run_panel(ExportPanel::GroupDraw);
+            }
+            Message::ShowGames => {
+                self.games_open = true;
+            }
+            Message::CloseGames => {
+                self.games_open = false;
+            }
+            Message::GameKindChanged(kind) => {
+                self.games.set_kind(kind);
+            }
+            Message::KenoPoolChanged(pool) => {
+                self.games.set_pool(pool);
+            }
+            Message::KenoPicksChanged(picks) => {
+                self.games.set_picks(picks);
+            }
+            Message::LootTableEdited(action) => {
+                self.games.edit_loot_table(action);
+            }
+            Message::PoolDiceChanged(dice) => {
+                self.games.set_dice(dice);
+            }
+            Message::PoolSidesChanged(sides) => {
+                self.games.set_sides(sides);
+            }
+            Message::PoolTargetChanged(target) => {
+                self.games.set_target(target);
+            }
+            Message::PoolExplodeChanged(explode) => {
+                self.games.set_explode(explode);
+            }
+            Message::GameCountChanged(count) => {
+                self.games.set_count(count);
+            }
+            Message::RunGame => {
+                return self.run_panel(ExportPanel::Games);
+            }
+            Message::ShowCompare => {
+                self.open_compare();
+            }
+            Message::CloseCompare => {
+                self.compare_open = false;
+            }
+            Message::CompareTargetChanged(target) => {
+                self.compare.set_target(target);
+                self.run_compare();
+            }
+            Message::CompareFileChanged(path) => {
+                self.compare.set_data_path(path);
+            }
+            Message::RunCompare => {
+                self.run_compare();
+            }
+            Message::ShowMerge => {
+                self.merge_open = true;
+            }
+            Message::CloseMerge => {
+                self.merge_open = false;
+            }
+            Message::MergeFilesEdited(action) => {
+                self.merge.edit_files(action);
+            }
+            Message::MergeUniqueToggled(unique) => {
+                self.merge.set_unique(unique);
+            }
+            Message::RunMerge => {
+                self.merge.run();
+            }
+            Message::OpenMergedTab => {
+                self.open_merged_tab();
+            }
+            Message::ShowHistory => {
+                self.history_open = true;
+                self.history.refresh();
+            }
+            Message::CloseHistory => {
+                self.history_open = false;
+            }
+            Message::HistorySearchChanged(search) => {
+                self.history.set_search(search);
+            }
+            Message::HistoryPeriodChanged(period) => {
+                self.history.set_period(period);
+            }
+            Message::OpenHistoryEntry(index) => {
+                self.open_history_entry(index);
+            }
+            Message::ShowBootstrap => {
+                self.bootstrap_open = true;
+            }
+            Message::CloseBootstrap => {
+                self.bootstrap_open = false;
+            }
+            Message::BootstrapDataChanged(path) => {
+                self.bootstrap.set_data_path(path);
+            }
+            Message::BootstrapResamplesChanged(resamples) => {
+                self.bootstrap.set_resamples(resamples);
+            }
+            Message::BootstrapSizeChanged(size) => {
+                self.bootstrap.set_size(size);
+            }
+            Message::RunBootstrap => {
+                return self.run_panel(ExportPanel::Bootstrap);
+            }
+            Message::CancelBootstrap => {
+                self.bootstrap.cancel();
+            }
+            Message::Bootstrap(event) => {
+                let finished = matches!(event, PanelEvent::Finished(_));
+                self.bootstrap.handle(event);
+                if finished {
+                    self.record_panel_draw(ExportPanel::Bootstrap);
+                }
+            }
+            Message::ShowWalk => {
+                self.walk_open = true;
+            }
+            Message::CloseWalk => {
+                self.walk_open = false;
+            }
+            Message::WalkLengthChanged(length) => {
+                self.walk.set_length(length);
+            }
+            Message::WalkStartChanged(start) => {
+                self.walk.set_start(start);
+            }
+            Message::WalkDriftChanged(drift) => {
+                self.walk.set_drift(drift);
+            }
+            Message::WalkPhiChanged(phi) => {
+                self.walk.set_phi(phi);
+            }
+            Message::WalkStepChanged(step) => {
+                self.walk.set_step(step);
+            }
+            Message::WalkScaleChanged(scale) => {
+                self.walk.set_scale(scale);
+            }
+            Message::WalkFilenameChanged(filename) => {
+                self.walk.set_filename(filename);
+            }
+            Message::RunWalk => {
+                return self.walk.run();
+            }
+            Message::CancelWalk => {
+                self.walk.cancel();
+            }
+            Message::Walk(event) => {
+                self.walk.handle(event);
+            }
+            Message::ExportWalk => {
+                self.walk.export();
+            }
+            Message::ShowMonteCarlo => {
+                self.monte_carlo_open = true;
+            }
+            Message::CloseMonteCarlo => {
+                // A running demo carries on and shows its result when the panel is opened again
+                self.monte_carlo_open = false;
+            }
+            Message::MonteCarloEstimateChanged(estimate) => {
+                self.monte_carlo.set_estimate(estimate);
+            }
+            Message::MonteCarloFormulaChanged(formula) => {
+                self.monte_carlo.set_formula(formula);
+            }
+            Message::MonteCarloLowerChanged(lower) => {
+                self.monte_carlo.set_lower(lower);
+            }
+            Message::MonteCarloUpperChanged(upper) => {
+                self.monte_carlo.set_upper(upper);
+            }
+            Message::MonteCarloSamplesChanged(samples) => {
+                self.monte_carlo.set_samples(samples);
+            }
+            Message::MonteCarloFilenameChanged(filename) => {
+                self.monte_carlo.set_filename(filename);
+            }
+            Message::RunMonteCarlo => {
+                return self.monte_carlo.start();
+            }
+            Message::CancelMonteCarlo => {
+                self.monte_carlo.cancel();
+            }
+            Message::MonteCarlo(event) => {
+                self.monte_carlo.handle(event);
+            }
+            Message::ExportMonteCarlo => {
+                self.monte_carlo.export();
+            }
+            Message::ShowGraph => {
+                self.graph_open = true;
+            }
+            Message::CloseGraph => {
+                self.graph_open = false;
+            }
+            Message::GraphModelChanged(model) => {
+                self.graph.set_model(model);
+            }
+            Message::GraphNodesChanged(nodes) => {
+                self.graph.set_nodes(nodes);
+            }
+            Message::GraphEdgesChanged(edges) => {
+                self.graph.set_edges(edges);
+            }
+            Message::GraphFormatChanged(format) => {
+                self.graph.set_format(format);
+            }
+            Message::GraphFilenameChanged(filename) => {
+                self.graph.set_filename(filename);
+            }
+            Message::RunGraph => {
+                return self.graph.run();
+            }
+            Message::CancelGraph => {
+                self.graph.cancel();
+            }
+            Message::Graph(event) => {
+                self.graph.handle(event);
+            }
+            Message::ExportGraph => {
+                self.graph.export();
+            }
+            Message::ShowDesign => {
+                self.design_open = true;
+            }
+            Message::CloseDesign => {
+                self.design_open = false;
+            }
+            Message::DesignKindChanged(kind) => {
+                self.design.set_kind(kind);
+            }
+            Message::DesignTreatmentsChanged(treatments) => {
+                self.design.set_treatments(treatments);
+            }
+            Message::DesignBlocksChanged(blocks) => {
+                self.design.set_blocks(blocks);
+            }
+            Message::RunDesign => {
+                return self.run_panel(ExportPanel::Design);
+            }
+            Message::CancelDesign => {
+                self.design.cancel();
+            }
+            Message::Design(event) => {
+                let finished = matches!(event, PanelEvent::Finished(_));
+                self.design.handle(event);
+                if finished {
+                    self.record_panel_draw(ExportPanel::Design);
+                }
+            }
+            Message::ShowDistribution => {
+                self.distribution_open = true;
+            }
+            Message::CloseDistribution => {
+                self.distribution_open = false;
+            }
+            Message::DistributionKindChanged(kind) => {
+                self.distribution.set_kind(kind);
+            }
+            Message::DistributionParameterChanged(index, value) => {
+                self.distribution.set_parameter(index, value);
+            }
+            Message::DistributionMinChanged(min) => {
+                self.distribution.set_min(min);
+            }
+            Message::DistributionMaxChanged(max) => {
+                self.distribution.set_max(max);
+            }
+            Message::DistributionCountChanged(count) => {
+                self.distribution.set_count(count);
+            }
+            Message::RunDistribution => {
+                return self.run_panel(ExportPanel::Distribution);
+            }
+            Message::CancelDistribution => {
+                self.distribution.cancel();
+            }
+            Message::Distribution(event) => {
+                let finished = matches!(event, PanelEvent::Finished(_));
+                self.distribution.handle(event);
+                if finished {
+                    self.record_panel_draw(ExportPanel::Distribution);
+                }
+            }
+            Message::ShowWeights => {
+                self.weights_open = true;
+            }
+            Message::CloseWeights => {
+                self.weights_open = false;
+            }
+            Message::WeightsComponentsChanged(components) => {
+                self.weights.set_components(components);
+            }
+            Message::WeightsConcentrationChanged(concentration) => {
+                self.weights.set_concentration(concentration);
+            }
+            Message::WeightsCountChanged(count) => {
+                self.weights.set_count(count);
+            }
+            Message::RunWeights => {
+                return self.run_panel(ExportPanel::Weights);
+            }
+            Message::ShowCorrelated => {
+                self.correlated_open = true;
+            }
+            Message::CloseCorrelated => {
+                self.correlated_open = false;
+            }
+            Message::CorrelatedMatrixChanged(correlation) => {
+                self.correlated.set_correlation(correlation);
+            }
+            Message::CorrelatedMeansChanged(means) => {
+                self.correlated.set_means(means);
+            }
+            Message::CorrelatedSdsChanged(sds) => {
+                self.correlated.set_sds(sds);
+            }
+            Message::CorrelatedCountChanged(count) => {
+                self.correlated.set_count(count);
+            }
+            Message::RunCorrelated => {
+                return self.run_panel(ExportPanel::Correlated);
+            }
+            Message::CancelCorrelated => {
+                self.correlated.cancel();
+            }
+            Message::Correlated(event) => {
+                let finished = matches!(event, PanelEvent::Finished(_));
+                self.correlated.handle(event);
+                if finished {
+                    self.record_panel_draw(ExportPanel::Correlated);
+                }
+            }
+            Message::ShowCdfTable => {
+                self.cdf_table_open = true;
+            }
+            Message::CloseCdfTable => {
+                self.cdf_table_open = false;
+            }
+            Message::CdfTableEdited(action) => {
+                self.cdf_table.edit_table(action);
+            }
+            Message::CdfTableCountChanged(count) => {
+                self.cdf_table.set_count(count);
+            }
+            Message::RunCdfTable => {
+                return self.run_panel(ExportPanel::CdfTable);
+            }
+            Message::PanelFilenameChanged(panel, filename) => {
+                self.export_file(panel).set_filename(filename);
+            }
+            Message::PanelFileFormatChanged(panel, format) => {
+                self.export_file(panel).set_format(format);
+            }
+            Message::PanelExport(panel) => {
+                self.export(panel);
+            }
+            Message::PanelSeedChanged(panel, text) => {
+                self.panel_seed(panel).set_text(text);
+            }
+            Message::PanelReplay(panel) => {
+                return self.replay_panel(panel);
             }
             Message::ShowAbout => {
                 self.about_open = true;
@@ -192,34 +1690,430 @@ impl RandomGeneratorApp {
             Message::CloseAbout => {
                 self.about_open = false;
             }
+            Message::EnterPresentation => {
+                self.presentation_open = true;
+                self.presentation_index = 0;
+                self.start_reveal(0);
+                return window::change_mode(self.window, window::Mode::Fullscreen);
+            }
+            Message::ExitPresentation => {
+                self.presentation_open = false;
+                self.confetti = None;
+                self.wheel = None;
+                return window::change_mode(self.window, window::Mode::Windowed);
+            }
+            Message::PresentationNext => {
+                let count = self.generator.get_numbers().len();
+                if self.presentation_index + 1 < count {
+                    self.presentation_index += 1;
+                    self.start_reveal(self.presentation_index);
+                }
+            }
+            Message::AnimateRevealToggled(value) => {
+                self.settings.animate_reveal = value;
+                if !value {
+                    self.reveal = None;
+                }
+                self.store_settings();
+            }
+            Message::SequentialRevealToggled(value) => {
+                self.settings.sequential_reveal = value;
+                self.revealed_count = 0;
+                self.store_settings();
+            }
+            Message::RevealNext => {
+                if self.revealed_count < self.generator.get_numbers().len() {
+                    self.revealed_count += 1;
+                    self.start_reveal(self.revealed_count - 1);
+                }
+            }
+            Message::SoundToggled(value) => {
+                self.settings.sound = value;
+                self.store_settings();
+            }
+            Message::NotifyToggled(value) => {
+                self.settings.notify = value;
+                self.store_settings();
+            }
+            Message::ConfettiToggled(value) => {
+                self.settings.confetti = value;
+                if !value {
+                    self.confetti = None;
+                }
+                self.store_settings();
+            }
+            Message::WheelToggled(value) => {
+                self.settings.wheel = value;
+                if !value {
+                    self.wheel = None;
+                }
+                self.store_settings();
+            }
+            Message::FilterChanged(value) => {
+                self.filter_input = value;
+            }
+            Message::FilterOnlyMatchesToggled(value) => {
+                self.filter_only_matches = value;
+            }
+            Message::CountSliderToggled(value) => {
+                self.count_slider = value;
+            }
+            Message::CountSliderChanged(value) => {
+                self.num_to_generate = value.to_string();
+            }
+            Message::SliderMaxChanged(value) => {
+                self.slider_max = value;
+            }
+            Message::SeparatorChanged(separator) => {
+                self.number_format.separator = separator;
+            }
+            Message::NumberedToggled(value) => {
+                self.number_format.numbered = value;
+            }
+            Message::PadWidthChanged(value) => {
+                // Empty means no padding; keep the last valid width while typing garbage
+                if value.trim().is_empty() {
+                    self.number_format.min_width = 0;
+                } else if let Ok(width) = value.trim().parse::<usize>() {
+                    self.number_format.min_width = width.min(20);
+                }
+                self.pad_width = value;
+            }
+            Message::AnimationTick(now) => {
+                if let Some(reveal) = &mut self.reveal {
+                    let stepped = reveal.tick(now);
+                    if reveal.is_finished() {
+                        self.reveal = None;
+                        self.play(Effect::Chime);
+                        self.celebrate_if_complete();
+                    } else if stepped {
+                        self.play(Effect::Tick);
+                    }
+                }
+                if let Some(wheel) = self.wheel.as_mut().filter(|wheel| !wheel.is_finished()) {
+                    let moved = wheel.tick(now);
+                    if wheel.is_finished() {
+                        self.play(Effect::Chime);
+                        self.celebrate_if_complete();
+                    } else if moved {
+                        self.play(Effect::Tick);
+                    }
+                }
+                if let Some(confetti) = &mut self.confetti {
+                    confetti.tick(now);
+                    if confetti.is_finished() {
+                        self.confetti = None;
+                    }
+                }
+            }
+            Message::RunBenchmark => {
+                if self.generation_progress.is_some() || !self.field_errors().is_empty() {
+                    return Task::none();
+                }
+                self.benchmark_report = Some(self.tr("Benchmarking...").to_string());
+                return Task::run(worker::benchmark(self.input_config(), self.input_params()), Message::BenchmarkFinished);
+            }
+            Message::BenchmarkFinished(result) => {
+                self.benchmark_report = Some(result.unwrap_or_else(|e| e));
+            }
+            Message::CloseBenchmark => {
+                self.benchmark_report = None;
+            }
+            Message::Rearrange(how) => {
+                self.rearrange_tab(how);
+            }
+            Message::RemoveDuplicates => {
+                self.remove_duplicates_from_tab();
+            }
+            Message::UseAsPool => {
+                self.use_results_as_pool();
+            }
+            Message::Replay => {
+                let Some(tab) = self.tabs.get(self.active_tab) else {
+                    return Task::none();
+                };
+                let Some((config, params)) = tab.draw.clone() else {
+                    return Task::none();
+                };
+                let number = tab.number;
+                self.status = None;
+                // Replays run locally; the seed gives the same numbers wherever the draw ran
+                let replay = worker::generate(config, params, None, None, Arc::new(AtomicBool::new(false)));
+                return Task::run(replay, move |event| Message::Replayed(number, event));
+            }
+            Message::Replayed(number, GenerationEvent::Finished(result)) => {
+                let Some(tab) = self.tabs.iter().find(|tab| tab.number == number) else {
+                    return Task::none();
+                };
+                self.status = Some(match result {
+                    Ok(draw) if tab.holds(&draw.numbers) => Status::Info(format!("Replay matches draw #{}", number)),
+                    Ok(_) => Status::Error(format!("Replay differs from draw #{}", number)),
+                    Err(e) => Status::Error(e),
+                });
+            }
+            Message::Replayed(_, GenerationEvent::Progress(_)) => {}
         }
         Task::none()
     }
 
-    fn view(&self) -> Element<Message> {
+    fn subscription(&self) -> Subscription<Message> {
+        let keys = accessibility::key_events();
+        let spinning = self.wheel.as_ref().is_some_and(|wheel| !wheel.is_finished());
+        let frames = if self.reveal.is_some() || self.confetti.is_some() || spinning {
+            window::frames().map(Message::AnimationTick)
+        } else {
+            Subscription::none()
+        };
+        let auto_draw = if self.auto_draw.is_running() {
+            Subscription::run(auto_draw::ticks).map(Message::AutoDrawTick)
+        } else {
+            Subscription::none()
+        };
+        let countdown = if self.locked_draw.is_some() {
+            window::frames().map(Message::LockedDrawTick)
+        } else {
+            Subscription::none()
+        };
+        Subscription::batch([keys, frames, auto_draw, countdown])
+    }
+
+    /// Draw from the generator's config in the background
+    fn start_generation(&mut self) -> Task<Message> {
+        self.generation_progress = Some(0.0);
+        self.generation_started = Some(Instant::now());
+        self.cancel_flag = Arc::new(AtomicBool::new(false));
+        let config = self.generator.get_config().clone();
+        let params = self.source_params.clone();
+        let random_org_key = (self.settings.use_random_org && !self.settings.random_org_key.trim().is_empty())
+            .then(|| self.settings.random_org_key.trim().to_string());
+        let daemon = self.settings.use_daemon.then(daemon::default_endpoint);
+        Task::run(
+            worker::generate(config, params, random_org_key, daemon, self.cancel_flag.clone()),
+            Message::Generation,
+        )
+    }
+
+    /// Start the slot-machine reveal for results from `first_index` on, if enabled
+    fn start_reveal(&mut self, first_index: usize) {
+        if self.generator.get_numbers().is_empty() {
+            return;
+        }
+        self.wheel = self.new_wheel(first_index);
+        if self.wheel.is_some() {
+            return;
+        }
+        if self.settings.animate_reveal {
+            self.reveal = Some(SlotReveal::new(Instant::now(), first_index));
+        } else {
+            self.play(Effect::Chime);
+            self.celebrate_if_complete();
+        }
+    }
+
+    /// How much of the current round is left in the shuffle bag
+    fn bag_status(&self, bag: &ShuffleBag<i64>) -> String {
+        if bag.size() == 0 {
+            self.tr("The bag is full").to_string()
+        } else {
+            i18n::fill(self.tr("{} of {} left in the bag"), &[&bag.left(), &bag.size()])
+        }
+    }
+
+    /// How many values the typed inputs draw from and, with duplicates, how likely a repeat is;
+    /// `None` for modes without a countable pool or while the range or list doesn't parse
+    fn draw_space_hint(&self, field_errors: &FieldErrors) -> Option<String> {
+        if field_errors.lower.is_some() || field_errors.upper.is_some() || field_errors.custom_list.is_some() {
+            return None;
+        }
+        let space = self.generator.draw_space(&self.input_config());
+        let plain = NumberFormat { separator: self.number_format.separator, ..Default::default() };
+        let mut parts = vec![i18n::fill(self.tr("Pool: {} values"), &[&plain.format(space.values?)])];
+        if let Some(capacity) = space.capacity.filter(|&capacity| Some(capacity) != space.values) {
+            parts.push(i18n::fill(self.tr("at most {} per draw"), &[&plain.format(capacity)]));
+        }
+        if let Some(chance) = space.collision_chance {
+            parts.push(i18n::fill(self.tr("chance of a repeat: {}"), &[&percent(chance)]));
+        }
+        Some(parts.join(", "))
+    }
+
+    /// Values sitting out because of the cooldown, `None` when nothing is cooling down
+    fn resting_values(&self) -> Option<String> {
+        let resting: Vec<String> = self.generator.cooldown()?.recent().map(|value| self.number_format.format(value)).collect();
+        (!resting.is_empty()).then(|| i18n::fill(self.tr("Sitting out: {}"), &[&resting.join(", ")]))
+    }
+
+    /// Wheel landing on the result at `index`, when presenting a custom list draw with the wheel turned on
+    fn new_wheel(&self, index: usize) -> Option<Wheel> {
+        if !(self.presentation_open && self.settings.wheel) {
+            return None;
+        }
+        let (config, _) = self.tabs.get(self.active_tab)?.draw.as_ref()?;
+        if config.mode != GeneratorMode::CUSTOM_LIST {
+            return None;
+        }
+        let numbers = self.generator.get_numbers();
+        let mut entries = ListSource::weights(config);
+        // Without repeats, values shown earlier in the presentation are off the wheel
+        if !config.allow_duplicates {
+            entries.retain(|(value, _)| !numbers[..index].contains(value));
+        }
+        let target = entries.iter().position(|(value, _)| *value == numbers[index])?;
+        if entries.len() > Wheel::MAX_SEGMENTS {
+            return None;
+        }
+        let entries = entries.into_iter().map(|(value, weight)| (self.number_format.format(value), weight)).collect();
+        Some(Wheel::new(Instant::now(), entries, target))
+    }
+
+    /// Fire the confetti once the last result of a presentation has been shown
+    fn celebrate_if_complete(&mut self) {
+        let is_last = self.presentation_index + 1 >= self.generator.get_numbers().len();
+        if self.presentation_open && self.settings.confetti && is_last {
+            self.confetti = Some(Confetti::new(Instant::now()));
+        }
+    }
+
+    fn play(&self, effect: Effect) {
+        if self.settings.sound {
+            self.sound.play(effect);
+        }
+    }
+
+    /// Text shown for a result, spinning while the reveal animation runs
+    fn display_number(&self, index: usize, num: i64) -> String {
+        let formatted = self.number_format.format(num);
+        match &self.reveal {
+            Some(reveal) if reveal.applies_to(index) => reveal.display(num, &formatted),
+            _ => formatted,
+        }
+    }
+
+    /// Write the current results to the chosen file in the configured export format,
+    /// or every tab as a group when asked to
+    fn save_results(&mut self) {
+        let saved = match self.save_format() {
+            SaveFormat::Builtin(format) if self.save_all_tabs && self.tabs.len() > 1 => self.save_groups(format),
+            SaveFormat::Builtin(format) => self.generator.save_numbers_as(&self.filename, format, &self.number_format),
+            SaveFormat::Plugin(name) => self.generator.save_numbers_with(&self.filename, &name, &self.number_format),
+        };
+        match saved {
+            Ok(_) => self.status = Some(Status::Info(format!("Saved to {}", self.filename))),
+            Err(e) => self.status = Some(Status::Error(format!("Save error: {}", e))),
+        }
+    }
+
+    /// Load a saved results file into a new tab, to look at, save in another format or exclude
+    fn open_results(&mut self, path: &str) {
+        let mut loaded = RandomGenerator::new();
+        match loaded.load_numbers(path) {
+            Ok(()) => {
+                self.open_tab(loaded.get_numbers().to_vec(), None);
+                // Nothing to reveal; the numbers were drawn before
+                self.revealed_count = self.generator.get_numbers().len();
+                self.status = Some(Status::Info(format!("Opened {}", path)));
+            }
+            Err(e) => self.status = Some(Status::Error(format!("Open error: {}: {}", path, e))),
+        }
+    }
+
+    /// Make pasted spreadsheet cells the custom list; a column of names is drawn by number and shown as labels
+    fn paste_list(&mut self, text: &str) {
+        let pasted = match PastedList::<i64>::parse(text) {
+            Ok(pasted) => pasted,
+            Err(RandomGeneratorError::EmptyList) => {
+                self.status = Some(Status::Error("The clipboard holds no list".to_string()));
+                return;
+            }
+            Err(e) => {
+                self.status = Some(Status::Error(e.to_string()));
+                return;
+            }
+        };
+        if let Err(e) = self.set_custom_list(pasted.to_list_input()) {
+            self.status = Some(Status::Error(e.to_string()));
+            return;
+        }
+        self.mode = GeneratorMode::CUSTOM_LIST;
+        if let Err(e) = self.generator.set_mode(GeneratorMode::CUSTOM_LIST) {
+            self.status = Some(Status::Error(e.to_string()));
+            return;
+        }
+        self.status = Some(Status::Info(match &pasted {
+            PastedList::Numbers(numbers) => format!("Pasted {} numbers", numbers.len()),
+            PastedList::Names(_) => {
+                self.labels = text_editor::Content::with_text(&pasted.labels().to_string());
+                self.labels_enabled = true;
+                self.apply_labels();
+                format!("Pasted {} names, drawn by number and shown as labels", pasted.len())
+            }
+        }));
+    }
+
+    /// Every tab's results in one file, each under a header from the settings' template
+    fn save_groups(&self, format: ExportFormat) -> Result<(), RandomGeneratorError> {
+        let header: GroupHeader = self.settings.group_header.parse()?;
+        let groups: Vec<Vec<i64>> = self.tabs.iter().map(|tab| tab.numbers.clone()).collect();
+        fs::write(&self.filename, format.render_groups(&groups, &header, &self.number_format))?;
+        Ok(())
+    }
+
+    /// Current results as text, one formatted value per line
+    fn results_text(&self) -> String {
+        self.generator
+            .get_numbers()
+            .iter()
+            .enumerate()
+            .map(|(index, &num)| self.number_format.format_at(index, num))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Results currently visible, honoring the one-by-one reveal
+    fn visible_numbers(&self) -> &[i64] {
+        let numbers = self.generator.get_numbers();
+        if self.settings.sequential_reveal {
+            &numbers[..self.revealed_count.min(numbers.len())]
+        } else {
+            numbers
+        }
+    }
+
+    fn view(&self) -> Element<'_, Message> {
+        if let Some(lock) = &self.locked_draw {
+            return self.locked_draw_view(lock);
+        }
+        if self.presentation_open {
+            return self.presentation_view();
+        }
+
         let header = row![
-            text("Random Generator")
+            text(self.tr("Random Generator"))
                 .size(18)
-                .color(if self.dark_mode {
+                .color(if self.settings.dark_mode {
                     Color::from_rgb(0.9, 0.9, 0.9)
                 } else {
                     Color::BLACK
                 }),
             Space::with_width(Length::Fill),
-            button(text(if self.dark_mode { "Light" } else { "Dark" })
+            button(text(self.tr(if self.settings.dark_mode { "Light" } else { "Dark" }))
                 .size(14))
                 .on_press(Message::ToggleTheme)
+                .padding(self.button_padding())
                 .style(move |_theme: &Theme, status| {
+                    if self.settings.high_contrast {
+                        return accessibility::high_contrast_button(self.settings.dark_mode, status);
+                    }
                     let is_pressed = status == button::Status::Pressed;
                     button::Style {
                         background: Some(Background::Color(
                             if is_pressed {
-                                if self.dark_mode {
+                                if self.settings.dark_mode {
                                     Color::from_rgb(0.2, 0.2, 0.25)
                                 } else {
                                     Color::from_rgb(0.8, 0.8, 0.85)
                                 }
-                            } else if self.dark_mode {
+                            } else if self.settings.dark_mode {
                                 Color::from_rgb(0.3, 0.3, 0.35)
                             } else {
                                 Color::from_rgb(0.9, 0.9, 0.9)
@@ -230,7 +2124,7 @@ impl RandomGeneratorApp {
                             width: 0.0,
                             radius: 12.0.into(),
                         },
-                        text_color: if self.dark_mode {
+                        text_color: if self.settings.dark_mode {
                             Color::from_rgb(0.9, 0.9, 0.9)
                         } else {
                             Color::BLACK
@@ -240,7 +2134,6 @@ impl RandomGeneratorApp {
                             offset: Vector::new(0.0, if is_pressed { 1.0 } else { 2.0 }),
                             blur_radius: if is_pressed { 2.0 } else { 4.0 },
                         },
-                        ..Default::default()
                     }
                 })
         ]
@@ -250,129 +2143,176 @@ impl RandomGeneratorApp {
         // Mode picker
         let mode_picker = container(
             row![
-                text("Mode:").size(14),
+                text(self.tr("Mode:")).size(14),
                 pick_list(
-                    &[GeneratorMode::Range, GeneratorMode::CustomList][..],
-                    Some(self.mode.clone()),
+                    self.generator.modes(),
+                    Some(self.mode),
                     Message::ModeChanged
                 )
                 .text_size(14)
-                .style(move |_theme: &Theme, _status| {
-                    pick_list::Style {
-                        placeholder_color: if self.dark_mode {
-                            Color::from_rgb(0.6, 0.6, 0.6)
-                        } else {
-                            Color::from_rgb(0.4, 0.4, 0.4)
-                        },
-                        handle_color: if self.dark_mode {
-                            Color::from_rgb(0.7, 0.7, 0.7)
-                        } else {
-                            Color::from_rgb(0.4, 0.4, 0.4)
-                        },
-                        text_color: if self.dark_mode {
-                            Color::from_rgb(0.9, 0.9, 0.9)
-                        } else {
-                            Color::BLACK
-                        },
-                        background: Background::Color(
-                            if self.dark_mode {
-                                Color::from_rgb(0.25, 0.25, 0.3)
-                            } else {
-                                Color::WHITE
-                            }
-                        ),
-                        border: Border {
-                            color: if self.dark_mode {
-                                Color::from_rgb(0.4, 0.4, 0.45)
-                            } else {
-                                Color::from_rgb(0.8, 0.8, 0.8)
-                            },
-                            width: 1.0,
-                            radius: 6.0.into(),
-                        },
-                    }
-                }),
+                .style(move |_theme: &Theme, _status| get_pick_list_style(self.settings.dark_mode, self.settings.high_contrast)),
             ]
                 .spacing(6)
                 .align_y(alignment::Vertical::Center)
         )
             .padding(2);
 
+        let field_errors = self.field_errors();
+        let lower_valid = field_errors.lower.is_none();
+        let upper_valid = field_errors.upper.is_none();
+        let count_valid = field_errors.count.is_none();
+        let total_valid = field_errors.total.is_none();
+        let max_repeats_valid = field_errors.max_repeats.is_none();
+        let max_streak_valid = field_errors.max_streak.is_none();
+        let cooldown_valid = field_errors.cooldown.is_none();
+        let transform_valid = field_errors.transform.is_none();
+
         // Range mode inputs - now includes Count
-        let range_inputs = if self.mode == GeneratorMode::Range {
+        let partition = self.mode == GeneratorMode::PARTITION;
+        let tickets_mode = self.mode == GeneratorMode::TICKETS;
+        let range_inputs = if self.mode_input() == SourceInput::Bounds {
+            // Partition mode splits a total into Count parts, each between From and To
+            let total_input = partition.then(|| {
+                column![
+                    text(self.tr("Total")).size(14),
+                    text_input("", &self.partition_total)
+                        .on_input(Message::PartitionTotalChanged)
+                        .on_submit(Message::Generate)
+                        .width(Length::Fixed(96.0))
+                        .size(14)
+                        .style(move |_theme: &Theme, status| {
+                            get_validated_input_style(self.settings.dark_mode, self.settings.high_contrast, total_valid, status)
+                        }),
+                    field_hint(field_errors.total.clone()),
+                ]
+                    .width(Length::Fixed(96.0))
+                    .spacing(2)
+            });
             container(
                 row![
                     // From input
                     column![
-                        text("From").size(14),
+                        text(self.tr(if tickets_mode { "First ticket" } else { "From" })).size(14),
                         text_input("", &self.lower_bound)
                             .on_input(Message::LowerBoundChanged)
-                            .width(Length::Fixed(60.0))
+                            .on_submit(Message::Generate)
+                            .width(Length::Fixed(96.0))
                             .size(14)
-                            .style(move |_theme: &Theme, _status| get_text_input_style(self.dark_mode))
+                            .style(move |_theme: &Theme, status| {
+                                get_validated_input_style(self.settings.dark_mode, self.settings.high_contrast, lower_valid, status)
+                            }),
+                        field_hint(field_errors.lower.clone()),
                     ]
+                    .width(Length::Fixed(96.0))
                     .spacing(2),
 
                     Space::with_width(Length::Fixed(8.0)),
 
-                    // To input
+                    // To input, or in tickets mode how many tickets were issued
                     column![
-                        text("To").size(14),
-                        text_input("", &self.upper_bound)
-                            .on_input(Message::UpperBoundChanged)
-                            .width(Length::Fixed(60.0))
+                        text(self.tr(if tickets_mode { "Issued" } else { "To" })).size(14),
+                        text_input("", if tickets_mode { &self.tickets_issued } else { &self.upper_bound })
+                            .on_input(if tickets_mode { Message::TicketsIssuedChanged } else { Message::UpperBoundChanged })
+                            .on_submit(Message::Generate)
+                            .width(Length::Fixed(96.0))
                             .size(14)
-                            .style(move |_theme: &Theme, _status| get_text_input_style(self.dark_mode))
+                            .style(move |_theme: &Theme, status| {
+                                get_validated_input_style(self.settings.dark_mode, self.settings.high_contrast, upper_valid, status)
+                            }),
+                        field_hint(field_errors.upper.clone()),
                     ]
+                    .width(Length::Fixed(96.0))
                     .spacing(2),
 
                     Space::with_width(Length::Fixed(8.0)),
 
                     // Count input
                     column![
-                        text("Count").size(14),
+                        text(self.tr(if partition { "Parts" } else { "Count" })).size(14),
                         text_input("", &self.num_to_generate)
                             .on_input(Message::NumToGenerateChanged)
-                            .width(Length::Fixed(60.0))
+                            .on_submit(Message::Generate)
+                            .width(Length::Fixed(96.0))
                             .size(14)
-                            .style(move |_theme: &Theme, _status| get_text_input_style(self.dark_mode))
+                            .style(move |_theme: &Theme, status| {
+                                get_validated_input_style(self.settings.dark_mode, self.settings.high_contrast, count_valid, status)
+                            }),
+                        field_hint(field_errors.count.clone()),
                     ]
+                    .width(Length::Fixed(96.0))
                     .spacing(2),
                 ]
+                    .push_maybe(total_input.map(|total| row![Space::with_width(Length::Fixed(8.0)), total]))
                     .spacing(6)
-                    .align_y(alignment::Vertical::Bottom)
+                    .align_y(alignment::Vertical::Top)
             )
         } else {
             container(Space::with_width(Length::Fixed(0.0)))
         };
 
-        // Custom list mode input
-        let custom_list_input = if self.mode == GeneratorMode::CustomList {
+        // Tickets mode counter for handing out ticket numbers
+        let ticket_counter: Element<'_, Message> = if tickets_mode {
+            self.ticket_counter()
+        } else {
+            Space::with_height(Length::Fixed(0.0)).into()
+        };
+
+        // Custom list mode editor
+        let custom_list_input: Element<'_, Message> = if self.mode_input() == SourceInput::List {
+            self.custom_list_editor()
+        } else {
+            Space::with_height(Length::Fixed(0.0)).into()
+        };
+
+        // Pool size and, with duplicates, the chance of a repeat
+        let draw_space_hint: Element<'_, Message> = match self.draw_space_hint(&field_errors) {
+            Some(hint) => text(hint)
+                .size(12)
+                .color(accessibility::dim_text_color(self.settings.dark_mode, self.settings.high_contrast))
+                .into(),
+            None => Space::with_height(Length::Fixed(0.0)).into(),
+        };
+
+        // Dice script mode editor
+        let script_input: Element<'_, Message> = if self.mode == GeneratorMode::SCRIPT {
+            self.script_input()
+        } else {
+            Space::with_height(Length::Fixed(0.0)).into()
+        };
+
+        // Geometric and negative-binomial inputs
+        let trials_input: Element<'_, Message> = if self.mode == GeneratorMode::GEOMETRIC || self.mode == GeneratorMode::NEGATIVE_BINOMIAL {
+            self.trials_input()
+        } else {
+            Space::with_height(Length::Fixed(0.0)).into()
+        };
+
+        // Stratified mode editor
+        let strata_input: Element<'_, Message> = if self.mode == GeneratorMode::STRATIFIED {
+            self.strata_input()
+        } else {
+            Space::with_height(Length::Fixed(0.0)).into()
+        };
+
+        // Optional slider for quick count adjustment
+        let count_slider = if self.count_slider {
+            let max = self.slider_max.trim().parse::<u32>().unwrap_or(20).max(1);
+            let value = self.num_to_generate.trim().parse::<u32>().unwrap_or(1).clamp(1, max);
             container(
-                column![
-                    text("Numbers (comma/space separated):").size(14),
-                    text_input("e.g. 1, 2, 3, 4, 5", &self.custom_list_input)
-                        .on_input(Message::CustomListChanged)
-                        .width(Length::Fill)
+                row![
+                    slider(1..=max, value, Message::CountSliderChanged)
+                        .width(Length::Fill),
+                    text(self.tr("Max")).size(14),
+                    text_input("20", &self.slider_max)
+                        .on_input(Message::SliderMaxChanged)
+                        .width(Length::Fixed(50.0))
                         .size(14)
-                        .style(move |_theme: &Theme, _status| get_text_input_style(self.dark_mode)),
-                    Space::with_height(Length::Fixed(4.0)),
-                    // Count input for custom list mode
-                    row![
-                        column![
-                            text("Count").size(14),
-                            text_input("", &self.num_to_generate)
-                                .on_input(Message::NumToGenerateChanged)
-                                .width(Length::Fixed(60.0))
-                                .size(14)
-                                .style(move |_theme: &Theme, _status| get_text_input_style(self.dark_mode))
-                        ]
-                        .spacing(2),
-                    ]
+                        .style(move |_theme: &Theme, status| get_text_input_style(self.settings.dark_mode, self.settings.high_contrast, status)),
                 ]
-                    .spacing(4)
+                    .spacing(6)
+                    .align_y(alignment::Vertical::Center)
             )
-                .padding(4)
+                .padding([0, 4])
         } else {
             container(Space::with_height(Length::Fixed(0.0)))
         };
@@ -382,7 +2322,7 @@ impl RandomGeneratorApp {
                 mode_picker,
                 horizontal_rule(1).style(move |_theme: &Theme| {
                     iced::widget::rule::Style {
-                        color: if self.dark_mode {
+                        color: if self.settings.dark_mode {
                             Color::from_rgb(0.4, 0.4, 0.45)
                         } else {
                             Color::from_rgb(0.8, 0.8, 0.8)
@@ -393,52 +2333,175 @@ impl RandomGeneratorApp {
                     }
                 }),
                 range_inputs,
+                ticket_counter,
                 custom_list_input,
+                script_input,
+                strata_input,
+                trials_input,
+                count_slider,
+                draw_space_hint,
                 Space::with_height(Length::Fixed(6.0)),
 
-                // Checkbox
-                checkbox("Allow duplicates", self.generator.get_allow_duplicates())
-                    .on_toggle(Message::AllowDuplicatesToggled)
-                    .size(14)
-                    .text_size(14)
-                    .style(move |_theme: &Theme, _status| {
-                        checkbox::Style {
-                            background: Background::Color(
-                                if self.dark_mode {
-                                    Color::from_rgb(0.25, 0.25, 0.3)
-                                } else {
-                                    Color::WHITE
-                                }
-                            ),
-                            icon_color: if self.dark_mode {
-                                Color::from_rgb(0.5, 0.8, 0.5)
-                            } else {
-                                Color::from_rgb(0.2, 0.6, 0.2)
-                            },
-                            border: Border {
-                                color: if self.dark_mode {
-                                    Color::from_rgb(0.4, 0.4, 0.45)
-                                } else {
-                                    Color::from_rgb(0.8, 0.8, 0.8)
-                                },
-                                width: 1.0,
-                                radius: 4.0.into(),
-                            },
-                            text_color: Some(if self.dark_mode {
-                                Color::from_rgb(0.9, 0.9, 0.9)
-                            } else {
-                                Color::BLACK
-                            }),
-                        }
-                    })
+                // Number format
+                row![
+                    text(self.tr("Separator:")).size(14),
+                    pick_list(
+                        &ThousandsSeparator::ALL[..],
+                        Some(self.number_format.separator),
+                        Message::SeparatorChanged
+                    )
+                    .text_size(14)
+                    .style(move |_theme: &Theme, _status| get_pick_list_style(self.settings.dark_mode, self.settings.high_contrast)),
+                    Space::with_width(Length::Fixed(8.0)),
+                    text(self.tr("Zero-pad:")).size(14),
+                    text_input("0", &self.pad_width)
+                        .on_input(Message::PadWidthChanged)
+                        .width(Length::Fixed(40.0))
+                        .size(14)
+                        .style(move |_theme: &Theme, status| get_text_input_style(self.settings.dark_mode, self.settings.high_contrast, status)),
+                    Space::with_width(Length::Fixed(8.0)),
+                    checkbox(self.tr("Number results"), self.number_format.numbered)
+                        .on_toggle(Message::NumberedToggled)
+                        .size(self.checkbox_size())
+                        .text_size(14)
+                        .style(move |_theme: &Theme, _status| get_checkbox_style(self.settings.dark_mode, self.settings.high_contrast)),
+                ]
+                    .spacing(6)
+                    .align_y(alignment::Vertical::Center),
+                self.labels_input(),
+
+                // Checkboxes
+                row![
+                    checkbox(self.tr("Allow duplicates"), self.generator.get_allow_duplicates())
+                        .on_toggle(Message::AllowDuplicatesToggled)
+                        .size(self.checkbox_size())
+                        .text_size(14)
+                        .style(move |_theme: &Theme, _status| get_checkbox_style(self.settings.dark_mode, self.settings.high_contrast)),
+                    checkbox(self.tr("Count slider"), self.count_slider)
+                        .on_toggle(Message::CountSliderToggled)
+                        .size(self.checkbox_size())
+                        .text_size(14)
+                        .style(move |_theme: &Theme, _status| get_checkbox_style(self.settings.dark_mode, self.settings.high_contrast)),
+                    checkbox(self.tr("Shuffle bag"), self.generator.shuffle_bag().is_some())
+                        .on_toggle(Message::ShuffleBagToggled)
+                        .size(self.checkbox_size())
+                        .text_size(14)
+                        .style(move |_theme: &Theme, _status| get_checkbox_style(self.settings.dark_mode, self.settings.high_contrast)),
+                ]
+                    .spacing(12)
+                    .align_y(alignment::Vertical::Center)
+                    .push_maybe(self.generator.shuffle_bag().map(|bag| {
+                        row![
+                            text(self.bag_status(bag)).size(13),
+                            button(text(self.tr("Refill")).size(13))
+                                .on_press(Message::RefillShuffleBag)
+                                .padding(self.button_padding())
+                                .style(move |_theme: &Theme, status| get_link_button_style(self.settings.dark_mode, self.settings.high_contrast, status)),
+                        ]
+                            .spacing(4)
+                            .align_y(alignment::Vertical::Center)
+                    })),
+
+                // Repeat limit, only with duplicates
+                row![
+                    text(self.tr("At most per value:")).size(14),
+                    text_input(self.tr("No limit"), &self.max_repeats_input)
+                        .on_input_maybe(self.generator.get_allow_duplicates().then_some(Message::MaxRepeatsChanged))
+                        .width(Length::Fixed(80.0))
+                        .size(14)
+                        .style(move |_theme: &Theme, status| {
+                            get_validated_input_style(self.settings.dark_mode, self.settings.high_contrast, max_repeats_valid, status)
+                        }),
+                    text(self.tr("In a row:")).size(14),
+                    text_input(self.tr("No limit"), &self.max_streak_input)
+                        .on_input_maybe(self.generator.get_allow_duplicates().then_some(Message::MaxStreakChanged))
+                        .width(Length::Fixed(80.0))
+                        .size(14)
+                        .style(move |_theme: &Theme, status| {
+                            get_validated_input_style(self.settings.dark_mode, self.settings.high_contrast, max_streak_valid, status)
+                        }),
+                ]
+                    .spacing(6)
+                    .align_y(alignment::Vertical::Center),
+                field_hint(field_errors.max_repeats.clone()),
+                field_hint(field_errors.max_streak.clone()),
+
+                // Cooldown
+                row![
+                    text(self.tr("Cooldown (draws):")).size(14),
+                    text_input("0", &self.cooldown_input)
+                        .on_input(Message::CooldownChanged)
+                        .width(Length::Fixed(40.0))
+                        .size(14)
+                        .style(move |_theme: &Theme, status| {
+                            get_validated_input_style(self.settings.dark_mode, self.settings.high_contrast, cooldown_valid, status)
+                        }),
+                ]
+                    .spacing(6)
+                    .align_y(alignment::Vertical::Center)
+                    .push_maybe(self.resting_values().map(|resting| {
+                        row![
+                            text(resting).size(13),
+                            button(text(self.tr("Clear")).size(13))
+                                .on_press(Message::ClearCooldown)
+                                .padding(self.button_padding())
+                                .style(move |_theme: &Theme, status| get_link_button_style(self.settings.dark_mode, self.settings.high_contrast, status)),
+                        ]
+                            .spacing(4)
+                            .align_y(alignment::Vertical::Center)
+                    })),
+                field_hint(field_errors.cooldown.clone()),
+
+                // Transform
+                row![
+                    text(self.tr("Transform:")).size(14),
+                    text_input(self.tr("e.g. scale 10, offset 5"), &self.transform_input)
+                        .on_input(Message::TransformChanged)
+                        .width(Length::Fixed(200.0))
+                        .size(14)
+                        .style(move |_theme: &Theme, status| {
+                            get_validated_input_style(self.settings.dark_mode, self.settings.high_contrast, transform_valid, status)
+                        }),
+                ]
+                    .spacing(6)
+                    .align_y(alignment::Vertical::Center),
+                field_hint(field_errors.transform.clone()),
             ]
+                // Exclusions, range and tickets modes only
+                .push_maybe((self.mode == GeneratorMode::RANGE || self.mode == GeneratorMode::TICKETS).then(|| {
+                    row![
+                        checkbox(self.tr("Exclude earlier results"), self.exclude_drawn.is_some())
+                            .on_toggle(Message::ExcludeDrawnToggled)
+                            .size(self.checkbox_size())
+                            .text_size(14)
+                            .style(move |_theme: &Theme, _status| get_checkbox_style(self.settings.dark_mode, self.settings.high_contrast)),
+                    ]
+                        .spacing(12)
+                        .align_y(alignment::Vertical::Center)
+                        .push_maybe(self.exclude_drawn.as_ref().map(|excluded| {
+                            row![
+                                text(i18n::fill(self.tr("{} excluded"), &[&excluded.len()])).size(13),
+                                button(text(self.tr("Reset")).size(13))
+                                    .on_press(Message::ResetExcluded)
+                                    .padding(self.button_padding())
+                                    .style(move |_theme: &Theme, status| get_link_button_style(self.settings.dark_mode, self.settings.high_contrast, status)),
+                                // e.g. winners opened from an earlier session's file
+                                button(text(self.tr("Exclude current results")).size(13))
+                                    .on_press_maybe((!self.generator.get_numbers().is_empty()).then_some(Message::ExcludeResults))
+                                    .padding(self.button_padding())
+                                    .style(move |_theme: &Theme, status| get_link_button_style(self.settings.dark_mode, self.settings.high_contrast, status)),
+                            ]
+                                .spacing(4)
+                                .align_y(alignment::Vertical::Center)
+                        }))
+                }))
                 .spacing(6)
                 .padding(10)
         )
             .style(move |_theme: &Theme| {
                 iced::widget::container::Style {
                     background: Some(Background::Color(
-                        if self.dark_mode {
+                        if self.settings.dark_mode {
                             Color::from_rgb(0.2, 0.2, 0.25)
                         } else {
                             Color::from_rgb(0.96, 0.96, 0.96)
@@ -460,20 +2523,24 @@ impl RandomGeneratorApp {
 
         // Button row with filename input
         let button_row = row![
-            button(text("Generate").size(14))
-                .on_press(Message::Generate)
+            button(text(self.tr("Generate")).size(14))
+                .on_press_maybe(self.generation_progress.is_none().then_some(Message::Generate))
                 .width(Length::Fixed(85.0))
+                .padding(self.button_padding())
                 .style(move |_theme: &Theme, status| {
+                    if self.settings.high_contrast {
+                        return accessibility::high_contrast_button(self.settings.dark_mode, status);
+                    }
                     let is_pressed = status == button::Status::Pressed;
                     button::Style {
                         background: Some(Background::Color(
                             if is_pressed {
-                                if self.dark_mode {
+                                if self.settings.dark_mode {
                                     Color::from_rgb(0.2, 0.4, 0.7)
                                 } else {
                                     Color::from_rgb(0.1, 0.5, 0.8)
                                 }
-                            } else if self.dark_mode {
+                            } else if self.settings.dark_mode {
                                 Color::from_rgb(0.3, 0.5, 0.8)
                             } else {
                                 Color::from_rgb(0.2, 0.6, 0.9)
@@ -490,24 +2557,27 @@ impl RandomGeneratorApp {
                             offset: Vector::new(0.0, if is_pressed { 1.0 } else { 2.0 }),
                             blur_radius: if is_pressed { 2.0 } else { 4.0 },
                         },
-                        ..Default::default()
                     }
                 }),
 
-            button(text("Clear").size(14))
+            button(text(self.tr("Clear")).size(14))
                 .on_press(Message::Clear)
                 .width(Length::Fixed(65.0))
+                .padding(self.button_padding())
                 .style(move |_theme: &Theme, status| {
+                    if self.settings.high_contrast {
+                        return accessibility::high_contrast_button(self.settings.dark_mode, status);
+                    }
                     let is_pressed = status == button::Status::Pressed;
                     button::Style {
                         background: Some(Background::Color(
                             if is_pressed {
-                                if self.dark_mode {
+                                if self.settings.dark_mode {
                                     Color::from_rgb(0.5, 0.2, 0.2)
                                 } else {
                                     Color::from_rgb(0.8, 0.3, 0.3)
                                 }
-                            } else if self.dark_mode {
+                            } else if self.settings.dark_mode {
                                 Color::from_rgb(0.6, 0.3, 0.3)
                             } else {
                                 Color::from_rgb(0.9, 0.4, 0.4)
@@ -524,24 +2594,27 @@ impl RandomGeneratorApp {
                             offset: Vector::new(0.0, if is_pressed { 1.0 } else { 2.0 }),
                             blur_radius: if is_pressed { 2.0 } else { 4.0 },
                         },
-                        ..Default::default()
                     }
                 }),
 
-            button(text("Save").size(14))
+            button(text(self.tr("Save")).size(14))
                 .on_press(Message::Save)
                 .width(Length::Fixed(65.0))
+                .padding(self.button_padding())
                 .style(move |_theme: &Theme, status| {
+                    if self.settings.high_contrast {
+                        return accessibility::high_contrast_button(self.settings.dark_mode, status);
+                    }
                     let is_pressed = status == button::Status::Pressed;
                     button::Style {
                         background: Some(Background::Color(
                             if is_pressed {
-                                if self.dark_mode {
+                                if self.settings.dark_mode {
                                     Color::from_rgb(0.2, 0.5, 0.2)
                                 } else {
                                     Color::from_rgb(0.3, 0.7, 0.3)
                                 }
-                            } else if self.dark_mode {
+                            } else if self.settings.dark_mode {
                                 Color::from_rgb(0.3, 0.6, 0.3)
                             } else {
                                 Color::from_rgb(0.4, 0.8, 0.4)
@@ -558,33 +2631,43 @@ impl RandomGeneratorApp {
                             offset: Vector::new(0.0, if is_pressed { 1.0 } else { 2.0 }),
                             blur_radius: if is_pressed { 2.0 } else { 4.0 },
                         },
-                        ..Default::default()
                     }
                 }),
 
             Space::with_width(Length::Fixed(8.0)),
 
             // Filename input
-            text("File:").size(14),
+            text(self.tr("File:")).size(14),
             text_input("", &self.filename)
                 .on_input(Message::FilenameChanged)
                 .width(Length::Fill)
                 .size(14)
-                .style(move |_theme: &Theme, _status| get_text_input_style(self.dark_mode))
+                .style(move |_theme: &Theme, status| get_text_input_style(self.settings.dark_mode, self.settings.high_contrast, status)),
+            button(text(self.tr("Open…")).size(14))
+                .on_press(Message::OpenResults)
+                .padding(self.button_padding())
+                .style(move |_theme: &Theme, status| get_link_button_style(self.settings.dark_mode, self.settings.high_contrast, status))
         ]
+            // Several result sets can go into one file, each under its own header
+            .push_maybe((self.tabs.len() > 1).then(|| {
+                checkbox(self.tr("All tabs"), self.save_all_tabs)
+                    .on_toggle(Message::SaveAllTabsToggled)
+                    .size(self.checkbox_size())
+                    .text_size(14)
+                    .style(move |_theme: &Theme, _status| get_checkbox_style(self.settings.dark_mode, self.settings.high_contrast))
+            }))
             .spacing(6)
             .align_y(alignment::Vertical::Center);
 
-        let error_display = if !self.error_message.is_empty() {
+        let error_display = if let Some(status) = &self.status {
             container(
-                text(&self.error_message)
+                text(status.to_string())
                     .size(13)
                     .style(move |_theme: &Theme| {
                         iced::widget::text::Style {
-                            color: Some(if self.error_message.starts_with("Saved") {
-                                Color::from_rgb(0.4, 0.8, 0.4)
-                            } else {
-                                Color::from_rgb(1.0, 0.4, 0.4)
+                            color: Some(match status {
+                                Status::Info(_) => Color::from_rgb(0.4, 0.8, 0.4),
+                                Status::Error(_) => Color::from_rgb(1.0, 0.4, 0.4),
                             }),
                         }
                     })
@@ -593,7 +2676,7 @@ impl RandomGeneratorApp {
                 .style(move |_theme: &Theme| {
                     iced::widget::container::Style {
                         background: Some(Background::Color(
-                            if self.dark_mode {
+                            if self.settings.dark_mode {
                                 Color::from_rgba(0.2, 0.2, 0.25, 0.8)
                             } else {
                                 Color::from_rgba(0.95, 0.95, 0.95, 0.8)
@@ -611,31 +2694,136 @@ impl RandomGeneratorApp {
             container(Space::with_height(Length::Fixed(0.0)))
         };
 
-        let results_display = if self.generator.get_numbers().is_empty() {
+        let total_count = self.generator.get_numbers().len();
+        let reveal_bar = if self.settings.sequential_reveal && total_count > 0 {
+            let remaining = total_count - self.revealed_count;
             container(
-                text(match self.mode {
-                    GeneratorMode::Range => "Click Generate to start",
-                    GeneratorMode::CustomList => "Enter numbers and click Generate",
-                })
+                row![
+                    text(i18n::fill(self.tr("Revealed {} of {}, {} remaining"), &[&self.revealed_count, &total_count, &remaining]))
+                        .size(13),
+                    Space::with_width(Length::Fill),
+                    button(text(self.tr("Next")).size(14))
+                        .on_press_maybe((remaining > 0).then_some(Message::RevealNext))
+                        .width(Length::Fixed(65.0))
+                        .padding(self.button_padding())
+                        .style(move |_theme: &Theme, status| {
+                            if self.settings.high_contrast {
+                                return accessibility::high_contrast_button(self.settings.dark_mode, status);
+                            }
+                            let is_pressed = status == button::Status::Pressed;
+                            button::Style {
+                                background: Some(Background::Color(
+                                    if is_pressed {
+                                        if self.settings.dark_mode {
+                                            Color::from_rgb(0.2, 0.4, 0.7)
+                                        } else {
+                                            Color::from_rgb(0.1, 0.5, 0.8)
+                                        }
+                                    } else if self.settings.dark_mode {
+                                        Color::from_rgb(0.3, 0.5, 0.8)
+                                    } else {
+                                        Color::from_rgb(0.2, 0.6, 0.9)
+                                    }
+                                )),
+                                border: Border {
+                                    color: Color::TRANSPARENT,
+                                    width: 0.0,
+                                    radius: 8.0.into(),
+                                },
+                                text_color: Color::WHITE,
+                                shadow: Shadow {
+                                    color: Color::from_rgba(0.0, 0.0, 0.0, 0.15),
+                                    offset: Vector::new(0.0, if is_pressed { 1.0 } else { 2.0 }),
+                                    blur_radius: if is_pressed { 2.0 } else { 4.0 },
+                                },
+                            }
+                        }),
+                ]
+                    .spacing(6)
+                    .align_y(alignment::Vertical::Center)
+            )
+                .padding([0, 4])
+        } else {
+            container(Space::with_height(Length::Fixed(0.0)))
+        };
+
+        let tab_bar = if !self.tabs.is_empty() && self.generation_progress.is_none() {
+            container(self.tab_bar()).padding([0, 4])
+        } else {
+            container(Space::with_height(Length::Fixed(0.0)))
+        };
+
+        let filter_bar = if !self.visible_numbers().is_empty() && self.generation_progress.is_none() {
+            container(
+                row![
+                    text(self.tr("Filter:")).size(14),
+                    text_input(self.tr("e.g. 42 or 10..20"), &self.filter_input)
+                        .on_input(Message::FilterChanged)
+                        .width(Length::Fill)
+                        .size(14)
+                        .style(move |_theme: &Theme, status| get_text_input_style(self.settings.dark_mode, self.settings.high_contrast, status)),
+                    checkbox(self.tr("Only matches"), self.filter_only_matches)
+                        .on_toggle(Message::FilterOnlyMatchesToggled)
+                        .size(self.checkbox_size())
+                        .text_size(14)
+                        .style(move |_theme: &Theme, _status| get_checkbox_style(self.settings.dark_mode, self.settings.high_contrast)),
+                ]
+                    .spacing(6)
+                    .align_y(alignment::Vertical::Center)
+            )
+                .padding([0, 4])
+        } else {
+            container(Space::with_height(Length::Fixed(0.0)))
+        };
+
+        let results_display = if let Some(progress) = self.generation_progress {
+            container(
+                column![
+                    row![
+                        text(i18n::fill(self.tr("Generating... {}%"), &[&format!("{:.0}", progress * 100.0)])).size(13),
+                        Space::with_width(Length::Fill),
+                        button(text(self.tr("Cancel")).size(13))
+                            .on_press(Message::CancelGeneration)
+                            .padding(self.button_padding())
+                            .style(move |_theme: &Theme, status| get_link_button_style(self.settings.dark_mode, self.settings.high_contrast, status)),
+                    ]
+                        .align_y(alignment::Vertical::Center),
+                    progress_bar(0.0..=1.0, progress).height(Length::Fixed(8.0)),
+                ]
+                    .spacing(6)
+                    .padding(10)
+            )
+                .center_y(Length::Fill)
+                .width(Length::Fill)
+        } else if self.visible_numbers().is_empty() {
+            container(
+                text(self.tr(if total_count > 0 {
+                    "Click Next to reveal the first result"
+                } else {
+                    match self.mode_input() {
+                        SourceInput::Bounds if self.mode == GeneratorMode::TICKETS => "Issue tickets, then click Generate to draw winners",
+                        SourceInput::Bounds => "Click Generate to start",
+                        SourceInput::List => "Enter numbers and click Generate",
+                        SourceInput::Own if self.mode == GeneratorMode::SCRIPT => "Write a script and click Generate",
+                        SourceInput::Own if self.mode == GeneratorMode::STRATIFIED => "Write the strata and click Generate",
+                        SourceInput::Own => "Set the chance of success and click Generate",
+                    }
+                }))
                     .size(14)
                     .style(move |_theme: &Theme| {
                         iced::widget::text::Style {
-                            color: Some(if self.dark_mode {
-                                Color::from_rgb(0.6, 0.6, 0.6)
-                            } else {
-                                Color::from_rgb(0.5, 0.5, 0.5)
-                            }),
+                            color: Some(accessibility::dim_text_color(self.settings.dark_mode, self.settings.high_contrast)),
                         }
                     })
             )
                 .center_x(Length::Fill)
-                .center_y(Length::Fixed(80.0))
+                .center_y(Length::Fill)
                 .width(Length::Fill)
-                .height(Length::Fixed(80.0))
+                .height(Length::Fill)
                 .style(move |_theme: &Theme| {
                     iced::widget::container::Style {
                         background: Some(Background::Color(
-                            if self.dark_mode {
+                            if self.settings.dark_mode {
                                 Color::from_rgb(0.15, 0.15, 0.20)
                             } else {
                                 Color::from_rgb(0.98, 0.98, 0.98)
@@ -650,15 +2838,24 @@ impl RandomGeneratorApp {
                     }
                 })
         } else {
-            let numbers = self.generator.get_numbers();
+            let numbers = self.visible_numbers();
+            let filter = ResultFilter::parse(&self.filter_input);
+            let is_match = |num: i64| filter.as_ref().is_some_and(|f| f.matches(num));
+            let shown: Vec<(usize, i64)> = numbers
+                .iter()
+                .copied()
+                .enumerate()
+                .filter(|&(_, num)| !self.filter_only_matches || filter.is_none() || is_match(num))
+                .collect();
             let chunk_size = 8;
 
             let mut rows = Vec::new();
-            for chunk in numbers.chunks(chunk_size) {
+            for chunk in shown.chunks(chunk_size) {
                 let number_row = row(
-                    chunk.iter().map(|num| {
+                    chunk.iter().map(|&(index, num)| {
+                        let highlighted = is_match(num);
                         container(
-                            text(format!("{}", num))
+                            text(self.number_format.number(index, self.display_number(index, num)))
                                 .size(13)
                                 .font(iced::Font::MONOSPACE)
                         )
@@ -666,7 +2863,13 @@ impl RandomGeneratorApp {
                             .style(move |_theme: &Theme| {
                                 iced::widget::container::Style {
                                     background: Some(Background::Color(
-                                        if self.dark_mode {
+                                        if highlighted {
+                                            if self.settings.dark_mode {
+                                                Color::from_rgb(0.6, 0.5, 0.15)
+                                            } else {
+                                                Color::from_rgb(1.0, 0.85, 0.4)
+                                            }
+                                        } else if self.settings.dark_mode {
                                             Color::from_rgb(0.25, 0.25, 0.3)
                                         } else {
                                             Color::from_rgb(0.92, 0.92, 0.92)
@@ -691,15 +2894,17 @@ impl RandomGeneratorApp {
             rows.push(Space::with_height(Length::Fixed(6.0)).into());
             rows.push(
                 container(
-                    text(format!("Total: {}", numbers.len()))
+                    text(match &filter {
+                        Some(_) => i18n::fill(
+                            self.tr("Total: {}, matches: {}"),
+                            &[&numbers.len(), &numbers.iter().filter(|&&num| is_match(num)).count()]
+                        ),
+                        None => i18n::fill(self.tr("Total: {}"), &[&numbers.len()]),
+                    })
                         .size(13)
                         .style(move |_theme: &Theme| {
                             iced::widget::text::Style {
-                                color: Some(if self.dark_mode {
-                                    Color::from_rgb(0.6, 0.6, 0.6)
-                                } else {
-                                    Color::from_rgb(0.5, 0.5, 0.5)
-                                }),
+                                color: Some(accessibility::dim_text_color(self.settings.dark_mode, self.settings.high_contrast)),
                             }
                         })
                 )
@@ -713,12 +2918,13 @@ impl RandomGeneratorApp {
                         .spacing(3)
                         .padding(6)
                 )
-                    .height(Length::Fixed(90.0))
+                    .height(Length::Fill)
             )
+                .height(Length::Fill)
                 .style(move |_theme: &Theme| {
                     iced::widget::container::Style {
                         background: Some(Background::Color(
-                            if self.dark_mode {
+                            if self.settings.dark_mode {
                                 Color::from_rgb(0.15, 0.15, 0.20)
                             } else {
                                 Color::from_rgb(0.98, 0.98, 0.98)
@@ -735,44 +2941,115 @@ impl RandomGeneratorApp {
         };
 
         let status_bar = row![
-            button(text("About")
+            button(text(self.tr("About"))
                 .size(13))
                 .on_press(Message::ShowAbout)
-                .style(move |_theme: &Theme, status| {
-                    let is_pressed = status == button::Status::Pressed;
-                    button::Style {
-                        background: Some(Background::Color(
-                            if is_pressed {
-                                if self.dark_mode {
-                                    Color::from_rgb(0.2, 0.2, 0.25)
-                                } else {
-                                    Color::from_rgb(0.9, 0.9, 0.9)
-                                }
-                            } else {
-                                Color::TRANSPARENT
-                            }
-                        )),
-                        border: Border {
-                            color: Color::TRANSPARENT,
-                            width: 0.0,
-                            radius: 8.0.into(),
-                        },
-                        text_color: if self.dark_mode {
-                            Color::from_rgb(0.7, 0.7, 0.7)
-                        } else {
-                            Color::from_rgb(0.5, 0.5, 0.5)
-                        },
-                        ..Default::default()
-                    }
-                }),
+                .padding(self.button_padding())
+                .style(move |_theme: &Theme, status| get_link_button_style(self.settings.dark_mode, self.settings.high_contrast, status)),
+            button(text(self.tr("Settings"))
+                .size(13))
+                .on_press(Message::ShowSettings)
+                .padding(self.button_padding())
+                .style(move |_theme: &Theme, status| get_link_button_style(self.settings.dark_mode, self.settings.high_contrast, status)),
+            button(text(self.tr("Present"))
+                .size(13))
+                .on_press(Message::EnterPresentation)
+                .padding(self.button_padding())
+                .style(move |_theme: &Theme, status| get_link_button_style(self.settings.dark_mode, self.settings.high_contrast, status)),
+            button(text(self.tr("Test data"))
+                .size(13))
+                .on_press(Message::ShowTestData)
+                .padding(self.button_padding())
+                .style(move |_theme: &Theme, status| get_link_button_style(self.settings.dark_mode, self.settings.high_contrast, status)),
+            button(text(self.tr("Random file"))
+                .size(13))
+                .on_press(Message::ShowRandomFile)
+                .padding(self.button_padding())
+                .style(move |_theme: &Theme, status| get_link_button_style(self.settings.dark_mode, self.settings.high_contrast, status)),
+            button(text(self.tr("Sample lines"))
+                .size(13))
+                .on_press(Message::ShowLineSample)
+                .padding(self.button_padding())
+                .style(move |_theme: &Theme, status| get_link_button_style(self.settings.dark_mode, self.settings.high_contrast, status)),
+            button(text(self.tr("Group draw"))
+                .size(13))
+                .on_press(Message::ShowGroupDraw)
+                .padding(self.button_padding())
+                .style(move |_theme: &Theme, status| get_link_button_style(self.settings.dark_mode, self.settings.high_contrast, status)),
+            button(text(self.tr("Games"))
+                .size(13))
+                .on_press(Message::ShowGames)
+                .padding(self.button_padding())
+                .style(move |_theme: &Theme, status| get_link_button_style(self.settings.dark_mode, self.settings.high_contrast, status)),
+            button(text(self.tr("Bootstrap"))
+                .size(13))
+                .on_press(Message::ShowBootstrap)
+                .padding(self.button_padding())
+                .style(move |_theme: &Theme, status| get_link_button_style(self.settings.dark_mode, self.settings.high_contrast, status)),
+            button(text(self.tr("Merge files"))
+                .size(13))
+                .on_press(Message::ShowMerge)
+                .padding(self.button_padding())
+                .style(move |_theme: &Theme, status| get_link_button_style(self.settings.dark_mode, self.settings.high_contrast, status)),
+            button(text(self.tr("History"))
+                .size(13))
+                .on_press(Message::ShowHistory)
+                .padding(self.button_padding())
+                .style(move |_theme: &Theme, status| get_link_button_style(self.settings.dark_mode, self.settings.high_contrast, status)),
+            button(text(self.tr("Export session report"))
+                .size(13))
+                .on_press_maybe((!self.session.is_empty()).then_some(Message::ExportReport))
+                .padding(self.button_padding())
+                .style(move |_theme: &Theme, status| get_link_button_style(self.settings.dark_mode, self.settings.high_contrast, status)),
+            button(text(self.tr("Random walk"))
+                .size(13))
+                .on_press(Message::ShowWalk)
+                .padding(self.button_padding())
+                .style(move |_theme: &Theme, status| get_link_button_style(self.settings.dark_mode, self.settings.high_contrast, status)),
+            button(text(self.tr("Distribution"))
+                .size(13))
+                .on_press(Message::ShowDistribution)
+                .padding(self.button_padding())
+                .style(move |_theme: &Theme, status| get_link_button_style(self.settings.dark_mode, self.settings.high_contrast, status)),
+            button(text(self.tr("Random weights"))
+                .size(13))
+                .on_press(Message::ShowWeights)
+                .padding(self.button_padding())
+                .style(move |_theme: &Theme, status| get_link_button_style(self.settings.dark_mode, self.settings.high_contrast, status)),
+            button(text(self.tr("Correlated normal"))
+                .size(13))
+                .on_press(Message::ShowCorrelated)
+                .padding(self.button_padding())
+                .style(move |_theme: &Theme, status| get_link_button_style(self.settings.dark_mode, self.settings.high_contrast, status)),
+            button(text(self.tr("Custom distribution"))
+                .size(13))
+                .on_press(Message::ShowCdfTable)
+                .padding(self.button_padding())
+                .style(move |_theme: &Theme, status| get_link_button_style(self.settings.dark_mode, self.settings.high_contrast, status)),
+            button(text(self.tr("Monte Carlo"))
+                .size(13))
+                .on_press(Message::ShowMonteCarlo)
+                .padding(self.button_padding())
+                .style(move |_theme: &Theme, status| get_link_button_style(self.settings.dark_mode, self.settings.high_contrast, status)),
+            button(text(self.tr("Random graph"))
+                .size(13))
+                .on_press(Message::ShowGraph)
+                .padding(self.button_padding())
+                .style(move |_theme: &Theme, status| get_link_button_style(self.settings.dark_mode, self.settings.high_contrast, status)),
+            button(text(self.tr("Experiment design"))
+                .size(13))
+                .on_press(Message::ShowDesign)
+                .padding(self.button_padding())
+                .style(move |_theme: &Theme, status| get_link_button_style(self.settings.dark_mode, self.settings.high_contrast, status)),
+            button(text(self.tr("New Window"))
+                .size(13))
+                .on_press(Message::NewWindow)
+                .padding(self.button_padding())
+                .style(move |_theme: &Theme, status| get_link_button_style(self.settings.dark_mode, self.settings.high_contrast, status)),
             Space::with_width(Length::Fill),
-            text("Random Generator")
+            text(self.tr("Random Generator"))
                 .size(12)
-                .color(if self.dark_mode {
-                    Color::from_rgb(0.6, 0.6, 0.6)
-                } else {
-                    Color::from_rgb(0.5, 0.5, 0.5)
-                })
+                .color(accessibility::dim_text_color(self.settings.dark_mode, self.settings.high_contrast))
         ]
             .spacing(4)
             .align_y(alignment::Vertical::Center);
@@ -783,22 +3060,85 @@ impl RandomGeneratorApp {
             input_section,
             Space::with_height(Length::Fixed(10.0)),
             button_row,
+            Space::with_height(Length::Fixed(4.0)),
+            self.auto_draw_view(),
             Space::with_height(Length::Fixed(6.0)),
             error_display,
+            self.benchmark_view(),
             Space::with_height(Length::Fixed(10.0)),
+            reveal_bar,
+            tab_bar,
+            filter_bar,
             results_display,
-            Space::with_height(Length::Fill),
+            Space::with_height(Length::Fixed(6.0)),
             status_bar
         ]
             .spacing(0)
             .padding(14);
 
-        if self.about_open {
+        if self.link_draw_pending || self.settings_open || self.test_data_open || self.random_file_open || self.line_sample_open || self.group_draw_open || self.games_open || self.bootstrap_open || self.compare_open || self.merge_open || self.history_open || self.walk_open || self.monte_carlo_open || self.graph_open || self.design_open || self.distribution_open || self.weights_open || self.correlated_open || self.cdf_table_open {
+            let panel = if self.link_draw_pending {
+                self.link_draw_view()
+            } else if self.settings_open {
+                self.settings_view()
+            } else if self.test_data_open {
+                self.test_data_view()
+            } else if self.random_file_open {
+                self.random_file_view()
+            } else if self.line_sample_open {
+                self.line_sample_view()
+            } else if self.group_draw_open {
+                self.group_draw_view()
+            } else if self.games_open {
+                self.games_view()
+            } else if self.bootstrap_open {
+                self.bootstrap_view()
+            } else if self.compare_open {
+                self.compare_view()
+            } else if self.merge_open {
+                self.merge_view()
+            } else if self.history_open {
+                self.history_view()
+            } else if self.walk_open {
+                self.walk_view()
+            } else if self.monte_carlo_open {
+                self.monte_carlo_view()
+            } else if self.graph_open {
+                self.graph_view()
+            } else if self.design_open {
+                self.design_view()
+            } else if self.distribution_open {
+                self.distribution_view()
+            } else if self.weights_open {
+                self.weights_view()
+            } else if self.correlated_open {
+                self.correlated_view()
+            } else {
+                self.cdf_table_view()
+            };
+            container(
+                container(panel)
+                    .center_x(Length::Fill)
+                    .center_y(Length::Fill)
+                    .width(Length::Fill)
+                    .height(Length::Fill)
+            )
+                .style(move |_theme: &Theme| {
+                    iced::widget::container::Style {
+                        background: Some(Background::Color(
+                            Color::from_rgba(0.0, 0.0, 0.0, 0.5)
+                        )),
+                        ..Default::default()
+                    }
+                })
+                .width(Length::Fill)
+                .height(Length::Fill).into()
+        } else if self.about_open {
             let about_content = container(
                 column![
-                    text("Random Generator")
+                    text(self.tr("Random Generator"))
                         .size(20)
-                        .color(if self.dark_mode { Color::from_rgb(0.9, 0.9, 0.9) } else { Color::BLACK }),
+                        .color(if self.settings.dark_mode { Color::from_rgb(0.9, 0.9, 0.9) } else { Color::BLACK }),
                     Space::with_height(Length::Fixed(10.0)),
                     text(format!("GUI: {}", self.gui_version))
                         .size(14),
@@ -808,27 +3148,31 @@ impl RandomGeneratorApp {
                     text("GitHub: https://github.com/Daihongyi/random-tool-github")
                         .size(12),
                     Space::with_height(Length::Fixed(10.0)),
-                    text("License: MPL-2.0")
+                    text(self.tr("License: MPL-2.0"))
                         .size(12),
-                    text("Built with Rust")
+                    text(self.tr("Built with Rust"))
                         .size(12),
-                    text("Powered by Iced")
+                    text(self.tr("Powered by Iced"))
                         .size(12),
                     Space::with_height(Length::Fixed(18.0)),
-                    button(text("Close").size(14))
+                    button(text(self.tr("Close")).size(14))
                         .on_press(Message::CloseAbout)
                         .width(Length::Fixed(80.0))
+                        .padding(self.button_padding())
                         .style(move |_theme: &Theme, status| {
+                            if self.settings.high_contrast {
+                                return accessibility::high_contrast_button(self.settings.dark_mode, status);
+                            }
                             let is_pressed = status == button::Status::Pressed;
                             button::Style {
                                 background: Some(Background::Color(
                                     if is_pressed {
-                                        if self.dark_mode {
+                                        if self.settings.dark_mode {
                                             Color::from_rgb(0.2, 0.2, 0.25)
                                         } else {
                                             Color::from_rgb(0.1, 0.5, 0.8)
                                         }
-                                    } else if self.dark_mode {
+                                    } else if self.settings.dark_mode {
                                         Color::from_rgb(0.3, 0.3, 0.35)
                                     } else {
                                         Color::from_rgb(0.2, 0.6, 0.9)
@@ -845,7 +3189,6 @@ impl RandomGeneratorApp {
                                     offset: Vector::new(0.0, if is_pressed { 1.0 } else { 2.0 }),
                                     blur_radius: if is_pressed { 2.0 } else { 4.0 },
                                 },
-                                ..Default::default()
                             }
                         })
                 ]
@@ -860,14 +3203,14 @@ impl RandomGeneratorApp {
                 .style(move |_theme: &Theme| {
                     iced::widget::container::Style {
                         background: Some(Background::Color(
-                            if self.dark_mode {
+                            if self.settings.dark_mode {
                                 Color::from_rgb(0.2, 0.2, 0.25)
                             } else {
                                 Color::WHITE
                             }
                         )),
                         border: Border {
-                            color: if self.dark_mode {
+                            color: if self.settings.dark_mode {
                                 Color::from_rgb(0.4, 0.4, 0.4)
                             } else {
                                 Color::from_rgb(0.8, 0.8, 0.8)
@@ -909,12 +3252,28 @@ impl RandomGeneratorApp {
     }
 
     fn theme(&self) -> Theme {
-        self.theme.clone()
+        if self.settings.dark_mode {
+            Theme::Dark
+        } else {
+            Theme::Light
+        }
     }
 }
 
 // Define function to get text input style
-fn get_text_input_style(dark_mode: bool) -> text_input::Style {
+fn get_text_input_style(dark_mode: bool, high_contrast: bool, status: text_input::Status) -> text_input::Style {
+    let mut style = if high_contrast {
+        accessibility::high_contrast_input(dark_mode)
+    } else {
+        get_plain_text_input_style(dark_mode)
+    };
+    if status == text_input::Status::Focused {
+        style.border = accessibility::focus_border(dark_mode, high_contrast);
+    }
+    style
+}
+
+fn get_plain_text_input_style(dark_mode: bool) -> text_input::Style {
     text_input::Style {
         background: Background::Color(
             if dark_mode {
@@ -947,26 +3306,176 @@ fn get_text_input_style(dark_mode: bool) -> text_input::Style {
     }
 }
 
+// Define function to get text input style with a red border for invalid values
+fn get_validated_input_style(dark_mode: bool, high_contrast: bool, is_valid: bool, status: text_input::Status) -> text_input::Style {
+    let mut style = get_text_input_style(dark_mode, high_contrast, status);
+    if !is_valid {
+        style.border.color = Color::from_rgb(0.9, 0.3, 0.3);
+    }
+    style
+}
+
+// Small red hint shown under an invalid field
+/// A limit typed into a field, `None` when the field is empty
+fn parse_limit(input: &str) -> Result<Option<usize>, std::num::ParseIntError> {
+    let input = input.trim();
+    if input.is_empty() {
+        Ok(None)
+    } else {
+        input.parse().map(Some)
+    }
+}
+
+/// A probability as a percentage, without claiming certainty it doesn't have
+fn percent(chance: f64) -> String {
+    match chance {
+        0.0 => "0%".to_string(),
+        1.0 => "100%".to_string(),
+        chance if chance < 0.0001 => "< 0.01%".to_string(),
+        chance if chance > 0.9999 => "> 99.99%".to_string(),
+        chance => format!("{}%", format!("{:.2}", chance * 100.0).trim_end_matches('0').trim_end_matches('.')),
+    }
+}
+
+fn field_hint<'a>(message: Option<String>) -> Element<'a, Message> {
+    match message {
+        Some(message) => text(message)
+            .size(11)
+            .color(Color::from_rgb(0.9, 0.3, 0.3))
+            .into(),
+        None => Space::with_height(Length::Fixed(0.0)).into(),
+    }
+}
+
+// Define function to get pick list style
+fn get_pick_list_style(dark_mode: bool, high_contrast: bool) -> pick_list::Style {
+    if high_contrast {
+        return accessibility::high_contrast_pick_list(dark_mode);
+    }
+    pick_list::Style {
+        placeholder_color: if dark_mode {
+            Color::from_rgb(0.6, 0.6, 0.6)
+        } else {
+            Color::from_rgb(0.4, 0.4, 0.4)
+        },
+        handle_color: if dark_mode {
+            Color::from_rgb(0.7, 0.7, 0.7)
+        } else {
+            Color::from_rgb(0.4, 0.4, 0.4)
+        },
+        text_color: if dark_mode {
+            Color::from_rgb(0.9, 0.9, 0.9)
+        } else {
+            Color::BLACK
+        },
+        background: Background::Color(
+            if dark_mode {
+                Color::from_rgb(0.25, 0.25, 0.3)
+            } else {
+                Color::WHITE
+            }
+        ),
+        border: Border {
+            color: if dark_mode {
+                Color::from_rgb(0.4, 0.4, 0.45)
+            } else {
+                Color::from_rgb(0.8, 0.8, 0.8)
+            },
+            width: 1.0,
+            radius: 6.0.into(),
+        },
+    }
+}
+
+// Define function to get checkbox style
+fn get_checkbox_style(dark_mode: bool, high_contrast: bool) -> checkbox::Style {
+    if high_contrast {
+        return accessibility::high_contrast_checkbox(dark_mode);
+    }
+    checkbox::Style {
+        background: Background::Color(
+            if dark_mode {
+                Color::from_rgb(0.25, 0.25, 0.3)
+            } else {
+                Color::WHITE
+            }
+        ),
+        icon_color: if dark_mode {
+            Color::from_rgb(0.5, 0.8, 0.5)
+        } else {
+            Color::from_rgb(0.2, 0.6, 0.2)
+        },
+        border: Border {
+            color: if dark_mode {
+                Color::from_rgb(0.4, 0.4, 0.45)
+            } else {
+                Color::from_rgb(0.8, 0.8, 0.8)
+            },
+            width: 1.0,
+            radius: 4.0.into(),
+        },
+        text_color: Some(if dark_mode {
+            Color::from_rgb(0.9, 0.9, 0.9)
+        } else {
+            Color::BLACK
+        }),
+    }
+}
+
+// Define function to get the flat text-only button style
+/// Native dialog for a saved results file, starting where results are saved
+async fn pick_results_file(filename: String) -> Option<String> {
+    let mut dialog = rfd::AsyncFileDialog::new()
+        .set_title("Open results")
+        .add_filter("Results", &["txt", "csv", "json"]);
+    if let Some(directory) = std::path::Path::new(&filename).parent().filter(|directory| directory.is_dir()) {
+        dialog = dialog.set_directory(directory);
+    }
+    dialog.pick_file().await.map(|file| file.path().to_string_lossy().into_owned())
+}
+
+fn get_link_button_style(dark_mode: bool, high_contrast: bool, status: button::Status) -> button::Style {
+    if high_contrast {
+        return accessibility::high_contrast_button(dark_mode, status);
+    }
+    let is_pressed = status == button::Status::Pressed;
+    button::Style {
+        background: Some(Background::Color(
+            if is_pressed {
+                if dark_mode {
+                    Color::from_rgb(0.2, 0.2, 0.25)
+                } else {
+                    Color::from_rgb(0.9, 0.9, 0.9)
+                }
+            } else {
+                Color::TRANSPARENT
+            }
+        )),
+        border: Border {
+            color: Color::TRANSPARENT,
+            width: 0.0,
+            radius: 8.0.into(),
+        },
+        text_color: if dark_mode {
+            Color::from_rgb(0.7, 0.7, 0.7)
+        } else {
+            Color::from_rgb(0.5, 0.5, 0.5)
+        },
+        ..Default::default()
+    }
+}
+
 fn main() -> iced::Result {
-    iced::application(
-        RandomGeneratorApp::title,
-        RandomGeneratorApp::update,
-        RandomGeneratorApp::view,
-    )
-        .theme(RandomGeneratorApp::theme)
-        .window(iced::window::Settings {
-            size: iced::Size::new(400.0, 400.0),
-            position: Default::default(),
-            min_size: Some(iced::Size::new(300.0, 400.0)),
-            max_size: Some(iced::Size::new(400.0, 600.0)),
-            visible: true,
-            resizable: true,
-            decorations: true,
-            transparent: false,
-            level: iced::window::Level::Normal,
-            icon: None,
-            platform_specific: Default::default(),
-            exit_on_close_request: true,
-        })
-        .run_with(RandomGeneratorApp::new)
-}
\ No newline at end of file
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    // Desktops start the program with the clicked link as the only argument
+    let link = match args.as_slice() {
+        [] => None,
+        [url] if deep_link::is_link(url) => Some(deep_link::parse(url)),
+        _ => std::process::exit(cli::run(&args)),
+    };
+
+    iced::daemon(Windows::title, Windows::update, Windows::view)
+        .theme(Windows::theme)
+        .subscription(Windows::subscription)
+        .run_with(move || Windows::new(link))
+}
@@ -0,0 +1,193 @@
+//! Panel merging saved result files.
+//!
+//! Several files saved from earlier sessions are read back, one path per
+//! line, and put together in order, optionally keeping only the first of
+//! each number. The panel shows how many numbers each file had and the
+//! stats of them all, and can open the merged numbers as a result tab to
+//! filter, compare or save them again. Reading and merging are
+//! `ExportFormat::read_numbers` and `MergedResults` in the core.
+
+use std::fs;
+
+use iced::widget::{button, checkbox, column, container, row, scrollable, text, text_editor, Space};
+use iced::{alignment, Background, Border, Color, Element, Font, Length, Shadow, Theme, Vector};
+use random_generator_core::{ExportFormat, MergedResults};
+
+use crate::{accessibility, get_checkbox_style, get_link_button_style, Message, RandomGeneratorApp};
+
+#[derive(Debug, Default)]
+pub struct MergePanel {
+    /// One path per line
+    files: text_editor::Content,
+    unique: bool,
+    merged: Option<MergedResults<i64>>,
+    status: String,
+}
+
+impl MergePanel {
+    pub fn edit_files(&mut self, action: text_editor::Action) {
+        self.files.perform(action);
+    }
+
+    pub fn set_unique(&mut self, unique: bool) {
+        self.unique = unique;
+    }
+
+    /// Reads the files again on every run, so newly saved results show up
+    pub fn run(&mut self) {
+        self.merged = None;
+        let text = self.files.text();
+        let paths: Vec<&str> = text.lines().map(str::trim).filter(|path| !path.is_empty()).collect();
+        if paths.is_empty() {
+            self.status = "Enter the paths of the result files, one per line".to_string();
+            return;
+        }
+        let mut files = Vec::new();
+        for path in paths {
+            let numbers = fs::read_to_string(path)
+                .map_err(|e| format!("Could not read {}: {}", path, e))
+                .and_then(|data| ExportFormat::from_path(path).read_numbers(&data).map_err(|e| format!("{}: {}", path, e)));
+            match numbers {
+                Ok(numbers) => files.push((path.to_string(), numbers)),
+                Err(e) => {
+                    self.status = e;
+                    return;
+                }
+            }
+        }
+        let merged = MergedResults::new(files, self.unique);
+        let merged_from = format!("Merged {} numbers from {} files", merged.numbers.len(), merged.files.len());
+        self.status = match merged.duplicates_removed {
+            0 => merged_from,
+            1 => format!("{}, 1 duplicate removed", merged_from),
+            removed => format!("{}, {} duplicates removed", merged_from, removed),
+        };
+        self.merged = Some(merged);
+    }
+}
+
+impl RandomGeneratorApp {
+    /// Show the merged numbers as a result tab; they were never drawn here, so there's nothing to replay
+    pub(crate) fn open_merged_tab(&mut self) {
+        let Some(merged) = &self.merge.merged else {
+            return;
+        };
+        let numbers = merged.numbers.clone();
+        self.open_tab(numbers, None);
+        self.revealed_count = self.generator.get_numbers().len();
+        self.merge_open = false;
+    }
+
+    pub(crate) fn merge_view(&self) -> Element<'_, Message> {
+        let panel = &self.merge;
+        let dark_mode = self.settings.dark_mode;
+        let high_contrast = self.settings.high_contrast;
+        let action = |content: &'static str, message: Option<Message>| {
+            button(text(self.tr(content)).size(14))
+                .on_press_maybe(message)
+                .padding(self.button_padding())
+                .style(move |_theme: &Theme, status| get_link_button_style(dark_mode, high_contrast, status))
+        };
+
+        let preview = panel.merged.as_ref().map(|merged| {
+            let lines = merged.to_text().lines().map(|line| text(line.to_string()).size(12).font(Font::MONOSPACE).into()).collect::<Vec<_>>();
+            container(scrollable(column(lines)).height(Length::Fixed(160.0)))
+                .padding(6)
+                .width(Length::Fill)
+        });
+
+        let content = column![
+            text(self.tr("Merge files"))
+                .size(20)
+                .color(if dark_mode { Color::from_rgb(0.9, 0.9, 0.9) } else { Color::BLACK }),
+            Space::with_height(Length::Fixed(10.0)),
+            text_editor(&panel.files)
+                .on_action(Message::MergeFilesEdited)
+                .placeholder(self.tr("One result file per line"))
+                .height(Length::Fixed(90.0))
+                .size(14),
+            text(self.tr(".csv and .json files are read as those formats, others as text with one number per line"))
+                .size(11)
+                .color(accessibility::dim_text_color(dark_mode, high_contrast)),
+            row![
+                checkbox(self.tr("Remove duplicates"), panel.unique)
+                    .on_toggle(Message::MergeUniqueToggled)
+                    .size(self.checkbox_size())
+                    .text_size(14)
+                    .style(move |_theme: &Theme, _status| get_checkbox_style(dark_mode, high_contrast)),
+                Space::with_width(Length::Fill),
+                action("Merge", Some(Message::RunMerge)),
+            ]
+                .spacing(6)
+                .align_y(alignment::Vertical::Center),
+        ]
+            .push_maybe(preview)
+            .push(
+                row![
+                    text(&panel.status).size(13).color(accessibility::dim_text_color(dark_mode, high_contrast)),
+                    Space::with_width(Length::Fill),
+                    action("Open as tab", panel.merged.as_ref().filter(|merged| !merged.numbers.is_empty()).map(|_| Message::OpenMergedTab)),
+                    action("Close", Some(Message::CloseMerge)),
+                ]
+                    .spacing(6)
+                    .align_y(alignment::Vertical::Center),
+            )
+            .spacing(6)
+            .padding(24);
+
+        container(content)
+            .width(Length::Fixed(460.0))
+            .style(move |_theme: &Theme| {
+                iced::widget::container::Style {
+                    background: Some(Background::Color(if dark_mode { Color::from_rgb(0.2, 0.2, 0.25) } else { Color::WHITE })),
+                    border: Border {
+                        color: if dark_mode { Color::from_rgb(0.4, 0.4, 0.4) } else { Color::from_rgb(0.8, 0.8, 0.8) },
+                        width: 1.0,
+                        radius: 16.0.into(),
+                    },
+                    shadow: Shadow {
+                        color: Color::from_rgba(0.0, 0.0, 0.0, 0.3),
+                        offset: Vector::new(0.0, 4.0),
+                        blur_radius: 20.0,
+                    },
+                    ..Default::default()
+                }
+            })
+            .into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge_saved_files_into_a_tab() {
+        let dir = std::env::temp_dir();
+        let first = dir.join(format!("random-tool-merge-{}.txt", std::process::id()));
+        let second = dir.join(format!("random-tool-merge-{}.csv", std::process::id()));
+        fs::write(&first, "#1: 4\n#2: 1,024").unwrap();
+        fs::write(&second, "index,value\n1,4\n2,9\n").unwrap();
+
+        let mut app = RandomGeneratorApp::default();
+        let paths = format!("{}\n\n{}\n", first.display(), second.display());
+        app.merge.files = text_editor::Content::with_text(&paths);
+        let _ = app.update(Message::MergeUniqueToggled(true));
+        let _ = app.update(Message::RunMerge);
+        assert_eq!(app.merge.status, "Merged 3 numbers from 2 files, 1 duplicate removed");
+        assert_eq!(app.merge.merged.as_ref().unwrap().numbers, [4, 1024, 9]);
+
+        let _ = app.update(Message::OpenMergedTab);
+        assert_eq!(app.tabs.len(), 1);
+        assert_eq!(app.generator.get_numbers(), &[4, 1024, 9]);
+        assert!(app.tabs[0].draw.is_none());
+        assert!(!app.merge_open);
+
+        fs::write(&second, "index,value\n1,four\n").unwrap();
+        let _ = app.update(Message::RunMerge);
+        assert!(app.merge.merged.is_none());
+        assert!(app.merge.status.ends_with("Results file line 2: 'four' is not a number"));
+        fs::remove_file(&first).unwrap();
+        fs::remove_file(&second).unwrap();
+    }
+}
@@ -0,0 +1,93 @@
+//! Counters for `/metrics` in server mode, in the Prometheus text format.
+//!
+//! The HTTP server answers `GET /metrics` with these and the daemon has a
+//! `metrics` method returning the same text, so either can be scraped or
+//! checked by an operator.
+
+use std::fmt::Write;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+/// Upper bounds of the latency histogram buckets, in seconds
+const BUCKETS: [f64; 10] = [0.0005, 0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.5, 1.0];
+
+/// Totals since the process started; updated from every connection thread
+#[derive(Debug, Default)]
+pub struct Metrics {
+    generations: AtomicU64,
+    values: AtomicU64,
+    errors: AtomicU64,
+    /// Draws per bucket, not cumulative; the last entry is above every bound
+    latency: [AtomicU64; BUCKETS.len() + 1],
+    latency_nanos: AtomicU64,
+}
+
+impl Metrics {
+    /// A finished draw of `values` numbers
+    pub fn record_generation(&self, values: usize, elapsed: Duration) {
+        self.generations.fetch_add(1, Ordering::Relaxed);
+        self.values.fetch_add(values as u64, Ordering::Relaxed);
+        let seconds = elapsed.as_secs_f64();
+        let bucket = BUCKETS.iter().position(|&bound| seconds <= bound).unwrap_or(BUCKETS.len());
+        self.latency[bucket].fetch_add(1, Ordering::Relaxed);
+        self.latency_nanos.fetch_add(elapsed.as_nanos() as u64, Ordering::Relaxed);
+    }
+
+    /// A request that was refused or whose draw failed
+    pub fn record_error(&self) {
+        self.errors.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Everything in the Prometheus text exposition format
+    pub fn render(&self) -> String {
+        let load = |counter: &AtomicU64| counter.load(Ordering::Relaxed);
+        let mut text = String::new();
+        let mut counter = |name: &str, help: &str, value: u64| {
+            let _ = writeln!(text, "# HELP {} {}\n# TYPE {} counter\n{} {}", name, help, name, name, value);
+        };
+        counter("random_tool_generations_total", "Draws completed.", load(&self.generations));
+        counter("random_tool_values_total", "Numbers produced by all draws.", load(&self.values));
+        counter("random_tool_errors_total", "Requests refused or draws that failed.", load(&self.errors));
+
+        let name = "random_tool_generation_seconds";
+        let _ = writeln!(text, "# HELP {} Time taken by each draw.\n# TYPE {} histogram", name, name);
+        let mut cumulative = 0;
+        for (bound, count) in BUCKETS.iter().zip(&self.latency) {
+            cumulative += load(count);
+            let _ = writeln!(text, "{}_bucket{{le=\"{}\"}} {}", name, bound, cumulative);
+        }
+        cumulative += load(&self.latency[BUCKETS.len()]);
+        let _ = writeln!(text, "{}_bucket{{le=\"+Inf\"}} {}", name, cumulative);
+        let _ = writeln!(text, "{}_sum {}", name, load(&self.latency_nanos) as f64 / 1e9);
+        let _ = writeln!(text, "{}_count {}", name, cumulative);
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let metrics = Metrics::default();
+        metrics.record_generation(5, Duration::from_micros(200));
+        metrics.record_generation(10, Duration::from_millis(20));
+        metrics.record_generation(1, Duration::from_secs(3));
+        metrics.record_error();
+
+        let text = metrics.render();
+        let lines: Vec<&str> = text.lines().collect();
+        assert!(lines.contains(&"random_tool_generations_total 3"));
+        assert!(lines.contains(&"random_tool_values_total 16"));
+        assert!(lines.contains(&"random_tool_errors_total 1"));
+        assert!(lines.contains(&"# TYPE random_tool_generation_seconds histogram"));
+        assert!(lines.contains(&"random_tool_generation_seconds_bucket{le=\"0.0005\"} 1"));
+        assert!(lines.contains(&"random_tool_generation_seconds_bucket{le=\"0.01\"} 1"));
+        assert!(lines.contains(&"random_tool_generation_seconds_bucket{le=\"0.025\"} 2"));
+        assert!(lines.contains(&"random_tool_generation_seconds_bucket{le=\"1\"} 2"));
+        assert!(lines.contains(&"random_tool_generation_seconds_bucket{le=\"+Inf\"} 3"));
+        assert!(lines.contains(&"random_tool_generation_seconds_sum 3.0202"));
+        assert!(lines.contains(&"random_tool_generation_seconds_count 3"));
+    }
+}
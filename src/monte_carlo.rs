@@ -0,0 +1,341 @@
+//! Panel for the Monte Carlo demo.
+//!
+//! Random points estimate π, or random `x` values estimate the integral of a
+//! formula. The samples are drawn on a worker thread that streams the running
+//! estimate back, so the convergence chart grows while the demo runs and the
+//! dashed line shows where π is. The estimates come from `MonteCarlo` in the
+//! core.
+
+use std::f64::consts::PI;
+use std::fmt;
+use std::fs;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use iced::widget::{button, canvas, column, container, pick_list, progress_bar, row, text, text_input, Space};
+use iced::{alignment, Background, Border, Color, Element, Length, Shadow, Task, Theme, Vector};
+use random_generator_core::{Formula, MonteCarlo, MonteCarloReport, MonteCarloTarget, Seed};
+
+use crate::line_chart::LineChart;
+use crate::worker::{self, MonteCarloEvent};
+use crate::{accessibility, get_link_button_style, get_pick_list_style, get_text_input_style, Message, RandomGeneratorApp};
+
+/// What the demo estimates
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Estimate {
+    #[default]
+    Pi,
+    Integral,
+}
+
+impl Estimate {
+    pub const ALL: [Estimate; 2] = [Estimate::Pi, Estimate::Integral];
+}
+
+impl fmt::Display for Estimate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Estimate::Pi => write!(f, "π"),
+            Estimate::Integral => write!(f, "Integral"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct MonteCarloPanel {
+    estimate: Estimate,
+    formula: String,
+    lower: String,
+    upper: String,
+    samples: String,
+    filename: String,
+    /// Samples to draw while a run is going
+    running: Option<usize>,
+    cancel: Arc<AtomicBool>,
+    /// Exact answer of the current run, marked on the chart
+    reference: Option<f64>,
+    /// Estimates streamed so far by the running demo
+    live: Vec<(usize, f64)>,
+    chart: Option<LineChart>,
+    report: Option<MonteCarloReport>,
+    status: String,
+}
+
+impl Default for MonteCarloPanel {
+    fn default() -> Self {
+        Self {
+            estimate: Estimate::Pi,
+            formula: "x^2".to_string(),
+            lower: "0".to_string(),
+            upper: "1".to_string(),
+            samples: "1000000".to_string(),
+            filename: "convergence.csv".to_string(),
+            running: None,
+            cancel: Arc::new(AtomicBool::new(false)),
+            reference: None,
+            live: Vec::new(),
+            chart: None,
+            report: None,
+            status: String::new(),
+        }
+    }
+}
+
+impl MonteCarloPanel {
+    pub fn set_estimate(&mut self, estimate: Estimate) {
+        self.estimate = estimate;
+    }
+
+    pub fn set_formula(&mut self, formula: String) {
+        self.formula = formula;
+    }
+
+    pub fn set_lower(&mut self, lower: String) {
+        self.lower = lower;
+    }
+
+    pub fn set_upper(&mut self, upper: String) {
+        self.upper = upper;
+    }
+
+    pub fn set_samples(&mut self, samples: String) {
+        self.samples = samples;
+    }
+
+    pub fn set_filename(&mut self, filename: String) {
+        self.filename = filename;
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.running.is_some()
+    }
+
+    /// Start drawing samples in the background
+    pub fn start(&mut self) -> Task<Message> {
+        if self.is_running() {
+            return Task::none();
+        }
+        let Ok(samples) = self.samples.trim().parse() else {
+            self.status = "Enter a whole number of samples".to_string();
+            return Task::none();
+        };
+        let target = match self.estimate {
+            Estimate::Pi => MonteCarloTarget::Pi,
+            Estimate::Integral => {
+                let formula = match self.formula.parse::<Formula>() {
+                    Ok(formula) => formula,
+                    Err(e) => {
+                        self.status = e.to_string();
+                        return Task::none();
+                    }
+                };
+                let (Ok(lower), Ok(upper)) = (self.lower.trim().parse(), self.upper.trim().parse()) else {
+                    self.status = "Enter numbers for both limits".to_string();
+                    return Task::none();
+                };
+                MonteCarloTarget::Integral { formula, lower, upper }
+            }
+        };
+        let demo = MonteCarlo { target, samples };
+        if let Err(e) = demo.validate() {
+            self.status = e.to_string();
+            return Task::none();
+        }
+
+        self.running = Some(samples);
+        self.live.clear();
+        self.chart = None;
+        self.report = None;
+        self.status.clear();
+        self.cancel = Arc::new(AtomicBool::new(false));
+        self.reference = matches!(demo.target, MonteCarloTarget::Pi).then_some(PI);
+        Task::run(
+            worker::monte_carlo(demo, Seed::random(&mut rand::thread_rng()), self.cancel.clone()),
+            Message::MonteCarlo,
+        )
+    }
+
+    pub fn cancel(&mut self) {
+        self.cancel.store(true, Ordering::Relaxed);
+    }
+
+    pub fn handle(&mut self, event: MonteCarloEvent) {
+        match event {
+            MonteCarloEvent::Progress(samples, estimate) => {
+                if self.is_running() {
+                    self.live.push((samples, estimate));
+                    self.chart = Some(self.convergence_chart(&self.live));
+                }
+            }
+            MonteCarloEvent::Finished(result) => {
+                self.running = None;
+                self.live.clear();
+                match result {
+                    Ok(report) => {
+                        self.chart = Some(self.convergence_chart(&report.convergence));
+                        self.status.clear();
+                        self.report = Some(report);
+                    }
+                    Err(e) => self.status = e,
+                }
+            }
+        }
+    }
+
+    /// Running estimate against the samples drawn, with the exact answer marked when there is one
+    fn convergence_chart(&self, points: &[(usize, f64)]) -> LineChart {
+        let estimates: Vec<f64> = points.iter().map(|&(_, estimate)| estimate).collect();
+        let first = points.first().map_or(0, |&(samples, _)| samples);
+        let last = points.last().map_or(0, |&(samples, _)| samples);
+        let chart = LineChart::new(&estimates).with_x_labels(first.to_string(), last.to_string());
+        match self.reference {
+            Some(exact) => chart.with_reference(exact),
+            None => chart,
+        }
+    }
+
+    pub fn export(&mut self) {
+        let Some(report) = &self.report else {
+            self.status = "Nothing to export yet".to_string();
+            return;
+        };
+        self.status = match fs::write(&self.filename, report.to_csv()) {
+            Ok(()) => format!("Saved to {}", self.filename),
+            Err(e) => format!("Export error: {}", e),
+        };
+    }
+}
+
+impl RandomGeneratorApp {
+    pub(crate) fn monte_carlo_view(&self) -> Element<'_, Message> {
+        let panel = &self.monte_carlo;
+        let dark_mode = self.settings.dark_mode;
+        let high_contrast = self.settings.high_contrast;
+        let label = |content: &'static str| text(self.tr(content)).size(14).width(Length::Fixed(80.0));
+        let action = |content: &'static str, message: Option<Message>| {
+            button(text(self.tr(content)).size(14))
+                .on_press_maybe(message)
+                .padding(self.button_padding())
+                .style(move |_theme: &Theme, status| get_link_button_style(dark_mode, high_contrast, status))
+        };
+        let input = |value: &str, on_input: fn(String) -> Message, width: Length| {
+            text_input("", value)
+                .on_input(on_input)
+                .on_submit(Message::RunMonteCarlo)
+                .width(width)
+                .size(14)
+                .style(move |_theme: &Theme, status| get_text_input_style(dark_mode, high_contrast, status))
+        };
+        let running = panel.is_running();
+
+        let integral = (panel.estimate == Estimate::Integral).then(|| {
+            column![
+                row![label("Formula"), input(&panel.formula, Message::MonteCarloFormulaChanged, Length::Fill)]
+                    .spacing(6)
+                    .align_y(alignment::Vertical::Center),
+                row![
+                    label("From"),
+                    input(&panel.lower, Message::MonteCarloLowerChanged, Length::Fixed(80.0)),
+                    label("To"),
+                    input(&panel.upper, Message::MonteCarloUpperChanged, Length::Fixed(80.0)),
+                ]
+                    .spacing(6)
+                    .align_y(alignment::Vertical::Center),
+            ]
+                .spacing(6)
+        });
+
+        let progress = panel.running.map(|samples| {
+            let fraction = panel.live.last().map_or(0.0, |&(done, _)| done as f32 / samples as f32);
+            row![
+                progress_bar(0.0..=1.0, fraction).height(Length::Fixed(8.0)),
+                text(format!("{:.0}%", fraction * 100.0)).size(13),
+                action("Cancel", Some(Message::CancelMonteCarlo)),
+            ]
+                .spacing(8)
+                .align_y(alignment::Vertical::Center)
+        });
+
+        let chart = panel.chart.as_ref().map(|chart| canvas(chart).width(Length::Fill).height(Length::Fixed(220.0)));
+        let report = panel.report.as_ref().map(|report| text(report.to_string()).size(13).font(iced::Font::MONOSPACE));
+
+        let content = column![
+            text(self.tr("Monte Carlo"))
+                .size(20)
+                .color(if dark_mode { Color::from_rgb(0.9, 0.9, 0.9) } else { Color::BLACK }),
+            Space::with_height(Length::Fixed(10.0)),
+            row![
+                label("Estimate"),
+                pick_list(&Estimate::ALL[..], Some(panel.estimate), Message::MonteCarloEstimateChanged)
+                    .text_size(14)
+                    .width(Length::Fixed(100.0))
+                    .style(move |_theme: &Theme, _status| get_pick_list_style(dark_mode, high_contrast)),
+            ]
+                .spacing(6)
+                .align_y(alignment::Vertical::Center),
+        ]
+            .push_maybe(integral)
+            .push(
+                row![
+                    label("Samples"),
+                    input(&panel.samples, Message::MonteCarloSamplesChanged, Length::Fixed(120.0)),
+                    Space::with_width(Length::Fill),
+                    action("Run", (!running).then_some(Message::RunMonteCarlo)),
+                ]
+                    .spacing(6)
+                    .align_y(alignment::Vertical::Center),
+            )
+            .push(
+                text(self.tr("π: points in a unit square, 4 × the share inside the quarter circle. Integral: the average of the formula at random x, times the width. The error shrinks like 1/√N"))
+                    .size(11)
+                    .color(accessibility::dim_text_color(dark_mode, high_contrast)),
+            )
+            .push_maybe(progress)
+            .push_maybe(chart)
+            .push_maybe(report)
+            .push(
+                row![
+                    label("File"),
+                    text_input("", &panel.filename)
+                        .on_input(Message::MonteCarloFilenameChanged)
+                        .on_submit(Message::ExportMonteCarlo)
+                        .width(Length::Fill)
+                        .size(14)
+                        .style(move |_theme: &Theme, status| get_text_input_style(dark_mode, high_contrast, status)),
+                    action("Export CSV", Some(Message::ExportMonteCarlo)),
+                ]
+                    .spacing(6)
+                    .align_y(alignment::Vertical::Center),
+            )
+            .push(
+                row![
+                    text(&panel.status).size(13).color(accessibility::dim_text_color(dark_mode, high_contrast)),
+                    Space::with_width(Length::Fill),
+                    action("Close", Some(Message::CloseMonteCarlo)),
+                ]
+                    .align_y(alignment::Vertical::Center),
+            )
+            .spacing(6)
+            .padding(24);
+
+        container(content)
+            .width(Length::Fixed(480.0))
+            .style(move |_theme: &Theme| {
+                iced::widget::container::Style {
+                    background: Some(Background::Color(if dark_mode { Color::from_rgb(0.2, 0.2, 0.25) } else { Color::WHITE })),
+                    border: Border {
+                        color: if dark_mode { Color::from_rgb(0.4, 0.4, 0.4) } else { Color::from_rgb(0.8, 0.8, 0.8) },
+                        width: 1.0,
+                        radius: 16.0.into(),
+                    },
+                    shadow: Shadow {
+                        color: Color::from_rgba(0.0, 0.0, 0.0, 0.3),
+                        offset: Vector::new(0.0, 4.0),
+                        blur_radius: 20.0,
+                    },
+                    ..Default::default()
+                }
+            })
+            .into()
+    }
+}
@@ -0,0 +1,29 @@
+//! Native desktop notification when a draw finishes while the user may be elsewhere.
+
+use std::thread;
+use std::time::Duration;
+
+/// Draws quicker than this finish while the user is still watching, so they don't notify
+pub const MIN_DURATION: Duration = Duration::from_secs(2);
+
+/// How many values are listed in the notification body
+pub const PREVIEW_COUNT: usize = 5;
+
+/// Announce `total` finished results, listing `preview`: the first few, formatted.
+///
+/// Runs on its own thread because talking to the notification service may block.
+pub fn results_ready(preview: Vec<String>, total: usize) {
+    let mut body = preview.into_iter().take(PREVIEW_COUNT).collect::<Vec<_>>().join(", ");
+    if total > PREVIEW_COUNT {
+        body.push_str(&format!(" … (+{} more)", total - PREVIEW_COUNT));
+    }
+
+    thread::spawn(move || {
+        // Nothing useful to do if no notification service is running
+        let _ = notify_rust::Notification::new()
+            .summary(&format!("{} random numbers ready", total))
+            .body(&body)
+            .appname("Random Generator")
+            .show();
+    });
+}
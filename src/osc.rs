@@ -0,0 +1,100 @@
+//! Open Sound Control output for show-control software.
+//!
+//! With a target set in the settings, every number of a finished draw is sent
+//! over UDP as its own OSC message, `<address> ,ii <index> <value>` (`,ih` when
+//! the value doesn't fit in 32 bits), so QLab or TouchDesigner cues can fire on it.
+
+use std::io;
+use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr, ToSocketAddrs, UdpSocket};
+use std::sync::Arc;
+use std::thread;
+
+use iced::futures::channel::oneshot;
+
+/// Send every number of a draw on a background thread; resolves to an error message if it failed
+pub async fn send_draw(target: String, address: String, numbers: Arc<[i64]>) -> Result<(), String> {
+    let (sender, receiver) = oneshot::channel();
+    thread::spawn(move || {
+        let result = send(&target, &address, &numbers).map_err(|e| format!("could not send to {}: {}", target, e));
+        let _ = sender.send(result);
+    });
+    receiver.await.unwrap_or_else(|_| Err("sending stopped unexpectedly".to_string()))
+}
+
+/// One message per number, in the order they were drawn
+pub fn send(target: &str, address: &str, numbers: &[i64]) -> io::Result<()> {
+    if !address.starts_with('/') || address.contains([' ', '#', '*', ',', '?', '[', ']', '{', '}']) {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, format!("'{}' is not an OSC address", address)));
+    }
+    let socket = connect(target)?;
+    for (index, &value) in numbers.iter().enumerate() {
+        socket.send(&message(address, index as i32, value))?;
+    }
+    Ok(())
+}
+
+/// A socket of the target's address family connected to it, trying each address the name resolves to
+fn connect(target: &str) -> io::Result<UdpSocket> {
+    let mut last_error = io::Error::new(io::ErrorKind::InvalidInput, format!("'{}' resolves to no address", target));
+    for address in target.to_socket_addrs()? {
+        let local = match address {
+            SocketAddr::V4(_) => SocketAddr::from((Ipv4Addr::UNSPECIFIED, 0)),
+            SocketAddr::V6(_) => SocketAddr::from((Ipv6Addr::UNSPECIFIED, 0)),
+        };
+        match UdpSocket::bind(local).and_then(|socket| socket.connect(address).map(|()| socket)) {
+            Ok(socket) => return Ok(socket),
+            Err(e) => last_error = e,
+        }
+    }
+    Err(last_error)
+}
+
+fn message(address: &str, index: i32, value: i64) -> Vec<u8> {
+    let mut packet = Vec::new();
+    put_string(&mut packet, address);
+    let small = i32::try_from(value).ok();
+    put_string(&mut packet, if small.is_some() { ",ii" } else { ",ih" });
+    packet.extend_from_slice(&index.to_be_bytes());
+    match small {
+        Some(value) => packet.extend_from_slice(&value.to_be_bytes()),
+        None => packet.extend_from_slice(&value.to_be_bytes()),
+    }
+    packet
+}
+
+/// OSC strings end with a NUL and are padded to a multiple of four bytes
+fn put_string(packet: &mut Vec<u8>, text: &str) {
+    packet.extend_from_slice(text.as_bytes());
+    let padding = 4 - text.len() % 4;
+    packet.extend(std::iter::repeat_n(0, padding));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_send_values() {
+        let receiver = UdpSocket::bind("127.0.0.1:0").unwrap();
+        receiver.set_read_timeout(Some(Duration::from_secs(5))).unwrap();
+        let target = receiver.local_addr().unwrap().to_string();
+
+        send(&target, "/raffle", &[42, 1 << 40]).unwrap();
+        let mut buffer = [0; 64];
+        let length = receiver.recv(&mut buffer).unwrap();
+        assert_eq!(&buffer[..length], b"/raffle\0,ii\0\0\0\0\0\0\0\0\x2a");
+        let length = receiver.recv(&mut buffer).unwrap();
+        assert_eq!(&buffer[..length], b"/raffle\0,ih\0\0\0\0\x01\0\0\x01\0\0\0\0\0");
+
+        // IPv6 targets get an IPv6 socket
+        let receiver = UdpSocket::bind("[::1]:0").unwrap();
+        receiver.set_read_timeout(Some(Duration::from_secs(5))).unwrap();
+        send(&receiver.local_addr().unwrap().to_string(), "/raffle", &[7]).unwrap();
+        let length = receiver.recv(&mut buffer).unwrap();
+        assert_eq!(&buffer[..length], b"/raffle\0,ii\0\0\0\0\0\0\0\0\x07");
+
+        assert!(send(&target, "raffle", &[1]).is_err());
+        assert!(send(&target, "/raffle/*", &[1]).is_err());
+    }
+}
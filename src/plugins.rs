@@ -0,0 +1,60 @@
+//! Third-party modes and export formats loaded from the plugins folder.
+//!
+//! Every dynamic library in `random-tool/plugins` next to the settings file
+//! is loaded once at startup. Generators created afterwards, in the window or
+//! on a worker thread, get the plugins' modes and formats through `install`.
+
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+use random_generator_core::{GeneratorConfig, Plugin, RandomGenerator, RandomGeneratorError};
+
+use crate::source_params::SourceParams;
+
+struct Loaded {
+    plugins: Vec<Plugin>,
+    /// One line per library that could not be loaded
+    failures: Vec<String>,
+}
+
+static LOADED: OnceLock<Loaded> = OnceLock::new();
+
+fn loaded() -> &'static Loaded {
+    LOADED.get_or_init(|| {
+        let Some(dir) = dir() else {
+            return Loaded { plugins: Vec::new(), failures: Vec::new() };
+        };
+        // SAFETY: the user installed these libraries to extend this program
+        let (plugins, failures) = unsafe { Plugin::load_dir(&dir) };
+        let failures = failures
+            .into_iter()
+            .map(|(path, e)| format!("{}: {}", path.file_name().unwrap_or_default().to_string_lossy(), e))
+            .collect();
+        Loaded { plugins, failures }
+    })
+}
+
+fn dir() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("random-tool").join("plugins"))
+}
+
+/// Register every plugin's modes and export formats with `generator`
+pub fn install(generator: &mut RandomGenerator) {
+    for plugin in &loaded().plugins {
+        plugin.install(generator);
+    }
+}
+
+/// A generator with the plugins and the mode's source with `params` installed, so `config` may use a plugin mode
+pub fn generator(config: GeneratorConfig, params: &SourceParams) -> Result<RandomGenerator, RandomGeneratorError> {
+    let mut generator = RandomGenerator::new();
+    install(&mut generator);
+    params.install(config.mode, &mut generator);
+    generator.set_config(config)?;
+    Ok(generator)
+}
+
+/// Why plugins failed to load, for showing once the window opens
+pub fn failures() -> &'static [String] {
+    &loaded().failures
+}
@@ -0,0 +1,94 @@
+use iced::keyboard::{self, key};
+use iced::widget::{button, canvas, column, container, row, stack, text, Space};
+use iced::{alignment, Background, Color, Element, Length, Theme};
+
+use crate::{accessibility, get_link_button_style, Message, RandomGeneratorApp};
+
+/// Map key presses to presentation actions while the fullscreen view is open
+pub fn handle_key(key: keyboard::Key, _modifiers: keyboard::Modifiers) -> Option<Message> {
+    match key {
+        keyboard::Key::Named(key::Named::Space) => Some(Message::PresentationNext),
+        keyboard::Key::Named(key::Named::Escape) => Some(Message::ExitPresentation),
+        _ => None,
+    }
+}
+
+impl RandomGeneratorApp {
+    /// Fullscreen view showing one result at a time in very large type, under the wheel when it spins
+    pub(crate) fn presentation_view(&self) -> Element<'_, Message> {
+        let numbers = self.generator.get_numbers();
+        let dim_color = accessibility::dim_text_color(self.settings.dark_mode, self.settings.high_contrast);
+        let main_color = if self.settings.dark_mode {
+            Color::from_rgb(0.95, 0.95, 0.95)
+        } else {
+            Color::BLACK
+        };
+
+        let (position, value) = match numbers.get(self.presentation_index) {
+            Some(num) => (
+                format!("{} / {}", self.presentation_index + 1, numbers.len()),
+                // Follow the pointer while the wheel turns
+                match &self.wheel {
+                    Some(wheel) => wheel.current_label().to_string(),
+                    None => self.display_number(self.presentation_index, *num),
+                },
+            ),
+            None => (String::new(), self.tr("No results yet").to_string()),
+        };
+
+        let hint = self.tr(if self.presentation_index + 1 < numbers.len() {
+            "Space: next    Esc: exit"
+        } else {
+            "Esc: exit"
+        });
+
+        let content = column![
+            row![
+                Space::with_width(Length::Fill),
+                button(text(self.tr("Exit")).size(13))
+                    .on_press(Message::ExitPresentation)
+                    .padding(self.button_padding())
+                    .style(move |_theme: &Theme, status| get_link_button_style(self.settings.dark_mode, self.settings.high_contrast, status)),
+            ],
+            Space::with_height(Length::Fill),
+            text(position).size(24).color(dim_color),
+        ]
+            .push_maybe(self.wheel.as_ref().map(|wheel| canvas(wheel).width(Length::Fill).height(Length::FillPortion(4))))
+            .push(
+                text(value)
+                    .size(if numbers.is_empty() { 48 } else if self.wheel.is_some() { 96 } else { 180 })
+                    .font(iced::Font::MONOSPACE)
+                    .color(main_color),
+            )
+            .push(Space::with_height(Length::Fill))
+            .push(text(hint).size(14).color(dim_color))
+            .spacing(12)
+            .padding(20)
+            .align_x(alignment::Horizontal::Center);
+
+        let base = container(content)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .style(move |_theme: &Theme| {
+                iced::widget::container::Style {
+                    background: Some(Background::Color(
+                        if self.settings.dark_mode {
+                            Color::from_rgb(0.1, 0.1, 0.13)
+                        } else {
+                            Color::WHITE
+                        }
+                    )),
+                    ..Default::default()
+                }
+            });
+
+        match &self.confetti {
+            Some(confetti) => stack![
+                base,
+                canvas(confetti).width(Length::Fill).height(Length::Fill),
+            ]
+                .into(),
+            None => base.into(),
+        }
+    }
+}
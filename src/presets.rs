@@ -0,0 +1,52 @@
+//! Named draw settings, listed by the gRPC service's `ListPresets`.
+//!
+//! `presets.json` sits next to the settings file and maps each name to a
+//! request body as `POST /generate` takes it, e.g.
+//! `{"lotto": {"from": 1, "to": 49, "count": 6, "order": "Sorted"}}`. The file
+//! is read again on every call, so edits show up without restarting the server.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::server::GenerateRequest;
+
+/// Saved draw settings by name, in name order
+#[derive(Debug, Default)]
+pub(crate) struct Presets(BTreeMap<String, GenerateRequest>);
+
+impl Presets {
+    /// Where the presets are kept: `presets.json` in the app's config directory
+    pub(crate) fn path() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join("random-tool").join("presets.json"))
+    }
+
+    /// Read the presets at `path`; none when the file doesn't exist
+    pub(crate) fn load(path: &Path) -> Result<Self, String> {
+        match fs::read_to_string(path) {
+            Ok(text) => Self::parse(&text).map_err(|e| format!("{}: {}", path.display(), e)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(format!("could not read {}: {}", path.display(), e)),
+        }
+    }
+
+    /// Presets from the text of `presets.json`
+    ///
+    /// A preset is settings only: a shuffle bag, cooldown or exclusion list is
+    /// the state of one client's draws and isn't shared through a preset.
+    pub(crate) fn parse(text: &str) -> Result<Self, String> {
+        let presets: BTreeMap<String, GenerateRequest> = serde_json::from_str(text).map_err(|e| e.to_string())?;
+        if let Some((name, _)) =
+            presets.iter().find(|(_, request)| request.bag.is_some() || request.cooldown.is_some() || request.excluded.is_some())
+        {
+            return Err(format!("preset '{}' has a bag, cooldown or exclusions; presets keep settings only", name));
+        }
+        Ok(Self(presets))
+    }
+
+    /// Each preset with its name
+    pub(crate) fn iter(&self) -> impl Iterator<Item = (&str, &GenerateRequest)> {
+        self.0.iter().map(|(name, request)| (name.as_str(), request))
+    }
+}
@@ -0,0 +1,200 @@
+//! Panel for writing a file of random bytes.
+//!
+//! Test fixtures and disk benchmarks need files of a given size with
+//! incompressible contents. The bytes come from `RandomBytes` in the core
+//! (ChaCha20 seeded from the system) and are written on a worker thread, so
+//! the window stays responsive and shows a progress bar.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use iced::widget::{button, column, container, pick_list, progress_bar, row, text, text_input, Space};
+use iced::{alignment, Background, Border, Color, Element, Length, Shadow, Task, Theme, Vector};
+use random_generator_core::{ByteSize, ByteUnit, RandomBytes, Seed};
+
+use crate::worker::{self, PanelEvent};
+use crate::{accessibility, get_link_button_style, get_pick_list_style, get_text_input_style, Message, RandomGeneratorApp};
+
+#[derive(Debug)]
+pub struct RandomFilePanel {
+    size: String,
+    unit: ByteUnit,
+    filename: String,
+    /// Fraction written while a write is running
+    progress: Option<f32>,
+    cancel: Arc<AtomicBool>,
+    status: String,
+}
+
+impl Default for RandomFilePanel {
+    fn default() -> Self {
+        Self {
+            size: "10".to_string(),
+            unit: ByteUnit::Megabytes,
+            filename: "random.bin".to_string(),
+            progress: None,
+            cancel: Arc::new(AtomicBool::new(false)),
+            status: String::new(),
+        }
+    }
+}
+
+impl RandomFilePanel {
+    pub fn set_size(&mut self, size: String) {
+        self.size = size;
+    }
+
+    pub fn set_unit(&mut self, unit: ByteUnit) {
+        self.unit = unit;
+    }
+
+    pub fn set_filename(&mut self, filename: String) {
+        self.filename = filename;
+    }
+
+    pub fn is_writing(&self) -> bool {
+        self.progress.is_some()
+    }
+
+    /// Start writing the file in the background
+    pub fn start(&mut self) -> Task<Message> {
+        if self.is_writing() {
+            return Task::none();
+        }
+        let size = self.size.trim().parse::<f64>().ok().and_then(|amount| ByteSize::new(amount, self.unit).ok());
+        let size = match size {
+            Some(size) if size.0 > 0 => size,
+            _ => {
+                self.status = "Enter a size greater than 0".to_string();
+                return Task::none();
+            }
+        };
+        if self.filename.trim().is_empty() {
+            self.status = "Enter a file name".to_string();
+            return Task::none();
+        }
+
+        let bytes = RandomBytes { size, seed: Seed::random(&mut rand::rngs::OsRng) };
+        self.progress = Some(0.0);
+        self.status.clear();
+        self.cancel = Arc::new(AtomicBool::new(false));
+        Task::run(
+            worker::write_random_file(self.filename.trim().to_string(), bytes, self.cancel.clone()),
+            Message::RandomFile,
+        )
+    }
+
+    pub fn cancel(&mut self) {
+        self.cancel.store(true, Ordering::Relaxed);
+    }
+
+    pub fn handle(&mut self, event: PanelEvent<String>) {
+        match event {
+            PanelEvent::Progress(fraction) => {
+                if self.progress.is_some() {
+                    self.progress = Some(fraction);
+                }
+            }
+            PanelEvent::Finished(result) => {
+                self.progress = None;
+                self.status = match result {
+                    Ok(done) => done,
+                    Err(e) => e,
+                };
+            }
+        }
+    }
+}
+
+impl RandomGeneratorApp {
+    pub(crate) fn random_file_view(&self) -> Element<'_, Message> {
+        let panel = &self.random_file;
+        let dark_mode = self.settings.dark_mode;
+        let high_contrast = self.settings.high_contrast;
+        let label = |content: &'static str| text(self.tr(content)).size(14).width(Length::Fixed(80.0));
+        let action = |content: &'static str, message: Option<Message>| {
+            button(text(self.tr(content)).size(14))
+                .on_press_maybe(message)
+                .padding(self.button_padding())
+                .style(move |_theme: &Theme, status| get_link_button_style(dark_mode, high_contrast, status))
+        };
+        let writing = panel.is_writing();
+
+        let progress = panel.progress.map(|fraction| {
+            row![
+                progress_bar(0.0..=1.0, fraction).height(Length::Fixed(8.0)),
+                text(format!("{:.0}%", fraction * 100.0)).size(13),
+                action("Cancel", Some(Message::CancelRandomFile)),
+            ]
+                .spacing(8)
+                .align_y(alignment::Vertical::Center)
+        });
+
+        let content = column![
+            text(self.tr("Random file"))
+                .size(20)
+                .color(if dark_mode { Color::from_rgb(0.9, 0.9, 0.9) } else { Color::BLACK }),
+            Space::with_height(Length::Fixed(10.0)),
+            row![
+                label("Size"),
+                text_input("", &panel.size)
+                    .on_input(Message::RandomFileSizeChanged)
+                    .on_submit(Message::WriteRandomFile)
+                    .width(Length::Fixed(100.0))
+                    .size(14)
+                    .style(move |_theme: &Theme, status| get_text_input_style(dark_mode, high_contrast, status)),
+                pick_list(&ByteUnit::ALL[..], Some(panel.unit), Message::RandomFileUnitChanged)
+                    .text_size(14)
+                    .style(move |_theme: &Theme, _status| get_pick_list_style(dark_mode, high_contrast)),
+            ]
+                .spacing(6)
+                .align_y(alignment::Vertical::Center),
+            row![
+                label("File"),
+                text_input("", &panel.filename)
+                    .on_input(Message::RandomFileNameChanged)
+                    .on_submit(Message::WriteRandomFile)
+                    .width(Length::Fill)
+                    .size(14)
+                    .style(move |_theme: &Theme, status| get_text_input_style(dark_mode, high_contrast, status)),
+                action("Write", (!writing).then_some(Message::WriteRandomFile)),
+            ]
+                .spacing(6)
+                .align_y(alignment::Vertical::Center),
+            text(self.tr("Bytes come from ChaCha20 seeded by the operating system; 1 KB is 1024 bytes"))
+                .size(11)
+                .color(accessibility::dim_text_color(dark_mode, high_contrast)),
+        ]
+            .push_maybe(progress)
+            .push(
+                row![
+                    text(&panel.status).size(13).color(accessibility::dim_text_color(dark_mode, high_contrast)),
+                    Space::with_width(Length::Fill),
+                    action("Close", Some(Message::CloseRandomFile)),
+                ]
+                    .align_y(alignment::Vertical::Center),
+            )
+            .spacing(6)
+            .padding(24);
+
+        container(content)
+            .width(Length::Fixed(420.0))
+            .style(move |_theme: &Theme| {
+                iced::widget::container::Style {
+                    background: Some(Background::Color(if dark_mode { Color::from_rgb(0.2, 0.2, 0.25) } else { Color::WHITE })),
+                    border: Border {
+                        color: if dark_mode { Color::from_rgb(0.4, 0.4, 0.4) } else { Color::from_rgb(0.8, 0.8, 0.8) },
+                        width: 1.0,
+                        radius: 16.0.into(),
+                    },
+                    shadow: Shadow {
+                        color: Color::from_rgba(0.0, 0.0, 0.0, 0.3),
+                        offset: Vector::new(0.0, 4.0),
+                        blur_radius: 20.0,
+                    },
+                    ..Default::default()
+                }
+            })
+            .into()
+    }
+}
@@ -0,0 +1,329 @@
+//! Draws answered by random.org's atmospheric-noise generator.
+//!
+//! With an API key configured, range and custom list draws ask the
+//! random.org JSON-RPC API's `generateIntegers` for the numbers themselves:
+//! the range's values directly, or positions in the list. `RandomOrgSource`
+//! stands in for the mode's own source and hands the draw to it when
+//! random.org can't answer it: offline, out of quota, or more than one
+//! request can carry. Other failures, such as a wrong key, fail the draw.
+//! Modes random.org can't draw, and draws with repeat limits or excluded
+//! values, are drawn locally as well; `DrawSource` says which one drew. The
+//! request is made with `ureq` over rustls, so no system TLS library or
+//! `curl` is needed.
+
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use rand::RngCore;
+use random_generator_core::{
+    GeneratorConfig, GeneratorMode, GeneratorSource, ListSource, Progress, RandomGenerator, RandomGeneratorError, RangeSource, SourceInput,
+};
+use serde_json::{json, Value};
+
+const ENDPOINT: &str = "https://api.random.org/json-rpc/4/invoke";
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Most integers one `generateIntegers` request returns
+const MAX_INTEGERS: usize = 10_000;
+
+/// random.org's integers lie within plus or minus this
+const LIMIT: i64 = 1_000_000_000;
+
+/// JSON-RPC error codes for a key that has used up its requests or its bits for the day
+const QUOTA_ERRORS: [i64; 2] = [402, 403];
+
+/// Allowance left on the API key, as reported with the last answer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Quota {
+    pub requests_left: i64,
+    pub bits_left: i64,
+}
+
+/// Who drew a generation's numbers when random.org was asked
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DrawSource {
+    /// random.org drew them, leaving this much on the key
+    RandomOrg(Quota),
+    /// The local generator drew them because random.org can't draw this kind of draw
+    Local(String),
+    /// The local generator drew them because random.org was offline, out of quota or the draw too large
+    Fallback(String),
+}
+
+/// Why random.org didn't answer
+#[derive(Debug, PartialEq, Eq)]
+enum Failure {
+    /// Offline or out of quota; the draw goes to the local generator
+    Unavailable(String),
+    /// Anything else, such as a wrong key; the draw fails
+    Failed(String),
+}
+
+/// One `generateIntegers` request and how its integers become the draw
+#[derive(Debug, PartialEq, Eq)]
+struct Request {
+    n: usize,
+    min: i64,
+    max: i64,
+    replacement: bool,
+    pick: Pick,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Pick {
+    /// The integers plus this are the results
+    Offset(i64),
+    /// The integers are positions in these values; without replacement the
+    /// first `count` distinct values are the results
+    Index { values: Vec<i64>, count: usize },
+}
+
+impl Request {
+    /// The request for `config`'s draw; the reason it's drawn locally when
+    /// random.org can't draw it, and whether that's because it's too large
+    fn new(config: &GeneratorConfig) -> Result<Request, (String, bool)> {
+        let count = config.num_to_generate;
+        if config.allow_duplicates && (config.max_repeats.is_some() || config.max_streak.is_some()) {
+            return Err(("random.org can't limit repeats".to_string(), false));
+        }
+        let request = match config.mode {
+            GeneratorMode::RANGE => {
+                if config.exclude_drawn.as_ref().is_some_and(|excluded| !excluded.is_empty()) {
+                    return Err(("random.org can't skip excluded values".to_string(), false));
+                }
+                let (lower, upper) = (config.lower_bound, config.upper_bound);
+                // A range outside ±10⁹ that is narrow enough is drawn from 0 and shifted
+                let (min, max, offset) = if lower >= -LIMIT && upper <= LIMIT {
+                    (lower, upper, 0)
+                } else if (upper as i128 - lower as i128) <= LIMIT as i128 {
+                    (0, upper - lower, lower)
+                } else {
+                    return Err((format!("random.org draws from ranges at most {} wide", LIMIT), true));
+                };
+                Request { n: count, min, max, replacement: config.allow_duplicates, pick: Pick::Offset(offset) }
+            }
+            GeneratorMode::CUSTOM_LIST => {
+                // Weighted lists repeat values; a draw without repeats then takes a
+                // shuffle of the whole list, as the local draw does
+                let values = ListSource.pool(config).unwrap_or_default();
+                let repeated = values.iter().collect::<HashSet<_>>().len() < values.len();
+                let n = if config.allow_duplicates || !repeated { count } else { values.len() };
+                let max = values.len() as i64 - 1;
+                Request { n, min: 0, max, replacement: config.allow_duplicates, pick: Pick::Index { values, count } }
+            }
+            mode => return Err((format!("random.org doesn't draw the {} mode", mode), false)),
+        };
+        if request.n > MAX_INTEGERS {
+            return Err((format!("random.org gives at most {} numbers per request", MAX_INTEGERS), true));
+        }
+        Ok(request)
+    }
+
+    fn body(&self, api_key: &str) -> Value {
+        json!({
+            "jsonrpc": "2.0",
+            "method": "generateIntegers",
+            "params": { "apiKey": api_key, "n": self.n, "min": self.min, "max": self.max, "replacement": self.replacement },
+            "id": 1,
+        })
+    }
+
+    fn results(&self, integers: Vec<i64>) -> Vec<i64> {
+        match &self.pick {
+            Pick::Offset(offset) => integers.into_iter().map(|value| value + offset).collect(),
+            Pick::Index { values, count } => {
+                let picked = integers.into_iter().filter_map(|index| usize::try_from(index).ok().and_then(|index| values.get(index)).copied());
+                if self.replacement {
+                    return picked.collect();
+                }
+                let mut seen = HashSet::new();
+                picked.filter(|value| seen.insert(*value)).take(*count).collect()
+            }
+        }
+    }
+
+    /// Ask random.org for the integers
+    fn fetch(&self, api_key: &str) -> Result<(Vec<i64>, Quota), Failure> {
+        let agent: ureq::Agent = ureq::Agent::config_builder().timeout_global(Some(TIMEOUT)).build().into();
+        let body = agent
+            .post(ENDPOINT)
+            .send_json(self.body(api_key))
+            .and_then(|mut response| response.body_mut().read_to_string())
+            .map_err(|e| match e {
+                ureq::Error::Io(_) | ureq::Error::Timeout(_) | ureq::Error::HostNotFound | ureq::Error::ConnectionFailed => {
+                    Failure::Unavailable(format!("offline ({})", e))
+                }
+                ureq::Error::StatusCode(status) if status >= 500 => Failure::Unavailable(format!("random.org is down ({})", status)),
+                e => Failure::Failed(e.to_string()),
+            })?;
+        let (integers, quota) = parse_response(&body)?;
+        if integers.len() != self.n {
+            return Err(Failure::Failed(format!("expected {} numbers, got {}", self.n, integers.len())));
+        }
+        Ok((integers, quota))
+    }
+}
+
+fn parse_response(body: &str) -> Result<(Vec<i64>, Quota), Failure> {
+    let response: Value = serde_json::from_str(body).map_err(|e| Failure::Failed(format!("unexpected answer: {}", e)))?;
+    let error = &response["error"];
+    if let Some(message) = error["message"].as_str() {
+        return Err(match error["code"].as_i64() {
+            Some(code) if QUOTA_ERRORS.contains(&code) => Failure::Unavailable(message.to_string()),
+            _ => Failure::Failed(message.to_string()),
+        });
+    }
+    let result = &response["result"];
+    let data = result["random"]["data"].as_array().ok_or(Failure::Failed("answer has no random data".to_string()))?;
+    let integers = data
+        .iter()
+        .map(|value| value.as_i64().ok_or(Failure::Failed("answer has a number out of range".to_string())))
+        .collect::<Result<_, _>>()?;
+    let quota = Quota {
+        requests_left: result["requestsLeft"].as_i64().unwrap_or_default(),
+        bits_left: result["bitsLeft"].as_i64().unwrap_or_default(),
+    };
+    Ok((integers, quota))
+}
+
+/// A mode's source that asks random.org first and draws with `local` when it can't
+struct RandomOrgSource<S> {
+    local: S,
+    api_key: String,
+    drawn: Arc<Mutex<Option<DrawSource>>>,
+}
+
+impl<S: GeneratorSource<i64>> GeneratorSource<i64> for RandomOrgSource<S> {
+    fn mode(&self) -> GeneratorMode {
+        self.local.mode()
+    }
+
+    fn input(&self) -> SourceInput {
+        self.local.input()
+    }
+
+    fn parameters(&self) -> Vec<(&'static str, String)> {
+        self.local.parameters()
+    }
+
+    fn validate(&self, config: &GeneratorConfig) -> Vec<RandomGeneratorError> {
+        self.local.validate(config)
+    }
+
+    fn available(&self, config: &GeneratorConfig) -> Option<u128> {
+        self.local.available(config)
+    }
+
+    fn pool(&self, config: &GeneratorConfig) -> Option<Vec<i64>> {
+        self.local.pool(config)
+    }
+
+    fn generate(
+        &self,
+        config: &GeneratorConfig,
+        rng: &mut dyn RngCore,
+        progress: &mut Progress<'_>,
+    ) -> Result<Vec<i64>, RandomGeneratorError> {
+        // Nothing is sent for a draw that was cancelled while it started
+        progress.checkpoint(0, config.num_to_generate)?;
+        let drawn = match Request::new(config) {
+            Ok(request) => match request.fetch(&self.api_key) {
+                Ok((integers, quota)) => {
+                    *self.drawn.lock().unwrap_or_else(|e| e.into_inner()) = Some(DrawSource::RandomOrg(quota));
+                    return Ok(request.results(integers));
+                }
+                Err(Failure::Unavailable(reason)) => DrawSource::Fallback(reason),
+                Err(Failure::Failed(message)) => return Err(RandomGeneratorError::Plugin(format!("random.org: {}", message))),
+            },
+            Err((reason, true)) => DrawSource::Fallback(reason),
+            Err((reason, false)) => DrawSource::Local(reason),
+        };
+        *self.drawn.lock().unwrap_or_else(|e| e.into_inner()) = Some(drawn);
+        self.local.generate(config, rng, progress)
+    }
+}
+
+/// Have `generator` ask random.org for range and custom list draws; the slot
+/// says who drew once a draw has run, and stays empty when the mode's source
+/// wasn't asked, as with a shuffle bag or a cooldown
+pub fn install(api_key: String, generator: &mut RandomGenerator) -> Arc<Mutex<Option<DrawSource>>> {
+    let drawn = Arc::new(Mutex::new(None));
+    generator.register_source(Box::new(RandomOrgSource { local: RangeSource, api_key: api_key.clone(), drawn: Arc::clone(&drawn) }));
+    generator.register_source(Box::new(RandomOrgSource { local: ListSource, api_key, drawn: Arc::clone(&drawn) }));
+    drawn
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_response() {
+        let body = r#"{"jsonrpc":"2.0","result":{"random":{"data":[7,-3,1000000000],
+            "completionTime":"2026-10-17 08:00:00Z"},"bitsUsed":90,"bitsLeft":249910,"requestsLeft":999,"advisoryDelay":0},"id":1}"#;
+        let (integers, quota) = parse_response(body).unwrap();
+        assert_eq!(integers, [7, -3, 1_000_000_000]);
+        assert_eq!(quota, Quota { requests_left: 999, bits_left: 249_910 });
+
+        let error = r#"{"jsonrpc":"2.0","error":{"code":400,"message":"The API key you specified does not exist","data":null},"id":1}"#;
+        assert_eq!(parse_response(error).unwrap_err(), Failure::Failed("The API key you specified does not exist".to_string()));
+        let quota = r#"{"jsonrpc":"2.0","error":{"code":403,"message":"The API key you specified has exceeded its daily bit allowance","data":null},"id":1}"#;
+        assert!(matches!(parse_response(quota), Err(Failure::Unavailable(_))));
+        assert!(matches!(parse_response("<html>"), Err(Failure::Failed(_))));
+    }
+
+    #[test]
+    fn test_range_request() {
+        let config = GeneratorConfig { lower_bound: 1, upper_bound: 49, num_to_generate: 6, ..Default::default() };
+        let request = Request::new(&config).unwrap();
+        assert_eq!(request, Request { n: 6, min: 1, max: 49, replacement: false, pick: Pick::Offset(0) });
+        let body = request.body("key");
+        assert_eq!((body["method"].as_str(), &body["params"]["max"], &body["params"]["replacement"]), (Some("generateIntegers"), &json!(49), &json!(false)));
+
+        // Far from zero but narrow: drawn from 0 and shifted back
+        let far = GeneratorConfig { lower_bound: 5_000_000_000, upper_bound: 5_000_000_100, allow_duplicates: true, ..config.clone() };
+        let request = Request::new(&far).unwrap();
+        assert_eq!((request.min, request.max, request.replacement), (0, 100, true));
+        assert_eq!(request.results(vec![0, 100]), [5_000_000_000, 5_000_000_100]);
+
+        let wide = GeneratorConfig { lower_bound: 0, upper_bound: 1 << 40, ..config.clone() };
+        assert!(matches!(Request::new(&wide), Err((_, true))));
+        let many = GeneratorConfig { upper_bound: 100_000, num_to_generate: 20_000, ..config.clone() };
+        assert!(matches!(Request::new(&many), Err((_, true))));
+        let limited = GeneratorConfig { allow_duplicates: true, max_repeats: Some(2), ..config.clone() };
+        assert!(matches!(Request::new(&limited), Err((_, false))));
+        let primes = GeneratorConfig { mode: GeneratorMode::PRIMES, ..config };
+        assert!(matches!(Request::new(&primes), Err((_, false))));
+    }
+
+    #[test]
+    fn test_list_request() {
+        let config = GeneratorConfig {
+            mode: GeneratorMode::CUSTOM_LIST,
+            custom_list: vec![10, 20, 30],
+            num_to_generate: 2,
+            ..Default::default()
+        };
+        let request = Request::new(&config).unwrap();
+        assert_eq!((request.n, request.min, request.max), (2, 0, 2));
+        assert_eq!(request.results(vec![2, 0]), [30, 10]);
+
+        // A weighted list without repeats takes a shuffle of every entry and keeps the first distinct values
+        let weighted = GeneratorConfig { custom_list: vec![10, 10, 10, 20, 30], ..config };
+        let request = Request::new(&weighted).unwrap();
+        assert_eq!((request.n, request.max, request.replacement), (5, 4, false));
+        assert_eq!(request.results(vec![1, 0, 2, 4, 3]), [10, 30]);
+    }
+
+    #[test]
+    fn test_draws_locally_what_random_org_cannot() {
+        let mut generator = RandomGenerator::new();
+        let drawn = install("key".to_string(), &mut generator);
+        let config = GeneratorConfig { num_to_generate: 50, allow_duplicates: true, max_repeats: Some(5), upper_bound: 10, ..Default::default() };
+        generator.set_config(config).unwrap();
+        generator.generate_numbers().unwrap();
+        assert_eq!(generator.get_numbers().len(), 50);
+        assert_eq!(*drawn.lock().unwrap(), Some(DrawSource::Local("random.org can't limit repeats".to_string())));
+    }
+}
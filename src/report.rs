@@ -0,0 +1,251 @@
+//! Session report.
+//!
+//! Every draw made since the window opened is kept in memory, closed tabs
+//! and the panels' draws included, and can be written out as one HTML or
+//! Markdown document with each draw's time, settings, seed, results and
+//! stats, to attach to the minutes of an official draw. A panel's draw, such
+//! as a Secret Santa or a bracket, is listed with its summary; its seed and
+//! settings draw it again. Times are local, as in the history. The format
+//! follows the file extension.
+
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+
+use random_generator_core::{GeneratorConfig, GeneratorSource, NumberFormat, Seed, Stats};
+
+use crate::{history, RandomGeneratorApp, Status};
+
+/// A draw made in this session
+#[derive(Debug, Clone)]
+pub struct SessionDraw {
+    /// Seconds since the Unix epoch
+    pub time: i64,
+    /// The mode, or the title of the panel that drew
+    pub mode: String,
+    /// Tab-separated `key=value` fields, as in the history
+    pub settings: String,
+    pub seed: Option<Seed>,
+    pub results: SessionResults,
+}
+
+/// What a session draw came up with
+#[derive(Debug, Clone)]
+pub enum SessionResults {
+    /// Shared with the history, which writes them on a worker thread
+    Numbers(Arc<[i64]>),
+    /// A panel's status line, e.g. `8 people drawn`
+    Summary(String),
+}
+
+impl SessionDraw {
+    /// A draw of numbers with `config` and `source`, made at `time`, as in [`history::settings`]
+    pub fn numbers(time: i64, config: &GeneratorConfig, source: Option<&dyn GeneratorSource<i64>>, numbers: Arc<[i64]>) -> Self {
+        SessionDraw {
+            time,
+            mode: config.mode.to_string(),
+            settings: history::settings(config, source),
+            seed: config.seed,
+            results: SessionResults::Numbers(numbers),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportFormat {
+    Html,
+    Markdown,
+}
+
+impl ReportFormat {
+    /// Markdown for `.md` and `.markdown` files, HTML otherwise
+    pub fn from_path(path: &str) -> Self {
+        match Path::new(path).extension().and_then(|extension| extension.to_str()) {
+            Some(extension) if extension.eq_ignore_ascii_case("md") || extension.eq_ignore_ascii_case("markdown") => {
+                ReportFormat::Markdown
+            }
+            _ => ReportFormat::Html,
+        }
+    }
+}
+
+/// One draw's part of the report, before it is put in either format
+struct Section {
+    heading: String,
+    /// Mode, settings and seed as label and value
+    facts: Vec<(String, String)>,
+    results: String,
+    /// `None` for a panel's draw, which has no numbers to sum up
+    stats: Option<String>,
+}
+
+/// The whole report; `started` and `made` are when the session began and the report was made
+pub fn render(draws: &[SessionDraw], started: i64, made: i64, format: ReportFormat, number_format: &NumberFormat) -> String {
+    let title = "Draw session report";
+    let summary = format!(
+        "Session started {}, report made {} (local time). {}.",
+        history::local_time(started),
+        history::local_time(made),
+        match draws.len() {
+            1 => "1 draw".to_string(),
+            count => format!("{} draws", count),
+        }
+    );
+    let sections: Vec<Section> = draws
+        .iter()
+        .enumerate()
+        .map(|(index, draw)| {
+            let mut facts = vec![("Mode".to_string(), draw.mode.clone())];
+            facts.extend(draw.settings.split('\t').filter_map(|field| {
+                field.split_once('=').map(|(key, value)| (key.to_string(), value.to_string()))
+            }));
+            facts.push(("Seed".to_string(), draw.seed.map_or("none".to_string(), |seed| seed.to_string())));
+            let (results, stats) = match &draw.results {
+                SessionResults::Numbers(numbers) => {
+                    let stats = Stats::of(numbers);
+                    let stats = match (stats.min, stats.max) {
+                        (Some(min), Some(max)) => format!(
+                            "Count {}, min {}, max {}, sum {}, mean {:.4}",
+                            stats.count,
+                            number_format.format(min),
+                            number_format.format(max),
+                            stats.sum.map_or("out of range".to_string(), |sum| number_format.format(sum)),
+                            stats.avg
+                        ),
+                        _ => "No numbers".to_string(),
+                    };
+                    (numbers.iter().map(|&num| number_format.format(num)).collect::<Vec<_>>().join(", "), Some(stats))
+                }
+                SessionResults::Summary(summary) => (summary.clone(), None),
+            };
+            Section {
+                heading: format!("Draw {} at {}", index + 1, history::local_time(draw.time)),
+                facts,
+                results,
+                stats,
+            }
+        })
+        .collect();
+
+    match format {
+        ReportFormat::Markdown => {
+            let mut out = format!("# {}\n\n{}\n", title, summary);
+            for section in sections {
+                out.push_str(&format!("\n## {}\n\n| Setting | Value |\n| --- | --- |\n", section.heading));
+                for (label, value) in &section.facts {
+                    out.push_str(&format!("| {} | {} |\n", label, value.replace('|', "\\|")));
+                }
+                out.push_str(&format!("\n**Results:** {}\n", section.results));
+                if let Some(stats) = &section.stats {
+                    out.push_str(&format!("\n{}\n", stats));
+                }
+            }
+            out
+        }
+        ReportFormat::Html => {
+            let mut out = format!(
+                "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{0}</title>\n\
+                 <style>body {{ font-family: sans-serif; }} table {{ border-collapse: collapse; }} \
+                 td, th {{ border: 1px solid #ccc; padding: 2px 8px; text-align: left; }}</style>\n\
+                 </head>\n<body>\n<h1>{0}</h1>\n<p>{1}</p>\n",
+                title,
+                escape_html(&summary)
+            );
+            for section in sections {
+                out.push_str(&format!("<h2>{}</h2>\n<table>\n<tr><th>Setting</th><th>Value</th></tr>\n", escape_html(&section.heading)));
+                for (label, value) in &section.facts {
+                    out.push_str(&format!("<tr><td>{}</td><td>{}</td></tr>\n", escape_html(label), escape_html(value)));
+                }
+                out.push_str(&format!("</table>\n<p><strong>Results:</strong> {}</p>\n", escape_html(&section.results)));
+                if let Some(stats) = &section.stats {
+                    out.push_str(&format!("<p>{}</p>\n", escape_html(stats)));
+                }
+            }
+            out.push_str("</body>\n</html>\n");
+            out
+        }
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+impl RandomGeneratorApp {
+    /// Write every draw of this session to `path`, as Markdown or HTML by its extension
+    pub(crate) fn save_report(&mut self, path: &str) {
+        let report = render(&self.session, self.session_started, history::now(), ReportFormat::from_path(path), &self.number_format);
+        self.status = Some(match fs::write(path, report) {
+            Ok(()) => Status::Info(format!("Saved session report to {}", path)),
+            Err(e) => Status::Error(format!("Save error: {}", e)),
+        });
+    }
+}
+
+/// Native dialog for where the session report goes, starting where results are saved
+pub async fn pick_report_file(filename: String) -> Option<String> {
+    let mut dialog = rfd::AsyncFileDialog::new()
+        .set_title("Export session report")
+        .set_file_name("session-report.html")
+        .add_filter("HTML", &["html"])
+        .add_filter("Markdown", &["md"]);
+    if let Some(directory) = Path::new(&filename).parent().filter(|directory| directory.is_dir()) {
+        dialog = dialog.set_directory(directory);
+    }
+    dialog.save_file().await.map(|file| file.path().to_string_lossy().into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use random_generator_core::{GeneratorMode, ScriptSource};
+
+    #[test]
+    fn test_session_report_lists_every_draw() {
+        assert_eq!(ReportFormat::from_path("minutes/draw.MD"), ReportFormat::Markdown);
+        assert_eq!(ReportFormat::from_path("draw.htm"), ReportFormat::Html);
+
+        let range = GeneratorConfig { lower_bound: 1, upper_bound: 6, num_to_generate: 2, seed: Some(Seed::from([0xab; 32])), ..Default::default() };
+        let script = GeneratorConfig { mode: GeneratorMode::SCRIPT, ..Default::default() };
+        let dice = ScriptSource { script: "roll(1, 6) < 3".to_string() };
+        let mut app = RandomGeneratorApp {
+            session_started: 1_700_000_000,
+            session: vec![
+                SessionDraw::numbers(1_700_000_060, &range, None, Arc::from([4, 2])),
+                SessionDraw::numbers(1_700_000_120, &script, Some(&dice), Arc::from([5])),
+                SessionDraw {
+                    time: 1_700_000_150,
+                    mode: "Group draw".to_string(),
+                    settings: "draw=Secret Santa\tnames=Alice; Bob; Carol".to_string(),
+                    seed: Some(Seed::from([0xcd; 32])),
+                    results: SessionResults::Summary("3 people drawn".to_string()),
+                },
+            ],
+            ..Default::default()
+        };
+
+        let markdown = render(&app.session, app.session_started, 1_700_000_180, ReportFormat::Markdown, &app.number_format);
+        // Times are local, like the history's
+        assert!(markdown.starts_with(&format!(
+            "# Draw session report\n\nSession started {}, report made {} (local time). 3 draws.\n",
+            history::local_time(1_700_000_000),
+            history::local_time(1_700_000_180)
+        )));
+        assert!(markdown.contains(&format!("## Draw 1 at {}", history::local_time(1_700_000_060))));
+        assert!(markdown.contains(&format!("| Seed | {} |", "ab".repeat(32))));
+        assert!(markdown.contains("**Results:** 4, 2\n\nCount 2, min 2, max 4, sum 6, mean 3.0000"));
+        assert!(markdown.contains(&format!("## Draw 2 at {}", history::local_time(1_700_000_120))));
+        assert!(markdown.contains("| Mode | Group draw |\n| draw | Secret Santa |"));
+        assert!(markdown.ends_with("**Results:** 3 people drawn\n"));
+
+        let path = std::env::temp_dir().join(format!("random-tool-report-{}.html", std::process::id()));
+        app.save_report(&path.to_string_lossy());
+        assert!(matches!(&app.status, Some(Status::Info(message)) if message.starts_with("Saved session report to ")));
+        let html = fs::read_to_string(&path).unwrap();
+        assert!(html.contains(&format!("<h2>Draw 2 at {}</h2>", history::local_time(1_700_000_120))));
+        assert!(html.contains("roll(1, 6) &lt; 3"));
+        assert!(html.contains("<p><strong>Results:</strong> 3 people drawn</p>\n</body>"));
+        assert!(html.ends_with("</html>\n"));
+        fs::remove_file(path).unwrap();
+    }
+}
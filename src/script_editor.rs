@@ -0,0 +1,59 @@
+use iced::widget::{column, container, row, text, text_editor, text_input};
+use iced::{Color, Element, Length, Theme};
+
+use crate::{field_hint, get_validated_input_style, Message, RandomGeneratorApp};
+
+/// Script a new window starts with
+pub const EXAMPLE: &str = "let dice = roll(2, 6);\ndice.reroll(1);\ndice.sum() + 3\n";
+
+impl RandomGeneratorApp {
+    /// Editor pane for the script mode, with the count below it
+    pub(crate) fn script_input(&self) -> Element<'_, Message> {
+        let field_errors = self.field_errors();
+        let script_valid = field_errors.script.is_none();
+        let count_valid = field_errors.count.is_none();
+        let dark_mode = self.settings.dark_mode;
+        let high_contrast = self.settings.high_contrast;
+
+        container(
+            column![
+                text(self.tr("Rhai script (the last value is the result):")).size(14),
+                text_editor(&self.script)
+                    .on_action(Message::ScriptEdited)
+                    .placeholder(self.tr("e.g. roll(2, 6).sum() + 3"))
+                    .height(Length::Fixed(110.0))
+                    .size(14)
+                    .style(move |theme: &Theme, status| {
+                        let mut style = text_editor::default(theme, status);
+                        if !script_valid {
+                            style.border.color = Color::from_rgb(0.9, 0.3, 0.3);
+                        }
+                        style
+                    }),
+                field_hint(field_errors.script.clone()),
+                text(self.tr("roll(count, sides) and random(low, high); dice have reroll, keep_highest/lowest, drop_highest/lowest and sum"))
+                    .size(11)
+                    .color(crate::accessibility::dim_text_color(dark_mode, high_contrast)),
+                row![
+                    column![
+                        text(self.tr("Count")).size(14),
+                        text_input("", &self.num_to_generate)
+                            .on_input(Message::NumToGenerateChanged)
+                            .on_submit(Message::Generate)
+                            .width(Length::Fixed(96.0))
+                            .size(14)
+                            .style(move |_theme: &Theme, status| {
+                                get_validated_input_style(dark_mode, high_contrast, count_valid, status)
+                            }),
+                        field_hint(field_errors.count.clone()),
+                    ]
+                    .width(Length::Fixed(96.0))
+                    .spacing(2),
+                ]
+            ]
+                .spacing(4)
+        )
+            .padding(4)
+            .into()
+    }
+}
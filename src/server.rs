@@ -0,0 +1,487 @@
+//! Server mode: `random-tool serve` answers draw requests over HTTP.
+//!
+//! `POST /generate` takes a JSON body such as `{"from": 1, "to": 100, "count": 5}`
+//! and answers `{"numbers": [...], "seed": "...", "stats": {...}}`; `GET /health` answers
+//! `{"status": "ok"}` and `GET /metrics` has Prometheus metrics (see
+//! [`metrics`](crate::metrics)). Every draw goes to the history with its seed, so one
+//! shared instance can serve a whole LAN and still be audited afterwards, and
+//! is streamed to WebSocket clients of `/live` (see [`live`](crate::live)).
+//! `proto/random_generator.proto` describes the same requests for other languages;
+//! `random-tool grpc` serves them over gRPC (see [`grpc`](crate::grpc)).
+
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use random_generator_core::{
+    Cooldown, ExclusionSet, GeneratorConfig, GeneratorMode, ListDuplicates, RandomGenerator, ResultOrder, Seed, ShuffleBag, Transform,
+};
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::history::{self, Retention};
+use crate::live::Live;
+use crate::metrics::Metrics;
+use crate::settings::Settings;
+
+/// Address used when `--addr` isn't given; only reachable from this machine
+pub const DEFAULT_ADDR: &str = "127.0.0.1:7878";
+
+/// Largest request body accepted
+const MAX_BODY: usize = 1 << 20;
+/// Longest request line and headers accepted, together
+const MAX_HEAD: usize = 16 << 10;
+/// Most headers accepted in one request
+const MAX_HEADERS: usize = 100;
+/// Most numbers one request may ask for, here and from the daemon
+///
+/// Lower than the core's [`MAX_COUNT`](random_generator_core::MAX_COUNT): the answer is built as
+/// one JSON document in memory, several times the size of the numbers, for every connection at once.
+pub(crate) const MAX_REQUEST_COUNT: usize = 1_000_000;
+/// Most connections served at once, WebSocket clients included; more are turned away with 503
+const MAX_CONNECTIONS: usize = 64;
+/// Connections that send nothing for this long are dropped
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Body of `POST /generate`, also the daemon's `generate` params and a saved preset; every field is optional
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub(crate) struct GenerateRequest {
+    pub(crate) from: i64,
+    pub(crate) to: i64,
+    pub(crate) count: usize,
+    pub(crate) duplicates: bool,
+    /// With `duplicates`, each number at most this many times
+    pub(crate) max_repeats: Option<usize>,
+    /// With `duplicates`, the same number at most this many times in a row
+    pub(crate) max_streak: Option<usize>,
+    /// Draw from these numbers instead of `from..=to`
+    pub(crate) list: Option<Vec<i64>>,
+    pub(crate) repeats: ListDuplicates,
+    pub(crate) order: ResultOrder,
+    /// Repeat an earlier draw
+    pub(crate) seed: Option<Seed>,
+    /// Steps applied to each number, e.g. `scale 10, offset 5`
+    pub(crate) transforms: Option<String>,
+    /// Draw from this shuffle bag; the answer has what's left in it
+    pub(crate) bag: Option<ShuffleBag<i64>>,
+    /// Skip values drawn recently; the answer has the values still cooling down
+    pub(crate) cooldown: Option<Cooldown<i64>>,
+    /// Never draw these values; the answer has them with the new results added
+    pub(crate) excluded: Option<ExclusionSet<i64>>,
+}
+
+impl Default for GenerateRequest {
+    fn default() -> Self {
+        let config = GeneratorConfig::default();
+        Self {
+            from: config.lower_bound,
+            to: config.upper_bound,
+            count: 1,
+            duplicates: false,
+            max_repeats: None,
+            max_streak: None,
+            list: None,
+            repeats: ListDuplicates::default(),
+            order: ResultOrder::default(),
+            seed: None,
+            transforms: None,
+            bag: None,
+            cooldown: None,
+            excluded: None,
+        }
+    }
+}
+
+struct Request {
+    method: String,
+    path: String,
+    headers: Vec<(String, String)>,
+    body: Vec<u8>,
+}
+
+impl Request {
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers.iter().find(|(key, _)| key.eq_ignore_ascii_case(name)).map(|(_, value)| value.as_str())
+    }
+}
+
+/// State shared by every connection
+#[derive(Debug, Clone)]
+pub(crate) struct Server {
+    live: Live,
+    metrics: Arc<Metrics>,
+    /// Keep draws in the history, pruned as the settings say; `None` to not keep them
+    log: Option<Retention>,
+}
+
+/// Serve requests until the process is stopped
+pub fn serve(addr: &str) -> io::Result<()> {
+    let listener = TcpListener::bind(addr)?;
+    eprintln!("listening on http://{}", listener.local_addr()?);
+    Server::new(true).run(listener);
+    Ok(())
+}
+
+/// Number of connections being served
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Connections(Arc<AtomicUsize>);
+
+/// One open connection, counted until it is dropped
+#[derive(Debug)]
+pub(crate) struct ConnectionSlot(Arc<AtomicUsize>);
+
+impl Connections {
+    /// Count one more connection; `None` when `max` are already open
+    pub(crate) fn open(&self, max: usize) -> Option<ConnectionSlot> {
+        self.0
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |open| (open < max).then_some(open + 1))
+            .ok()
+            .map(|_| ConnectionSlot(Arc::clone(&self.0)))
+    }
+}
+
+impl Drop for ConnectionSlot {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+impl Server {
+    pub(crate) fn new(log: bool) -> Self {
+        Server { live: Live::default(), metrics: Arc::default(), log: log.then(|| Settings::load().history_retention()) }
+    }
+
+    /// Answer the connections made to `listener`, each on its own thread
+    fn run(&self, listener: TcpListener) {
+        let connections = Connections::default();
+        for stream in listener.incoming() {
+            let Ok(stream) = stream else {
+                continue;
+            };
+            let Some(slot) = connections.open(MAX_CONNECTIONS) else {
+                // A short answer fits in the socket buffer, so this doesn't hold up the next accept
+                let _ = stream.set_write_timeout(Some(TIMEOUT));
+                let _ = write_response(stream, 503, &json!({ "error": "too many connections, try again later" }));
+                continue;
+            };
+            let server = self.clone();
+            thread::spawn(move || {
+                if let Err(e) = server.handle(stream, slot) {
+                    eprintln!("connection error: {}", e);
+                }
+            });
+        }
+    }
+
+    /// Answer one connection; `/live` clients keep `slot` for as long as they listen
+    fn handle(&self, stream: TcpStream, slot: ConnectionSlot) -> io::Result<()> {
+        stream.set_read_timeout(Some(TIMEOUT))?;
+        let mut reader = BufReader::new(stream.try_clone()?);
+        let (status, body) = match read_request(&mut reader) {
+            Ok(request) if request.path == "/live" && request.method == "GET" => {
+                let upgrade = request.header("upgrade").is_some_and(|value| value.eq_ignore_ascii_case("websocket"));
+                match request.header("sec-websocket-key") {
+                    Some(key) if upgrade => return self.live.accept(stream, slot, key),
+                    _ => (400, json!({ "error": "/live needs a WebSocket upgrade" })),
+                }
+            }
+            Ok(request) if request.path == "/metrics" && request.method == "GET" => {
+                return write_body(stream, 200, "text/plain; version=0.0.4", &self.metrics.render());
+            }
+            Ok(request) => self.respond(&request),
+            Err((status, message)) => {
+                self.metrics.record_error();
+                (status, json!({ "error": message }))
+            }
+        };
+        write_response(stream, status, &body)
+    }
+
+    pub(crate) fn metrics(&self) -> &Metrics {
+        &self.metrics
+    }
+
+    /// Status code and JSON body for a request
+    fn respond(&self, request: &Request) -> (u16, Value) {
+        match (request.method.as_str(), request.path.as_str()) {
+            ("GET", "/health") => (200, json!({ "status": "ok", "version": env!("CARGO_PKG_VERSION") })),
+            ("POST", "/generate") => match serde_json::from_slice::<GenerateRequest>(&request.body) {
+                Ok(draw) => match self.generate(draw) {
+                    Ok(body) => (200, body),
+                    Err(e) => (422, json!({ "error": e })),
+                },
+                Err(e) => {
+                    self.metrics.record_error();
+                    (400, json!({ "error": format!("invalid request: {}", e) }))
+                }
+            },
+            (_, "/health" | "/generate" | "/live" | "/metrics") => (405, json!({ "error": "method not allowed" })),
+            _ => (404, json!({ "error": "not found" })),
+        }
+    }
+
+    /// Draw for a request; the answer has the numbers, seed and stats
+    pub(crate) fn generate(&self, request: GenerateRequest) -> Result<Value, String> {
+        let started = Instant::now();
+        let result = self.draw(request);
+        match &result {
+            Ok(answer) => {
+                let values = answer["numbers"].as_array().map_or(0, Vec::len);
+                self.metrics.record_generation(values, started.elapsed());
+            }
+            Err(_) => self.metrics.record_error(),
+        }
+        result
+    }
+
+    fn draw(&self, request: GenerateRequest) -> Result<Value, String> {
+        if request.count > MAX_REQUEST_COUNT {
+            return Err(format!("at most {} numbers per request", MAX_REQUEST_COUNT));
+        }
+        let mut config = GeneratorConfig {
+            lower_bound: request.from,
+            upper_bound: request.to,
+            num_to_generate: request.count,
+            allow_duplicates: request.duplicates,
+            max_repeats: request.max_repeats,
+            max_streak: request.max_streak,
+            list_duplicates: request.repeats,
+            order: request.order,
+            seed: request.seed,
+            shuffle_bag: request.bag,
+            cooldown: request.cooldown,
+            exclude_drawn: request.excluded,
+            ..Default::default()
+        };
+        if let Some(list) = request.list {
+            config.mode = GeneratorMode::CUSTOM_LIST;
+            config.custom_list = list;
+        }
+        if let Some(transforms) = request.transforms {
+            config.transforms = Transform::parse_list(&transforms).map_err(|e| e.to_string())?;
+        }
+
+        let mut generator = RandomGenerator::with_config(config).map_err(|e| e.to_string())?;
+        if self.live.has_clients() {
+            let live = self.live.clone();
+            generator.on_start(move |config| live.broadcast(&json!({ "event": "start", "count": config.num_to_generate })));
+            let live = self.live.clone();
+            let mut index = 0;
+            generator.on_value(move |value| {
+                live.broadcast(&json!({ "event": "value", "index": index, "value": value }));
+                index += 1;
+            });
+        }
+        generator.generate_numbers().map_err(|e| e.to_string())?;
+
+        let seed = generator.last_seed().map(|seed| seed.to_string());
+        let numbers = generator.get_numbers();
+        self.live.broadcast(&json!({ "event": "complete", "numbers": numbers, "seed": seed }));
+        if let Some(keep) = self.log {
+            // Same as in the window: the history is a convenience and must not fail the draw
+            let config = GeneratorConfig { seed: generator.last_seed(), ..generator.get_config().clone() };
+            let _ = history::record(&config, generator.source(config.mode), numbers, keep);
+        }
+        let stats = generator.get_stats();
+        let mut answer = json!({
+            "numbers": numbers,
+            "seed": seed,
+            "stats": { "count": stats.count, "min": stats.min, "max": stats.max, "sum": stats.sum, "avg": stats.avg },
+        });
+        // The state after this draw, for the client to send with its next one
+        let config = generator.get_config();
+        if let Some(bag) = &config.shuffle_bag {
+            answer["bag"] = json!(bag);
+        }
+        if let Some(cooldown) = &config.cooldown {
+            answer["cooldown"] = json!(cooldown);
+        }
+        if let Some(excluded) = &config.exclude_drawn {
+            answer["excluded"] = json!(excluded);
+        }
+        Ok(answer)
+    }
+}
+
+/// The request, or the status and message to refuse it with
+fn read_request(reader: &mut impl BufRead) -> Result<Request, (u16, String)> {
+    let bad = |message: &str| (400, message.to_string());
+    let io_error = |e: io::Error| (400, e.to_string());
+    let too_large = || (431, "request headers too large".to_string());
+
+    // The head is read through one cap so an endless header line can't grow without bound
+    let mut head = io::Read::take(&mut *reader, MAX_HEAD as u64);
+    let mut read_line = |line: &mut String| {
+        line.clear();
+        let read = head.read_line(line).map_err(io_error)?;
+        if !line.ends_with('\n') && head.limit() == 0 {
+            return Err(too_large());
+        }
+        Ok(read)
+    };
+    let mut line = String::new();
+    read_line(&mut line)?;
+    let mut parts = line.split_whitespace();
+    let (Some(method), Some(target)) = (parts.next(), parts.next()) else {
+        return Err(bad("malformed request line"));
+    };
+    let method = method.to_string();
+    let path = target.split('?').next().unwrap_or_default().to_string();
+
+    let mut headers = Vec::new();
+    let mut length = 0;
+    for count in 0.. {
+        if read_line(&mut line)? == 0 {
+            return Err(bad("connection closed in the headers"));
+        }
+        let header = line.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if count == MAX_HEADERS {
+            return Err(too_large());
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("content-length") {
+                length = value.trim().parse().map_err(|_| bad("invalid Content-Length"))?;
+            }
+            headers.push((name.trim().to_string(), value.trim().to_string()));
+        }
+    }
+    if length > MAX_BODY {
+        return Err((413, "request body too large".to_string()));
+    }
+
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body).map_err(io_error)?;
+    Ok(Request { method, path, headers, body })
+}
+
+fn write_response(stream: TcpStream, status: u16, body: &Value) -> io::Result<()> {
+    write_body(stream, status, "application/json", &body.to_string())
+}
+
+fn write_body(mut stream: TcpStream, status: u16, content_type: &str, body: &str) -> io::Result<()> {
+    let reason = match status {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        413 => "Payload Too Large",
+        431 => "Request Header Fields Too Large",
+        503 => "Service Unavailable",
+        _ => "Unprocessable Entity",
+    };
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        reason,
+        content_type,
+        body.len(),
+        body
+    )?;
+    stream.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(raw: &str) -> Request {
+        read_request(&mut raw.as_bytes()).unwrap()
+    }
+
+    fn respond_quietly(request: &Request) -> (u16, Value) {
+        Server::new(false).respond(request)
+    }
+
+    #[test]
+    fn test_read_request() {
+        let request = request("POST /generate?x=1 HTTP/1.1\r\nHost: a\r\ncontent-length: 2\r\n\r\n{}");
+        assert_eq!((request.method.as_str(), request.path.as_str()), ("POST", "/generate"));
+        assert_eq!(request.body, b"{}");
+        assert_eq!(request.header("Content-Length"), Some("2"));
+        let too_large = format!("POST /generate HTTP/1.1\r\nContent-Length: {}\r\n\r\n", MAX_BODY + 1);
+        assert_eq!(read_request(&mut too_large.as_bytes()).err().map(|(status, _)| status), Some(413));
+
+        let long_header = format!("GET /health HTTP/1.1\r\nX-Padding: {}\r\n\r\n", "a".repeat(MAX_HEAD));
+        assert_eq!(read_request(&mut long_header.as_bytes()).err().map(|(status, _)| status), Some(431));
+        let many_headers = format!("GET /health HTTP/1.1\r\n{}\r\n", "X-A: 1\r\n".repeat(MAX_HEADERS + 1));
+        assert_eq!(read_request(&mut many_headers.as_bytes()).err().map(|(status, _)| status), Some(431));
+        let enough_headers = format!("GET /health HTTP/1.1\r\n{}\r\n", "X-A: 1\r\n".repeat(MAX_HEADERS));
+        assert_eq!(read_request(&mut enough_headers.as_bytes()).map(|request| request.headers.len()), Ok(MAX_HEADERS));
+    }
+
+    #[test]
+    fn test_responses() {
+        assert_eq!(respond_quietly(&request("GET /health HTTP/1.1\r\n\r\n")).0, 200);
+        assert_eq!(respond_quietly(&request("GET /generate HTTP/1.1\r\n\r\n")).0, 405);
+        assert_eq!(respond_quietly(&request("GET /nothing HTTP/1.1\r\n\r\n")).0, 404);
+
+        let body = r#"{"from": 1, "to": 10, "count": 10, "order": "Sorted"}"#;
+        let raw = format!("POST /generate HTTP/1.1\r\nContent-Length: {}\r\n\r\n{}", body.len(), body);
+        let (status, answer) = respond_quietly(&request(&raw));
+        assert_eq!(status, 200);
+        assert_eq!(answer["numbers"], json!([1, 2, 3, 4, 5, 6, 7, 8, 9, 10]));
+        assert_eq!(answer["seed"].as_str().map(str::len), Some(64));
+        assert_eq!(answer["stats"]["sum"], 55);
+
+        let body = r#"{"from": 1, "to": 3, "count": 3, "order": "Sorted", "transforms": "scale 10, offset -1"}"#;
+        let raw = format!("POST /generate HTTP/1.1\r\nContent-Length: {}\r\n\r\n{}", body.len(), body);
+        assert_eq!(respond_quietly(&request(&raw)).1["numbers"], json!([9, 19, 29]));
+
+        let invalid = [r#"{"from": 5, "to": 1}"#, r#"{"count": 10000000}"#, r#"{"list": []}"#, r#"{"transforms": "mod 0"}"#];
+        for body in invalid {
+            let raw = format!("POST /generate HTTP/1.1\r\nContent-Length: {}\r\n\r\n{}", body.len(), body);
+            assert_eq!(respond_quietly(&request(&raw)).0, 422, "{}", body);
+        }
+        let raw = "POST /generate HTTP/1.1\r\nContent-Length: 9\r\n\r\n{\"cont\":1";
+        assert_eq!(respond_quietly(&request(raw)).0, 400);
+    }
+
+    #[test]
+    fn test_metrics_count_draws_and_errors() {
+        let server = Server::new(false);
+        for body in [r#"{"count": 4}"#, r#"{"count": 2}"#, r#"{"from": 5, "to": 1}"#, "{"] {
+            let raw = format!("POST /generate HTTP/1.1\r\nContent-Length: {}\r\n\r\n{}", body.len(), body);
+            server.respond(&request(&raw));
+        }
+        let text = server.metrics().render();
+        assert!(text.contains("\nrandom_tool_generations_total 2\n"));
+        assert!(text.contains("\nrandom_tool_values_total 6\n"));
+        assert!(text.contains("\nrandom_tool_errors_total 2\n"));
+        assert!(text.contains("\nrandom_tool_generation_seconds_count 2\n"));
+        assert_eq!(server.respond(&request("POST /metrics HTTP/1.1\r\n\r\n")).0, 405);
+    }
+
+    #[test]
+    fn test_connections_are_capped() {
+        let connections = Connections::default();
+        let mut slots: Vec<ConnectionSlot> = (0..3).map(|_| connections.open(3).unwrap()).collect();
+        assert!(connections.open(3).is_none());
+        slots.pop();
+        assert!(connections.open(3).is_some());
+    }
+
+    #[test]
+    fn test_live_clients_count_as_connections() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        thread::spawn(move || Server::new(false).run(listener));
+        let upgrade = |expected: &str| {
+            let mut stream = TcpStream::connect(addr).unwrap();
+            write!(stream, "GET /live HTTP/1.1\r\nUpgrade: websocket\r\nSec-WebSocket-Key: dGhlIHNhbXBsZSBub25jZQ==\r\n\r\n").unwrap();
+            let mut status = String::new();
+            BufReader::new(stream.try_clone().unwrap()).read_line(&mut status).unwrap();
+            assert!(status.starts_with(expected), "{}", status);
+            stream
+        };
+        let _clients: Vec<TcpStream> = (0..MAX_CONNECTIONS).map(|_| upgrade("HTTP/1.1 101")).collect();
+        upgrade("HTTP/1.1 503");
+    }
+}
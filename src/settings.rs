@@ -0,0 +1,469 @@
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use iced::widget::{button, checkbox, column, container, pick_list, row, text, text_input, Space};
+use iced::{alignment, Background, Border, Color, Element, Length, Shadow, Theme, Vector};
+use serde::{Deserialize, Serialize};
+
+use crate::accessibility;
+use crate::history::Retention;
+use crate::i18n::Language;
+use random_generator_core::{ExportFormat, GroupHeader, ListDuplicates, ResultOrder, RngBackend};
+use crate::locked_draw::MAX_COUNTDOWN;
+use crate::{field_hint, get_checkbox_style, get_pick_list_style, get_text_input_style, get_validated_input_style, mqtt, sound, Message, RandomGeneratorApp, Status};
+
+/// Preferences kept between runs.
+///
+/// Unknown or missing fields fall back to their defaults, so older files keep loading.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub dark_mode: bool,
+    pub language: Language,
+    pub rng_backend: RngBackend,
+    /// Keep results in the order they were drawn, or sort them
+    pub result_order: ResultOrder,
+    /// What repeated numbers in a custom list mean
+    pub list_duplicates: ListDuplicates,
+    /// Refuse a custom list with items that aren't numbers instead of skipping them
+    pub strict_list: bool,
+    /// Have random.org draw range and custom list draws when a key is set
+    pub use_random_org: bool,
+    pub random_org_key: String,
+    /// Send draws to `random-tool daemon` instead of drawing in the window
+    pub use_daemon: bool,
+    /// `[user:password@]host[:port]` to publish finished draws to; empty to not publish
+    pub mqtt_broker: String,
+    pub mqtt_topic: String,
+    /// `host:port` to send each drawn number to over OSC; empty to not send
+    pub osc_target: String,
+    pub osc_address: String,
+    /// Filename a new window starts with
+    pub default_filename: String,
+    pub export_format: ExportFormat,
+    /// Export format added by a plugin; used instead of `export_format` while that plugin is installed
+    pub export_plugin: Option<String>,
+    /// Header template for each tab when saving them all into one file, e.g. `Round {n}`
+    pub group_header: String,
+    /// Seconds to count down with the inputs locked before each draw; empty for no countdown
+    pub draw_countdown: String,
+    /// Days of draws the history keeps; empty to keep them however old
+    pub history_days: String,
+    /// Latest draws the history keeps; empty to keep them all
+    pub history_draws: String,
+    pub animate_reveal: bool,
+    pub sequential_reveal: bool,
+    pub confetti: bool,
+    /// Spin a wheel of the list's entries when presenting a custom list draw
+    pub wheel: bool,
+    pub sound: bool,
+    pub notify: bool,
+    /// Save every finished generation to the current file
+    pub autosave: bool,
+    /// Stronger colors, focus outlines and bigger click targets
+    pub high_contrast: bool,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            dark_mode: false,
+            language: Language::default(),
+            rng_backend: RngBackend::default(),
+            result_order: ResultOrder::default(),
+            list_duplicates: ListDuplicates::default(),
+            strict_list: false,
+            use_random_org: false,
+            random_org_key: String::new(),
+            use_daemon: false,
+            mqtt_broker: String::new(),
+            mqtt_topic: "random-tool/draws".to_string(),
+            osc_target: String::new(),
+            osc_address: "/random-tool/value".to_string(),
+            default_filename: "numbers.txt".to_string(),
+            export_format: ExportFormat::default(),
+            export_plugin: None,
+            group_header: GroupHeader::default().to_string(),
+            draw_countdown: String::new(),
+            history_days: String::new(),
+            history_draws: String::new(),
+            animate_reveal: false,
+            sequential_reveal: false,
+            confetti: true,
+            wheel: false,
+            sound: true,
+            notify: true,
+            autosave: false,
+            high_contrast: false,
+        }
+    }
+}
+
+/// Entry in the export format list: a built-in format or one added by a plugin
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SaveFormat {
+    Builtin(ExportFormat),
+    Plugin(String),
+}
+
+impl fmt::Display for SaveFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SaveFormat::Builtin(format) => write!(f, "{}", format),
+            SaveFormat::Plugin(name) => write!(f, "{}", name),
+        }
+    }
+}
+
+impl Settings {
+    /// Settings from the last run, or the defaults if there are none yet
+    pub fn load() -> Self {
+        Self::path()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let path = Self::path()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no configuration directory"))?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let content = serde_json::to_string_pretty(self).map_err(io::Error::other)?;
+        fs::write(path, content)
+    }
+
+    /// Countdown before each draw, `None` when it's off or not a number of seconds the dialog accepts
+    pub fn draw_countdown(&self) -> Option<Duration> {
+        self.draw_countdown
+            .trim()
+            .parse()
+            .ok()
+            .filter(|seconds| (1..=MAX_COUNTDOWN).contains(seconds))
+            .map(Duration::from_secs)
+    }
+
+    /// How much the history keeps; a field that isn't a whole number keeps everything by it
+    pub fn history_retention(&self) -> Retention {
+        Retention {
+            days: self.history_days.trim().parse().ok(),
+            draws: self.history_draws.trim().parse().ok(),
+        }
+    }
+
+    fn path() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join("random-tool").join("settings.json"))
+    }
+}
+
+impl RandomGeneratorApp {
+    /// Format results are saved in; a plugin format that is no longer installed falls back to the built-in one
+    pub(crate) fn save_format(&self) -> SaveFormat {
+        match &self.settings.export_plugin {
+            Some(name) if self.generator.exporters().any(|exporter| exporter.name() == name) => {
+                SaveFormat::Plugin(name.clone())
+            }
+            _ => SaveFormat::Builtin(self.settings.export_format),
+        }
+    }
+
+    /// Built-in formats followed by those added by plugins
+    fn save_formats(&self) -> Vec<SaveFormat> {
+        ExportFormat::ALL
+            .into_iter()
+            .map(SaveFormat::Builtin)
+            .chain(self.generator.exporters().map(|exporter| SaveFormat::Plugin(exporter.name().to_string())))
+            .collect()
+    }
+
+    /// Persist the settings after a change made in the dialog
+    pub(crate) fn store_settings(&mut self) {
+        if let Err(e) = self.settings.save() {
+            self.status = Some(Status::Error(format!("Could not save settings: {}", e)));
+        }
+    }
+
+    /// Dialog card listing every preference, shown over the dimmed main view
+    pub(crate) fn settings_view(&self) -> Element<'_, Message> {
+        let dark_mode = self.settings.dark_mode;
+        let high_contrast = self.settings.high_contrast;
+        let broker_error = mqtt::Broker::parse(&self.settings.mqtt_broker).err();
+        let broker_valid = broker_error.is_none();
+        let label = |content: &'static str| text(self.tr(content)).size(14).width(Length::Fixed(110.0));
+        let toggle = |content: &'static str, value: bool, on_toggle: fn(bool) -> Message| {
+            checkbox(self.tr(content), value)
+                .on_toggle(on_toggle)
+                .size(self.checkbox_size())
+                .text_size(14)
+                .style(move |_theme: &Theme, _status| get_checkbox_style(dark_mode, high_contrast))
+        };
+
+        let content = column![
+            text(self.tr("Settings"))
+                .size(20)
+                .color(if dark_mode { Color::from_rgb(0.9, 0.9, 0.9) } else { Color::BLACK }),
+            Space::with_height(Length::Fixed(10.0)),
+            row![
+                label("Language"),
+                pick_list(&Language::ALL[..], Some(self.settings.language), Message::LanguageChanged)
+                    .text_size(14)
+                    .style(move |_theme: &Theme, _status| get_pick_list_style(dark_mode, high_contrast)),
+            ]
+                .align_y(alignment::Vertical::Center),
+            row![
+                label("Random source"),
+                pick_list(&RngBackend::ALL[..], Some(self.settings.rng_backend), Message::RngBackendChanged)
+                    .text_size(14)
+                    .style(move |_theme: &Theme, _status| get_pick_list_style(dark_mode, high_contrast)),
+            ]
+                .align_y(alignment::Vertical::Center),
+            row![
+                label("Result order"),
+                pick_list(&ResultOrder::ALL[..], Some(self.settings.result_order), Message::ResultOrderChanged)
+                    .text_size(14)
+                    .style(move |_theme: &Theme, _status| get_pick_list_style(dark_mode, high_contrast)),
+            ]
+                .align_y(alignment::Vertical::Center),
+            row![
+                label("Repeated entries"),
+                pick_list(&ListDuplicates::ALL[..], Some(self.settings.list_duplicates), Message::ListDuplicatesChanged)
+                    .text_size(14)
+                    .style(move |_theme: &Theme, _status| get_pick_list_style(dark_mode, high_contrast)),
+            ]
+                .align_y(alignment::Vertical::Center),
+            toggle("Stop on list items that aren't numbers", self.settings.strict_list, Message::StrictListToggled),
+            row![
+                label("random.org key"),
+                text_input("", &self.settings.random_org_key)
+                    .on_input(Message::RandomOrgKeyChanged)
+                    .secure(true)
+                    .width(Length::Fill)
+                    .size(14)
+                    .style(move |_theme: &Theme, status| get_text_input_style(dark_mode, high_contrast, status)),
+            ]
+                .align_y(alignment::Vertical::Center),
+            row![
+                toggle("random.org draws", self.settings.use_random_org, Message::RandomOrgToggled),
+                Space::with_width(Length::Fill),
+                // Known once a draw has used the key
+                text(self.random_org_quota.map_or(String::new(), |quota| {
+                    format!("{} {} / {} bits", self.tr("Quota left:"), quota.requests_left, quota.bits_left)
+                }))
+                    .size(12)
+                    .color(accessibility::dim_text_color(dark_mode, high_contrast)),
+            ]
+                .align_y(alignment::Vertical::Center),
+            text(self.tr("Range and custom list draws come from random.org; other modes, and draws while it's offline or out of quota, are drawn locally. random.org draws can't be replayed"))
+                .size(11)
+                .color(accessibility::dim_text_color(dark_mode, high_contrast)),
+            toggle("Draw with the daemon", self.settings.use_daemon, Message::DaemonToggled),
+            row![
+                label("MQTT broker"),
+                text_input("host:1883", &self.settings.mqtt_broker)
+                    .on_input(Message::MqttBrokerChanged)
+                    .width(Length::Fill)
+                    .size(14)
+                    .style(move |_theme: &Theme, status| get_validated_input_style(dark_mode, high_contrast, broker_valid, status)),
+            ]
+                .align_y(alignment::Vertical::Center),
+            field_hint(broker_error),
+            row![
+                label("MQTT topic"),
+                text_input("", &self.settings.mqtt_topic)
+                    .on_input(Message::MqttTopicChanged)
+                    .width(Length::Fill)
+                    .size(14)
+                    .style(move |_theme: &Theme, status| get_text_input_style(dark_mode, high_contrast, status)),
+            ]
+                .align_y(alignment::Vertical::Center),
+            row![
+                label("OSC target"),
+                text_input("host:53000", &self.settings.osc_target)
+                    .on_input(Message::OscTargetChanged)
+                    .width(Length::Fill)
+                    .size(14)
+                    .style(move |_theme: &Theme, status| get_text_input_style(dark_mode, high_contrast, status)),
+            ]
+                .align_y(alignment::Vertical::Center),
+            row![
+                label("OSC address"),
+                text_input("", &self.settings.osc_address)
+                    .on_input(Message::OscAddressChanged)
+                    .width(Length::Fill)
+                    .size(14)
+                    .style(move |_theme: &Theme, status| get_text_input_style(dark_mode, high_contrast, status)),
+            ]
+                .align_y(alignment::Vertical::Center),
+            row![
+                label("Default file"),
+                text_input("", &self.settings.default_filename)
+                    .on_input(Message::DefaultFilenameChanged)
+                    .width(Length::Fill)
+                    .size(14)
+                    .style(move |_theme: &Theme, status| get_text_input_style(dark_mode, high_contrast, status)),
+            ]
+                .align_y(alignment::Vertical::Center),
+            row![
+                label("Export format"),
+                pick_list(self.save_formats(), Some(self.save_format()), Message::ExportFormatChanged)
+                    .text_size(14)
+                    .style(move |_theme: &Theme, _status| get_pick_list_style(dark_mode, high_contrast)),
+            ]
+                .align_y(alignment::Vertical::Center),
+            row![
+                label("Group header"),
+                text_input("Round {n}", &self.settings.group_header)
+                    .on_input(Message::GroupHeaderChanged)
+                    .width(Length::Fill)
+                    .size(14)
+                    .style(move |_theme: &Theme, status| {
+                        get_validated_input_style(dark_mode, high_contrast, self.settings.group_header.parse::<GroupHeader>().is_ok(), status)
+                    }),
+            ]
+                .align_y(alignment::Vertical::Center),
+            row![
+                label("Countdown (s)"),
+                // Locks the inputs and counts down in full view before each draw
+                text_input(self.tr("off"), &self.settings.draw_countdown)
+                    .on_input(Message::DrawCountdownChanged)
+                    .width(Length::Fixed(96.0))
+                    .size(14)
+                    .style(move |_theme: &Theme, status| {
+                        let valid = self.settings.draw_countdown.trim().is_empty() || self.settings.draw_countdown().is_some();
+                        get_validated_input_style(dark_mode, high_contrast, valid, status)
+                    }),
+            ]
+                .align_y(alignment::Vertical::Center),
+            row![
+                label("Keep history"),
+                // Older draws are pruned from the history after each new one
+                text_input(self.tr("all"), &self.settings.history_days)
+                    .on_input(Message::HistoryDaysChanged)
+                    .width(Length::Fixed(96.0))
+                    .size(14)
+                    .style(move |_theme: &Theme, status| {
+                        let valid = self.settings.history_days.trim().is_empty() || self.settings.history_retention().days.is_some();
+                        get_validated_input_style(dark_mode, high_contrast, valid, status)
+                    }),
+                text(self.tr("days")).size(14),
+                text_input(self.tr("all"), &self.settings.history_draws)
+                    .on_input(Message::HistoryDrawsChanged)
+                    .width(Length::Fixed(96.0))
+                    .size(14)
+                    .style(move |_theme: &Theme, status| {
+                        let valid = self.settings.history_draws.trim().is_empty() || self.settings.history_retention().draws.is_some();
+                        get_validated_input_style(dark_mode, high_contrast, valid, status)
+                    }),
+                text(self.tr("draws")).size(14),
+            ]
+                .spacing(6)
+                .align_y(alignment::Vertical::Center),
+            Space::with_height(Length::Fixed(6.0)),
+            toggle("Dark theme", dark_mode, |_| Message::ToggleTheme),
+            toggle("High contrast", high_contrast, Message::HighContrastToggled),
+            toggle("Autosave results", self.settings.autosave, Message::AutosaveToggled),
+            toggle("Animate reveal", self.settings.animate_reveal, Message::AnimateRevealToggled),
+            toggle("One by one", self.settings.sequential_reveal, Message::SequentialRevealToggled),
+            toggle("Confetti", self.settings.confetti, Message::ConfettiToggled),
+            toggle("Spin a wheel for lists", self.settings.wheel, Message::WheelToggled),
+            toggle("Notify when done", self.settings.notify, Message::NotifyToggled),
+        ]
+            .push_maybe(sound::AVAILABLE.then(|| toggle("Sound", self.settings.sound, Message::SoundToggled)))
+            .push(Space::with_height(Length::Fixed(14.0)))
+            .push(
+                container(
+                    button(text(self.tr("Close")).size(14))
+                        .on_press(Message::CloseSettings)
+                        .width(Length::Fixed(80.0))
+                        .padding(self.button_padding())
+                        .style(move |_theme: &Theme, status| {
+                            if high_contrast {
+                                return accessibility::high_contrast_button(dark_mode, status);
+                            }
+                            let is_pressed = status == button::Status::Pressed;
+                            button::Style {
+                                background: Some(Background::Color(
+                                    if is_pressed {
+                                        if dark_mode {
+                                            Color::from_rgb(0.2, 0.2, 0.25)
+                                        } else {
+                                            Color::from_rgb(0.1, 0.5, 0.8)
+                                        }
+                                    } else if dark_mode {
+                                        Color::from_rgb(0.3, 0.3, 0.35)
+                                    } else {
+                                        Color::from_rgb(0.2, 0.6, 0.9)
+                                    }
+                                )),
+                                border: Border {
+                                    color: Color::TRANSPARENT,
+                                    width: 0.0,
+                                    radius: 8.0.into(),
+                                },
+                                text_color: Color::WHITE,
+                                shadow: Shadow {
+                                    color: Color::from_rgba(0.0, 0.0, 0.0, 0.15),
+                                    offset: Vector::new(0.0, if is_pressed { 1.0 } else { 2.0 }),
+                                    blur_radius: if is_pressed { 2.0 } else { 4.0 },
+                                },
+                            }
+                        })
+                )
+                    .center_x(Length::Fill)
+            )
+            .spacing(6)
+            .padding(24);
+
+        container(content)
+            .width(Length::Fixed(340.0))
+            .style(move |_theme: &Theme| {
+                iced::widget::container::Style {
+                    background: Some(Background::Color(
+                        if dark_mode {
+                            Color::from_rgb(0.2, 0.2, 0.25)
+                        } else {
+                            Color::WHITE
+                        }
+                    )),
+                    border: Border {
+                        color: if dark_mode {
+                            Color::from_rgb(0.4, 0.4, 0.4)
+                        } else {
+                            Color::from_rgb(0.8, 0.8, 0.8)
+                        },
+                        width: 1.0,
+                        radius: 16.0.into(),
+                    },
+                    shadow: Shadow {
+                        color: Color::from_rgba(0.0, 0.0, 0.0, 0.3),
+                        offset: Vector::new(0.0, 4.0),
+                        blur_radius: 20.0,
+                    },
+                    ..Default::default()
+                }
+            })
+            .into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_fields_use_defaults() {
+        let settings: Settings = serde_json::from_str(r#"{ "dark_mode": true, "export_format": "Csv" }"#).unwrap();
+        assert_eq!(
+            settings,
+            Settings {
+                dark_mode: true,
+                export_format: ExportFormat::Csv,
+                ..Default::default()
+            }
+        );
+    }
+}
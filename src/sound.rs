@@ -0,0 +1,91 @@
+//! Optional audio feedback for draws.
+//!
+//! Audio output lives on its own thread so the UI never waits on the device.
+//! Without the `sound` feature every call is a no-op.
+
+/// Short sound effects played during a draw
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Effect {
+    /// Reel click while the reveal animation spins
+    Tick,
+    /// Result has been revealed
+    Chime,
+}
+
+/// Whether this build can play audio at all
+pub const AVAILABLE: bool = cfg!(feature = "sound");
+
+#[cfg(feature = "sound")]
+pub struct SoundPlayer {
+    sender: Option<std::sync::mpsc::Sender<Effect>>,
+}
+
+#[cfg(feature = "sound")]
+impl SoundPlayer {
+    pub fn new() -> Self {
+        use rodio::source::{SineWave, Source};
+        use rodio::{OutputStream, Sink};
+        use std::time::Duration;
+
+        let (sender, receiver) = std::sync::mpsc::channel::<Effect>();
+        let spawned = std::thread::Builder::new()
+            .name("sound".to_string())
+            .spawn(move || {
+                // The stream must stay alive for as long as anything plays
+                let Ok((_stream, handle)) = OutputStream::try_default() else {
+                    return;
+                };
+                for effect in receiver {
+                    let Ok(sink) = Sink::try_new(&handle) else {
+                        continue;
+                    };
+                    match effect {
+                        Effect::Tick => {
+                            sink.append(
+                                SineWave::new(1800.0)
+                                    .take_duration(Duration::from_millis(12))
+                                    .amplify(0.15),
+                            );
+                        }
+                        Effect::Chime => {
+                            for (freq, millis) in [(880.0, 120), (1318.5, 260)] {
+                                sink.append(
+                                    SineWave::new(freq)
+                                        .take_duration(Duration::from_millis(millis))
+                                        .fade_in(Duration::from_millis(5))
+                                        .amplify(0.2),
+                                );
+                            }
+                        }
+                    }
+                    sink.detach();
+                }
+            });
+
+        Self { sender: spawned.ok().map(|_| sender) }
+    }
+
+    pub fn play(&self, effect: Effect) {
+        if let Some(sender) = &self.sender {
+            let _ = sender.send(effect);
+        }
+    }
+}
+
+#[cfg(not(feature = "sound"))]
+pub struct SoundPlayer;
+
+#[cfg(not(feature = "sound"))]
+impl SoundPlayer {
+    pub fn new() -> Self {
+        Self
+    }
+
+    pub fn play(&self, _effect: Effect) {}
+}
+
+impl Default for SoundPlayer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
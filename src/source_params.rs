@@ -0,0 +1,76 @@
+//! Inputs of the modes whose parameters live in their source.
+//!
+//! The dice script, the strata, the partition total and the chance of
+//! success aren't part of the generator config; each mode's source holds its
+//! own. `SourceParams` keeps what was typed for them, so a draw can be
+//! checked before it runs, run on a worker thread and replayed later with
+//! the same source.
+
+use random_generator_core::{
+    GeneratorMode, GeneratorSource, GeometricSource, NegativeBinomialSource, PartitionSource, RandomGenerator, ScriptSource, StratifiedSource,
+};
+
+/// Typed inputs of the script, stratified, partition, geometric and negative-binomial modes
+#[derive(Debug, Clone, PartialEq)]
+pub struct SourceParams {
+    pub script: String,
+    pub strata: String,
+    pub partition_total: i64,
+    pub success_chance: f64,
+    pub successes: u64,
+}
+
+impl Default for SourceParams {
+    fn default() -> Self {
+        let trials = NegativeBinomialSource::default();
+        Self {
+            script: String::new(),
+            strata: String::new(),
+            partition_total: PartitionSource::<i64>::default().total,
+            success_chance: trials.chance,
+            successes: trials.successes,
+        }
+    }
+}
+
+impl SourceParams {
+    /// The source `mode` draws from with these inputs, `None` for modes that take everything from the config
+    pub fn source(&self, mode: GeneratorMode) -> Option<Box<dyn GeneratorSource<i64>>> {
+        let source: Box<dyn GeneratorSource<i64>> = match mode {
+            GeneratorMode::SCRIPT => Box::new(ScriptSource { script: self.script.clone() }),
+            GeneratorMode::STRATIFIED => Box::new(StratifiedSource { strata: self.strata.clone() }),
+            GeneratorMode::PARTITION => Box::new(PartitionSource { total: self.partition_total }),
+            GeneratorMode::GEOMETRIC => Box::new(GeometricSource { chance: self.success_chance }),
+            GeneratorMode::NEGATIVE_BINOMIAL => Box::new(NegativeBinomialSource { chance: self.success_chance, successes: self.successes }),
+            _ => return None,
+        };
+        Some(source)
+    }
+
+    /// Register the source for `mode` with these inputs, replacing the one `generator` had
+    pub fn install(&self, mode: GeneratorMode, generator: &mut RandomGenerator) {
+        if let Some(source) = self.source(mode) {
+            generator.register_source(source);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use random_generator_core::GeneratorConfig;
+
+    #[test]
+    fn test_install_replaces_the_source() {
+        let params = SourceParams { script: "roll(1, 4).sum()\n+ 10".to_string(), ..Default::default() };
+        let config = GeneratorConfig { mode: GeneratorMode::SCRIPT, num_to_generate: 20, ..Default::default() };
+        let mut generator = RandomGenerator::new();
+        assert!(!generator.validate(&config).is_empty());
+
+        params.install(GeneratorMode::SCRIPT, &mut generator);
+        generator.set_config(config).unwrap();
+        generator.generate_numbers().unwrap();
+        assert!(generator.get_numbers().iter().all(|n| (11..=14).contains(n)));
+        assert!(params.source(GeneratorMode::RANGE).is_none());
+    }
+}
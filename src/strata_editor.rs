@@ -0,0 +1,45 @@
+use iced::widget::{column, container, text, text_editor};
+use iced::{Color, Element, Length, Theme};
+use random_generator_core::Strata;
+
+use crate::{field_hint, i18n, Message, RandomGeneratorApp};
+
+/// Strata a new window starts with
+pub const EXAMPLE: &str = "1..50: 2\n51..100: 3\n";
+
+impl RandomGeneratorApp {
+    /// Editor pane for the stratified mode; the strata set the count, so there's no count field
+    pub(crate) fn strata_input(&self) -> Element<'_, Message> {
+        let field_errors = self.field_errors();
+        let strata_valid = field_errors.strata.is_none();
+        let dark_mode = self.settings.dark_mode;
+        let high_contrast = self.settings.high_contrast;
+        let total = Strata::<i64>::parse(&self.strata.text()).map(|strata| strata.total());
+
+        container(
+            column![
+                text(self.tr("Strata (one per line, values: count):")).size(14),
+                text_editor(&self.strata)
+                    .on_action(Message::StrataEdited)
+                    .placeholder(self.tr("e.g. 1..100: 3"))
+                    .height(Length::Fixed(110.0))
+                    .size(14)
+                    .style(move |theme: &Theme, status| {
+                        let mut style = text_editor::default(theme, status);
+                        if !strata_valid {
+                            style.border.color = Color::from_rgb(0.9, 0.3, 0.3);
+                        }
+                        style
+                    }),
+                field_hint(field_errors.strata.clone()),
+                text(self.tr("Values are a range like 1..100 or numbers like 7, 13, 42; strata must not overlap"))
+                    .size(11)
+                    .color(crate::accessibility::dim_text_color(dark_mode, high_contrast)),
+            ]
+                .push_maybe(total.ok().map(|total| text(i18n::fill(self.tr("{} in total"), &[&total])).size(14)))
+                .spacing(4)
+        )
+            .padding(4)
+            .into()
+    }
+}
@@ -0,0 +1,374 @@
+use std::collections::HashSet;
+
+use iced::widget::{button, row, scrollable, text, Space};
+use iced::{alignment, Background, Border, Color, Element, Length, Theme};
+
+use random_generator_core::{GeneratorConfig, GeneratorMode, Rearrange};
+
+use crate::source_params::SourceParams;
+use crate::{accessibility, get_link_button_style, Message, RandomGeneratorApp, Status};
+
+/// One generation's results, kept open next to the others
+#[derive(Debug, Clone)]
+pub struct ResultTab {
+    /// Shown in the tab label; keeps counting up as tabs are opened
+    pub number: usize,
+    pub numbers: Vec<i64>,
+    /// Config and seed that drew these numbers with the inputs of the mode's source, `None` when they weren't drawn here
+    pub draw: Option<(GeneratorConfig, SourceParams)>,
+    /// Sorted, shuffled or reversed since the draw, so a replay only matches as a set
+    pub rearranged: bool,
+    /// Repeated values were removed since the draw, keeping each one's first place
+    pub deduplicated: bool,
+}
+
+impl ResultTab {
+    /// Whether these are the tab's numbers; after a rearrange only the order may differ,
+    /// and after removing duplicates each value only has to be there once
+    pub fn holds(&self, numbers: &[i64]) -> bool {
+        let mut theirs = numbers.to_vec();
+        if self.deduplicated {
+            let mut seen = HashSet::new();
+            theirs.retain(|&number| seen.insert(number));
+        }
+        if !self.rearranged {
+            return self.numbers == theirs;
+        }
+        let mut ours = self.numbers.clone();
+        ours.sort_unstable();
+        theirs.sort_unstable();
+        ours == theirs
+    }
+}
+
+impl RandomGeneratorApp {
+    /// Keep fresh results in a new tab and switch to it
+    pub(crate) fn open_tab(&mut self, numbers: Vec<i64>, draw: Option<(GeneratorConfig, SourceParams)>) {
+        self.next_tab_number += 1;
+        self.tabs.push(ResultTab { number: self.next_tab_number, numbers: numbers.clone(), draw, rearranged: false, deduplicated: false });
+        self.active_tab = self.tabs.len() - 1;
+        *self.generator.get_numbers_mut() = numbers;
+        self.presentation_index = 0;
+        self.revealed_count = 0;
+        self.reveal = None;
+        self.wheel = None;
+    }
+
+    /// Show an earlier result set; it was already revealed, so it appears in full
+    pub(crate) fn select_tab(&mut self, index: usize) {
+        let Some(tab) = self.tabs.get(index) else {
+            return;
+        };
+        self.active_tab = index;
+        *self.generator.get_numbers_mut() = tab.numbers.clone();
+        self.presentation_index = 0;
+        self.revealed_count = tab.numbers.len();
+        self.reveal = None;
+        self.wheel = None;
+    }
+
+    /// Reorder the active tab's results in place without drawing again
+    pub(crate) fn rearrange_tab(&mut self, how: Rearrange) {
+        let Some(tab) = self.tabs.get_mut(self.active_tab) else {
+            return;
+        };
+        self.generator.rearrange(how);
+        tab.numbers = self.generator.get_numbers().to_vec();
+        tab.rearranged = true;
+        self.presentation_index = 0;
+        self.revealed_count = tab.numbers.len();
+        self.reveal = None;
+        self.wheel = None;
+    }
+
+    /// Drop repeated values from the active tab's results, saying how many went
+    pub(crate) fn remove_duplicates_from_tab(&mut self) {
+        let Some(tab) = self.tabs.get_mut(self.active_tab) else {
+            return;
+        };
+        let removed = self.generator.remove_duplicates();
+        self.status = Some(Status::Info(match removed {
+            0 => "Removed no duplicates; every result is different".to_string(),
+            1 => "Removed 1 duplicate".to_string(),
+            _ => format!("Removed {} duplicates", removed),
+        }));
+        if removed == 0 {
+            return;
+        }
+        tab.numbers = self.generator.get_numbers().to_vec();
+        tab.deduplicated = true;
+        self.presentation_index = 0;
+        self.revealed_count = tab.numbers.len();
+        self.reveal = None;
+        self.wheel = None;
+    }
+
+    /// Draw the next round from the active tab's results, e.g. the finalists of an elimination draw
+    pub(crate) fn use_results_as_pool(&mut self) {
+        let numbers = self.generator.get_numbers();
+        if numbers.is_empty() {
+            return;
+        }
+        let pool: Vec<String> = numbers.iter().map(i64::to_string).collect();
+        self.status = Some(match self.set_custom_list(pool.join(", ")) {
+            Ok(()) => Status::Info(format!("Pool set to the {} results", pool.len())),
+            Err(e) => Status::Error(e.to_string()),
+        });
+        self.mode = GeneratorMode::CUSTOM_LIST;
+        if let Err(e) = self.generator.set_mode(GeneratorMode::CUSTOM_LIST) {
+            self.status = Some(Status::Error(e.to_string()));
+        }
+    }
+
+    /// Close a tab, falling back to its neighbour (or no results at all)
+    pub(crate) fn close_tab(&mut self, index: usize) {
+        if index >= self.tabs.len() {
+            return;
+        }
+        self.tabs.remove(index);
+        if self.tabs.is_empty() {
+            self.active_tab = 0;
+            self.generator.clear_numbers();
+            self.presentation_index = 0;
+            self.revealed_count = 0;
+            self.reveal = None;
+            self.wheel = None;
+        } else if index <= self.active_tab {
+            // Closing the active tab or one before it shifts the selection left
+            self.select_tab(self.active_tab.saturating_sub(1).min(self.tabs.len() - 1));
+        }
+    }
+
+    /// Tab strip above the results, with copy for the active tab
+    pub(crate) fn tab_bar(&self) -> Element<'_, Message> {
+        let tabs = row(self.tabs.iter().enumerate().map(|(index, tab)| {
+            let active = index == self.active_tab;
+            row![
+                button(text(format!("#{} ({})", tab.number, tab.numbers.len())).size(13))
+                    .on_press(Message::SelectTab(index))
+                    .padding(if self.settings.high_contrast { [10, 8] } else { [2, 8] })
+                    .style(move |_theme: &Theme, status| {
+                        if self.settings.high_contrast {
+                            return accessibility::high_contrast_tab(self.settings.dark_mode, active, status);
+                        }
+                        get_tab_style(self.settings.dark_mode, active, status)
+                    }),
+                button(text("×").size(13))
+                    .on_press(Message::CloseTab(index))
+                    .padding(if self.settings.high_contrast { [10, 6] } else { [2, 4] })
+                    .style(move |_theme: &Theme, status| get_link_button_style(self.settings.dark_mode, self.settings.high_contrast, status)),
+            ]
+                .align_y(alignment::Vertical::Center)
+                .into()
+        }))
+            .spacing(4);
+
+        let rearrange = row(Rearrange::ALL.map(|how| {
+            let label = match how {
+                Rearrange::SortAscending => "Sort ↑",
+                Rearrange::SortDescending => "Sort ↓",
+                Rearrange::Shuffle => "Shuffle",
+                Rearrange::Reverse => "Reverse",
+            };
+            button(text(self.tr(label)).size(13))
+                .on_press(Message::Rearrange(how))
+                .padding(self.button_padding())
+                .style(move |_theme: &Theme, status| get_link_button_style(self.settings.dark_mode, self.settings.high_contrast, status))
+                .into()
+        }));
+
+        row![
+            scrollable(tabs)
+                .direction(scrollable::Direction::Horizontal(
+                    scrollable::Scrollbar::new().width(3).scroller_width(3),
+                ))
+                .width(Length::Fill),
+            Space::with_width(Length::Fixed(6.0)),
+            rearrange,
+            button(text(self.tr("Remove duplicates")).size(13))
+                .on_press(Message::RemoveDuplicates)
+                .padding(self.button_padding())
+                .style(move |_theme: &Theme, status| get_link_button_style(self.settings.dark_mode, self.settings.high_contrast, status)),
+            button(text(self.tr("Use as pool")).size(13))
+                .on_press(Message::UseAsPool)
+                .padding(self.button_padding())
+                .style(move |_theme: &Theme, status| get_link_button_style(self.settings.dark_mode, self.settings.high_contrast, status)),
+            button(text(self.tr("Compare")).size(13))
+                .on_press(Message::ShowCompare)
+                .padding(self.button_padding())
+                .style(move |_theme: &Theme, status| get_link_button_style(self.settings.dark_mode, self.settings.high_contrast, status)),
+            button(text(self.tr("Replay")).size(13))
+                .on_press_maybe(
+                    self.tabs
+                        .get(self.active_tab)
+                        .and_then(|tab| tab.draw.as_ref())
+                        .filter(|(config, _)| config.seed.is_some())
+                        .map(|_| Message::Replay),
+                )
+                .padding(self.button_padding())
+                .style(move |_theme: &Theme, status| get_link_button_style(self.settings.dark_mode, self.settings.high_contrast, status)),
+            button(text(self.tr("Copy")).size(13))
+                .on_press(Message::CopyResults)
+                .padding(self.button_padding())
+                .style(move |_theme: &Theme, status| get_link_button_style(self.settings.dark_mode, self.settings.high_contrast, status)),
+        ]
+            .align_y(alignment::Vertical::Center)
+            .into()
+    }
+}
+
+fn get_tab_style(dark_mode: bool, active: bool, status: button::Status) -> button::Style {
+    let background = match (active, dark_mode) {
+        (true, true) => Color::from_rgb(0.3, 0.5, 0.8),
+        (true, false) => Color::from_rgb(0.2, 0.6, 0.9),
+        (false, true) if status == button::Status::Hovered => Color::from_rgb(0.3, 0.3, 0.35),
+        (false, false) if status == button::Status::Hovered => Color::from_rgb(0.88, 0.88, 0.88),
+        (false, true) => Color::from_rgb(0.25, 0.25, 0.3),
+        (false, false) => Color::from_rgb(0.92, 0.92, 0.92),
+    };
+    button::Style {
+        background: Some(Background::Color(background)),
+        border: Border {
+            color: Color::TRANSPARENT,
+            width: 0.0,
+            radius: 6.0.into(),
+        },
+        text_color: if active {
+            Color::WHITE
+        } else if dark_mode {
+            Color::from_rgb(0.85, 0.85, 0.85)
+        } else {
+            Color::from_rgb(0.2, 0.2, 0.2)
+        },
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_closing_tabs_keeps_a_valid_selection() {
+        let mut app = RandomGeneratorApp::default();
+        app.open_tab(vec![1, 2], None);
+        app.open_tab(vec![3], None);
+        app.open_tab(vec![4, 5, 6], None);
+        assert_eq!(app.active_tab, 2);
+
+        app.select_tab(1);
+        app.close_tab(0);
+        assert_eq!(app.active_tab, 0);
+        assert_eq!(app.generator.get_numbers(), &[3]);
+
+        app.close_tab(0);
+        assert_eq!(app.generator.get_numbers(), &[4, 5, 6]);
+        assert_eq!(app.tabs[0].number, 3);
+
+        app.close_tab(0);
+        assert!(app.tabs.is_empty());
+        assert!(app.generator.get_numbers().is_empty());
+    }
+
+    #[test]
+    fn test_rearranging_keeps_the_tab_in_step() {
+        let mut app = RandomGeneratorApp::default();
+        app.open_tab(vec![5, 1, 3], None);
+        app.open_tab(vec![2, 9], None);
+        app.select_tab(0);
+
+        let _ = app.update(Message::Rearrange(Rearrange::SortAscending));
+        assert_eq!(app.tabs[0].numbers, [1, 3, 5]);
+        assert_eq!(app.generator.get_numbers(), &[1, 3, 5]);
+        assert!(app.tabs[0].rearranged);
+        assert!(!app.tabs[1].rearranged);
+
+        let _ = app.update(Message::Rearrange(Rearrange::Reverse));
+        assert_eq!(app.tabs[0].numbers, [5, 3, 1]);
+        assert_eq!(app.revealed_count, 3);
+
+        app.select_tab(1);
+        app.select_tab(0);
+        assert_eq!(app.generator.get_numbers(), &[5, 3, 1]);
+        assert!(app.tabs[0].holds(&[3, 5, 1]));
+        assert!(!app.tabs[0].holds(&[3, 5]));
+        assert!(!app.tabs[1].holds(&[9, 2]));
+    }
+
+    #[test]
+    fn test_removing_duplicates_from_a_tab() {
+        let mut app = RandomGeneratorApp::default();
+        app.open_tab(vec![3, 1, 3, 2, 1], None);
+        let _ = app.update(Message::RemoveDuplicates);
+        assert_eq!(app.tabs[0].numbers, [3, 1, 2]);
+        assert_eq!(app.generator.get_numbers(), &[3, 1, 2]);
+        assert_eq!(app.status, Some(Status::Info("Removed 2 duplicates".to_string())));
+        assert!(app.tabs[0].holds(&[3, 1, 3, 2, 1]));
+        assert!(!app.tabs[0].holds(&[1, 3, 2]));
+
+        let _ = app.update(Message::Rearrange(Rearrange::SortAscending));
+        assert!(app.tabs[0].holds(&[3, 1, 3, 2, 1]));
+
+        let _ = app.update(Message::RemoveDuplicates);
+        assert!(matches!(&app.status, Some(Status::Info(message)) if message.starts_with("Removed no duplicates")));
+        assert_eq!(app.tabs[0].numbers, [1, 2, 3]);
+    }
+
+    #[test]
+    fn test_open_results_file_and_exclude_them() {
+        let path = std::env::temp_dir().join(format!("random-tool-open-{}.csv", std::process::id()));
+        std::fs::write(&path, "index,value\n1,12\n2,\"1,024\"\n3,12\n").unwrap();
+        let path = path.to_string_lossy().into_owned();
+
+        let mut app = RandomGeneratorApp::default();
+        let _ = app.update(Message::ResultsFileChosen(Some(path.clone())));
+        assert_eq!(app.tabs.len(), 1);
+        assert!(app.tabs[0].draw.is_none());
+        assert_eq!(app.visible_numbers(), &[12, 1024, 12]);
+        assert_eq!(app.status, Some(Status::Info(format!("Opened {}", path))));
+
+        let _ = app.update(Message::ExcludeDrawnToggled(true));
+        let _ = app.update(Message::ExcludeResults);
+        assert_eq!(app.exclude_drawn.as_ref().map(|excluded| excluded.len()), Some(2));
+        assert_eq!(app.status, Some(Status::Info("Excluded 2 more numbers".to_string())));
+
+        std::fs::write(&path, "index,value\n1,twelve\n").unwrap();
+        let _ = app.update(Message::ResultsFileChosen(Some(path.clone())));
+        assert_eq!(app.tabs.len(), 1);
+        assert!(matches!(&app.status, Some(Status::Error(message)) if message.starts_with("Open error")));
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_results_become_the_next_pool() {
+        let mut app = RandomGeneratorApp::default();
+        app.open_tab(vec![41, -3, 17], None);
+        let _ = app.update(Message::UseAsPool);
+        assert_eq!(app.mode, GeneratorMode::CUSTOM_LIST);
+        assert_eq!(app.custom_list_input.text(), "41, -3, 17\n");
+        assert_eq!(app.status, Some(Status::Info("Pool set to the 3 results".to_string())));
+
+        let _ = app.update(Message::NumToGenerateChanged("2".to_string()));
+        let _ = app.update(Message::AllowDuplicatesToggled(false));
+        let config = app.input_config();
+        assert_eq!(config.mode, GeneratorMode::CUSTOM_LIST);
+        assert_eq!(config.custom_list, [41, -3, 17]);
+        assert!(app.field_errors().is_empty());
+    }
+
+    #[test]
+    fn test_save_all_tabs_as_groups() {
+        let path = std::env::temp_dir().join(format!("random-tool-groups-{}.txt", std::process::id()));
+        let mut app = RandomGeneratorApp { filename: path.to_string_lossy().into_owned(), ..Default::default() };
+        app.open_tab(vec![1, 2], None);
+        app.open_tab(vec![3], None);
+        app.save_all_tabs = true;
+        let _ = app.update(Message::Save);
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "Round 1\n1\n2\n\nRound 2\n3");
+
+        app.settings.group_header = "Round".to_string();
+        let _ = app.update(Message::Save);
+        assert!(matches!(&app.status, Some(Status::Error(message)) if message.contains("Group header")));
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,851 @@
+//! Panel for generating rows of fake test data.
+//!
+//! Pick the columns (names, emails, phone numbers, addresses) and a locale,
+//! generate rows and save them as CSV or JSON. Or define columns of your own,
+//! each with a range, a distribution or a list to pick from, and save the
+//! rows as CSV. Alternatively paste a JSON
+//! Schema or field template and generate JSON documents conforming to it, or
+//! give a pattern like `AA-####` for serial numbers, make coupon codes
+//! with a check character that skip the codes in an earlier export, make
+//! device PINs that avoid easily guessed ones, make Diceware passphrases, or
+//! make MAC, IPv4 and IPv6 addresses for network tests.
+//! The rows come from `FieldSchema`, `ColumnSchema`, `JsonSchema`, `StringPattern`,
+//! `CodeSpec`, `PinSpec`, `PassphraseSpec` and `AddressSpec` in the core.
+
+use std::collections::HashSet;
+use std::fmt;
+use std::fs;
+
+use iced::widget::{button, checkbox, column, container, pick_list, row, scrollable, text, text_editor, text_input, Space};
+use iced::{alignment, Background, Border, Color, Element, Font, Length, Shadow, Theme, Vector};
+use random_generator_core::{
+    AddressKind, AddressSpec, Capitalization, CheckCharacter, CodeSpec, ColumnSchema, ExportFormat, FakeField, FakeLocale, FieldSchema, JsonSchema, PassphraseSpec,
+    PinSpec, Seed, StringPattern, Wordlist,
+};
+use serde_json::Value;
+
+use crate::export_panel::{settings_line, ExportFile, ExportPanel, PanelSeed};
+use crate::{
+    accessibility, get_checkbox_style, get_link_button_style, get_pick_list_style, get_text_input_style, Message,
+    RandomGeneratorApp,
+};
+
+/// More rows than this would make the window stall while generating
+const MAX_ROWS: usize = 100_000;
+/// Rows shown in the panel; the saved file has all of them
+const PREVIEW_ROWS: usize = 20;
+/// Longer passphrases than this are rarely typed by anyone
+const MAX_PASSPHRASE_WORDS: usize = 20;
+/// Schema the editor starts with
+const EXAMPLE_SCHEMA: &str = r#"{
+  "type": "object",
+  "properties": {
+    "id": {"type": "string", "format": "uuid"},
+    "name": {"type": "string", "format": "name"},
+    "age": {"type": "integer", "minimum": 18, "maximum": 90},
+    "tags": {"type": "array", "items": {"enum": ["new", "vip", "staff"]}, "maxItems": 2}
+  }
+}
+"#;
+/// Columns the editor starts with
+const EXAMPLE_COLUMNS: &str = "id: 1..1000\nscore: normal(50, 10) round 1\nsize: S, M, L, XL\n";
+
+/// What the panel generates
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TestDataSource {
+    #[default]
+    Fields,
+    Columns,
+    Schema,
+    Pattern,
+    Codes,
+    Pins,
+    Passphrases,
+    Addresses,
+}
+
+impl TestDataSource {
+    pub const ALL: [TestDataSource; 8] = [
+        TestDataSource::Fields,
+        TestDataSource::Columns,
+        TestDataSource::Schema,
+        TestDataSource::Pattern,
+        TestDataSource::Codes,
+        TestDataSource::Pins,
+        TestDataSource::Passphrases,
+        TestDataSource::Addresses,
+    ];
+
+    /// Formats the results can be saved in, the default first
+    fn formats(self) -> &'static [ExportFormat] {
+        match self {
+            TestDataSource::Fields => &[ExportFormat::Csv, ExportFormat::Json],
+            TestDataSource::Columns => &[ExportFormat::Csv],
+            TestDataSource::Schema => &[ExportFormat::Json],
+            TestDataSource::Pattern
+            | TestDataSource::Codes
+            | TestDataSource::Pins
+            | TestDataSource::Passphrases
+            | TestDataSource::Addresses => &[ExportFormat::Text, ExportFormat::Csv, ExportFormat::Json],
+        }
+    }
+}
+
+impl fmt::Display for TestDataSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TestDataSource::Fields => write!(f, "Fields"),
+            TestDataSource::Columns => write!(f, "Custom columns"),
+            TestDataSource::Schema => write!(f, "JSON schema"),
+            TestDataSource::Pattern => write!(f, "Pattern"),
+            TestDataSource::Codes => write!(f, "Coupon codes"),
+            TestDataSource::Pins => write!(f, "PINs"),
+            TestDataSource::Passphrases => write!(f, "Passphrases"),
+            TestDataSource::Addresses => write!(f, "Network addresses"),
+        }
+    }
+}
+
+/// Kinds of easily guessed PINs that can be left out
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PinRule {
+    Repeats,
+    Years,
+    Sequences,
+}
+
+#[derive(Debug)]
+pub struct TestDataPanel {
+    source: TestDataSource,
+    /// Chosen columns, in the order of `FakeField::ALL`
+    fields: Vec<FakeField>,
+    locale: FakeLocale,
+    schema_text: text_editor::Content,
+    /// One column per line, like `die: 1..6`
+    columns_text: text_editor::Content,
+    pattern: String,
+    /// No string twice in one batch
+    unique: bool,
+    code_length: String,
+    /// Characters between dashes, empty for none
+    code_group: String,
+    code_check: CheckCharacter,
+    /// Earlier export whose codes must not come up again, empty for none
+    code_exclude: String,
+    /// Digits per PIN and the rules it must follow
+    pin_length: String,
+    pin: PinSpec,
+    passphrase_words: String,
+    passphrase_separator: String,
+    passphrase_case: Capitalization,
+    /// Wordlist file, empty for the bundled list
+    wordlist_path: String,
+    address_kind: AddressKind,
+    /// Vendor prefix for MAC addresses, CIDR block for IP addresses
+    address_range: String,
+    count: String,
+    seed: PanelSeed,
+    file: ExportFile,
+    /// Rows from the last generation and the columns they have
+    rows: Vec<Vec<String>>,
+    schema: Option<FieldSchema>,
+    columns: Option<ColumnSchema>,
+    documents: Vec<Value>,
+    strings: Vec<String>,
+    status: String,
+}
+
+impl Default for TestDataPanel {
+    fn default() -> Self {
+        Self {
+            fields: vec![FakeField::FullName, FakeField::Email, FakeField::Phone],
+            locale: FakeLocale::default(),
+            source: TestDataSource::default(),
+            schema_text: text_editor::Content::with_text(EXAMPLE_SCHEMA),
+            columns_text: text_editor::Content::with_text(EXAMPLE_COLUMNS),
+            pattern: "AA-####".to_string(),
+            unique: true,
+            code_length: "8".to_string(),
+            code_group: "4".to_string(),
+            code_check: CheckCharacter::Luhn,
+            code_exclude: String::new(),
+            pin_length: PinSpec::default().length.to_string(),
+            pin: PinSpec::default(),
+            passphrase_words: PassphraseSpec::default().words.to_string(),
+            passphrase_separator: PassphraseSpec::default().separator,
+            passphrase_case: Capitalization::default(),
+            wordlist_path: String::new(),
+            address_kind: AddressKind::default(),
+            address_range: default_address_range(AddressKind::default()).to_string(),
+            count: "10".to_string(),
+            seed: PanelSeed::default(),
+            file: ExportFile::new("test-data.csv", ExportFormat::Csv),
+            rows: Vec::new(),
+            schema: None,
+            columns: None,
+            documents: Vec::new(),
+            strings: Vec::new(),
+            status: String::new(),
+        }
+    }
+}
+
+impl TestDataPanel {
+    pub fn toggle_field(&mut self, field: FakeField, selected: bool) {
+        self.fields.retain(|&chosen| chosen != field);
+        if selected {
+            self.fields.push(field);
+            self.fields.sort_by_key(|field| FakeField::ALL.iter().position(|other| other == field));
+        }
+    }
+
+    pub fn set_locale(&mut self, locale: FakeLocale) {
+        self.locale = locale;
+    }
+
+    /// Also picks a format the new source can be saved in
+    pub fn set_source(&mut self, source: TestDataSource) {
+        self.source = source;
+        if !source.formats().contains(&self.file.format()) {
+            self.file.set_format(source.formats()[0]);
+        }
+    }
+
+    pub fn set_pattern(&mut self, pattern: String) {
+        self.pattern = pattern;
+    }
+
+    pub fn set_unique(&mut self, unique: bool) {
+        self.unique = unique;
+    }
+
+    pub fn set_code_length(&mut self, length: String) {
+        self.code_length = length;
+    }
+
+    pub fn set_code_group(&mut self, group: String) {
+        self.code_group = group;
+    }
+
+    pub fn set_code_check(&mut self, check: CheckCharacter) {
+        self.code_check = check;
+    }
+
+    pub fn set_code_exclude(&mut self, path: String) {
+        self.code_exclude = path;
+    }
+
+    pub fn set_pin_length(&mut self, length: String) {
+        self.pin_length = length;
+    }
+
+    pub fn set_pin_rule(&mut self, rule: PinRule, on: bool) {
+        match rule {
+            PinRule::Repeats => self.pin.no_repeats = on,
+            PinRule::Years => self.pin.no_years = on,
+            PinRule::Sequences => self.pin.no_sequences = on,
+        }
+    }
+
+    pub fn set_passphrase_words(&mut self, words: String) {
+        self.passphrase_words = words;
+    }
+
+    pub fn set_passphrase_separator(&mut self, separator: String) {
+        self.passphrase_separator = separator;
+    }
+
+    pub fn set_passphrase_case(&mut self, case: Capitalization) {
+        self.passphrase_case = case;
+    }
+
+    pub fn set_wordlist_path(&mut self, path: String) {
+        self.wordlist_path = path;
+    }
+
+    /// Also puts in an example range for the new kind
+    pub fn set_address_kind(&mut self, kind: AddressKind) {
+        if kind != self.address_kind {
+            self.address_kind = kind;
+            self.address_range = default_address_range(kind).to_string();
+        }
+    }
+
+    pub fn set_address_range(&mut self, range: String) {
+        self.address_range = range;
+    }
+
+    pub fn edit_schema(&mut self, action: text_editor::Action) {
+        self.schema_text.perform(action);
+    }
+
+    pub fn edit_columns(&mut self, action: text_editor::Action) {
+        self.columns_text.perform(action);
+    }
+
+    pub fn set_count(&mut self, count: String) {
+        self.count = count;
+    }
+
+    pub fn file_mut(&mut self) -> &mut ExportFile {
+        &mut self.file
+    }
+
+    pub fn seed_mut(&mut self) -> &mut PanelSeed {
+        &mut self.seed
+    }
+
+    /// How the last draw or export went
+    pub fn status(&self) -> &str {
+        &self.status
+    }
+
+    /// Settings of the draw, for the history
+    pub fn settings(&self) -> String {
+        let mut fields = vec![("source", self.source.to_string()), ("count", self.count.clone())];
+        match self.source {
+            TestDataSource::Fields => {
+                let names: Vec<String> = self.fields.iter().map(ToString::to_string).collect();
+                fields.extend([("fields", names.join(", ")), ("locale", self.locale.to_string())]);
+            }
+            TestDataSource::Columns => fields.push(("columns", self.columns_text.text())),
+            TestDataSource::Schema => fields.extend([("schema", self.schema_text.text()), ("locale", self.locale.to_string())]),
+            TestDataSource::Pattern => fields.extend([("pattern", self.pattern.clone()), ("unique", self.unique.to_string())]),
+            TestDataSource::Codes => fields.extend([
+                ("length", self.code_length.clone()),
+                ("group", self.code_group.clone()),
+                ("check", self.code_check.to_string()),
+                ("exclude", self.code_exclude.clone()),
+            ]),
+            TestDataSource::Pins => fields.push(("digits", self.pin_length.clone())),
+            TestDataSource::Passphrases => fields.extend([
+                ("words", self.passphrase_words.clone()),
+                ("separator", self.passphrase_separator.clone()),
+                ("case", self.passphrase_case.to_string()),
+                ("wordlist", self.wordlist_path.clone()),
+            ]),
+            TestDataSource::Addresses => fields.extend([
+                ("kind", self.address_kind.to_string()),
+                ("range", self.address_range.clone()),
+                ("unique", self.unique.to_string()),
+            ]),
+        }
+        settings_line(&fields)
+    }
+
+    pub fn generate(&mut self) {
+        let seed = match self.seed.next() {
+            Ok(seed) => seed,
+            Err(message) => {
+                self.status = message;
+                return;
+            }
+        };
+        let count = match self.count.trim().parse::<usize>() {
+            Ok(count) if (1..=MAX_ROWS).contains(&count) => count,
+            _ => {
+                self.status = format!("Enter a number of rows from 1 to {}", MAX_ROWS);
+                return;
+            }
+        };
+        let generated = match self.source {
+            TestDataSource::Fields => {
+                let schema = FieldSchema::new(self.fields.clone(), self.locale);
+                schema.generate(count, seed).map(|rows| {
+                    self.rows = rows;
+                    self.schema = Some(schema);
+                    self.rows.len()
+                })
+            }
+            TestDataSource::Columns => ColumnSchema::parse(&self.columns_text.text()).and_then(|columns| {
+                self.rows = columns.generate(count, seed)?;
+                self.columns = Some(columns);
+                Ok(self.rows.len())
+            }),
+            TestDataSource::Schema => JsonSchema::parse(&self.schema_text.text()).map(|mut schema| {
+                schema.locale = self.locale;
+                self.documents = schema.generate(count, seed);
+                self.documents.len()
+            }),
+            TestDataSource::Pattern => self
+                .pattern
+                .parse::<StringPattern>()
+                .and_then(|pattern| pattern.generate(count, self.unique, seed))
+                .map(|strings| {
+                    self.strings = strings;
+                    self.strings.len()
+                }),
+            TestDataSource::Pins => match self.pin_length.trim().parse::<usize>() {
+                Ok(length) => PinSpec { length, ..self.pin }.generate(count, seed).map(|pins| {
+                    self.strings = pins;
+                    self.strings.len()
+                }),
+                Err(_) => {
+                    self.status = "Enter how many digits a PIN has".to_string();
+                    return;
+                }
+            },
+            TestDataSource::Addresses => AddressSpec::parse(self.address_kind, &self.address_range)
+                .and_then(|spec| spec.generate(count, self.unique, seed))
+                .map(|addresses| {
+                    self.strings = addresses;
+                    self.strings.len()
+                }),
+            TestDataSource::Passphrases => {
+                // The status also reports the entropy
+                self.generate_passphrases(count, seed);
+                return;
+            }
+            TestDataSource::Codes => match self.code_spec() {
+                Ok((spec, exclude)) => spec.generate(count, seed, &exclude).map(|codes| {
+                    self.strings = codes;
+                    self.strings.len()
+                }),
+                Err(message) => {
+                    self.status = message;
+                    return;
+                }
+            },
+        };
+        self.status = match generated {
+            Ok(count) => {
+                self.seed.keep(seed);
+                format!("{} rows", count)
+            }
+            Err(e) => e.to_string(),
+        };
+    }
+
+    fn generate_passphrases(&mut self, count: usize, seed: Seed) {
+        let words = match self.passphrase_words.trim().parse::<usize>() {
+            Ok(words) if (1..=MAX_PASSPHRASE_WORDS).contains(&words) => words,
+            _ => {
+                self.status = format!("Enter a number of words from 1 to {}", MAX_PASSPHRASE_WORDS);
+                return;
+            }
+        };
+        let wordlist = match self.wordlist_path.trim() {
+            "" => Ok(Wordlist::bundled()),
+            path => fs::read_to_string(path)
+                .map_err(|e| format!("{}: {}", path, e))
+                .and_then(|text| Wordlist::parse(&text).map_err(|e| format!("{}: {}", path, e))),
+        };
+        let spec = PassphraseSpec {
+            words,
+            separator: self.passphrase_separator.clone(),
+            capitalization: self.passphrase_case,
+        };
+        self.status = match wordlist.and_then(|wordlist| {
+            let phrases = spec.generate(&wordlist, count, seed).map_err(|e| e.to_string())?;
+            Ok((phrases, spec.entropy_bits(&wordlist), wordlist.len()))
+        }) {
+            Ok((phrases, bits, size)) => {
+                self.seed.keep(seed);
+                self.strings = phrases;
+                format!("{} rows, about {:.1} bits each from {} words", self.strings.len(), bits, size)
+            }
+            Err(message) => message,
+        };
+    }
+
+    pub fn export(&mut self) {
+        let contents = match (self.source, &self.schema, &self.columns) {
+            (TestDataSource::Fields, Some(schema), _) => Some(match self.file.format() {
+                ExportFormat::Json => schema.to_json(&self.rows),
+                _ => schema.to_csv(&self.rows),
+            }),
+            (TestDataSource::Columns, _, Some(columns)) => Some(columns.to_csv(&self.rows)),
+            (TestDataSource::Schema, _, _) if !self.documents.is_empty() => {
+                Some(serde_json::to_string_pretty(&self.documents).unwrap_or_default() + "\n")
+            }
+            (
+                TestDataSource::Pattern
+                | TestDataSource::Codes
+                | TestDataSource::Pins
+                | TestDataSource::Passphrases
+                | TestDataSource::Addresses,
+                _,
+                _,
+            ) if !self.strings.is_empty() => Some(match self.file.format() {
+                ExportFormat::Text => self.strings.join("\n") + "\n",
+                ExportFormat::Csv => {
+                    let cells = self.strings.iter().map(|string| csv_cell(string));
+                    std::iter::once("value".to_string()).chain(cells).collect::<Vec<_>>().join("\n") + "\n"
+                }
+                ExportFormat::Json => serde_json::to_string_pretty(&self.strings).unwrap_or_default() + "\n",
+            }),
+            _ => None,
+        };
+        self.status = self.file.save(contents);
+    }
+
+    /// The code settings and the codes to skip from the earlier export
+    fn code_spec(&self) -> Result<(CodeSpec, HashSet<String>), String> {
+        let length = match self.code_length.trim().parse::<usize>() {
+            Ok(length) if (1..=64).contains(&length) => length,
+            _ => return Err("Enter a code length from 1 to 64".to_string()),
+        };
+        let group = match self.code_group.trim() {
+            "" => 0,
+            group => group.parse::<usize>().map_err(|_| "Enter how many characters go between dashes".to_string())?,
+        };
+        let exclude = match self.code_exclude.trim() {
+            "" => HashSet::new(),
+            path => CodeSpec::collect_codes(&fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))?),
+        };
+        Ok((CodeSpec { length, check: self.code_check, group, ..Default::default() }, exclude))
+    }
+}
+
+/// What the range input starts with for each kind of address
+fn default_address_range(kind: AddressKind) -> &'static str {
+    match kind {
+        AddressKind::Mac => "",
+        AddressKind::Ipv4 => "192.168.0.0/16",
+        AddressKind::Ipv6 => "2001:db8::/32",
+    }
+}
+
+/// Quote a CSV cell when it has a separator, quote or line break
+fn csv_cell(cell: &str) -> String {
+    if cell.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", cell.replace('"', "\"\""))
+    } else {
+        cell.to_string()
+    }
+}
+
+impl RandomGeneratorApp {
+    pub(crate) fn test_data_view(&self) -> Element<'_, Message> {
+        let panel = &self.test_data;
+        let dark_mode = self.settings.dark_mode;
+        let high_contrast = self.settings.high_contrast;
+        let label = |content: &'static str| text(self.tr(content)).size(14).width(Length::Fixed(80.0));
+        let action = |content: &'static str, message: Message| {
+            button(text(self.tr(content)).size(14))
+                .on_press(message)
+                .padding(self.button_padding())
+                .style(move |_theme: &Theme, status| get_link_button_style(dark_mode, high_contrast, status))
+        };
+        let field_box = |field: FakeField| {
+            checkbox(self.tr(field.label()), panel.fields.contains(&field))
+                .on_toggle(move |selected| Message::TestDataFieldToggled(field, selected))
+                .size(self.checkbox_size())
+                .text_size(14)
+                .width(Length::FillPortion(1))
+                .style(move |_theme: &Theme, _status| get_checkbox_style(dark_mode, high_contrast))
+        };
+
+        let mut fields = column![].spacing(6);
+        match panel.source {
+            TestDataSource::Fields => {
+                for pair in FakeField::ALL.chunks(2) {
+                    fields = fields.push(row(pair.iter().map(|&field| field_box(field).into())).spacing(8));
+                }
+            }
+            TestDataSource::Columns => {
+                fields = fields
+                    .push(
+                        text_editor(&panel.columns_text)
+                            .on_action(Message::TestDataColumnsEdited)
+                            .placeholder(self.tr("One column per line, like die: 1..6"))
+                            .height(Length::Fixed(120.0))
+                            .size(13)
+                            .font(Font::MONOSPACE),
+                    )
+                    .push(
+                        text(self.tr("name: 1..6 for whole numbers, normal(50, 10) round 1 for a distribution, red, green, blue to pick one"))
+                            .size(11)
+                            .color(accessibility::dim_text_color(dark_mode, high_contrast)),
+                    );
+            }
+            TestDataSource::Schema => {
+                fields = fields.push(
+                    text_editor(&panel.schema_text)
+                        .on_action(Message::TestDataSchemaEdited)
+                        .placeholder(self.tr("JSON Schema or a template like {\"id\": \"uuid\", \"name\": \"name\"}"))
+                        .height(Length::Fixed(150.0))
+                        .size(13)
+                        .font(Font::MONOSPACE),
+                );
+            }
+            TestDataSource::Pattern => {
+                fields = fields
+                    .push(
+                        row![
+                            label("Pattern"),
+                            text_input("AA-####", &panel.pattern)
+                                .on_input(Message::TestDataPatternChanged)
+                                .on_submit(Message::GenerateTestData)
+                                .width(Length::Fill)
+                                .size(14)
+                                .font(Font::MONOSPACE)
+                                .style(move |_theme: &Theme, status| get_text_input_style(dark_mode, high_contrast, status)),
+                        ]
+                            .align_y(alignment::Vertical::Center),
+                    )
+                    .push(
+                        text(self.tr("# digit, A/a letter, * letter or digit, [A-F] one of, (X|Y) either, {4} repeat"))
+                            .size(11)
+                            .color(accessibility::dim_text_color(dark_mode, high_contrast)),
+                    )
+                    .push(
+                        checkbox(self.tr("No duplicates"), panel.unique)
+                            .on_toggle(Message::TestDataUniqueToggled)
+                            .size(self.checkbox_size())
+                            .text_size(14)
+                            .style(move |_theme: &Theme, _status| get_checkbox_style(dark_mode, high_contrast)),
+                    );
+            }
+            TestDataSource::Codes => {
+                let small_input = |value: &str, on_input: fn(String) -> Message| {
+                    text_input("", value)
+                        .on_input(on_input)
+                        .on_submit(Message::GenerateTestData)
+                        .width(Length::Fixed(60.0))
+                        .size(14)
+                        .style(move |_theme: &Theme, status| get_text_input_style(dark_mode, high_contrast, status))
+                };
+                fields = fields
+                    .push(
+                        row![
+                            label("Length"),
+                            small_input(&panel.code_length, Message::TestDataCodeLengthChanged),
+                            Space::with_width(Length::Fixed(12.0)),
+                            text(self.tr("Dash every")).size(14),
+                            small_input(&panel.code_group, Message::TestDataCodeGroupChanged),
+                        ]
+                            .spacing(6)
+                            .align_y(alignment::Vertical::Center),
+                    )
+                    .push(
+                        row![
+                            label("Check"),
+                            pick_list(&CheckCharacter::ALL[..], Some(panel.code_check), Message::TestDataCodeCheckChanged)
+                                .text_size(14)
+                                .style(move |_theme: &Theme, _status| get_pick_list_style(dark_mode, high_contrast)),
+                        ]
+                            .align_y(alignment::Vertical::Center),
+                    )
+                    .push(
+                        row![
+                            label("Skip codes in"),
+                            text_input(self.tr("Earlier export (optional)"), &panel.code_exclude)
+                                .on_input(Message::TestDataCodeExcludeChanged)
+                                .width(Length::Fill)
+                                .size(14)
+                                .style(move |_theme: &Theme, status| get_text_input_style(dark_mode, high_contrast, status)),
+                        ]
+                            .align_y(alignment::Vertical::Center),
+                    )
+                    .push(
+                        text(self.tr("Codes use A-Z and 2-9 without O and I, and never repeat in a batch"))
+                            .size(11)
+                            .color(accessibility::dim_text_color(dark_mode, high_contrast)),
+                    );
+            }
+            TestDataSource::Pins => {
+                let rule_box = |content: &'static str, rule: PinRule, on: bool| {
+                    checkbox(self.tr(content), on)
+                        .on_toggle(move |on| Message::TestDataPinRuleToggled(rule, on))
+                        .size(self.checkbox_size())
+                        .text_size(14)
+                        .style(move |_theme: &Theme, _status| get_checkbox_style(dark_mode, high_contrast))
+                };
+                fields = fields
+                    .push(
+                        row![
+                            label("Digits"),
+                            text_input("4-12", &panel.pin_length)
+                                .on_input(Message::TestDataPinLengthChanged)
+                                .on_submit(Message::GenerateTestData)
+                                .width(Length::Fixed(60.0))
+                                .size(14)
+                                .style(move |_theme: &Theme, status| get_text_input_style(dark_mode, high_contrast, status)),
+                        ]
+                            .align_y(alignment::Vertical::Center),
+                    )
+                    .push(rule_box("No digit three times in a row (777)", PinRule::Repeats, panel.pin.no_repeats))
+                    .push(rule_box("No years 1900-2099", PinRule::Years, panel.pin.no_years))
+                    .push(rule_box("No runs like 345 or 987", PinRule::Sequences, panel.pin.no_sequences));
+            }
+            TestDataSource::Addresses => {
+                let (range_label, placeholder) = match panel.address_kind {
+                    AddressKind::Mac => ("Vendor prefix", "Any (locally administered)"),
+                    AddressKind::Ipv4 | AddressKind::Ipv6 => ("Network", "Any, or a CIDR block like 10.0.0.0/8"),
+                };
+                fields = fields
+                    .push(
+                        row![
+                            label("Kind"),
+                            pick_list(&AddressKind::ALL[..], Some(panel.address_kind), Message::TestDataAddressKindChanged)
+                                .text_size(14)
+                                .style(move |_theme: &Theme, _status| get_pick_list_style(dark_mode, high_contrast)),
+                        ]
+                            .align_y(alignment::Vertical::Center),
+                    )
+                    .push(
+                        row![
+                            label(range_label),
+                            text_input(self.tr(placeholder), &panel.address_range)
+                                .on_input(Message::TestDataAddressRangeChanged)
+                                .on_submit(Message::GenerateTestData)
+                                .width(Length::Fill)
+                                .size(14)
+                                .font(Font::MONOSPACE)
+                                .style(move |_theme: &Theme, status| get_text_input_style(dark_mode, high_contrast, status)),
+                        ]
+                            .align_y(alignment::Vertical::Center),
+                    )
+                    .push(
+                        checkbox(self.tr("No duplicates"), panel.unique)
+                            .on_toggle(Message::TestDataUniqueToggled)
+                            .size(self.checkbox_size())
+                            .text_size(14)
+                            .style(move |_theme: &Theme, _status| get_checkbox_style(dark_mode, high_contrast)),
+                    );
+            }
+            TestDataSource::Passphrases => {
+                fields = fields
+                    .push(
+                        row![
+                            label("Words"),
+                            text_input("", &panel.passphrase_words)
+                                .on_input(Message::TestDataPassphraseWordsChanged)
+                                .on_submit(Message::GenerateTestData)
+                                .width(Length::Fixed(60.0))
+                                .size(14)
+                                .style(move |_theme: &Theme, status| get_text_input_style(dark_mode, high_contrast, status)),
+                            Space::with_width(Length::Fixed(12.0)),
+                            text(self.tr("Separator")).size(14),
+                            text_input("", &panel.passphrase_separator)
+                                .on_input(Message::TestDataPassphraseSeparatorChanged)
+                                .width(Length::Fixed(60.0))
+                                .size(14)
+                                .font(Font::MONOSPACE)
+                                .style(move |_theme: &Theme, status| get_text_input_style(dark_mode, high_contrast, status)),
+                        ]
+                            .spacing(6)
+                            .align_y(alignment::Vertical::Center),
+                    )
+                    .push(
+                        row![
+                            label("Case"),
+                            pick_list(&Capitalization::ALL[..], Some(panel.passphrase_case), Message::TestDataPassphraseCaseChanged)
+                                .text_size(14)
+                                .style(move |_theme: &Theme, _status| get_pick_list_style(dark_mode, high_contrast)),
+                        ]
+                            .align_y(alignment::Vertical::Center),
+                    )
+                    .push(
+                        row![
+                            label("Wordlist"),
+                            text_input(self.tr("Bundled list of 1296 words"), &panel.wordlist_path)
+                                .on_input(Message::TestDataWordlistChanged)
+                                .width(Length::Fill)
+                                .size(14)
+                                .style(move |_theme: &Theme, status| get_text_input_style(dark_mode, high_contrast, status)),
+                        ]
+                            .align_y(alignment::Vertical::Center),
+                    );
+            }
+        }
+
+        let lines: Option<Vec<String>> = match panel.source {
+            TestDataSource::Fields => panel.schema.as_ref().map(|schema| {
+                std::iter::once(schema.fields.iter().map(|field| field.key()).collect::<Vec<_>>().join(" | "))
+                    .chain(panel.rows.iter().take(PREVIEW_ROWS).map(|row| row.join(" | ")))
+                    .collect()
+            }),
+            TestDataSource::Columns => panel.columns.as_ref().map(|columns| {
+                std::iter::once(columns.columns.iter().map(|column| column.name.as_str()).collect::<Vec<_>>().join(" | "))
+                    .chain(panel.rows.iter().take(PREVIEW_ROWS).map(|row| row.join(" | ")))
+                    .collect()
+            }),
+            TestDataSource::Schema => (!panel.documents.is_empty())
+                .then(|| panel.documents.iter().take(PREVIEW_ROWS).map(|document| document.to_string()).collect()),
+            TestDataSource::Pattern
+            | TestDataSource::Codes
+            | TestDataSource::Pins
+            | TestDataSource::Passphrases
+            | TestDataSource::Addresses => {
+                (!panel.strings.is_empty()).then(|| panel.strings.iter().take(PREVIEW_ROWS).cloned().collect())
+            }
+        };
+        let preview = lines.map(|lines| {
+            container(
+                scrollable(column(lines.into_iter().map(|line| text(line).size(12).font(Font::MONOSPACE).into())).spacing(2))
+                    .height(Length::Fixed(160.0)),
+            )
+            .padding(6)
+            .width(Length::Fill)
+        });
+
+        let content = column![
+            text(self.tr("Test data"))
+                .size(20)
+                .color(if dark_mode { Color::from_rgb(0.9, 0.9, 0.9) } else { Color::BLACK }),
+            Space::with_height(Length::Fixed(10.0)),
+            row![
+                label("Generate from"),
+                pick_list(&TestDataSource::ALL[..], Some(panel.source), Message::TestDataSourceChanged)
+                    .text_size(14)
+                    .style(move |_theme: &Theme, _status| get_pick_list_style(dark_mode, high_contrast)),
+            ]
+                .align_y(alignment::Vertical::Center),
+            fields,
+            row![
+                label("Locale"),
+                pick_list(&FakeLocale::ALL[..], Some(panel.locale), Message::TestDataLocaleChanged)
+                    .text_size(14)
+                    .style(move |_theme: &Theme, _status| get_pick_list_style(dark_mode, high_contrast)),
+            ]
+                .align_y(alignment::Vertical::Center),
+            row![
+                label("Rows"),
+                text_input("", &panel.count)
+                    .on_input(Message::TestDataCountChanged)
+                    .on_submit(Message::GenerateTestData)
+                    .width(Length::Fixed(100.0))
+                    .size(14)
+                    .style(move |_theme: &Theme, status| get_text_input_style(dark_mode, high_contrast, status)),
+                Space::with_width(Length::Fill),
+                action("Generate", Message::GenerateTestData),
+            ]
+                .align_y(alignment::Vertical::Center),
+            self.seed_row(ExportPanel::TestData, &panel.seed),
+            self.export_row(ExportPanel::TestData, &panel.file, panel.source.formats(), "Save"),
+        ]
+            .push_maybe(preview)
+            .push(
+                row![
+                    text(&panel.status).size(13).color(accessibility::dim_text_color(dark_mode, high_contrast)),
+                    Space::with_width(Length::Fill),
+                    action("Close", Message::CloseTestData),
+                ]
+                    .align_y(alignment::Vertical::Center),
+            )
+            .spacing(6)
+            .padding(24);
+
+        container(content)
+            .width(Length::Fixed(460.0))
+            .style(move |_theme: &Theme| {
+                iced::widget::container::Style {
+                    background: Some(Background::Color(if dark_mode { Color::from_rgb(0.2, 0.2, 0.25) } else { Color::WHITE })),
+                    border: Border {
+                        color: if dark_mode { Color::from_rgb(0.4, 0.4, 0.4) } else { Color::from_rgb(0.8, 0.8, 0.8) },
+                        width: 1.0,
+                        radius: 16.0.into(),
+                    },
+                    shadow: Shadow {
+                        color: Color::from_rgba(0.0, 0.0, 0.0, 0.3),
+                        offset: Vector::new(0.0, 4.0),
+                        blur_radius: 20.0,
+                    },
+                    ..Default::default()
+                }
+            })
+            .into()
+    }
+}
@@ -0,0 +1,125 @@
+//! Ticket check-in for on-site raffles. In the tickets mode each entrant is
+//! handed the next number as they arrive, and Generate draws winners only from
+//! the numbers handed out so far, from the first ticket up to the last issued.
+
+use iced::widget::{button, container, row, text, Space};
+use iced::{alignment, Background, Border, Color, Element, Length, Shadow, Theme, Vector};
+
+use crate::{accessibility, Message, RandomGeneratorApp, Status};
+
+/// Last ticket issued when tickets start at `first` and `issued` were handed out;
+/// one below `first` while none are out
+pub fn last_ticket(first: i64, issued: &str) -> Result<i64, &'static str> {
+    let issued: u32 = issued.trim().parse().map_err(|_| "Must be a whole number")?;
+    first.checked_add(i64::from(issued) - 1).ok_or("Too many tickets")
+}
+
+impl RandomGeneratorApp {
+    /// Number the next entrant gets, `None` while the first ticket or the count doesn't parse
+    pub(crate) fn next_ticket(&self) -> Option<i64> {
+        let first = self.lower_bound.trim().parse().ok()?;
+        last_ticket(first, &self.tickets_issued).ok()?.checked_add(1)
+    }
+
+    /// Hand out the next ticket
+    pub(crate) fn issue_ticket(&mut self) {
+        let issued = self.tickets_issued.trim().parse::<u32>().ok().and_then(|issued| issued.checked_add(1));
+        let (Some(ticket), Some(issued)) = (self.next_ticket(), issued) else {
+            self.status = Some(Status::Error("Please fix the highlighted fields".to_string()));
+            return;
+        };
+        self.tickets_issued = issued.to_string();
+        self.status = Some(Status::Info(format!("Issued ticket {}", self.number_format.format(ticket))));
+    }
+
+    /// The next ticket number in large type and the button that issues it
+    pub(crate) fn ticket_counter(&self) -> Element<'_, Message> {
+        let dark_mode = self.settings.dark_mode;
+        let high_contrast = self.settings.high_contrast;
+        let next = self.next_ticket();
+        container(
+            row![
+                text(self.tr("Next ticket")).size(14),
+                text(next.map_or("–".to_string(), |ticket| self.number_format.format(ticket)))
+                    .size(28)
+                    .font(iced::Font::MONOSPACE),
+                Space::with_width(Length::Fill),
+                button(text(self.tr("Issue ticket")).size(14))
+                    .on_press_maybe(next.map(|_| Message::IssueTicket))
+                    .padding(self.button_padding())
+                    .style(move |_theme: &Theme, status| {
+                        if high_contrast {
+                            return accessibility::high_contrast_button(dark_mode, status);
+                        }
+                        let is_pressed = status == button::Status::Pressed;
+                        button::Style {
+                            background: Some(Background::Color(
+                                if is_pressed {
+                                    if dark_mode {
+                                        Color::from_rgb(0.2, 0.5, 0.2)
+                                    } else {
+                                        Color::from_rgb(0.3, 0.7, 0.3)
+                                    }
+                                } else if dark_mode {
+                                    Color::from_rgb(0.3, 0.6, 0.3)
+                                } else {
+                                    Color::from_rgb(0.4, 0.8, 0.4)
+                                }
+                            )),
+                            border: Border {
+                                color: Color::TRANSPARENT,
+                                width: 0.0,
+                                radius: 8.0.into(),
+                            },
+                            text_color: Color::WHITE,
+                            shadow: Shadow {
+                                color: Color::from_rgba(0.0, 0.0, 0.0, 0.15),
+                                offset: Vector::new(0.0, if is_pressed { 1.0 } else { 2.0 }),
+                                blur_radius: if is_pressed { 2.0 } else { 4.0 },
+                            },
+                        }
+                    }),
+            ]
+                .spacing(10)
+                .align_y(alignment::Vertical::Center)
+        )
+            .padding([4, 4])
+            .into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use random_generator_core::GeneratorMode;
+
+    #[test]
+    fn test_issue_tickets_and_draw_from_them() {
+        assert_eq!(last_ticket(1, "0"), Ok(0));
+        assert_eq!(last_ticket(101, "40"), Ok(140));
+        assert_eq!(last_ticket(i64::MAX, "2"), Err("Too many tickets"));
+
+        let mut app = RandomGeneratorApp::default();
+        let _ = app.update(Message::ModeChanged(GeneratorMode::TICKETS));
+        let _ = app.update(Message::LowerBoundChanged("1".to_string()));
+        assert_eq!(app.field_errors().upper.as_deref(), Some("Draw: no tickets have been issued yet"));
+
+        let _ = app.update(Message::IssueTicket);
+        let _ = app.update(Message::IssueTicket);
+        assert_eq!(app.tickets_issued, "2");
+        assert_eq!(app.status, Some(Status::Info("Issued ticket 2".to_string())));
+        assert_eq!(app.next_ticket(), Some(3));
+        assert!(app.field_errors().is_empty());
+        let config = app.input_config();
+        assert_eq!((config.lower_bound, config.upper_bound), (1, 2));
+
+        // Winners are excluded from later rounds, as in range mode
+        let _ = app.update(Message::ExcludeDrawnToggled(true));
+        let _ = app.update(Message::NumToGenerateChanged("3".to_string()));
+        assert_eq!(app.field_errors().count.as_deref(), Some("At most 2 without duplicates"));
+
+        let _ = app.update(Message::TicketsIssuedChanged("lots".to_string()));
+        assert_eq!(app.field_errors().upper.as_deref(), Some("Must be a whole number"));
+        assert_eq!(app.next_ticket(), None);
+    }
+}
@@ -0,0 +1,113 @@
+//! System tray icon with quick actions, so small draws don't need the window focused.
+//!
+//! On Linux the tray needs a GTK main loop, which gets its own thread; elsewhere the
+//! icon is created on the UI thread and driven by the window's event loop.
+
+use iced::futures::{executor, SinkExt, Stream};
+use tray_icon::menu::{Menu, MenuEvent, MenuItem};
+use tray_icon::{Icon, TrayIcon, TrayIconBuilder};
+
+/// Actions offered by the tray menu
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrayAction {
+    GenerateAgain,
+    CopyResults,
+    ShowWindow,
+}
+
+impl TrayAction {
+    const ALL: [TrayAction; 3] = [
+        TrayAction::GenerateAgain,
+        TrayAction::CopyResults,
+        TrayAction::ShowWindow,
+    ];
+
+    fn id(self) -> &'static str {
+        match self {
+            TrayAction::GenerateAgain => "generate",
+            TrayAction::CopyResults => "copy",
+            TrayAction::ShowWindow => "show",
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            TrayAction::GenerateAgain => "Generate again",
+            TrayAction::CopyResults => "Copy last results",
+            TrayAction::ShowWindow => "Show window",
+        }
+    }
+}
+
+/// Keeps the tray icon alive; dropping it removes the icon
+pub struct TrayHandle {
+    _icon: Option<TrayIcon>,
+}
+
+/// Create the tray icon
+pub fn spawn() -> TrayHandle {
+    #[cfg(target_os = "linux")]
+    {
+        std::thread::spawn(|| {
+            if gtk::init().is_err() {
+                return;
+            }
+            let _icon = build();
+            gtk::main();
+        });
+        TrayHandle { _icon: None }
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        TrayHandle { _icon: build() }
+    }
+}
+
+fn build() -> Option<TrayIcon> {
+    let menu = Menu::new();
+    for action in TrayAction::ALL {
+        let item = MenuItem::with_id(action.id(), action.label(), true, None);
+        menu.append(&item).ok()?;
+    }
+
+    TrayIconBuilder::new()
+        .with_menu(Box::new(menu))
+        .with_tooltip("Random Generator")
+        .with_icon(icon())
+        .build()
+        .ok()
+}
+
+/// Menu clicks, forwarded from the tray's event channel
+pub fn events() -> impl Stream<Item = TrayAction> {
+    iced::stream::channel(8, |mut output| async move {
+        std::thread::spawn(move || {
+            while let Ok(event) = MenuEvent::receiver().recv() {
+                let action = TrayAction::ALL
+                    .into_iter()
+                    .find(|action| event.id().as_ref() == action.id());
+                if let Some(action) = action {
+                    if executor::block_on(output.send(action)).is_err() {
+                        break;
+                    }
+                }
+            }
+        });
+    })
+}
+
+/// Simple round blue icon so no image assets are needed
+fn icon() -> Icon {
+    const SIZE: u32 = 32;
+    let center = (SIZE as f32 - 1.0) / 2.0;
+    let mut rgba = Vec::with_capacity((SIZE * SIZE * 4) as usize);
+    for y in 0..SIZE {
+        for x in 0..SIZE {
+            let distance = ((x as f32 - center).powi(2) + (y as f32 - center).powi(2)).sqrt();
+            let alpha = if distance <= center { 255 } else { 0 };
+            rgba.extend_from_slice(&[51, 153, 230, alpha]);
+        }
+    }
+    Icon::from_rgba(rgba, SIZE, SIZE).expect("icon buffer matches its size")
+}
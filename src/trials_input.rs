@@ -0,0 +1,58 @@
+use iced::widget::{column, container, row, text, text_input, Column, Space};
+use iced::{Element, Length, Theme};
+use random_generator_core::GeneratorMode;
+
+use crate::{field_hint, get_validated_input_style, Message, RandomGeneratorApp};
+
+impl RandomGeneratorApp {
+    /// Inputs for the geometric and negative-binomial modes: the chance of success,
+    /// the successes to wait for (negative binomial only) and the count
+    pub(crate) fn trials_input(&self) -> Element<'_, Message> {
+        let field_errors = self.field_errors();
+        let dark_mode = self.settings.dark_mode;
+        let high_contrast = self.settings.high_contrast;
+        let field = |label: &'static str, value: &str, on_input: fn(String) -> Message, error: Option<String>| -> Column<'_, Message> {
+            let valid = error.is_none();
+            column![
+                text(self.tr(label)).size(14),
+                text_input("", value)
+                    .on_input(on_input)
+                    .on_submit(Message::Generate)
+                    .width(Length::Fixed(96.0))
+                    .size(14)
+                    .style(move |_theme: &Theme, status| get_validated_input_style(dark_mode, high_contrast, valid, status)),
+                field_hint(error),
+            ]
+                .width(Length::Fixed(96.0))
+                .spacing(2)
+        };
+        let negative_binomial = self.mode == GeneratorMode::NEGATIVE_BINOMIAL;
+        let successes = negative_binomial.then(|| {
+            row![
+                Space::with_width(Length::Fixed(8.0)),
+                field("Successes", &self.successes, Message::SuccessesChanged, field_errors.successes.clone()),
+            ]
+        });
+        let hint = if negative_binomial {
+            "Each value is how many tries it took to get that many successes"
+        } else {
+            "Each value is how many tries it took to get the first success"
+        };
+
+        container(
+            column![
+                row![field("Chance of success", &self.success_chance, Message::SuccessChanceChanged, field_errors.chance.clone())]
+                    .push_maybe(successes)
+                    .push(Space::with_width(Length::Fixed(8.0)))
+                    .push(field("Count", &self.num_to_generate, Message::NumToGenerateChanged, field_errors.count.clone()))
+                    .spacing(6),
+                text(self.tr(hint))
+                    .size(11)
+                    .color(crate::accessibility::dim_text_color(dark_mode, high_contrast)),
+            ]
+                .spacing(4)
+        )
+            .padding(4)
+            .into()
+    }
+}
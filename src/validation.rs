@@ -0,0 +1,561 @@
+use random_generator_core::{GeneratorMode, LabelMap, RandomGenerator, RandomGeneratorError, ResultOrder, SourceInput, Transform, MAX_SUCCESSES};
+use crate::{parse_limit, tickets, RandomGeneratorApp};
+
+/// Problems with the current inputs, one optional hint per field
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct FieldErrors {
+    pub lower: Option<String>,
+    pub upper: Option<String>,
+    pub count: Option<String>,
+    pub total: Option<String>,
+    pub chance: Option<String>,
+    pub successes: Option<String>,
+    pub custom_list: Option<String>,
+    pub script: Option<String>,
+    pub strata: Option<String>,
+    pub max_repeats: Option<String>,
+    pub max_streak: Option<String>,
+    pub cooldown: Option<String>,
+    pub transform: Option<String>,
+    pub labels: Option<String>,
+}
+
+impl FieldErrors {
+    pub fn is_empty(&self) -> bool {
+        self.lower.is_none()
+            && self.upper.is_none()
+            && self.count.is_none()
+            && self.total.is_none()
+            && self.chance.is_none()
+            && self.successes.is_none()
+            && self.custom_list.is_none()
+            && self.script.is_none()
+            && self.strata.is_none()
+            && self.max_repeats.is_none()
+            && self.max_streak.is_none()
+            && self.cooldown.is_none()
+            && self.transform.is_none()
+            && self.labels.is_none()
+    }
+}
+
+impl RandomGeneratorApp {
+    /// Validate the text inputs as typed, without touching the generator
+    pub(crate) fn field_errors(&self) -> FieldErrors {
+        let mut errors = FieldErrors::default();
+        let mut config = self.generator.get_config().clone();
+        config.mode = self.mode;
+        config.list_duplicates = self.settings.list_duplicates;
+        config.order = self.settings.result_order;
+        config.exclude_drawn = self.exclusions();
+
+        // Values that don't parse are flagged here; the rest go to the core to check together
+        let mut parsed = true;
+        match self.mode_input() {
+            SourceInput::Bounds => {
+                match self.lower_bound.trim().parse::<i64>() {
+                    Ok(lower) => config.lower_bound = lower,
+                    Err(_) => errors.lower = Some("Must be an integer".to_string()),
+                }
+                // In tickets mode the issued count stands in for To
+                let upper = match config.mode {
+                    GeneratorMode::TICKETS => tickets::last_ticket(config.lower_bound, &self.tickets_issued),
+                    _ => self.upper_bound.trim().parse::<i64>().map_err(|_| "Must be an integer"),
+                };
+                match upper {
+                    Ok(upper) => config.upper_bound = upper,
+                    Err(message) => errors.upper = Some(message.to_string()),
+                }
+                if config.mode == GeneratorMode::PARTITION && self.partition_total.trim().parse::<i64>().is_err() {
+                    errors.total = Some("Must be an integer".to_string());
+                }
+                parsed = errors.lower.is_none() && errors.upper.is_none() && errors.total.is_none();
+            }
+            // Items that aren't numbers are only an error in strict lists; otherwise they're skipped
+            SourceInput::List if !self.settings.strict_list => {
+                let items = RandomGenerator::custom_list_items(&self.custom_list_input.text());
+                config.custom_list = items.into_iter().filter_map(|(_, value)| value).collect();
+            }
+            SourceInput::List => match RandomGenerator::parse_custom_list_input(&self.custom_list_input.text()) {
+                Ok(list) => config.custom_list = list,
+                Err(_) => {
+                    errors.custom_list = Some("Contains something that is not an integer".to_string());
+                    parsed = false;
+                }
+            },
+            // The script and the strata are checked by their source
+            SourceInput::Own => {
+                let trials = config.mode == GeneratorMode::GEOMETRIC || config.mode == GeneratorMode::NEGATIVE_BINOMIAL;
+                if trials && self.success_chance.trim().parse::<f64>().is_err() {
+                    errors.chance = Some("Must be a number".to_string());
+                }
+                if config.mode == GeneratorMode::NEGATIVE_BINOMIAL && self.successes.trim().parse::<u64>().is_err() {
+                    errors.successes = Some("Must be a whole number".to_string());
+                }
+                parsed = errors.chance.is_none() && errors.successes.is_none();
+            }
+        }
+        // The strata set their own counts and the count field is hidden
+        match self.num_to_generate.trim().parse::<usize>() {
+            Ok(count) => config.num_to_generate = count,
+            Err(_) if config.mode == GeneratorMode::STRATIFIED => {}
+            Err(_) => errors.count = Some("Must be a whole number".to_string()),
+        }
+        match parse_limit(&self.max_repeats_input) {
+            Ok(max) => config.max_repeats = max,
+            Err(_) => errors.max_repeats = Some("Must be a whole number".to_string()),
+        }
+        match parse_limit(&self.max_streak_input) {
+            Ok(max) => config.max_streak = max,
+            Err(_) => errors.max_streak = Some("Must be a whole number".to_string()),
+        }
+        if !self.cooldown_input.trim().is_empty() && self.cooldown_input.trim().parse::<usize>().is_err() {
+            errors.cooldown = Some("Must be a whole number".to_string());
+        }
+        match Transform::parse_list(&self.transform_input) {
+            Ok(transforms) => config.transforms = transforms,
+            Err(e) => errors.transform = Some(e.to_string()),
+        }
+        if self.labels_enabled {
+            errors.labels = LabelMap::parse(&self.labels.text()).err().map(|e| e.to_string());
+        }
+        if !parsed {
+            return errors;
+        }
+
+        // Modes with parameters of their own are checked with the typed ones, not the installed ones
+        let params = self.input_params();
+        let problems = match params.source(config.mode) {
+            Some(source) => RandomGenerator::validate_with(source.as_ref(), &config),
+            None => self.generator.validate(&config),
+        };
+        for problem in problems {
+            match problem {
+                RandomGeneratorError::InvalidBounds => {
+                    errors.upper = Some("Must not be below From".to_string());
+                }
+                RandomGeneratorError::EmptyList => {
+                    errors.custom_list = Some("Enter at least one number".to_string());
+                }
+                RandomGeneratorError::InvalidScript { .. } => {
+                    errors.script = Some(problem.to_string());
+                }
+                // One stratum at a time, like the script
+                RandomGeneratorError::InvalidStrata { .. } if errors.strata.is_none() => {
+                    errors.strata = Some(problem.to_string());
+                }
+                RandomGeneratorError::InvalidPartition(_) => {
+                    errors.total = Some(problem.to_string());
+                }
+                RandomGeneratorError::InvalidTrials(_)
+                    if config.mode == GeneratorMode::NEGATIVE_BINOMIAL && !(1..=MAX_SUCCESSES).contains(&params.successes) =>
+                {
+                    errors.successes = Some(problem.to_string());
+                }
+                RandomGeneratorError::InvalidTrials(_) => {
+                    errors.chance = Some(problem.to_string());
+                }
+                RandomGeneratorError::DuplicateEntries => {
+                    errors.custom_list = Some("Contains repeated numbers".to_string());
+                }
+                // Only the repeat limits, the shuffle bag and the cooldown are checked this way here
+                RandomGeneratorError::InvalidDraw(_) => {
+                    let message = Some(problem.to_string());
+                    // The core checks the limits before the bag and the cooldown
+                    let clashes = config.shuffle_bag.is_some() || config.cooldown.is_some();
+                    let repeats = config.max_repeats.is_some_and(|max| max == 0 || clashes);
+                    let streak = config.max_streak.is_some_and(|max| max == 0 || clashes || config.order == ResultOrder::Sorted);
+                    match self.mode_input() {
+                        _ if config.allow_duplicates && repeats => errors.max_repeats = message,
+                        _ if config.allow_duplicates && streak => errors.max_streak = message,
+                        _ if config.cooldown.is_some() => errors.cooldown = message,
+                        SourceInput::Bounds => errors.upper = message,
+                        SourceInput::List => errors.custom_list = message,
+                        SourceInput::Own if config.mode == GeneratorMode::SCRIPT => errors.script = message,
+                        SourceInput::Own if config.mode == GeneratorMode::STRATIFIED => errors.strata = message,
+                        SourceInput::Own => errors.chance = message,
+                    }
+                }
+                RandomGeneratorError::InvalidTransform(_) => {
+                    errors.transform = Some(problem.to_string());
+                }
+                RandomGeneratorError::CountTooLarge => {
+                    errors.count = Some(problem.to_string());
+                }
+                RandomGeneratorError::TooManyNumbers if errors.count.is_none() => {
+                    let pool = self.generator.source(config.mode).and_then(|source| source.available(&config));
+                    errors.count = Some(match (self.generator.capacity(&config), config.max_repeats) {
+                        (Some(capacity), _) if !config.allow_duplicates => format!("At most {} without duplicates", capacity),
+                        (Some(capacity), _) if pool == Some(1) => format!("At most {} of the only value", capacity),
+                        (Some(capacity), Some(max)) => format!("At most {} with {} of each", capacity, max),
+                        _ => problem.to_string(),
+                    });
+                }
+                _ => {}
+            }
+        }
+
+        errors
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Message, Status};
+    use iced::widget::text_editor;
+    use random_generator_core::{ExclusionSet, GeneratorMode, ListDuplicates, ShuffleBag};
+
+    #[test]
+    fn test_defaults_are_valid() {
+        let app = RandomGeneratorApp::default();
+        assert!(app.field_errors().is_empty());
+    }
+
+    #[test]
+    fn test_flags_each_bad_field() {
+        let mut app = RandomGeneratorApp {
+            lower_bound: "abc".to_string(),
+            num_to_generate: "-1".to_string(),
+            ..Default::default()
+        };
+        let errors = app.field_errors();
+        assert!(errors.lower.is_some());
+        assert!(errors.upper.is_none());
+        assert!(errors.count.is_some());
+
+        app.lower_bound = "10".to_string();
+        app.upper_bound = "5".to_string();
+        app.num_to_generate = "1".to_string();
+        assert!(app.field_errors().upper.is_some());
+    }
+
+    #[test]
+    fn test_count_limited_by_pool_without_duplicates() {
+        let mut app = RandomGeneratorApp {
+            lower_bound: "1".to_string(),
+            upper_bound: "3".to_string(),
+            num_to_generate: "4".to_string(),
+            ..Default::default()
+        };
+        assert!(app.field_errors().count.is_some());
+
+        app.mode = GeneratorMode::CUSTOM_LIST;
+        app.custom_list_input = text_editor::Content::with_text("1, 2, x");
+        app.settings.strict_list = true;
+        let errors = app.field_errors();
+        assert!(errors.custom_list.is_some());
+        assert!(errors.lower.is_none());
+
+        // Skipped unless strict, leaving too few numbers for the count
+        app.settings.strict_list = false;
+        let errors = app.field_errors();
+        assert!(errors.custom_list.is_none());
+        assert!(errors.count.is_some());
+
+        // Repeated entries count once towards the pool
+        app.custom_list_input = text_editor::Content::with_text("1, 1, 2, 2, 3");
+        assert!(app.field_errors().count.is_some());
+        app.settings.list_duplicates = ListDuplicates::Reject;
+        assert!(app.field_errors().custom_list.is_some());
+    }
+
+    #[test]
+    fn test_script_errors_point_at_the_line() {
+        let mut app = RandomGeneratorApp {
+            mode: GeneratorMode::SCRIPT,
+            // Script draws may repeat, so the count isn't limited by the range
+            upper_bound: "1".to_string(),
+            num_to_generate: "50".to_string(),
+            ..Default::default()
+        };
+        assert!(app.field_errors().is_empty());
+
+        app.script = text_editor::Content::with_text("let dice = roll(2, 6);\ndice.explode(6)");
+        let errors = app.field_errors();
+        assert!(errors.script.as_deref().is_some_and(|error| error.contains("line 2")));
+        assert!(errors.upper.is_none());
+    }
+
+    #[test]
+    fn test_shuffle_bag_needs_a_small_pool() {
+        let mut app = RandomGeneratorApp {
+            lower_bound: "1".to_string(),
+            upper_bound: "100".to_string(),
+            ..Default::default()
+        };
+        app.generator.set_shuffle_bag(Some(ShuffleBag::new()));
+        assert!(app.field_errors().is_empty());
+
+        app.upper_bound = "100000000".to_string();
+        assert!(app.field_errors().upper.is_some());
+
+        app.mode = GeneratorMode::SCRIPT;
+        assert!(app.field_errors().script.is_some());
+    }
+
+    #[test]
+    fn test_cooldown_must_leave_a_value() {
+        let mut app = RandomGeneratorApp {
+            lower_bound: "1".to_string(),
+            upper_bound: "3".to_string(),
+            cooldown_input: "x".to_string(),
+            ..Default::default()
+        };
+        assert!(app.field_errors().cooldown.is_some());
+
+        let _ = app.update(Message::CooldownChanged("2".to_string()));
+        assert!(app.field_errors().is_empty());
+        let _ = app.update(Message::CooldownChanged("3".to_string()));
+        let errors = app.field_errors();
+        assert!(errors.cooldown.is_some());
+        assert!(errors.upper.is_none());
+    }
+
+    #[test]
+    fn test_transform_steps() {
+        let mut app = RandomGeneratorApp::default();
+        let _ = app.update(Message::TransformChanged("scale 2, mod 0".to_string()));
+        assert!(app.field_errors().transform.is_some());
+        assert!(app.generator.transforms().is_empty());
+
+        let _ = app.update(Message::TransformChanged("scale 2, offset 1".to_string()));
+        assert!(app.field_errors().is_empty());
+        assert_eq!(app.generator.transforms(), [Transform::Scale(2), Transform::Offset(1)]);
+        // The last valid steps stay while the text is being edited
+        let _ = app.update(Message::TransformChanged("scale 2, offset".to_string()));
+        assert!(app.field_errors().transform.is_some());
+        assert_eq!(app.generator.transforms().len(), 2);
+    }
+
+    #[test]
+    fn test_labels_table() {
+        let mut app = RandomGeneratorApp::default();
+        assert!(app.number_format.labels.is_empty());
+        let _ = app.update(Message::LabelsToggled(true));
+        assert_eq!(app.number_format.format(2), "blue team");
+
+        // A bad line is flagged and the last valid labels stay in use
+        app.labels = text_editor::Content::with_text("1: red team\n2 blue team");
+        let _ = app.update(Message::LabelsEdited(text_editor::Action::SelectAll));
+        assert!(app.field_errors().labels.is_some());
+        assert_eq!(app.number_format.format(2), "blue team");
+
+        let _ = app.update(Message::LabelsToggled(false));
+        assert!(app.field_errors().is_empty());
+        assert_eq!(app.number_format.format(2), "2");
+    }
+
+    #[test]
+    fn test_custom_list_editor_marks_bad_items() {
+        use crate::list_editor::BadItems;
+        use iced::advanced::text::Highlighter;
+        use std::sync::Arc;
+
+        let mut app = RandomGeneratorApp::default();
+        let _ = app.update(Message::ModeChanged(GeneratorMode::CUSTOM_LIST));
+        let _ = app.update(Message::CustomListEdited(text_editor::Action::SelectAll));
+        let paste = text_editor::Edit::Paste(Arc::new("4, 8\n15; x 16".to_string()));
+        let _ = app.update(Message::CustomListEdited(text_editor::Action::Edit(paste)));
+        assert_eq!(app.custom_list_input.text(), "4, 8\n15; x 16\n");
+        assert_eq!(app.generator.get_custom_list_input(), "4, 8\n15; x 16\n");
+        assert_eq!(app.generator.get_config().custom_list, [4, 8, 15, 16]);
+        assert_eq!(app.generator.skipped_list_items(), ["x"]);
+        assert!(app.field_errors().custom_list.is_none());
+
+        let _ = app.update(Message::StrictListToggled(true));
+        assert!(app.field_errors().custom_list.is_some());
+        assert!(matches!(app.status, Some(Status::Error(_))));
+
+        let mut highlighter = BadItems::new(&());
+        assert_eq!(highlighter.highlight_line("4, 8").count(), 0);
+        assert_eq!(highlighter.highlight_line("15; x 16").collect::<Vec<_>>(), [(4..5, ())]);
+        assert_eq!(highlighter.current_line(), 2);
+    }
+
+    #[test]
+    fn test_custom_list_summary_and_preview() {
+        let mut app = RandomGeneratorApp { custom_list_input: text_editor::Content::with_text(""), ..Default::default() };
+        assert_eq!(app.custom_list_summary(), ("0 valid items parsed".to_string(), None));
+
+        app.custom_list_input = text_editor::Content::with_text("7");
+        assert_eq!(app.custom_list_summary(), ("1 valid item parsed".to_string(), Some("7".to_string())));
+
+        // Semicolons and new lines split items like commas do
+        app.custom_list_input = text_editor::Content::with_text("1;2;3\n4 5,6 7\n8\n9 x");
+        let (summary, preview) = app.custom_list_summary();
+        assert_eq!(summary, "9 valid items parsed, 1 skipped: x");
+        assert_eq!(preview.unwrap(), "1, 2, 3, 4, 5, 6, 7, 8, …");
+
+        app.custom_list_input = text_editor::Content::with_text("1.5, -2, 1e3");
+        assert_eq!(app.custom_list_summary(), ("1 valid item parsed, 2 skipped: 1.5, 1e3".to_string(), Some("-2".to_string())));
+
+        // Strict lists don't skip anything, so the items are only counted
+        app.settings.strict_list = true;
+        assert_eq!(app.custom_list_summary().0, "1 valid item parsed, 2 not numbers (in red)");
+        app.custom_list_input = text_editor::Content::with_text("1 x");
+        assert_eq!(app.custom_list_summary().0, "1 valid item parsed, 1 not a number (in red)");
+    }
+
+    #[test]
+    fn test_paste_list_from_a_spreadsheet() {
+        let mut app = RandomGeneratorApp::default();
+        let _ = app.update(Message::ListPasted(Some("Name\tTicket\nAnn\t104\nBo\t1,250\n".to_string())));
+        assert_eq!(app.mode, GeneratorMode::CUSTOM_LIST);
+        assert_eq!(app.custom_list_input.text(), "104, 1250\n");
+        assert_eq!(app.status, Some(Status::Info("Pasted 2 numbers".to_string())));
+        assert!(!app.labels_enabled);
+
+        // A column of names is drawn by number and shown by name
+        let _ = app.update(Message::ListPasted(Some("Ann\n\"Lee, Bo\"\nCy\n".to_string())));
+        assert_eq!(app.custom_list_input.text(), "1, 2, 3\n");
+        assert_eq!(app.status, Some(Status::Info("Pasted 3 names, drawn by number and shown as labels".to_string())));
+        assert!(app.labels_enabled);
+        assert_eq!(app.number_format.format(2), "Lee, Bo");
+        assert!(app.field_errors().is_empty());
+
+        let _ = app.update(Message::ListPasted(None));
+        assert_eq!(app.status, Some(Status::Error("The clipboard holds no list".to_string())));
+        assert_eq!(app.custom_list_input.text(), "1, 2, 3\n");
+    }
+
+    #[test]
+    fn test_repeat_limit_caps_the_count() {
+        let mut app = RandomGeneratorApp {
+            lower_bound: "1".to_string(),
+            upper_bound: "3".to_string(),
+            num_to_generate: "7".to_string(),
+            max_repeats_input: "2".to_string(),
+            ..Default::default()
+        };
+        app.generator.set_allow_duplicates(true).unwrap();
+        assert_eq!(app.field_errors().count.as_deref(), Some("At most 6 with 2 of each"));
+
+        app.num_to_generate = "6".to_string();
+        assert!(app.field_errors().is_empty());
+        app.max_repeats_input = "0".to_string();
+        assert!(app.field_errors().max_repeats.is_some());
+
+        // The limit only counts with duplicates
+        app.max_repeats_input = "2".to_string();
+        let _ = app.update(Message::AllowDuplicatesToggled(false));
+        assert!(app.field_errors().count.is_some_and(|error| error.contains("without duplicates")));
+    }
+
+    #[test]
+    fn test_draw_space_hint() {
+        let mut app = RandomGeneratorApp {
+            lower_bound: "1".to_string(),
+            upper_bound: "3".to_string(),
+            num_to_generate: "4".to_string(),
+            ..Default::default()
+        };
+        // The pool explains why four numbers don't fit
+        let errors = app.field_errors();
+        assert!(errors.count.is_some());
+        assert_eq!(app.draw_space_hint(&errors).as_deref(), Some("Pool: 3 values"));
+
+        app.upper_bound = "1000".to_string();
+        let mut excluded = ExclusionSet::new();
+        excluded.extend([5, 6, 2000]);
+        app.exclude_drawn = Some(excluded);
+        app.generator.set_allow_duplicates(true).unwrap();
+        assert_eq!(app.draw_space_hint(&app.field_errors()).as_deref(), Some("Pool: 998 values, chance of a repeat: 0.6%"));
+        app.max_repeats_input = "2".to_string();
+        assert_eq!(app.draw_space_hint(&app.field_errors()).as_deref(), Some("Pool: 998 values, at most 1996 per draw, chance of a repeat: 0.6%"));
+
+        app.upper_bound = "x".to_string();
+        assert_eq!(app.draw_space_hint(&app.field_errors()), None);
+
+        assert_eq!(crate::percent(0.28), "28%");
+        assert_eq!(crate::percent(0.123456), "12.35%");
+        assert_eq!(crate::percent(1e-9), "< 0.01%");
+        assert_eq!(crate::percent(0.999999), "> 99.99%");
+    }
+
+    #[test]
+    fn test_streak_limit_needs_the_draw_order() {
+        let mut app = RandomGeneratorApp {
+            lower_bound: "5".to_string(),
+            upper_bound: "5".to_string(),
+            num_to_generate: "4".to_string(),
+            max_streak_input: "3".to_string(),
+            ..Default::default()
+        };
+        app.generator.set_allow_duplicates(true).unwrap();
+        assert_eq!(app.field_errors().count.as_deref(), Some("At most 3 of the only value"));
+
+        app.upper_bound = "6".to_string();
+        assert!(app.field_errors().is_empty());
+        app.settings.result_order = ResultOrder::Sorted;
+        let errors = app.field_errors();
+        assert!(errors.max_streak.is_some());
+        assert!(errors.upper.is_none());
+    }
+
+    #[test]
+    fn test_strata_errors_go_to_the_editor() {
+        let mut app = RandomGeneratorApp {
+            mode: GeneratorMode::STRATIFIED,
+            num_to_generate: "x".to_string(),
+            ..Default::default()
+        };
+        // The example strata are fine, and the hidden count doesn't matter
+        assert!(app.field_errors().is_empty());
+
+        app.strata = text_editor::Content::with_text("1..5: 6");
+        let errors = app.field_errors();
+        assert!(errors.strata.as_deref().is_some_and(|error| error.contains("line 1")));
+        assert!(errors.count.is_none());
+    }
+
+    #[test]
+    fn test_partition_errors_go_to_the_total() {
+        let mut app = RandomGeneratorApp {
+            mode: GeneratorMode::PARTITION,
+            lower_bound: "0".to_string(),
+            upper_bound: "20".to_string(),
+            num_to_generate: "7".to_string(),
+            partition_total: "100".to_string(),
+            ..Default::default()
+        };
+        assert!(app.field_errors().is_empty());
+
+        app.partition_total = "141".to_string();
+        let errors = app.field_errors();
+        assert!(errors.total.as_deref().is_some_and(|error| error.contains("at most 20")));
+        assert!(errors.count.is_none() && errors.upper.is_none());
+        app.partition_total = "lots".to_string();
+        assert_eq!(app.field_errors().total.as_deref(), Some("Must be an integer"));
+    }
+
+    #[test]
+    fn test_trials_errors_go_to_their_fields() {
+        let mut app = RandomGeneratorApp {
+            mode: GeneratorMode::NEGATIVE_BINOMIAL,
+            success_chance: "0.25".to_string(),
+            successes: "3".to_string(),
+            num_to_generate: "10".to_string(),
+            ..Default::default()
+        };
+        assert!(app.field_errors().is_empty());
+
+        app.successes = "0".to_string();
+        let errors = app.field_errors();
+        assert!(errors.successes.as_deref().is_some_and(|error| error.contains("successes")));
+        assert!(errors.chance.is_none());
+        app.successes = "3".to_string();
+        app.success_chance = "1.5".to_string();
+        let errors = app.field_errors();
+        assert!(errors.chance.as_deref().is_some_and(|error| error.contains("chance")));
+        assert!(errors.successes.is_none());
+        app.success_chance = "often".to_string();
+        assert_eq!(app.field_errors().chance.as_deref(), Some("Must be a number"));
+
+        // The geometric mode has no successes field
+        app.mode = GeneratorMode::GEOMETRIC;
+        app.success_chance = "0.5".to_string();
+        app.successes = "lots".to_string();
+        assert!(app.field_errors().is_empty());
+    }
+}
@@ -0,0 +1,244 @@
+//! Panel for random walks and AR(1) time series.
+//!
+//! The series starts at a given value and takes one random step per index,
+//! plus an optional drift; with phi below 1 it becomes an AR(1) series that
+//! keeps returning towards its mean. It is drawn as a line chart and can be
+//! saved as CSV with an index column. The series comes from `RandomWalk` in
+//! the core and is drawn on a worker thread with a progress bar.
+
+use std::fs;
+
+use iced::widget::{button, canvas, column, container, pick_list, row, text, text_input, Space};
+use iced::{alignment, Background, Border, Color, Element, Length, Shadow, Task, Theme, Vector};
+use random_generator_core::{RandomWalk, Seed, StepDistribution};
+
+use crate::line_chart::LineChart;
+use crate::worker::{PanelEvent, PanelRun};
+use crate::{accessibility, get_link_button_style, get_pick_list_style, get_text_input_style, Message, RandomGeneratorApp};
+
+#[derive(Debug)]
+pub struct WalkPanel {
+    length: String,
+    start: String,
+    drift: String,
+    phi: String,
+    step: StepDistribution,
+    scale: String,
+    filename: String,
+    running: PanelRun,
+    series: Option<(Vec<f64>, LineChart)>,
+    status: String,
+}
+
+impl Default for WalkPanel {
+    fn default() -> Self {
+        let walk = RandomWalk::default();
+        Self {
+            length: walk.length.to_string(),
+            start: walk.start.to_string(),
+            drift: walk.drift.to_string(),
+            phi: walk.phi.to_string(),
+            step: walk.step,
+            scale: walk.scale.to_string(),
+            filename: "walk.csv".to_string(),
+            running: PanelRun::default(),
+            series: None,
+            status: String::new(),
+        }
+    }
+}
+
+impl WalkPanel {
+    pub fn set_length(&mut self, length: String) {
+        self.length = length;
+    }
+
+    pub fn set_start(&mut self, start: String) {
+        self.start = start;
+    }
+
+    pub fn set_drift(&mut self, drift: String) {
+        self.drift = drift;
+    }
+
+    pub fn set_phi(&mut self, phi: String) {
+        self.phi = phi;
+    }
+
+    pub fn set_step(&mut self, step: StepDistribution) {
+        self.step = step;
+    }
+
+    pub fn set_scale(&mut self, scale: String) {
+        self.scale = scale;
+    }
+
+    pub fn set_filename(&mut self, filename: String) {
+        self.filename = filename;
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.running.is_running()
+    }
+
+    /// Draw the series and its chart on a worker thread
+    pub fn run(&mut self) -> Task<Message> {
+        if self.is_running() {
+            return Task::none();
+        }
+        self.series = None;
+        let Ok(length) = self.length.trim().parse() else {
+            self.status = "Enter a whole number for the length".to_string();
+            return Task::none();
+        };
+        let (Ok(start), Ok(drift), Ok(phi), Ok(scale)) =
+            (self.start.trim().parse(), self.drift.trim().parse(), self.phi.trim().parse(), self.scale.trim().parse())
+        else {
+            self.status = "Enter numbers for the start, drift, phi and step size".to_string();
+            return Task::none();
+        };
+        let walk = RandomWalk { length, start, drift, phi, step: self.step, scale };
+        let seed = Seed::random(&mut rand::thread_rng());
+        self.status.clear();
+        let draw = self.running.start(move |cancel, on_progress| {
+            let series = walk.series_with_progress(seed, cancel, on_progress).map_err(|e| e.to_string())?;
+            let chart = LineChart::new(&series);
+            Ok((series, chart))
+        });
+        Task::run(draw, Message::Walk)
+    }
+
+    pub fn cancel(&self) {
+        self.running.cancel();
+    }
+
+    /// Follow the running draw; the chart shows once it has finished
+    pub fn handle(&mut self, event: PanelEvent<(Vec<f64>, LineChart)>) {
+        let Some(result) = self.running.update(event) else {
+            return;
+        };
+        self.status = match result {
+            Ok((series, chart)) => {
+                let status = match series.last() {
+                    Some(last) => format!("{} values, ending at {:.4}", series.len(), last),
+                    None => String::new(),
+                };
+                self.series = Some((series, chart));
+                status
+            }
+            Err(e) => e,
+        };
+    }
+
+    pub fn export(&mut self) {
+        let Some((series, _)) = &self.series else {
+            self.status = "Nothing to export yet".to_string();
+            return;
+        };
+        self.status = match fs::write(&self.filename, RandomWalk::to_csv(series)) {
+            Ok(()) => format!("Saved to {}", self.filename),
+            Err(e) => format!("Export error: {}", e),
+        };
+    }
+}
+
+impl RandomGeneratorApp {
+    pub(crate) fn walk_view(&self) -> Element<'_, Message> {
+        let panel = &self.walk;
+        let dark_mode = self.settings.dark_mode;
+        let high_contrast = self.settings.high_contrast;
+        let label = |content: &'static str| text(self.tr(content)).size(14).width(Length::Fixed(80.0));
+        let action = |content: &'static str, message: Option<Message>| {
+            button(text(self.tr(content)).size(14))
+                .on_press_maybe(message)
+                .padding(self.button_padding())
+                .style(move |_theme: &Theme, status| get_link_button_style(dark_mode, high_contrast, status))
+        };
+        let number_input = |value: &str, on_input: fn(String) -> Message| {
+            text_input("", value)
+                .on_input(on_input)
+                .on_submit(Message::RunWalk)
+                .width(Length::Fixed(80.0))
+                .size(14)
+                .style(move |_theme: &Theme, status| get_text_input_style(dark_mode, high_contrast, status))
+        };
+        let options = |first: (&'static str, &str, fn(String) -> Message), second: (&'static str, &str, fn(String) -> Message)| {
+            row![label(first.0), number_input(first.1, first.2), label(second.0), number_input(second.1, second.2)]
+                .spacing(6)
+                .align_y(alignment::Vertical::Center)
+        };
+
+        let chart = panel.series.as_ref().map(|(_, chart)| canvas(chart).width(Length::Fill).height(Length::Fixed(220.0)));
+
+        let content = column![
+            text(self.tr("Random walk"))
+                .size(20)
+                .color(if dark_mode { Color::from_rgb(0.9, 0.9, 0.9) } else { Color::BLACK }),
+            Space::with_height(Length::Fixed(10.0)),
+            options(("Length", &panel.length, Message::WalkLengthChanged), ("Start", &panel.start, Message::WalkStartChanged)),
+            options(("Drift", &panel.drift, Message::WalkDriftChanged), ("Phi", &panel.phi, Message::WalkPhiChanged)),
+            row![
+                label("Steps"),
+                pick_list(&StepDistribution::ALL[..], Some(panel.step), Message::WalkStepChanged)
+                    .text_size(14)
+                    .width(Length::Fixed(80.0))
+                    .style(move |_theme: &Theme, _status| get_pick_list_style(dark_mode, high_contrast)),
+                label("Step size"),
+                number_input(&panel.scale, Message::WalkScaleChanged),
+                Space::with_width(Length::Fill),
+                action("Generate", (!panel.is_running()).then_some(Message::RunWalk)),
+            ]
+                .spacing(6)
+                .align_y(alignment::Vertical::Center),
+            text(self.tr("Each value is drift + phi × the last value + a random step. Phi 1 is a random walk; below 1 the series keeps returning to its mean"))
+                .size(11)
+                .color(accessibility::dim_text_color(dark_mode, high_contrast)),
+        ]
+            .push_maybe(self.progress_row(&panel.running, Message::CancelWalk))
+            .push_maybe(chart)
+            .push(
+                row![
+                    label("File"),
+                    text_input("", &panel.filename)
+                        .on_input(Message::WalkFilenameChanged)
+                        .on_submit(Message::ExportWalk)
+                        .width(Length::Fill)
+                        .size(14)
+                        .style(move |_theme: &Theme, status| get_text_input_style(dark_mode, high_contrast, status)),
+                    action("Export CSV", Some(Message::ExportWalk)),
+                ]
+                    .spacing(6)
+                    .align_y(alignment::Vertical::Center),
+            )
+            .push(
+                row![
+                    text(&panel.status).size(13).color(accessibility::dim_text_color(dark_mode, high_contrast)),
+                    Space::with_width(Length::Fill),
+                    action("Close", Some(Message::CloseWalk)),
+                ]
+                    .align_y(alignment::Vertical::Center),
+            )
+            .spacing(6)
+            .padding(24);
+
+        container(content)
+            .width(Length::Fixed(480.0))
+            .style(move |_theme: &Theme| {
+                iced::widget::container::Style {
+                    background: Some(Background::Color(if dark_mode { Color::from_rgb(0.2, 0.2, 0.25) } else { Color::WHITE })),
+                    border: Border {
+                        color: if dark_mode { Color::from_rgb(0.4, 0.4, 0.4) } else { Color::from_rgb(0.8, 0.8, 0.8) },
+                        width: 1.0,
+                        radius: 16.0.into(),
+                    },
+                    shadow: Shadow {
+                        color: Color::from_rgba(0.0, 0.0, 0.0, 0.3),
+                        offset: Vector::new(0.0, 4.0),
+                        blur_radius: 20.0,
+                    },
+                    ..Default::default()
+                }
+            })
+            .into()
+    }
+}
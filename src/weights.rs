@@ -0,0 +1,215 @@
+//! Panel for random probability vectors.
+//!
+//! Each vector has N non-negative values adding up to 1, drawn from a
+//! Dirichlet distribution: handy as random weights or as test probability
+//! distributions. One concentration applies to every component, or a list
+//! gives each its own; the vectors are shown with their means and saved as
+//! text or CSV. The sampling is `Dirichlet` in the core.
+
+use iced::widget::{button, column, container, row, scrollable, text, text_input, Space};
+use iced::{alignment, Background, Border, Color, Element, Font, Length, Shadow, Theme, Vector};
+use random_generator_core::{Dirichlet, DirichletSample, ExportFormat};
+
+use crate::export_panel::{settings_line, ExportFile, ExportPanel, PanelSeed};
+use crate::{accessibility, get_link_button_style, get_text_input_style, Message, RandomGeneratorApp};
+
+/// Formats the vectors can be saved in
+const FILE_FORMATS: [ExportFormat; 2] = [ExportFormat::Text, ExportFormat::Csv];
+
+#[derive(Debug)]
+pub struct WeightsPanel {
+    /// May be empty when the concentration lists one value per component
+    components: String,
+    concentration: String,
+    count: String,
+    seed: PanelSeed,
+    file: ExportFile,
+    sample: Option<DirichletSample>,
+    status: String,
+}
+
+impl Default for WeightsPanel {
+    fn default() -> Self {
+        Self {
+            components: "3".to_string(),
+            concentration: "1".to_string(),
+            count: "10".to_string(),
+            seed: PanelSeed::default(),
+            file: ExportFile::new("weights.txt", ExportFormat::Text),
+            sample: None,
+            status: String::new(),
+        }
+    }
+}
+
+impl WeightsPanel {
+    pub fn set_components(&mut self, components: String) {
+        self.components = components;
+    }
+
+    pub fn set_concentration(&mut self, concentration: String) {
+        self.concentration = concentration;
+    }
+
+    pub fn set_count(&mut self, count: String) {
+        self.count = count;
+    }
+
+    pub fn file_mut(&mut self) -> &mut ExportFile {
+        &mut self.file
+    }
+
+    pub fn seed_mut(&mut self) -> &mut PanelSeed {
+        &mut self.seed
+    }
+
+    /// How the last draw or export went
+    pub fn status(&self) -> &str {
+        &self.status
+    }
+
+    /// Settings of the draw, for the history
+    pub fn settings(&self) -> String {
+        settings_line(&[
+            ("components", self.components.clone()),
+            ("concentration", self.concentration.clone()),
+            ("count", self.count.clone()),
+        ])
+    }
+
+    pub fn run(&mut self) {
+        self.sample = None;
+        let seed = match self.seed.next() {
+            Ok(seed) => seed,
+            Err(message) => {
+                self.status = message;
+                return;
+            }
+        };
+        let components = match self.components.trim() {
+            "" => None,
+            components => match components.parse() {
+                Ok(components) => Some(components),
+                Err(_) => {
+                    self.status = "Enter a whole number of components".to_string();
+                    return;
+                }
+            },
+        };
+        let Ok(count) = self.count.trim().parse() else {
+            self.status = "Enter a whole number of vectors".to_string();
+            return;
+        };
+        let sample = Dirichlet::parse(components, &self.concentration)
+            .and_then(|dirichlet| dirichlet.sample(count, seed));
+        self.status = match sample {
+            Ok(sample) => {
+                self.seed.keep(seed);
+                let status = format!("{} vectors of {} weights", sample.vectors.len(), sample.dirichlet.concentration().len());
+                self.sample = Some(sample);
+                status
+            }
+            Err(e) => e.to_string(),
+        };
+    }
+
+    pub fn export(&mut self) {
+        let csv = self.file.format() == ExportFormat::Csv;
+        self.status = self.file.save(self.sample.as_ref().map(|sample| if csv { sample.to_csv() } else { sample.to_text() }));
+    }
+}
+
+impl RandomGeneratorApp {
+    pub(crate) fn weights_view(&self) -> Element<'_, Message> {
+        let panel = &self.weights;
+        let dark_mode = self.settings.dark_mode;
+        let high_contrast = self.settings.high_contrast;
+        let label = |content: &'static str| text(self.tr(content)).size(14).width(Length::Fixed(100.0));
+        let action = |content: &'static str, message: Message| {
+            button(text(self.tr(content)).size(14))
+                .on_press(message)
+                .padding(self.button_padding())
+                .style(move |_theme: &Theme, status| get_link_button_style(dark_mode, high_contrast, status))
+        };
+        let input = |value: &str, on_input: fn(String) -> Message, width: Length| {
+            text_input("", value)
+                .on_input(on_input)
+                .on_submit(Message::RunWeights)
+                .width(width)
+                .size(14)
+                .style(move |_theme: &Theme, status| get_text_input_style(dark_mode, high_contrast, status))
+        };
+
+        let preview = panel.sample.as_ref().map(|sample| {
+            let lines = sample.to_text().lines().map(|line| text(line.to_string()).size(12).font(Font::MONOSPACE).into()).collect::<Vec<_>>();
+            container(
+                scrollable(column(lines))
+                    .direction(scrollable::Direction::Both {
+                        vertical: scrollable::Scrollbar::default(),
+                        horizontal: scrollable::Scrollbar::default(),
+                    })
+                    .height(Length::Fixed(200.0)),
+            )
+                .padding(6)
+                .width(Length::Fill)
+        });
+
+        let content = column![
+            text(self.tr("Random weights"))
+                .size(20)
+                .color(if dark_mode { Color::from_rgb(0.9, 0.9, 0.9) } else { Color::BLACK }),
+            Space::with_height(Length::Fixed(10.0)),
+            row![
+                label("Components"),
+                input(&panel.components, Message::WeightsComponentsChanged, Length::Fixed(80.0)),
+                label("Vectors"),
+                input(&panel.count, Message::WeightsCountChanged, Length::Fixed(80.0)),
+            ]
+                .spacing(6)
+                .align_y(alignment::Vertical::Center),
+            row![
+                label("Concentration"),
+                input(&panel.concentration, Message::WeightsConcentrationChanged, Length::Fill),
+                action("Generate", Message::RunWeights),
+            ]
+                .spacing(6)
+                .align_y(alignment::Vertical::Center),
+            text(self.tr("One number for every component, or one per component separated by commas. 1 makes every vector equally likely; larger values keep the weights close to their proportions, smaller ones pile most weight on a few"))
+                .size(11)
+                .color(accessibility::dim_text_color(dark_mode, high_contrast)),
+        ]
+            .push_maybe(preview)
+            .push(self.seed_row(ExportPanel::Weights, &panel.seed))
+            .push(self.export_row(ExportPanel::Weights, &panel.file, &FILE_FORMATS, "Export"))
+            .push(
+                row![
+                    text(&panel.status).size(13).color(accessibility::dim_text_color(dark_mode, high_contrast)),
+                    Space::with_width(Length::Fill),
+                    action("Close", Message::CloseWeights),
+                ]
+                    .align_y(alignment::Vertical::Center),
+            )
+            .spacing(6)
+            .padding(24);
+
+        container(content)
+            .width(Length::Fixed(480.0))
+            .style(move |_theme: &Theme| {
+                iced::widget::container::Style {
+                    background: Some(Background::Color(if dark_mode { Color::from_rgb(0.2, 0.2, 0.25) } else { Color::WHITE })),
+                    border: Border {
+                        color: if dark_mode { Color::from_rgb(0.4, 0.4, 0.4) } else { Color::from_rgb(0.8, 0.8, 0.8) },
+                        width: 1.0,
+                        radius: 16.0.into(),
+                    },
+                    shadow: Shadow {
+                        color: Color::from_rgba(0.0, 0.0, 0.0, 0.3),
+                        offset: Vector::new(0.0, 4.0),
+                        blur_radius: 20.0,
+                    },
+                    ..Default::default()
+                }
+            })
+            .into()
+    }
+}
@@ -0,0 +1,206 @@
+use iced::mouse;
+use iced::widget::canvas::{self, path::Arc, Frame, Geometry, Path, Stroke, Text};
+use iced::{alignment, Color, Point, Radians, Rectangle, Renderer, Theme};
+use rand::Rng;
+use std::f32::consts::{FRAC_PI_2, TAU};
+use std::time::{Duration, Instant};
+
+/// Spinning wheel shown in the presentation view for custom lists.
+///
+/// Each distinct entry is a segment sized by its chance of being drawn. The
+/// result is already drawn when the wheel starts; the spin only eases out so
+/// that the pointer stops somewhere inside the drawn entry's segment.
+#[derive(Debug, Clone)]
+pub struct Wheel {
+    segments: Vec<Segment>,
+    target: usize,
+    /// Turns made by the time the wheel stops
+    turns: f32,
+    started: Instant,
+    now: Instant,
+    /// Segment under the pointer at the last tick
+    current: usize,
+}
+
+#[derive(Debug, Clone)]
+struct Segment {
+    label: String,
+    /// Start and size as fractions of a full turn, clockwise from the pointer
+    start: f32,
+    sweep: f32,
+}
+
+impl Wheel {
+    /// Lists with more entries than this can't be read on a wheel and are shown as plain results
+    pub const MAX_SEGMENTS: usize = 100;
+
+    const DURATION: Duration = Duration::from_millis(4500);
+    /// Full turns before the wheel starts to settle
+    const TURNS: f32 = 5.0;
+    /// Segments narrower than this fraction of a turn have no label
+    const MIN_LABELLED_SWEEP: f32 = 1.0 / 60.0;
+
+    const COLORS: [Color; 6] = [
+        Color::from_rgb(0.95, 0.3, 0.3),
+        Color::from_rgb(0.2, 0.6, 0.9),
+        Color::from_rgb(0.4, 0.8, 0.4),
+        Color::from_rgb(0.98, 0.8, 0.2),
+        Color::from_rgb(0.7, 0.4, 0.9),
+        Color::from_rgb(1.0, 0.55, 0.2),
+    ];
+
+    /// Spin to `target`, an index into `entries` of labels and weights
+    pub fn new(now: Instant, entries: Vec<(String, usize)>, target: usize) -> Self {
+        let total = entries.iter().map(|(_, weight)| *weight).sum::<usize>().max(1) as f32;
+        let mut start = 0.0;
+        let segments: Vec<Segment> = entries
+            .into_iter()
+            .map(|(label, weight)| {
+                let sweep = weight as f32 / total;
+                let segment = Segment { label, start, sweep };
+                start += sweep;
+                segment
+            })
+            .collect();
+
+        // Stop away from the edges so there's no doubt which segment won
+        let landing = segments[target].start + segments[target].sweep * rand::thread_rng().gen_range(0.15..0.85);
+        let turns = Self::TURNS + 1.0 - landing;
+        Self { segments, target, turns, started: now, now, current: 0 }
+    }
+
+    /// Advance the animation clock to the current frame.
+    ///
+    /// Returns `true` when the pointer passed into another segment.
+    pub fn tick(&mut self, now: Instant) -> bool {
+        self.now = now;
+        let current = self.under_pointer();
+        let moved = current != self.current;
+        self.current = current;
+        moved
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.elapsed() >= Self::DURATION
+    }
+
+    /// Label of the segment under the pointer
+    pub fn current_label(&self) -> &str {
+        &self.segments[self.under_pointer()].label
+    }
+
+    /// Turns made so far, fast at first and slowing down to a stop
+    fn rotation(&self) -> f32 {
+        let progress = (self.elapsed().as_secs_f32() / Self::DURATION.as_secs_f32()).min(1.0);
+        self.turns * (1.0 - (1.0 - progress).powi(3))
+    }
+
+    fn under_pointer(&self) -> usize {
+        if self.is_finished() {
+            return self.target;
+        }
+        self.segment_at(self.rotation())
+    }
+
+    /// Segment under the pointer after turning the wheel by `rotation` turns
+    fn segment_at(&self, rotation: f32) -> usize {
+        let position = (-rotation).rem_euclid(1.0);
+        self.segments
+            .iter()
+            .rposition(|segment| segment.start <= position)
+            .unwrap_or(0)
+    }
+
+    fn elapsed(&self) -> Duration {
+        self.now.saturating_duration_since(self.started)
+    }
+}
+
+impl<Message> canvas::Program<Message> for Wheel {
+    type State = ();
+
+    fn draw(
+        &self,
+        _state: &Self::State,
+        renderer: &Renderer,
+        theme: &Theme,
+        bounds: Rectangle,
+        _cursor: mouse::Cursor,
+    ) -> Vec<Geometry> {
+        let mut frame = Frame::new(renderer, bounds.size());
+        let center = frame.center();
+        let radius = bounds.width.min(bounds.height) / 2.0 - 24.0;
+        if radius <= 0.0 {
+            return vec![frame.into_geometry()];
+        }
+        let rotation = self.rotation();
+        let finished = self.is_finished();
+        // Fractions of a turn clockwise from the top, to canvas angles clockwise from the right
+        let angle = |turns: f32| (turns + rotation) * TAU - FRAC_PI_2;
+
+        for (i, segment) in self.segments.iter().enumerate() {
+            let color = Self::COLORS[i % Self::COLORS.len()];
+            // Once stopped, everything but the drawn entry fades back
+            let color = if finished && i != self.target { Color { a: 0.3, ..color } } else { color };
+            let wedge = Path::new(|builder| {
+                builder.move_to(center);
+                builder.arc(Arc {
+                    center,
+                    radius,
+                    start_angle: Radians(angle(segment.start)),
+                    end_angle: Radians(angle(segment.start + segment.sweep)),
+                });
+                builder.close();
+            });
+            frame.fill(&wedge, color);
+            frame.stroke(&wedge, Stroke::default().with_color(Color::WHITE).with_width(1.5));
+
+            if segment.sweep >= Self::MIN_LABELLED_SWEEP {
+                let middle = angle(segment.start + segment.sweep / 2.0);
+                frame.fill_text(Text {
+                    content: segment.label.clone(),
+                    position: Point::new(center.x + radius * 0.68 * middle.cos(), center.y + radius * 0.68 * middle.sin()),
+                    color: Color { a: if finished && i != self.target { 0.5 } else { 1.0 }, ..Color::BLACK },
+                    size: (radius / 9.0).clamp(12.0, 40.0).into(),
+                    horizontal_alignment: alignment::Horizontal::Center,
+                    vertical_alignment: alignment::Vertical::Center,
+                    ..Text::default()
+                });
+            }
+        }
+
+        // Pointer at the top, over the rim
+        let pointer = Path::new(|builder| {
+            builder.move_to(Point::new(center.x - 14.0, center.y - radius - 16.0));
+            builder.line_to(Point::new(center.x + 14.0, center.y - radius - 16.0));
+            builder.line_to(Point::new(center.x, center.y - radius + 12.0));
+            builder.close();
+        });
+        frame.fill(&pointer, theme.palette().text);
+        frame.fill(&Path::circle(center, radius * 0.08), theme.palette().text);
+
+        vec![frame.into_geometry()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stops_on_target() {
+        let entries = vec![("a".to_string(), 1), ("b".to_string(), 6), ("c".to_string(), 2), ("d".to_string(), 1)];
+        for target in 0..entries.len() {
+            for _ in 0..20 {
+                let wheel = Wheel::new(Instant::now(), entries.clone(), target);
+                assert_eq!(wheel.segment_at(wheel.turns), target);
+            }
+        }
+
+        let mut wheel = Wheel::new(Instant::now(), entries, 3);
+        assert!(!wheel.tick(wheel.started));
+        assert!(wheel.tick(wheel.started + Wheel::DURATION));
+        assert!(wheel.is_finished());
+        assert_eq!(wheel.current_label(), "d");
+    }
+}
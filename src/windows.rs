@@ -0,0 +1,144 @@
+use std::collections::BTreeMap;
+
+use iced::widget::Space;
+use iced::{window, Element, Subscription, Task, Theme};
+
+#[cfg(feature = "tray")]
+use crate::tray;
+use crate::deep_link::DeepLink;
+use crate::{Message, RandomGeneratorApp};
+
+/// All open generator windows; each one has its own config and results
+pub struct Windows {
+    windows: BTreeMap<window::Id, RandomGeneratorApp>,
+    /// Window that had focus last, quick actions from the tray go there
+    focused: Option<window::Id>,
+    #[cfg(feature = "tray")]
+    _tray: tray::TrayHandle,
+}
+
+#[derive(Debug, Clone)]
+pub enum WindowsMessage {
+    Window(window::Id, Message),
+    Event(window::Id, window::Event),
+    #[cfg(feature = "tray")]
+    Tray(tray::TrayAction),
+}
+
+impl Windows {
+    /// Open the first window, set up from `link` when started by a `randomtool://` link
+    pub fn new(link: Option<Result<DeepLink, String>>) -> (Self, Task<WindowsMessage>) {
+        let mut windows = Self {
+            windows: BTreeMap::new(),
+            focused: None,
+            #[cfg(feature = "tray")]
+            _tray: tray::spawn(),
+        };
+        let open = windows.open_window();
+        if let (Some(app), Some(link)) = (windows.windows.values_mut().next(), link) {
+            app.open_link(link);
+        }
+        (windows, open)
+    }
+
+    fn open_window(&mut self) -> Task<WindowsMessage> {
+        let (id, open) = window::open(window_settings());
+        self.windows.insert(id, RandomGeneratorApp::for_window(id));
+        open.discard()
+    }
+
+    pub fn title(&self, id: window::Id) -> String {
+        let index = self.windows.keys().position(|&window| window == id).unwrap_or(0);
+        match self.windows.get(&id) {
+            Some(app) if index > 0 => format!("{} ({})", app.title(), index + 1),
+            Some(app) => app.title(),
+            None => String::new(),
+        }
+    }
+
+    pub fn update(&mut self, message: WindowsMessage) -> Task<WindowsMessage> {
+        match message {
+            WindowsMessage::Window(_, Message::NewWindow) => self.open_window(),
+            WindowsMessage::Window(id, message) => match self.windows.get_mut(&id) {
+                Some(app) => app.update(message).map(move |message| WindowsMessage::Window(id, message)),
+                None => Task::none(),
+            },
+            WindowsMessage::Event(id, window::Event::Focused) => {
+                self.focused = Some(id);
+                Task::none()
+            }
+            WindowsMessage::Event(id, window::Event::Closed) => {
+                self.windows.remove(&id);
+                if self.focused == Some(id) {
+                    self.focused = None;
+                }
+                // Closing the last window quits, like a single-window app
+                if self.windows.is_empty() {
+                    iced::exit()
+                } else {
+                    Task::none()
+                }
+            }
+            WindowsMessage::Event(..) => Task::none(),
+            #[cfg(feature = "tray")]
+            WindowsMessage::Tray(action) => {
+                let Some(id) = self.focused.or_else(|| self.windows.keys().next_back().copied()) else {
+                    return Task::none();
+                };
+                match action {
+                    tray::TrayAction::GenerateAgain => self.update(WindowsMessage::Window(id, Message::Generate)),
+                    tray::TrayAction::CopyResults => self.update(WindowsMessage::Window(id, Message::CopyResults)),
+                    tray::TrayAction::ShowWindow => {
+                        Task::batch([window::minimize(id, false), window::gain_focus(id)])
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn view(&self, id: window::Id) -> Element<'_, WindowsMessage> {
+        match self.windows.get(&id) {
+            Some(app) => app.view().map(move |message| WindowsMessage::Window(id, message)),
+            None => Space::new(0, 0).into(),
+        }
+    }
+
+    pub fn theme(&self, id: window::Id) -> Theme {
+        self.windows.get(&id).map(RandomGeneratorApp::theme).unwrap_or_default()
+    }
+
+    pub fn subscription(&self) -> Subscription<WindowsMessage> {
+        let per_window = self.windows.iter().map(|(&id, app)| {
+            app.subscription()
+                .with(id)
+                .map(|(id, message)| WindowsMessage::Window(id, message))
+        });
+        let events = iced::event::listen_with(|event, _status, id| match event {
+            iced::Event::Window(event @ (window::Event::Focused | window::Event::Closed)) => {
+                Some(WindowsMessage::Event(id, event))
+            }
+            _ => None,
+        });
+        #[cfg(feature = "tray")]
+        let events = Subscription::batch([events, Subscription::run(tray::events).map(WindowsMessage::Tray)]);
+
+        Subscription::batch(per_window.chain(std::iter::once(events)))
+    }
+}
+
+fn window_settings() -> window::Settings {
+    window::Settings {
+        size: iced::Size::new(440.0, 640.0),
+        position: Default::default(),
+        min_size: Some(iced::Size::new(360.0, 520.0)),
+        max_size: None,
+        visible: true,
+        resizable: true,
+        decorations: true,
+        transparent: false,
+        level: window::Level::Normal,
+        icon: None,
+        platform_specific: Default::default(),
+        exit_on_close_request: true,
+    }
+}
@@ -0,0 +1,281 @@
+use iced::futures::{executor, SinkExt, Stream};
+use std::fs::{self, File};
+use std::io::{BufReader, BufWriter};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+
+use random_generator_core::{Cooldown, ExclusionSet, GeneratorConfig, LineSample, MonteCarlo, MonteCarloReport, RandomBytes, SampledLines, Seed, ShuffleBag};
+
+use crate::{daemon, plugins};
+use crate::random_org::{self, DrawSource};
+use crate::source_params::SourceParams;
+
+/// Rounds per strategy in the diagnostic benchmark
+const BENCHMARK_ROUNDS: usize = 5;
+
+/// Updates sent from a background generation back to the UI
+#[derive(Debug, Clone)]
+pub enum GenerationEvent {
+    /// Fraction of the work done so far (0.0 - 1.0)
+    Progress(f32),
+    /// The draw is boxed to keep the event small while it passes through the message queue
+    Finished(Result<Box<Draw>, String>),
+}
+
+/// Updates sent while a Monte Carlo estimate is running
+#[derive(Debug, Clone)]
+pub enum MonteCarloEvent {
+    /// Samples drawn so far and the estimate after them
+    Progress(usize, f64),
+    /// The report, or why it stopped
+    Finished(Result<MonteCarloReport, String>),
+}
+
+/// Updates sent while a panel draws on a worker thread
+#[derive(Debug, Clone)]
+pub enum PanelEvent<T> {
+    /// Fraction of the draw done so far (0.0 - 1.0)
+    Progress(f32),
+    /// The results, or why it stopped
+    Finished(Result<T, String>),
+}
+
+/// A panel's draw running on a worker thread, with its progress and a way to cancel it
+#[derive(Debug, Default)]
+pub struct PanelRun {
+    /// Fraction done while a draw is running
+    progress: Option<f32>,
+    cancel: Arc<AtomicBool>,
+}
+
+impl PanelRun {
+    pub fn is_running(&self) -> bool {
+        self.progress.is_some()
+    }
+
+    /// Fraction done while a draw is running
+    pub fn progress(&self) -> Option<f32> {
+        self.progress
+    }
+
+    /// Run `draw` on a worker thread; it gets the cancel flag and a `(done, total)`
+    /// progress callback, as the core's `*_with_progress` functions take them
+    pub fn start<T: Send + 'static>(
+        &mut self,
+        draw: impl FnOnce(&AtomicBool, &mut dyn FnMut(usize, usize)) -> Result<T, String> + Send + 'static,
+    ) -> impl Stream<Item = PanelEvent<T>> {
+        self.progress = Some(0.0);
+        self.cancel = Arc::new(AtomicBool::new(false));
+        let cancel = self.cancel.clone();
+        iced::stream::channel(16, move |output| async move {
+            thread::spawn(move || {
+                let mut progress = output.clone();
+                let mut on_progress = |done: usize, total: usize| {
+                    if total > 0 {
+                        let _ = progress.try_send(PanelEvent::Progress(done as f32 / total as f32));
+                    }
+                };
+                let result = draw(&cancel, &mut on_progress);
+                let mut output = output;
+                let _ = executor::block_on(output.send(PanelEvent::Finished(result)));
+            });
+        })
+    }
+
+    /// Stop the running draw at its next checkpoint
+    pub fn cancel(&self) {
+        self.cancel.store(true, Ordering::Relaxed);
+    }
+
+    /// Follow an event of the running draw; the results, once it has finished
+    pub fn update<T>(&mut self, event: PanelEvent<T>) -> Option<Result<T, String>> {
+        match event {
+            PanelEvent::Progress(fraction) => {
+                if self.is_running() {
+                    self.progress = Some(fraction);
+                }
+                None
+            }
+            PanelEvent::Finished(result) => {
+                self.progress = None;
+                Some(result)
+            }
+        }
+    }
+}
+
+/// Results of a finished generation
+#[derive(Debug, Clone)]
+pub struct Draw {
+    pub numbers: Vec<i64>,
+    /// The config it ran with, seed included, so running it again gives the same numbers
+    pub config: GeneratorConfig,
+    /// Inputs of the mode's source it ran with
+    pub params: SourceParams,
+    /// Whether random.org or the local generator drew the numbers, `None` when random.org wasn't asked
+    pub random_org: Option<DrawSource>,
+    /// What's left in the shuffle bag afterwards, `None` when the draw didn't use one
+    pub bag: Option<ShuffleBag<i64>>,
+    /// Values still cooling down afterwards, `None` when the draw had no cooldown
+    pub cooldown: Option<Cooldown<i64>>,
+    /// Values excluded afterwards, new results included; `None` when the draw excluded nothing
+    pub excluded: Option<ExclusionSet<i64>>,
+}
+
+/// Run a generation on a worker thread so the window stays responsive.
+///
+/// With a random.org API key, range and custom list draws come from
+/// random.org, falling back to the local generator when it can't answer. With
+/// a daemon endpoint the daemon draws instead of this process. Setting
+/// `cancel` makes the worker stop at its next checkpoint.
+pub fn generate(
+    config: GeneratorConfig,
+    params: SourceParams,
+    random_org_key: Option<String>,
+    daemon: Option<String>,
+    cancel: Arc<AtomicBool>,
+) -> impl Stream<Item = GenerationEvent> {
+    iced::stream::channel(16, move |output| async move {
+        thread::spawn(move || {
+            let mut progress = output.clone();
+            let mut on_progress = |done: usize, total: usize| {
+                if total > 0 {
+                    // Dropping an update when the UI is busy is fine
+                    let _ = progress.try_send(GenerationEvent::Progress(done as f32 / total as f32));
+                }
+            };
+
+            let result = match daemon {
+                // As below, the config keeps the state from before the draw and the draw carries the state after it
+                Some(endpoint) => daemon::generate(&endpoint, &config).map(|(numbers, after)| Draw {
+                    numbers,
+                    config: GeneratorConfig { seed: after.seed, ..config },
+                    params,
+                    random_org: random_org_key.map(|_| DrawSource::Local("the daemon drew it".to_string())),
+                    bag: after.shuffle_bag,
+                    cooldown: after.cooldown,
+                    excluded: after.exclude_drawn,
+                }),
+                None => plugins::generator(config.clone(), &params)
+                    .and_then(|mut generator| {
+                        let drawn = random_org_key.map(|key| random_org::install(key, &mut generator));
+                        generator.generate_numbers_with_progress(&cancel, &mut on_progress)?;
+                        let random_org = drawn.map(|drawn| {
+                            let drawn = drawn.lock().unwrap_or_else(|e| e.into_inner()).take();
+                            drawn.unwrap_or_else(|| DrawSource::Local("draws with a shuffle bag or a cooldown are drawn locally".to_string()))
+                        });
+                        // Keep the bag, cooldown and exclusions as they were before the draw, so replaying the config repeats it
+                        let after = generator.get_config().clone();
+                        // random.org's numbers don't come from the seed, so there is none to replay them with
+                        let replayable = !matches!(random_org, Some(DrawSource::RandomOrg(_)));
+                        let config = GeneratorConfig {
+                            seed: generator.last_seed().filter(|_| replayable),
+                            shuffle_bag: config.shuffle_bag,
+                            cooldown: config.cooldown,
+                            exclude_drawn: config.exclude_drawn,
+                            ..after.clone()
+                        };
+                        Ok(Draw {
+                            numbers: std::mem::take(generator.get_numbers_mut()),
+                            config,
+                            params,
+                            random_org,
+                            bag: after.shuffle_bag,
+                            cooldown: after.cooldown,
+                            excluded: after.exclude_drawn,
+                        })
+                    })
+                    .map_err(|e| e.to_string()),
+            };
+
+            let mut output = output;
+            let _ = executor::block_on(output.send(GenerationEvent::Finished(result.map(Box::new))));
+        });
+    })
+}
+
+/// Time each unique-draw strategy for `config` on a worker thread and send back the report
+pub fn benchmark(config: GeneratorConfig, params: SourceParams) -> impl Stream<Item = Result<String, String>> {
+    iced::stream::channel(1, move |mut output| async move {
+        thread::spawn(move || {
+            let result = plugins::generator(config, &params)
+                .and_then(|mut generator| generator.benchmark(BENCHMARK_ROUNDS))
+                .map(|report| report.to_string())
+                .map_err(|e| e.to_string());
+            let _ = executor::block_on(output.send(result));
+        });
+    })
+}
+
+/// Write `bytes` to `path` on a worker thread.
+///
+/// A cancelled or failed write removes the partial file.
+pub fn write_random_file(path: String, bytes: RandomBytes, cancel: Arc<AtomicBool>) -> impl Stream<Item = PanelEvent<String>> {
+    iced::stream::channel(16, move |output| async move {
+        thread::spawn(move || {
+            let mut progress = output.clone();
+            let mut on_progress = |done: u64, total: u64| {
+                if total > 0 {
+                    let _ = progress.try_send(PanelEvent::Progress(done as f32 / total as f32));
+                }
+            };
+            let result = match File::create(&path) {
+                Ok(file) => {
+                    let mut writer = BufWriter::new(file);
+                    let written = bytes.write_with_progress(&mut writer, &cancel, &mut on_progress);
+                    // Close the file first, Windows won't remove an open one
+                    drop(writer);
+                    match written {
+                        Ok(()) => Ok(format!("Wrote {} to {}", bytes.size, path)),
+                        Err(e) => {
+                            let _ = fs::remove_file(&path);
+                            Err(e.to_string())
+                        }
+                    }
+                }
+                Err(e) => Err(format!("{}: {}", path, e)),
+            };
+
+            let mut output = output;
+            let _ = executor::block_on(output.send(PanelEvent::Finished(result)));
+        });
+    })
+}
+
+/// Run a Monte Carlo estimate on a worker thread, streaming the estimate as it converges
+pub fn monte_carlo(demo: MonteCarlo, seed: Seed, cancel: Arc<AtomicBool>) -> impl Stream<Item = MonteCarloEvent> {
+    iced::stream::channel(64, move |output| async move {
+        thread::spawn(move || {
+            let mut progress = output.clone();
+            let mut on_progress = |samples: usize, estimate: f64| {
+                // A missed point only leaves a gap in the live chart; the report has all of them
+                let _ = progress.try_send(MonteCarloEvent::Progress(samples, estimate));
+            };
+            let result = demo.run_with_progress(seed, &cancel, &mut on_progress).map_err(|e| e.to_string());
+            let mut output = output;
+            let _ = executor::block_on(output.send(MonteCarloEvent::Finished(result)));
+        });
+    })
+}
+
+/// Sample lines from the file at `path` on a worker thread, reading it once from start to end
+pub fn sample_lines(path: String, sample: LineSample, cancel: Arc<AtomicBool>) -> impl Stream<Item = PanelEvent<SampledLines>> {
+    iced::stream::channel(16, move |output| async move {
+        thread::spawn(move || {
+            let mut progress = output.clone();
+            let result = File::open(&path).and_then(|file| Ok((file.metadata()?.len(), file))).map_err(|e| format!("{}: {}", path, e));
+            let result = result.and_then(|(size, file)| {
+                let mut on_progress = |read: u64| {
+                    if size > 0 {
+                        let _ = progress.try_send(PanelEvent::Progress(read as f32 / size as f32));
+                    }
+                };
+                sample.sample_with_progress(&mut BufReader::new(file), &cancel, &mut on_progress).map_err(|e| e.to_string())
+            });
+
+            let mut output = output;
+            let _ = executor::block_on(output.send(PanelEvent::Finished(result)));
+        });
+    })
+}